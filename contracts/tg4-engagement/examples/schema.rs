@@ -5,8 +5,10 @@ use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, s
 
 pub use tg4::{AdminResponse, MemberListResponse, MemberResponse, TotalPointsResponse};
 pub use tg4_engagement::msg::{
-    DelegatedResponse, ExecuteMsg, InstantiateMsg, PreauthResponse, QueryMsg, RewardsResponse,
-    SudoMsg,
+    DelegatedResponse, DelegationsResponse, DistributionSnapshotsResponse,
+    DistributionThrottleResponse, ExecuteMsg, InstantiateMsg, PendingSuggestionsResponse,
+    PendingTransfersResponse, PointsOracleResponse, PreauthResponse, QueryMsg, RewardsResponse,
+    SudoMsg, SuggestPointsResponse, TransferPolicyResponse, VoucherSignerResponse,
 };
 
 fn main() {
@@ -26,4 +28,13 @@ fn main() {
     export_schema(&schema_for!(SudoMsg), &out_dir);
     export_schema(&schema_for!(RewardsResponse), &out_dir);
     export_schema(&schema_for!(DelegatedResponse), &out_dir);
+    export_schema(&schema_for!(DelegationsResponse), &out_dir);
+    export_schema(&schema_for!(VoucherSignerResponse), &out_dir);
+    export_schema(&schema_for!(DistributionThrottleResponse), &out_dir);
+    export_schema(&schema_for!(TransferPolicyResponse), &out_dir);
+    export_schema(&schema_for!(PendingTransfersResponse), &out_dir);
+    export_schema(&schema_for!(DistributionSnapshotsResponse), &out_dir);
+    export_schema(&schema_for!(PointsOracleResponse), &out_dir);
+    export_schema(&schema_for!(PendingSuggestionsResponse), &out_dir);
+    export_schema(&schema_for!(SuggestPointsResponse), &out_dir);
 }
@@ -1,30 +1,49 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, to_binary, Addr, BankMsg, Binary, Coin, CustomQuery, Decimal, Deps, DepsMut, Env, Event,
-    MessageInfo, Order, StdResult, Timestamp, Uint128,
+    coin, to_binary, Addr, BankMsg, Binary, Coin, CustomQuery, Decimal, Deps, DepsMut, Empty, Env,
+    Event, MessageInfo, Order, OverflowError, OverflowOperation, StdError, StdResult, Storage,
+    Timestamp, Uint128,
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use cw_utils::{ensure_from_older_version, maybe_addr};
+use std::cmp::max;
+use std::collections::BTreeMap;
 use tg4::{
     HooksResponse, Member, MemberChangedHookMsg, MemberDiff, MemberInfo, MemberListResponse,
     MemberResponse, TotalPointsResponse,
 };
 
 use crate::error::ContractError;
+use crate::merkle;
 use crate::migration::generate_pending_member_updates;
+use crate::migration::index_delegations;
 use crate::migration::migrate_config;
 use crate::msg::{
-    DelegatedResponse, ExecuteMsg, HalflifeInfo, HalflifeResponse, InstantiateMsg, MigrateMsg,
-    PreauthResponse, QueryMsg, RewardsResponse, SudoMsg,
+    AddPointsBatchResponse, AdjustmentDiff, DelegatedResponse, DelegationInfo, DelegationsResponse,
+    DistributionSnapshotInfo, DistributionSnapshotsResponse, DistributionThrottleResponse,
+    ExecuteMsg, HalflifeInfo, HalflifeResponse, InstantiateMsg, LatestMerkleStageResponse,
+    MerkleRootResponse, MigrateMsg, PendingSuggestionInfo, PendingSuggestionsResponse,
+    PendingTransferInfo, PendingTransfersResponse, PointsOracleResponse, PreauthResponse, QueryMsg,
+    RejectedPoints, RewardsResponse, SimulateUpdateMembersResponse, SudoMsg, SuggestPointsResponse,
+    TransferPolicyResponse, Voucher, VoucherSignDoc, VoucherSignerResponse,
+    WithdrawalStreamResponse,
 };
 use crate::state::{
-    Distribution, Halflife, WithdrawAdjustment, DISTRIBUTION, HALFLIFE, PREAUTH_SLASHING,
-    SHARES_SHIFT, SLASHERS, WITHDRAW_ADJUSTMENT,
+    Distribution, DistributionSnapshot, DistributionThrottle, Halflife, OracleBudget,
+    OracleBudgetUsage, PendingSuggestion, PendingTransfer, PointsOracle, RewardsStream,
+    TransferPolicy, VoucherKeyType, VoucherSigner, WithdrawAdjustment, DELEGATIONS, DISTRIBUTION,
+    DISTRIBUTION_SEQ, DISTRIBUTION_SNAPSHOTS, DISTRIBUTION_THROTTLE, HALFLIFE, LATEST_MERKLE_STAGE,
+    MAX_REWARDS_STREAMS, MERKLE_CLAIMED, MERKLE_ROOTS, ORACLE_BUDGET_USAGE, PENDING_SUGGESTIONS,
+    PENDING_SUGGESTION_ID, PENDING_TRANSFERS, PENDING_TRANSFER_ID, POINTS_ORACLE, PREAUTH_SLASHING,
+    REWARDS_STREAMS, REWARDS_STREAM_ID, SHARES_SHIFT, SLASHERS, TRANSFER_POLICY, VOUCHER_NONCES,
+    VOUCHER_SIGNER, WITHDRAWAL_STREAM_PERIOD, WITHDRAW_ADJUSTMENT,
 };
-use tg_bindings::{request_privileges, Privilege, PrivilegeChangeMsg, TgradeMsg, TgradeQuery};
-use tg_utils::{members, validate_portion, Duration, ADMIN, HOOKS, PREAUTH_HOOKS, TOTAL};
+use tg_bindings::{
+    events, request_privileges, Privilege, PrivilegeChangeMsg, TgradeMsg, TgradeQuery,
+};
+use tg_utils::{members, Duration, ADMIN, HOOKS, PREAUTH_HOOKS, TOTAL};
 
 pub type Response = cosmwasm_std::Response<TgradeMsg>;
 pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
@@ -37,14 +56,14 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut<TgradeQuery>,
+    mut deps: DepsMut<TgradeQuery>,
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     create(
-        deps,
+        deps.branch(),
         msg.admin,
         msg.members,
         msg.preauths_hooks,
@@ -55,9 +74,40 @@ pub fn instantiate(
         msg.denom,
     )?;
 
+    let throttle = DistributionThrottle {
+        min_amount: msg.min_distribution,
+        min_interval: msg.distribution_interval.filter(|d| d.seconds() > 0),
+        last_distribution: None,
+    };
+    DISTRIBUTION_THROTTLE.save(deps.storage, &throttle)?;
+
+    if let Some(period) = msg.withdrawal_stream_period.filter(|d| d.seconds() > 0) {
+        WITHDRAWAL_STREAM_PERIOD.save(deps.storage, &period)?;
+    }
+    validate_withdrawal_stream(deps.storage)?;
+
     Ok(Response::default())
 }
 
+/// Ensures distributions are rare enough for the number of streams unlocking at the same time
+/// to stay within `MAX_REWARDS_STREAMS`.
+pub(crate) fn validate_withdrawal_stream(storage: &dyn Storage) -> Result<(), ContractError> {
+    let period = match WITHDRAWAL_STREAM_PERIOD.may_load(storage)? {
+        Some(period) => period,
+        None => return Ok(()),
+    };
+    let interval = DISTRIBUTION_THROTTLE
+        .may_load(storage)?
+        .and_then(|throttle| throttle.min_interval)
+        .map_or(0, |interval| interval.seconds());
+    if interval.saturating_mul(MAX_REWARDS_STREAMS) < period.seconds() {
+        return Err(ContractError::StreamingWithoutInterval {
+            max_streams: MAX_REWARDS_STREAMS,
+        });
+    }
+    Ok(())
+}
+
 // create is the instantiation logic with set_contract_version removed so it can more
 // easily be imported in other contracts
 #[allow(clippy::too_many_arguments)]
@@ -111,10 +161,11 @@ pub fn create<Q: CustomQuery>(
             shares_correction: 0i128.into(),
             withdrawn_rewards: Uint128::zero(),
             delegated: member_addr.clone(),
+            delegation_expires: None,
         };
         WITHDRAW_ADJUSTMENT.save(deps.storage, &member_addr, &adjustment)?;
     }
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, height)?;
 
     SLASHERS.instantiate(deps.storage)?;
 
@@ -140,16 +191,39 @@ pub fn execute(
         )?),
         UpdateMembers { add, remove } => execute_update_members(deps, env, info, add, remove),
         AddPoints { addr, points } => execute_add_points(deps, env, info, addr, points),
+        AddPointsBatch { entries } => execute_add_points_batch(deps, env, info, entries),
         AddHook { addr } => execute_add_hook(deps, info, addr),
         RemoveHook { addr } => execute_remove_hook(deps, info, addr),
         DistributeRewards { sender } => execute_distribute_rewards(deps, env, info, sender),
         WithdrawRewards { owner, receiver } => {
-            execute_withdraw_rewards(deps, info, owner, receiver)
+            execute_withdraw_rewards(deps, env, info, owner, receiver)
         }
-        DelegateWithdrawal { delegated } => execute_delegate_withdrawal(deps, info, delegated),
-        AddSlasher { addr } => execute_add_slasher(deps, info, addr),
+        DelegateWithdrawal { delegated, expires } => {
+            execute_delegate_withdrawal(deps, env, info, delegated, expires)
+        }
+        AddSlasher { addr, max_portion } => execute_add_slasher(deps, info, addr, max_portion),
         RemoveSlasher { addr } => execute_remove_slasher(deps, info, addr),
         Slash { addr, portion } => execute_slash(deps, env, info, addr, portion),
+        SetVoucherSigner { signer } => execute_set_voucher_signer(deps, info, signer),
+        ClaimPoints { voucher, signature } => {
+            execute_claim_points(deps, env, info, voucher, signature)
+        }
+        RegisterMerkleRoot { merkle_root } => execute_register_merkle_root(deps, info, merkle_root),
+        ClaimMerklePoints {
+            stage,
+            index,
+            addr,
+            points,
+            proof,
+        } => execute_claim_merkle_points(deps, env, info, stage, index, addr, points, proof),
+        SetTransferPolicy { policy } => execute_set_transfer_policy(deps, info, policy),
+        TransferPoints { to, points } => execute_transfer_points(deps, env, info, to, points),
+        ApproveTransfer { id } => execute_approve_transfer(deps, env, info, id),
+        RejectTransfer { id } => execute_reject_transfer(deps, info, id),
+        SetPointsOracle { oracle, budget } => execute_set_points_oracle(deps, info, oracle, budget),
+        SuggestPoints { entries } => execute_suggest_points(deps, env, info, entries),
+        ApproveSuggestions { ids } => execute_approve_suggestions(deps, env, info, ids),
+        RejectSuggestions { ids } => execute_reject_suggestions(deps, info, ids),
     }
 }
 
@@ -167,18 +241,181 @@ pub fn execute_add_points<Q: CustomQuery>(
 
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
+    // make the local update
+    let diff = add_points(deps.branch(), env.block.height, addr, points)?;
+    // call all registered hooks
+    res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+        diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+    })?;
+    Ok(res)
+}
+
+pub fn execute_add_points_batch<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    entries: Vec<(String, u64)>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if entries.is_empty() {
+        return Err(ContractError::EmptyPointsBatch {});
+    }
+
+    // new points of every member, summing up all its valid entries
+    let mut grants: BTreeMap<Addr, (u64, Option<u64>)> = BTreeMap::new();
+    let mut applied = 0;
+    let mut rejected = vec![];
+    for (addr, points) in entries {
+        let grant = || -> Result<_, ContractError> {
+            if points == 0 {
+                return Err(ContractError::ZeroPoints {});
+            }
+            let member = deps.api.addr_validate(&addr)?;
+            let (old_points, start_height) = match grants.get(&member) {
+                Some(grant) => *grant,
+                None => members()
+                    .may_load(deps.storage, &member)?
+                    .map_or((0, None), |info| (info.points, info.start_height)),
+            };
+            let new_points = old_points
+                .checked_add(points)
+                .ok_or_else(|| OverflowError::new(OverflowOperation::Add, old_points, points))
+                .map_err(StdError::from)?;
+            Ok((member, (new_points, start_height)))
+        };
+        match grant() {
+            Ok((member, grant)) => {
+                grants.insert(member, grant);
+                applied += 1;
+            }
+            Err(err) => rejected.push(RejectedPoints {
+                addr,
+                points,
+                error: err.to_string(),
+            }),
+        }
+    }
+
+    let to_add = grants
+        .into_iter()
+        .map(|(addr, (points, start_height))| Member {
+            addr: addr.into(),
+            points,
+            start_height,
+        })
+        .collect();
+    // make the local update, all at once
+    let diff = update_members(deps.branch(), env.block.height, to_add, vec![])?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "add_points_batch")
+        .add_attribute("applied", applied.to_string())
+        .add_attribute("rejected", rejected.len().to_string())
+        .add_attribute("sender", info.sender)
+        .set_data(to_binary(&AddPointsBatchResponse { applied, rejected })?);
+    // call all registered hooks
+    if !diff.diffs.is_empty() {
+        res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+            diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+        })?;
+    }
+    Ok(res)
+}
+
+/// Adds points to the existing member's points, creating the member if needed
+fn add_points<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    height: u64,
+    addr: String,
+    points: u64,
+) -> Result<MemberChangedHookMsg, ContractError> {
     let old_points = query_member(deps.as_ref(), addr.clone(), None)?;
 
-    // make the local update
-    let diff = update_members(
-        deps.branch(),
-        env.block.height,
+    update_members(
+        deps,
+        height,
         vec![Member {
             addr,
             points: old_points.points.unwrap_or_default() + points,
             start_height: old_points.start_height,
         }],
         vec![],
+    )
+}
+
+pub fn execute_set_voucher_signer<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    signer: Option<VoucherSigner>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    match signer {
+        Some(signer) => {
+            let valid_len = match signer.key_type {
+                VoucherKeyType::Secp256k1 => [33, 65].contains(&signer.pubkey.len()),
+                VoucherKeyType::Ed25519 => signer.pubkey.len() == 32,
+            };
+            if !valid_len {
+                return Err(ContractError::InvalidVoucherSigner {});
+            }
+            VOUCHER_SIGNER.save(deps.storage, &signer)?;
+        }
+        None => VOUCHER_SIGNER.remove(deps.storage),
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "set_voucher_signer")
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+pub fn execute_claim_points<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    voucher: Voucher,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let signer = VOUCHER_SIGNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoVoucherSigner {})?;
+
+    let sign_doc = VoucherSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        voucher,
+    };
+    let hash = sign_doc.hash()?;
+    let verified = match signer.key_type {
+        VoucherKeyType::Secp256k1 => deps.api.secp256k1_verify(&hash, &signature, &signer.pubkey),
+        VoucherKeyType::Ed25519 => deps.api.ed25519_verify(&hash, &signature, &signer.pubkey),
+    }
+    .map_err(|_| ContractError::InvalidVoucherSignature {})?;
+    if !verified {
+        return Err(ContractError::InvalidVoucherSignature {});
+    }
+    let voucher = sign_doc.voucher;
+
+    if VOUCHER_NONCES.has(deps.storage, voucher.nonce) {
+        return Err(ContractError::VoucherClaimed(voucher.nonce));
+    }
+    VOUCHER_NONCES.save(deps.storage, voucher.nonce, &Empty {})?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "claim_points")
+        .add_attribute("to_member", &voucher.addr)
+        .add_attribute("amount", voucher.points.to_string())
+        .add_attribute("nonce", voucher.nonce.to_string())
+        .add_attribute("sender", info.sender);
+
+    // make the local update
+    let diff = add_points(
+        deps.branch(),
+        env.block.height,
+        voucher.addr,
+        voucher.points,
     )?;
     // call all registered hooks
     res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
@@ -187,6 +424,474 @@ pub fn execute_add_points<Q: CustomQuery>(
     Ok(res)
 }
 
+pub fn execute_register_merkle_root<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    merkle_root: Binary,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if merkle_root.len() != 32 {
+        return Err(ContractError::InvalidMerkleRoot {});
+    }
+
+    let stage = LATEST_MERKLE_STAGE
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    LATEST_MERKLE_STAGE.save(deps.storage, &stage)?;
+    MERKLE_ROOTS.save(deps.storage, stage, &merkle_root)?;
+
+    let res = Response::new()
+        .add_attribute("action", "register_merkle_root")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("merkle_root", merkle_root.to_base64())
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_claim_merkle_points<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    stage: u64,
+    index: u64,
+    addr: String,
+    points: u64,
+    proof: Vec<Binary>,
+) -> Result<Response, ContractError> {
+    let root = MERKLE_ROOTS
+        .may_load(deps.storage, stage)?
+        .ok_or(ContractError::UnknownMerkleStage(stage))?;
+
+    if !merkle::verify_proof(&root, merkle::leaf_hash(index, &addr, points), &proof) {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    let key = (stage, index / 64);
+    let bit = 1u64 << (index % 64);
+    let claimed = MERKLE_CLAIMED
+        .may_load(deps.storage, key)?
+        .unwrap_or_default();
+    if claimed & bit != 0 {
+        return Err(ContractError::MerkleClaimed { stage, index });
+    }
+    MERKLE_CLAIMED.save(deps.storage, key, &(claimed | bit))?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "claim_merkle_points")
+        .add_attribute("to_member", &addr)
+        .add_attribute("amount", points.to_string())
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("index", index.to_string())
+        .add_attribute("sender", info.sender);
+
+    // make the local update
+    let diff = add_points(deps.branch(), env.block.height, addr, points)?;
+    // call all registered hooks
+    res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+        diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+    })?;
+    Ok(res)
+}
+
+fn is_merkle_claimed(storage: &dyn Storage, stage: u64, index: u64) -> StdResult<bool> {
+    let claimed = MERKLE_CLAIMED
+        .may_load(storage, (stage, index / 64))?
+        .unwrap_or_default();
+    Ok(claimed & (1u64 << (index % 64)) != 0)
+}
+
+pub fn execute_set_transfer_policy<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    policy: TransferPolicy,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    TRANSFER_POLICY.save(deps.storage, &policy)?;
+
+    let res = Response::new()
+        .add_attribute("action", "set_transfer_policy")
+        .add_attribute("policy", format!("{:?}", policy))
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+pub fn execute_transfer_points<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+    points: u64,
+) -> Result<Response, ContractError> {
+    let policy = TRANSFER_POLICY.may_load(deps.storage)?.unwrap_or_default();
+    if policy == TransferPolicy::Disabled {
+        return Err(ContractError::TransfersDisabled {});
+    }
+
+    let to = deps.api.addr_validate(&to)?;
+    if to == info.sender {
+        return Err(ContractError::TransferToSelf {});
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "transfer_points")
+        .add_attribute("from", &info.sender)
+        .add_attribute("to", &to)
+        .add_attribute("amount", points.to_string());
+
+    if policy == TransferPolicy::AdminApproved {
+        // fail early, but everything is verified again on approval
+        transfer_members(deps.as_ref(), &info.sender, &to, points)?;
+
+        let id = PENDING_TRANSFER_ID
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        PENDING_TRANSFER_ID.save(deps.storage, &id)?;
+        let transfer = PendingTransfer {
+            from: info.sender,
+            to,
+            points,
+        };
+        PENDING_TRANSFERS.save(deps.storage, id, &transfer)?;
+
+        return Ok(res.add_attribute("transfer_id", id.to_string()));
+    }
+
+    let diff = transfer_points(deps.branch(), env.block.height, &info.sender, &to, points)?;
+    res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+        diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+    })?;
+    Ok(res)
+}
+
+pub fn execute_approve_transfer<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let transfer = PENDING_TRANSFERS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoPendingTransfer(id))?;
+
+    let diff = transfer_points(
+        deps.branch(),
+        env.block.height,
+        &transfer.from,
+        &transfer.to,
+        transfer.points,
+    )?;
+    PENDING_TRANSFERS.remove(deps.storage, id);
+
+    let mut res = Response::new()
+        .add_attribute("action", "approve_transfer")
+        .add_attribute("transfer_id", id.to_string())
+        .add_attribute("from", transfer.from)
+        .add_attribute("to", transfer.to)
+        .add_attribute("amount", transfer.points.to_string());
+    res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+        diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+    })?;
+    Ok(res)
+}
+
+pub fn execute_reject_transfer<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let transfer = PENDING_TRANSFERS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoPendingTransfer(id))?;
+
+    if info.sender != transfer.from && !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized(
+            "Sender is neither transfer sender or an admin".to_owned(),
+        ));
+    }
+
+    PENDING_TRANSFERS.remove(deps.storage, id);
+
+    let res = Response::new()
+        .add_attribute("action", "reject_transfer")
+        .add_attribute("transfer_id", id.to_string())
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+pub fn execute_set_points_oracle<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    oracle: Option<String>,
+    budget: Option<OracleBudget>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if let Some(budget) = budget {
+        if budget.points == 0 || budget.period.seconds() == 0 {
+            return Err(ContractError::InvalidOracleBudget {});
+        }
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_points_oracle")
+        .add_attribute("sender", info.sender);
+    match oracle {
+        Some(oracle) => {
+            let addr = deps.api.addr_validate(&oracle)?;
+            res = res.add_attribute("oracle", &addr);
+            POINTS_ORACLE.save(deps.storage, &PointsOracle { addr, budget })?;
+        }
+        None => POINTS_ORACLE.remove(deps.storage),
+    }
+    // budget usage is counted from scratch for the new configuration
+    ORACLE_BUDGET_USAGE.remove(deps.storage);
+    Ok(res)
+}
+
+pub fn execute_suggest_points<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    entries: Vec<(String, u64)>,
+) -> Result<Response, ContractError> {
+    let oracle = POINTS_ORACLE.may_load(deps.storage)?;
+    let oracle = match oracle {
+        Some(oracle) if oracle.addr == info.sender => oracle,
+        _ => {
+            return Err(ContractError::Unauthorized(
+                "Sender is not the points oracle".to_owned(),
+            ))
+        }
+    };
+    if entries.is_empty() {
+        return Err(ContractError::EmptyPointsBatch {});
+    }
+
+    let mut usage = oracle_budget_usage(deps.storage, oracle.budget, env.block.time)?;
+    let mut grants = vec![];
+    let mut pending = vec![];
+    for (addr, points) in entries {
+        if points == 0 {
+            return Err(ContractError::ZeroPoints {});
+        }
+        let addr = deps.api.addr_validate(&addr)?;
+        let within_budget = oracle
+            .budget
+            .and_then(|budget| budget.points.checked_sub(usage.used))
+            .map(|remaining| points <= remaining)
+            .unwrap_or_default();
+        if within_budget {
+            usage.used += points;
+            grants.push((addr, points));
+        } else {
+            let id = PENDING_SUGGESTION_ID
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            PENDING_SUGGESTION_ID.save(deps.storage, &id)?;
+            let suggestion = PendingSuggestion {
+                addr,
+                points,
+                oracle: oracle.addr.clone(),
+            };
+            PENDING_SUGGESTIONS.save(deps.storage, id, &suggestion)?;
+            pending.push(id);
+        }
+    }
+    if oracle.budget.is_some() {
+        ORACLE_BUDGET_USAGE.save(deps.storage, &usage)?;
+    }
+
+    let applied = grants.len() as u32;
+    let mut res = Response::new()
+        .add_attribute("action", "suggest_points")
+        .add_attribute("applied", applied.to_string())
+        .add_attribute("pending", pending.len().to_string())
+        .add_attribute("sender", info.sender)
+        .set_data(to_binary(&SuggestPointsResponse { applied, pending })?);
+    if !grants.is_empty() {
+        let diff = grant_points(deps.branch(), env.block.height, grants)?;
+        res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+            diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+        })?;
+    }
+    Ok(res)
+}
+
+pub fn execute_approve_suggestions<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    // suggestions of a replaced or removed oracle can only be rejected
+    let oracle = POINTS_ORACLE
+        .may_load(deps.storage)?
+        .map(|oracle| oracle.addr);
+    let grants = take_pending_suggestions(deps.storage, &ids)?
+        .into_iter()
+        .zip(&ids)
+        .map(|(suggestion, id)| {
+            if oracle.as_ref() != Some(&suggestion.oracle) {
+                return Err(ContractError::StaleSuggestion(*id));
+            }
+            Ok((suggestion.addr, suggestion.points))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "approve_suggestions")
+        .add_attribute("approved", grants.len().to_string())
+        .add_attribute("sender", info.sender);
+    if !grants.is_empty() {
+        let diff = grant_points(deps.branch(), env.block.height, grants)?;
+        res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+            diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+        })?;
+    }
+    Ok(res)
+}
+
+pub fn execute_reject_suggestions<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let rejected = take_pending_suggestions(deps.storage, &ids)?;
+
+    let res = Response::new()
+        .add_attribute("action", "reject_suggestions")
+        .add_attribute("rejected", rejected.len().to_string())
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+/// Removes pending suggestions with given ids, failing if any of them doesn't exist
+fn take_pending_suggestions(
+    storage: &mut dyn Storage,
+    ids: &[u64],
+) -> Result<Vec<PendingSuggestion>, ContractError> {
+    ids.iter()
+        .map(|id| {
+            let suggestion = PENDING_SUGGESTIONS
+                .may_load(storage, *id)?
+                .ok_or(ContractError::NoPendingSuggestion(*id))?;
+            PENDING_SUGGESTIONS.remove(storage, *id);
+            Ok(suggestion)
+        })
+        .collect()
+}
+
+/// Oracle budget usage in the budget epoch of `t`
+fn oracle_budget_usage(
+    storage: &dyn Storage,
+    budget: Option<OracleBudget>,
+    t: Timestamp,
+) -> StdResult<OracleBudgetUsage> {
+    let epoch = match budget {
+        Some(budget) => budget.epoch(t),
+        None => return Ok(OracleBudgetUsage::default()),
+    };
+    match ORACLE_BUDGET_USAGE.may_load(storage)? {
+        Some(usage) if usage.epoch == epoch => Ok(usage),
+        _ => Ok(OracleBudgetUsage { epoch, used: 0 }),
+    }
+}
+
+/// Adds points to members with a single update, summing points of members appearing
+/// more than once
+fn grant_points<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    height: u64,
+    grants: Vec<(Addr, u64)>,
+) -> Result<MemberChangedHookMsg, ContractError> {
+    let mut members_points: BTreeMap<Addr, (u64, Option<u64>)> = BTreeMap::new();
+    for (addr, points) in grants {
+        let (old_points, start_height) = match members_points.get(&addr) {
+            Some(grant) => *grant,
+            None => members()
+                .may_load(deps.storage, &addr)?
+                .map_or((0, None), |info| (info.points, info.start_height)),
+        };
+        let new_points = old_points
+            .checked_add(points)
+            .ok_or_else(|| OverflowError::new(OverflowOperation::Add, old_points, points))
+            .map_err(StdError::from)?;
+        members_points.insert(addr, (new_points, start_height));
+    }
+
+    let to_add = members_points
+        .into_iter()
+        .map(|(addr, (points, start_height))| Member {
+            addr: addr.into(),
+            points,
+            start_height,
+        })
+        .collect();
+    update_members(deps, height, to_add, vec![])
+}
+
+/// Verifies the points transfer, returning updated `from` and `to` members
+fn transfer_members<Q: CustomQuery>(
+    deps: Deps<Q>,
+    from: &Addr,
+    to: &Addr,
+    points: u64,
+) -> Result<Vec<Member>, ContractError> {
+    if points == 0 {
+        return Err(ContractError::ZeroTransfer {});
+    }
+    let from_info = members()
+        .may_load(deps.storage, from)?
+        .ok_or_else(|| ContractError::NotAMember(from.to_string()))?;
+    let to_info = members()
+        .may_load(deps.storage, to)?
+        .ok_or_else(|| ContractError::NotAMember(to.to_string()))?;
+    if from_info.points < points {
+        return Err(ContractError::InsufficientPoints {
+            available: from_info.points,
+            required: points,
+        });
+    }
+
+    Ok(vec![
+        Member {
+            addr: from.to_string(),
+            points: from_info.points - points,
+            start_height: from_info.start_height,
+        },
+        Member {
+            addr: to.to_string(),
+            points: to_info.points + points,
+            start_height: to_info.start_height,
+        },
+    ])
+}
+
+/// Moves points between members. Withdraw adjustments of both are updated, so rewards
+/// distributed before the transfer stay with the sender
+fn transfer_points<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    height: u64,
+    from: &Addr,
+    to: &Addr,
+    points: u64,
+) -> Result<MemberChangedHookMsg, ContractError> {
+    let members = transfer_members(deps.as_ref(), from, to, points)?;
+    update_members(deps, height, members, vec![])
+}
+
 pub fn execute_add_hook<Q: CustomQuery>(
     deps: DepsMut<Q>,
     info: MessageInfo,
@@ -288,6 +993,50 @@ pub fn execute_distribute_rewards<Q: CustomQuery>(
         return Ok(Response::new());
     }
 
+    // Throttled calls are not failing, as rewards are send here by the valset end block. Funds are
+    // kept in the contract and distributed by the first call which is allowed to.
+    let mut throttle = DISTRIBUTION_THROTTLE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if let Some(next_distribution) = throttle.next_distribution() {
+        if env.block.time < next_distribution {
+            let resp = Response::new()
+                .add_attribute("action", "distribute_rewards_deferred")
+                .add_attribute("sender", sender.as_str())
+                .add_attribute("amount", amount.to_string())
+                .add_attribute("next_distribution", next_distribution.seconds().to_string());
+            return Ok(resp);
+        }
+    }
+    if amount < throttle.min_amount.u128() {
+        let resp = Response::new()
+            .add_attribute("action", "distribute_rewards_deferred")
+            .add_attribute("sender", sender.as_str())
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("min_distribution", throttle.min_amount.to_string());
+        return Ok(resp);
+    }
+
+    // Streams started under a longer period may still be unlocking after it was shortened
+    let stream_period = WITHDRAWAL_STREAM_PERIOD.may_load(deps.storage)?;
+    if stream_period.is_some() {
+        prune_rewards_streams(deps.storage, env.block.time)?;
+        let active_streams = REWARDS_STREAMS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count() as u64;
+        if active_streams >= MAX_REWARDS_STREAMS {
+            let resp = Response::new()
+                .add_attribute("action", "distribute_rewards_deferred")
+                .add_attribute("sender", sender.as_str())
+                .add_attribute("amount", amount.to_string())
+                .add_attribute("active_streams", active_streams.to_string());
+            return Ok(resp);
+        }
+    }
+
+    throttle.last_distribution = Some(env.block.time);
+    DISTRIBUTION_THROTTLE.save(deps.storage, &throttle)?;
+
     let leftover: u128 = distribution.shares_leftover.into();
     let points = (amount << SHARES_SHIFT) + leftover;
     let points_per_share = points / total;
@@ -303,17 +1052,53 @@ pub fn execute_distribute_rewards<Q: CustomQuery>(
 
     DISTRIBUTION.save(deps.storage, &distribution)?;
 
+    let seq = DISTRIBUTION_SEQ.may_load(deps.storage)?.unwrap_or_default() + 1;
+    DISTRIBUTION_SEQ.save(deps.storage, &seq)?;
+    let snapshot = DistributionSnapshot {
+        denom: distribution.denom.clone(),
+        amount: Uint128::new(amount),
+        total_points: total as u64,
+        shares_per_point: Uint128::from(points_per_share),
+        height: env.block.height,
+        time: env.block.time,
+    };
+    DISTRIBUTION_SNAPSHOTS.save(deps.storage, seq, &snapshot)?;
+
+    // With withdrawal streaming the distributed shares unlock over the stream period
+    if let Some(period) = stream_period {
+        if points_per_share > 0 {
+            let id = REWARDS_STREAM_ID
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            REWARDS_STREAM_ID.save(deps.storage, &id)?;
+            let stream = RewardsStream {
+                shares_per_point: Uint128::from(points_per_share),
+                height: env.block.height,
+                start: env.block.time,
+                end: env.block.time.plus_seconds(period.seconds()),
+            };
+            REWARDS_STREAMS.save(deps.storage, id, &stream)?;
+        }
+    }
+
     let resp = Response::new()
         .add_attribute("action", "distribute_rewards")
         .add_attribute("sender", sender.as_str())
         .add_attribute("denom", &distribution.denom)
-        .add_attribute("amount", amount.to_string());
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("seq", seq.to_string())
+        .add_event(events::distribution(
+            &distribution.denom,
+            Uint128::new(amount),
+        ));
 
     Ok(resp)
 }
 
 pub fn execute_withdraw_rewards<Q: CustomQuery>(
     deps: DepsMut<Q>,
+    env: Env,
     info: MessageInfo,
     owner: Option<String>,
     receiver: Option<String>,
@@ -326,13 +1111,19 @@ pub fn execute_withdraw_rewards<Q: CustomQuery>(
     let mut distribution = DISTRIBUTION.load(deps.storage)?;
     let mut adjustment = WITHDRAW_ADJUSTMENT.load(deps.storage, &owner)?;
 
-    if ![&owner, &adjustment.delegated].contains(&&info.sender) {
+    if ![&owner, adjustment.delegated_at(&owner, &env.block)].contains(&&info.sender) {
         return Err(ContractError::Unauthorized(
             "Sender is neither owner or delegated".to_owned(),
         ));
     }
 
-    let reward = withdrawable_rewards(deps.as_ref(), &owner, &distribution, &adjustment)?;
+    let reward = withdrawable_rewards(
+        deps.as_ref(),
+        &owner,
+        &distribution,
+        &adjustment,
+        env.block.time,
+    )?;
     let receiver = receiver
         .map(|receiver| deps.api.addr_validate(&receiver))
         .transpose()?
@@ -345,6 +1136,10 @@ pub fn execute_withdraw_rewards<Q: CustomQuery>(
 
     adjustment.withdrawn_rewards += reward.amount;
     WITHDRAW_ADJUSTMENT.save(deps.storage, &owner, &adjustment)?;
+    if adjustment.delegated_at(&owner, &env.block) == &owner {
+        // delegation expired, no longer worth listing
+        DELEGATIONS.remove(deps.storage, &owner);
+    }
     distribution.withdrawable_total -= reward.amount;
     DISTRIBUTION.save(deps.storage, &distribution)?;
 
@@ -365,29 +1160,47 @@ pub fn execute_withdraw_rewards<Q: CustomQuery>(
 
 pub fn execute_delegate_withdrawal<Q: CustomQuery>(
     deps: DepsMut<Q>,
+    env: Env,
     info: MessageInfo,
     delegated: String,
+    expires: Option<Timestamp>,
 ) -> Result<Response, ContractError> {
     let delegated = deps.api.addr_validate(&delegated)?;
 
+    if matches!(expires, Some(expires) if expires <= env.block.time) {
+        return Err(ContractError::DelegationExpirationInPast {});
+    }
+    // delegating back to self never expires
+    let expires = expires.filter(|_| delegated != info.sender);
+
     WITHDRAW_ADJUSTMENT.update(deps.storage, &info.sender, |data| -> StdResult<_> {
         Ok(data.map_or_else(
             || WithdrawAdjustment {
                 shares_correction: 0.into(),
                 withdrawn_rewards: Uint128::zero(),
                 delegated: delegated.clone(),
+                delegation_expires: expires,
             },
             |mut data| {
                 data.delegated = delegated.clone();
+                data.delegation_expires = expires;
                 data
             },
         ))
     })?;
+    if delegated == info.sender {
+        DELEGATIONS.remove(deps.storage, &info.sender);
+    } else {
+        DELEGATIONS.save(deps.storage, &info.sender, &Empty {})?;
+    }
 
-    let resp = Response::new()
+    let mut resp = Response::new()
         .add_attribute("action", "delegate_withdrawal")
         .add_attribute("sender", info.sender.as_str())
         .add_attribute("delegated", &delegated);
+    if let Some(expires) = expires {
+        resp = resp.add_attribute("expires", expires.nanos().to_string());
+    }
 
     Ok(resp)
 }
@@ -397,12 +1210,13 @@ pub fn execute_add_slasher<Q: CustomQuery>(
     deps: DepsMut<Q>,
     info: MessageInfo,
     slasher: String,
+    max_portion: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     if !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
         PREAUTH_SLASHING.use_auth(deps.storage)?;
     }
 
-    SLASHERS.add_slasher(deps.storage, deps.api.addr_validate(&slasher)?)?;
+    SLASHERS.add_slasher(deps.storage, deps.api.addr_validate(&slasher)?, max_portion)?;
 
     let res = Response::new()
         .add_attribute("action", "add_slasher")
@@ -457,7 +1271,7 @@ pub fn execute_slash<Q: CustomQuery>(
         return Ok(Response::new());
     };
 
-    validate_portion(portion)?;
+    SLASHERS.validate_slash(deps.storage, &info.sender, portion)?;
 
     let ppw: u128 = DISTRIBUTION.load(deps.storage)?.shares_per_point.into();
 
@@ -483,24 +1297,27 @@ pub fn execute_slash<Q: CustomQuery>(
     )?;
     apply_points_correction(deps.branch(), &addr, ppw, diff)?;
 
-    TOTAL.update(deps.storage, |total| -> StdResult<_> {
-        Ok((total as i128 + diff) as _)
+    TOTAL.update(deps.storage, env.block.height, |total| -> StdResult<_> {
+        Ok((total.unwrap_or_default() as i128 + diff) as _)
     })?;
 
     let res = Response::new()
         .add_attribute("action", "slash")
         .add_attribute("addr", &addr)
-        .add_attribute("sender", info.sender);
+        .add_attribute("sender", info.sender)
+        .add_event(events::slash(&addr, portion, Uint128::new(-diff as u128)));
 
     Ok(res)
 }
 
-/// Calculates withdrawable_rewards from distribution and adjustment info.
+/// Calculates withdrawable_rewards from distribution and adjustment info. Shares of
+/// distributions still unlocking at `time` are not withdrawable.
 pub fn withdrawable_rewards<Q: CustomQuery>(
     deps: Deps<Q>,
     owner: &Addr,
     distribution: &Distribution,
     adjustment: &WithdrawAdjustment,
+    time: Timestamp,
 ) -> StdResult<Coin> {
     let ppw: u128 = distribution.shares_per_point.into();
     let points: u128 = members()
@@ -510,14 +1327,48 @@ pub fn withdrawable_rewards<Q: CustomQuery>(
         .into();
     let correction: i128 = adjustment.shares_correction.into();
     let withdrawn: u128 = adjustment.withdrawn_rewards.into();
+    let locked = locked_shares(deps, owner, time)? as i128;
     let points = (ppw * points) as i128;
-    let points = points + correction;
-    let amount = points as u128 >> SHARES_SHIFT;
-    let amount = amount - withdrawn;
+    let points = points + correction - locked;
+    // Locked shares are based on the points at the time of distribution, so if points were
+    // changed in the same block, they may exceed the earned shares for a while
+    let amount = max(points, 0) as u128 >> SHARES_SHIFT;
+    let amount = amount.saturating_sub(withdrawn);
 
     Ok(coin(amount, &distribution.denom))
 }
 
+/// Sums shares of the owner from all distributions still unlocking at `time`.
+fn locked_shares<Q: CustomQuery>(deps: Deps<Q>, owner: &Addr, time: Timestamp) -> StdResult<u128> {
+    REWARDS_STREAMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|stream| -> StdResult<_> {
+            let (_, stream) = stream?;
+            if stream.end <= time {
+                return Ok(0);
+            }
+            let points = members()
+                .may_load_at_height(deps.storage, owner, stream.height + 1)?
+                .map(|member| member.points)
+                .unwrap_or_default();
+            Ok(stream.locked_shares(points, time))
+        })
+        .sum()
+}
+
+/// Removes streams of distributions which are fully unlocked.
+fn prune_rewards_streams(storage: &mut dyn Storage, time: Timestamp) -> StdResult<()> {
+    let unlocked = REWARDS_STREAMS
+        .range(storage, None, None, Order::Ascending)
+        .filter(|stream| matches!(stream, Ok((_, stream)) if stream.end <= time))
+        .map(|stream| stream.map(|(id, _)| id))
+        .collect::<StdResult<Vec<_>>>()?;
+    for id in unlocked {
+        REWARDS_STREAMS.remove(storage, id);
+    }
+    Ok(())
+}
+
 pub fn sudo_add_member<Q: CustomQuery>(
     mut deps: DepsMut<Q>,
     env: Env,
@@ -581,7 +1432,7 @@ pub fn update_members<Q: CustomQuery>(
         }
     }
 
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, height)?;
     Ok(MemberChangedHookMsg { diffs })
 }
 
@@ -602,6 +1453,7 @@ pub fn apply_points_correction<Q: CustomQuery>(
                 shares_correction: 0.into(),
                 withdrawn_rewards: Uint128::zero(),
                 delegated: addr.clone(),
+                delegation_expires: None,
             }
         });
         let shares_correction: i128 = old.shares_correction.into();
@@ -706,12 +1558,9 @@ fn end_block<Q: CustomQuery>(mut deps: DepsMut<Q>, env: Env) -> Result<Response,
 
     let mut total = TOTAL.load(deps.storage)?;
     total -= reduction;
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, env.block.height)?;
 
-    let evt = Event::new("halflife")
-        .add_attribute("height", env.block.height.to_string())
-        .add_attribute("reduction", reduction.to_string());
-    let resp = resp.add_event(evt);
+    let resp = resp.add_event(events::halflife(env.block.height, reduction));
 
     Ok(resp)
 }
@@ -728,7 +1577,19 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
         ListMembersByPoints { start_after, limit } => {
             to_binary(&list_members_by_points(deps, start_after, limit)?)
         }
-        TotalPoints {} => to_binary(&query_total_points(deps)?),
+        ListMembersByPointsRange {
+            min,
+            max,
+            start_after,
+            limit,
+        } => to_binary(&list_members_by_points_range(
+            deps,
+            min,
+            max,
+            start_after,
+            limit,
+        )?),
+        TotalPoints { at_height } => to_binary(&query_total_points(deps, at_height)?),
         Admin {} => to_binary(&ADMIN.query_admin(deps)?),
         Hooks {} => {
             let hooks = HOOKS.list_hooks(deps.storage)?;
@@ -738,26 +1599,137 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
             let preauths = PREAUTH_HOOKS.get_auth(deps.storage)?;
             to_binary(&PreauthResponse { preauths })
         }
-        WithdrawableRewards { owner } => to_binary(&query_withdrawable_rewards(deps, owner)?),
+        WithdrawableRewards { owner } => to_binary(&query_withdrawable_rewards(deps, env, owner)?),
+        LockedRewards { owner } => to_binary(&query_locked_rewards(deps, env, owner)?),
         DistributedRewards {} => to_binary(&query_distributed_rewards(deps)?),
         UndistributedRewards {} => to_binary(&query_undistributed_rewards(deps, env)?),
-        Delegated { owner } => to_binary(&query_delegated(deps, owner)?),
+        Delegated { owner } => to_binary(&query_delegated(deps, env, owner)?),
+        ListDelegations { start_after, limit } => {
+            to_binary(&list_delegations(deps, env, start_after, limit)?)
+        }
         Halflife {} => to_binary(&query_halflife(deps)?),
         IsSlasher { addr } => {
             let addr = deps.api.addr_validate(&addr)?;
             to_binary(&SLASHERS.is_slasher(deps.storage, &addr)?)
         }
         ListSlashers {} => to_binary(&SLASHERS.list_slashers(deps.storage)?),
+        SlasherLimit { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            to_binary(&SLASHERS.slasher_limit(deps.storage, &addr)?)
+        }
         DistributionData {} => to_binary(&DISTRIBUTION.may_load(deps.storage)?),
+        DistributionThrottle {} => to_binary(&query_distribution_throttle(deps)?),
+        WithdrawalStream {} => to_binary(&query_withdrawal_stream(deps, env)?),
         WithdrawAdjustmentData { addr } => {
             let addr = deps.api.addr_validate(&addr)?;
             to_binary(&WITHDRAW_ADJUSTMENT.may_load(deps.storage, &addr)?)
         }
+        VoucherSigner {} => to_binary(&VoucherSignerResponse {
+            signer: VOUCHER_SIGNER.may_load(deps.storage)?,
+        }),
+        IsVoucherClaimed { nonce } => to_binary(&VOUCHER_NONCES.has(deps.storage, nonce)),
+        MerkleRoot { stage } => to_binary(&MerkleRootResponse {
+            root: MERKLE_ROOTS.may_load(deps.storage, stage)?,
+        }),
+        LatestMerkleStage {} => to_binary(&LatestMerkleStageResponse {
+            stage: LATEST_MERKLE_STAGE
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        }),
+        IsMerkleClaimed { stage, index } => {
+            to_binary(&is_merkle_claimed(deps.storage, stage, index)?)
+        }
+        TransferPolicy {} => to_binary(&TransferPolicyResponse {
+            policy: TRANSFER_POLICY.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        ListPendingTransfers { start_after, limit } => {
+            to_binary(&list_pending_transfers(deps, start_after, limit)?)
+        }
+        PointsOracle {} => to_binary(&query_points_oracle(deps, env)?),
+        ListPendingSuggestions { start_after, limit } => {
+            to_binary(&list_pending_suggestions(deps, start_after, limit)?)
+        }
+        ListDistributionSnapshots { start_after, limit } => {
+            to_binary(&list_distribution_snapshots(deps, start_after, limit)?)
+        }
+        SimulateUpdateMembers { add, remove } => {
+            to_binary(&simulate_update_members(deps, add, remove)?)
+        }
+    }
+}
+
+/// Mirrors `update_members` without touching the storage. Changes are kept in memory, so
+/// repeated or conflicting entries are resolved the same way as on execution.
+fn simulate_update_members<Q: CustomQuery>(
+    deps: Deps<Q>,
+    to_add: Vec<Member>,
+    to_remove: Vec<String>,
+) -> StdResult<SimulateUpdateMembersResponse> {
+    let mut total = TOTAL.load(deps.storage)?;
+    let mut diffs: Vec<MemberDiff> = vec![];
+    // points after the update, and sum of points changes per address
+    let mut points: BTreeMap<Addr, Option<u64>> = BTreeMap::new();
+    let mut changes: BTreeMap<Addr, i128> = BTreeMap::new();
+
+    let ppw: u128 = DISTRIBUTION.load(deps.storage)?.shares_per_point.into();
+
+    let current = |points: &BTreeMap<Addr, Option<u64>>, addr: &Addr| -> StdResult<Option<u64>> {
+        match points.get(addr) {
+            Some(points) => Ok(*points),
+            None => Ok(members().may_load(deps.storage, addr)?.map(|mi| mi.points)),
+        }
+    };
+
+    for add in to_add {
+        let add_addr = deps.api.addr_validate(&add.addr)?;
+        let old = current(&points, &add_addr)?;
+        diffs.push(MemberDiff::new(add.addr, old, Some(add.points)));
+        let old = old.unwrap_or_default();
+        total -= old;
+        total += add.points;
+        *changes.entry(add_addr.clone()).or_default() += add.points as i128 - old as i128;
+        points.insert(add_addr, Some(add.points));
+    }
+
+    for remove in to_remove {
+        let remove_addr = deps.api.addr_validate(&remove)?;
+        if let Some(old) = current(&points, &remove_addr)? {
+            diffs.push(MemberDiff::new(remove, Some(old), None));
+            total -= old;
+            *changes.entry(remove_addr.clone()).or_default() -= old as i128;
+            points.insert(remove_addr, None);
+        }
     }
+
+    let adjustments = changes
+        .into_iter()
+        .map(|(addr, diff)| {
+            let old = WITHDRAW_ADJUSTMENT
+                .may_load(deps.storage, &addr)?
+                .map(|adjustment| adjustment.shares_correction);
+            let correction: i128 = old.unwrap_or_default().into();
+            let new = (correction - ppw as i128 * diff).into();
+            Ok(AdjustmentDiff { addr, old, new })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(SimulateUpdateMembersResponse {
+        diffs,
+        total_points: total,
+        adjustments,
+    })
 }
 
-fn query_total_points<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<TotalPointsResponse> {
-    let points = TOTAL.load(deps.storage)?;
+fn query_total_points<Q: CustomQuery>(
+    deps: Deps<Q>,
+    height: Option<u64>,
+) -> StdResult<TotalPointsResponse> {
+    let points = match height {
+        Some(h) => TOTAL
+            .may_load_at_height(deps.storage, h)?
+            .unwrap_or_default(),
+        None => TOTAL.load(deps.storage)?,
+    };
     Ok(TotalPointsResponse { points })
 }
 
@@ -776,6 +1748,7 @@ fn query_member<Q: CustomQuery>(
 
 pub fn query_withdrawable_rewards<Q: CustomQuery>(
     deps: Deps<Q>,
+    env: Env,
     owner: String,
 ) -> StdResult<RewardsResponse> {
     // Not checking address, as if it is invalid it is guaranteed not to appear in maps, so
@@ -790,10 +1763,23 @@ pub fn query_withdrawable_rewards<Q: CustomQuery>(
         });
     };
 
-    let rewards = withdrawable_rewards(deps, &owner, &distribution, &adjustment)?;
+    let rewards = withdrawable_rewards(deps, &owner, &distribution, &adjustment, env.block.time)?;
     Ok(RewardsResponse { rewards })
 }
 
+pub fn query_locked_rewards<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    owner: String,
+) -> StdResult<RewardsResponse> {
+    let owner = Addr::unchecked(owner);
+    let denom = DISTRIBUTION.load(deps.storage)?.denom;
+    let locked = locked_shares(deps, &owner, env.block.time)?;
+    Ok(RewardsResponse {
+        rewards: coin(locked >> SHARES_SHIFT, denom),
+    })
+}
+
 pub fn query_undistributed_rewards<Q: CustomQuery>(
     deps: Deps<Q>,
     env: Env,
@@ -821,15 +1807,146 @@ pub fn query_distributed_rewards<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<Rew
 
 pub fn query_delegated<Q: CustomQuery>(
     deps: Deps<Q>,
+    env: Env,
     owner: String,
 ) -> StdResult<DelegatedResponse> {
     let owner = deps.api.addr_validate(&owner)?;
 
-    let delegated = WITHDRAW_ADJUSTMENT
-        .may_load(deps.storage, &owner)?
-        .map_or(owner, |data| data.delegated);
+    let resp = match WITHDRAW_ADJUSTMENT.may_load(deps.storage, &owner)? {
+        Some(data) if data.delegated_at(&owner, &env.block) != &owner => DelegatedResponse {
+            delegated: data.delegated,
+            expires: data.delegation_expires,
+        },
+        _ => DelegatedResponse {
+            delegated: owner,
+            expires: None,
+        },
+    };
+
+    Ok(resp)
+}
+
+fn list_delegations<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DelegationsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let delegations = DELEGATIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(|owner| -> StdResult<_> {
+            let owner = owner?;
+            let data = WITHDRAW_ADJUSTMENT.load(deps.storage, &owner)?;
+            Ok((owner, data))
+        })
+        .filter(|item| {
+            !matches!(item, Ok((owner, data)) if data.delegated_at(owner, &env.block) == owner)
+        })
+        .take(limit)
+        .map(|item| {
+            let (owner, data) = item?;
+            Ok(DelegationInfo {
+                owner,
+                delegated: data.delegated,
+                expires: data.delegation_expires,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(DelegationsResponse { delegations })
+}
+
+fn query_points_oracle<Q: CustomQuery>(deps: Deps<Q>, env: Env) -> StdResult<PointsOracleResponse> {
+    let oracle = POINTS_ORACLE.may_load(deps.storage)?;
+    let budget = oracle.as_ref().and_then(|oracle| oracle.budget);
+    let usage = oracle_budget_usage(deps.storage, budget, env.block.time)?;
+    Ok(PointsOracleResponse {
+        oracle: oracle.map(|oracle| oracle.addr),
+        budget,
+        budget_used: usage.used,
+    })
+}
+
+fn list_pending_suggestions<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingSuggestionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let suggestions = PENDING_SUGGESTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, suggestion) = item?;
+            Ok(PendingSuggestionInfo {
+                id,
+                addr: suggestion.addr,
+                points: suggestion.points,
+                oracle: suggestion.oracle,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(PendingSuggestionsResponse { suggestions })
+}
+
+fn list_pending_transfers<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingTransfersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let transfers = PENDING_TRANSFERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, transfer) = item?;
+            Ok(PendingTransferInfo {
+                id,
+                from: transfer.from,
+                to: transfer.to,
+                points: transfer.points,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(PendingTransfersResponse { transfers })
+}
+
+fn list_distribution_snapshots<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DistributionSnapshotsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let snapshots = DISTRIBUTION_SNAPSHOTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (seq, snapshot) = item?;
+            Ok(DistributionSnapshotInfo {
+                seq,
+                denom: snapshot.denom,
+                amount: snapshot.amount,
+                total_points: snapshot.total_points,
+                shares_per_point: snapshot.shares_per_point,
+                height: snapshot.height,
+                time: snapshot.time,
+            })
+        })
+        .collect::<StdResult<_>>()?;
 
-    Ok(DelegatedResponse { delegated })
+    Ok(DistributionSnapshotsResponse { snapshots })
 }
 
 fn query_halflife<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<HalflifeResponse> {
@@ -851,6 +1968,36 @@ fn query_halflife<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<HalflifeResponse>
     })
 }
 
+fn query_distribution_throttle<Q: CustomQuery>(
+    deps: Deps<Q>,
+) -> StdResult<DistributionThrottleResponse> {
+    let throttle = DISTRIBUTION_THROTTLE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    Ok(DistributionThrottleResponse {
+        min_distribution: throttle.min_amount,
+        distribution_interval: throttle.min_interval,
+        last_distribution: throttle.last_distribution,
+        next_distribution: throttle.next_distribution(),
+    })
+}
+
+fn query_withdrawal_stream<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+) -> StdResult<WithdrawalStreamResponse> {
+    let active_streams = REWARDS_STREAMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|stream| !matches!(stream, Ok((_, stream)) if stream.end <= env.block.time))
+        .count() as u64;
+
+    Ok(WithdrawalStreamResponse {
+        period: WITHDRAWAL_STREAM_PERIOD.may_load(deps.storage)?,
+        active_streams,
+    })
+}
+
 // settings for pagination
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 30;
@@ -923,6 +2070,47 @@ fn list_members_by_points<Q: CustomQuery>(
     Ok(MemberListResponse { members: members? })
 }
 
+fn list_members_by_points_range<Q: CustomQuery>(
+    deps: Deps<Q>,
+    min: Option<u64>,
+    max: Option<u64>,
+    start_after: Option<Member>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = min.unwrap_or_default();
+    // Pages starting above `max` start right at `max` instead. Keys are `(points, addr)`,
+    // so everything below `(max + 1, "")` has at most `max` points.
+    let start = match start_after.filter(|m| m.points <= max.unwrap_or(u64::MAX)) {
+        Some(m) => Some(Bound::exclusive((
+            m.points,
+            deps.api.addr_validate(&m.addr)?,
+        ))),
+        None => max
+            .and_then(|max| max.checked_add(1))
+            .map(|above| Bound::exclusive((above, Addr::unchecked("")))),
+    };
+
+    let range = members()
+        .idx
+        .points
+        .range(deps.storage, None, start, Order::Descending);
+    let members = range
+        .take_while(|item| !matches!(item, Ok((_, info)) if info.points < min))
+        .take(limit)
+        .map(|item| {
+            let (addr, info) = item?;
+            Ok(Member {
+                addr: addr.into(),
+                points: info.points,
+                start_height: info.start_height,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(MemberListResponse { members })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
     mut deps: DepsMut<TgradeQuery>,
@@ -944,6 +2132,7 @@ pub fn migrate(
         let evt =
             Event::new("halflife-updates").add_attribute("height", env.block.height.to_string());
         resp = resp.add_event(evt);
+        index_delegations(deps.branch())?;
     }
 
     Ok(resp)
@@ -996,6 +2185,9 @@ mod tests {
             preauths_slashing: 0,
             halflife: Some(Duration::new(HALFLIFE)),
             denom: "usdc".to_owned(),
+            min_distribution: Uint128::zero(),
+            distribution_interval: None,
+            withdrawal_stream_period: None,
         };
         let info = mock_info("creator", &[]);
         instantiate(deps, mock_env(), info, msg).unwrap();
@@ -1010,7 +2202,7 @@ mod tests {
         let res = ADMIN.query_admin(deps.as_ref()).unwrap();
         assert_eq!(Some(INIT_ADMIN.into()), res.admin);
 
-        let res = query_total_points(deps.as_ref()).unwrap();
+        let res = query_total_points(deps.as_ref(), None).unwrap();
         assert_eq!(17, res.points);
 
         let preauths = PREAUTH_HOOKS.get_auth(&deps.storage).unwrap();
@@ -1044,6 +2236,7 @@ mod tests {
                 shares_correction: Int128::zero(),
                 withdrawn_rewards: Uint128::zero(),
                 delegated: Addr::unchecked("user1"),
+                delegation_expires: None,
             }
         );
     }
@@ -1186,6 +2379,42 @@ mod tests {
         assert_eq!(members.len(), 0);
     }
 
+    #[test]
+    fn try_list_members_by_points_range() {
+        let mut deps = mock_deps_tgrade();
+        do_instantiate(deps.as_mut());
+
+        let list = |min, max, start_after: Option<(&str, u64)>, limit| -> Vec<String> {
+            let start_after = start_after.map(|(addr, points)| Member {
+                addr: addr.to_owned(),
+                points,
+                start_height: None,
+            });
+            list_members_by_points_range(deps.as_ref(), min, max, start_after, limit)
+                .unwrap()
+                .members
+                .into_iter()
+                .map(|m| m.addr)
+                .collect()
+        };
+
+        assert_eq!(list(None, None, None, None), [USER1, USER2]);
+        assert_eq!(list(Some(7), None, None, None), [USER1]);
+        assert_eq!(list(None, Some(10), None, None), [USER2]);
+        assert_eq!(list(Some(12), None, None, None), [] as [&str; 0]);
+
+        // Bounds are inclusive, and pages continue after `start_after`
+        assert_eq!(list(Some(6), Some(11), None, Some(1)), [USER1]);
+        assert_eq!(list(Some(6), Some(11), Some((USER1, 11)), Some(1)), [USER2]);
+        assert_eq!(
+            list(Some(6), Some(11), Some((USER2, 6)), None),
+            [] as [&str; 0]
+        );
+
+        // Starting above `max` starts at `max`
+        assert_eq!(list(None, Some(10), Some((USER1, 11)), None), [USER2]);
+    }
+
     #[test]
     fn try_halflife_queries() {
         let mut deps = mock_deps_tgrade();
@@ -1233,6 +2462,9 @@ mod tests {
             preauths_slashing: 0,
             halflife: None,
             denom: "usdc".to_owned(),
+            min_distribution: Uint128::zero(),
+            distribution_interval: None,
+            withdrawal_stream_period: None,
         };
         let info = mock_info("creator", &[]);
 
@@ -1289,7 +2521,7 @@ mod tests {
             let members = list_members(deps.as_ref(), None, None).unwrap();
             assert_eq!(count, members.members.len());
 
-            let total = query_total_points(deps.as_ref()).unwrap();
+            let total = query_total_points(deps.as_ref(), None).unwrap();
             assert_eq!(sum, total.points); // 17 - 11 + 15 = 21
         }
     }
@@ -1734,7 +2966,16 @@ mod tests {
                 .add_message(msg.into_cosmos_msg(contract1).unwrap());
         }
         assert_eq!(
-            migrate(deps.as_mut(), env, MigrateMsg { halflife: None }),
+            migrate(
+                deps.as_mut(),
+                env,
+                MigrateMsg {
+                    halflife: None,
+                    min_distribution: None,
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
+                }
+            ),
             Ok(resp)
         );
     }
@@ -1768,6 +3009,94 @@ mod tests {
             let new_member = query_member(deps.as_ref(), new_user, None).unwrap();
             assert_eq!(new_member.points, Some(10));
         }
+
+        #[test]
+        fn add_batch() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut());
+            let admin = mock_info(INIT_ADMIN, &[]);
+            execute_add_hook(deps.as_mut(), admin.clone(), "hook".to_owned()).unwrap();
+
+            let entries = vec![
+                (USER1.to_owned(), 4),
+                (USER3.to_owned(), 5),
+                (USER2.to_owned(), 0),
+                ("u".to_owned(), 3),
+                (USER1.to_owned(), 6),
+            ];
+            let res = execute_add_points_batch(deps.as_mut(), mock_env(), admin, entries).unwrap();
+            assert_users(&deps, Some(21), Some(6), Some(5), None);
+
+            let data: AddPointsBatchResponse = from_slice(&res.data.unwrap()).unwrap();
+            assert_eq!(data.applied, 3);
+            assert_eq!(data.rejected.len(), 2);
+            assert_eq!(
+                data.rejected[0],
+                RejectedPoints {
+                    addr: USER2.to_owned(),
+                    points: 0,
+                    error: ContractError::ZeroPoints {}.to_string(),
+                }
+            );
+            assert_eq!(
+                (data.rejected[1].addr.as_str(), data.rejected[1].points),
+                ("u", 3)
+            );
+
+            // single hook message with a diff per member
+            assert_eq!(res.messages.len(), 1);
+            let diff = MemberChangedHookMsg {
+                diffs: vec![
+                    MemberDiff::new(USER1, Some(USER1_POINTS), Some(21)),
+                    MemberDiff::new(USER3, None, Some(5)),
+                ],
+            };
+            assert_eq!(
+                res.messages[0],
+                SubMsg::new(diff.into_cosmos_msg("hook").unwrap())
+            );
+        }
+
+        #[test]
+        fn add_batch_checks() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut());
+
+            let entries = vec![(USER1.to_owned(), 4)];
+            let err =
+                execute_add_points_batch(deps.as_mut(), mock_env(), mock_info(USER1, &[]), entries)
+                    .unwrap_err();
+            assert_eq!(err, AdminError::NotAdmin {}.into());
+
+            let err = execute_add_points_batch(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                vec![],
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::EmptyPointsBatch {});
+
+            // overflowing entry is rejected, the rest applied
+            let entries = vec![(USER1.to_owned(), u64::MAX), (USER2.to_owned(), 1)];
+            let res = execute_add_points_batch(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                entries,
+            )
+            .unwrap();
+            let data: AddPointsBatchResponse = from_slice(&res.data.unwrap()).unwrap();
+            assert_eq!(data.applied, 1);
+            assert_eq!(data.rejected[0].addr, USER1);
+            assert_users(
+                &deps,
+                Some(USER1_POINTS),
+                Some(USER2_POINTS + 1),
+                None,
+                None,
+            );
+        }
     }
 
     #[test]
@@ -1777,7 +3106,7 @@ mod tests {
 
         let user1 = Addr::unchecked(USER1);
         SLASHERS
-            .add_slasher(&mut deps.storage, user1.clone())
+            .add_slasher(&mut deps.storage, user1.clone(), None)
             .unwrap();
 
         // Trying to slash nonexisting user will result in no-op
@@ -1794,4 +3123,86 @@ mod tests {
         .unwrap();
         assert_eq!(res, Response::new());
     }
+
+    #[test]
+    fn delegations_index_follows_delegation() {
+        let mut deps = mock_deps_tgrade();
+        do_instantiate(deps.as_mut());
+
+        let indexed = |deps: Deps<TgradeQuery>| -> Vec<Addr> {
+            DELEGATIONS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<_>>()
+                .unwrap()
+        };
+
+        execute_delegate_withdrawal(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER1, &[]),
+            USER3.to_owned(),
+            None,
+        )
+        .unwrap();
+        execute_delegate_withdrawal(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER2, &[]),
+            USER3.to_owned(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            indexed(deps.as_ref()),
+            vec![Addr::unchecked(USER1), Addr::unchecked(USER2)]
+        );
+
+        // Delegating back to self drops the owner from the index
+        execute_delegate_withdrawal(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER1, &[]),
+            USER1.to_owned(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(indexed(deps.as_ref()), vec![Addr::unchecked(USER2)]);
+
+        // Migration rebuilds the index from withdrawal adjustments
+        DELEGATIONS.remove(&mut deps.storage, &Addr::unchecked(USER2));
+        index_delegations(deps.as_mut()).unwrap();
+        assert_eq!(indexed(deps.as_ref()), vec![Addr::unchecked(USER2)]);
+    }
+
+    #[test]
+    fn streaming_requires_distribution_interval() {
+        let mut deps = mock_deps_tgrade();
+        let mut msg = InstantiateMsg {
+            admin: Some(INIT_ADMIN.into()),
+            members: vec![],
+            preauths_hooks: 0,
+            preauths_slashing: 0,
+            halflife: None,
+            denom: "usdc".to_owned(),
+            min_distribution: Uint128::zero(),
+            distribution_interval: None,
+            withdrawal_stream_period: Some(Duration::new(MAX_REWARDS_STREAMS * 10)),
+        };
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::StreamingWithoutInterval {
+                max_streams: MAX_REWARDS_STREAMS
+            }
+        );
+
+        msg.distribution_interval = Some(Duration::new(10));
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+    }
 }
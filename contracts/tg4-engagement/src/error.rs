@@ -29,4 +29,67 @@ pub enum ContractError {
 
     #[error("No members to distribute tokens to")]
     NoMembersToDistributeTo {},
+
+    #[error("Invalid voucher signer public key")]
+    InvalidVoucherSigner {},
+
+    #[error("Voucher claims are disabled")]
+    NoVoucherSigner {},
+
+    #[error("Invalid voucher signature")]
+    InvalidVoucherSignature {},
+
+    #[error("Voucher with nonce {0} already claimed")]
+    VoucherClaimed(u64),
+
+    #[error("Invalid merkle root, must be a 32 bytes sha256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("No merkle root registered for stage {0}")]
+    UnknownMerkleStage(u64),
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("Leaf {index} of merkle stage {stage} already claimed")]
+    MerkleClaimed { stage: u64, index: u64 },
+
+    #[error("Delegation expiration must be in the future")]
+    DelegationExpirationInPast {},
+
+    #[error("Points transfers are disabled")]
+    TransfersDisabled {},
+
+    #[error("Cannot transfer points to self")]
+    TransferToSelf {},
+
+    #[error("Address {0} is not a member")]
+    NotAMember(String),
+
+    #[error("Points to transfer must be greater than zero")]
+    ZeroTransfer {},
+
+    #[error("Insufficient points: {available} available, {required} required")]
+    InsufficientPoints { available: u64, required: u64 },
+
+    #[error("No pending transfer with id {0}")]
+    NoPendingTransfer(u64),
+
+    #[error("No pending suggestion with id {0}")]
+    NoPendingSuggestion(u64),
+
+    #[error("Pending suggestion {0} was made by an oracle which is no longer set")]
+    StaleSuggestion(u64),
+
+    #[error("Oracle budget points and period must be greater than zero")]
+    InvalidOracleBudget {},
+
+    #[error("Points batch is empty")]
+    EmptyPointsBatch {},
+
+    #[error("Points to add must be greater than zero")]
+    ZeroPoints {},
+
+    #[error("Withdrawal streaming requires a distribution interval of at least 1/{max_streams} of the stream period")]
+    StreamingWithoutInterval { max_streams: u64 },
 }
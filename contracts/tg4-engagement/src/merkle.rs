@@ -0,0 +1,27 @@
+use sha2::{Digest, Sha256};
+
+/// Hash of a single `(index, addr, points)` leaf of the points distribution tree: sha256 of
+/// big-endian `index`, big-endian `points` and the address bytes, concatenated.
+pub fn leaf_hash(index: u64, addr: &str, points: u64) -> [u8; 32] {
+    Sha256::new()
+        .chain(index.to_be_bytes())
+        .chain(points.to_be_bytes())
+        .chain(addr.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// Hash of two sibling nodes. Nodes are sorted before hashing, so proofs don't need to carry
+/// the side of each sibling.
+pub fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new().chain(first).chain(second).finalize().into()
+}
+
+/// Verifies that `leaf` belongs to the tree with the given `root`.
+pub fn verify_proof(root: &[u8], leaf: [u8; 32], proof: &[impl AsRef<[u8]>]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling.as_ref()));
+    computed == root
+}
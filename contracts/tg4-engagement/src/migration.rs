@@ -1,12 +1,16 @@
-use cosmwasm_std::{Deps, DepsMut, Order, StdResult};
+use cosmwasm_std::{Deps, DepsMut, Empty, Order, StdResult};
 
 use tg4::{MemberChangedHookMsg, MemberDiff, MemberInfo};
 use tg_bindings::TgradeQuery;
 use tg_utils::members;
 
+use crate::contract::validate_withdrawal_stream;
 use crate::error::ContractError;
 use crate::msg::MigrateMsg;
-use crate::state::{Halflife, HALFLIFE};
+use crate::state::{
+    Halflife, DELEGATIONS, DISTRIBUTION_THROTTLE, HALFLIFE, WITHDRAWAL_STREAM_PERIOD,
+    WITHDRAW_ADJUSTMENT,
+};
 
 pub(crate) fn migrate_config(
     deps: DepsMut<TgradeQuery>,
@@ -26,6 +30,50 @@ pub(crate) fn migrate_config(
             })
         })?;
     }
+
+    if msg.min_distribution.is_some() || msg.distribution_interval.is_some() {
+        let mut throttle = DISTRIBUTION_THROTTLE
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        if let Some(min_amount) = msg.min_distribution {
+            throttle.min_amount = min_amount;
+        }
+        if let Some(interval) = msg.distribution_interval {
+            // Zero duration removes the interval limit
+            throttle.min_interval = if interval.seconds() > 0 {
+                Some(interval)
+            } else {
+                None
+            };
+        }
+        DISTRIBUTION_THROTTLE.save(deps.storage, &throttle)?;
+    }
+
+    if let Some(period) = msg.withdrawal_stream_period {
+        // Zero duration disables streaming, distributions already streaming keep unlocking
+        if period.seconds() > 0 {
+            WITHDRAWAL_STREAM_PERIOD.save(deps.storage, &period)?;
+        } else {
+            WITHDRAWAL_STREAM_PERIOD.remove(deps.storage);
+        }
+    }
+    validate_withdrawal_stream(deps.storage)?;
+    Ok(())
+}
+
+/// Fills the delegations index with withdrawal delegations made before it existed
+pub(crate) fn index_delegations(deps: DepsMut<TgradeQuery>) -> StdResult<()> {
+    let owners: Vec<_> = WITHDRAW_ADJUSTMENT
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((owner, data)) if data.delegated == owner => None,
+            Ok((owner, _)) => Some(Ok(owner)),
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<_>>()?;
+    for owner in owners {
+        DELEGATIONS.save(deps.storage, &owner, &Empty {})?;
+    }
     Ok(())
 }
 
@@ -1,11 +1,15 @@
-use cosmwasm_std::{Addr, Coin, Decimal, Timestamp};
+use cosmwasm_std::{to_vec, Addr, Binary, Coin, Decimal, StdResult, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use tg4::Member;
+use tg4::{Member, MemberDiff};
 use tg_bindings::{Evidence, PrivilegeChangeMsg};
 use tg_utils::Duration;
 
+use crate::i128::Int128;
+use crate::state::{OracleBudget, TransferPolicy, VoucherSigner};
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
@@ -20,6 +24,18 @@ pub struct InstantiateMsg {
     pub halflife: Option<Duration>,
     /// Denom of tokens which may be distributed by this contract.
     pub denom: String,
+    /// Minimal undistributed amount for `DistributeRewards` to actually distribute. Smaller
+    /// amounts are kept in the contract until they grow enough.
+    #[serde(default)]
+    pub min_distribution: Uint128,
+    /// Minimal time between two distributions. Calls in between are deferred.
+    #[serde(default)]
+    pub distribution_interval: Option<Duration>,
+    /// Period over which every distribution unlocks linearly for withdrawal. Distributed
+    /// rewards are withdrawable immediately if not set. Requires `distribution_interval` of at
+    /// least 1/`MAX_REWARDS_STREAMS` of the period.
+    #[serde(default)]
+    pub withdrawal_stream_period: Option<Duration>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -35,13 +51,19 @@ pub enum ExecuteMsg {
     },
     /// Add points to member's address
     AddPoints { addr: String, points: u64 },
+    /// Adds points to many addresses at once, with a single total points update and a single
+    /// hook message. Invalid entries are skipped and reported in `AddPointsBatchResponse` data,
+    /// points of an address appearing more than once are summed. Must be called by Admin.
+    AddPointsBatch { entries: Vec<(String, u64)> },
     /// Add a new hook to be informed of all membership changes. Must be called by Admin
     AddHook { addr: String },
     /// Remove a hook. Must be called by Admin
     RemoveHook { addr: String },
     /// Distributes rewards sent with this message, and all rewards transferred since last call of this
     /// to members, proportionally to their points. Rewards are not immediately send to members, but
-    /// assigned to them for later withdrawal (see: `ExecuteMsg::WithdrawFunds`)
+    /// assigned to them for later withdrawal (see: `ExecuteMsg::WithdrawFunds`).
+    /// If the undistributed amount is below `min_distribution`, or `distribution_interval` didn't
+    /// pass since the last distribution, the rewards are kept for a later call instead.
     DistributeRewards {
         /// Original source of rewards, informational. If present overwrites "sender" field on
         /// propagated event.
@@ -63,13 +85,94 @@ pub enum ExecuteMsg {
         /// Account delegated for withdrawal. To disallow current withdrawal, the best is to set it
         /// to own address.
         delegated: String,
+        /// Time after which the delegation automatically reverts to the sender. Never expires if
+        /// not set.
+        #[serde(default)]
+        expires: Option<Timestamp>,
+    },
+    /// Adds slasher for contract if there are enough `slasher_preauths` left.
+    /// `max_portion` limits how much the slasher can slash at once, up to 100% if not set.
+    AddSlasher {
+        addr: String,
+        #[serde(default)]
+        max_portion: Option<Decimal>,
     },
-    /// Adds slasher for contract if there are enough `slasher_preauths` left
-    AddSlasher { addr: String },
     /// Removes slasher for contract
     RemoveSlasher { addr: String },
     /// Slash engagement points from address
     Slash { addr: String, portion: Decimal },
+    /// Sets the key vouchers have to be signed with. `None` disables voucher claims.
+    /// Must be called by Admin
+    SetVoucherSigner { signer: Option<VoucherSigner> },
+    /// Adds points from a voucher signed by the voucher signer. Anyone can submit a voucher,
+    /// points are always added to the address in the voucher.
+    ClaimPoints {
+        voucher: Voucher,
+        /// Signature of `VoucherSignDoc::hash`
+        signature: Binary,
+    },
+    /// Commits a root of a merkle tree of `(index, addr, points)` leaves, opening a new claim
+    /// stage. Must be called by Admin
+    RegisterMerkleRoot { merkle_root: Binary },
+    /// Adds points from a leaf of the merkle tree registered for `stage`. Anyone can submit a
+    /// claim, points are always added to the address in the leaf.
+    ClaimMerklePoints {
+        stage: u64,
+        index: u64,
+        addr: String,
+        points: u64,
+        /// Sibling hashes on the path from the leaf to the root
+        proof: Vec<Binary>,
+    },
+    /// Sets the policy of points transfers between members. Must be called by Admin
+    SetTransferPolicy { policy: TransferPolicy },
+    /// Transfers part of sender's points to another member. Executed immediately with the
+    /// `free` policy, and only registered for the admin approval with the `admin_approved` one.
+    TransferPoints { to: String, points: u64 },
+    /// Executes pending points transfer. Must be called by Admin
+    ApproveTransfer { id: u64 },
+    /// Drops pending points transfer. Can be called by Admin or the transfer sender
+    RejectTransfer { id: u64 },
+    /// Sets the external contract allowed to suggest points, and its budget of suggestions
+    /// applied without approval. Removes the oracle if not set. Must be called by Admin
+    SetPointsOracle {
+        oracle: Option<String>,
+        budget: Option<OracleBudget>,
+    },
+    /// Suggests adding points to members. Entries fitting in the remaining budget of the
+    /// current budget epoch are applied right away, the rest is queued for the admin approval.
+    /// Returns `SuggestPointsResponse` data. Must be called by the points oracle
+    SuggestPoints { entries: Vec<(String, u64)> },
+    /// Applies pending suggestions. Must be called by Admin
+    ApproveSuggestions { ids: Vec<u64> },
+    /// Drops pending suggestions. Must be called by Admin
+    RejectSuggestions { ids: Vec<u64> },
+}
+
+/// Points claim signed off-chain by the voucher signer
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Voucher {
+    /// Member to receive the points
+    pub addr: String,
+    pub points: u64,
+    /// Unique voucher identifier. Every nonce can be claimed only once
+    pub nonce: u64,
+}
+
+/// Document the voucher signer signs. Chain id and contract address are included, so a voucher
+/// is valid only on a single contract instance.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoucherSignDoc {
+    pub chain_id: String,
+    pub contract: String,
+    pub voucher: Voucher,
+}
+
+impl VoucherSignDoc {
+    /// sha256 of JSON-encoded sign doc, which is what the signature is verified against
+    pub fn hash(&self) -> StdResult<Vec<u8>> {
+        Ok(Sha256::digest(&to_vec(self)?).to_vec())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -77,8 +180,9 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Return AdminResponse
     Admin {},
-    /// Return TotalPointsResponse
-    TotalPoints {},
+    /// Return TotalPointsResponse. If at_height is set, returns total points at the
+    /// beginning of the given block.
+    TotalPoints { at_height: Option<u64> },
     /// Returns MemberListResponse
     ListMembers {
         start_after: Option<String>,
@@ -89,6 +193,14 @@ pub enum QueryMsg {
         start_after: Option<Member>,
         limit: Option<u32>,
     },
+    /// Returns MemberListResponse of members with points between `min` and `max` (both
+    /// inclusive, unbounded if not set), sorted by points descending
+    ListMembersByPointsRange {
+        min: Option<u64>,
+        max: Option<u64>,
+        start_after: Option<Member>,
+        limit: Option<u32>,
+    },
     /// Returns MemberResponse
     Member {
         addr: String,
@@ -101,6 +213,9 @@ pub enum QueryMsg {
     /// Return how many rewards are assigned for withdrawal from the given address. Returns
     /// `RewardsResponse`.
     WithdrawableRewards { owner: String },
+    /// Return how many rewards assigned to the given address are still unlocking, and cannot
+    /// be withdrawn yet. Returns `RewardsResponse`.
+    LockedRewards { owner: String },
     /// Return how many rewards were distributed in total by this contract. Returns
     /// `RewardsResponse`.
     DistributedRewards {},
@@ -109,6 +224,12 @@ pub enum QueryMsg {
     UndistributedRewards {},
     /// Return address allowed for withdrawal of the funds assigned to owner. Returns `DelegateResponse`
     Delegated { owner: String },
+    /// Lists owners with active (not expired) withdrawal delegations to other addresses.
+    /// Returns `DelegationsResponse`
+    ListDelegations {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns information about the half-life, including the duration in seconds, the last
     /// and the next occurrence.
     Halflife {},
@@ -116,10 +237,54 @@ pub enum QueryMsg {
     IsSlasher { addr: String },
     /// Returns all active slashers as a vector of addresses
     ListSlashers {},
+    /// Returns the maximum portion (Option<Decimal>) the address can slash at once, `None` if
+    /// it is not a slasher
+    SlasherLimit { addr: String },
     /// Returns rewards distribution data
     DistributionData {},
+    /// Returns DistributionThrottleResponse
+    DistributionThrottle {},
+    /// Returns WithdrawalStreamResponse
+    WithdrawalStream {},
     /// Returns withdraw adjustment data
     WithdrawAdjustmentData { addr: String },
+    /// Returns VoucherSignerResponse
+    VoucherSigner {},
+    /// Returns information (bool) about whether the voucher with given nonce was already claimed
+    IsVoucherClaimed { nonce: u64 },
+    /// Returns MerkleRootResponse
+    MerkleRoot { stage: u64 },
+    /// Returns LatestMerkleStageResponse
+    LatestMerkleStage {},
+    /// Returns information (bool) about whether the leaf with given index of the merkle stage
+    /// was already claimed
+    IsMerkleClaimed { stage: u64, index: u64 },
+    /// Returns TransferPolicyResponse
+    TransferPolicy {},
+    /// Returns PendingTransfersResponse
+    ListPendingTransfers {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns PointsOracleResponse
+    PointsOracle {},
+    /// Returns PendingSuggestionsResponse
+    ListPendingSuggestions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists snapshots of past distributions by their sequence numbers, ascending.
+    /// Returns DistributionSnapshotsResponse
+    ListDistributionSnapshots {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Previews the effects of `ExecuteMsg::UpdateMembers` with the same arguments, without
+    /// executing it. Returns SimulateUpdateMembersResponse
+    SimulateUpdateMembers {
+        add: Vec<Member>,
+        remove: Vec<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -155,9 +320,41 @@ pub struct RewardsResponse {
     pub rewards: Coin,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DistributionThrottleResponse {
+    pub min_distribution: Uint128,
+    pub distribution_interval: Option<Duration>,
+    pub last_distribution: Option<Timestamp>,
+    /// Earliest time of the next distribution, `None` if not limited by time
+    pub next_distribution: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct WithdrawalStreamResponse {
+    /// Period over which distributions unlock, `None` if withdrawals are not streamed
+    pub period: Option<Duration>,
+    /// Number of distributions still unlocking
+    pub active_streams: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct DelegatedResponse {
     pub delegated: Addr,
+    /// Time after which the delegation reverts to the owner
+    #[serde(default)]
+    pub expires: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegationInfo {
+    pub owner: Addr,
+    pub delegated: Addr,
+    pub expires: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegationsResponse {
+    pub delegations: Vec<DelegationInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -183,10 +380,139 @@ pub struct ListSlashersResponse {
     pub slashers: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoucherSignerResponse {
+    pub signer: Option<VoucherSigner>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MerkleRootResponse {
+    /// `None` if no root was registered for the stage
+    pub root: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct LatestMerkleStageResponse {
+    /// Zero if no root was registered yet
+    pub stage: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct TransferPolicyResponse {
+    pub policy: TransferPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingTransferInfo {
+    pub id: u64,
+    pub from: Addr,
+    pub to: Addr,
+    pub points: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingTransfersResponse {
+    pub transfers: Vec<PendingTransferInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PointsOracleResponse {
+    pub oracle: Option<Addr>,
+    pub budget: Option<OracleBudget>,
+    /// Points of suggestions applied in the current budget epoch
+    pub budget_used: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingSuggestionInfo {
+    pub id: u64,
+    pub addr: Addr,
+    pub points: u64,
+    /// Oracle which made the suggestion
+    pub oracle: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingSuggestionsResponse {
+    pub suggestions: Vec<PendingSuggestionInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SuggestPointsResponse {
+    /// Number of entries applied within the budget
+    pub applied: u32,
+    /// Ids of entries queued for the admin approval
+    pub pending: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DistributionSnapshotInfo {
+    /// Sequence number of the distribution, starting from 1
+    pub seq: u64,
+    pub denom: String,
+    pub amount: Uint128,
+    /// Total points of members at the time of the distribution
+    pub total_points: u64,
+    /// Shares per point added by the distribution
+    pub shares_per_point: Uint128,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DistributionSnapshotsResponse {
+    pub snapshots: Vec<DistributionSnapshotInfo>,
+}
+
+/// Change of the withdraw adjustment of a single member
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct AdjustmentDiff {
+    pub addr: Addr,
+    /// Shares correction before the update, `None` if the address has no adjustment yet
+    pub old: Option<Int128>,
+    pub new: Int128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct AddPointsBatchResponse {
+    /// Number of entries applied
+    pub applied: u32,
+    /// Entries which were skipped, in the order of the batch
+    pub rejected: Vec<RejectedPoints>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RejectedPoints {
+    pub addr: String,
+    pub points: u64,
+    /// Why the entry was skipped
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SimulateUpdateMembersResponse {
+    /// Diffs which would be sent to the registered hooks
+    pub diffs: Vec<MemberDiff>,
+    /// Total points after the update
+    pub total_points: u64,
+    /// Withdraw adjustments changed by the update, sorted by address
+    pub adjustments: Vec<AdjustmentDiff>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct MigrateMsg {
     pub halflife: Option<Duration>,
+    /// Updates the minimal distributed amount
+    #[serde(default)]
+    pub min_distribution: Option<Uint128>,
+    /// Updates the minimal time between distributions. Zero duration removes the limit.
+    #[serde(default)]
+    pub distribution_interval: Option<Duration>,
+    /// Updates the withdrawal stream period. Zero duration disables streaming of future
+    /// distributions.
+    #[serde(default)]
+    pub withdrawal_stream_period: Option<Duration>,
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@ use crate::error::ContractError;
 use cosmwasm_std::{coin, coins, Decimal, Event};
 use suite::{expected_members, SuiteBuilder};
 use tg4::Member;
-use tg_utils::{Duration, PreauthError};
+use tg_utils::{Duration, PreauthError, SlasherError};
 
 /// Helper constructor for a member
 fn member(addr: &str, points: u64) -> Member {
@@ -17,6 +17,8 @@ fn member(addr: &str, points: u64) -> Member {
 
 mod funds_distribution {
     use super::*;
+    use crate::msg::DelegationInfo;
+    use cosmwasm_std::Addr;
 
     fn distribution_event(sender: &str, denom: &str, amount: u128) -> Event {
         Event::new("wasm")
@@ -559,6 +561,73 @@ mod funds_distribution {
         assert_eq!(suite.token_balance(&members[2]).unwrap(), 0);
     }
 
+    #[test]
+    fn funds_withdrawal_delegation_expires() {
+        let members = vec![
+            "member1".to_owned(),
+            "member2".to_owned(),
+            "member3".to_owned(),
+        ];
+
+        let mut suite = SuiteBuilder::new()
+            .with_member(&members[0], 4)
+            .with_member(&members[1], 6)
+            .with_funds(&members[2], 100)
+            .build();
+
+        let denom = suite.denom.clone();
+        let now = suite.app.block_info().time;
+
+        // Expiration has to be in the future
+        let err = suite
+            .delegate_withdrawal_until(&members[1], &members[0], now)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::DelegationExpirationInPast {},
+            err.downcast().unwrap()
+        );
+
+        let expires = now.plus_seconds(100);
+        suite
+            .delegate_withdrawal_until(&members[1], &members[0], expires)
+            .unwrap();
+        assert_eq!(
+            suite.delegated(&members[1]).unwrap().as_str(),
+            members[0].as_str()
+        );
+        assert_eq!(
+            suite.list_delegations().unwrap(),
+            vec![DelegationInfo {
+                owner: Addr::unchecked(&members[1]),
+                delegated: Addr::unchecked(&members[0]),
+                expires: Some(expires),
+            }]
+        );
+
+        suite
+            .distribute_funds(&members[2], None, &coins(100, denom))
+            .unwrap();
+
+        // After expiration delegation reverts to the owner
+        suite.app.advance_seconds(100);
+        assert_eq!(
+            suite.delegated(&members[1]).unwrap().as_str(),
+            members[1].as_str()
+        );
+        assert_eq!(suite.list_delegations().unwrap(), vec![]);
+
+        let err = suite
+            .withdraw_funds(&members[0], members[1].as_str(), None)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized("Sender is neither owner or delegated".to_owned()),
+            err.downcast().unwrap()
+        );
+
+        suite.withdraw_funds(&members[1], None, None).unwrap();
+        assert_eq!(suite.token_balance(&members[1]).unwrap(), 60);
+    }
+
     #[test]
     fn querying_unknown_address() {
         let suite = SuiteBuilder::new().with_denom("usdc").build();
@@ -568,6 +637,363 @@ mod funds_distribution {
     }
 }
 
+mod distribution_throttle {
+    use super::*;
+
+    #[test]
+    fn small_amounts_are_deferred() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_member("member2", 1)
+            .with_funds("member3", 100)
+            .with_min_distribution(50)
+            .build();
+
+        let denom = suite.denom.clone();
+
+        let resp = suite
+            .distribute_funds("member3", None, &coins(30, &denom))
+            .unwrap();
+        resp.assert_event(
+            &Event::new("wasm")
+                .add_attribute("action", "distribute_rewards_deferred")
+                .add_attribute("amount", "30")
+                .add_attribute("min_distribution", "50"),
+        );
+        assert_eq!(suite.distributed_funds().unwrap(), coin(0, &denom));
+        assert_eq!(suite.undistributed_funds().unwrap(), coin(30, &denom));
+
+        // Deferred funds are distributed together with later ones
+        suite
+            .distribute_funds("member3", None, &coins(30, &denom))
+            .unwrap();
+        assert_eq!(suite.distributed_funds().unwrap(), coin(60, &denom));
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(30, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("member2").unwrap(),
+            coin(30, &denom)
+        );
+    }
+
+    #[test]
+    fn distributions_are_spaced_by_interval() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_funds("member2", 300)
+            .with_distribution_interval(Duration::new(100))
+            .build();
+
+        let denom = suite.denom.clone();
+
+        suite
+            .distribute_funds("member2", None, &coins(100, &denom))
+            .unwrap();
+        assert_eq!(suite.distributed_funds().unwrap(), coin(100, &denom));
+
+        let throttle = suite.distribution_throttle().unwrap();
+        let last = throttle.last_distribution.unwrap();
+        let next = last.plus_seconds(100);
+        assert_eq!(throttle.next_distribution, Some(next));
+
+        suite.app.advance_seconds(50);
+        let resp = suite
+            .distribute_funds("member2", None, &coins(100, &denom))
+            .unwrap();
+        resp.assert_event(
+            &Event::new("wasm")
+                .add_attribute("action", "distribute_rewards_deferred")
+                .add_attribute("amount", "100")
+                .add_attribute("next_distribution", next.seconds().to_string()),
+        );
+        assert_eq!(suite.distributed_funds().unwrap(), coin(100, &denom));
+
+        suite.app.advance_seconds(50);
+        suite
+            .distribute_funds("member2", None, &coins(100, &denom))
+            .unwrap();
+        assert_eq!(suite.distributed_funds().unwrap(), coin(300, &denom));
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(300, &denom)
+        );
+    }
+}
+
+mod distribution_snapshots {
+    use super::*;
+    use crate::msg::DistributionSnapshotInfo;
+    use cosmwasm_std::Uint128;
+
+    #[test]
+    fn distributions_are_recorded() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_member("member2", 2)
+            .with_funds("member3", 300)
+            .build();
+
+        let denom = suite.denom.clone();
+        assert_eq!(suite.list_distribution_snapshots(None, None).unwrap(), []);
+
+        suite
+            .distribute_funds("member3", None, &coins(100, &denom))
+            .unwrap();
+        let first_block = suite.app.block_info();
+        suite.app.advance_seconds(50);
+        let admin = suite.admin().to_owned();
+        suite
+            .modify_members(&admin, &[("member1", 3)], &[])
+            .unwrap();
+        let resp = suite
+            .distribute_funds("member3", None, &coins(200, &denom))
+            .unwrap();
+        resp.assert_event(
+            &Event::new("wasm")
+                .add_attribute("action", "distribute_rewards")
+                .add_attribute("seq", "2"),
+        );
+        let second_block = suite.app.block_info();
+
+        let snapshots = suite.list_distribution_snapshots(None, None).unwrap();
+        assert_eq!(
+            snapshots,
+            [
+                DistributionSnapshotInfo {
+                    seq: 1,
+                    denom: denom.clone(),
+                    amount: Uint128::new(100),
+                    total_points: 3,
+                    shares_per_point: Uint128::new((100 << 32) / 3),
+                    height: first_block.height,
+                    time: first_block.time,
+                },
+                DistributionSnapshotInfo {
+                    seq: 2,
+                    denom,
+                    amount: Uint128::new(200),
+                    total_points: 5,
+                    shares_per_point: Uint128::new(((200 << 32) + (100 << 32) % 3) / 5),
+                    height: second_block.height,
+                    time: second_block.time,
+                },
+            ]
+        );
+
+        assert_eq!(
+            suite.list_distribution_snapshots(1, None).unwrap(),
+            snapshots[1..]
+        );
+        assert_eq!(
+            suite.list_distribution_snapshots(None, 1).unwrap(),
+            snapshots[..1]
+        );
+    }
+}
+
+mod withdrawal_streaming {
+    use super::*;
+    use crate::msg::{MigrateMsg, WithdrawalStreamResponse};
+    use crate::state::MAX_REWARDS_STREAMS;
+
+    #[test]
+    fn rewards_unlock_linearly() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_member("member2", 3)
+            .with_funds("member3", 400)
+            .with_distribution_interval(Duration::new(10))
+            .with_withdrawal_stream_period(Duration::new(100))
+            .build();
+
+        let denom = suite.denom.clone();
+
+        suite
+            .distribute_funds("member3", None, &coins(400, &denom))
+            .unwrap();
+        assert_eq!(suite.distributed_funds().unwrap(), coin(400, &denom));
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(0, &denom)
+        );
+        assert_eq!(suite.locked_rewards("member1").unwrap(), coin(100, &denom));
+
+        suite.app.advance_seconds(25);
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(25, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("member2").unwrap(),
+            coin(75, &denom)
+        );
+        assert_eq!(suite.locked_rewards("member2").unwrap(), coin(225, &denom));
+
+        suite.withdraw_funds("member2", None, None).unwrap();
+        assert_eq!(suite.token_balance("member2").unwrap(), 75);
+        assert_eq!(
+            suite.withdrawable_rewards("member2").unwrap(),
+            coin(0, &denom)
+        );
+        assert_eq!(suite.withdrawal_stream().unwrap().active_streams, 1);
+
+        suite.app.advance_seconds(75);
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(100, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("member2").unwrap(),
+            coin(225, &denom)
+        );
+        assert_eq!(suite.locked_rewards("member1").unwrap(), coin(0, &denom));
+        assert_eq!(
+            suite.withdrawal_stream().unwrap(),
+            WithdrawalStreamResponse {
+                period: Some(Duration::new(100)),
+                active_streams: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn streams_use_points_at_distribution() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_funds("member3", 200)
+            .with_distribution_interval(Duration::new(10))
+            .with_withdrawal_stream_period(Duration::new(100))
+            .build();
+        let admin = suite.admin().to_owned();
+
+        let denom = suite.denom.clone();
+
+        suite
+            .distribute_funds("member3", None, &coins(100, &denom))
+            .unwrap();
+
+        // Member joining later gets no part of the ongoing stream
+        suite.app.advance_seconds(50);
+        suite
+            .modify_members(&admin, &[("member2", 1)], &[])
+            .unwrap();
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(50, &denom)
+        );
+        assert_eq!(suite.locked_rewards("member2").unwrap(), coin(0, &denom));
+
+        suite
+            .distribute_funds("member3", None, &coins(100, &denom))
+            .unwrap();
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(50, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("member2").unwrap(),
+            coin(0, &denom)
+        );
+
+        suite.app.advance_seconds(100);
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(150, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("member2").unwrap(),
+            coin(50, &denom)
+        );
+    }
+
+    #[test]
+    fn active_streams_are_capped() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_funds("member3", 3100)
+            .with_distribution_interval(Duration::new(100))
+            .with_withdrawal_stream_period(Duration::new(3000))
+            .build();
+        let admin = suite.admin().to_owned();
+
+        let denom = suite.denom.clone();
+
+        for i in 0..MAX_REWARDS_STREAMS {
+            if i > 0 {
+                suite.app.advance_seconds(100);
+            }
+            suite
+                .distribute_funds("member3", None, &coins(100, &denom))
+                .unwrap();
+        }
+        assert_eq!(
+            suite.withdrawal_stream().unwrap().active_streams,
+            MAX_REWARDS_STREAMS
+        );
+
+        // Shortening the period doesn't shorten streams already unlocking
+        suite
+            .migrate(
+                &admin,
+                &MigrateMsg {
+                    halflife: None,
+                    min_distribution: None,
+                    distribution_interval: Some(Duration::new(10)),
+                    withdrawal_stream_period: Some(Duration::new(300)),
+                },
+            )
+            .unwrap();
+
+        suite.app.advance_seconds(10);
+        let resp = suite
+            .distribute_funds("member3", None, &coins(100, &denom))
+            .unwrap();
+        resp.assert_event(
+            &Event::new("wasm")
+                .add_attribute("action", "distribute_rewards_deferred")
+                .add_attribute("amount", "100")
+                .add_attribute("active_streams", MAX_REWARDS_STREAMS.to_string()),
+        );
+        assert_eq!(suite.distributed_funds().unwrap(), coin(3000, &denom));
+
+        // Once the oldest stream is unlocked, deferred funds are distributed
+        suite.app.advance_seconds(90);
+        suite.distribute_funds("member3", None, &[]).unwrap();
+        assert_eq!(suite.distributed_funds().unwrap(), coin(3100, &denom));
+        assert_eq!(
+            suite.withdrawal_stream().unwrap().active_streams,
+            MAX_REWARDS_STREAMS
+        );
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 1)
+            .with_funds("member3", 100)
+            .build();
+
+        let denom = suite.denom.clone();
+
+        suite
+            .distribute_funds("member3", None, &coins(100, &denom))
+            .unwrap();
+        assert_eq!(
+            suite.withdrawable_rewards("member1").unwrap(),
+            coin(100, &denom)
+        );
+        assert_eq!(
+            suite.withdrawal_stream().unwrap(),
+            WithdrawalStreamResponse {
+                period: None,
+                active_streams: 0,
+            }
+        );
+    }
+}
+
 mod slashing {
     use super::*;
 
@@ -760,31 +1186,90 @@ mod slashing {
     }
 
     #[test]
-    fn non_admin_cant_add_slasher_without_preauth() {
-        // Add then remove slasher by himself. Then ensure that the removed slasher can't slash
-        let members = vec!["member1", "member2"];
+    fn slasher_limit_is_enforced() {
+        let members = ["member1", "member2", "member3"];
 
-        let mut suite = SuiteBuilder::new().with_member(members[0], 10).build();
+        let mut suite = SuiteBuilder::new().with_member(members[0], 100).build();
 
-        let err = suite.add_slasher(members[0], members[1]).unwrap_err();
+        let admin = suite.owner.clone();
 
+        suite
+            .add_limited_slasher(admin.as_ref(), members[1], Decimal::percent(20))
+            .unwrap();
+        suite.add_slasher(admin.as_ref(), members[2]).unwrap();
         assert_eq!(
-            ContractError::Preauth(PreauthError::NoPreauth {}),
+            suite.slasher_limit(members[1]).unwrap(),
+            Some(Decimal::percent(20))
+        );
+        assert_eq!(
+            suite.slasher_limit(members[2]).unwrap(),
+            Some(Decimal::one())
+        );
+        assert_eq!(suite.slasher_limit(members[0]).unwrap(), None);
+
+        // Limit itself has to be a valid portion
+        let err = suite
+            .add_limited_slasher(admin.as_ref(), members[0], Decimal::zero())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Slashing(SlasherError::InvalidPortion(Decimal::zero())),
             err.downcast().unwrap()
         );
-    }
 
-    #[test]
-    fn add_slasher_with_preauth() {
-        // Initialize two members with equal points of 10. Slash one of members. Ensure proper
-        // points. Perform distribution and withdraw, ensure proper payouts.
-        let members = vec!["member1", "member2", "member3"];
+        let err = suite
+            .slash(members[1], members[0], Decimal::percent(50))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Slashing(SlasherError::PortionAboveLimit {
+                portion: Decimal::percent(50),
+                max: Decimal::percent(20),
+            }),
+            err.downcast().unwrap()
+        );
 
-        let mut suite = SuiteBuilder::new()
-            .with_member(members[0], 10)
-            .with_member(members[1], 10)
-            .with_funds(members[2], 600)
-            .with_preaths_slashing(1)
+        suite
+            .slash(members[1], members[0], Decimal::percent(20))
+            .unwrap();
+        suite
+            .slash(members[2], members[0], Decimal::percent(50))
+            .unwrap();
+        assert_eq!(suite.members().unwrap(), vec![member(members[0], 40)]);
+
+        // Limit is dropped together with the slasher
+        suite.remove_slasher(admin.as_ref(), members[1]).unwrap();
+        suite.add_slasher(admin.as_ref(), members[1]).unwrap();
+        assert_eq!(
+            suite.slasher_limit(members[1]).unwrap(),
+            Some(Decimal::one())
+        );
+    }
+
+    #[test]
+    fn non_admin_cant_add_slasher_without_preauth() {
+        // Add then remove slasher by himself. Then ensure that the removed slasher can't slash
+        let members = vec!["member1", "member2"];
+
+        let mut suite = SuiteBuilder::new().with_member(members[0], 10).build();
+
+        let err = suite.add_slasher(members[0], members[1]).unwrap_err();
+
+        assert_eq!(
+            ContractError::Preauth(PreauthError::NoPreauth {}),
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn add_slasher_with_preauth() {
+        // Initialize two members with equal points of 10. Slash one of members. Ensure proper
+        // points. Perform distribution and withdraw, ensure proper payouts.
+        let members = vec!["member1", "member2", "member3"];
+
+        let mut suite = SuiteBuilder::new()
+            .with_member(members[0], 10)
+            .with_member(members[1], 10)
+            .with_funds(members[2], 600)
+            .with_preaths_slashing(1)
             .build();
 
         let denom = suite.denom.clone();
@@ -922,9 +1407,673 @@ mod slashing {
     }
 }
 
+mod vouchers {
+    use super::suite::Suite;
+    use super::*;
+    use crate::msg::{Voucher, VoucherSignDoc};
+    use crate::state::{VoucherKeyType, VoucherSigner};
+    use cosmwasm_std::Binary;
+    use ed25519_zebra::{SigningKey, VerificationKey};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from([7u8; 32])
+    }
+
+    fn signer() -> VoucherSigner {
+        let pubkey: [u8; 32] = VerificationKey::from(&signing_key()).into();
+        VoucherSigner {
+            key_type: VoucherKeyType::Ed25519,
+            pubkey: Binary::from(pubkey.to_vec()),
+        }
+    }
+
+    fn sign_for(chain_id: &str, contract: &str, voucher: &Voucher) -> Binary {
+        let hash = VoucherSignDoc {
+            chain_id: chain_id.to_owned(),
+            contract: contract.to_owned(),
+            voucher: voucher.clone(),
+        }
+        .hash()
+        .unwrap();
+        let signature: [u8; 64] = signing_key().sign(&hash).into();
+        Binary::from(signature.to_vec())
+    }
+
+    fn sign(suite: &Suite, voucher: &Voucher) -> Binary {
+        sign_for(
+            &suite.app.block_info().chain_id,
+            suite.contract.as_str(),
+            voucher,
+        )
+    }
+
+    fn voucher(addr: &str, points: u64, nonce: u64) -> Voucher {
+        Voucher {
+            addr: addr.to_owned(),
+            points,
+            nonce,
+        }
+    }
+
+    #[test]
+    fn claim_points_with_voucher() {
+        let mut suite = SuiteBuilder::new().with_member("member1", 5).build();
+        let admin = suite.admin().to_owned();
+        suite.set_voucher_signer(&admin, signer()).unwrap();
+
+        let first = voucher("member1", 3, 1);
+        let second = voucher("member2", 4, 2);
+        let first_sig = sign(&suite, &first);
+        let second_sig = sign(&suite, &second);
+        suite.claim_points("relayer", first, first_sig).unwrap();
+        suite.claim_points("member2", second, second_sig).unwrap();
+
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 8), ("member2", 4)])
+        );
+        assert!(suite.is_voucher_claimed(1).unwrap());
+        assert!(!suite.is_voucher_claimed(3).unwrap());
+    }
+
+    #[test]
+    fn voucher_cannot_be_replayed() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+        suite.set_voucher_signer(&admin, signer()).unwrap();
+
+        let claim = voucher("member1", 3, 1);
+        let signature = sign(&suite, &claim);
+        suite
+            .claim_points("member1", claim.clone(), signature.clone())
+            .unwrap();
+        let err = suite.claim_points("member1", claim, signature).unwrap_err();
+
+        assert_eq!(ContractError::VoucherClaimed(1), err.downcast().unwrap());
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 3)])
+        );
+    }
+
+    #[test]
+    fn tampered_voucher_is_rejected() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+        suite.set_voucher_signer(&admin, signer()).unwrap();
+
+        let claim = voucher("member1", 3, 1);
+        let signature = sign(&suite, &claim);
+        let tampered = Voucher {
+            points: 300,
+            ..claim
+        };
+        let err = suite
+            .claim_points("member1", tampered, signature)
+            .unwrap_err();
+
+        assert_eq!(
+            ContractError::InvalidVoucherSignature {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn voucher_for_other_contract_is_rejected() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+        suite.set_voucher_signer(&admin, signer()).unwrap();
+
+        let claim = voucher("member1", 3, 1);
+        let chain_id = suite.app.block_info().chain_id;
+        let signature = sign_for(&chain_id, "other", &claim);
+        let err = suite.claim_points("member1", claim, signature).unwrap_err();
+
+        assert_eq!(
+            ContractError::InvalidVoucherSignature {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn voucher_for_other_chain_is_rejected() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+        suite.set_voucher_signer(&admin, signer()).unwrap();
+
+        let claim = voucher("member1", 3, 1);
+        let signature = sign_for("other-chain", suite.contract.as_str(), &claim);
+        let err = suite.claim_points("member1", claim, signature).unwrap_err();
+
+        assert_eq!(
+            ContractError::InvalidVoucherSignature {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn claims_disabled_without_signer() {
+        let mut suite = SuiteBuilder::new().build();
+
+        let claim = voucher("member1", 3, 1);
+        let signature = sign(&suite, &claim);
+        let err = suite.claim_points("member1", claim, signature).unwrap_err();
+        assert_eq!(ContractError::NoVoucherSigner {}, err.downcast().unwrap());
+
+        // Only admin can set the signer
+        let err = suite.set_voucher_signer("member1", signer()).unwrap_err();
+        assert!(matches!(err.downcast().unwrap(), ContractError::Admin(_)));
+    }
+}
+
+mod merkle_claims {
+    use super::*;
+    use crate::merkle::{hash_pair, leaf_hash};
+    use cw_controllers::AdminError;
+
+    /// Four leaves tree: root = H(H(l0, l1), H(l2, l3))
+    struct Tree {
+        leaves: [[u8; 32]; 4],
+    }
+
+    impl Tree {
+        fn new(claims: [(&str, u64); 4]) -> Self {
+            let mut leaves = [[0; 32]; 4];
+            for (index, (addr, points)) in claims.iter().enumerate() {
+                leaves[index] = leaf_hash(index as u64, addr, *points);
+            }
+            Tree { leaves }
+        }
+
+        fn root(&self) -> [u8; 32] {
+            hash_pair(
+                &hash_pair(&self.leaves[0], &self.leaves[1]),
+                &hash_pair(&self.leaves[2], &self.leaves[3]),
+            )
+        }
+
+        fn proof(&self, index: usize) -> [[u8; 32]; 2] {
+            let pair = index / 2 * 2;
+            let other = 2 - pair;
+            [
+                self.leaves[index ^ 1],
+                hash_pair(&self.leaves[other], &self.leaves[other + 1]),
+            ]
+        }
+    }
+
+    fn tree() -> Tree {
+        Tree::new([
+            ("member1", 10),
+            ("member2", 20),
+            ("member3", 30),
+            ("member1", 5),
+        ])
+    }
+
+    #[test]
+    fn claim_points_with_proof() {
+        let mut suite = SuiteBuilder::new().with_member("member1", 5).build();
+        let admin = suite.admin().to_owned();
+        let tree = tree();
+
+        suite.register_merkle_root(&admin, &tree.root()).unwrap();
+        assert_eq!(suite.latest_merkle_stage().unwrap(), 1);
+
+        suite
+            .claim_merkle_points("relayer", 1, 0, "member1", 10, &tree.proof(0))
+            .unwrap();
+        suite
+            .claim_merkle_points("member3", 1, 2, "member3", 30, &tree.proof(2))
+            .unwrap();
+        suite
+            .claim_merkle_points("member1", 1, 3, "member1", 5, &tree.proof(3))
+            .unwrap();
+
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 20), ("member3", 30)])
+        );
+        assert!(suite.is_merkle_claimed(1, 0).unwrap());
+        assert!(!suite.is_merkle_claimed(1, 1).unwrap());
+    }
+
+    #[test]
+    fn leaf_cannot_be_claimed_twice() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+        let tree = tree();
+        suite.register_merkle_root(&admin, &tree.root()).unwrap();
+
+        suite
+            .claim_merkle_points("member2", 1, 1, "member2", 20, &tree.proof(1))
+            .unwrap();
+        let err = suite
+            .claim_merkle_points("member2", 1, 1, "member2", 20, &tree.proof(1))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::MerkleClaimed { stage: 1, index: 1 },
+            err.downcast().unwrap()
+        );
+
+        // The same tree registered again is a separate stage
+        suite.register_merkle_root(&admin, &tree.root()).unwrap();
+        suite
+            .claim_merkle_points("member2", 2, 1, "member2", 20, &tree.proof(1))
+            .unwrap();
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member2", 40)])
+        );
+    }
+
+    #[test]
+    fn invalid_claims_rejected() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+        let tree = tree();
+
+        let err = suite
+            .claim_merkle_points("member1", 1, 0, "member1", 10, &tree.proof(0))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::UnknownMerkleStage(1),
+            err.downcast().unwrap()
+        );
+
+        suite.register_merkle_root(&admin, &tree.root()).unwrap();
+
+        // Tampered points
+        let err = suite
+            .claim_merkle_points("member1", 1, 0, "member1", 100, &tree.proof(0))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMerkleProof {},
+            err.downcast().unwrap()
+        );
+
+        // Leaf claimed for other address
+        let err = suite
+            .claim_merkle_points("member2", 1, 0, "member2", 10, &tree.proof(0))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMerkleProof {},
+            err.downcast().unwrap()
+        );
+
+        // Proof of other leaf
+        let err = suite
+            .claim_merkle_points("member1", 1, 0, "member1", 10, &tree.proof(2))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMerkleProof {},
+            err.downcast().unwrap()
+        );
+
+        assert!(!suite.is_merkle_claimed(1, 0).unwrap());
+        assert_eq!(suite.members().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn only_admin_registers_valid_roots() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_owned();
+
+        let err = suite
+            .register_merkle_root("member1", &tree().root())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Admin(AdminError::NotAdmin {}),
+            err.downcast().unwrap()
+        );
+
+        let err = suite.register_merkle_root(&admin, &[1; 20]).unwrap_err();
+        assert_eq!(ContractError::InvalidMerkleRoot {}, err.downcast().unwrap());
+        assert_eq!(suite.latest_merkle_stage().unwrap(), 0);
+    }
+}
+
+mod points_transfer {
+    use super::*;
+    use crate::state::TransferPolicy;
+    use cw_controllers::AdminError;
+
+    #[test]
+    fn transfers_disabled_by_default() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 10)
+            .with_member("member2", 5)
+            .build();
+
+        let err = suite.transfer_points("member1", "member2", 3).unwrap_err();
+        assert_eq!(ContractError::TransfersDisabled {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn free_transfer_keeps_distributed_rewards() {
+        let members = ["member1", "member2", "member3"];
+        let mut suite = SuiteBuilder::new()
+            .with_member(members[0], 10)
+            .with_member(members[1], 10)
+            .with_funds(members[2], 1200)
+            .build();
+        let admin = suite.owner.to_string();
+        let denom = suite.denom.clone();
+        suite
+            .set_transfer_policy(&admin, TransferPolicy::Free)
+            .unwrap();
+
+        suite
+            .distribute_funds(members[2], None, &coins(600, &denom))
+            .unwrap();
+
+        suite.transfer_points(members[0], members[1], 5).unwrap();
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![(members[0], 5), (members[1], 15)])
+        );
+
+        suite
+            .distribute_funds(members[2], None, &coins(600, &denom))
+            .unwrap();
+
+        // 300 + 5/20 * 600 and 300 + 15/20 * 600
+        assert_eq!(
+            suite.withdrawable_rewards(members[0]).unwrap(),
+            coin(450, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards(members[1]).unwrap(),
+            coin(750, &denom)
+        );
+
+        let err = suite
+            .transfer_points(members[0], members[1], 6)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InsufficientPoints {
+                available: 5,
+                required: 6
+            },
+            err.downcast().unwrap()
+        );
+
+        let err = suite
+            .transfer_points(members[0], members[2], 1)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAMember(members[2].to_owned()),
+            err.downcast().unwrap()
+        );
+
+        let err = suite
+            .transfer_points(members[0], members[0], 1)
+            .unwrap_err();
+        assert_eq!(ContractError::TransferToSelf {}, err.downcast().unwrap());
+
+        let err = suite
+            .transfer_points(members[0], members[1], 0)
+            .unwrap_err();
+        assert_eq!(ContractError::ZeroTransfer {}, err.downcast().unwrap());
+
+        // non-members cannot send even empty transfers, so they never become members
+        let err = suite
+            .transfer_points(members[2], members[1], 1)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAMember(members[2].to_owned()),
+            err.downcast().unwrap()
+        );
+        assert_eq!(suite.members().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn admin_approved_transfer() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 10)
+            .with_member("member2", 5)
+            .build();
+        let admin = suite.owner.to_string();
+        suite
+            .set_transfer_policy(&admin, TransferPolicy::AdminApproved)
+            .unwrap();
+
+        suite.transfer_points("member1", "member2", 3).unwrap();
+        suite.transfer_points("member1", "member2", 4).unwrap();
+        // Nothing moved until approved
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 10), ("member2", 5)])
+        );
+        let pending = suite.list_pending_transfers().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].id, 1);
+        assert_eq!(pending[0].points, 3);
+
+        let err = suite.approve_transfer("member2", 1).unwrap_err();
+        assert_eq!(
+            ContractError::Admin(AdminError::NotAdmin {}),
+            err.downcast().unwrap()
+        );
+
+        suite.approve_transfer(&admin, 1).unwrap();
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 7), ("member2", 8)])
+        );
+
+        // Only the transfer sender or admin can reject it
+        let err = suite.reject_transfer("member2", 2).unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized("Sender is neither transfer sender or an admin".to_owned()),
+            err.downcast().unwrap()
+        );
+        suite.reject_transfer("member1", 2).unwrap();
+        assert_eq!(suite.list_pending_transfers().unwrap(), []);
+
+        let err = suite.approve_transfer(&admin, 2).unwrap_err();
+        assert_eq!(ContractError::NoPendingTransfer(2), err.downcast().unwrap());
+    }
+}
+
+mod points_oracle {
+    use super::*;
+    use crate::state::OracleBudget;
+    use cosmwasm_std::Addr;
+    use cw_controllers::AdminError;
+
+    #[test]
+    fn only_oracle_suggests_points() {
+        let mut suite = SuiteBuilder::new().with_member("member1", 10).build();
+        let admin = suite.owner.to_string();
+
+        let err = suite
+            .suggest_points("oracle", &[("member1", 5)])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized("Sender is not the points oracle".to_owned()),
+            err.downcast().unwrap()
+        );
+
+        let err = suite
+            .set_points_oracle("member1", Some("oracle"), None)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Admin(AdminError::NotAdmin {}),
+            err.downcast().unwrap()
+        );
+
+        suite
+            .set_points_oracle(&admin, Some("oracle"), None)
+            .unwrap();
+        let err = suite
+            .suggest_points("member1", &[("member1", 5)])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized("Sender is not the points oracle".to_owned()),
+            err.downcast().unwrap()
+        );
+        let err = suite
+            .suggest_points("oracle", &[("member1", 0)])
+            .unwrap_err();
+        assert_eq!(ContractError::ZeroPoints {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn suggestions_without_budget_wait_for_approval() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 10)
+            .with_member("member2", 5)
+            .build();
+        let admin = suite.owner.to_string();
+        suite
+            .set_points_oracle(&admin, Some("oracle"), None)
+            .unwrap();
+
+        let resp = suite
+            .suggest_points("oracle", &[("member1", 3), ("member2", 4), ("member3", 2)])
+            .unwrap();
+        assert_eq!(resp.applied, 0);
+        assert_eq!(resp.pending, [1, 2, 3]);
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 10), ("member2", 5)])
+        );
+        let pending = suite.list_pending_suggestions().unwrap();
+        assert_eq!(pending.len(), 3);
+        assert_eq!(pending[2].addr, Addr::unchecked("member3"));
+        assert_eq!(pending[2].points, 2);
+
+        let err = suite.approve_suggestions("oracle", &[1]).unwrap_err();
+        assert_eq!(
+            ContractError::Admin(AdminError::NotAdmin {}),
+            err.downcast().unwrap()
+        );
+
+        suite.approve_suggestions(&admin, &[1, 3]).unwrap();
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 13), ("member2", 5), ("member3", 2)])
+        );
+
+        suite.reject_suggestions(&admin, &[2]).unwrap();
+        assert_eq!(suite.list_pending_suggestions().unwrap(), []);
+
+        let err = suite.approve_suggestions(&admin, &[2]).unwrap_err();
+        assert_eq!(
+            ContractError::NoPendingSuggestion(2),
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn suggestions_of_replaced_oracle_cannot_be_approved() {
+        let mut suite = SuiteBuilder::new().with_member("member1", 10).build();
+        let admin = suite.owner.to_string();
+        suite
+            .set_points_oracle(&admin, Some("oracle"), None)
+            .unwrap();
+        suite
+            .suggest_points("oracle", &[("member1", 3), ("member1", 4)])
+            .unwrap();
+        assert_eq!(
+            suite.list_pending_suggestions().unwrap()[0].oracle,
+            Addr::unchecked("oracle")
+        );
+
+        suite
+            .set_points_oracle(&admin, Some("new_oracle"), None)
+            .unwrap();
+        let err = suite.approve_suggestions(&admin, &[1]).unwrap_err();
+        assert_eq!(ContractError::StaleSuggestion(1), err.downcast().unwrap());
+
+        // Neither when the oracle is removed
+        suite.set_points_oracle(&admin, None, None).unwrap();
+        let err = suite.approve_suggestions(&admin, &[2]).unwrap_err();
+        assert_eq!(ContractError::StaleSuggestion(2), err.downcast().unwrap());
+
+        // Stale suggestions can still be rejected
+        suite.reject_suggestions(&admin, &[1, 2]).unwrap();
+        assert_eq!(suite.list_pending_suggestions().unwrap(), []);
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 10)])
+        );
+    }
+
+    #[test]
+    fn suggestions_within_budget_applied() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("member1", 10)
+            .with_member("member2", 5)
+            .build();
+        let admin = suite.owner.to_string();
+        let budget = OracleBudget {
+            points: 10,
+            period: Duration::new(100),
+        };
+
+        let err = suite
+            .set_points_oracle(
+                &admin,
+                Some("oracle"),
+                Some(OracleBudget {
+                    points: 0,
+                    ..budget
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidOracleBudget {},
+            err.downcast().unwrap()
+        );
+        suite
+            .set_points_oracle(&admin, Some("oracle"), Some(budget))
+            .unwrap();
+
+        // second entry doesn't fit in the budget, third one does
+        let resp = suite
+            .suggest_points("oracle", &[("member1", 6), ("member2", 5), ("member1", 4)])
+            .unwrap();
+        assert_eq!(resp.applied, 2);
+        assert_eq!(resp.pending, [1]);
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 20), ("member2", 5)])
+        );
+        let oracle = suite.points_oracle().unwrap();
+        assert_eq!(oracle.oracle, Some(Addr::unchecked("oracle")));
+        assert_eq!(oracle.budget, Some(budget));
+        assert_eq!(oracle.budget_used, 10);
+
+        // budget is renewed in the next epoch
+        suite.app.advance_seconds(100);
+        assert_eq!(suite.points_oracle().unwrap().budget_used, 0);
+        let resp = suite.suggest_points("oracle", &[("member2", 5)]).unwrap();
+        assert_eq!(resp.applied, 1);
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("member1", 20), ("member2", 10)])
+        );
+        assert_eq!(suite.list_pending_suggestions().unwrap().len(), 1);
+
+        // removed oracle cannot suggest anymore
+        suite.set_points_oracle(&admin, None, None).unwrap();
+        let err = suite
+            .suggest_points("oracle", &[("member2", 1)])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized("Sender is not the points oracle".to_owned()),
+            err.downcast().unwrap()
+        );
+    }
+}
+
 mod migration {
     use super::*;
     use crate::msg::MigrateMsg;
+    use crate::state::MAX_REWARDS_STREAMS;
+    use cosmwasm_std::Uint128;
 
     #[test]
     fn migration_can_alter_cfg() {
@@ -941,6 +2090,9 @@ mod migration {
                 &admin,
                 &MigrateMsg {
                     halflife: Some(Duration::new(200)),
+                    min_distribution: None,
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
             )
             .unwrap();
@@ -964,6 +2116,9 @@ mod migration {
                 &admin,
                 &MigrateMsg {
                     halflife: Some(Duration::new(0)),
+                    min_distribution: None,
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
             )
             .unwrap();
@@ -971,4 +2126,148 @@ mod migration {
         let cfg = suite.halflife().unwrap();
         assert!(cfg.halflife_info.is_none());
     }
+
+    #[test]
+    fn migration_can_set_distribution_throttle() {
+        let mut suite = SuiteBuilder::new()
+            .with_distribution_interval(Duration::new(100))
+            .build();
+        let admin = suite.admin().to_string();
+
+        suite
+            .migrate(
+                &admin,
+                &MigrateMsg {
+                    halflife: None,
+                    min_distribution: Some(Uint128::new(50)),
+                    distribution_interval: Some(Duration::new(0)),
+                    withdrawal_stream_period: None,
+                },
+            )
+            .unwrap();
+
+        let throttle = suite.distribution_throttle().unwrap();
+        assert_eq!(throttle.min_distribution, Uint128::new(50));
+        assert_eq!(throttle.distribution_interval, None);
+    }
+
+    #[test]
+    fn migration_can_set_withdrawal_stream_period() {
+        let mut suite = SuiteBuilder::new().build();
+        let admin = suite.admin().to_string();
+        assert_eq!(suite.withdrawal_stream().unwrap().period, None);
+
+        let mut msg = MigrateMsg {
+            halflife: None,
+            min_distribution: None,
+            distribution_interval: None,
+            withdrawal_stream_period: Some(Duration::new(100)),
+        };
+        // Streaming needs distributions to be rare enough to bound the active streams
+        let err = suite.migrate(&admin, &msg).unwrap_err();
+        assert_eq!(
+            ContractError::StreamingWithoutInterval {
+                max_streams: MAX_REWARDS_STREAMS
+            },
+            err.downcast().unwrap()
+        );
+
+        msg.distribution_interval = Some(Duration::new(3));
+        suite.migrate(&admin, &msg).unwrap_err();
+
+        msg.distribution_interval = Some(Duration::new(4));
+        suite.migrate(&admin, &msg).unwrap();
+        assert_eq!(
+            suite.withdrawal_stream().unwrap().period,
+            Some(Duration::new(100))
+        );
+
+        msg.withdrawal_stream_period = Some(Duration::new(0));
+        suite.migrate(&admin, &msg).unwrap();
+        assert_eq!(suite.withdrawal_stream().unwrap().period, None);
+    }
+}
+
+mod simulate_update_members {
+    use super::*;
+    use crate::msg::AdjustmentDiff;
+    use cosmwasm_std::Addr;
+    use tg4::MemberDiff;
+
+    #[test]
+    fn preview_matches_execution() {
+        let mut suite = SuiteBuilder::new()
+            .with_member("alice", 1)
+            .with_member("bob", 3)
+            .with_funds("distributor", 400)
+            .build();
+        let admin = suite.admin().to_owned();
+        let denom = suite.denom.clone();
+
+        suite
+            .distribute_funds("distributor", None, &coins(400, &denom))
+            .unwrap();
+
+        let add = [("carol", 4), ("alice", 2), ("carol", 6)];
+        let remove = ["bob", "dave"];
+        let preview = suite.simulate_update_members(&add, &remove).unwrap();
+
+        assert_eq!(
+            preview.diffs,
+            vec![
+                MemberDiff::new("carol", None, Some(4)),
+                MemberDiff::new("alice", Some(1), Some(2)),
+                MemberDiff::new("carol", Some(4), Some(6)),
+                MemberDiff::new("bob", Some(3), None),
+            ]
+        );
+        assert_eq!(preview.total_points, 8);
+
+        // nothing changed by the query itself
+        assert_eq!(suite.members().unwrap().len(), 2);
+
+        suite.modify_members(&admin, &add, &remove).unwrap();
+        assert_eq!(
+            suite.members().unwrap(),
+            expected_members(vec![("alice", 2), ("carol", 6)])
+        );
+
+        let expected: Vec<_> = ["alice", "bob", "carol"]
+            .iter()
+            .map(|addr| AdjustmentDiff {
+                addr: Addr::unchecked(*addr),
+                old: match *addr {
+                    "carol" => None,
+                    _ => Some(0.into()),
+                },
+                new: suite
+                    .withdraw_adjustment(addr)
+                    .unwrap()
+                    .unwrap()
+                    .shares_correction,
+            })
+            .collect();
+        assert_eq!(preview.adjustments, expected);
+
+        // rewards earned before the update are kept
+        assert_eq!(
+            suite.withdrawable_rewards("alice").unwrap(),
+            coin(100, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("bob").unwrap(),
+            coin(300, &denom)
+        );
+        assert_eq!(
+            suite.withdrawable_rewards("carol").unwrap(),
+            coin(0, &denom)
+        );
+    }
+
+    #[test]
+    fn invalid_address_fails() {
+        let suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+        suite.simulate_update_members(&[("", 4)], &[]).unwrap_err();
+    }
 }
@@ -1,7 +1,10 @@
 use crate::error::ContractError;
 use crate::msg::*;
+use crate::state::{OracleBudget, TransferPolicy, VoucherSigner, WithdrawAdjustment};
 use anyhow::Result as AnyResult;
-use cosmwasm_std::{Addr, Coin, CosmosMsg, Decimal, StdResult};
+use cosmwasm_std::{
+    from_binary, Addr, Binary, Coin, CosmosMsg, Decimal, StdResult, Timestamp, Uint128,
+};
 use cw_multi_test::{AppResponse, Contract, ContractWrapper, CosmosRouter, Executor};
 use derivative::Derivative;
 use tg4::{Member, MemberListResponse};
@@ -41,6 +44,9 @@ pub struct SuiteBuilder {
     #[derivative(Default(value = "\"usdc\".to_owned()"))]
     denom: String,
     preauths_slashing: u64,
+    min_distribution: u128,
+    distribution_interval: Option<Duration>,
+    withdrawal_stream_period: Option<Duration>,
 }
 
 impl SuiteBuilder {
@@ -74,6 +80,21 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_min_distribution(mut self, amount: u128) -> Self {
+        self.min_distribution = amount;
+        self
+    }
+
+    pub fn with_distribution_interval(mut self, interval: Duration) -> Self {
+        self.distribution_interval = Some(interval);
+        self
+    }
+
+    pub fn with_withdrawal_stream_period(mut self, period: Duration) -> Self {
+        self.withdrawal_stream_period = Some(period);
+        self
+    }
+
     #[track_caller]
     pub fn build(self) -> Suite {
         let funds = self.funds;
@@ -119,6 +140,9 @@ impl SuiteBuilder {
                     preauths_slashing: self.preauths_slashing,
                     halflife: self.halflife,
                     denom: denom.clone(),
+                    min_distribution: Uint128::new(self.min_distribution),
+                    distribution_interval: self.distribution_interval,
+                    withdrawal_stream_period: self.withdrawal_stream_period,
                 },
                 &[],
                 "engagement",
@@ -199,12 +223,22 @@ impl Suite {
         &mut self,
         executor: &str,
         delegated: &str,
+    ) -> AnyResult<AppResponse> {
+        self.delegate_withdrawal_until(executor, delegated, None)
+    }
+
+    pub fn delegate_withdrawal_until(
+        &mut self,
+        executor: &str,
+        delegated: &str,
+        expires: impl Into<Option<Timestamp>>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(executor),
             self.contract.clone(),
             &ExecuteMsg::DelegateWithdrawal {
                 delegated: delegated.to_owned(),
+                expires: expires.into(),
             },
             &[],
         )
@@ -236,11 +270,21 @@ impl Suite {
     }
 
     pub fn add_slasher(&mut self, executor: &str, addr: &str) -> AnyResult<AppResponse> {
+        self.add_limited_slasher(executor, addr, None)
+    }
+
+    pub fn add_limited_slasher(
+        &mut self,
+        executor: &str,
+        addr: &str,
+        max_portion: impl Into<Option<Decimal>>,
+    ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(executor),
             self.contract.clone(),
             &ExecuteMsg::AddSlasher {
                 addr: addr.to_owned(),
+                max_portion: max_portion.into(),
             },
             &[],
         )
@@ -274,6 +318,238 @@ impl Suite {
         )
     }
 
+    pub fn set_voucher_signer(
+        &mut self,
+        executor: &str,
+        signer: impl Into<Option<VoucherSigner>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::SetVoucherSigner {
+                signer: signer.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn claim_points(
+        &mut self,
+        executor: &str,
+        voucher: Voucher,
+        signature: Binary,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::ClaimPoints { voucher, signature },
+            &[],
+        )
+    }
+
+    pub fn register_merkle_root(
+        &mut self,
+        executor: &str,
+        merkle_root: &[u8],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::RegisterMerkleRoot {
+                merkle_root: Binary::from(merkle_root),
+            },
+            &[],
+        )
+    }
+
+    pub fn claim_merkle_points(
+        &mut self,
+        executor: &str,
+        stage: u64,
+        index: u64,
+        addr: &str,
+        points: u64,
+        proof: &[[u8; 32]],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::ClaimMerklePoints {
+                stage,
+                index,
+                addr: addr.to_owned(),
+                points,
+                proof: proof.iter().map(|node| Binary::from(&node[..])).collect(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_transfer_policy(
+        &mut self,
+        executor: &str,
+        policy: TransferPolicy,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::SetTransferPolicy { policy },
+            &[],
+        )
+    }
+
+    pub fn transfer_points(
+        &mut self,
+        executor: &str,
+        to: &str,
+        points: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::TransferPoints {
+                to: to.to_owned(),
+                points,
+            },
+            &[],
+        )
+    }
+
+    pub fn approve_transfer(&mut self, executor: &str, id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::ApproveTransfer { id },
+            &[],
+        )
+    }
+
+    pub fn reject_transfer(&mut self, executor: &str, id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::RejectTransfer { id },
+            &[],
+        )
+    }
+
+    pub fn list_pending_transfers(&self) -> StdResult<Vec<PendingTransferInfo>> {
+        let resp: PendingTransfersResponse = self.app.wrap().query_wasm_smart(
+            &self.contract,
+            &QueryMsg::ListPendingTransfers {
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp.transfers)
+    }
+
+    pub fn set_points_oracle(
+        &mut self,
+        executor: &str,
+        oracle: Option<&str>,
+        budget: Option<OracleBudget>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::SetPointsOracle {
+                oracle: oracle.map(str::to_owned),
+                budget,
+            },
+            &[],
+        )
+    }
+
+    pub fn suggest_points(
+        &mut self,
+        executor: &str,
+        entries: &[(&str, u64)],
+    ) -> AnyResult<SuggestPointsResponse> {
+        let resp = self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::SuggestPoints {
+                entries: entries
+                    .iter()
+                    .map(|(addr, points)| (addr.to_string(), *points))
+                    .collect(),
+            },
+            &[],
+        )?;
+        Ok(from_binary(&resp.data.unwrap())?)
+    }
+
+    pub fn approve_suggestions(&mut self, executor: &str, ids: &[u64]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::ApproveSuggestions { ids: ids.to_vec() },
+            &[],
+        )
+    }
+
+    pub fn reject_suggestions(&mut self, executor: &str, ids: &[u64]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::RejectSuggestions { ids: ids.to_vec() },
+            &[],
+        )
+    }
+
+    pub fn points_oracle(&self) -> StdResult<PointsOracleResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::PointsOracle {})
+    }
+
+    pub fn list_pending_suggestions(&self) -> StdResult<Vec<PendingSuggestionInfo>> {
+        let resp: PendingSuggestionsResponse = self.app.wrap().query_wasm_smart(
+            &self.contract,
+            &QueryMsg::ListPendingSuggestions {
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp.suggestions)
+    }
+
+    pub fn list_distribution_snapshots(
+        &self,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<DistributionSnapshotInfo>> {
+        let resp: DistributionSnapshotsResponse = self.app.wrap().query_wasm_smart(
+            &self.contract,
+            &QueryMsg::ListDistributionSnapshots {
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(resp.snapshots)
+    }
+
+    pub fn is_voucher_claimed(&self, nonce: u64) -> StdResult<bool> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::IsVoucherClaimed { nonce })
+    }
+
+    pub fn latest_merkle_stage(&self) -> StdResult<u64> {
+        let resp: LatestMerkleStageResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::LatestMerkleStage {})?;
+        Ok(resp.stage)
+    }
+
+    pub fn is_merkle_claimed(&self, stage: u64, index: u64) -> StdResult<bool> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::IsMerkleClaimed { stage, index })
+    }
+
     pub fn is_slasher(&self, addr: &str) -> Result<bool, ContractError> {
         let is_slasher: bool = self.app.wrap().query_wasm_smart(
             self.contract.clone(),
@@ -284,6 +560,15 @@ impl Suite {
         Ok(is_slasher)
     }
 
+    pub fn slasher_limit(&self, addr: &str) -> StdResult<Option<Decimal>> {
+        self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::SlasherLimit {
+                addr: addr.to_owned(),
+            },
+        )
+    }
+
     pub fn list_slashers(&mut self) -> Result<Vec<String>, ContractError> {
         let slashers_list: Vec<String> = self
             .app
@@ -302,6 +587,16 @@ impl Suite {
         Ok(resp.rewards)
     }
 
+    pub fn locked_rewards(&self, owner: &str) -> Result<Coin, ContractError> {
+        let resp: RewardsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::LockedRewards {
+                owner: owner.to_owned(),
+            },
+        )?;
+        Ok(resp.rewards)
+    }
+
     pub fn distributed_funds(&self) -> Result<Coin, ContractError> {
         let resp: RewardsResponse = self
             .app
@@ -328,6 +623,17 @@ impl Suite {
         Ok(resp.delegated)
     }
 
+    pub fn list_delegations(&self) -> StdResult<Vec<DelegationInfo>> {
+        let resp: DelegationsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ListDelegations {
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp.delegations)
+    }
+
     /// Shortcut for querying distributeable token balance of contract
     pub fn token_balance(&self, owner: &str) -> StdResult<u128> {
         let amount = self
@@ -349,6 +655,36 @@ impl Suite {
         Ok(resp.members)
     }
 
+    pub fn simulate_update_members(
+        &self,
+        add: &[(&str, u64)],
+        remove: &[&str],
+    ) -> StdResult<SimulateUpdateMembersResponse> {
+        let add = add
+            .iter()
+            .map(|(addr, points)| Member {
+                addr: (*addr).to_owned(),
+                points: *points,
+                start_height: None,
+            })
+            .collect();
+        let remove = remove.iter().map(|addr| (*addr).to_owned()).collect();
+
+        self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::SimulateUpdateMembers { add, remove },
+        )
+    }
+
+    pub fn withdraw_adjustment(&self, addr: &str) -> StdResult<Option<WithdrawAdjustment>> {
+        self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::WithdrawAdjustmentData {
+                addr: addr.to_owned(),
+            },
+        )
+    }
+
     /// Queries engagement contract for its halflife
     pub fn halflife(&self) -> StdResult<HalflifeResponse> {
         self.app
@@ -356,6 +692,18 @@ impl Suite {
             .query_wasm_smart(&self.contract, &QueryMsg::Halflife {})
     }
 
+    pub fn distribution_throttle(&self) -> StdResult<DistributionThrottleResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::DistributionThrottle {})
+    }
+
+    pub fn withdrawal_stream(&self) -> StdResult<WithdrawalStreamResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.contract, &QueryMsg::WithdrawalStream {})
+    }
+
     /// Migrates the contract to the same version (same code id), but possibly changing
     /// some cfg values via MigrateMsg.
     pub fn migrate(&mut self, addr: &str, msg: &MigrateMsg) -> AnyResult<AppResponse> {
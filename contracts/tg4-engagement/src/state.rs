@@ -1,8 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use std::cmp::max;
+
 use crate::i128::Int128;
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Empty, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 use tg_utils::Duration;
 pub use tg_utils::{PREAUTH_SLASHING, SLASHERS};
@@ -59,12 +61,213 @@ pub struct WithdrawAdjustment {
     pub withdrawn_rewards: Uint128,
     /// User delegated for funds withdrawal
     pub delegated: Addr,
+    /// Time after which the delegation reverts to the owner, never if `None`
+    #[serde(default)]
+    pub delegation_expires: Option<Timestamp>,
+}
+
+impl WithdrawAdjustment {
+    /// Returns address currently allowed for withdrawal - `owner` once the delegation expired.
+    pub fn delegated_at<'a>(&'a self, owner: &'a Addr, block: &BlockInfo) -> &'a Addr {
+        match self.delegation_expires {
+            Some(expires) if block.time >= expires => owner,
+            _ => &self.delegated,
+        }
+    }
+}
+
+/// Signature scheme of the voucher signer key
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VoucherKeyType {
+    Secp256k1,
+    Ed25519,
+}
+
+/// Key trusted to sign vouchers for self-service points claims
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoucherSigner {
+    pub key_type: VoucherKeyType,
+    pub pubkey: Binary,
 }
 
 /// Rewards distribution data
 pub const DISTRIBUTION: Item<Distribution> = Item::new("distribution");
+
+/// Limits on how often and how small `DistributeRewards` may be. Contracts instantiated before
+/// throttling was introduced have no such item stored, which means no limits.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DistributionThrottle {
+    /// Undistributed amount below which distribution is deferred
+    pub min_amount: Uint128,
+    /// Minimal time between two distributions
+    pub min_interval: Option<Duration>,
+    /// Time of the last actual distribution
+    pub last_distribution: Option<Timestamp>,
+}
+
+impl DistributionThrottle {
+    /// Earliest time the next distribution may happen, `None` if not limited by time.
+    pub fn next_distribution(&self) -> Option<Timestamp> {
+        self.min_interval
+            .zip(self.last_distribution)
+            .map(|(interval, last)| last.plus_seconds(interval.seconds()))
+    }
+}
+
+pub const DISTRIBUTION_THROTTLE: Item<DistributionThrottle> = Item::new("distribution_throttle");
+
+/// Distribution which unlocks for withdrawal linearly over the withdrawal stream period
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RewardsStream {
+    /// How many shares the distribution added to every point
+    pub shares_per_point: Uint128,
+    /// Height of the distribution. Members get their part by points they had at the end of
+    /// this block.
+    pub height: u64,
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl RewardsStream {
+    /// Shares of a member with `points` still locked at the given time.
+    pub fn locked_shares(&self, points: u64, t: Timestamp) -> u128 {
+        if t >= self.end {
+            return 0;
+        }
+        let shares = self.shares_per_point.u128() * points as u128;
+        let remaining = self.end.seconds() - max(t, self.start).seconds();
+        let period = self.end.seconds() - self.start.seconds();
+        Uint128::new(shares)
+            .multiply_ratio(remaining, period)
+            .u128()
+    }
+}
+
+/// Period over which distributed rewards unlock for withdrawal. Rewards are withdrawable right
+/// after distribution if not set.
+pub const WITHDRAWAL_STREAM_PERIOD: Item<Duration> = Item::new("withdrawal_stream_period");
+/// Maximal number of distributions unlocking at the same time. The distribution interval has to
+/// be long enough for the stream period not to exceed it.
+pub const MAX_REWARDS_STREAMS: u64 = 30;
+/// Distributions which are still unlocking, by id
+pub const REWARDS_STREAMS: Map<u64, RewardsStream> = Map::new("rewards_streams");
+/// Id of the last rewards stream
+pub const REWARDS_STREAM_ID: Item<u64> = Item::new("rewards_stream_id");
+/// Record of a single rewards distribution, kept for auditing withdrawals against past
+/// distributions
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DistributionSnapshot {
+    pub denom: String,
+    /// Distributed amount
+    pub amount: Uint128,
+    /// Total points of members the amount was distributed between
+    pub total_points: u64,
+    /// Shares per point added by this distribution
+    pub shares_per_point: Uint128,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+/// Append-only history of distributions, by sequence number starting from 1
+pub const DISTRIBUTION_SNAPSHOTS: Map<u64, DistributionSnapshot> =
+    Map::new("distribution_snapshots");
+/// Sequence number of the last distribution
+pub const DISTRIBUTION_SEQ: Item<u64> = Item::new("distribution_seq");
 /// Information how to exactly adjust rewards while withdrawal
 pub const WITHDRAW_ADJUSTMENT: Map<&Addr, WithdrawAdjustment> = Map::new("withdraw_adjustment");
+/// Owners with withdrawal delegated to another address. Entries are dropped when the delegation
+/// is reverted to the owner, or when it is found expired on withdrawal.
+pub const DELEGATIONS: Map<&Addr, Empty> = Map::new("delegations");
+/// Key vouchers have to be signed with. No vouchers are accepted if not set
+pub const VOUCHER_SIGNER: Item<VoucherSigner> = Item::new("voucher_signer");
+/// Nonces of already claimed vouchers, to prevent replays
+pub const VOUCHER_NONCES: Map<u64, Empty> = Map::new("voucher_nonces");
+
+/// Roots of committed points distribution trees, by stage. See `merkle::leaf_hash` for the
+/// leaves format.
+pub const MERKLE_ROOTS: Map<u64, Binary> = Map::new("merkle_roots");
+/// Stage of the most recently registered merkle root
+pub const LATEST_MERKLE_STAGE: Item<u64> = Item::new("latest_merkle_stage");
+/// Bitmap of claimed leaves, keyed by `(stage, index / 64)`. Bit `index % 64` of the word is
+/// set once the leaf is claimed.
+pub const MERKLE_CLAIMED: Map<(u64, u64), u64> = Map::new("merkle_claimed");
+
+/// Who decides about points transfers between members
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferPolicy {
+    /// Points cannot be transferred
+    #[default]
+    Disabled,
+    /// Transfers are pending until approved by the admin
+    AdminApproved,
+    /// Transfers are executed immediately
+    Free,
+}
+
+/// Points transfer waiting for the admin approval
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingTransfer {
+    pub from: Addr,
+    pub to: Addr,
+    pub points: u64,
+}
+
+/// Transfer policy. Contracts instantiated before transfers were introduced have no such item
+/// stored, which means transfers are disabled.
+pub const TRANSFER_POLICY: Item<TransferPolicy> = Item::new("transfer_policy");
+/// Transfers awaiting admin approval, by id
+pub const PENDING_TRANSFERS: Map<u64, PendingTransfer> = Map::new("pending_transfers");
+/// Id of the last pending transfer
+pub const PENDING_TRANSFER_ID: Item<u64> = Item::new("pending_transfer_id");
+
+/// External contract (eg. contribution scoring oracle) allowed to suggest points for members
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PointsOracle {
+    pub addr: Addr,
+    /// Suggestions within the budget are applied right away, all others wait for the admin
+    /// approval
+    pub budget: Option<OracleBudget>,
+}
+
+/// Points of oracle suggestions applied without the admin approval in every budget epoch
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+pub struct OracleBudget {
+    pub points: u64,
+    /// Length of the budget epoch. Epochs are counted from the unix epoch, so the budget is
+    /// renewed every `period` seconds.
+    pub period: Duration,
+}
+
+impl OracleBudget {
+    pub fn epoch(&self, t: Timestamp) -> u64 {
+        t.seconds() / self.period.seconds()
+    }
+}
+
+/// Points of oracle suggestions applied in the budget `epoch`
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct OracleBudgetUsage {
+    pub epoch: u64,
+    pub used: u64,
+}
+
+/// Oracle suggestion waiting for the admin approval
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingSuggestion {
+    pub addr: Addr,
+    pub points: u64,
+    /// Oracle which made the suggestion, it can only be approved while this oracle is set
+    pub oracle: Addr,
+}
+
+pub const POINTS_ORACLE: Item<PointsOracle> = Item::new("points_oracle");
+pub const ORACLE_BUDGET_USAGE: Item<OracleBudgetUsage> = Item::new("oracle_budget_usage");
+/// Oracle suggestions awaiting admin approval, by id
+pub const PENDING_SUGGESTIONS: Map<u64, PendingSuggestion> = Map::new("pending_suggestions");
+/// Id of the last pending suggestion
+pub const PENDING_SUGGESTION_ID: Item<u64> = Item::new("pending_suggestion_id");
 
 #[cfg(test)]
 mod tests {
@@ -4,7 +4,9 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
 pub use tg4::{AdminResponse, MemberListResponse, MemberResponse, TotalPointsResponse};
-pub use tg4_group::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+pub use tg4_group::msg::{
+    ApplicationListResponse, ApplicationResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -19,4 +21,6 @@ fn main() {
     export_schema(&schema_for!(MemberListResponse), &out_dir);
     export_schema(&schema_for!(MemberResponse), &out_dir);
     export_schema(&schema_for!(TotalPointsResponse), &out_dir);
+    export_schema(&schema_for!(ApplicationResponse), &out_dir);
+    export_schema(&schema_for!(ApplicationListResponse), &out_dir);
 }
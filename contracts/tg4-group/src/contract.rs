@@ -5,6 +5,7 @@ use cosmwasm_std::{
     SubMsg,
 };
 use cw2::set_contract_version;
+use cw_controllers::{AdminError, HookError};
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
 
@@ -12,8 +13,13 @@ use cw4::{MemberChangedHookMsg, MemberDiff};
 use tg4::{Member, MemberInfo, MemberListResponse, MemberResponse, TotalPointsResponse};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{ADMIN, HOOKS, MEMBERS, TOTAL};
+use crate::msg::{
+    AdminPoliciesResponse, AdminPolicy, ApplicationInfo, ApplicationListResponse,
+    ApplicationResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{
+    AdminAction, Application, ADMIN, APPLICATIONS, HOOKS, MEMBERS, POLICIES, TOTAL,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:tg4-group";
@@ -23,13 +29,17 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    create(deps, msg.admin, msg.members, env.block.height)?;
+    create(deps.branch(), msg.admin, msg.members, env.block.height)?;
+    for policy in msg.policies {
+        let controller = deps.api.addr_validate(&policy.controller)?;
+        POLICIES.save(deps.storage, policy.action.as_str(), &controller)?;
+    }
     Ok(Response::default())
 }
 
@@ -57,7 +67,7 @@ pub fn create(
             height,
         )?;
     }
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, height)?;
 
     Ok(())
 }
@@ -72,21 +82,201 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let api = deps.api;
     match msg {
-        ExecuteMsg::UpdateAdmin { admin } => Ok(ADMIN.execute_update_admin(
+        ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(
             deps,
             info,
             admin.map(|admin| api.addr_validate(&admin)).transpose()?,
-        )?),
+        ),
+        ExecuteMsg::SetAdminPolicy { action, controller } => execute_set_admin_policy(
+            deps,
+            info,
+            action,
+            controller
+                .map(|controller| api.addr_validate(&controller))
+                .transpose()?,
+        ),
         ExecuteMsg::UpdateMembers { add, remove } => {
             execute_update_members(deps, env, info, add, remove)
         }
-        ExecuteMsg::AddHook { addr } => {
-            Ok(HOOKS.execute_add_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
-        }
+        ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, api.addr_validate(&addr)?),
         ExecuteMsg::RemoveHook { addr } => {
-            Ok(HOOKS.execute_remove_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
+            execute_remove_hook(deps, info, api.addr_validate(&addr)?)
+        }
+        ExecuteMsg::Apply { metadata } => execute_apply(deps, env, info, metadata),
+        ExecuteMsg::WithdrawApplication {} => execute_withdraw_application(deps, info),
+        ExecuteMsg::ProcessApplications { approve, reject } => {
+            execute_process_applications(deps, env, info, approve, reject)
+        }
+    }
+}
+
+/// Checks the sender is the controller of the action, or the admin if the action has no policy
+pub fn assert_authorized(deps: Deps, action: AdminAction, sender: &Addr) -> Result<(), AdminError> {
+    match POLICIES.may_load(deps.storage, action.as_str())? {
+        Some(controller) if controller == *sender => Ok(()),
+        Some(_) => Err(AdminError::NotAdmin {}),
+        None => ADMIN.assert_admin(deps, sender),
+    }
+}
+
+pub fn execute_update_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: Option<Addr>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), AdminAction::UpdateAdmin, &info.sender)?;
+
+    let attributes = vec![
+        attr("action", "update_admin"),
+        attr(
+            "admin",
+            admin
+                .as_ref()
+                .map_or_else(|| "None".to_owned(), Addr::to_string),
+        ),
+        attr("sender", info.sender),
+    ];
+    ADMIN.set(deps, admin)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+pub fn execute_set_admin_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    action: AdminAction,
+    controller: Option<Addr>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), AdminAction::UpdateAdmin, &info.sender)?;
+
+    let res = Response::new()
+        .add_attribute("action", "set_admin_policy")
+        .add_attribute("admin_action", action.as_str())
+        .add_attribute("sender", info.sender);
+    match controller {
+        Some(controller) => {
+            POLICIES.save(deps.storage, action.as_str(), &controller)?;
+            Ok(res.add_attribute("controller", controller))
+        }
+        None => {
+            POLICIES.remove(deps.storage, action.as_str());
+            Ok(res)
+        }
+    }
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: Addr,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), AdminAction::ManageHooks, &info.sender)
+        .map_err(HookError::from)?;
+    HOOKS.add_hook(deps.storage, addr.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("hook", addr)
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: Addr,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), AdminAction::ManageHooks, &info.sender)
+        .map_err(HookError::from)?;
+    HOOKS.remove_hook(deps.storage, addr.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("hook", addr)
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_apply(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    metadata: String,
+) -> Result<Response, ContractError> {
+    if MEMBERS.may_load(deps.storage, &info.sender)?.is_some() {
+        return Err(ContractError::AlreadyMember(info.sender.into()));
+    }
+    if APPLICATIONS.has(deps.storage, &info.sender) {
+        return Err(ContractError::ApplicationPending(info.sender.into()));
+    }
+
+    let application = Application {
+        metadata,
+        applied_at: env.block.height,
+    };
+    APPLICATIONS.save(deps.storage, &info.sender, &application)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "apply")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_withdraw_application(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if !APPLICATIONS.has(deps.storage, &info.sender) {
+        return Err(ContractError::NoApplication(info.sender.into()));
+    }
+    APPLICATIONS.remove(deps.storage, &info.sender);
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_application")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_process_applications(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    approve: Vec<Member>,
+    reject: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_authorized(deps.as_ref(), AdminAction::UpdateMembers, &info.sender)?;
+
+    let attributes = vec![
+        attr("action", "process_applications"),
+        attr("approved", approve.len().to_string()),
+        attr("rejected", reject.len().to_string()),
+        attr("sender", &info.sender),
+    ];
+
+    // every processed address has to have a pending application
+    for addr in approve.iter().map(|m| &m.addr).chain(reject.iter()) {
+        let addr = deps.api.addr_validate(addr)?;
+        if !APPLICATIONS.has(deps.storage, &addr) {
+            return Err(ContractError::NoApplication(addr.into()));
         }
+        APPLICATIONS.remove(deps.storage, &addr);
     }
+
+    // approved applicants become members, exactly as if added by admin
+    let diff = update_members(
+        deps.branch(),
+        env.block.height,
+        info.sender,
+        approve,
+        vec![],
+    )?;
+    let messages = if diff.diffs.is_empty() {
+        vec![]
+    } else {
+        HOOKS.prepare_hooks(deps.storage, |h| {
+            diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+        })?
+    };
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .add_attributes(attributes))
 }
 
 pub fn execute_update_members(
@@ -122,7 +312,7 @@ pub fn update_members(
     to_add: Vec<Member>,
     to_remove: Vec<String>,
 ) -> Result<MemberChangedHookMsg, ContractError> {
-    ADMIN.assert_admin(deps.as_ref(), &sender)?;
+    assert_authorized(deps.as_ref(), AdminAction::UpdateMembers, &sender)?;
 
     let mut total = TOTAL.load(deps.storage)?;
     let mut diffs: Vec<MemberDiff> = vec![];
@@ -153,7 +343,7 @@ pub fn update_members(
         }
     }
 
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, height)?;
     Ok(MemberChangedHookMsg { diffs })
 }
 
@@ -167,14 +357,51 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListMembers { start_after, limit } => {
             to_binary(&list_members(deps, start_after, limit)?)
         }
-        QueryMsg::TotalPoints {} => to_binary(&query_total_points(deps)?),
+        QueryMsg::ListMembersAtHeight {
+            height,
+            start_after,
+            limit,
+        } => to_binary(&list_members_at_height(deps, height, start_after, limit)?),
+        QueryMsg::TotalPoints { at_height } => to_binary(&query_total_points(deps, at_height)?),
+        QueryMsg::TotalPointsAtHeight { height } => {
+            to_binary(&query_total_points(deps, Some(height))?)
+        }
         QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::AdminPolicies {} => to_binary(&query_admin_policies(deps)?),
         QueryMsg::Hooks {} => to_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::Application { addr } => to_binary(&query_application(deps, addr)?),
+        QueryMsg::ListApplications { start_after, limit } => {
+            to_binary(&list_applications(deps, start_after, limit)?)
+        }
+    }
+}
+
+fn query_admin_policies(deps: Deps) -> StdResult<AdminPoliciesResponse> {
+    let mut policies = vec![];
+    for action in AdminAction::ALL {
+        if let Some(controller) = POLICIES.may_load(deps.storage, action.as_str())? {
+            policies.push(AdminPolicy {
+                action,
+                controller: controller.into(),
+            });
+        }
     }
+    Ok(AdminPoliciesResponse { policies })
+}
+
+fn query_application(deps: Deps, addr: String) -> StdResult<ApplicationResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let application = APPLICATIONS.may_load(deps.storage, &addr)?;
+    Ok(ApplicationResponse { application })
 }
 
-fn query_total_points(deps: Deps) -> StdResult<TotalPointsResponse> {
-    let points = TOTAL.load(deps.storage)?;
+fn query_total_points(deps: Deps, height: Option<u64>) -> StdResult<TotalPointsResponse> {
+    let points = match height {
+        Some(h) => TOTAL
+            .may_load_at_height(deps.storage, h)?
+            .unwrap_or_default(),
+        None => TOTAL.load(deps.storage)?,
+    };
     Ok(TotalPointsResponse { points })
 }
 
@@ -215,6 +442,61 @@ fn list_members(
     Ok(MemberListResponse { members })
 }
 
+/// Pages over current members, returning the points of those which existed at `height`. Members
+/// which joined later are skipped, so a page can be shorter than `limit`.
+fn list_members_at_height(
+    deps: Deps,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let members = MEMBERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .filter_map(|addr| {
+            addr.and_then(|addr| {
+                let member_info = MEMBERS.may_load_at_height(deps.storage, &addr, height)?;
+                Ok(member_info.map(|member_info| Member {
+                    addr: addr.into(),
+                    points: member_info.points,
+                    start_height: member_info.start_height,
+                }))
+            })
+            .transpose()
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(MemberListResponse { members })
+}
+
+fn list_applications(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ApplicationListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let applications = APPLICATIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(addr, application)| ApplicationInfo {
+                addr: addr.into(),
+                metadata: application.metadata,
+                applied_at: application.applied_at,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ApplicationListResponse { applications })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,6 +525,7 @@ mod tests {
                     start_height: None,
                 },
             ],
+            policies: vec![],
         };
         let info = mock_info("creator", &[]);
         instantiate(deps, mock_env(), info, msg).unwrap();
@@ -257,7 +540,7 @@ mod tests {
         let res = ADMIN.query_admin(deps.as_ref()).unwrap();
         assert_eq!(Some(INIT_ADMIN.into()), res.admin);
 
-        let res = query_total_points(deps.as_ref()).unwrap();
+        let res = query_total_points(deps.as_ref(), None).unwrap();
         assert_eq!(17, res.points);
     }
 
@@ -307,7 +590,7 @@ mod tests {
             let members = list_members(deps.as_ref(), None, None).unwrap();
             assert_eq!(count, members.members.len());
 
-            let total = query_total_points(deps.as_ref()).unwrap();
+            let total = query_total_points(deps.as_ref(), None).unwrap();
             assert_eq!(sum, total.points); // 17 - 11 + 15 = 21
         }
     }
@@ -359,6 +642,92 @@ mod tests {
 
         // snapshot still shows old value
         assert_users(&deps, Some(11), Some(6), None, Some(height + 1));
+
+        // total points are snapshotted as well
+        let total = query_total_points(deps.as_ref(), Some(height)).unwrap();
+        assert_eq!(total.points, 0);
+        let total = query_total_points(deps.as_ref(), Some(height + 10)).unwrap();
+        assert_eq!(total.points, 17);
+        let total = query_total_points(deps.as_ref(), Some(height + 11)).unwrap();
+        assert_eq!(total.points, 21);
+    }
+
+    #[test]
+    fn members_at_height() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+        let height = mock_env().block.height;
+
+        update_members(
+            deps.as_mut(),
+            height + 10,
+            Addr::unchecked(INIT_ADMIN),
+            vec![Member {
+                addr: USER3.into(),
+                points: 15,
+                start_height: None,
+            }],
+            vec![USER1.into()],
+        )
+        .unwrap();
+        update_members(
+            deps.as_mut(),
+            height + 20,
+            Addr::unchecked(INIT_ADMIN),
+            vec![Member {
+                addr: USER2.into(),
+                points: 8,
+                start_height: None,
+            }],
+            vec![],
+        )
+        .unwrap();
+
+        let members_at = |height, start_after: Option<&str>, limit| {
+            list_members_at_height(deps.as_ref(), height, start_after.map(Into::into), limit)
+                .unwrap()
+                .members
+                .into_iter()
+                .map(|m| (m.addr, m.points))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(members_at(height, None, None), []);
+        // removed members are not listed
+        assert_eq!(members_at(height + 1, None, None), [(USER2.to_owned(), 6)]);
+        assert_eq!(
+            members_at(height + 11, None, None),
+            [(USER2.to_owned(), 6), (USER3.to_owned(), 15)]
+        );
+        assert_eq!(
+            members_at(height + 21, None, None),
+            [(USER2.to_owned(), 8), (USER3.to_owned(), 15)]
+        );
+
+        // members which joined later are skipped, so pages can be shorter than the limit
+        assert_eq!(members_at(height + 1, Some(USER2), Some(1)), []);
+        assert_eq!(
+            members_at(height + 11, None, Some(1)),
+            [(USER2.to_owned(), 6)]
+        );
+        assert_eq!(
+            members_at(height + 11, Some(USER2), Some(1)),
+            [(USER3.to_owned(), 15)]
+        );
+        assert_eq!(members_at(height + 11, Some(USER3), Some(1)), []);
+
+        let total: TotalPointsResponse = from_slice(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TotalPointsAtHeight {
+                    height: height + 11,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(total.points, 21);
     }
 
     #[test]
@@ -550,6 +919,240 @@ mod tests {
         assert_eq!(res.messages, vec![msg1, msg2]);
     }
 
+    #[test]
+    fn applications_are_queued() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+
+        let apply = |metadata: &str| ExecuteMsg::Apply {
+            metadata: metadata.to_owned(),
+        };
+
+        // members cannot apply
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER1, &[]), apply("")).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyMember(USER1.to_owned()));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER3, &[]),
+            apply("kyc done"),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("applicant", &[]),
+            apply(""),
+        )
+        .unwrap();
+
+        // cannot apply twice
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER3, &[]), apply("")).unwrap_err();
+        assert_eq!(err, ContractError::ApplicationPending(USER3.to_owned()));
+
+        let application = query_application(deps.as_ref(), USER3.into()).unwrap();
+        assert_eq!(
+            application.application,
+            Some(Application {
+                metadata: "kyc done".to_owned(),
+                applied_at: mock_env().block.height,
+            })
+        );
+
+        // applications are paginated by address
+        let list = list_applications(deps.as_ref(), None, Some(1)).unwrap();
+        assert_eq!(list.applications.len(), 1);
+        assert_eq!(list.applications[0].addr, "applicant");
+        let list = list_applications(deps.as_ref(), Some("applicant".to_owned()), Some(1)).unwrap();
+        assert_eq!(list.applications.len(), 1);
+        assert_eq!(list.applications[0].addr, USER3);
+
+        // application can be withdrawn only once
+        let withdraw = ExecuteMsg::WithdrawApplication {};
+        let info = mock_info("applicant", &[]);
+        execute(deps.as_mut(), mock_env(), info.clone(), withdraw.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info, withdraw).unwrap_err();
+        assert_eq!(err, ContractError::NoApplication("applicant".to_owned()));
+
+        let list = list_applications(deps.as_ref(), None, None).unwrap();
+        assert_eq!(list.applications.len(), 1);
+    }
+
+    #[test]
+    fn applications_are_processed_by_admin() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+
+        let admin_info = mock_info(INIT_ADMIN, &[]);
+        let hook_msg = ExecuteMsg::AddHook {
+            addr: "hook".to_owned(),
+        };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), hook_msg).unwrap();
+
+        for applicant in [USER3, "rejected"] {
+            let msg = ExecuteMsg::Apply {
+                metadata: String::new(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(applicant, &[]), msg).unwrap();
+        }
+
+        let process = ExecuteMsg::ProcessApplications {
+            approve: vec![Member {
+                addr: USER3.into(),
+                points: 5,
+                start_height: None,
+            }],
+            reject: vec!["rejected".to_owned()],
+        };
+
+        // non-admin cannot process applications
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER1, &[]),
+            process.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, AdminError::NotAdmin {}.into());
+
+        // only pending applications can be processed
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info.clone(),
+            ExecuteMsg::ProcessApplications {
+                approve: vec![],
+                reject: vec![USER2.to_owned()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoApplication(USER2.to_owned()));
+
+        let res = execute(deps.as_mut(), mock_env(), admin_info, process).unwrap();
+        assert_users(&deps, Some(11), Some(6), Some(5), None);
+
+        // hooks are informed about new member
+        let hook_msg = MemberChangedHookMsg {
+            diffs: vec![MemberDiff::new(USER3, None, Some(5))],
+        };
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(hook_msg.into_cosmos_msg("hook").unwrap())]
+        );
+
+        let list = list_applications(deps.as_ref(), None, None).unwrap();
+        assert!(list.applications.is_empty());
+    }
+
+    #[test]
+    fn admin_policies_restrict_actions() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+
+        let admin_info = mock_info(INIT_ADMIN, &[]);
+        let oc_info = mock_info("oc_contract", &[]);
+        let update_members = ExecuteMsg::UpdateMembers {
+            add: vec![Member {
+                addr: USER3.into(),
+                points: 5,
+                start_height: None,
+            }],
+            remove: vec![],
+        };
+
+        // only admin can restrict actions
+        let set_policy = ExecuteMsg::SetAdminPolicy {
+            action: AdminAction::UpdateMembers,
+            controller: Some("oc_contract".to_owned()),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            oc_info.clone(),
+            set_policy.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, AdminError::NotAdmin {}.into());
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), set_policy).unwrap();
+
+        let policies = query_admin_policies(deps.as_ref()).unwrap();
+        assert_eq!(
+            policies.policies,
+            vec![AdminPolicy {
+                action: AdminAction::UpdateMembers,
+                controller: "oc_contract".to_owned(),
+            }]
+        );
+
+        // restricted action can be executed by the controller only
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info.clone(),
+            update_members.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, AdminError::NotAdmin {}.into());
+        execute(deps.as_mut(), mock_env(), oc_info.clone(), update_members).unwrap();
+        assert_users(&deps, Some(11), Some(6), Some(5), None);
+
+        // other actions are still executed by admin
+        let add_hook = ExecuteMsg::AddHook {
+            addr: "hook".to_owned(),
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), oc_info.clone(), add_hook.clone()).unwrap_err();
+        assert_eq!(err, HookError::Admin(AdminError::NotAdmin {}).into());
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), add_hook).unwrap();
+
+        // once admin rights are handed over, admin cannot change the admin anymore
+        let set_policy = ExecuteMsg::SetAdminPolicy {
+            action: AdminAction::UpdateAdmin,
+            controller: Some("voting".to_owned()),
+        };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), set_policy).unwrap();
+        let update_admin = ExecuteMsg::UpdateAdmin { admin: None };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info.clone(),
+            update_admin.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, AdminError::NotAdmin {}.into());
+
+        // removing the policy gives the action back to the admin
+        let remove_policy = ExecuteMsg::SetAdminPolicy {
+            action: AdminAction::UpdateMembers,
+            controller: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("voting", &[]),
+            remove_policy,
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("voting", &[]),
+            update_admin,
+        )
+        .unwrap();
+        assert_eq!(ADMIN.get(deps.as_ref()).unwrap(), None);
+
+        let policies = query_admin_policies(deps.as_ref()).unwrap();
+        assert_eq!(
+            policies.policies,
+            vec![AdminPolicy {
+                action: AdminAction::UpdateAdmin,
+                controller: "voting".to_owned(),
+            }]
+        );
+    }
+
     #[test]
     fn raw_queries_work() {
         // add will over-write and remove have no effect
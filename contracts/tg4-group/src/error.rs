@@ -16,4 +16,13 @@ pub enum ContractError {
 
     #[error("Unauthorized")]
     Unauthorized {},
+
+    #[error("Already a member: {0}")]
+    AlreadyMember(String),
+
+    #[error("Application already pending for {0}")]
+    ApplicationPending(String),
+
+    #[error("No pending application for {0}")]
+    NoApplication(String),
 }
@@ -40,4 +40,13 @@ impl Tg4GroupContract {
         let msg = ExecuteMsg::UpdateMembers { remove, add };
         self.encode_msg(msg)
     }
+
+    pub fn process_applications(
+        &self,
+        approve: Vec<Member>,
+        reject: Vec<String>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::ProcessApplications { approve, reject };
+        self.encode_msg(msg)
+    }
 }
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 
 use tg4::Member;
 
+use crate::state::{AdminAction, Application};
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
@@ -10,23 +12,55 @@ pub struct InstantiateMsg {
     /// Omit it to make the group immutable.
     pub admin: Option<String>,
     pub members: Vec<Member>,
+    /// Admin actions restricted to other controllers than the admin
+    #[serde(default)]
+    pub policies: Vec<AdminPolicy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct AdminPolicy {
+    pub action: AdminAction,
+    /// The only address allowed to execute the action
+    pub controller: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    /// Change the admin
+    /// Change the admin. Must be called by Admin, or by the `update_admin` controller instead,
+    /// if set
     UpdateAdmin { admin: Option<String> },
+    /// Restricts the action to the controller, or gives it back to the admin if no controller
+    /// is set. Must be called by Admin, or by the `update_admin` controller instead, if set
+    SetAdminPolicy {
+        action: AdminAction,
+        controller: Option<String>,
+    },
     /// apply a diff to the existing members.
-    /// remove is applied after add, so if an address is in both, it is removed
+    /// remove is applied after add, so if an address is in both, it is removed.
+    /// Must be called by Admin, or by the `update_members` controller instead, if set
     UpdateMembers {
         remove: Vec<String>,
         add: Vec<Member>,
     },
-    /// Add a new hook to be informed of all membership changes. Must be called by Admin
+    /// Add a new hook to be informed of all membership changes. Must be called by Admin,
+    /// or by the `manage_hooks` controller instead, if set
     AddHook { addr: String },
-    /// Remove a hook. Must be called by Admin
+    /// Remove a hook. Must be called by Admin, or by the `manage_hooks` controller instead,
+    /// if set
     RemoveHook { addr: String },
+    /// Applies for the group membership. Application is queued until the admin processes it.
+    /// Cannot be called by an existing member, nor by someone with an application already pending.
+    Apply { metadata: String },
+    /// Withdraws the pending application of the sender
+    WithdrawApplication {},
+    /// Processes pending applications in a batch. Approved applicants are added as members with
+    /// the given points, rejected ones are just removed from the queue. Must be called by Admin,
+    /// or by the `update_members` controller instead, if set
+    ProcessApplications {
+        approve: Vec<Member>,
+        reject: Vec<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -34,13 +68,26 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Return AdminResponse
     Admin {},
-    /// Return TotalPointsResponse
-    TotalPoints {},
+    /// Returns AdminPoliciesResponse - actions restricted to other controllers than the admin
+    AdminPolicies {},
+    /// Return TotalPointsResponse. If at_height is set, returns total points at the
+    /// beginning of the given block.
+    TotalPoints { at_height: Option<u64> },
     /// Returns MembersListResponse
     ListMembers {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Returns MembersListResponse with current members and their points at the beginning of
+    /// the given block. Members which joined later are skipped, so a page can be shorter than
+    /// `limit`
+    ListMembersAtHeight {
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return TotalPointsResponse with total points at the beginning of the given block
+    TotalPointsAtHeight { height: u64 },
     /// Returns MemberResponse
     Member {
         addr: String,
@@ -48,4 +95,34 @@ pub enum QueryMsg {
     },
     /// Shows all registered hooks. Returns HooksResponse.
     Hooks {},
+    /// Returns ApplicationResponse
+    Application { addr: String },
+    /// Paginates over pending applications, ordered by applicant address.
+    /// Returns ApplicationListResponse
+    ListApplications {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct AdminPoliciesResponse {
+    pub policies: Vec<AdminPolicy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ApplicationResponse {
+    pub application: Option<Application>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ApplicationInfo {
+    pub addr: String,
+    pub metadata: String,
+    pub applied_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ApplicationListResponse {
+    pub applications: Vec<ApplicationInfo>,
 }
@@ -1,12 +1,19 @@
 use cosmwasm_std::Addr;
 use cw_controllers::{Admin, Hooks};
-use cw_storage_plus::{Item, SnapshotMap, Strategy};
+use cw_storage_plus::{Map, SnapshotItem, SnapshotMap, Strategy};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tg4::{MemberInfo, TOTAL_KEY};
 
 pub const ADMIN: Admin = Admin::new("admin");
 pub const HOOKS: Hooks = Hooks::new("tg4-hooks");
 
-pub const TOTAL: Item<u64> = Item::new(TOTAL_KEY);
+pub const TOTAL: SnapshotItem<u64> = SnapshotItem::new(
+    TOTAL_KEY,
+    tg4::TOTAL_CHECKPOINTS,
+    tg4::TOTAL_CHANGELOG,
+    Strategy::EveryBlock,
+);
 
 pub const MEMBERS: SnapshotMap<&Addr, MemberInfo> = SnapshotMap::new(
     tg4::MEMBERS_KEY,
@@ -14,3 +21,46 @@ pub const MEMBERS: SnapshotMap<&Addr, MemberInfo> = SnapshotMap::new(
     tg4::MEMBERS_CHANGELOG,
     Strategy::EveryBlock,
 );
+
+/// Pending membership application
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Application {
+    /// Any additional information provided by the applicant
+    pub metadata: String,
+    /// Height at which the application was submitted
+    pub applied_at: u64,
+}
+
+pub const APPLICATIONS: Map<&Addr, Application> = Map::new("applications");
+
+/// Admin actions which can be restricted to a separate controller
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    /// `UpdateMembers` and `ProcessApplications`
+    UpdateMembers,
+    /// `UpdateAdmin` and `SetAdminPolicy`
+    UpdateAdmin,
+    /// `AddHook` and `RemoveHook`
+    ManageHooks,
+}
+
+impl AdminAction {
+    pub const ALL: [AdminAction; 3] = [
+        AdminAction::UpdateMembers,
+        AdminAction::UpdateAdmin,
+        AdminAction::ManageHooks,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminAction::UpdateMembers => "update_members",
+            AdminAction::UpdateAdmin => "update_admin",
+            AdminAction::ManageHooks => "manage_hooks",
+        }
+    }
+}
+
+/// Controller allowed to execute the admin action instead of the admin. Actions without a policy
+/// are executed by the admin.
+pub const POLICIES: Map<&str, Addr> = Map::new("admin_policies");
@@ -1,17 +1,21 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
     to_binary, Addr, Binary, CustomQuery, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order,
-    StdError, StdResult,
+    Reply, StdError, StdResult, Storage, SubMsgResult,
 };
 
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use cw_utils::{ensure_from_older_version, maybe_addr};
 
-use tg_bindings::{TgradeMsg, TgradeQuery};
+use tg_bindings::{
+    request_privileges, Privilege, PrivilegeChangeMsg, TgradeMsg, TgradeQuery, TgradeSudoMsg,
+};
 use tg_utils::{
-    validate_portion, SlashMsg, HOOKS, PREAUTH_HOOKS, PREAUTH_SLASHING, SLASHERS, TOTAL,
+    validate_portion, SlashMsg, ADMIN, HOOKS, PREAUTH_HOOKS, PREAUTH_SLASHING, SLASHERS, TOTAL,
 };
 
 use tg4::{
@@ -20,13 +24,15 @@ use tg4::{
 };
 
 use crate::error::ContractError;
-use crate::functions::PoEFunction;
+use crate::functions::{Clamped, PoEFunction};
 use crate::member_indexes::members;
 use crate::msg::{
-    ExecuteMsg, GroupsResponse, InstantiateMsg, MixerFunctionResponse, PoEFunctionType,
-    PreauthResponse, QueryMsg,
+    ExecuteMsg, GroupSide, GroupsResponse, InstantiateMsg, MemberBreakdownResponse,
+    MixerFunctionResponse, PoEFunctionType, PreauthResponse, QueryMsg, SourceClampsResponse,
+};
+use crate::state::{
+    Groups, GROUPS, HOOK_BATCH_LIMIT, POE_FUNCTION_TYPE, QUEUED_MEMBERS, SOURCE_CLAMPS,
 };
-use crate::state::{Groups, GROUPS, POE_FUNCTION_TYPE};
 
 pub type Response = cosmwasm_std::Response<TgradeMsg>;
 pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
@@ -35,14 +41,24 @@ pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
 const CONTRACT_NAME: &str = "crates.io:tg4-mixer";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply to unregistering from the replaced upstream group
+const UNBIND_REPLY_ID: u64 = 1;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut<TgradeQuery>,
+    mut deps: DepsMut<TgradeQuery>,
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let api = deps.api;
+    ADMIN.set(
+        deps.branch(),
+        msg.admin
+            .map(|admin| api.addr_validate(&admin))
+            .transpose()?,
+    )?;
     PREAUTH_HOOKS.set_auth(deps.storage, msg.preauths_hooks)?;
     PREAUTH_SLASHING.set_auth(deps.storage, msg.preauths_slashing)?;
 
@@ -51,6 +67,19 @@ pub fn instantiate(
     // Store the PoE function type / params
     POE_FUNCTION_TYPE.save(deps.storage, &msg.function_type)?;
 
+    msg.source_clamps.validate()?;
+    SOURCE_CLAMPS.save(deps.storage, &msg.source_clamps)?;
+
+    if let Some(limit) = msg.hook_batch_limit {
+        if limit == 0 {
+            return Err(ContractError::ParameterRange(
+                "hook_batch_limit",
+                limit.to_string(),
+            ));
+        }
+        HOOK_BATCH_LIMIT.save(deps.storage, &limit)?;
+    }
+
     // validate the two input groups and save
     let left = verify_tg4_input(deps.as_ref(), &msg.left_group)?;
     let right = verify_tg4_input(deps.as_ref(), &msg.right_group)?;
@@ -62,6 +91,7 @@ pub fn instantiate(
     // that these contracts must implement.
     let slash_msg = to_binary(&SlashMsg::AddSlasher {
         addr: env.contract.address.to_string(),
+        max_portion: None,
     })?;
 
     // add hooks to listen for all changes
@@ -72,13 +102,20 @@ pub fn instantiate(
         .add_submessage(groups.right.encode_raw_msg(slash_msg)?);
 
     // Instantiate PoE function
-    let poe_function = msg.function_type.to_poe_fn()?;
+    let poe_function = load_poe_fn(deps.storage)?;
 
     // calculate initial state from current members on both sides
     initialize_members(deps, groups, &*poe_function, env.block.height)?;
     Ok(res)
 }
 
+/// Loads the configured PoE function, clamping upstream points before mixing
+fn load_poe_fn(storage: &dyn Storage) -> Result<Box<dyn PoEFunction>, ContractError> {
+    let poe_function = POE_FUNCTION_TYPE.load(storage)?.to_poe_fn()?;
+    let clamps = SOURCE_CLAMPS.may_load(storage)?.unwrap_or_default();
+    Ok(Box::new(Clamped::new(clamps, poe_function)))
+}
+
 fn verify_tg4_input<Q: CustomQuery>(
     deps: Deps<Q>,
     addr: &str,
@@ -124,7 +161,7 @@ fn initialize_members<Q: CustomQuery>(
         // and get the next page
         batch = groups.left.list_members(&deps.querier, last, QUERY_LIMIT)?;
     }
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, height)?;
     Ok(())
 }
 
@@ -135,8 +172,15 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let api = deps.api;
     match msg {
+        ExecuteMsg::UpdateAdmin { admin } => Ok(ADMIN.execute_update_admin(
+            deps,
+            info,
+            admin.map(|admin| api.addr_validate(&admin)).transpose()?,
+        )?),
         ExecuteMsg::MemberChangedHook(changes) => execute_member_changed(deps, env, info, changes),
+        ExecuteMsg::RebindGroup { side, addr } => execute_rebind_group(deps, env, info, side, addr),
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
         ExecuteMsg::AddSlasher { addr } => execute_add_slasher(deps, info, addr),
@@ -158,21 +202,37 @@ pub fn execute_member_changed<Q: CustomQuery>(
 
     let groups = GROUPS.load(deps.storage)?;
 
+    // in queued mode, changes are only recorded and processed in the end block
+    if HOOK_BATCH_LIMIT.may_load(deps.storage)?.is_some() {
+        if info.sender != groups.left.addr() && info.sender != groups.right.addr() {
+            return Err(ContractError::Unauthorized(
+                "Sender is neither left nor right side group contract".to_owned(),
+            ));
+        }
+        for change in changes.diffs {
+            let member_addr = deps.api.addr_validate(&change.key)?;
+            QUEUED_MEMBERS.save(deps.storage, &member_addr, &Empty {})?;
+        }
+        return Ok(res.add_attribute("queued", "true"));
+    }
+
     // authorization check
     let diff = if info.sender == groups.left.addr() {
-        let poe_function = POE_FUNCTION_TYPE.load(deps.storage)?.to_poe_fn()?;
+        let poe_function = load_poe_fn(deps.storage)?;
         update_members(
             deps.branch(),
             env.block.height,
+            GroupSide::Left,
             groups.right,
             changes.diffs,
             &*poe_function,
         )
     } else if info.sender == groups.right.addr() {
-        let poe_function = POE_FUNCTION_TYPE.load(deps.storage)?.to_poe_fn()?;
+        let poe_function = load_poe_fn(deps.storage)?;
         update_members(
             deps.branch(),
             env.block.height,
+            GroupSide::Right,
             groups.left,
             changes.diffs,
             &*poe_function,
@@ -190,10 +250,12 @@ pub fn execute_member_changed<Q: CustomQuery>(
     Ok(res)
 }
 
-// the logic from execute_update_members extracted for easier re-usability
+// the logic from execute_update_members extracted for easier re-usability.
+// `changed` is the side reporting the changes, `query_group` is the other one.
 pub fn update_members<Q: CustomQuery>(
     deps: DepsMut<Q>,
     height: u64,
+    changed: GroupSide,
     query_group: Tg4Contract,
     changes: Vec<MemberDiff>,
     poe_function: &dyn PoEFunction,
@@ -206,7 +268,10 @@ pub fn update_members<Q: CustomQuery>(
         let member_addr = deps.api.addr_validate(&change.key)?;
         let new_points = match change.new {
             Some(x) => match query_group.is_member(&deps.querier, &member_addr)? {
-                Some(y) => Some(poe_function.mix(x, y)?),
+                Some(y) => Some(match changed {
+                    GroupSide::Left => poe_function.mix(x, y)?,
+                    GroupSide::Right => poe_function.mix(y, x)?,
+                }),
                 None => None,
             },
             None => None,
@@ -241,7 +306,173 @@ pub fn update_members<Q: CustomQuery>(
         ));
     }
 
-    TOTAL.save(deps.storage, &total)?;
+    TOTAL.save(deps.storage, &total, height)?;
+    Ok(MemberChangedHookMsg { diffs })
+}
+
+/// Recalculates mixed points of the given members from their current points in both upstream
+/// groups, returning the changes made
+fn recalculate_members<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    height: u64,
+    groups: &Groups,
+    addrs: Vec<Addr>,
+    poe_function: &dyn PoEFunction,
+) -> Result<MemberChangedHookMsg, ContractError> {
+    let mut total = TOTAL.load(deps.storage)?;
+    let mut diffs = vec![];
+
+    for addr in addrs {
+        QUEUED_MEMBERS.remove(deps.storage, &addr);
+
+        let left = groups.left.is_member(&deps.querier, &addr)?;
+        let right = groups.right.is_member(&deps.querier, &addr)?;
+        let new_points = match (left, right) {
+            (Some(left), Some(right)) => Some(poe_function.mix(left, right)?),
+            _ => None,
+        };
+
+        let prev = members().may_load(deps.storage, &addr)?;
+        let prev_points = prev.as_ref().map(|mi| mi.points);
+        if prev_points == new_points {
+            continue;
+        }
+        total -= prev_points.unwrap_or_default();
+        total += new_points.unwrap_or_default();
+
+        match new_points {
+            Some(points) => {
+                let start_height = prev.and_then(|mi| mi.start_height).unwrap_or(height);
+                members().save(
+                    deps.storage,
+                    &addr,
+                    &MemberInfo::new_with_height(points, start_height),
+                    height,
+                )?
+            }
+            None => members().remove(deps.storage, &addr, height)?,
+        };
+        diffs.push(MemberDiff::new(addr, prev_points, new_points));
+    }
+
+    TOTAL.save(deps.storage, &total, height)?;
+    Ok(MemberChangedHookMsg { diffs })
+}
+
+pub fn execute_rebind_group<Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    side: GroupSide,
+    addr: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let new_group = verify_tg4_input(deps.as_ref(), &addr)?;
+    let mut groups = GROUPS.load(deps.storage)?;
+    if new_group == groups.left || new_group == groups.right {
+        return Err(ContractError::GroupAlreadyBound(addr));
+    }
+    let old_group = match side {
+        GroupSide::Left => std::mem::replace(&mut groups.left, new_group.clone()),
+        GroupSide::Right => std::mem::replace(&mut groups.right, new_group.clone()),
+    };
+    GROUPS.save(deps.storage, &groups)?;
+
+    let contract = env.contract.address.to_string();
+    let remove_slasher = to_binary(&SlashMsg::RemoveSlasher {
+        addr: contract.clone(),
+    })?;
+    let add_slasher = to_binary(&SlashMsg::AddSlasher {
+        addr: contract.clone(),
+        max_portion: None,
+    })?;
+
+    // the old group may be already gone or broken, which shouldn't prevent re-binding
+    let unbind = vec![
+        old_group.remove_hook(&contract)?,
+        old_group.encode_raw_msg(remove_slasher)?,
+    ]
+    .into_iter()
+    .map(|sub| SubMsg::reply_on_error(sub.msg, UNBIND_REPLY_ID));
+
+    let poe_function = load_poe_fn(deps.storage)?;
+    let diff = rebalance_members(deps.branch(), groups, &*poe_function, env.block.height)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "rebind_group")
+        .add_attribute("old_group", old_group.addr())
+        .add_attribute("new_group", new_group.addr())
+        .add_attribute("changed", diff.diffs.len().to_string())
+        .add_attribute("sender", info.sender)
+        .add_submessages(unbind)
+        .add_submessage(new_group.add_hook(&contract)?)
+        .add_submessage(new_group.encode_raw_msg(add_slasher)?);
+
+    // call all registered hooks
+    if !diff.diffs.is_empty() {
+        res.messages.extend(HOOKS.prepare_hooks(deps.storage, |h| {
+            diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+        })?);
+    }
+    Ok(res)
+}
+
+/// Recalculates points of all members from scratch, returning the changes made
+fn rebalance_members<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    groups: Groups,
+    poe_function: &dyn PoEFunction,
+    height: u64,
+) -> Result<MemberChangedHookMsg, ContractError> {
+    let mut old: BTreeMap<Addr, MemberInfo> = members()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    let mut total = 0u64;
+    let mut diffs = vec![];
+    let mut batch = groups.left.list_members(&deps.querier, None, QUERY_LIMIT)?;
+    while !batch.is_empty() {
+        let last = Some(batch.last().unwrap().addr.clone());
+        for member in batch.into_iter() {
+            let addr = deps.api.addr_validate(&member.addr)?;
+            let right = match groups.right.is_member(&deps.querier, &addr)? {
+                Some(right) => right,
+                None => continue,
+            };
+            let points = poe_function.mix(member.points, right)?;
+            total += points;
+
+            let prev = old.remove(&addr);
+            if prev.as_ref().map(|mi| mi.points) == Some(points) {
+                continue;
+            }
+            let start_height = prev
+                .as_ref()
+                .and_then(|mi| mi.start_height)
+                .unwrap_or(height);
+            members().save(
+                deps.storage,
+                &addr,
+                &MemberInfo::new_with_height(points, start_height),
+                height,
+            )?;
+            diffs.push(MemberDiff::new(
+                addr,
+                prev.map(|mi| mi.points),
+                Some(points),
+            ));
+        }
+        batch = groups.left.list_members(&deps.querier, last, QUERY_LIMIT)?;
+    }
+
+    // whoever is left is not a member of both groups anymore
+    for (addr, prev) in old {
+        members().remove(deps.storage, &addr, height)?;
+        diffs.push(MemberDiff::new(addr, Some(prev.points), None));
+    }
+
+    TOTAL.save(deps.storage, &total, height)?;
     Ok(MemberChangedHookMsg { diffs })
 }
 
@@ -297,7 +528,7 @@ pub fn execute_add_slasher<Q: CustomQuery>(
     PREAUTH_SLASHING.use_auth(deps.storage)?;
 
     // add the slasher
-    SLASHERS.add_slasher(deps.storage, deps.api.addr_validate(&slasher)?)?;
+    SLASHERS.add_slasher(deps.storage, deps.api.addr_validate(&slasher)?, None)?;
 
     // response
     let res = Response::new()
@@ -366,6 +597,7 @@ pub fn execute_slash<Q: CustomQuery>(
 pub fn query(deps: Deps<TgradeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     use QueryMsg::*;
     match msg {
+        Admin {} => to_binary(&ADMIN.query_admin(deps)?),
         Member {
             addr,
             at_height: height,
@@ -374,7 +606,7 @@ pub fn query(deps: Deps<TgradeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Bin
         ListMembersByPoints { start_after, limit } => {
             to_binary(&list_members_by_points(deps, start_after, limit)?)
         }
-        TotalPoints {} => to_binary(&query_total_points(deps)?),
+        TotalPoints { at_height } => to_binary(&query_total_points(deps, at_height)?),
         Groups {} => to_binary(&query_groups(deps)?),
         Hooks {} => {
             let hooks = HOOKS.list_hooks(deps.storage)?;
@@ -398,11 +630,24 @@ pub fn query(deps: Deps<TgradeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Bin
             to_binary(&SLASHERS.is_slasher(deps.storage, &addr)?)
         }
         ListSlashers {} => to_binary(&SLASHERS.list_slashers(deps.storage)?),
+        MemberBreakdown { addr } => to_binary(&query_member_breakdown(deps, addr)?),
+        SourceClamps {} => {
+            let clamps = SOURCE_CLAMPS.may_load(deps.storage)?.unwrap_or_default();
+            to_binary(&SourceClampsResponse { clamps })
+        }
     }
 }
 
-fn query_total_points<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<TotalPointsResponse> {
-    let points = TOTAL.load(deps.storage)?;
+fn query_total_points<Q: CustomQuery>(
+    deps: Deps<Q>,
+    height: Option<u64>,
+) -> StdResult<TotalPointsResponse> {
+    let points = match height {
+        Some(h) => TOTAL
+            .may_load_at_height(deps.storage, h)?
+            .unwrap_or_default(),
+        None => TOTAL.load(deps.storage)?,
+    };
     Ok(TotalPointsResponse { points })
 }
 
@@ -427,6 +672,20 @@ fn query_member<Q: CustomQuery>(
     Ok(mi.into())
 }
 
+fn query_member_breakdown<Q: CustomQuery>(
+    deps: Deps<Q>,
+    addr: String,
+) -> StdResult<MemberBreakdownResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let groups = GROUPS.load(deps.storage)?;
+    Ok(MemberBreakdownResponse {
+        left_points: groups.left.is_member(&deps.querier, &addr)?,
+        right_points: groups.right.is_member(&deps.querier, &addr)?,
+        points: members().may_load(deps.storage, &addr)?.map(|mi| mi.points),
+        poe_function: POE_FUNCTION_TYPE.load(deps.storage)?,
+    })
+}
+
 // settings for pagination
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 30;
@@ -518,6 +777,80 @@ pub fn query_mixer_function<Q: CustomQuery>(
     poe_function.mix(stake, engagement)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(
+    deps: DepsMut<TgradeQuery>,
+    env: Env,
+    msg: TgradeSudoMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        TgradeSudoMsg::PrivilegeChange(PrivilegeChangeMsg::Promoted {}) => privilege_promote(deps),
+        TgradeSudoMsg::EndBlock {} => end_block(deps, env),
+        _ => Err(ContractError::UnknownSudoMsg {}),
+    }
+}
+
+fn privilege_promote<Q: CustomQuery>(deps: DepsMut<Q>) -> Result<Response, ContractError> {
+    let mut res = Response::new();
+    // end block is only needed for processing queued changes
+    if HOOK_BATCH_LIMIT.may_load(deps.storage)?.is_some() {
+        res = res.add_submessages(request_privileges(&[Privilege::EndBlocker]));
+    }
+    Ok(res)
+}
+
+/// Processes up to `HOOK_BATCH_LIMIT` queued members, sending the changes to registered hooks
+fn end_block<Q: CustomQuery>(mut deps: DepsMut<Q>, env: Env) -> Result<Response, ContractError> {
+    let limit = match HOOK_BATCH_LIMIT.may_load(deps.storage)? {
+        Some(limit) => limit,
+        None => return Ok(Response::new()),
+    };
+    let addrs = QUEUED_MEMBERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    if addrs.is_empty() {
+        return Ok(Response::new());
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "process_queued_members")
+        .add_attribute("processed", addrs.len().to_string());
+
+    let groups = GROUPS.load(deps.storage)?;
+    let poe_function = load_poe_fn(deps.storage)?;
+    let diff = recalculate_members(
+        deps.branch(),
+        env.block.height,
+        &groups,
+        addrs,
+        &*poe_function,
+    )?;
+
+    // call all registered hooks
+    if !diff.diffs.is_empty() {
+        res.messages = HOOKS.prepare_hooks(deps.storage, |h| {
+            diff.clone().into_cosmos_msg(h).map(SubMsg::new)
+        })?;
+    }
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    _deps: DepsMut<TgradeQuery>,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        (UNBIND_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "unbind_failed")
+            .add_attribute("error", err)),
+        (UNBIND_REPLY_ID, SubMsgResult::Ok(_)) => Ok(Response::new()),
+        (id, _) => Err(StdError::generic_err(format!("Unknown reply id: {}", id)).into()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
     deps: DepsMut<TgradeQuery>,
@@ -531,8 +864,9 @@ pub fn migrate(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::msg::PoEFunctionType;
-    use cosmwasm_std::{coins, Addr, BankMsg, Uint128};
+    use crate::msg::{PoEFunctionType, SourceClamps};
+    use cosmwasm_std::{coins, Addr, BankMsg, Uint128, Uint64};
+    use cw_controllers::AdminError;
     use cw_multi_test::{next_block, AppBuilder, BasicApp, Contract, ContractWrapper, Executor};
     use tg_bindings::{TgradeMsg, TgradeQuery};
 
@@ -559,7 +893,9 @@ mod tests {
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
-        );
+        )
+        .with_reply(crate::contract::reply)
+        .with_sudo(crate::contract::sudo);
         Box::new(contract)
     }
 
@@ -592,6 +928,9 @@ mod tests {
             preauths_slashing: 1,
             halflife: None,
             denom: STAKE_DENOM.to_owned(),
+            min_distribution: Uint128::zero(),
+            distribution_interval: None,
+            withdrawal_stream_period: None,
         };
         app.instantiate_contract(group_id, Addr::unchecked(OWNER), &msg, &[], "group", admin)
             .unwrap()
@@ -613,6 +952,15 @@ mod tests {
             preauths_hooks: 1,
             preauths_slashing: 1,
             auto_return_limit: 0,
+            unbond_veto_period: None,
+            max_bond_per_address: None,
+            max_total_bond: None,
+            exit_fee: None,
+            lockup_tiers: vec![],
+            average_stake_period: None,
+            unbonding_interest: None,
+            denom_metadata: None,
+            max_pause_duration: None,
         };
         let contract = app
             .instantiate_contract(
@@ -646,14 +994,19 @@ mod tests {
         app: &mut BasicApp<TgradeMsg, TgradeQuery>,
         left: &Addr,
         right: &Addr,
+        hook_batch_limit: Option<u32>,
+        source_clamps: SourceClamps,
     ) -> Addr {
         let flex_id = app.store_code(contract_mixer());
         let msg = crate::msg::InstantiateMsg {
+            admin: Some(OWNER.into()),
             left_group: left.to_string(),
             right_group: right.to_string(),
             preauths_hooks: 0,
             preauths_slashing: 1,
             function_type: PoEFunctionType::GeometricMean {},
+            hook_batch_limit,
+            source_clamps,
         };
         app.instantiate_contract(flex_id, Addr::unchecked(OWNER), &msg, &[], "mixer", None)
             .unwrap()
@@ -667,6 +1020,16 @@ mod tests {
     fn setup_test_case(
         app: &mut BasicApp<TgradeMsg, TgradeQuery>,
         stakers: Vec<Member>,
+    ) -> (Addr, Addr, Addr) {
+        setup_test_case_with_batch_limit(app, stakers, None)
+    }
+
+    /// Same as `setup_test_case`, but the mixer queues upstream changes if `hook_batch_limit`
+    /// is set
+    fn setup_test_case_with_batch_limit(
+        app: &mut BasicApp<TgradeMsg, TgradeQuery>,
+        stakers: Vec<Member>,
+        hook_batch_limit: Option<u32>,
     ) -> (Addr, Addr, Addr) {
         // 1. Instantiate group contract with members (and OWNER as admin)
         let members = vec![
@@ -685,7 +1048,13 @@ mod tests {
         app.update_block(next_block);
 
         // 3. Set up mixer backed by these two groups
-        let mixer_addr = instantiate_mixer(app, &group_addr, &stake_addr);
+        let mixer_addr = instantiate_mixer(
+            app,
+            &group_addr,
+            &stake_addr,
+            hook_batch_limit,
+            SourceClamps::default(),
+        );
         app.update_block(next_block);
 
         (mixer_addr, group_addr, stake_addr)
@@ -776,6 +1145,187 @@ mod tests {
         );
     }
 
+    #[test]
+    fn member_breakdown() {
+        let stakers = vec![
+            member(VOTER1, 10000), // 10000 stake, 100 points -> 1000 mixed
+            member(VOTER2, 50),    // below stake threshold -> None
+        ];
+
+        let mut app = AppBuilder::new_custom().build(|router, _, storage| {
+            for staker in &stakers {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(&staker.addr),
+                        coins(staker.points as u128, STAKE_DENOM),
+                    )
+                    .unwrap();
+            }
+        });
+
+        let (mixer_addr, _, _) = setup_test_case(&mut app, stakers);
+
+        let breakdown = |addr: &str| -> MemberBreakdownResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &mixer_addr,
+                    &QueryMsg::MemberBreakdown { addr: addr.into() },
+                )
+                .unwrap()
+        };
+
+        assert_eq!(
+            breakdown(VOTER1),
+            MemberBreakdownResponse {
+                left_points: Some(100),
+                right_points: Some(10000),
+                points: Some(1000),
+                poe_function: PoEFunctionType::GeometricMean {},
+            }
+        );
+        assert_eq!(
+            breakdown(VOTER2),
+            MemberBreakdownResponse {
+                left_points: Some(200),
+                right_points: None,
+                points: None,
+                poe_function: PoEFunctionType::GeometricMean {},
+            }
+        );
+    }
+
+    #[test]
+    fn source_clamps() {
+        let stakers = vec![
+            member(VOTER1, 10000), // 10000 stake capped at 10x 100 points -> 316 mixed
+            member(VOTER3, 7500),  // 7500 stake capped at 2500, 300 points -> 866 mixed
+        ];
+
+        let mut app = AppBuilder::new_custom().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(RESERVE),
+                    coins(10000, STAKE_DENOM),
+                )
+                .unwrap();
+
+            for staker in &stakers {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(&staker.addr),
+                        coins(staker.points as u128, STAKE_DENOM),
+                    )
+                    .unwrap();
+            }
+        });
+
+        let members = vec![
+            member(VOTER1, 100),
+            member(VOTER3, 300),
+            member(VOTER5, 500),
+        ];
+        let group_addr = instantiate_group(&mut app, members);
+        let stake_addr = instantiate_staking(&mut app, stakers);
+        app.update_block(next_block);
+
+        // invalid clamps are rejected
+        let flex_id = app.store_code(contract_mixer());
+        let invalid = crate::msg::InstantiateMsg {
+            admin: None,
+            left_group: group_addr.to_string(),
+            right_group: stake_addr.to_string(),
+            preauths_hooks: 0,
+            preauths_slashing: 1,
+            function_type: PoEFunctionType::GeometricMean {},
+            hook_batch_limit: None,
+            source_clamps: SourceClamps {
+                right_min: Some(Uint64::new(3000)),
+                right_max: Some(Uint64::new(2500)),
+                ..SourceClamps::default()
+            },
+        };
+        let err = app
+            .instantiate_contract(
+                flex_id,
+                Addr::unchecked(OWNER),
+                &invalid,
+                &[],
+                "mixer",
+                None,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            ContractError::ParameterRange("right_min", _)
+        ));
+
+        let clamps = SourceClamps {
+            right_max: Some(Uint64::new(2500)),
+            right_max_ratio: Some(Decimal::from_ratio(10u8, 1u8)),
+            ..SourceClamps::default()
+        };
+        let mixer_addr =
+            instantiate_mixer(&mut app, &group_addr, &stake_addr, None, clamps.clone());
+        app.update_block(next_block);
+
+        let res: SourceClampsResponse = app
+            .wrap()
+            .query_wasm_smart(&mixer_addr, &QueryMsg::SourceClamps {})
+            .unwrap();
+        assert_eq!(res.clamps, clamps);
+
+        check_membership(
+            &app,
+            &mixer_addr,
+            None,
+            Some(316),
+            None,
+            Some(866),
+            None,
+            None,
+        );
+
+        // clamps are also applied on upstream changes
+        let bond = |app: &mut BasicApp<TgradeMsg, TgradeQuery>, addr: &str, amount: u128| {
+            let balance = coins(amount, STAKE_DENOM);
+            app.execute(
+                Addr::unchecked(RESERVE),
+                BankMsg::Send {
+                    to_address: addr.to_owned(),
+                    amount: balance.clone(),
+                }
+                .into(),
+            )
+            .unwrap();
+            let msg = tg4_stake::msg::ExecuteMsg::Bond {
+                vesting_tokens: None,
+            };
+            app.execute_contract(Addr::unchecked(addr), stake_addr.clone(), &msg, &balance)
+                .unwrap();
+        };
+        // 8500 stake capped at 2500 -> no change
+        bond(&mut app, VOTER3, 1000);
+        // 5000 stake capped at 2500, 500 points -> 1118 mixed
+        bond(&mut app, VOTER5, 5000);
+
+        check_membership(
+            &app,
+            &mixer_addr,
+            None,
+            Some(316),
+            None,
+            Some(866),
+            None,
+            Some(1118),
+        );
+    }
+
     #[test]
     fn update_with_upstream_change() {
         let stakers = vec![
@@ -882,6 +1432,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn queued_upstream_changes_processed_in_end_block() {
+        let stakers = vec![
+            member(VOTER1, 10000), // 10000 stake, 100 points -> 1000 mixed
+            member(VOTER3, 7500),  // 7500 stake, 300 points -> 1500 mixed
+        ];
+
+        let mut app = AppBuilder::new_custom().build(|router, _, storage| {
+            for staker in &stakers {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(&staker.addr),
+                        coins(staker.points as u128, STAKE_DENOM),
+                    )
+                    .unwrap();
+            }
+        });
+
+        let (mixer_addr, group_addr, _) =
+            setup_test_case_with_batch_limit(&mut app, stakers, Some(1));
+        check_membership(
+            &app,
+            &mixer_addr,
+            None,
+            Some(1000),
+            None,
+            Some(1500),
+            None,
+            None,
+        );
+
+        // voter1 => None, voter3 => 1200 (mixed = 3000)
+        let msg = tg4_engagement::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![VOTER1.into()],
+            add: vec![member(VOTER3, 1200)],
+        };
+        app.execute_contract(Addr::unchecked(OWNER), group_addr, &msg, &[])
+            .unwrap();
+
+        // changes are only queued
+        check_membership(
+            &app,
+            &mixer_addr,
+            None,
+            Some(1000),
+            None,
+            Some(1500),
+            None,
+            None,
+        );
+
+        // a single member is processed per block
+        app.wasm_sudo(mixer_addr.clone(), &TgradeSudoMsg::<Empty>::EndBlock {})
+            .unwrap();
+        check_membership(&app, &mixer_addr, None, None, None, Some(1500), None, None);
+
+        app.wasm_sudo(mixer_addr.clone(), &TgradeSudoMsg::<Empty>::EndBlock {})
+            .unwrap();
+        check_membership(&app, &mixer_addr, None, None, None, Some(3000), None, None);
+        let total: TotalPointsResponse = app
+            .wrap()
+            .query_wasm_smart(&mixer_addr, &QueryMsg::TotalPoints { at_height: None })
+            .unwrap();
+        assert_eq!(total.points, 3000);
+
+        // nothing left in the queue
+        let res = app
+            .wasm_sudo(mixer_addr, &TgradeSudoMsg::<Empty>::EndBlock {})
+            .unwrap();
+        assert_eq!(res.events.len(), 1);
+    }
+
     #[test]
     fn hook_on_engagement() {
         let stakers = vec![
@@ -958,6 +1582,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rebind_group() {
+        let stakers = vec![
+            member(VOTER1, 10000), // 10000 stake, 100 points -> 1000 mixed
+            member(VOTER3, 7500),  // 7500 stake, 300 points -> 1500 mixed
+        ];
+
+        let mut app = AppBuilder::new_custom().build(|router, _, storage| {
+            for staker in &stakers {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(&staker.addr),
+                        coins(staker.points as u128, STAKE_DENOM),
+                    )
+                    .unwrap();
+            }
+        });
+
+        let (mixer_addr, group_addr, stake_addr) = setup_test_case(&mut app, stakers);
+
+        // group migrated to the new address, with changed members
+        let new_group_addr =
+            instantiate_group(&mut app, vec![member(VOTER1, 400), member(VOTER2, 200)]);
+        let rebind = ExecuteMsg::RebindGroup {
+            side: GroupSide::Left,
+            addr: new_group_addr.to_string(),
+        };
+
+        // only admin can rebind
+        let err = app
+            .execute_contract(Addr::unchecked(VOTER1), mixer_addr.clone(), &rebind, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Admin(AdminError::NotAdmin {}),
+            err.downcast().unwrap()
+        );
+
+        // groups already mixed cannot be bound again
+        let err = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                mixer_addr.clone(),
+                &ExecuteMsg::RebindGroup {
+                    side: GroupSide::Left,
+                    addr: stake_addr.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::GroupAlreadyBound(stake_addr.to_string()),
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(Addr::unchecked(OWNER), mixer_addr.clone(), &rebind, &[])
+            .unwrap();
+
+        let groups: GroupsResponse = app
+            .wrap()
+            .query_wasm_smart(&mixer_addr, &QueryMsg::Groups {})
+            .unwrap();
+        assert_eq!(groups.left, new_group_addr.to_string());
+        assert_eq!(groups.right, stake_addr.to_string());
+
+        // all points are recalculated from the new group
+        // sqrt(400 * 10000) = 2000, voter3 is not a member of new group anymore
+        check_membership(&app, &mixer_addr, None, Some(2000), None, None, None, None);
+        let total: TotalPointsResponse = app
+            .wrap()
+            .query_wasm_smart(&mixer_addr, &QueryMsg::TotalPoints { at_height: None })
+            .unwrap();
+        assert_eq!(total.points, 2000);
+
+        // hooks are moved to the new group
+        let hooks = |group: &Addr| -> Vec<String> {
+            let res: HooksResponse = app
+                .wrap()
+                .query_wasm_smart(group, &tg4_engagement::msg::QueryMsg::Hooks {})
+                .unwrap();
+            res.hooks
+        };
+        assert_eq!(hooks(&group_addr), Vec::<String>::new());
+        assert_eq!(hooks(&new_group_addr), vec![mixer_addr.to_string()]);
+
+        // changes in the new group are mixed in
+        let msg = tg4_engagement::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![],
+            add: vec![member(VOTER3, 300)],
+        };
+        app.execute_contract(Addr::unchecked(OWNER), new_group_addr, &msg, &[])
+            .unwrap();
+        check_membership(
+            &app,
+            &mixer_addr,
+            None,
+            Some(2000),
+            None,
+            Some(1500),
+            None,
+            None,
+        );
+    }
+
     #[test]
     fn slashing_works() {
         let stakers = vec![
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_controllers::AdminError;
 use tg_utils::{HookError, PreauthError, SlasherError};
 use thiserror::Error;
 
@@ -7,6 +8,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
     #[error("{0}")]
     Hook(#[from] HookError),
 
@@ -22,6 +26,9 @@ pub enum ContractError {
     #[error("Contract {0} doesn't fulfill the tg4 interface")]
     NotTg4(String),
 
+    #[error("Contract {0} is already one of the mixed groups")]
+    GroupAlreadyBound(String),
+
     #[error("Overflow when multiplying group points - the product must be less than 10^18")]
     PointsOverflow {},
 
@@ -33,4 +40,7 @@ pub enum ContractError {
 
     #[error("The parameter '{0}' is out of range: {1}")]
     ParameterRange(&'static str, String),
+
+    #[error("Unrecognized sudo message")]
+    UnknownSudoMsg {},
 }
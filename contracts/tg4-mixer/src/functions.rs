@@ -6,6 +6,7 @@ use rust_decimal_macros::dec;
 use cosmwasm_std::{Decimal as StdDecimal, Fraction, Uint64};
 
 use crate::error::ContractError;
+use crate::msg::SourceClamps;
 
 pub fn std_to_decimal(std_decimal: StdDecimal) -> Decimal {
     Decimal::from_i128_with_scale(std_decimal.numerator().u128() as i128, 18) // FIXME: StdDecimal::DECIMAL_PLACES is private (https://github.com/CosmWasm/cosmwasm/issues/1361)
@@ -18,6 +19,25 @@ pub trait PoEFunction {
     fn mix(&self, stake: u64, engagement: u64) -> Result<u64, ContractError>;
 }
 
+/// Clamps the points of both sources before passing them to the wrapped function
+pub struct Clamped {
+    clamps: SourceClamps,
+    inner: Box<dyn PoEFunction>,
+}
+
+impl Clamped {
+    pub fn new(clamps: SourceClamps, inner: Box<dyn PoEFunction>) -> Self {
+        Self { clamps, inner }
+    }
+}
+
+impl PoEFunction for Clamped {
+    fn mix(&self, stake: u64, engagement: u64) -> Result<u64, ContractError> {
+        let (stake, engagement) = self.clamps.apply(stake, engagement);
+        self.inner.mix(stake, engagement)
+    }
+}
+
 /// This takes a geometric mean of stake and engagement points using integer math
 #[derive(Default)]
 pub struct GeometricMean {}
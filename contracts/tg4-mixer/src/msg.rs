@@ -1,7 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
-use cosmwasm_std::{Decimal as StdDecimal, Uint64};
+use cosmwasm_std::{Decimal as StdDecimal, Uint128, Uint64};
 use tg4::{Member, MemberChangedHookMsg};
 
 use crate::error::ContractError;
@@ -9,6 +10,10 @@ use crate::functions::{AlgebraicSigmoid, GeometricMean, PoEFunction, Sigmoid, Si
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct InstantiateMsg {
+    /// The admin is the only account that can re-bind the upstream groups (usually the
+    /// governance contract). Omit it to bind them permanently.
+    #[serde(default)]
+    pub admin: Option<String>,
     /// One of the groups we feed to the mixer function
     pub left_group: String,
     /// The other group we feed to the mixer function
@@ -21,6 +26,100 @@ pub struct InstantiateMsg {
     pub preauths_slashing: u64,
     /// Enum to store the proof-of-engagement function parameters used for this contract
     pub function_type: PoEFunctionType,
+    /// If set, member changes reported by upstream groups are only queued in the hook, and
+    /// processed in the end block, at most `hook_batch_limit` members per block. This requires
+    /// the `EndBlocker` privilege. If not set, changes are processed right away in the hook.
+    #[serde(default)]
+    pub hook_batch_limit: Option<u32>,
+    /// Bounds on the points of each upstream group, applied before mixing. Not clamped by default.
+    #[serde(default)]
+    pub source_clamps: SourceClamps,
+}
+
+/// Caps and floors on the contribution of each upstream group, applied to the points of a member
+/// of both groups before they are passed to the PoE function. Absolute bounds are applied first,
+/// then ratio caps are evaluated against the other side after its absolute bounds.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SourceClamps {
+    /// Left group points below this value are raised to it
+    #[serde(default)]
+    pub left_min: Option<Uint64>,
+    /// Left group points above this value are lowered to it
+    #[serde(default)]
+    pub left_max: Option<Uint64>,
+    /// Right group points below this value are raised to it
+    #[serde(default)]
+    pub right_min: Option<Uint64>,
+    /// Right group points above this value are lowered to it
+    #[serde(default)]
+    pub right_max: Option<Uint64>,
+    /// Left group points are capped at this multiple of right group points
+    #[serde(default)]
+    pub left_max_ratio: Option<StdDecimal>,
+    /// Right group points are capped at this multiple of left group points (eg. `2` caps
+    /// engagement at twice the stake)
+    #[serde(default)]
+    pub right_max_ratio: Option<StdDecimal>,
+}
+
+impl SourceClamps {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if let (Some(min), Some(max)) = (self.left_min, self.left_max) {
+            if min > max {
+                return Err(ContractError::ParameterRange(
+                    "left_min",
+                    format!("{} is above left_max {}", min, max),
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.right_min, self.right_max) {
+            if min > max {
+                return Err(ContractError::ParameterRange(
+                    "right_min",
+                    format!("{} is above right_max {}", min, max),
+                ));
+            }
+        }
+        if let Some(ratio) = self.left_max_ratio {
+            if ratio.is_zero() {
+                return Err(ContractError::ParameterRange(
+                    "left_max_ratio",
+                    ratio.to_string(),
+                ));
+            }
+        }
+        if let Some(ratio) = self.right_max_ratio {
+            if ratio.is_zero() {
+                return Err(ContractError::ParameterRange(
+                    "right_max_ratio",
+                    ratio.to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the clamped `(left, right)` points
+    pub fn apply(&self, left: u64, right: u64) -> (u64, u64) {
+        let bound = |points: u64, min: Option<Uint64>, max: Option<Uint64>| {
+            let points = min.map_or(points, |min| points.max(min.u64()));
+            max.map_or(points, |max| points.min(max.u64()))
+        };
+        let left = bound(left, self.left_min, self.left_max);
+        let right = bound(right, self.right_min, self.right_max);
+
+        let cap = |points: u64, other: u64, ratio: Option<StdDecimal>| match ratio {
+            Some(ratio) => {
+                let max = Uint128::from(other) * ratio;
+                points.min(u64::try_from(max.u128()).unwrap_or(u64::MAX))
+            }
+            None => points,
+        };
+        (
+            cap(left, right, self.left_max_ratio),
+            cap(right, left, self.right_max_ratio),
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -73,8 +172,13 @@ impl PoEFunctionType {
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Change the admin
+    UpdateAdmin { admin: Option<String> },
     /// This handles a callback from one of the linked groups
     MemberChangedHook(MemberChangedHookMsg),
+    /// Replaces one of the upstream groups with a new contract (eg. after the group was migrated
+    /// to a new address), and recalculates all the mixed points. Must be called by Admin
+    RebindGroup { side: GroupSide, addr: String },
     /// Add a new hook to be informed of all membership changes.
     AddHook { addr: String },
     /// Remove a hook. Must be called by the contract being removed
@@ -87,11 +191,24 @@ pub enum ExecuteMsg {
     Slash { addr: String, portion: StdDecimal },
 }
 
+/// Selects one of the two upstream groups
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupSide {
+    /// Group passed as `left_group` on instantiation, usually the stake contract
+    Left,
+    /// Group passed as `right_group` on instantiation, usually the engagement contract
+    Right,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    /// Return TotalPointsResponse
-    TotalPoints {},
+    /// Return AdminResponse
+    Admin {},
+    /// Return TotalPointsResponse. If at_height is set, returns total points at the
+    /// beginning of the given block.
+    TotalPoints { at_height: Option<u64> },
     /// Returns MemberListResponse
     ListMembers {
         start_after: Option<String>,
@@ -124,6 +241,11 @@ pub enum QueryMsg {
     IsSlasher { addr: String },
     /// Shows all active slashers as vector of addresses
     ListSlashers {},
+    /// Shows points of the member in both upstream groups, together with the mixed result and
+    /// the PoE function used to compute it. Returns MemberBreakdownResponse
+    MemberBreakdown { addr: String },
+    /// Bounds applied to upstream points before mixing. Returns SourceClampsResponse
+    SourceClamps {},
 }
 
 /// Return the two groups we are listening to
@@ -142,3 +264,96 @@ pub struct PreauthResponse {
 pub struct MixerFunctionResponse {
     pub points: u64,
 }
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MemberBreakdownResponse {
+    /// Points in the left group, passed as stake to the PoE function
+    pub left_points: Option<u64>,
+    /// Points in the right group, passed as engagement to the PoE function
+    pub right_points: Option<u64>,
+    /// Mixed points of the member in this group
+    pub points: Option<u64>,
+    /// PoE function used for mixing
+    pub poe_function: PoEFunctionType,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SourceClampsResponse {
+    pub clamps: SourceClamps,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_clamps_validation() {
+        assert_eq!(SourceClamps::default().validate(), Ok(()));
+
+        let clamps = SourceClamps {
+            left_min: Some(Uint64::new(10)),
+            left_max: Some(Uint64::new(10)),
+            right_max_ratio: Some(StdDecimal::percent(200)),
+            ..SourceClamps::default()
+        };
+        assert_eq!(clamps.validate(), Ok(()));
+
+        let clamps = SourceClamps {
+            right_min: Some(Uint64::new(11)),
+            right_max: Some(Uint64::new(10)),
+            ..SourceClamps::default()
+        };
+        assert!(matches!(
+            clamps.validate(),
+            Err(ContractError::ParameterRange("right_min", _))
+        ));
+
+        let clamps = SourceClamps {
+            left_max_ratio: Some(StdDecimal::zero()),
+            ..SourceClamps::default()
+        };
+        assert!(matches!(
+            clamps.validate(),
+            Err(ContractError::ParameterRange("left_max_ratio", _))
+        ));
+    }
+
+    #[test]
+    fn source_clamps_apply() {
+        assert_eq!(SourceClamps::default().apply(0, 1000), (0, 1000));
+
+        let clamps = SourceClamps {
+            left_min: Some(Uint64::new(100)),
+            right_max: Some(Uint64::new(5000)),
+            ..SourceClamps::default()
+        };
+        assert_eq!(clamps.apply(0, 1000), (100, 1000));
+        assert_eq!(clamps.apply(200, 8000), (200, 5000));
+
+        // engagement capped at twice the stake
+        let clamps = SourceClamps {
+            right_max_ratio: Some(StdDecimal::percent(200)),
+            ..SourceClamps::default()
+        };
+        assert_eq!(clamps.apply(100, 1000), (100, 200));
+        assert_eq!(clamps.apply(100, 150), (100, 150));
+        assert_eq!(clamps.apply(0, 150), (0, 0));
+
+        // ratio is evaluated against the other side after its absolute bounds
+        let clamps = SourceClamps {
+            left_min: Some(Uint64::new(50)),
+            right_max_ratio: Some(StdDecimal::percent(200)),
+            ..SourceClamps::default()
+        };
+        assert_eq!(clamps.apply(0, 150), (50, 100));
+
+        // both ratios are evaluated against the other side before its ratio cap
+        let clamps = SourceClamps {
+            left_max_ratio: Some(StdDecimal::one()),
+            right_max_ratio: Some(StdDecimal::one()),
+            ..SourceClamps::default()
+        };
+        assert_eq!(clamps.apply(100, 300), (100, 100));
+        assert_eq!(clamps.apply(u64::MAX, 300), (300, 300));
+    }
+}
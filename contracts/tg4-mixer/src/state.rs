@@ -1,11 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use crate::msg::PoEFunctionType;
-use cw_storage_plus::Item;
+use crate::msg::{PoEFunctionType, SourceClamps};
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
 use tg4::Tg4Contract;
 
 pub const POE_FUNCTION_TYPE: Item<PoEFunctionType> = Item::new("poe-function-type");
 
+/// Bounds applied to upstream points before mixing. Missing in contracts instantiated before
+/// it was introduced, which means no clamping.
+pub const SOURCE_CLAMPS: Item<SourceClamps> = Item::new("source-clamps");
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Groups {
     pub left: Tg4Contract,
@@ -13,3 +18,10 @@ pub struct Groups {
 }
 
 pub const GROUPS: Item<Groups> = Item::new("groups");
+
+/// Maximal number of queued members processed in a single end block. If not set, upstream
+/// changes are processed synchronously in the hook.
+pub const HOOK_BATCH_LIMIT: Item<u32> = Item::new("hook-batch-limit");
+
+/// Members changed in any of upstream groups, whose mixed points are not recalculated yet
+pub const QUEUED_MEMBERS: Map<&Addr, Empty> = Map::new("queued-members");
@@ -5,8 +5,10 @@ use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, s
 
 pub use tg4::{AdminResponse, MemberListResponse, MemberResponse, TotalPointsResponse};
 pub use tg4_stake::msg::{
-    ClaimsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PreauthResponse, QueryMsg,
-    StakedResponse, UnbondingPeriodResponse,
+    BondHeadroomResponse, ClaimPermitSignDoc, ClaimsResponse, DenomResponse, DistributionMsg,
+    ExecuteMsg, ExportStakesResponse, InstantiateMsg, ListAllStakesResponse, MigrateMsg,
+    PausedResponse, PermitInfoResponse, PreauthResponse, QueryMsg, SiblingsResponse,
+    StakedResponse, UnbondingBucketsResponse, UnbondingPeriodResponse,
 };
 
 fn main() {
@@ -19,6 +21,7 @@ fn main() {
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
     export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
+    export_schema(&schema_for!(DistributionMsg), &out_dir);
     export_schema(&schema_for!(AdminResponse), &out_dir);
     export_schema(&schema_for!(MemberListResponse), &out_dir);
     export_schema(&schema_for!(MemberResponse), &out_dir);
@@ -27,4 +30,13 @@ fn main() {
     export_schema(&schema_for!(UnbondingPeriodResponse), &out_dir);
     export_schema(&schema_for!(StakedResponse), &out_dir);
     export_schema(&schema_for!(PreauthResponse), &out_dir);
+    export_schema(&schema_for!(ExportStakesResponse), &out_dir);
+    export_schema(&schema_for!(ListAllStakesResponse), &out_dir);
+    export_schema(&schema_for!(SiblingsResponse), &out_dir);
+    export_schema(&schema_for!(PermitInfoResponse), &out_dir);
+    export_schema(&schema_for!(ClaimPermitSignDoc), &out_dir);
+    export_schema(&schema_for!(BondHeadroomResponse), &out_dir);
+    export_schema(&schema_for!(DenomResponse), &out_dir);
+    export_schema(&schema_for!(UnbondingBucketsResponse), &out_dir);
+    export_schema(&schema_for!(PausedResponse), &out_dir);
 }
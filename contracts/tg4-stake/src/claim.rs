@@ -8,9 +8,12 @@ use serde::{Deserialize, Serialize};
 use crate::msg::Undelegation;
 use crate::state::CONFIG;
 use cosmwasm_std::{
-    coin, Addr, BlockInfo, CustomQuery, Decimal, Deps, Order, StdResult, Storage, Uint128,
+    coin, Addr, Binary, BlockInfo, CustomQuery, Decimal, Deps, Order, StdResult, Storage,
+    Timestamp, Uint128,
+};
+use cw_storage_plus::{
+    Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, PrefixBound, PrimaryKey,
 };
-use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex, PrefixBound};
 use tg_bindings::TgradeMsg;
 use tg_bindings::TgradeMsg::Undelegate;
 use tg_utils::Expiration;
@@ -19,16 +22,37 @@ use tg_utils::Expiration;
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 30;
 
+/// Maximal length (in bytes) of the claim tag
+pub const MAX_CLAIM_TAG_LENGTH: usize = 32;
+/// Tag of claims created by `ExecuteMsg::ForceUnbond`
+pub const FORCE_UNBOND_CLAIM_TAG: &str = "force-unbond";
+
+/// Length of a year in seconds, which the unbonding interest rate is relative to
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Width of the release time window of a single claims bucket
+pub const CLAIM_BUCKET_SECONDS: u64 = 60 * 60;
+const CLAIM_BUCKET_NANOS: u64 = CLAIM_BUCKET_SECONDS * 1_000_000_000;
+
+/// Index of the bucket of claims released at given time
+fn claim_bucket(release_at: u64) -> u64 {
+    release_at / CLAIM_BUCKET_NANOS
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct TokenReleaseInfo {
     pub addr: Addr,
     pub amount: Uint128,
+    /// Unbonding interest accrued on the released tokens, not covered by the interest pool yet
+    pub interest: Uint128,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct ReleaseData {
     pub liquid_releases: Vec<TokenReleaseInfo>,
     pub vesting_releases: Vec<TokenReleaseInfo>,
+    /// Number of released claims
+    pub released: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -46,14 +70,46 @@ pub struct Claim {
     pub release_at: Expiration,
     /// Height of a blockchain in a moment of creation of this claim
     pub creation_height: u64,
+    /// End of the veto window of this claim. Until then the claim is `Pending` and the unbonding
+    /// can be cancelled by a slasher. Only set for claims of flagged validator operators.
+    #[serde(default)]
+    pub veto_until: Option<Expiration>,
+    /// Optional tag of the claim recorded on its creation, describing its origin. Claims with
+    /// different tags are never merged.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Time from which the liquid amount accrues unbonding interest until `release_at`. Only set
+    /// for claims created while the unbonding interest was enabled.
+    #[serde(default)]
+    pub interest_since: Option<Timestamp>,
+}
+
+/// Summary of all claims released within a single `CLAIM_BUCKET_SECONDS` window
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct ClaimBucket {
+    /// Number of claims in the bucket
+    pub claims: u64,
+    /// Liquid amount of tokens in the bucket claims
+    pub amount: Uint128,
+    /// Vesting amount of tokens in the bucket claims
+    pub vesting_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimStatus {
+    /// Claim is still in its veto window, and the unbonding may be cancelled
+    Pending,
+    /// Claim can no longer be vetoed, and would be released at `release_at`
+    Matured,
 }
 
-struct ClaimIndexes<'a> {
+struct ClaimIndexes<'a, PK> {
     // Last type param defines the pk deserialization type
-    pub release_at: MultiIndex<'a, u64, Claim, (Addr, u64)>,
+    pub release_at: MultiIndex<'a, u64, Claim, PK>,
 }
 
-impl<'a> IndexList<Claim> for ClaimIndexes<'a> {
+impl<'a, PK> IndexList<Claim> for ClaimIndexes<'a, PK> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Claim>> + '_> {
         let v: Vec<&dyn Index<Claim>> = vec![&self.release_at];
         Box::new(v.into_iter())
@@ -74,18 +130,83 @@ impl Claim {
             vesting_amount: Some(vesting_amount.into()),
             release_at: released,
             creation_height,
+            veto_until: None,
+            tag: None,
+            interest_since: None,
+        }
+    }
+
+    /// Interest accrued on the liquid amount over the unbonding period, with the given yearly
+    /// `rate`
+    pub fn interest(&self, rate: Decimal) -> Uint128 {
+        match self.interest_since {
+            Some(since) => {
+                let elapsed = Timestamp::from(self.release_at)
+                    .seconds()
+                    .saturating_sub(since.seconds());
+                self.amount.multiply_ratio(elapsed, SECONDS_PER_YEAR) * rate
+            }
+            None => Uint128::zero(),
+        }
+    }
+
+    /// Vesting amount of tokens in claim, zero if not set
+    fn vesting(&self) -> Uint128 {
+        self.vesting_amount.unwrap_or_default()
+    }
+
+    /// Key of the claim in the claims map
+    fn key(&self) -> (&Addr, u64, &str) {
+        (
+            &self.addr,
+            self.release_at.as_key(),
+            self.tag.as_deref().unwrap_or_default(),
+        )
+    }
+
+    pub fn status(&self, block: &BlockInfo) -> ClaimStatus {
+        match self.veto_until {
+            Some(veto_until) if !veto_until.is_expired(block) => ClaimStatus::Pending,
+            _ => ClaimStatus::Matured,
         }
     }
 }
 
+type ClaimsMap<'a> =
+    IndexedMap<'a, (&'a Addr, u64, &'a str), Claim, ClaimIndexes<'a, (Addr, u64, String)>>;
+
+/// Claims storage layout before introducing claim tags
+type LegacyClaimsMap<'a> = IndexedMap<'a, (&'a Addr, u64), Claim, ClaimIndexes<'a, (Addr, u64)>>;
+
 pub struct Claims<'a> {
-    /// Claims are indexed by `(addr, release_at)` pair. Claims falling into the same key are
-    /// merged (summarized) as there is no point to distinguish them.
-    claims: IndexedMap<'a, (&'a Addr, u64), Claim, ClaimIndexes<'a>>,
+    /// Claims are indexed by `(addr, release_at, tag)` triple (untagged claims use an empty tag).
+    /// Claims falling into the same key are merged (summarized) as there is no point to
+    /// distinguish them.
+    claims: ClaimsMap<'a>,
+    /// Number of stored claims, so it can be reported without scanning all of them
+    count: Item<'a, u64>,
+    /// Claims summarized by release time windows, so expired claims and totals of pending
+    /// unbondings can be found without scanning all claims
+    buckets: Map<'a, u64, ClaimBucket>,
+    /// Raw key of the last claim added to `buckets` while claims stored before the buckets were
+    /// introduced are being moved there. Not set once all claims are in `buckets`.
+    bucket_cursor: Item<'a, Binary>,
+    /// Claims stored with the layout from before claim tags were introduced. They are moved to
+    /// `claims` lazily: all claims of an address once any of them is updated, and in bounded
+    /// batches by `migrate_legacy_claims`.
+    legacy_claims: LegacyClaimsMap<'a>,
 }
 
 impl<'a> Claims<'a> {
-    pub fn new(storage_key: &'a str, release_subkey: &'a str) -> Self {
+    pub fn new(
+        storage_key: &'a str,
+        release_subkey: &'a str,
+        count_key: &'a str,
+        buckets_key: &'a str,
+        bucket_cursor_key: &'a str,
+        legacy_storage_key: &'a str,
+        legacy_release_subkey: &'a str,
+    ) -> Self {
         let indexes = ClaimIndexes {
             release_at: MultiIndex::new(
                 |_, claim| claim.release_at.as_key(),
@@ -94,12 +215,201 @@ impl<'a> Claims<'a> {
             ),
         };
         let claims = IndexedMap::new(storage_key, indexes);
+        let count = Item::new(count_key);
+        let buckets = Map::new(buckets_key);
+        let bucket_cursor = Item::new(bucket_cursor_key);
+        let legacy_indexes = ClaimIndexes {
+            release_at: MultiIndex::new(
+                |_, claim| claim.release_at.as_key(),
+                legacy_storage_key,
+                legacy_release_subkey,
+            ),
+        };
+        let legacy_claims = IndexedMap::new(legacy_storage_key, legacy_indexes);
+
+        Self {
+            claims,
+            count,
+            buckets,
+            bucket_cursor,
+            legacy_claims,
+        }
+    }
+
+    /// Number of claims not released yet
+    pub fn count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.count.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Counts all stored claims if they were not counted yet, as older versions didn't keep
+    /// the counter
+    pub fn init_count(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        if self.count.may_load(storage)?.is_none() {
+            let count = self
+                .claims
+                .keys_raw(storage, None, None, Order::Ascending)
+                .count() as u64;
+            self.count.save(storage, &count)?;
+        }
+        Ok(())
+    }
+
+    /// Starts moving claims into buckets if there are claims stored, but no buckets yet, as
+    /// older versions didn't keep the buckets. Claims are moved lazily, in batches, by
+    /// `migrate_buckets`.
+    pub fn init_buckets(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        let bucketed = self
+            .buckets
+            .keys_raw(storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+        if !bucketed && self.count(storage)? > 0 && self.bucket_cursor.may_load(storage)?.is_none()
+        {
+            self.bucket_cursor.save(storage, &Binary::default())?;
+        }
+        Ok(())
+    }
+
+    /// Whether all claims are summarized in buckets
+    pub fn buckets_complete(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.bucket_cursor.may_load(storage)?.is_none())
+    }
+
+    /// Adds up to `limit` claims not moved to buckets yet to their buckets. Returns number of
+    /// moved claims.
+    pub fn migrate_buckets(&self, storage: &mut dyn Storage, limit: u64) -> StdResult<u64> {
+        let cursor = match self.bucket_cursor.may_load(storage)? {
+            Some(cursor) => cursor,
+            None => return Ok(0),
+        };
+
+        let claims = self
+            .claims
+            .range_raw(
+                storage,
+                Some(Bound::ExclusiveRaw(cursor.into())),
+                None,
+                Order::Ascending,
+            )
+            .take(limit as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (_, claim) in &claims {
+            self.add_to_bucket(
+                storage,
+                claim.release_at.as_key(),
+                1,
+                claim.amount,
+                claim.vesting(),
+            )?;
+        }
+
+        match claims.last() {
+            Some((key, _)) if claims.len() as u64 == limit => {
+                self.bucket_cursor.save(storage, &key.clone().into())?
+            }
+            _ => self.bucket_cursor.remove(storage),
+        }
+
+        Ok(claims.len() as u64)
+    }
+
+    /// Whether the claim stored under `key` is already summarized in its bucket
+    fn is_bucketed(&self, storage: &dyn Storage, key: (&Addr, u64, &str)) -> StdResult<bool> {
+        Ok(match self.bucket_cursor.may_load(storage)? {
+            Some(cursor) => key.joined_key() <= cursor.to_vec(),
+            None => true,
+        })
+    }
+
+    fn add_to_bucket(
+        &self,
+        storage: &mut dyn Storage,
+        release_at: u64,
+        claims: u64,
+        amount: Uint128,
+        vesting_amount: Uint128,
+    ) -> StdResult<()> {
+        let bucket = claim_bucket(release_at);
+        let mut summary = self.buckets.may_load(storage, bucket)?.unwrap_or_default();
+        summary.claims += claims;
+        summary.amount += amount;
+        summary.vesting_amount += vesting_amount;
+        self.buckets.save(storage, bucket, &summary)
+    }
+
+    fn remove_from_bucket(
+        &self,
+        storage: &mut dyn Storage,
+        release_at: u64,
+        claims: u64,
+        amount: Uint128,
+        vesting_amount: Uint128,
+    ) -> StdResult<()> {
+        let bucket = claim_bucket(release_at);
+        let mut summary = self.buckets.may_load(storage, bucket)?.unwrap_or_default();
+        summary.claims = summary.claims.saturating_sub(claims);
+        summary.amount = summary.amount.saturating_sub(amount);
+        summary.vesting_amount = summary.vesting_amount.saturating_sub(vesting_amount);
+        if summary.claims == 0 {
+            self.buckets.remove(storage, bucket);
+            Ok(())
+        } else {
+            self.buckets.save(storage, bucket, &summary)
+        }
+    }
 
-        Self { claims }
+    /// Whether all claims stored with the legacy `(addr, release_at)` layout were moved
+    pub fn legacy_claims_complete(&self, storage: &dyn Storage) -> bool {
+        self.legacy_claims
+            .keys_raw(storage, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    }
+
+    /// Moves up to `limit` claims stored with the legacy `(addr, release_at)` layout to this
+    /// map, as untagged claims. Returns number of moved claims.
+    pub fn migrate_legacy_claims(&self, storage: &mut dyn Storage, limit: u64) -> StdResult<u64> {
+        let claims = self
+            .legacy_claims
+            .range(storage, None, None, Order::Ascending)
+            .take(limit as usize)
+            .map(|r| r.map(|(_, c)| c))
+            .collect::<StdResult<Vec<_>>>()?;
+        self.move_legacy_claims(storage, claims)
+    }
+
+    /// Moves all legacy claims of the address to this map, so they are updated together with
+    /// claims created since
+    fn migrate_legacy_claims_for_addr(
+        &self,
+        storage: &mut dyn Storage,
+        addr: &Addr,
+    ) -> StdResult<()> {
+        let claims = self
+            .legacy_claims
+            .prefix(addr)
+            .range(storage, None, None, Order::Ascending)
+            .map(|r| r.map(|(_, c)| c))
+            .collect::<StdResult<Vec<_>>>()?;
+        self.move_legacy_claims(storage, claims)?;
+        Ok(())
+    }
+
+    fn move_legacy_claims(&self, storage: &mut dyn Storage, claims: Vec<Claim>) -> StdResult<u64> {
+        let moved = claims.len() as u64;
+        for claim in claims {
+            self.legacy_claims
+                .remove(storage, (&claim.addr, claim.release_at.as_key()))?;
+            self.store_claim(storage, claim)?;
+        }
+        Ok(moved)
     }
 
     /// This creates a claim, such that the given address can claim an amount of tokens after
-    /// the release date.
+    /// the release date. If `veto_until` is set, the claim stays `Pending` until then. If
+    /// `interest_since` is set, the liquid amount accrues unbonding interest since then.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_claim(
         &self,
         storage: &mut dyn Storage,
@@ -108,76 +418,150 @@ impl<'a> Claims<'a> {
         vesting_amount: Uint128,
         release_at: Expiration,
         creation_height: u64,
+        veto_until: Option<Expiration>,
+        tag: Option<String>,
+        interest_since: Option<Timestamp>,
     ) -> StdResult<()> {
-        let addr = &addr;
-        // Add a claim to this user to get their tokens after the unbonding period
-        self.claims.update(
+        self.migrate_legacy_claims_for_addr(storage, &addr)?;
+        self.store_claim(
             storage,
-            (addr, release_at.as_key()),
-            move |claim| -> StdResult<_> {
-                match claim {
-                    Some(mut claim) => {
-                        claim.amount += amount;
-                        claim.vesting_amount =
-                            Some(claim.vesting_amount.unwrap_or_default() + vesting_amount);
-                        Ok(claim)
-                    }
-                    None => Ok(Claim {
-                        addr: addr.clone(),
-                        amount,
-                        vesting_amount: Some(vesting_amount),
-                        release_at,
-                        creation_height,
-                    }),
-                }
+            Claim {
+                addr,
+                amount,
+                vesting_amount: Some(vesting_amount),
+                release_at,
+                creation_height,
+                veto_until,
+                tag,
+                interest_since,
             },
-        )?;
+        )
+    }
+
+    /// Stores the claim, merging it with the claim stored under the same key if any
+    fn store_claim(&self, storage: &mut dyn Storage, new_claim: Claim) -> StdResult<()> {
+        let tag_key = new_claim.tag.clone().unwrap_or_default();
+        let key = (
+            &new_claim.addr,
+            new_claim.release_at.as_key(),
+            tag_key.as_str(),
+        );
+        let new = self.claims.may_load(storage, key)?.is_none();
+        if new {
+            let count = self.count(storage)?;
+            self.count.save(storage, &(count + 1))?;
+        }
+        if self.is_bucketed(storage, key)? {
+            self.add_to_bucket(
+                storage,
+                new_claim.release_at.as_key(),
+                new as u64,
+                new_claim.amount,
+                new_claim.vesting(),
+            )?;
+        }
+        // Add a claim to this user to get their tokens after the unbonding period
+        self.claims.update(storage, key, |claim| -> StdResult<_> {
+            match claim {
+                Some(mut claim) => {
+                    claim.amount += new_claim.amount;
+                    claim.vesting_amount = Some(claim.vesting() + new_claim.vesting());
+                    // keep the longest veto window of merged claims
+                    claim.veto_until = match (claim.veto_until, new_claim.veto_until) {
+                        (Some(old), Some(new)) if old.as_key() >= new.as_key() => Some(old),
+                        (old, None) => old,
+                        (_, new) => new,
+                    };
+                    // merged claims accrue interest since the latest unbonding, so the
+                    // interest is never paid for time the tokens were still bonded
+                    claim.interest_since = claim.interest_since.max(new_claim.interest_since);
+                    Ok(claim)
+                }
+                None => Ok(new_claim.clone()),
+            }
+        })?;
 
         Ok(())
     }
 
     /// This iterates over all mature claims for the address, and removes them, up to an optional limit.
-    /// It removes the finished claims and returns the total amount of tokens to be released.
+    /// It removes the finished claims and returns the total amounts of liquid and vesting tokens
+    /// to be released, and the unbonding interest accrued with the given `interest_rate`.
     pub fn claim_addr(
         &self,
         storage: &mut dyn Storage,
         addr: &Addr,
         block: &BlockInfo,
         limit: impl Into<Option<u64>>,
-    ) -> StdResult<(Uint128, Uint128)> {
+        interest_rate: Option<Decimal>,
+    ) -> StdResult<(Uint128, Uint128, Uint128)> {
+        self.migrate_legacy_claims_for_addr(storage, addr)?;
         let claims = self
             .claims
-            .prefix(addr)
-            // take all claims for the addr
+            .sub_prefix(addr)
+            // take all claims for the addr, released at most at the current block
             .range_raw(
                 storage,
                 None,
-                Some(Bound::inclusive(Expiration::now(block).as_key())),
+                Some(Bound::exclusive((Expiration::now(block).as_key() + 1, ""))),
                 Order::Ascending,
             );
 
-        let claims = self.collect_claims(claims, limit.into())?;
+        let claims = self.collect_claims(claims, block, limit.into())?;
         let amount = claims.iter().map(|claim| claim.amount).sum();
         let vesting_amount = claims
             .iter()
             .map(|claim| claim.vesting_amount.unwrap_or_default())
             .sum();
+        let interest = match interest_rate {
+            Some(rate) => claims.iter().map(|claim| claim.interest(rate)).sum(),
+            None => Uint128::zero(),
+        };
 
         self.release_claims(storage, claims)?;
 
-        Ok((amount, vesting_amount))
+        Ok((amount, vesting_amount, interest))
     }
 
     /// This iterates over all mature claims of any addresses, and removes them. Up to `limit`
     /// claims would be processed, starting from the oldest. It removes the finished claims and
     /// returns a pair of vectors representing the amounts of liquid and vesting tokens
-    /// to be released to particular addresses.
+    /// to be released to particular addresses. Liquid releases include the unbonding interest
+    /// accrued with the given `interest_rate`.
     pub(crate) fn claim_expired(
         &self,
         storage: &mut dyn Storage,
         block: &BlockInfo,
         limit: impl Into<Option<u64>>,
+        interest_rate: Option<Decimal>,
     ) -> StdResult<ReleaseData> {
+        // Unless claims are still being moved to buckets, start right at the first bucket due
+        // for release, or skip the claims index entirely if there is none
+        let start = if self.buckets_complete(storage)? {
+            let first_due = self
+                .buckets
+                .keys(
+                    storage,
+                    None,
+                    Some(Bound::inclusive(claim_bucket(block.time.nanos()))),
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()?;
+            match first_due {
+                Some(bucket) => Some(PrefixBound::inclusive(bucket * CLAIM_BUCKET_NANOS)),
+                None => {
+                    return Ok(ReleaseData {
+                        liquid_releases: vec![],
+                        vesting_releases: vec![],
+                        released: 0,
+                    })
+                }
+            }
+        } else {
+            None
+        };
+
         let claims = self
             .claims
             .idx
@@ -185,12 +569,12 @@ impl<'a> Claims<'a> {
             // take all claims which are expired (at most same timestamp as current block)
             .prefix_range_raw(
                 storage,
-                None,
+                start,
                 Some(PrefixBound::inclusive(block.time.nanos())),
                 Order::Ascending,
             );
 
-        let mut claims = self.collect_claims(claims, limit.into())?;
+        let mut claims = self.collect_claims(claims, block, limit.into())?;
         claims.sort_by_key(|claim| claim.addr.clone());
 
         let liquid_releases = claims
@@ -199,9 +583,16 @@ impl<'a> Claims<'a> {
             // is stabilized [https://github.com/rust-lang/rust/issues/80552]
             .group_by(|claim| &claim.addr)
             .into_iter()
-            .map(|(addr, group)| TokenReleaseInfo {
-                addr: addr.clone(),
-                amount: group.map(|claim| claim.amount).sum(),
+            .map(|(addr, group)| {
+                let group: Vec<_> = group.collect();
+                TokenReleaseInfo {
+                    addr: addr.clone(),
+                    amount: group.iter().map(|claim| claim.amount).sum(),
+                    interest: match interest_rate {
+                        Some(rate) => group.iter().map(|claim| claim.interest(rate)).sum(),
+                        None => Uint128::zero(),
+                    },
+                }
             })
             .collect();
 
@@ -216,31 +607,38 @@ impl<'a> Claims<'a> {
                 amount: group
                     .map(|claim| claim.vesting_amount.unwrap_or_default())
                     .sum(),
+                interest: Uint128::zero(),
             })
             .collect();
 
+        let released = claims.len() as u64;
         self.release_claims(storage, claims)?;
 
         let release_data = ReleaseData {
             liquid_releases,
             vesting_releases,
+            released,
         };
 
         Ok(release_data)
     }
 
-    /// Processes claims filtering those which are to be released. Returns vector of claims to be
-    /// released
+    /// Processes claims filtering those which are to be released. Claims still `Pending` are
+    /// never released. Returns vector of claims to be released
     fn collect_claims(
         &self,
         claims: impl IntoIterator<Item = StdResult<(Vec<u8>, Claim)>>,
+        block: &BlockInfo,
         limit: Option<u64>,
     ) -> StdResult<Vec<Claim>> {
         // apply limit and collect - it is needed to collect intermediately, as it is impossible to
         // remove from map while iterating as it borrows map internally; collecting to result, so
         // it returns early on failure; collecting would also trigger a final map, so amount would
         // be properly fulfilled
-        let claims = claims.into_iter().map(|r| r.map(|(_, c)| c));
+        let claims = claims
+            .into_iter()
+            .map(|r| r.map(|(_, c)| c))
+            .filter(|c| !matches!(c, Ok(c) if c.status(block) == ClaimStatus::Pending));
         if let Some(limit) = limit {
             claims.take(limit as usize).collect()
         } else {
@@ -254,32 +652,74 @@ impl<'a> Claims<'a> {
         storage: &mut dyn Storage,
         claims: impl IntoIterator<Item = Claim>,
     ) -> StdResult<()> {
+        let mut released = 0;
         for claim in claims {
-            self.claims
-                .remove(storage, (&claim.addr, claim.release_at.as_key()))?;
+            if self.is_bucketed(storage, claim.key())? {
+                self.remove_from_bucket(
+                    storage,
+                    claim.release_at.as_key(),
+                    1,
+                    claim.amount,
+                    claim.vesting(),
+                )?;
+            }
+            self.claims.remove(storage, claim.key())?;
+            released += 1;
         }
+        let count = self.count(storage)?;
+        self.count.save(storage, &count.saturating_sub(released))?;
 
         Ok(())
     }
 
+    /// Removes all `Pending` claims of the address, cancelling their unbonding. Returns the total
+    /// liquid and vesting amounts of removed claims, so they can be restored as a stake.
+    pub fn veto_claims_for_addr(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        block: &BlockInfo,
+    ) -> StdResult<(Uint128, Uint128)> {
+        self.migrate_legacy_claims_for_addr(storage, address)?;
+        let claims = self
+            .claims
+            .sub_prefix(address)
+            .range(storage, None, None, Order::Ascending)
+            .map(|r| r.map(|(_, c)| c))
+            .filter(|c| !matches!(c, Ok(c) if c.status(block) == ClaimStatus::Matured))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let amount = claims.iter().map(|claim| claim.amount).sum();
+        let vesting_amount = claims
+            .iter()
+            .map(|claim| claim.vesting_amount.unwrap_or_default())
+            .sum();
+
+        self.release_claims(storage, claims)?;
+
+        Ok((amount, vesting_amount))
+    }
+
     pub fn slash_claims_for_addr(
         &self,
         storage: &mut dyn Storage,
         address: Addr,
         portion: Decimal,
     ) -> StdResult<(Uint128, Uint128)> {
+        self.migrate_legacy_claims_for_addr(storage, &address)?;
         let claims: StdResult<Vec<_>> = self
             .claims
-            .prefix(&address)
+            .sub_prefix(&address)
             .range(storage, None, None, Order::Ascending)
+            .map(|r| r.map(|(_, c)| c))
             .collect();
         let claims = claims?;
 
         let mut total_slashed = Uint128::zero();
         let mut total_vesting_slashed = Uint128::zero();
 
-        for (release_at, claim) in claims {
-            let key = (&address, release_at);
+        for claim in claims {
+            let key = claim.key();
 
             let slashed = claim.amount * portion;
             let vesting_slashed = claim.vesting_amount.unwrap_or_default() * portion;
@@ -290,6 +730,15 @@ impl<'a> Claims<'a> {
 
             self.claims
                 .replace(storage, key, Some(&new_claim), Some(&claim))?;
+            if self.is_bucketed(storage, key)? {
+                self.remove_from_bucket(
+                    storage,
+                    claim.release_at.as_key(),
+                    0,
+                    slashed,
+                    vesting_slashed,
+                )?;
+            }
 
             total_slashed += slashed;
             total_vesting_slashed += vesting_slashed;
@@ -298,23 +747,75 @@ impl<'a> Claims<'a> {
         Ok((total_slashed, total_vesting_slashed))
     }
 
+    /// Returns claims of the address ordered by release time, optionally only those with the
+    /// given `tag`
     pub fn query_claims<Q: CustomQuery>(
         &self,
         deps: Deps<Q>,
         address: Addr,
         limit: Option<u32>,
         start_after: Option<Expiration>,
+        tag: Option<String>,
     ) -> StdResult<Vec<Claim>> {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_after.map(|s| Bound::exclusive(s.as_key()));
+        // skip all claims released at `start_after`, regardless of their tags
+        let start = start_after.map(|s| Bound::inclusive((s.as_key() + 1, "")));
 
-        self.claims
-            .prefix(&address)
+        let mut claims = self
+            .claims
+            .sub_prefix(&address)
             .range(deps.storage, start, None, Order::Ascending)
             .map(|claim| match claim {
                 Ok((_, claim)) => Ok(claim),
                 Err(err) => Err(err),
             })
+            .filter(|claim| match (claim, &tag) {
+                (Ok(claim), Some(tag)) => claim.tag.as_ref() == Some(tag),
+                _ => true,
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // legacy claims not moved yet are untagged
+        if tag.is_none() {
+            let start = start_after.map(|s| Bound::exclusive(s.as_key()));
+            let legacy = self
+                .legacy_claims
+                .prefix(&address)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|claim| claim.map(|(_, claim)| claim))
+                .collect::<StdResult<Vec<_>>>()?;
+            if !legacy.is_empty() {
+                claims.extend(legacy);
+                claims.sort_by(|a, b| {
+                    (a.release_at.as_key(), &a.tag).cmp(&(b.release_at.as_key(), &b.tag))
+                });
+                claims.truncate(limit);
+            }
+        }
+
+        Ok(claims)
+    }
+
+    /// Returns buckets of claims ordered by release time, as `(bucket start, summary)` pairs,
+    /// starting after the bucket starting at `start_after`
+    pub fn query_buckets(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<Timestamp>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(Timestamp, ClaimBucket)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::exclusive(claim_bucket(s.nanos())));
+
+        self.buckets
+            .range(storage, start, None, Order::Ascending)
+            .map(|bucket| {
+                bucket.map(|(bucket, summary)| {
+                    (Timestamp::from_nanos(bucket * CLAIM_BUCKET_NANOS), summary)
+                })
+            })
             .take(limit)
             .collect()
     }
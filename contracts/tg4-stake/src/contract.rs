@@ -1,13 +1,18 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, coins, to_binary, Addr, BankMsg, Binary, Coin, CustomQuery, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, StdError, StdResult, Storage, Uint128,
+    coin, coins, to_binary, to_vec, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomQuery,
+    Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, Reply, StdError, StdResult,
+    Storage, Timestamp, Uint128, WasmMsg,
 };
+use sha2::{Digest, Sha256};
 use std::cmp::min;
 use std::ops::Sub;
 
-use crate::claim::process_pending_undelegations;
+use crate::claim::{
+    process_pending_undelegations, CLAIM_BUCKET_SECONDS, FORCE_UNBOND_CLAIM_TAG,
+    MAX_CLAIM_TAG_LENGTH,
+};
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use cw_utils::{ensure_from_older_version, maybe_addr};
@@ -16,19 +21,29 @@ use tg4::{
     MemberResponse, TotalPointsResponse,
 };
 use tg_bindings::{
-    request_privileges, Privilege, PrivilegeChangeMsg, TgradeMsg, TgradeQuery, TgradeSudoMsg,
+    events, request_privileges, Privilege, PrivilegeChangeMsg, TgradeMsg, TgradeQuery,
+    TgradeSudoMsg,
 };
 use tg_utils::{
-    members, validate_portion, Duration, ADMIN, HOOKS, PREAUTH_HOOKS, PREAUTH_SLASHING, SLASHERS,
-    TOTAL,
+    members, Duration, Expiration, ADMIN, HOOKS, PREAUTH_HOOKS, PREAUTH_SLASHING, SLASHERS, TOTAL,
 };
 
 use crate::error::ContractError;
 use crate::msg::{
-    ClaimsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PreauthResponse, QueryMsg,
-    StakedResponse, UnbondingPeriodResponse,
+    AverageStakeResponse, BondHeadroomResponse, ClaimPermit, ClaimPermitSignDoc, ClaimsResponse,
+    DenomResponse, DistributionMsg, ExecuteMsg, ExportStakesResponse, InstantiateMsg,
+    InterestPoolResponse, ListAllStakesResponse, LockupResponse, LockupsResponse, MigrateMsg,
+    PausedResponse, PayoutAddressResponse, PermitInfoResponse, PreauthResponse, QueryMsg,
+    SiblingsResponse, StakeEntry, StakedResponse, StatsResponse, UnbondControllersResponse,
+    UnbondingBucket, UnbondingBucketsResponse, UnbondingPeriodResponse, UnvalidatedExitFee,
+};
+use crate::state::{
+    assert_not_paused, claims, take_interest, update_stats, Config, DenomMetadata, Lockup,
+    LockupTier, PausableAction, PendingRedelegation, StakeAverage, CONFIG, INTEREST_POOL, LOCKUPS,
+    LOCKUP_EXPIRATIONS, LOCKUP_ID, MAX_LOCKUPS_PER_ADDRESS, OPERATORS, PAUSES, PAYOUT_ADDRESSES,
+    PENDING_PAYOUT_ADDRESSES, PENDING_REDELEGATION, PERMIT_KEYS, PERMIT_NONCES, SIBLINGS, STAKE,
+    STAKE_AVERAGES, STAKE_VESTING, STATS, TOTAL_BONDED, UNBOND_CONTROLLERS,
 };
-use crate::state::{claims, Config, CONFIG, STAKE, STAKE_VESTING};
 
 pub type Response = cosmwasm_std::Response<TgradeMsg>;
 pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
@@ -37,12 +52,14 @@ pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
 const CONTRACT_NAME: &str = "crates.io:tg4-stake";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const REDELEGATE_REPLY_ID: u64 = 1;
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut<TgradeQuery>,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -60,15 +77,42 @@ pub fn instantiate(
         msg.min_bond
     };
 
+    validate_lockup_tiers(&msg.lockup_tiers)?;
+    if let Some(metadata) = &msg.denom_metadata {
+        validate_denom_metadata(metadata)?;
+    }
+
     let config = Config {
-        denom: msg.denom,
+        denom: validate_denom(api, &msg.denom)?,
         tokens_per_point: msg.tokens_per_point,
         min_bond,
         unbonding_period: Duration::new(msg.unbonding_period),
         auto_return_limit: msg.auto_return_limit,
+        unbond_veto_period: msg
+            .unbond_veto_period
+            .filter(|period| *period > 0)
+            .map(Duration::new),
+        max_bond_per_address: msg.max_bond_per_address.filter(|max| !max.is_zero()),
+        max_total_bond: msg.max_total_bond.filter(|max| !max.is_zero()),
+        exit_fee: msg
+            .exit_fee
+            .map(|exit_fee| exit_fee.validate(api))
+            .transpose()?,
+        lockup_tiers: msg.lockup_tiers,
+        average_stake_period: msg
+            .average_stake_period
+            .filter(|period| *period > 0)
+            .map(Duration::new),
+        unbonding_interest: msg.unbonding_interest.filter(|rate| !rate.is_zero()),
+        denom_metadata: msg.denom_metadata,
+        max_pause_duration: msg
+            .max_pause_duration
+            .filter(|duration| *duration > 0)
+            .map(Duration::new),
     };
     CONFIG.save(deps.storage, &config)?;
-    TOTAL.save(deps.storage, &0)?;
+    TOTAL.save(deps.storage, &0, env.block.height)?;
+    TOTAL_BONDED.save(deps.storage, &Uint128::zero())?;
     SLASHERS.instantiate(deps.storage)?;
 
     Ok(Response::default())
@@ -92,11 +136,54 @@ pub fn execute(
         ExecuteMsg::Bond { vesting_tokens } => execute_bond(deps, env, info, vesting_tokens),
         ExecuteMsg::Unbond {
             tokens: Coin { amount, denom },
-        } => execute_unbond(deps, env, info, amount, denom),
+            tag,
+        } => execute_unbond(deps, env, info, amount, denom, tag),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
-        ExecuteMsg::AddSlasher { addr } => execute_add_slasher(deps, info, addr),
+        ExecuteMsg::Lock { tokens, period } => execute_lock(deps, env, info, tokens, period),
+        ExecuteMsg::AddSlasher { addr, max_portion } => {
+            execute_add_slasher(deps, info, addr, max_portion)
+        }
         ExecuteMsg::RemoveSlasher { addr } => execute_remove_slasher(deps, info, addr),
         ExecuteMsg::Slash { addr, portion } => execute_slash(deps, env, info, addr, portion),
+        ExecuteMsg::SetOperator { addr, operator } => {
+            execute_set_operator(deps, info, addr, operator)
+        }
+        ExecuteMsg::VetoUnbond { addr } => execute_veto_unbond(deps, env, info, addr),
+        ExecuteMsg::Redelegate {
+            to_contract,
+            amount,
+        } => execute_redelegate(deps, env, info, to_contract, amount),
+        ExecuteMsg::ReceiveRedelegation { staker } => {
+            execute_receive_redelegation(deps, env, info, staker)
+        }
+        ExecuteMsg::AddSibling { contract } => execute_add_sibling(deps, info, contract),
+        ExecuteMsg::RemoveSibling { contract } => execute_remove_sibling(deps, info, contract),
+        ExecuteMsg::SetPermitKey { pubkey } => execute_set_permit_key(deps, info, pubkey),
+        ExecuteMsg::ClaimWithPermit { permit, signature } => {
+            execute_claim_with_permit(deps, env, info, permit, signature)
+        }
+        ExecuteMsg::SetExitFee { exit_fee } => execute_set_exit_fee(deps, info, exit_fee),
+        ExecuteMsg::AddUnbondController { addr } => execute_add_unbond_controller(deps, info, addr),
+        ExecuteMsg::RemoveUnbondController { addr } => {
+            execute_remove_unbond_controller(deps, info, addr)
+        }
+        ExecuteMsg::ForceUnbond { addr } => execute_force_unbond(deps, env, info, addr),
+        ExecuteMsg::SetPayoutAddress {
+            addr,
+            require_confirmation,
+        } => execute_set_payout_address(deps, info, addr, require_confirmation),
+        ExecuteMsg::ConfirmPayoutAddress { staker } => {
+            execute_confirm_payout_address(deps, info, staker)
+        }
+        ExecuteMsg::SetDenomMetadata { metadata } => {
+            execute_set_denom_metadata(deps, info, metadata)
+        }
+        ExecuteMsg::FundInterestPool {} => execute_fund_interest_pool(deps, info),
+        ExecuteMsg::MigrateClaimBuckets { limit } => execute_migrate_claim_buckets(deps, limit),
+        ExecuteMsg::Pause { actions, duration } => {
+            execute_pause(deps, env, info, actions, duration)
+        }
+        ExecuteMsg::Unpause { actions } => execute_unpause(deps, env, info, actions),
     }
 }
 
@@ -151,6 +238,8 @@ pub fn execute_bond<Q: CustomQuery>(
     info: MessageInfo,
     vesting_tokens: Option<Coin>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PausableAction::Bond, &env.block)?;
+
     let cfg = CONFIG.load(deps.storage)?;
     let amount = validate_funds(&info.funds, &cfg.denom)?;
     let vesting_amount = vesting_tokens
@@ -160,6 +249,10 @@ pub fn execute_bond<Q: CustomQuery>(
     if amount + vesting_amount == Uint128::zero() {
         return Err(ContractError::NoFunds {});
     }
+    add_bonded(deps.storage, &cfg, &info.sender, amount + vesting_amount)?;
+    update_stats(deps.storage, |stats| {
+        stats.total_bonds += amount + vesting_amount
+    })?;
 
     // update the sender's stake
     let new_stake = STAKE.update(deps.storage, &info.sender, |stake| -> StdResult<_> {
@@ -193,7 +286,7 @@ pub fn execute_bond<Q: CustomQuery>(
         info.sender,
         new_stake + new_vesting_stake,
         &cfg,
-        env.block.height,
+        &env.block,
     )?);
 
     Ok(res)
@@ -205,10 +298,18 @@ pub fn execute_unbond<Q: CustomQuery>(
     info: MessageInfo,
     amount: Uint128,
     denom: String,
+    tag: Option<String>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PausableAction::Unbond, &env.block)?;
+
     if amount.is_zero() {
         return Err(ContractError::ZeroAmount {});
     }
+    if let Some(tag) = &tag {
+        if tag.is_empty() || tag.len() > MAX_CLAIM_TAG_LENGTH {
+            return Err(ContractError::InvalidClaimTag(MAX_CLAIM_TAG_LENGTH));
+        }
+    }
 
     // provide them a claim
     let cfg = CONFIG.load(deps.storage)?;
@@ -221,6 +322,11 @@ pub fn execute_unbond<Q: CustomQuery>(
     let stake = STAKE
         .may_load(deps.storage, &info.sender)?
         .unwrap_or_default();
+    // Locked stake cannot be unbonded
+    let unlocked = unlocked_stake(deps.storage, &info.sender, stake, &env.block)?;
+    if min(stake, amount) > unlocked {
+        return Err(ContractError::InsufficientUnlockedStake { unlocked });
+    }
     // Reduce the sender's stake - saturating if insufficient
     let new_stake = STAKE.update(deps.storage, &info.sender, |stake| -> StdResult<_> {
         Ok(stake.unwrap_or_default().saturating_sub(amount))
@@ -238,16 +344,62 @@ pub fn execute_unbond<Q: CustomQuery>(
         STAKE_VESTING.update(deps.storage, &info.sender, |stake| -> StdResult<_> {
             Ok(stake.unwrap_or_default().checked_sub(vesting_amount)?)
         })?;
+    remove_bonded(deps.storage, amount)?;
+    update_stats(deps.storage, |stats| stats.total_unbonds += amount)?;
+
+    // Exit fee is taken from the liquid tokens only, as vesting tokens are undelegated back to
+    // the vesting account. It is rounded down, in favour of the staker.
+    let liquid_amount = min(stake, amount);
+    let fee = cfg
+        .exit_fee
+        .as_ref()
+        .map(|exit_fee| liquid_amount * exit_fee.portion)
+        .unwrap_or_default();
+    if !fee.is_zero() {
+        let fee_coins = coins(fee.u128(), &cfg.denom);
+        res = match cfg
+            .exit_fee
+            .as_ref()
+            .and_then(|f| f.distribution_contract.as_ref())
+        {
+            Some(contract) => res.add_message(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: to_binary(&DistributionMsg::DistributeRewards {})?,
+                funds: fee_coins,
+            }),
+            None => res.add_message(BankMsg::Burn { amount: fee_coins }),
+        };
+        res = res.add_attribute("exit_fee", fee);
+    }
 
-    // Create claim for unbonded liquid and vesting amounts
-    let completion = cfg.unbonding_period.after(&env.block);
+    // Unbonding of flagged validator operators goes through the veto window first
+    let veto_until = match cfg.unbond_veto_period {
+        Some(period) if OPERATORS.has(deps.storage, &info.sender) => Some(period.after(&env.block)),
+        _ => None,
+    };
+
+    // Create claim for unbonded liquid and vesting amounts; it cannot be released before the
+    // veto window is over
+    let mut completion = cfg.unbonding_period.after(&env.block);
+    if let Some(veto_until) = veto_until {
+        if veto_until.as_key() > completion.as_key() {
+            completion = veto_until;
+        }
+        res = res.add_attribute("veto_until", veto_until.time().nanos().to_string());
+    }
+    if let Some(tag) = &tag {
+        res = res.add_attribute("claim_tag", tag);
+    }
     claims().create_claim(
         deps.storage,
         info.sender.clone(),
-        min(stake, amount),
+        liquid_amount - fee,
         vesting_amount,
         completion,
         env.block.height,
+        veto_until,
+        tag,
+        cfg.unbonding_interest.map(|_| env.block.time),
     )?;
     res = res.add_attribute("completion_time", completion.time().nanos().to_string());
 
@@ -257,16 +409,295 @@ pub fn execute_unbond<Q: CustomQuery>(
         info.sender,
         new_stake + new_vesting_stake,
         &cfg,
-        env.block.height,
+        &env.block,
     )?);
 
     Ok(res)
 }
 
+pub fn execute_add_unbond_controller<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let controller = deps.api.addr_validate(&addr)?;
+    UNBOND_CONTROLLERS.save(deps.storage, &controller, &Empty {})?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_unbond_controller")
+        .add_attribute("controller", addr)
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+pub fn execute_remove_unbond_controller<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let controller = deps.api.addr_validate(&addr)?;
+    UNBOND_CONTROLLERS.remove(deps.storage, &controller);
+
+    let res = Response::new()
+        .add_attribute("action", "remove_unbond_controller")
+        .add_attribute("controller", addr)
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+/// Unbonds whole stake of the member on behalf of a controller, eg. when the membership is
+/// revoked for compliance reasons. Unlike slashing, no tokens are taken - the member can claim
+/// all of them back after the unbonding period.
+pub fn execute_force_unbond<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    if !UNBOND_CONTROLLERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized(
+            "Sender is not an unbond controller".to_owned(),
+        ));
+    }
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let addr = deps.api.addr_validate(&addr)?;
+
+    let liquid = STAKE.may_load(deps.storage, &addr)?.unwrap_or_default();
+    let vesting = STAKE_VESTING
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    if liquid.is_zero() && vesting.is_zero() {
+        return Err(ContractError::NothingStaked(addr.to_string()));
+    }
+
+    STAKE.save(deps.storage, &addr, &Uint128::zero())?;
+    STAKE_VESTING.save(deps.storage, &addr, &Uint128::zero())?;
+    remove_bonded(deps.storage, liquid + vesting)?;
+    update_stats(deps.storage, |stats| {
+        stats.total_unbonds += liquid + vesting
+    })?;
+
+    // Lockups don't hold back the forced unbonding
+    let lockups = LOCKUPS
+        .prefix(&addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, lockup) in lockups {
+        LOCKUPS.remove(deps.storage, (&addr, id));
+        LOCKUP_EXPIRATIONS.remove(deps.storage, (lockup.locked_until.as_key(), &addr, id));
+    }
+
+    let completion = cfg.unbonding_period.after(&env.block);
+    claims().create_claim(
+        deps.storage,
+        addr.clone(),
+        liquid,
+        vesting,
+        completion,
+        env.block.height,
+        None,
+        Some(FORCE_UNBOND_CLAIM_TAG.to_owned()),
+        cfg.unbonding_interest.map(|_| env.block.time),
+    )?;
+
+    let event = events::force_unbond(&addr, &info.sender, liquid, vesting, completion.time());
+    let res = Response::new()
+        .add_attribute("action", "force_unbond")
+        .add_attribute("addr", &addr)
+        .add_attribute("sender", info.sender)
+        .add_event(event)
+        .add_submessages(update_membership(
+            deps.storage,
+            addr,
+            Uint128::zero(),
+            &cfg,
+            &env.block,
+        )?);
+
+    Ok(res)
+}
+
+pub fn execute_set_denom_metadata<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    metadata: Option<DenomMetadata>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_denom_metadata")
+        .add_attribute("sender", info.sender);
+    if let Some(metadata) = &metadata {
+        validate_denom_metadata(metadata)?;
+        res = res
+            .add_attribute("display", &metadata.display)
+            .add_attribute("exponent", metadata.exponent.to_string());
+    }
+
+    CONFIG.update::<_, StdError>(deps.storage, |mut cfg| {
+        cfg.denom_metadata = metadata;
+        Ok(cfg)
+    })?;
+
+    Ok(res)
+}
+
+pub fn execute_set_exit_fee<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    exit_fee: Option<UnvalidatedExitFee>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let exit_fee = exit_fee
+        .map(|exit_fee| exit_fee.validate(deps.api))
+        .transpose()?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_exit_fee")
+        .add_attribute("sender", info.sender);
+    if let Some(exit_fee) = &exit_fee {
+        res = res.add_attribute("portion", exit_fee.portion.to_string());
+        if let Some(contract) = &exit_fee.distribution_contract {
+            res = res.add_attribute("distribution_contract", contract);
+        }
+    }
+
+    CONFIG.update::<_, StdError>(deps.storage, |mut cfg| {
+        cfg.exit_fee = exit_fee;
+        Ok(cfg)
+    })?;
+
+    Ok(res)
+}
+
+pub fn execute_fund_interest_pool<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let amount = validate_funds(&info.funds, &cfg.denom)?;
+    if amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    let pool = INTEREST_POOL.may_load(deps.storage)?.unwrap_or_default() + amount;
+    INTEREST_POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_interest_pool")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("pool", pool))
+}
+
+pub fn execute_migrate_claim_buckets<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let limit = limit
+        .unwrap_or(CLAIM_BUCKETS_MIGRATION_BATCH)
+        .min(CLAIM_BUCKETS_MIGRATION_BATCH);
+    let migrated = migrate_claims(deps.storage, limit)?;
+    let complete =
+        claims().legacy_claims_complete(deps.storage) && claims().buckets_complete(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_claim_buckets")
+        .add_attribute("migrated", migrated.to_string())
+        .add_attribute("complete", complete.to_string()))
+}
+
+/// Moves up to `limit` claims stored by older versions: legacy claims to the tagged claims map
+/// first, then claims not summarized yet to their buckets. Returns number of moved claims.
+fn migrate_claims(storage: &mut dyn Storage, limit: u64) -> StdResult<u64> {
+    let moved = claims().migrate_legacy_claims(storage, limit)?;
+    let bucketed = claims().migrate_buckets(storage, limit - moved)?;
+    Ok(moved + bucketed)
+}
+
+pub fn execute_pause<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    actions: Vec<PausableAction>,
+    duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let max = CONFIG
+        .load(deps.storage)?
+        .max_pause_duration
+        .ok_or(ContractError::PausingDisabled {})?;
+    let duration = duration.unwrap_or_else(|| max.seconds());
+    if duration == 0 || duration > max.seconds() {
+        return Err(ContractError::InvalidPauseDuration { max: max.seconds() });
+    }
+    if actions.is_empty() {
+        return Err(ContractError::NoPauseActions {});
+    }
+
+    let until = Duration::new(duration).after(&env.block);
+    let mut pauses = PAUSES.may_load(deps.storage)?.unwrap_or_default();
+    let mut res = Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("sender", info.sender)
+        .add_attribute("until", until.time().nanos().to_string());
+    for action in actions {
+        // Otherwise the pause could be extended indefinitely
+        if pauses.paused_until(action, &env.block).is_some() {
+            return Err(ContractError::AlreadyPaused(action.as_str().to_owned()));
+        }
+        pauses.set(action, Some(until));
+        res = res.add_event(
+            Event::new("pause")
+                .add_attribute("action", action.as_str())
+                .add_attribute("until", until.time().nanos().to_string()),
+        );
+    }
+    PAUSES.save(deps.storage, &pauses)?;
+
+    Ok(res)
+}
+
+pub fn execute_unpause<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    actions: Vec<PausableAction>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if actions.is_empty() {
+        return Err(ContractError::NoPauseActions {});
+    }
+
+    let mut pauses = PAUSES.may_load(deps.storage)?.unwrap_or_default();
+    let mut res = Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("sender", info.sender);
+    for action in actions {
+        // Expired pauses are just cleaned up
+        if pauses.paused_until(action, &env.block).is_some() {
+            res = res.add_event(Event::new("unpause").add_attribute("action", action.as_str()));
+        }
+        pauses.set(action, None);
+    }
+    PAUSES.save(deps.storage, &pauses)?;
+
+    Ok(res)
+}
+
 pub fn execute_add_slasher<Q: CustomQuery>(
     deps: DepsMut<Q>,
     info: MessageInfo,
     slasher: String,
+    max_portion: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     // custom guard: using a preauth OR being admin
     if !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
@@ -274,7 +705,7 @@ pub fn execute_add_slasher<Q: CustomQuery>(
     }
 
     // add the slasher
-    SLASHERS.add_slasher(deps.storage, deps.api.addr_validate(&slasher)?)?;
+    SLASHERS.add_slasher(deps.storage, deps.api.addr_validate(&slasher)?, max_portion)?;
 
     // response
     let res = Response::new()
@@ -321,7 +752,7 @@ pub fn execute_slash<Q: CustomQuery>(
         ));
     }
 
-    validate_portion(portion)?;
+    SLASHERS.validate_slash(deps.storage, &info.sender, portion)?;
 
     let cfg = CONFIG.load(deps.storage)?;
     let addr = deps.api.addr_validate(&addr)?;
@@ -359,12 +790,31 @@ pub fn execute_slash<Q: CustomQuery>(
             Ok(stake.unwrap_or_default().sub(vesting_slashed))
         })?;
     }
+    remove_bonded(deps.storage, liquid_slashed + vesting_slashed)?;
+
+    // slashed liquid stake is taken from lockups proportionally
+    let lockups = LOCKUPS
+        .prefix(&addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, mut lockup) in lockups {
+        lockup.amount -= lockup.amount * portion;
+        LOCKUPS.save(deps.storage, (&addr, id), &lockup)?;
+    }
 
     // slash the liquid and vesting claims
     let (liquid_claims_slashed, vesting_claims_slashed) =
         claims().slash_claims_for_addr(deps.storage, addr.clone(), portion)?;
     liquid_slashed += liquid_claims_slashed;
     vesting_slashed += vesting_claims_slashed;
+    update_stats(deps.storage, |stats| {
+        stats.total_slashed += liquid_slashed + vesting_slashed
+    })?;
+    res = res.add_event(events::slash(
+        &addr,
+        portion,
+        liquid_slashed + vesting_slashed,
+    ));
 
     // burn the liquid slashed tokens
     if liquid_slashed > Uint128::zero() {
@@ -387,2473 +837,6205 @@ pub fn execute_slash<Q: CustomQuery>(
         addr,
         new_liquid_stake + new_vesting_stake,
         &cfg,
-        env.block.height,
+        &env.block,
     )?);
 
     Ok(res)
 }
 
-/// Validates funds sent with the message, that they are containing only a single denom. Returns
-/// amount of funds sent, or error if:
-/// * More than a single denom is sent (`ExtraDenoms` error)
-/// * Invalid single denom is sent (`MissingDenom` error)
-/// Note that no funds (or a coin of the right denom but zero amount) is a valid option here.
-pub fn validate_funds(funds: &[Coin], stake_denom: &str) -> Result<Uint128, ContractError> {
-    match funds {
-        [] => Ok(Uint128::zero()),
-        [Coin { denom, amount }] if denom == stake_denom => Ok(*amount),
-        [_] => Err(ContractError::MissingDenom(stake_denom.to_string())),
-        _ => Err(ContractError::ExtraDenoms(stake_denom.to_string())),
-    }
-}
-
-fn update_membership(
-    storage: &mut dyn Storage,
-    sender: Addr,
-    new_stake: Uint128,
-    cfg: &Config,
-    height: u64,
-) -> StdResult<Vec<SubMsg>> {
-    // update their membership points
-    let new = calc_points(new_stake, cfg);
-    let old = members().may_load(storage, &sender)?.map(|mi| mi.points);
-
-    // short-circuit if no change
-    if new == old {
-        return Ok(vec![]);
+pub fn execute_set_operator<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    addr: String,
+    operator: bool,
+) -> Result<Response, ContractError> {
+    if !SLASHERS.is_slasher(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized(
+            "Sender is not on slashers list".to_owned(),
+        ));
     }
-    // otherwise, record change of points
-    match new.as_ref() {
-        Some(&p) => members().save(storage, &sender, &MemberInfo::new(p), height),
-        None => members().remove(storage, &sender, height),
-    }?;
-
-    // update total
-    TOTAL.update(storage, |total| -> StdResult<_> {
-        Ok(total + new.unwrap_or_default() - old.unwrap_or_default())
-    })?;
-
-    // alert the hooks
-    let diff = MemberDiff::new(sender, old, new);
-    HOOKS.prepare_hooks(storage, |h| {
-        MemberChangedHookMsg::one(diff.clone())
-            .into_cosmos_msg(h)
-            .map(SubMsg::new)
-    })
-}
 
-fn calc_points(stake: Uint128, cfg: &Config) -> Option<u64> {
-    if stake < cfg.min_bond {
-        None
+    let addr = deps.api.addr_validate(&addr)?;
+    if operator {
+        OPERATORS.save(deps.storage, &addr, &Empty {})?;
     } else {
-        let p = stake.u128() / cfg.tokens_per_point.u128();
-        Some(p as u64)
+        OPERATORS.remove(deps.storage, &addr);
     }
+
+    let res = Response::new()
+        .add_attribute("action", "set_operator")
+        .add_attribute("addr", addr)
+        .add_attribute("operator", operator.to_string())
+        .add_attribute("sender", info.sender);
+    Ok(res)
 }
 
-pub fn execute_claim<Q: CustomQuery>(
+pub fn execute_veto_unbond<Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
     info: MessageInfo,
+    addr: String,
 ) -> Result<Response, ContractError> {
-    let (release, vesting_release) =
-        claims().claim_addr(deps.storage, &info.sender, &env.block, None)?;
-    if release.is_zero() && vesting_release.is_zero() {
-        return Err(ContractError::NothingToClaim {});
+    if !SLASHERS.is_slasher(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized(
+            "Sender is not on slashers list".to_owned(),
+        ));
     }
 
-    let config = CONFIG.load(deps.storage)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let addr = deps.api.addr_validate(&addr)?;
 
-    let mut res = Response::new()
-        .add_attribute("action", "claim")
-        .add_attribute("sender", &info.sender);
+    let (liquid, vesting) = claims().veto_claims_for_addr(deps.storage, &addr, &env.block)?;
+    if liquid.is_zero() && vesting.is_zero() {
+        return Err(ContractError::NothingToVeto {});
+    }
 
-    if !release.is_zero() {
-        let amount = coin(release.into(), config.denom.clone());
-        res = res
-            .add_attribute("liquid_tokens", amount.to_string())
-            .add_message(BankMsg::Send {
-                to_address: info.sender.clone().into(),
-                amount: vec![amount],
+    // restore vetoed tokens as a stake
+    let new_liquid_stake = STAKE.update(deps.storage, &addr, |stake| -> StdResult<_> {
+        Ok(stake.unwrap_or_default() + liquid)
+    })?;
+    let new_vesting_stake = STAKE_VESTING.update(deps.storage, &addr, |stake| -> StdResult<_> {
+        Ok(stake.unwrap_or_default() + vesting)
+    })?;
+    // restored stake was bonded before, so bond caps are not checked
+    TOTAL_BONDED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total + liquid + vesting)
+    })?;
+
+    let res = Response::new()
+        .add_attribute("action", "veto_unbond")
+        .add_attribute("addr", &addr)
+        .add_attribute("liquid", liquid)
+        .add_attribute("vesting", vesting)
+        .add_attribute("sender", info.sender)
+        .add_submessages(update_membership(
+            deps.storage,
+            addr,
+            new_liquid_stake + new_vesting_stake,
+            &cfg,
+            &env.block,
+        )?);
+
+    Ok(res)
+}
+
+/// Adds tokens about to be bonded by `addr` to the total, checking the bond caps first
+fn add_bonded(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    addr: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if let Some(max) = cfg.max_bond_per_address {
+        let stake = STAKE.may_load(storage, addr)?.unwrap_or_default()
+            + STAKE_VESTING.may_load(storage, addr)?.unwrap_or_default();
+        if stake + amount > max {
+            return Err(ContractError::AddressBondCapExceeded {
+                headroom: max.saturating_sub(stake),
             });
+        }
     }
 
-    if !vesting_release.is_zero() {
-        let vesting_amount = coin(vesting_release.into(), config.denom);
-        // Undelegate (unstake from contract) to sender's vesting account
-        res = res
-            .add_attribute("vesting_tokens", vesting_amount.to_string())
-            .add_message(TgradeMsg::Undelegate {
-                funds: vesting_amount,
-                recipient: info.sender.to_string(),
+    let total = TOTAL_BONDED.may_load(storage)?.unwrap_or_default();
+    if let Some(max) = cfg.max_total_bond {
+        if total + amount > max {
+            return Err(ContractError::TotalBondCapExceeded {
+                headroom: max.saturating_sub(total),
             });
+        }
     }
+    TOTAL_BONDED.save(storage, &(total + amount))?;
 
-    Ok(res)
+    Ok(())
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(
-    deps: DepsMut<TgradeQuery>,
+fn remove_bonded(storage: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    TOTAL_BONDED.update(storage, |total| -> StdResult<_> {
+        Ok(total.saturating_sub(amount))
+    })?;
+    Ok(())
+}
+
+/// Validates funds sent with the message, that they are containing only a single denom. Returns
+/// amount of funds sent, or error if:
+/// * More than a single denom is sent (`ExtraDenoms` error)
+/// * Invalid single denom is sent (`MissingDenom` error)
+/// Note that no funds (or a coin of the right denom but zero amount) is a valid option here.
+pub fn execute_redelegate<Q: CustomQuery>(
+    deps: DepsMut<Q>,
     env: Env,
-    msg: TgradeSudoMsg,
+    info: MessageInfo,
+    to_contract: String,
+    amount: Coin,
 ) -> Result<Response, ContractError> {
-    match msg {
-        TgradeSudoMsg::PrivilegeChange(PrivilegeChangeMsg::Promoted {}) => privilege_promote(deps),
-        TgradeSudoMsg::EndBlock {} => end_block(deps, env),
-        _ => Err(ContractError::UnknownSudoMsg {}),
+    // Moving stake out is unbonding from this contract point of view
+    assert_not_paused(deps.storage, PausableAction::Unbond, &env.block)?;
+
+    let cfg = CONFIG.load(deps.storage)?;
+    if amount.amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    if cfg.denom != amount.denom {
+        return Err(ContractError::InvalidDenom {});
     }
-}
 
-fn privilege_promote<Q: CustomQuery>(deps: DepsMut<Q>) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+    let to_contract = deps.api.addr_validate(&to_contract)?;
+    if !SIBLINGS.has(deps.storage, &to_contract) {
+        return Err(ContractError::NotSibling(to_contract.into()));
+    }
 
-    let mut res = Response::new();
-    if config.auto_return_limit > 0 {
-        let msgs = request_privileges(&[Privilege::EndBlocker]);
-        res = res.add_submessages(msgs);
+    // Flagged operators would escape the unbonding veto window this way
+    if cfg.unbond_veto_period.is_some() && OPERATORS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized(
+            "Flagged operators cannot redelegate".to_owned(),
+        ));
     }
-    let msgs = request_privileges(&[Privilege::Delegator]);
-    res = res.add_submessages(msgs);
+
+    // Only liquid stake can be moved, vesting one is delegated from the vesting account
+    let stake = STAKE
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let unlocked = unlocked_stake(deps.storage, &info.sender, stake, &env.block)?;
+    if amount.amount <= stake && amount.amount > unlocked {
+        return Err(ContractError::InsufficientUnlockedStake { unlocked });
+    }
+    let new_stake = STAKE.update(deps.storage, &info.sender, |stake| {
+        stake
+            .unwrap_or_default()
+            .checked_sub(amount.amount)
+            .map_err(|_| ContractError::InsufficientLiquidStake {})
+    })?;
+    remove_bonded(deps.storage, amount.amount)?;
+    let vesting_stake = STAKE_VESTING
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // Confirmed by the sibling in the reply
+    PENDING_REDELEGATION.save(
+        deps.storage,
+        &PendingRedelegation {
+            staker: info.sender.clone(),
+            to_contract: to_contract.clone(),
+            amount: amount.amount,
+        },
+    )?;
+    let receive = WasmMsg::Execute {
+        contract_addr: to_contract.to_string(),
+        msg: to_binary(&ExecuteMsg::ReceiveRedelegation {
+            staker: info.sender.to_string(),
+        })?,
+        funds: vec![amount.clone()],
+    };
+
+    let res = Response::new()
+        .add_attribute("action", "redelegate")
+        .add_attribute("amount", amount.amount)
+        .add_attribute("to_contract", &to_contract)
+        .add_attribute("sender", &info.sender)
+        .add_submessages(update_membership(
+            deps.storage,
+            info.sender,
+            new_stake + vesting_stake,
+            &cfg,
+            &env.block,
+        )?)
+        .add_submessage(SubMsg::reply_on_success(receive, REDELEGATE_REPLY_ID));
 
     Ok(res)
 }
 
-fn end_block<Q: CustomQuery>(deps: DepsMut<Q>, env: Env) -> Result<Response, ContractError> {
-    let mut resp = Response::new();
-
-    let config = CONFIG.load(deps.storage)?;
-    if config.auto_return_limit > 0 {
-        let sub_msgs = release_expired_claims(deps, env, config)?;
-        resp = resp.add_submessages(sub_msgs);
+pub fn execute_receive_redelegation<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    staker: String,
+) -> Result<Response, ContractError> {
+    if !SIBLINGS.has(deps.storage, &info.sender) {
+        return Err(ContractError::NotSibling(info.sender.into()));
     }
+    assert_not_paused(deps.storage, PausableAction::Bond, &env.block)?;
 
-    Ok(resp)
+    let cfg = CONFIG.load(deps.storage)?;
+    let amount = validate_funds(&info.funds, &cfg.denom)?;
+    let staker = deps.api.addr_validate(&staker)?;
+    add_bonded(deps.storage, &cfg, &staker, amount)?;
+
+    let new_stake = STAKE.update(deps.storage, &staker, |stake| -> StdResult<_> {
+        Ok(stake.unwrap_or_default() + amount)
+    })?;
+    let vesting_stake = STAKE_VESTING.update(deps.storage, &staker, |stake| -> StdResult<_> {
+        Ok(stake.unwrap_or_default())
+    })?;
+
+    let res = Response::new()
+        .add_attribute("action", "receive_redelegation")
+        .add_attribute("amount", amount)
+        .add_attribute("staker", &staker)
+        .add_attribute("sender", info.sender)
+        .add_submessages(update_membership(
+            deps.storage,
+            staker,
+            new_stake + vesting_stake,
+            &cfg,
+            &env.block,
+        )?);
+
+    Ok(res)
 }
 
-fn release_expired_claims<Q: CustomQuery>(
+pub fn execute_add_sibling<Q: CustomQuery>(
     deps: DepsMut<Q>,
-    env: Env,
-    config: Config,
-) -> Result<Vec<SubMsg>, ContractError> {
-    let release_data =
-        claims().claim_expired(deps.storage, &env.block, config.auto_return_limit)?;
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    let send_msgs = release_data
-        .liquid_releases
-        .into_iter()
-        .filter(|release_info| !release_info.amount.is_zero())
-        .map(|release_info| {
-            let amount = coins(release_info.amount.into(), config.denom.clone());
-            Ok(SubMsg::new(BankMsg::Send {
-                to_address: release_info.addr.into(),
-                amount,
-            }))
-        })
-        .collect::<StdResult<Vec<_>>>()?;
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    SIBLINGS.save(deps.storage, &contract_addr, &Empty {})?;
 
-    let undelegate_msgs = release_data
-        .vesting_releases
-        .into_iter()
-        .filter(|release_info| !release_info.amount.is_zero())
-        .map(|release_info| {
-            let amount = coin(release_info.amount.into(), config.denom.clone());
-            Ok(SubMsg::new(TgradeMsg::Undelegate {
-                funds: amount,
-                recipient: release_info.addr.to_string(),
-            }))
-        })
-        .collect::<StdResult<Vec<_>>>()?;
+    let res = Response::new()
+        .add_attribute("action", "add_sibling")
+        .add_attribute("sibling", contract)
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
 
-    Ok(send_msgs.into_iter().chain(undelegate_msgs).collect())
+pub fn execute_remove_sibling<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    SIBLINGS.remove(deps.storage, &contract_addr);
+
+    let res = Response::new()
+        .add_attribute("action", "remove_sibling")
+        .add_attribute("sibling", contract)
+        .add_attribute("sender", info.sender);
+    Ok(res)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<TgradeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    use QueryMsg::*;
-    match msg {
-        Configuration {} => to_binary(&CONFIG.load(deps.storage)?),
-        Member {
-            addr,
-            at_height: height,
-        } => to_binary(&query_member(deps, addr, height)?),
-        ListMembers { start_after, limit } => to_binary(&list_members(deps, start_after, limit)?),
-        ListMembersByPoints { start_after, limit } => {
-            to_binary(&list_members_by_points(deps, start_after, limit)?)
-        }
-        TotalPoints {} => to_binary(&query_total_points(deps)?),
-        Claims {
-            address,
-            limit,
-            start_after,
-        } => to_binary(&ClaimsResponse {
-            claims: claims().query_claims(
-                deps,
-                deps.api.addr_validate(&address)?,
-                limit,
-                start_after,
-            )?,
-        }),
-        Staked { address } => to_binary(&query_staked(deps, address)?),
-        Admin {} => to_binary(&ADMIN.query_admin(deps)?),
-        Hooks {} => {
-            let hooks = HOOKS.list_hooks(deps.storage)?;
-            to_binary(&HooksResponse { hooks })
-        }
-        Preauths {} => {
-            let preauths_hooks = PREAUTH_HOOKS.get_auth(deps.storage)?;
-            to_binary(&PreauthResponse { preauths_hooks })
-        }
-        UnbondingPeriod {} => {
-            let Config {
-                unbonding_period, ..
-            } = CONFIG.load(deps.storage)?;
-            to_binary(&UnbondingPeriodResponse { unbonding_period })
-        }
-        IsSlasher { addr } => {
-            let addr = deps.api.addr_validate(&addr)?;
-            to_binary(&SLASHERS.is_slasher(deps.storage, &addr)?)
-        }
-        ListSlashers {} => to_binary(&SLASHERS.list_slashers(deps.storage)?),
+pub fn validate_funds(funds: &[Coin], stake_denom: &str) -> Result<Uint128, ContractError> {
+    match funds {
+        [] => Ok(Uint128::zero()),
+        [Coin { denom, amount }] if denom == stake_denom => Ok(*amount),
+        [_] => Err(ContractError::MissingDenom(stake_denom.to_string())),
+        _ => Err(ContractError::ExtraDenoms(stake_denom.to_string())),
     }
 }
 
-fn query_total_points<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<TotalPointsResponse> {
-    let points = TOTAL.load(deps.storage)?;
-    Ok(TotalPointsResponse { points })
-}
+fn update_membership(
+    storage: &mut dyn Storage,
+    sender: Addr,
+    new_stake: Uint128,
+    cfg: &Config,
+    block: &BlockInfo,
+) -> StdResult<Vec<SubMsg>> {
+    let height = block.height;
+    if let Some(period) = cfg.average_stake_period {
+        update_stake_average(storage, &sender, new_stake, period, block)?;
+    }
+    // update their membership points, locked stake counts with its multiplier
+    let bonus = lockup_bonus(storage, &sender, block)?;
+    let new = calc_points(new_stake, bonus, cfg);
+    let old = members().may_load(storage, &sender)?.map(|mi| mi.points);
 
-pub fn query_staked<Q: CustomQuery>(deps: Deps<Q>, addr: String) -> StdResult<StakedResponse> {
-    let addr = deps.api.addr_validate(&addr)?;
-    let stake = STAKE.may_load(deps.storage, &addr)?.unwrap_or_default();
-    let vesting = STAKE_VESTING
-        .may_load(deps.storage, &addr)?
-        .unwrap_or_default();
-    let config = CONFIG.load(deps.storage)?;
+    // short-circuit if no change
+    if new == old {
+        return Ok(vec![]);
+    }
+    // otherwise, record change of points
+    match new.as_ref() {
+        Some(&p) => members().save(storage, &sender, &MemberInfo::new(p), height),
+        None => members().remove(storage, &sender, height),
+    }?;
 
-    Ok(StakedResponse {
-        liquid: coin(stake.u128(), config.denom.clone()),
-        vesting: coin(vesting.u128(), config.denom),
+    // update total
+    TOTAL.update(storage, height, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + new.unwrap_or_default() - old.unwrap_or_default())
+    })?;
+
+    // alert the hooks
+    let diff = MemberDiff::new(sender, old, new);
+    HOOKS.prepare_hooks(storage, |h| {
+        MemberChangedHookMsg::one(diff.clone())
+            .into_cosmos_msg(h)
+            .map(SubMsg::new)
     })
 }
 
-fn query_member<Q: CustomQuery>(
-    deps: Deps<Q>,
-    addr: String,
-    height: Option<u64>,
-) -> StdResult<MemberResponse> {
-    let addr = deps.api.addr_validate(&addr)?;
-    let mi = match height {
-        Some(h) => members().may_load_at_height(deps.storage, &addr, h),
-        None => members().may_load(deps.storage, &addr),
-    }?;
-    Ok(mi.into())
+/// Accounts the stake held since the last change to the average, and starts the new stake
+fn update_stake_average(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    new_stake: Uint128,
+    period: Duration,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    let now = block.time.seconds();
+    let average = STAKE_AVERAGES
+        .may_load(storage, addr)?
+        .map(|average| average.average_at(now, period))
+        .unwrap_or_default();
+    STAKE_AVERAGES.save(
+        storage,
+        addr,
+        &StakeAverage {
+            average,
+            stake: new_stake,
+            updated_at: now,
+        },
+    )
 }
 
-// settings for pagination
-const MAX_LIMIT: u32 = 100;
-const DEFAULT_LIMIT: u32 = 30;
+fn calc_points(stake: Uint128, lockup_bonus: Uint128, cfg: &Config) -> Option<u64> {
+    if stake < cfg.min_bond {
+        None
+    } else {
+        let p = (stake + lockup_bonus).u128() / cfg.tokens_per_point.u128();
+        Some(p as u64)
+    }
+}
 
-fn list_members<Q: CustomQuery>(
-    deps: Deps<Q>,
-    start_after: Option<String>,
-    limit: Option<u32>,
-) -> StdResult<MemberListResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let addr = maybe_addr(deps.api, start_after)?;
-    let start = addr.as_ref().map(Bound::exclusive);
+/// Stake counted for points on top of the actual stake thanks to multipliers of active lockups
+fn lockup_bonus(storage: &dyn Storage, addr: &Addr, block: &BlockInfo) -> StdResult<Uint128> {
+    LOCKUPS
+        .prefix(addr)
+        .range(storage, None, None, Order::Ascending)
+        .filter(|lockup| !matches!(lockup, Ok((_, l)) if l.locked_until.is_expired(block)))
+        .map(|lockup| lockup.map(|(_, l)| l.amount * (l.multiplier - Decimal::one())))
+        .sum()
+}
 
-    let members: StdResult<Vec<_>> = members()
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
-        .map(|item| {
-            let (
-                addr,
-                MemberInfo {
-                    points,
-                    start_height,
-                },
-            ) = item?;
-            Ok(Member {
-                addr: addr.into(),
-                points,
-                start_height,
-            })
-        })
-        .collect();
-
-    Ok(MemberListResponse { members: members? })
-}
-
-fn list_members_by_points<Q: CustomQuery>(
-    deps: Deps<Q>,
-    start_after: Option<Member>,
-    limit: Option<u32>,
-) -> StdResult<MemberListResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after
-        .map(|m| {
-            deps.api
-                .addr_validate(&m.addr)
-                .map(|addr| Bound::exclusive((m.points, addr)))
-        })
-        .transpose()?;
-
-    let members: StdResult<Vec<_>> = members()
-        .idx
-        .points
-        .range(deps.storage, None, start, Order::Descending)
-        .take(limit)
-        .map(|item| {
-            let (
-                addr,
-                MemberInfo {
-                    points,
-                    start_height,
-                },
-            ) = item?;
-            Ok(Member {
-                addr: addr.into(),
-                points,
-                start_height,
-            })
-        })
-        .collect();
-
-    Ok(MemberListResponse { members: members? })
+/// Part of the liquid `stake` of `addr` which is not locked by any active lockup
+fn unlocked_stake(
+    storage: &dyn Storage,
+    addr: &Addr,
+    stake: Uint128,
+    block: &BlockInfo,
+) -> StdResult<Uint128> {
+    let locked = LOCKUPS
+        .prefix(addr)
+        .range(storage, None, None, Order::Ascending)
+        .filter(|lockup| !matches!(lockup, Ok((_, l)) if l.locked_until.is_expired(block)))
+        .map(|lockup| lockup.map(|(_, l)| l.amount))
+        .sum::<StdResult<Uint128>>()?;
+    Ok(stake.saturating_sub(locked))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(
-    deps: DepsMut<TgradeQuery>,
-    _env: Env,
-    msg: MigrateMsg,
-) -> Result<Response, ContractError> {
-    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-
-    CONFIG.update::<_, StdError>(deps.storage, |mut cfg| {
-        if let Some(tokens_per_point) = msg.tokens_per_point {
-            let tokens_per_point = if tokens_per_point == Uint128::zero() {
-                Uint128::new(1)
-            } else {
-                tokens_per_point
-            };
-            cfg.tokens_per_point = tokens_per_point;
-        }
-        if let Some(min_bond) = msg.min_bond {
-            let min_bond = if min_bond == Uint128::zero() {
-                Uint128::new(1)
-            } else {
-                min_bond
-            };
-            cfg.min_bond = min_bond;
+/// Highest exponent accepted in denom metadata, enough for any token in the Cosmos ecosystem
+const MAX_DENOM_EXPONENT: u32 = 18;
+
+/// Validates the staking denom, returning it in its canonical form. Accepted are:
+/// * native denoms (eg. `utgd`), taken as they are,
+/// * IBC denoms (`ibc/{hash}`), with the sha256 hash normalized to upper case hex as the
+///   chain reports it in balances,
+/// * token factory denoms (`factory/{creator}/{subdenom}`), with the creator address
+///   validated and normalized to lower case.
+pub fn validate_denom(api: &dyn Api, denom: &str) -> Result<String, ContractError> {
+    let invalid = || ContractError::InvalidStakingDenom(denom.to_owned());
+    let is_denom_char = |c: char| c.is_ascii_alphanumeric() || "/:._-".contains(c);
+
+    match denom.split_once('/') {
+        Some((prefix, hash)) if prefix.eq_ignore_ascii_case("ibc") => {
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            Ok(format!("ibc/{}", hash.to_ascii_uppercase()))
         }
-        if let Some(unbonding_period) = msg.unbonding_period {
-            cfg.unbonding_period = Duration::new(unbonding_period);
+        Some((prefix, rest)) if prefix.eq_ignore_ascii_case("factory") => {
+            let (creator, subdenom) = rest.split_once('/').ok_or_else(invalid)?;
+            let creator = api
+                .addr_validate(&creator.to_ascii_lowercase())
+                .map_err(|_| invalid())?;
+            if subdenom.is_empty() || subdenom.len() > 44 || !subdenom.chars().all(is_denom_char) {
+                return Err(invalid());
+            }
+            Ok(format!("factory/{}/{}", creator, subdenom))
         }
-        if let Some(auto_return_limit) = msg.auto_return_limit {
-            cfg.auto_return_limit = auto_return_limit;
+        _ => {
+            let first_is_letter = denom.starts_with(|c: char| c.is_ascii_alphabetic());
+            if !(3..=128).contains(&denom.len())
+                || !first_is_letter
+                || !denom.chars().all(is_denom_char)
+            {
+                return Err(invalid());
+            }
+            Ok(denom.to_owned())
         }
-        Ok(cfg)
-    })?;
+    }
+}
 
-    if let Some(undelegations) = msg.undelegations {
-        let msgs = process_pending_undelegations(deps.as_ref(), &undelegations)?;
-        Ok(Response::new().add_messages(msgs))
-    } else {
-        Ok(Response::new())
+fn validate_denom_metadata(metadata: &DenomMetadata) -> Result<(), ContractError> {
+    if metadata.display.trim().is_empty() || metadata.exponent > MAX_DENOM_EXPONENT {
+        return Err(ContractError::InvalidDenomMetadata(MAX_DENOM_EXPONENT));
     }
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::claim::Claim;
-    use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{
-        from_slice, CosmosMsg, OverflowError, OverflowOperation, StdError, Storage,
-    };
-    use tg4::{member_key, TOTAL_KEY};
-    use tg_utils::{Expiration, HookError, PreauthError, SlasherError};
+fn validate_lockup_tiers(tiers: &[LockupTier]) -> Result<(), ContractError> {
+    for (idx, tier) in tiers.iter().enumerate() {
+        if tier.period.seconds() == 0
+            || tier.multiplier < Decimal::one()
+            || tiers[..idx].iter().any(|t| t.period == tier.period)
+        {
+            return Err(ContractError::InvalidLockupTiers {});
+        }
+    }
+    Ok(())
+}
 
-    use crate::error::ContractError;
+pub fn execute_lock<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    tokens: Coin,
+    period: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if tokens.amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    if cfg.denom != tokens.denom {
+        return Err(ContractError::InvalidDenom {});
+    }
+    let tier = cfg
+        .lockup_tiers
+        .iter()
+        .find(|tier| tier.period.seconds() == period)
+        .ok_or(ContractError::UnknownLockupPeriod(period))?;
+
+    let lockups = LOCKUPS
+        .prefix(&info.sender)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if lockups >= MAX_LOCKUPS_PER_ADDRESS {
+        return Err(ContractError::TooManyLockups(MAX_LOCKUPS_PER_ADDRESS));
+    }
 
-    use super::*;
-    use tg_bindings_test::mock_deps_tgrade;
+    let stake = STAKE
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let unlocked = unlocked_stake(deps.storage, &info.sender, stake, &env.block)?;
+    if tokens.amount > unlocked {
+        return Err(ContractError::InsufficientUnlockedStake { unlocked });
+    }
 
-    const INIT_ADMIN: &str = "juan";
-    const USER1: &str = "user1";
-    const USER2: &str = "user2";
-    const USER3: &str = "user3";
-    const DENOM: &str = "stake";
-    const TOKENS_PER_POINT: Uint128 = Uint128::new(1_000);
-    const MIN_BOND: Uint128 = Uint128::new(5_000);
-    const UNBONDING_DURATION: u64 = 100;
+    let id = LOCKUP_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+    LOCKUP_ID.save(deps.storage, &id)?;
+    let lockup = Lockup {
+        amount: tokens.amount,
+        multiplier: tier.multiplier,
+        locked_until: tier.period.after(&env.block),
+    };
+    LOCKUPS.save(deps.storage, (&info.sender, id), &lockup)?;
+    LOCKUP_EXPIRATIONS.save(
+        deps.storage,
+        (lockup.locked_until.as_key(), &info.sender, id),
+        &Empty {},
+    )?;
 
-    fn default_instantiate(deps: DepsMut<TgradeQuery>) {
-        do_instantiate(deps, TOKENS_PER_POINT, MIN_BOND, UNBONDING_DURATION, 0)
-    }
+    let vesting_stake = STAKE_VESTING
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
 
-    fn do_instantiate(
-        deps: DepsMut<TgradeQuery>,
-        tokens_per_point: Uint128,
-        min_bond: Uint128,
-        unbonding_period: u64,
-        auto_return_limit: u64,
-    ) {
-        let msg = InstantiateMsg {
-            denom: "stake".to_owned(),
-            tokens_per_point,
-            min_bond,
-            unbonding_period,
-            admin: Some(INIT_ADMIN.into()),
-            preauths_hooks: 1,
-            preauths_slashing: 1,
-            auto_return_limit,
-        };
-        let info = mock_info("creator", &[]);
-        instantiate(deps, mock_env(), info, msg).unwrap();
-    }
+    let res = Response::new()
+        .add_attribute("action", "lock")
+        .add_attribute("amount", tokens.amount)
+        .add_attribute("lockup_id", id.to_string())
+        .add_attribute(
+            "locked_until",
+            lockup.locked_until.time().nanos().to_string(),
+        )
+        .add_attribute("sender", &info.sender)
+        .add_submessages(update_membership(
+            deps.storage,
+            info.sender,
+            stake + vesting_stake,
+            &cfg,
+            &env.block,
+        )?);
 
-    // Helper for staking only liquid assets
-    fn bond_liquid(
-        deps: DepsMut<TgradeQuery>,
-        user1: u128,
-        user2: u128,
-        user3: u128,
-        height_delta: u64,
-    ) {
-        bond(deps, (user1, 0), (user2, 0), (user3, 0), height_delta);
-    }
+    Ok(res)
+}
 
-    // Helper for staking only illiquid assets
-    fn bond_vesting(
-        deps: DepsMut<TgradeQuery>,
-        user1: u128,
-        user2: u128,
-        user3: u128,
-        height_delta: u64,
-    ) {
-        bond(deps, (0, user1), (0, user2), (0, user3), height_delta);
-    }
+pub fn execute_claim<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let res = Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("sender", &info.sender);
 
-    // Full stake is composed of `(liquid, illiquid (vesting))` amounts
-    fn bond(
-        mut deps: DepsMut<TgradeQuery>,
-        user1_stake: (u128, u128),
-        user2_stake: (u128, u128),
-        user3_stake: (u128, u128),
-        height_delta: u64,
-    ) {
-        let mut env = mock_env();
-        env.block.height += height_delta;
+    release_claims(deps, env, &info.sender, res)
+}
 
-        for (addr, stake) in &[
-            (USER1, user1_stake),
-            (USER2, user2_stake),
-            (USER3, user3_stake),
-        ] {
-            if stake.0 != 0 || stake.1 != 0 {
-                let vesting_tokens = if stake.1 != 0 {
-                    Some(coin(stake.1, DENOM))
-                } else {
-                    None
-                };
-                let msg = ExecuteMsg::Bond { vesting_tokens };
-                let info = mock_info(addr, &coins(stake.0, DENOM));
-                execute(deps.branch(), env.clone(), info, msg).unwrap();
+pub fn execute_set_permit_key<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    match pubkey {
+        Some(pubkey) => {
+            if pubkey.len() != 33 && pubkey.len() != 65 {
+                return Err(ContractError::InvalidPermitKey {});
             }
+            PERMIT_KEYS.save(deps.storage, &info.sender, &pubkey)?;
         }
+        None => PERMIT_KEYS.remove(deps.storage, &info.sender),
     }
 
-    fn unbond(
-        mut deps: DepsMut<TgradeQuery>,
-        user1: u128,
-        user2: u128,
-        user3: u128,
-        height_delta: u64,
-        time_delta: u64,
-    ) {
-        let mut env = mock_env();
-        env.block.height += height_delta;
-        env.block.time = env.block.time.plus_seconds(time_delta);
+    Ok(Response::new()
+        .add_attribute("action", "set_permit_key")
+        .add_attribute("sender", &info.sender))
+}
 
-        for (addr, stake) in &[(USER1, user1), (USER2, user2), (USER3, user3)] {
-            if *stake != 0 {
-                let msg = ExecuteMsg::Unbond {
-                    tokens: coin(*stake, DENOM),
-                };
-                let info = mock_info(addr, &[]);
-                execute(deps.branch(), env.clone(), info, msg).unwrap();
+pub fn execute_set_payout_address<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    addr: Option<String>,
+    require_confirmation: bool,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("action", "set_payout_address")
+        .add_attribute("sender", &info.sender);
+
+    // Any previously pending address is replaced
+    PENDING_PAYOUT_ADDRESSES.remove(deps.storage, &info.sender);
+    match addr {
+        Some(addr) => {
+            let addr = deps.api.addr_validate(&addr)?;
+            res = res
+                .add_attribute("payout_address", &addr)
+                .add_attribute("pending", require_confirmation.to_string());
+            if require_confirmation {
+                PENDING_PAYOUT_ADDRESSES.save(deps.storage, &info.sender, &addr)?;
+            } else {
+                PAYOUT_ADDRESSES.save(deps.storage, &info.sender, &addr)?;
             }
         }
+        None => PAYOUT_ADDRESSES.remove(deps.storage, &info.sender),
     }
 
-    #[test]
-    fn proper_instantiation() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+    Ok(res)
+}
 
-        // it worked, let's query the state
-        let res = ADMIN.query_admin(deps.as_ref()).unwrap();
-        assert_eq!(Some(INIT_ADMIN.into()), res.admin);
+pub fn execute_confirm_payout_address<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    staker: String,
+) -> Result<Response, ContractError> {
+    let staker = deps.api.addr_validate(&staker)?;
+    match PENDING_PAYOUT_ADDRESSES.may_load(deps.storage, &staker)? {
+        Some(pending) if pending == info.sender => (),
+        _ => return Err(ContractError::NoPendingPayoutAddress(staker.into())),
+    }
 
-        let res = query_total_points(deps.as_ref()).unwrap();
-        assert_eq!(0, res.points);
+    PENDING_PAYOUT_ADDRESSES.remove(deps.storage, &staker);
+    PAYOUT_ADDRESSES.save(deps.storage, &staker, &info.sender)?;
 
-        let raw = query(deps.as_ref(), mock_env(), QueryMsg::Configuration {}).unwrap();
-        let res: Config = from_slice(&raw).unwrap();
-        assert_eq!(
-            res,
-            Config {
-                denom: "stake".to_owned(),
-                tokens_per_point: TOKENS_PER_POINT,
-                min_bond: MIN_BOND,
-                unbonding_period: Duration::new(UNBONDING_DURATION),
-                auto_return_limit: 0,
-            }
-        );
+    Ok(Response::new()
+        .add_attribute("action", "confirm_payout_address")
+        .add_attribute("sender", &info.sender)
+        .add_attribute("staker", staker))
+}
 
-        // query the admin's staked amount (just to confirm the query works)
-        let res = query_staked(deps.as_ref(), INIT_ADMIN.into()).unwrap();
-        assert_eq!(coin(0, "stake"), res.liquid);
-        assert_eq!(coin(0, "stake"), res.vesting);
+/// Address liquid tokens of `staker` matured claims are sent to
+fn payout_address(storage: &dyn Storage, staker: &Addr) -> StdResult<Addr> {
+    Ok(PAYOUT_ADDRESSES
+        .may_load(storage, staker)?
+        .unwrap_or_else(|| staker.clone()))
+}
+
+pub fn execute_claim_with_permit<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    permit: ClaimPermit,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&permit.owner)?;
+
+    if env.block.time > permit.deadline {
+        return Err(ContractError::PermitExpired {});
     }
 
-    #[test]
-    fn unbonding_period_query_works() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+    let pubkey = PERMIT_KEYS
+        .may_load(deps.storage, &owner)?
+        .ok_or_else(|| ContractError::NoPermitKey(owner.to_string()))?;
 
-        let raw = query(deps.as_ref(), mock_env(), QueryMsg::UnbondingPeriod {}).unwrap();
-        let res: UnbondingPeriodResponse = from_slice(&raw).unwrap();
-        assert_eq!(res.unbonding_period, Duration::new(UNBONDING_DURATION));
+    let nonce = PERMIT_NONCES
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    if permit.nonce != nonce {
+        return Err(ContractError::InvalidPermitNonce {
+            expected: nonce,
+            got: permit.nonce,
+        });
     }
 
-    fn get_member(deps: Deps<TgradeQuery>, addr: String, at_height: Option<u64>) -> Option<u64> {
-        let raw = query(deps, mock_env(), QueryMsg::Member { addr, at_height }).unwrap();
-        let res: MemberResponse = from_slice(&raw).unwrap();
-        res.points
+    let hash = ClaimPermitSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        permit,
+    }
+    .hash()?;
+    let valid = deps
+        .api
+        .secp256k1_verify(&hash, &signature, &pubkey)
+        .map_err(|_| ContractError::InvalidPermitSignature {})?;
+    if !valid {
+        return Err(ContractError::InvalidPermitSignature {});
     }
 
-    // this tests the member queries
-    #[track_caller]
-    fn assert_users(
-        deps: Deps<TgradeQuery>,
-        user1_points: Option<u64>,
-        user2_points: Option<u64>,
-        user3_points: Option<u64>,
-        height: Option<u64>,
-    ) {
-        let member1 = get_member(deps, USER1.into(), height);
-        assert_eq!(member1, user1_points);
+    PERMIT_NONCES.save(deps.storage, &owner, &(nonce + 1))?;
 
-        let member2 = get_member(deps, USER2.into(), height);
-        assert_eq!(member2, user2_points);
+    let res = Response::new()
+        .add_attribute("action", "claim_with_permit")
+        .add_attribute("sender", &info.sender)
+        .add_attribute("owner", &owner);
 
-        let member3 = get_member(deps, USER3.into(), height);
-        assert_eq!(member3, user3_points);
+    release_claims(deps, env, &owner, res)
+}
 
-        // this is only valid if we are not doing a historical query
-        if height.is_none() {
-            // compute expected metrics
-            let points = vec![user1_points, user2_points, user3_points];
-            let sum: u64 = points.iter().map(|x| x.unwrap_or_default()).sum();
-            let count = points.iter().filter(|x| x.is_some()).count();
+/// Releases matured claims of `owner`, sending liquid tokens to the owner's payout address and
+/// undelegating vesting tokens back to the owner
+fn release_claims<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    owner: &Addr,
+    mut res: Response,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, PausableAction::Claim, &env.block)?;
 
-            // TODO: more detailed compare?
-            let msg = QueryMsg::ListMembers {
-                start_after: None,
-                limit: None,
-            };
-            let raw = query(deps, mock_env(), msg).unwrap();
-            let members: MemberListResponse = from_slice(&raw).unwrap();
-            assert_eq!(count, members.members.len());
+    let config = CONFIG.load(deps.storage)?;
 
-            let raw = query(deps, mock_env(), QueryMsg::TotalPoints {}).unwrap();
-            let total: TotalPointsResponse = from_slice(&raw).unwrap();
-            assert_eq!(sum, total.points); // 17 - 11 + 15 = 21
-        }
+    let (release, vesting_release, interest) = claims().claim_addr(
+        deps.storage,
+        owner,
+        &env.block,
+        None,
+        config.unbonding_interest,
+    )?;
+    if release.is_zero() && vesting_release.is_zero() {
+        return Err(ContractError::NothingToClaim {});
     }
 
-    // this tests the member queries of liquid amounts
-    #[track_caller]
-    fn assert_stake_liquid(deps: Deps<TgradeQuery>, user1: u128, user2: u128, user3: u128) {
-        let stake1 = query_staked(deps, USER1.into()).unwrap();
-        assert_eq!(stake1.liquid, coin(user1, DENOM));
+    let interest = take_interest(deps.storage, interest)?;
+    if !interest.is_zero() {
+        res = res.add_attribute("interest", interest);
+    }
+    let release = release + interest;
 
-        let stake2 = query_staked(deps, USER2.into()).unwrap();
-        assert_eq!(stake2.liquid, coin(user2, DENOM));
+    if !release.is_zero() {
+        let amount = coin(release.into(), config.denom.clone());
+        res = res
+            .add_attribute("liquid_tokens", amount.to_string())
+            .add_message(BankMsg::Send {
+                to_address: payout_address(deps.storage, owner)?.to_string(),
+                amount: vec![amount],
+            });
+    }
 
-        let stake3 = query_staked(deps, USER3.into()).unwrap();
-        assert_eq!(stake3.liquid, coin(user3, DENOM));
+    if !vesting_release.is_zero() {
+        let vesting_amount = coin(vesting_release.into(), config.denom);
+        // Undelegate (unstake from contract) to sender's vesting account
+        res = res
+            .add_attribute("vesting_tokens", vesting_amount.to_string())
+            .add_message(TgradeMsg::Undelegate {
+                funds: vesting_amount,
+                recipient: owner.to_string(),
+            });
     }
 
-    // this tests the member queries of illiquid amounts
-    #[track_caller]
-    fn assert_stake_vesting(deps: Deps<TgradeQuery>, user1: u128, user2: u128, user3: u128) {
-        let stake1 = query_staked(deps, USER1.into()).unwrap();
-        assert_eq!(stake1.vesting, coin(user1, DENOM));
+    Ok(res)
+}
 
-        let stake2 = query_staked(deps, USER2.into()).unwrap();
-        assert_eq!(stake2.vesting, coin(user2, DENOM));
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(
+    deps: DepsMut<TgradeQuery>,
+    env: Env,
+    msg: TgradeSudoMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        TgradeSudoMsg::PrivilegeChange(PrivilegeChangeMsg::Promoted {}) => privilege_promote(deps),
+        TgradeSudoMsg::EndBlock {} => end_block(deps, env),
+        _ => Err(ContractError::UnknownSudoMsg {}),
+    }
+}
 
-        let stake3 = query_staked(deps, USER3.into()).unwrap();
-        assert_eq!(stake3.vesting, coin(user3, DENOM));
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut<TgradeQuery>, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        REDELEGATE_REPLY_ID => redelegate_reply(deps),
+        _ => Err(ContractError::UnrecognisedReply(msg.id)),
     }
+}
 
-    #[test]
-    fn bond_stake_liquid_adds_membership() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
-        let height = mock_env().block.height;
+/// Sibling accepted the redelegated stake
+fn redelegate_reply<Q: CustomQuery>(deps: DepsMut<Q>) -> Result<Response, ContractError> {
+    let pending = PENDING_REDELEGATION.load(deps.storage)?;
+    PENDING_REDELEGATION.remove(deps.storage);
 
-        // Assert original points
-        assert_users(deps.as_ref(), None, None, None, None);
+    Ok(Response::new()
+        .add_attribute("action", "redelegate_confirmed")
+        .add_attribute("staker", pending.staker)
+        .add_attribute("to_contract", pending.to_contract)
+        .add_attribute("amount", pending.amount))
+}
 
-        // ensure it rounds down, and respects cut-off
-        bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+fn privilege_promote<Q: CustomQuery>(deps: DepsMut<Q>) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
 
-        // Assert updated points
-        assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
-        assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+    let mut res = Response::new();
+    if config.auto_return_limit > 0 || !config.lockup_tiers.is_empty() {
+        let msgs = request_privileges(&[Privilege::EndBlocker]);
+        res = res.add_submessages(msgs);
+    }
+    let msgs = request_privileges(&[Privilege::Delegator]);
+    res = res.add_submessages(msgs);
 
-        // add some more, ensure the sum is properly respected (7.5 + 7.6 = 15 not 14)
-        bond_liquid(deps.as_mut(), 0, 7_600, 1_200, 2);
+    Ok(res)
+}
 
-        // Assert updated points
-        assert_stake_liquid(deps.as_ref(), 12_000, 15_100, 5_200);
-        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), None);
+fn end_block<Q: CustomQuery>(deps: DepsMut<Q>, env: Env) -> Result<Response, ContractError> {
+    let mut resp = Response::new();
 
-        // check historical queries all work
-        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
-        assert_users(deps.as_ref(), Some(12), Some(7), None, Some(height + 2)); // after first stake
-        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), Some(height + 3));
-        // after second stake
+    let config = CONFIG.load(deps.storage)?;
+    let sub_msgs = release_expired_lockups(deps.storage, &env.block, &config)?;
+    resp = resp.add_submessages(sub_msgs);
+
+    migrate_claims(deps.storage, CLAIM_BUCKETS_MIGRATION_BATCH)?;
+
+    // Paused claims are returned once the pause ends
+    let claims_paused = PAUSES
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .paused_until(PausableAction::Claim, &env.block)
+        .is_some();
+    if config.auto_return_limit > 0 && !claims_paused {
+        let sub_msgs = release_expired_claims(deps, env, config)?;
+        resp = resp.add_submessages(sub_msgs);
     }
 
-    #[test]
-    fn bond_stake_vesting_adds_membership() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
-        let height = mock_env().block.height;
-
-        // Assert original points
-        assert_users(deps.as_ref(), None, None, None, None);
-
-        // ensure it rounds down, and respects cut-off
-        bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+    Ok(resp)
+}
 
-        // Assert updated points
-        assert_stake_vesting(deps.as_ref(), 12_000, 7_500, 4_000);
-        assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+/// Maximum number of claims moved to the tagged claims map or claims buckets in a single end
+/// block or `MigrateClaimBuckets` call
+const CLAIM_BUCKETS_MIGRATION_BATCH: u64 = 100;
 
-        // add some more, ensure the sum is properly respected (7.5 + 7.6 = 15 not 14)
-        bond_vesting(deps.as_mut(), 0, 7_600, 1_200, 2);
+/// Maximum number of expired lockups released in a single end block
+const LOCKUP_RELEASE_LIMIT: usize = 50;
 
-        // Assert updated points
-        assert_stake_vesting(deps.as_ref(), 12_000, 15_100, 5_200);
-        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), None);
+/// Removes expired lockups, so their multipliers are not applied to the stake anymore
+fn release_expired_lockups(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    config: &Config,
+) -> StdResult<Vec<SubMsg>> {
+    let now = block.time.nanos();
+    let expired = LOCKUP_EXPIRATIONS
+        .keys(storage, None, None, Order::Ascending)
+        .take_while(|key| !matches!(key, Ok((expiration, _, _)) if *expiration > now))
+        .take(LOCKUP_RELEASE_LIMIT)
+        .collect::<StdResult<Vec<_>>>()?;
 
-        // check historical queries all work
-        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
-        assert_users(deps.as_ref(), Some(12), Some(7), None, Some(height + 2)); // after first stake
-        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), Some(height + 3));
-        // after second stake
+    let mut stakers = vec![];
+    for (expiration, staker, id) in expired {
+        LOCKUP_EXPIRATIONS.remove(storage, (expiration, &staker, id));
+        LOCKUPS.remove(storage, (&staker, id));
+        if !stakers.contains(&staker) {
+            stakers.push(staker);
+        }
     }
 
-    #[test]
-    fn bond_mixed_stake_adds_membership() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
-        let height = mock_env().block.height;
+    let mut msgs = vec![];
+    for staker in stakers {
+        let stake = STAKE.may_load(storage, &staker)?.unwrap_or_default()
+            + STAKE_VESTING
+                .may_load(storage, &staker)?
+                .unwrap_or_default();
+        msgs.extend(update_membership(storage, staker, stake, config, block)?);
+    }
+    Ok(msgs)
+}
 
-        // Assert original points
-        assert_users(deps.as_ref(), None, None, None, None);
+fn release_expired_claims<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    config: Config,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let release_data = claims().claim_expired(
+        deps.storage,
+        &env.block,
+        config.auto_return_limit,
+        config.unbonding_interest,
+    )?;
+    if release_data.released > 0 {
+        update_stats(deps.storage, |stats| {
+            stats.claims_auto_returned += release_data.released
+        })?;
+    }
 
-        // ensure it rounds down, and respects cut-off
-        bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+    let send_msgs = release_data
+        .liquid_releases
+        .into_iter()
+        .filter(|release_info| !release_info.amount.is_zero())
+        .map(|release_info| {
+            let interest = take_interest(deps.storage, release_info.interest)?;
+            let amount = coins(
+                (release_info.amount + interest).into(),
+                config.denom.clone(),
+            );
+            Ok(SubMsg::new(BankMsg::Send {
+                to_address: payout_address(deps.storage, &release_info.addr)?.into(),
+                amount,
+            }))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
-        // Assert updated points
-        assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
-        assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+    let undelegate_msgs = release_data
+        .vesting_releases
+        .into_iter()
+        .filter(|release_info| !release_info.amount.is_zero())
+        .map(|release_info| {
+            let amount = coin(release_info.amount.into(), config.denom.clone());
+            Ok(SubMsg::new(TgradeMsg::Undelegate {
+                funds: amount,
+                recipient: release_info.addr.to_string(),
+            }))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
-        // add some more, ensure the sum is properly respected (7.5 + 7.6 = 15 not 14)
-        bond_vesting(deps.as_mut(), 0, 7_600, 1_200, 2);
+    Ok(send_msgs.into_iter().chain(undelegate_msgs).collect())
+}
 
-        // Assert updated points
-        assert_stake_vesting(deps.as_ref(), 0, 7_600, 1_200);
-        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), None);
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    use QueryMsg::*;
+    match msg {
+        Configuration {} => to_binary(&CONFIG.load(deps.storage)?),
+        Member {
+            addr,
+            at_height: height,
+        } => to_binary(&query_member(deps, addr, height)?),
+        ListMembers { start_after, limit } => to_binary(&list_members(deps, start_after, limit)?),
+        ListMembersByPoints { start_after, limit } => {
+            to_binary(&list_members_by_points(deps, start_after, limit)?)
+        }
+        TotalPoints { at_height } => to_binary(&query_total_points(deps, at_height)?),
+        Claims {
+            address,
+            limit,
+            start_after,
+            tag,
+        } => to_binary(&query_claims(deps, address, limit, start_after, tag)?),
+        Staked { address } => to_binary(&query_staked(deps, address)?),
+        Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        Hooks {} => {
+            let hooks = HOOKS.list_hooks(deps.storage)?;
+            to_binary(&HooksResponse { hooks })
+        }
+        Preauths {} => {
+            let preauths_hooks = PREAUTH_HOOKS.get_auth(deps.storage)?;
+            to_binary(&PreauthResponse { preauths_hooks })
+        }
+        UnbondingPeriod {} => {
+            let Config {
+                unbonding_period, ..
+            } = CONFIG.load(deps.storage)?;
+            to_binary(&UnbondingPeriodResponse { unbonding_period })
+        }
+        IsSlasher { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            to_binary(&SLASHERS.is_slasher(deps.storage, &addr)?)
+        }
+        ListSlashers {} => to_binary(&SLASHERS.list_slashers(deps.storage)?),
+        SlasherLimit { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            to_binary(&SLASHERS.slasher_limit(deps.storage, &addr)?)
+        }
+        IsOperator { addr } => {
+            let addr = deps.api.addr_validate(&addr)?;
+            to_binary(&OPERATORS.has(deps.storage, &addr))
+        }
+        ListSiblings {} => to_binary(&list_siblings(deps)?),
+        ListUnbondControllers {} => to_binary(&list_unbond_controllers(deps)?),
+        ExportStakes {
+            start_after,
+            limit,
+            prev_hash,
+        } => to_binary(&export_stakes(deps, env, start_after, limit, prev_hash)?),
+        ListAllStakes { start_after, limit } => {
+            to_binary(&list_all_stakes(deps, start_after, limit)?)
+        }
+        PermitInfo { owner } => to_binary(&query_permit_info(deps, owner)?),
+        PayoutAddress { staker } => to_binary(&query_payout_address(deps, staker)?),
+        BondHeadroom { address } => to_binary(&query_bond_headroom(deps, address)?),
+        Lockups { address } => to_binary(&query_lockups(deps, address)?),
+        AverageStake { address } => to_binary(&query_average_stake(deps, env, address)?),
+        Stats {} => to_binary(&query_stats(deps)?),
+        InterestPool {} => to_binary(&query_interest_pool(deps)?),
+        Denom {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&DenomResponse {
+                denom: config.denom,
+                metadata: config.denom_metadata,
+            })
+        }
+        UnbondingBuckets { start_after, limit } => {
+            to_binary(&query_unbonding_buckets(deps, start_after, limit)?)
+        }
+        Paused {} => to_binary(&query_paused(deps, &env.block)?),
+    }
+}
+
+fn query_paused<Q: CustomQuery>(deps: Deps<Q>, block: &BlockInfo) -> StdResult<PausedResponse> {
+    let pauses = PAUSES.may_load(deps.storage)?.unwrap_or_default();
+    let max_pause_duration = CONFIG.load(deps.storage)?.max_pause_duration;
+    Ok(PausedResponse {
+        bond: pauses.paused_until(PausableAction::Bond, block),
+        unbond: pauses.paused_until(PausableAction::Unbond, block),
+        claim: pauses.paused_until(PausableAction::Claim, block),
+        max_pause_duration: max_pause_duration.map(|duration| duration.seconds()),
+    })
+}
+
+fn query_unbonding_buckets<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<Timestamp>,
+    limit: Option<u32>,
+) -> StdResult<UnbondingBucketsResponse> {
+    let buckets = claims()
+        .query_buckets(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(start, bucket)| UnbondingBucket {
+            start,
+            end: start.plus_seconds(CLAIM_BUCKET_SECONDS),
+            claims: bucket.claims,
+            amount: bucket.amount,
+            vesting_amount: bucket.vesting_amount,
+        })
+        .collect();
+
+    Ok(UnbondingBucketsResponse {
+        buckets,
+        complete: claims().buckets_complete(deps.storage)?,
+    })
+}
+
+fn query_claims<Q: CustomQuery>(
+    deps: Deps<Q>,
+    address: String,
+    limit: Option<u32>,
+    start_after: Option<Expiration>,
+    tag: Option<String>,
+) -> StdResult<ClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let claims = claims().query_claims(deps, address, limit, start_after, tag)?;
+
+    let rate = CONFIG.load(deps.storage)?.unbonding_interest;
+    let pool = INTEREST_POOL.may_load(deps.storage)?.unwrap_or_default();
+    let payouts = claims
+        .iter()
+        .map(|claim| match rate {
+            Some(rate) => claim.amount + claim.interest(rate).min(pool),
+            None => claim.amount,
+        })
+        .collect();
+
+    Ok(ClaimsResponse { claims, payouts })
+}
+
+fn query_interest_pool<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<InterestPoolResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let balance = INTEREST_POOL.may_load(deps.storage)?.unwrap_or_default();
+    Ok(InterestPoolResponse {
+        rate: config.unbonding_interest,
+        balance: coin(balance.u128(), config.denom),
+    })
+}
+
+fn query_stats<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<StatsResponse> {
+    let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(StatsResponse {
+        total_bonds: stats.total_bonds,
+        total_unbonds: stats.total_unbonds,
+        total_slashed: stats.total_slashed,
+        claims_outstanding: claims().count(deps.storage)?,
+        claims_auto_returned: stats.claims_auto_returned,
+    })
+}
+
+fn query_average_stake<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    address: String,
+) -> StdResult<AverageStakeResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let stake = STAKE.may_load(deps.storage, &address)?.unwrap_or_default()
+        + STAKE_VESTING
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+    let average = match CONFIG.load(deps.storage)?.average_stake_period {
+        Some(period) => Some(
+            STAKE_AVERAGES
+                .may_load(deps.storage, &address)?
+                .map(|average| average.average_at(env.block.time.seconds(), period))
+                .unwrap_or_default(),
+        ),
+        None => None,
+    };
+    Ok(AverageStakeResponse { stake, average })
+}
+
+fn query_lockups<Q: CustomQuery>(deps: Deps<Q>, address: String) -> StdResult<LockupsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let lockups = LOCKUPS
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|lockup| {
+            lockup.map(|(id, lockup)| LockupResponse {
+                id,
+                amount: lockup.amount,
+                multiplier: lockup.multiplier,
+                locked_until: lockup.locked_until,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(LockupsResponse { lockups })
+}
+
+fn query_bond_headroom<Q: CustomQuery>(
+    deps: Deps<Q>,
+    address: String,
+) -> StdResult<BondHeadroomResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let stake = STAKE.may_load(deps.storage, &address)?.unwrap_or_default()
+        + STAKE_VESTING
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+    let total_bonded = TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(BondHeadroomResponse {
+        address: cfg
+            .max_bond_per_address
+            .map(|max| max.saturating_sub(stake)),
+        total: cfg
+            .max_total_bond
+            .map(|max| max.saturating_sub(total_bonded)),
+        total_bonded,
+    })
+}
+
+fn query_permit_info<Q: CustomQuery>(
+    deps: Deps<Q>,
+    owner: String,
+) -> StdResult<PermitInfoResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let pubkey = PERMIT_KEYS.may_load(deps.storage, &owner)?;
+    let nonce = PERMIT_NONCES
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    Ok(PermitInfoResponse { pubkey, nonce })
+}
+
+fn query_payout_address<Q: CustomQuery>(
+    deps: Deps<Q>,
+    staker: String,
+) -> StdResult<PayoutAddressResponse> {
+    let staker = deps.api.addr_validate(&staker)?;
+    let payout_address = PAYOUT_ADDRESSES
+        .may_load(deps.storage, &staker)?
+        .map(Addr::into_string);
+    let pending = PENDING_PAYOUT_ADDRESSES
+        .may_load(deps.storage, &staker)?
+        .map(Addr::into_string);
+    Ok(PayoutAddressResponse {
+        payout_address,
+        pending,
+    })
+}
+
+fn query_total_points<Q: CustomQuery>(
+    deps: Deps<Q>,
+    height: Option<u64>,
+) -> StdResult<TotalPointsResponse> {
+    let points = match height {
+        Some(h) => TOTAL
+            .may_load_at_height(deps.storage, h)?
+            .unwrap_or_default(),
+        None => TOTAL.load(deps.storage)?,
+    };
+    Ok(TotalPointsResponse { points })
+}
+
+pub fn query_staked<Q: CustomQuery>(deps: Deps<Q>, addr: String) -> StdResult<StakedResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let stake = STAKE.may_load(deps.storage, &addr)?.unwrap_or_default();
+    let vesting = STAKE_VESTING
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default();
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(StakedResponse {
+        liquid: coin(stake.u128(), config.denom.clone()),
+        vesting: coin(vesting.u128(), config.denom),
+    })
+}
+
+fn query_member<Q: CustomQuery>(
+    deps: Deps<Q>,
+    addr: String,
+    height: Option<u64>,
+) -> StdResult<MemberResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let mi = match height {
+        Some(h) => members().may_load_at_height(deps.storage, &addr, h),
+        None => members().may_load(deps.storage, &addr),
+    }?;
+    Ok(mi.into())
+}
+
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 30;
+
+fn list_members<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let members: StdResult<Vec<_>> = members()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (
+                addr,
+                MemberInfo {
+                    points,
+                    start_height,
+                },
+            ) = item?;
+            Ok(Member {
+                addr: addr.into(),
+                points,
+                start_height,
+            })
+        })
+        .collect();
+
+    Ok(MemberListResponse { members: members? })
+}
+
+fn list_members_by_points<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<Member>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|m| {
+            deps.api
+                .addr_validate(&m.addr)
+                .map(|addr| Bound::exclusive((m.points, addr)))
+        })
+        .transpose()?;
+
+    let members: StdResult<Vec<_>> = members()
+        .idx
+        .points
+        .range(deps.storage, None, start, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (
+                addr,
+                MemberInfo {
+                    points,
+                    start_height,
+                },
+            ) = item?;
+            Ok(Member {
+                addr: addr.into(),
+                points,
+                start_height,
+            })
+        })
+        .collect();
+
+    Ok(MemberListResponse { members: members? })
+}
+
+fn list_siblings<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<SiblingsResponse> {
+    let siblings = SIBLINGS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|addr| addr.map(String::from))
+        .collect::<StdResult<_>>()?;
+    Ok(SiblingsResponse { siblings })
+}
+
+fn list_unbond_controllers<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<UnbondControllersResponse> {
+    let controllers = UNBOND_CONTROLLERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|addr| addr.map(String::from))
+        .collect::<StdResult<_>>()?;
+    Ok(UnbondControllersResponse { controllers })
+}
+
+fn export_stakes<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    prev_hash: Option<Binary>,
+) -> StdResult<ExportStakesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    // Every bond creates both liquid and vesting entries, so iterating liquid stakes covers
+    // all stakers. Fully unbonded addresses are skipped.
+    let stakes = STAKE
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| {
+            let (addr, liquid) = item?;
+            let vesting = STAKE_VESTING
+                .may_load(deps.storage, &addr)?
+                .unwrap_or_default();
+            Ok(StakeEntry {
+                addr: addr.into(),
+                liquid,
+                vesting,
+            })
+        })
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .map_or(true, |e| !(e.liquid + e.vesting).is_zero())
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut hasher = Sha256::new();
+    if let Some(prev_hash) = prev_hash {
+        hasher.update(prev_hash.as_slice());
+    }
+    hasher.update(to_vec(&stakes)?);
+    let hash = Binary::from(hasher.finalize().as_slice());
+
+    Ok(ExportStakesResponse {
+        height: env.block.height,
+        stakes,
+        hash,
+    })
+}
+
+fn list_all_stakes<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListAllStakesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    let stakes = STAKE
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (addr, liquid) = item?;
+            let vesting = STAKE_VESTING
+                .may_load(deps.storage, &addr)?
+                .unwrap_or_default();
+            Ok(StakeEntry {
+                addr: addr.into(),
+                liquid,
+                vesting,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListAllStakesResponse {
+        total_bonded: TOTAL_BONDED.may_load(deps.storage)?.unwrap_or_default(),
+        total_points: TOTAL.may_load(deps.storage)?.unwrap_or_default(),
+        stakes,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut<TgradeQuery>,
+    _env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.update::<_, StdError>(deps.storage, |mut cfg| {
+        if let Some(tokens_per_point) = msg.tokens_per_point {
+            let tokens_per_point = if tokens_per_point == Uint128::zero() {
+                Uint128::new(1)
+            } else {
+                tokens_per_point
+            };
+            cfg.tokens_per_point = tokens_per_point;
+        }
+        if let Some(min_bond) = msg.min_bond {
+            let min_bond = if min_bond == Uint128::zero() {
+                Uint128::new(1)
+            } else {
+                min_bond
+            };
+            cfg.min_bond = min_bond;
+        }
+        if let Some(unbonding_period) = msg.unbonding_period {
+            cfg.unbonding_period = Duration::new(unbonding_period);
+        }
+        if let Some(auto_return_limit) = msg.auto_return_limit {
+            cfg.auto_return_limit = auto_return_limit;
+        }
+        if let Some(unbond_veto_period) = msg.unbond_veto_period {
+            cfg.unbond_veto_period =
+                (unbond_veto_period > 0).then(|| Duration::new(unbond_veto_period));
+        }
+        if let Some(max_bond_per_address) = msg.max_bond_per_address {
+            cfg.max_bond_per_address =
+                (!max_bond_per_address.is_zero()).then_some(max_bond_per_address);
+        }
+        if let Some(max_total_bond) = msg.max_total_bond {
+            cfg.max_total_bond = (!max_total_bond.is_zero()).then_some(max_total_bond);
+        }
+        if let Some(average_stake_period) = msg.average_stake_period {
+            cfg.average_stake_period =
+                (average_stake_period > 0).then(|| Duration::new(average_stake_period));
+        }
+        if let Some(unbonding_interest) = msg.unbonding_interest {
+            cfg.unbonding_interest = (!unbonding_interest.is_zero()).then_some(unbonding_interest);
+        }
+        if let Some(max_pause_duration) = msg.max_pause_duration {
+            cfg.max_pause_duration =
+                (max_pause_duration > 0).then(|| Duration::new(max_pause_duration));
+        }
+        Ok(cfg)
+    })?;
+
+    // Total bonded tokens were not tracked by older versions
+    if TOTAL_BONDED.may_load(deps.storage)?.is_none() {
+        let total_bonded = STAKE
+            .range(deps.storage, None, None, Order::Ascending)
+            .chain(STAKE_VESTING.range(deps.storage, None, None, Order::Ascending))
+            .try_fold(Uint128::zero(), |total, stake| -> StdResult<_> {
+                Ok(total + stake?.1)
+            })?;
+        TOTAL_BONDED.save(deps.storage, &total_bonded)?;
+    }
+
+    // Claims were not tagged by older versions, they are moved to the tagged claims lazily and
+    // counted once moved. Neither were tagged claims counted
+    claims().init_count(deps.storage)?;
+    // Nor summarized in buckets, they are moved there lazily
+    claims().init_buckets(deps.storage)?;
+
+    if let Some(undelegations) = msg.undelegations {
+        let msgs = process_pending_undelegations(deps.as_ref(), &undelegations)?;
+        Ok(Response::new().add_messages(msgs))
+    } else {
+        Ok(Response::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::claim::Claim;
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::{
+        from_slice, CosmosMsg, OverflowError, OverflowOperation, StdError, Storage,
+    };
+    use tg4::{member_key, TOTAL_KEY};
+    use tg_utils::{HookError, PreauthError, SlasherError};
+
+    use crate::error::ContractError;
+
+    use super::*;
+    use tg_bindings_test::mock_deps_tgrade;
+
+    const INIT_ADMIN: &str = "juan";
+    const USER1: &str = "user1";
+    const USER2: &str = "user2";
+    const USER3: &str = "user3";
+    const DENOM: &str = "stake";
+    const TOKENS_PER_POINT: Uint128 = Uint128::new(1_000);
+    const MIN_BOND: Uint128 = Uint128::new(5_000);
+    const UNBONDING_DURATION: u64 = 100;
+
+    fn default_instantiate(deps: DepsMut<TgradeQuery>) {
+        do_instantiate(deps, TOKENS_PER_POINT, MIN_BOND, UNBONDING_DURATION, 0)
+    }
+
+    fn do_instantiate(
+        deps: DepsMut<TgradeQuery>,
+        tokens_per_point: Uint128,
+        min_bond: Uint128,
+        unbonding_period: u64,
+        auto_return_limit: u64,
+    ) {
+        let msg = InstantiateMsg {
+            denom: "stake".to_owned(),
+            tokens_per_point,
+            min_bond,
+            unbonding_period,
+            admin: Some(INIT_ADMIN.into()),
+            preauths_hooks: 1,
+            preauths_slashing: 1,
+            auto_return_limit,
+            unbond_veto_period: None,
+            max_bond_per_address: None,
+            max_total_bond: None,
+            exit_fee: None,
+            lockup_tiers: vec![],
+            average_stake_period: None,
+            unbonding_interest: None,
+            denom_metadata: None,
+            max_pause_duration: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    // Helper for staking only liquid assets
+    fn bond_liquid(
+        deps: DepsMut<TgradeQuery>,
+        user1: u128,
+        user2: u128,
+        user3: u128,
+        height_delta: u64,
+    ) {
+        bond(deps, (user1, 0), (user2, 0), (user3, 0), height_delta);
+    }
+
+    // Helper for staking only illiquid assets
+    fn bond_vesting(
+        deps: DepsMut<TgradeQuery>,
+        user1: u128,
+        user2: u128,
+        user3: u128,
+        height_delta: u64,
+    ) {
+        bond(deps, (0, user1), (0, user2), (0, user3), height_delta);
+    }
+
+    // Full stake is composed of `(liquid, illiquid (vesting))` amounts
+    fn bond(
+        mut deps: DepsMut<TgradeQuery>,
+        user1_stake: (u128, u128),
+        user2_stake: (u128, u128),
+        user3_stake: (u128, u128),
+        height_delta: u64,
+    ) {
+        let mut env = mock_env();
+        env.block.height += height_delta;
+
+        for (addr, stake) in &[
+            (USER1, user1_stake),
+            (USER2, user2_stake),
+            (USER3, user3_stake),
+        ] {
+            if stake.0 != 0 || stake.1 != 0 {
+                let vesting_tokens = if stake.1 != 0 {
+                    Some(coin(stake.1, DENOM))
+                } else {
+                    None
+                };
+                let msg = ExecuteMsg::Bond { vesting_tokens };
+                let info = mock_info(addr, &coins(stake.0, DENOM));
+                execute(deps.branch(), env.clone(), info, msg).unwrap();
+            }
+        }
+    }
+
+    fn unbond(
+        mut deps: DepsMut<TgradeQuery>,
+        user1: u128,
+        user2: u128,
+        user3: u128,
+        height_delta: u64,
+        time_delta: u64,
+    ) {
+        let mut env = mock_env();
+        env.block.height += height_delta;
+        env.block.time = env.block.time.plus_seconds(time_delta);
+
+        for (addr, stake) in &[(USER1, user1), (USER2, user2), (USER3, user3)] {
+            if *stake != 0 {
+                let msg = ExecuteMsg::Unbond {
+                    tokens: coin(*stake, DENOM),
+                    tag: None,
+                };
+                let info = mock_info(addr, &[]);
+                execute(deps.branch(), env.clone(), info, msg).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn proper_instantiation() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        // it worked, let's query the state
+        let res = ADMIN.query_admin(deps.as_ref()).unwrap();
+        assert_eq!(Some(INIT_ADMIN.into()), res.admin);
+
+        let res = query_total_points(deps.as_ref(), None).unwrap();
+        assert_eq!(0, res.points);
+
+        let raw = query(deps.as_ref(), mock_env(), QueryMsg::Configuration {}).unwrap();
+        let res: Config = from_slice(&raw).unwrap();
+        assert_eq!(
+            res,
+            Config {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: Duration::new(UNBONDING_DURATION),
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            }
+        );
+
+        // query the admin's staked amount (just to confirm the query works)
+        let res = query_staked(deps.as_ref(), INIT_ADMIN.into()).unwrap();
+        assert_eq!(coin(0, "stake"), res.liquid);
+        assert_eq!(coin(0, "stake"), res.vesting);
+    }
+
+    #[test]
+    fn unbonding_period_query_works() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        let raw = query(deps.as_ref(), mock_env(), QueryMsg::UnbondingPeriod {}).unwrap();
+        let res: UnbondingPeriodResponse = from_slice(&raw).unwrap();
+        assert_eq!(res.unbonding_period, Duration::new(UNBONDING_DURATION));
+    }
+
+    fn get_member(deps: Deps<TgradeQuery>, addr: String, at_height: Option<u64>) -> Option<u64> {
+        let raw = query(deps, mock_env(), QueryMsg::Member { addr, at_height }).unwrap();
+        let res: MemberResponse = from_slice(&raw).unwrap();
+        res.points
+    }
+
+    // this tests the member queries
+    #[track_caller]
+    fn assert_users(
+        deps: Deps<TgradeQuery>,
+        user1_points: Option<u64>,
+        user2_points: Option<u64>,
+        user3_points: Option<u64>,
+        height: Option<u64>,
+    ) {
+        let member1 = get_member(deps, USER1.into(), height);
+        assert_eq!(member1, user1_points);
+
+        let member2 = get_member(deps, USER2.into(), height);
+        assert_eq!(member2, user2_points);
+
+        let member3 = get_member(deps, USER3.into(), height);
+        assert_eq!(member3, user3_points);
+
+        // this is only valid if we are not doing a historical query
+        if height.is_none() {
+            // compute expected metrics
+            let points = vec![user1_points, user2_points, user3_points];
+            let sum: u64 = points.iter().map(|x| x.unwrap_or_default()).sum();
+            let count = points.iter().filter(|x| x.is_some()).count();
+
+            // TODO: more detailed compare?
+            let msg = QueryMsg::ListMembers {
+                start_after: None,
+                limit: None,
+            };
+            let raw = query(deps, mock_env(), msg).unwrap();
+            let members: MemberListResponse = from_slice(&raw).unwrap();
+            assert_eq!(count, members.members.len());
+
+            let raw = query(deps, mock_env(), QueryMsg::TotalPoints { at_height: None }).unwrap();
+            let total: TotalPointsResponse = from_slice(&raw).unwrap();
+            assert_eq!(sum, total.points); // 17 - 11 + 15 = 21
+        }
+    }
+
+    // this tests the member queries of liquid amounts
+    #[track_caller]
+    fn assert_stake_liquid(deps: Deps<TgradeQuery>, user1: u128, user2: u128, user3: u128) {
+        let stake1 = query_staked(deps, USER1.into()).unwrap();
+        assert_eq!(stake1.liquid, coin(user1, DENOM));
+
+        let stake2 = query_staked(deps, USER2.into()).unwrap();
+        assert_eq!(stake2.liquid, coin(user2, DENOM));
+
+        let stake3 = query_staked(deps, USER3.into()).unwrap();
+        assert_eq!(stake3.liquid, coin(user3, DENOM));
+    }
+
+    // this tests the member queries of illiquid amounts
+    #[track_caller]
+    fn assert_stake_vesting(deps: Deps<TgradeQuery>, user1: u128, user2: u128, user3: u128) {
+        let stake1 = query_staked(deps, USER1.into()).unwrap();
+        assert_eq!(stake1.vesting, coin(user1, DENOM));
+
+        let stake2 = query_staked(deps, USER2.into()).unwrap();
+        assert_eq!(stake2.vesting, coin(user2, DENOM));
+
+        let stake3 = query_staked(deps, USER3.into()).unwrap();
+        assert_eq!(stake3.vesting, coin(user3, DENOM));
+    }
+
+    #[test]
+    fn bond_stake_liquid_adds_membership() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+        let height = mock_env().block.height;
+
+        // Assert original points
+        assert_users(deps.as_ref(), None, None, None, None);
+
+        // ensure it rounds down, and respects cut-off
+        bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+
+        // Assert updated points
+        assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+        assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+
+        // add some more, ensure the sum is properly respected (7.5 + 7.6 = 15 not 14)
+        bond_liquid(deps.as_mut(), 0, 7_600, 1_200, 2);
+
+        // Assert updated points
+        assert_stake_liquid(deps.as_ref(), 12_000, 15_100, 5_200);
+        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), None);
+
+        // check historical queries all work
+        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
+        assert_users(deps.as_ref(), Some(12), Some(7), None, Some(height + 2)); // after first stake
+        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), Some(height + 3));
+        // after second stake
+    }
+
+    #[test]
+    fn bond_stake_vesting_adds_membership() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+        let height = mock_env().block.height;
+
+        // Assert original points
+        assert_users(deps.as_ref(), None, None, None, None);
+
+        // ensure it rounds down, and respects cut-off
+        bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+
+        // Assert updated points
+        assert_stake_vesting(deps.as_ref(), 12_000, 7_500, 4_000);
+        assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+
+        // add some more, ensure the sum is properly respected (7.5 + 7.6 = 15 not 14)
+        bond_vesting(deps.as_mut(), 0, 7_600, 1_200, 2);
+
+        // Assert updated points
+        assert_stake_vesting(deps.as_ref(), 12_000, 15_100, 5_200);
+        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), None);
+
+        // check historical queries all work
+        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
+        assert_users(deps.as_ref(), Some(12), Some(7), None, Some(height + 2)); // after first stake
+        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), Some(height + 3));
+        // after second stake
+    }
+
+    #[test]
+    fn bond_mixed_stake_adds_membership() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+        let height = mock_env().block.height;
+
+        // Assert original points
+        assert_users(deps.as_ref(), None, None, None, None);
+
+        // ensure it rounds down, and respects cut-off
+        bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+
+        // Assert updated points
+        assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+        assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+
+        // add some more, ensure the sum is properly respected (7.5 + 7.6 = 15 not 14)
+        bond_vesting(deps.as_mut(), 0, 7_600, 1_200, 2);
+
+        // Assert updated points
+        assert_stake_vesting(deps.as_ref(), 0, 7_600, 1_200);
+        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), None);
+
+        // check historical queries all work
+        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
+        assert_users(deps.as_ref(), Some(12), Some(7), None, Some(height + 2)); // after first stake
+        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), Some(height + 3));
+        // after second stake
+    }
+
+    #[test]
+    fn export_stakes_paginates_with_rolling_hash() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        bond(deps.as_mut(), (12_000, 0), (7_400, 100), (0, 4_000), 1);
+        // fully unbonded stakers are not exported
+        unbond(deps.as_mut(), 12_000, 0, 0, 2, 0);
+
+        let env = mock_env();
+        let full = export_stakes(deps.as_ref(), env.clone(), None, None, None).unwrap();
+        assert_eq!(full.height, env.block.height);
+        assert_eq!(
+            full.stakes,
+            vec![
+                StakeEntry {
+                    addr: USER2.to_owned(),
+                    liquid: Uint128::new(7_400),
+                    vesting: Uint128::new(100),
+                },
+                StakeEntry {
+                    addr: USER3.to_owned(),
+                    liquid: Uint128::zero(),
+                    vesting: Uint128::new(4_000),
+                },
+            ]
+        );
+
+        let page1 = export_stakes(deps.as_ref(), env.clone(), None, Some(1), None).unwrap();
+        assert_eq!(page1.stakes, full.stakes[..1]);
+        assert_eq!(
+            page1.hash.as_slice(),
+            Sha256::digest(&to_vec(&full.stakes[..1]).unwrap()).as_slice()
+        );
+
+        let page2 = export_stakes(
+            deps.as_ref(),
+            env.clone(),
+            Some(USER2.to_owned()),
+            Some(1),
+            Some(page1.hash.clone()),
+        )
+        .unwrap();
+        assert_eq!(page2.stakes, full.stakes[1..]);
+        let mut hasher = Sha256::new();
+        hasher.update(page1.hash.as_slice());
+        hasher.update(to_vec(&full.stakes[1..]).unwrap());
+        assert_eq!(page2.hash.as_slice(), hasher.finalize().as_slice());
+
+        // the export is deterministic, and any stake change is reflected in the hash
+        let again = export_stakes(deps.as_ref(), env.clone(), None, None, None).unwrap();
+        assert_eq!(again, full);
+        bond_liquid(deps.as_mut(), 0, 0, 1, 3);
+        let changed = export_stakes(deps.as_ref(), env, None, None, None).unwrap();
+        assert_ne!(changed.hash, full.hash);
+    }
+
+    #[test]
+    fn list_all_stakes_with_totals() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        bond(deps.as_mut(), (12_000, 0), (7_400, 100), (0, 4_000), 1);
+        // fully unbonded stakers are still listed
+        unbond(deps.as_mut(), 12_000, 0, 0, 2, 0);
+
+        let all = list_all_stakes(deps.as_ref(), None, None).unwrap();
+        assert_eq!(all.total_bonded, Uint128::new(11_500));
+        assert_eq!(
+            all.total_points,
+            query_total_points(deps.as_ref(), None).unwrap().points
+        );
+        assert_eq!(
+            all.stakes,
+            [
+                StakeEntry {
+                    addr: USER1.to_owned(),
+                    liquid: Uint128::zero(),
+                    vesting: Uint128::zero(),
+                },
+                StakeEntry {
+                    addr: USER2.to_owned(),
+                    liquid: Uint128::new(7_400),
+                    vesting: Uint128::new(100),
+                },
+                StakeEntry {
+                    addr: USER3.to_owned(),
+                    liquid: Uint128::zero(),
+                    vesting: Uint128::new(4_000),
+                },
+            ]
+        );
+
+        let page = list_all_stakes(deps.as_ref(), Some(USER1.to_owned()), Some(1)).unwrap();
+        assert_eq!(page.stakes, all.stakes[1..2]);
+        assert_eq!(page.total_bonded, all.total_bonded);
+    }
+
+    #[test]
+    fn try_member_queries() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        bond(deps.as_mut(), (12_000, 0), (7_400, 100), (0, 4_000), 1);
+
+        let member1 = query_member(deps.as_ref(), USER1.into(), None).unwrap();
+        assert_eq!(member1.points, Some(12));
+
+        let member2 = query_member(deps.as_ref(), USER2.into(), None).unwrap();
+        assert_eq!(member2.points, Some(7));
+
+        let member3 = query_member(deps.as_ref(), USER3.into(), None).unwrap();
+        assert_eq!(member3.points, None);
+
+        let members = list_members(deps.as_ref(), None, None).unwrap().members;
+        assert_eq!(members.len(), 2);
+        // Assert the set is proper
+        assert_eq!(
+            members,
+            vec![
+                Member {
+                    addr: USER1.into(),
+                    points: 12,
+                    start_height: None,
+                },
+                Member {
+                    addr: USER2.into(),
+                    points: 7,
+                    start_height: None,
+                },
+            ]
+        );
+
+        // Test pagination / limits
+        let members = list_members(deps.as_ref(), None, Some(1)).unwrap().members;
+        assert_eq!(members.len(), 1);
+        // Assert the set is proper
+        assert_eq!(
+            members,
+            vec![Member {
+                addr: USER1.into(),
+                points: 12,
+                start_height: None,
+            },]
+        );
+
+        // Next page
+        let start_after = Some(members[0].addr.clone());
+        let members = list_members(deps.as_ref(), start_after, Some(1))
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 1);
+        // Assert the set is proper
+        assert_eq!(
+            members,
+            vec![Member {
+                addr: USER2.into(),
+                points: 7,
+                start_height: None,
+            },]
+        );
+
+        // Assert there's no more
+        let start_after = Some(members[0].addr.clone());
+        let members = list_members(deps.as_ref(), start_after, Some(1))
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 0);
+    }
+
+    #[test]
+    fn try_list_members_by_points() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        bond(deps.as_mut(), (10_000, 1_000), (6_500, 0), (0, 5_000), 1);
+
+        let members = list_members_by_points(deps.as_ref(), None, None)
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 3);
+        // Assert the set is sorted by (descending) points
+        assert_eq!(
+            members,
+            vec![
+                Member {
+                    addr: USER1.into(),
+                    points: 11,
+                    start_height: None,
+                },
+                Member {
+                    addr: USER2.into(),
+                    points: 6,
+                    start_height: None,
+                },
+                Member {
+                    addr: USER3.into(),
+                    points: 5,
+                    start_height: None,
+                },
+            ]
+        );
+
+        // Test pagination / limits
+        let members = list_members_by_points(deps.as_ref(), None, Some(1))
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 1);
+        // Assert the set is proper
+        assert_eq!(
+            members,
+            vec![Member {
+                addr: USER1.into(),
+                points: 11,
+                start_height: None,
+            },]
+        );
+
+        // Next page
+        let last = members.last().unwrap();
+        let start_after = Some(last.clone());
+        let members = list_members_by_points(deps.as_ref(), start_after, None)
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 2);
+        // Assert the set is proper
+        assert_eq!(
+            members,
+            vec![
+                Member {
+                    addr: USER2.into(),
+                    points: 6,
+                    start_height: None,
+                },
+                Member {
+                    addr: USER3.into(),
+                    points: 5,
+                    start_height: None,
+                },
+            ]
+        );
+
+        // Assert there's no more
+        let last = members.last().unwrap();
+        let start_after = Some(last.clone());
+        let members = list_members_by_points(deps.as_ref(), start_after, Some(1))
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 0);
+    }
+
+    #[test]
+    fn unbond_validations() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        // Zero amount unbonds are rejected
+        let msg = ExecuteMsg::Unbond {
+            tokens: coin(0, DENOM),
+            tag: None,
+        };
+        let env = mock_env();
+        let info = mock_info(USER1, &[]);
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(ContractError::ZeroAmount {}, err);
+
+        // Invalid denom unbonds are rejected
+        let msg = ExecuteMsg::Unbond {
+            tokens: coin(1234, "INV"),
+            tag: None,
+        };
+        let env = mock_env();
+        let info = mock_info(USER1, &[]);
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(ContractError::InvalidDenom {}, err);
+    }
+
+    #[test]
+    fn unbond_stake_update_membership() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+        let height = mock_env().block.height;
+
+        // ensure it rounds down, and respects cut-off
+        bond(deps.as_mut(), (0, 12_000), (500, 7_000), (3_000, 3_000), 1);
+        assert_users(deps.as_ref(), Some(12), Some(7), Some(6), None);
+
+        unbond(deps.as_mut(), 4_500, 2_600, 1_000, 2, 0);
+
+        // Assert updated points
+        assert_stake_liquid(deps.as_ref(), 0, 0, 2000);
+        assert_stake_vesting(deps.as_ref(), 7_500, 4_900, 3000);
+        assert_users(deps.as_ref(), Some(7), None, Some(5), None);
+
+        // Adding a little more returns points
+        bond(deps.as_mut(), (500, 100), (100, 0), (0, 2_222), 3);
+
+        // Assert updated points
+        assert_stake_liquid(deps.as_ref(), 500, 100, 2000);
+        assert_stake_vesting(deps.as_ref(), 7_600, 4_900, 5_222);
+        assert_users(deps.as_ref(), Some(8), Some(5), Some(7), None);
+
+        // check historical queries all work
+        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
+        assert_users(deps.as_ref(), Some(12), Some(7), Some(6), Some(height + 2)); // after first bond
+        assert_users(deps.as_ref(), Some(7), None, Some(5), Some(height + 3)); // after first unbond
+        assert_users(deps.as_ref(), Some(8), Some(5), Some(7), Some(height + 4)); // after second bond
+
+        // error if try to unbond more than stake (USER2 has 5000 staked)
+        let msg = ExecuteMsg::Unbond {
+            tokens: coin(5100, DENOM),
+            tag: None,
+        };
+        let mut env = mock_env();
+        env.block.height += 5;
+        let info = mock_info(USER2, &[]);
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::overflow(OverflowError::new(
+                OverflowOperation::Sub,
+                4900,
+                5000,
+            )))
+        );
+    }
+
+    #[test]
+    fn raw_queries_work() {
+        // add will over-write and remove have no effect
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+        // Set values as (11, 6, None)
+        bond(deps.as_mut(), (1_000, 10_000), (6_000, 0), (0, 0), 1);
+
+        // get total from raw key
+        let total_raw = deps.storage.get(TOTAL_KEY.as_bytes()).unwrap();
+        let total: u64 = from_slice(&total_raw).unwrap();
+        assert_eq!(17, total);
+
+        // get member votes from raw key
+        let member2_raw = deps.storage.get(&member_key(USER2)).unwrap();
+        let member2: MemberInfo = from_slice(&member2_raw).unwrap();
+        assert_eq!(6, member2.points);
+
+        // and execute misses
+        let member3_raw = deps.storage.get(&member_key(USER3));
+        assert_eq!(None, member3_raw);
+    }
+
+    #[track_caller]
+    fn get_claims(
+        deps: Deps<TgradeQuery>,
+        addr: Addr,
+        limit: Option<u32>,
+        start_after: Option<Expiration>,
+    ) -> Vec<Claim> {
+        claims()
+            .query_claims(deps, addr, limit, start_after, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn unbond_claim_workflow() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        // create some data
+        bond(deps.as_mut(), (4_000, 7_500), (7_500, 0), (3_000, 1_000), 1);
+        let height_delta = 2;
+        // 4_000 (liquid) and 500 (vesting) will be claimed for USER1
+        unbond(deps.as_mut(), 4_500, 2_600, 0, height_delta, 0);
+        let mut env = mock_env();
+        env.block.height += height_delta;
+
+        // check the claims for each user
+        let expires = Duration::new(UNBONDING_DURATION).after(&env.block);
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
+            vec![Claim::new(
+                Addr::unchecked(USER1),
+                4_000,
+                500,
+                expires,
+                env.block.height,
+            )]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
+            vec![Claim::new(
+                Addr::unchecked(USER2),
+                2_600,
+                0,
+                expires,
+                env.block.height,
+            )]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER3), None, None),
+            vec![]
+        );
+
+        // do another unbond later on
+        let mut env2 = mock_env();
+        let height_delta = 22;
+        env2.block.height += height_delta;
+        let time_delta = 50;
+        unbond(deps.as_mut(), 0, 1_345, 1_500, height_delta, time_delta);
+
+        // with updated claims
+        let expires2 = Duration::new(UNBONDING_DURATION + time_delta).after(&env2.block);
+        assert_ne!(expires, expires2);
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
+            vec![Claim::new(
+                Addr::unchecked(USER1),
+                4_000,
+                500,
+                expires,
+                env.block.height,
+            )]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
+            vec![
+                Claim::new(Addr::unchecked(USER2), 2_600, 0, expires, env.block.height),
+                Claim::new(
+                    Addr::unchecked(USER2),
+                    1_345,
+                    0,
+                    expires2,
+                    env2.block.height,
+                ),
+            ]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER3), None, None),
+            vec![Claim::new(
+                Addr::unchecked(USER3),
+                1_500,
+                0,
+                expires2,
+                env2.block.height,
+            )]
+        );
+
+        // nothing can be withdrawn yet
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER1, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingToClaim {});
+
+        // now mature first section, withdraw that
+        let mut env3 = mock_env();
+        env3.block.time = env3.block.time.plus_seconds(UNBONDING_DURATION);
+        // first one can now release
+        let res = execute(
+            deps.as_mut(),
+            env3.clone(),
+            mock_info(USER1, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: USER1.into(),
+                    amount: coins(4_000, DENOM),
+                }),
+                SubMsg::new(TgradeMsg::Undelegate {
+                    funds: coin(500, DENOM),
+                    recipient: USER1.into(),
+                })
+            ]
+        );
+
+        // second releases partially
+        let res = execute(
+            deps.as_mut(),
+            env3.clone(),
+            mock_info(USER2, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: USER2.into(),
+                amount: coins(2_600, DENOM),
+            })]
+        );
+
+        // but the third one cannot release
+        let err = execute(
+            deps.as_mut(),
+            env3,
+            mock_info(USER3, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingToClaim {});
+
+        // claims updated properly
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
+            vec![]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
+            vec![Claim::new(
+                Addr::unchecked(USER2),
+                1_345,
+                0,
+                expires2,
+                env2.block.height,
+            )]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER3), None, None),
+            vec![Claim::new(
+                Addr::unchecked(USER3),
+                1_500,
+                0,
+                expires2,
+                env2.block.height,
+            )]
+        );
+
+        // add another few claims for 2
+        unbond(deps.as_mut(), 0, 600, 0, 30, 0);
+        unbond(deps.as_mut(), 0, 1_005, 0, 50, 0);
+
+        // ensure second can claim all tokens at once
+        let mut env4 = mock_env();
+        env4.block.time = env4
+            .block
+            .time
+            .plus_seconds(UNBONDING_DURATION + time_delta);
+        let res = execute(
+            deps.as_mut(),
+            env4,
+            mock_info(USER2, &[]),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: USER2.into(),
+                // 1_345 + 600 + 1_005
+                amount: coins(2_950, DENOM),
+            })]
+        );
+        assert_eq!(
+            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn claim_with_permit() {
+        use k256::ecdsa::signature::hazmat::PrehashSigner;
+        use k256::ecdsa::{Signature, SigningKey};
+
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+        bond_liquid(deps.as_mut(), 12_000, 0, 0, 1);
+        unbond(deps.as_mut(), 4_000, 0, 0, 2, 0);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+        let relayer = mock_info("relayer", &[]);
+
+        let key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+        let permit = |nonce| ClaimPermit {
+            owner: USER1.to_owned(),
+            nonce,
+            deadline: env.block.time.plus_seconds(60),
+        };
+        let sign = |permit: &ClaimPermit| {
+            let hash = ClaimPermitSignDoc {
+                chain_id: env.block.chain_id.clone(),
+                contract: env.contract.address.to_string(),
+                permit: permit.clone(),
+            }
+            .hash()
+            .unwrap();
+            let signature: Signature = key.sign_prehash(&hash).unwrap();
+            Binary::from(signature.as_ref())
+        };
+        let claim = |permit: ClaimPermit, signature: Binary| ExecuteMsg::ClaimWithPermit {
+            permit,
+            signature,
+        };
+
+        // no key registered yet
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            claim(permit(0), sign(&permit(0))),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoPermitKey(USER1.to_owned()));
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            ExecuteMsg::SetPermitKey {
+                pubkey: Some(Binary::from(vec![1; 32])),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermitKey {});
+
+        let pubkey = Binary::from(key.verifying_key().to_bytes().as_slice());
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            ExecuteMsg::SetPermitKey {
+                pubkey: Some(pubkey.clone()),
+            },
+        )
+        .unwrap();
+
+        // nonce has to match
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            claim(permit(1), sign(&permit(1))),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPermitNonce {
+                expected: 0,
+                got: 1
+            }
+        );
+
+        // permit cannot be used after deadline
+        let mut late_env = env.clone();
+        late_env.block.time = late_env.block.time.plus_seconds(61);
+        let err = execute(
+            deps.as_mut(),
+            late_env,
+            relayer.clone(),
+            claim(permit(0), sign(&permit(0))),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PermitExpired {});
+
+        // signature has to cover the whole permit
+        let mut extended = permit(0);
+        extended.deadline = extended.deadline.plus_seconds(3600);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            claim(extended, sign(&permit(0))),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermitSignature {});
+
+        // relayer claims, but tokens go to the owner
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            claim(permit(0), sign(&permit(0))),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: USER1.into(),
+                amount: coins(4_000, DENOM),
+            })]
+        );
+
+        // permit cannot be replayed
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer,
+            claim(permit(0), sign(&permit(0))),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPermitNonce {
+                expected: 1,
+                got: 0
+            }
+        );
+
+        let info = query_permit_info(deps.as_ref(), USER1.to_owned()).unwrap();
+        assert_eq!(
+            info,
+            PermitInfoResponse {
+                pubkey: Some(pubkey),
+                nonce: 1
+            }
+        );
+    }
+
+    #[test]
+    fn add_remove_hooks() {
+        // add will over-write and remove have no effect
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
+        assert!(hooks.is_empty());
+
+        let contract1 = String::from("hook1");
+        let contract2 = String::from("hook2");
+
+        let add_msg = ExecuteMsg::AddHook {
+            addr: contract1.clone(),
+        };
+
+        // anyone can add the first one, until preauth is consume
+        assert_eq!(1, PREAUTH_HOOKS.get_auth(&deps.storage).unwrap());
+        let user_info = mock_info(USER1, &[]);
+        let _ = execute(deps.as_mut(), mock_env(), user_info, add_msg.clone()).unwrap();
+        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
+        assert_eq!(hooks, vec![contract1.clone()]);
+
+        // non-admin cannot add hook without preauth
+        assert_eq!(0, PREAUTH_HOOKS.get_auth(&deps.storage).unwrap());
+        let user_info = mock_info(USER1, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            user_info.clone(),
+            add_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, PreauthError::NoPreauth {}.into());
+
+        // cannot remove a non-registered contract
+        let admin_info = mock_info(INIT_ADMIN, &[]);
+        let remove_msg = ExecuteMsg::RemoveHook {
+            addr: contract2.clone(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), remove_msg).unwrap_err();
+        assert_eq!(err, HookError::HookNotRegistered {}.into());
+
+        // admin can second contract, and it appears in the query
+        let add_msg2 = ExecuteMsg::AddHook {
+            addr: contract2.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg2).unwrap();
+        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
+        assert_eq!(hooks, vec![contract1.clone(), contract2.clone()]);
+
+        // cannot re-add an existing contract
+        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg).unwrap_err();
+        assert_eq!(err, HookError::HookAlreadyRegistered {}.into());
+
+        // non-admin cannot remove
+        let remove_msg = ExecuteMsg::RemoveHook { addr: contract1 };
+        let err = execute(deps.as_mut(), mock_env(), user_info, remove_msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized(
+                "Hook address is not same as sender's and sender is not an admin".to_owned()
+            )
+        );
+
+        // remove the original
+        execute(deps.as_mut(), mock_env(), admin_info, remove_msg).unwrap();
+        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
+        assert_eq!(hooks, vec![contract2.clone()]);
+
+        // contract can self-remove
+        let contract_info = mock_info(&contract2, &[]);
+        let remove_msg2 = ExecuteMsg::RemoveHook { addr: contract2 };
+        execute(deps.as_mut(), mock_env(), contract_info, remove_msg2).unwrap();
+        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
+        assert_eq!(hooks, Vec::<String>::new());
+    }
+
+    mod slash {
+        use super::*;
+
+        fn query_is_slasher(deps: Deps<TgradeQuery>, env: Env, addr: String) -> StdResult<bool> {
+            let msg = QueryMsg::IsSlasher { addr };
+            let raw = query(deps, env, msg)?;
+            let is_slasher: bool = from_slice(&raw)?;
+            Ok(is_slasher)
+        }
+
+        fn query_list_slashers(deps: Deps<TgradeQuery>, env: Env) -> StdResult<Vec<String>> {
+            let msg = QueryMsg::ListSlashers {};
+            let raw = query(deps, env, msg)?;
+            let slashers: Vec<String> = from_slice(&raw)?;
+            Ok(slashers)
+        }
+
+        fn add_slasher(deps: DepsMut<TgradeQuery>) -> String {
+            let slasher = String::from("slasher");
+            let add_msg = ExecuteMsg::AddSlasher {
+                addr: slasher.clone(),
+                max_portion: None,
+            };
+            let user_info = mock_info(USER1, &[]);
+            execute(deps, mock_env(), user_info, add_msg).unwrap();
+
+            slasher
+        }
+
+        fn remove_slasher(deps: DepsMut<TgradeQuery>, slasher: &str) {
+            let add_msg = ExecuteMsg::RemoveSlasher {
+                addr: slasher.to_string(),
+            };
+            let user_info = mock_info(INIT_ADMIN, &[]);
+            execute(deps, mock_env(), user_info, add_msg).unwrap();
+        }
+
+        fn slash(
+            deps: DepsMut<TgradeQuery>,
+            slasher: &str,
+            addr: &str,
+            portion: Decimal,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Slash {
+                addr: addr.to_string(),
+                portion,
+            };
+            let slasher_info = mock_info(slasher, &[]);
+
+            execute(deps, mock_env(), slasher_info, msg)
+        }
+
+        fn assert_burned(res: Response, expected_liquid: &[Coin], expected_vesting: &[Coin]) {
+            // Args checks for robustness
+            assert!(expected_liquid.len() <= 1);
+            assert!(expected_vesting.len() <= 1);
+
+            // Find all instances of BankMsg::Burn in the response and extract the burned amounts
+            let burned_amounts: Vec<_> = res
+                .messages
+                .iter()
+                .filter_map(|sub_msg| match &sub_msg.msg {
+                    CosmosMsg::Bank(BankMsg::Burn { amount }) => Some(amount),
+                    _ => None,
+                })
+                .collect();
+
+            assert!(
+                burned_amounts.len() == 1 || burned_amounts.len() == 2,
+                "Expected exactly 1 or 2 Bank::Burn message, got {}",
+                burned_amounts.len()
+            );
+
+            let mut index = 0;
+            if !expected_liquid.is_empty() {
+                assert_eq!(
+                    burned_amounts[index], &expected_liquid,
+                    "Expected to burn {} liquid, burned {}",
+                    expected_liquid[0], burned_amounts[index][0]
+                );
+                index += 1;
+            }
+            if !expected_vesting.is_empty() {
+                assert_eq!(
+                    burned_amounts[index], &expected_vesting,
+                    "Expected to burn {} vesting, burned {}",
+                    expected_liquid[0], burned_amounts[index][0]
+                );
+            }
+        }
+
+        #[test]
+        fn add_remove_slashers() {
+            let mut deps = mock_deps_tgrade();
+            let env = mock_env();
+            default_instantiate(deps.as_mut());
+
+            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
+            assert!(slashers.is_empty());
+
+            let contract1 = String::from("slasher1");
+            let contract2 = String::from("slasher2");
+
+            let add_msg = ExecuteMsg::AddSlasher {
+                addr: contract1.clone(),
+                max_portion: None,
+            };
+
+            // anyone can add the first one, until preauth is consumed
+            assert_eq!(1, PREAUTH_SLASHING.get_auth(&deps.storage).unwrap());
+            let user_info = mock_info(USER1, &[]);
+            let _ = execute(deps.as_mut(), mock_env(), user_info, add_msg.clone()).unwrap();
+            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
+            assert_eq!(slashers, vec![contract1.clone()]);
+
+            // non-admin cannot add slasher without preauth
+            assert_eq!(0, PREAUTH_SLASHING.get_auth(&deps.storage).unwrap());
+            let user_info = mock_info(USER1, &[]);
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                user_info.clone(),
+                add_msg.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(err, PreauthError::NoPreauth {}.into());
+
+            // cannot remove a non-registered slasher
+            let admin_info = mock_info(INIT_ADMIN, &[]);
+            let remove_msg = ExecuteMsg::RemoveSlasher {
+                addr: contract2.clone(),
+            };
+            let err =
+                execute(deps.as_mut(), mock_env(), admin_info.clone(), remove_msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Slasher(SlasherError::SlasherNotRegistered(contract2.clone()))
+            );
+
+            // admin can add a second slasher, and it appears in the query
+            let add_msg2 = ExecuteMsg::AddSlasher {
+                addr: contract2.clone(),
+                max_portion: None,
+            };
+            execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg2).unwrap();
+            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
+            assert_eq!(slashers, vec![contract1.clone(), contract2.clone()]);
+
+            // cannot re-add an existing contract
+            let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Slasher(SlasherError::SlasherAlreadyRegistered(contract1.clone()))
+            );
+
+            // non-admin cannot remove
+            let remove_msg = ExecuteMsg::RemoveSlasher { addr: contract1 };
+            let err =
+                execute(deps.as_mut(), mock_env(), user_info, remove_msg.clone()).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized(
+                    "Only slasher might remove himself and sender is not an admin".to_owned()
+                )
+            );
+
+            // remove the original
+            execute(deps.as_mut(), mock_env(), admin_info, remove_msg).unwrap();
+            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
+            assert_eq!(slashers, vec![contract2.clone()]);
+
+            // contract can self-remove
+            let contract_info = mock_info(&contract2, &[]);
+            let remove_msg2 = ExecuteMsg::RemoveSlasher { addr: contract2 };
+            execute(deps.as_mut(), mock_env(), contract_info, remove_msg2).unwrap();
+            let slashers = query_list_slashers(deps.as_ref(), env).unwrap();
+            assert_eq!(slashers, Vec::<String>::new());
+        }
+
+        #[test]
+        fn slashing_nonexisting_member() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+
+            // confirm address doesn't return true on slasher query
+            assert!(!query_is_slasher(deps.as_ref(), mock_env(), "slasher".to_owned()).unwrap());
+
+            let slasher = add_slasher(deps.as_mut());
+            assert!(query_is_slasher(deps.as_ref(), mock_env(), slasher.clone()).unwrap());
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            bond_vesting(deps.as_mut(), 1_200, 750, 8_000, 2);
+            assert_stake_vesting(deps.as_ref(), 1_200, 750, 8_000);
+
+            // Trying to slash nonexisting user will result in no-op
+            let res = slash(deps.as_mut(), &slasher, "nonexisting", Decimal::percent(20)).unwrap();
+            assert_eq!(res, Response::new());
+        }
+
+        #[test]
+        fn slashing_bonded_liquid_tokens_works() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let cfg = CONFIG.load(&deps.storage).unwrap();
+            let slasher = add_slasher(deps.as_mut());
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            // The slasher we added can slash
+            let res1 = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
+            let res2 = slash(deps.as_mut(), &slasher, USER3, Decimal::percent(50)).unwrap();
+            assert_stake_liquid(deps.as_ref(), 9_600, 7_500, 2_000);
+
+            // Tokens are burned
+            assert_burned(res1, &coins(2_400, &cfg.denom), &[]);
+            assert_burned(res2, &coins(2_000, &cfg.denom), &[]);
+        }
+
+        #[test]
+        fn slashing_is_limited_per_slasher() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::AddSlasher {
+                addr: "arbiter".to_owned(),
+                max_portion: Some(Decimal::percent(20)),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+
+            let msg = QueryMsg::SlasherLimit {
+                addr: "arbiter".to_owned(),
+            };
+            let limit: Option<Decimal> =
+                from_slice(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            assert_eq!(limit, Some(Decimal::percent(20)));
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+
+            let err = slash(deps.as_mut(), "arbiter", USER1, Decimal::percent(50)).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Slasher(SlasherError::PortionAboveLimit {
+                    portion: Decimal::percent(50),
+                    max: Decimal::percent(20),
+                })
+            );
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            slash(deps.as_mut(), "arbiter", USER1, Decimal::percent(20)).unwrap();
+            assert_stake_liquid(deps.as_ref(), 9_600, 7_500, 4_000);
+        }
+
+        #[test]
+        fn slashing_bonded_vesting_tokens_works() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let cfg = CONFIG.load(&deps.storage).unwrap();
+            let slasher = add_slasher(deps.as_mut());
+
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            assert_stake_vesting(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            // The slasher we added can slash
+            let res1 = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
+            let res2 = slash(deps.as_mut(), &slasher, USER3, Decimal::percent(50)).unwrap();
+            assert_stake_vesting(deps.as_ref(), 9_600, 7_500, 2_000);
+
+            // Tokens are burned
+            assert_burned(res1, &[], &coins(2_400, &cfg.denom));
+            assert_burned(res2, &[], &coins(2_000, &cfg.denom));
+        }
+
+        #[test]
+        fn slashing_bonded_mixed_tokens_works() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let cfg = CONFIG.load(&deps.storage).unwrap();
+            let slasher = add_slasher(deps.as_mut());
+
+            bond_liquid(deps.as_mut(), 12_000, 1_500, 0, 1);
+            assert_stake_liquid(deps.as_ref(), 12_000, 1_500, 0);
+            bond_vesting(deps.as_mut(), 0, 6_000, 4_000, 1);
+            assert_stake_vesting(deps.as_ref(), 0, 6_000, 4_000);
+
+            // The slasher we added can slash
+            let res1 = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
+            let res2 = slash(deps.as_mut(), &slasher, USER3, Decimal::percent(50)).unwrap();
+            let res3 = slash(deps.as_mut(), &slasher, USER2, Decimal::percent(10)).unwrap();
+            assert_stake_liquid(deps.as_ref(), 9_600, 1_350, 0);
+            assert_stake_vesting(deps.as_ref(), 0, 5_400, 2_000);
+
+            // Slashed amount is reported in a single event
+            assert_eq!(
+                res3.events,
+                [events::slash(
+                    USER2,
+                    Decimal::percent(10),
+                    Uint128::new(750)
+                )]
+            );
+
+            // Tokens are burned
+            assert_burned(res1, &coins(2_400, &cfg.denom), &[]);
+            assert_burned(res2, &[], &coins(2_000, &cfg.denom));
+            assert_burned(res3, &coins(150, &cfg.denom), &coins(600, &cfg.denom));
+        }
+
+        #[test]
+        fn slashing_stake_update_membership() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let slasher = add_slasher(deps.as_mut());
+
+            // ensure it rounds down, and respects cut-off
+            bond(deps.as_mut(), (0, 12_000), (7_000, 0), (3_000, 4_000), 1);
+            assert_users(deps.as_ref(), Some(12), Some(7), Some(7), None);
+
+            slash(deps.as_mut(), &slasher, USER1, Decimal::percent(50)).unwrap();
+            slash(deps.as_mut(), &slasher, USER2, Decimal::percent(10)).unwrap();
+            slash(deps.as_mut(), &slasher, USER3, Decimal::percent(20)).unwrap();
+
+            // Assert updated points
+            assert_stake_liquid(deps.as_ref(), 0, 6_300, 2_400);
+            assert_stake_vesting(deps.as_ref(), 6_000, 0, 3_200);
+            assert_users(deps.as_ref(), Some(6), Some(6), Some(5), None);
+        }
+
+        #[test]
+        fn slashing_claims_works() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let cfg = CONFIG.load(&deps.storage).unwrap();
+            let slasher = add_slasher(deps.as_mut());
+
+            // create some data
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            bond_vesting(deps.as_mut(), 1_000, 750, 40, 1);
+            let height_delta = 2;
+            unbond(deps.as_mut(), 13_000, 2_600, 0, height_delta, 0);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+
+            // check the claims for each user
+            let expires = Duration::new(UNBONDING_DURATION).after(&env.block);
+            assert_eq!(
+                get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
+                vec![Claim::new(
+                    Addr::unchecked(USER1),
+                    12_000,
+                    1_000,
+                    expires,
+                    env.block.height,
+                )]
+            );
+
+            let res = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
+
+            assert_eq!(
+                get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
+                vec![Claim::new(
+                    Addr::unchecked(USER1),
+                    9_600,
+                    800,
+                    expires,
+                    env.block.height,
+                )]
+            );
+            assert_burned(res, &coins(2_400, &cfg.denom), &coins(200, &cfg.denom));
+        }
+
+        #[test]
+        fn random_user_cannot_slash() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let _slasher = add_slasher(deps.as_mut());
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            bond_vesting(deps.as_mut(), 1_200, 750, 8_000, 2);
+            assert_stake_vesting(deps.as_ref(), 1_200, 750, 8_000);
+
+            let res = slash(deps.as_mut(), USER2, USER1, Decimal::percent(20));
+            assert_eq!(
+                res,
+                Err(ContractError::Unauthorized(
+                    "Sender is not on slashers list".to_owned()
+                ))
+            );
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+        }
+
+        #[test]
+        fn admin_cannot_slash() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            let _slasher = add_slasher(deps.as_mut());
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            bond_vesting(deps.as_mut(), 1_200, 750, 8_000, 2);
+            assert_stake_vesting(deps.as_ref(), 1_200, 750, 8_000);
+
+            let res = slash(deps.as_mut(), INIT_ADMIN, USER1, Decimal::percent(20));
+            assert_eq!(
+                res,
+                Err(ContractError::Unauthorized(
+                    "Sender is not on slashers list".to_owned()
+                ))
+            );
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+        }
+
+        #[test]
+        fn removed_slasher_cannot_slash() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+
+            // Add, then remove a slasher
+            let slasher = add_slasher(deps.as_mut());
+            remove_slasher(deps.as_mut(), &slasher);
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 8_000, 2);
+            assert_stake_vesting(deps.as_ref(), 12_000, 7_500, 8_000);
+
+            let res = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20));
+            assert_eq!(
+                res,
+                Err(ContractError::Unauthorized(
+                    "Sender is not on slashers list".to_owned()
+                ))
+            );
+            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+        }
+    }
+
+    mod unbond_veto {
+        use super::*;
+        use crate::claim::ClaimStatus;
+
+        const VETO_PERIOD: u64 = 50;
+
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, veto_period: u64) -> String {
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 0,
+                auto_return_limit: 0,
+                unbond_veto_period: Some(veto_period),
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            let mut deps = deps;
+            instantiate(deps.branch(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+            let slasher = String::from("slasher");
+            let msg = ExecuteMsg::AddSlasher {
+                addr: slasher.clone(),
+                max_portion: None,
+            };
+            execute(deps, mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+            slasher
+        }
+
+        fn set_operator(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            addr: &str,
+            operator: bool,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::SetOperator {
+                addr: addr.to_owned(),
+                operator,
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
+
+        fn veto_unbond(
+            deps: DepsMut<TgradeQuery>,
+            env: Env,
+            sender: &str,
+            addr: &str,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::VetoUnbond {
+                addr: addr.to_owned(),
+            };
+            execute(deps, env, mock_info(sender, &[]), msg)
+        }
+
+        fn query_is_operator(deps: Deps<TgradeQuery>, addr: &str) -> bool {
+            let msg = QueryMsg::IsOperator {
+                addr: addr.to_owned(),
+            };
+            from_slice(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn only_slasher_can_flag_operators() {
+            let mut deps = mock_deps_tgrade();
+            let slasher = do_instantiate(deps.as_mut(), VETO_PERIOD);
+
+            let err = set_operator(deps.as_mut(), INIT_ADMIN, USER1, true).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized("Sender is not on slashers list".to_owned())
+            );
+            assert!(!query_is_operator(deps.as_ref(), USER1));
+
+            set_operator(deps.as_mut(), &slasher, USER1, true).unwrap();
+            assert!(query_is_operator(deps.as_ref(), USER1));
+            assert!(!query_is_operator(deps.as_ref(), USER2));
+
+            set_operator(deps.as_mut(), &slasher, USER1, false).unwrap();
+            assert!(!query_is_operator(deps.as_ref(), USER1));
+        }
+
+        #[test]
+        fn flagged_operator_cannot_redelegate() {
+            let mut deps = mock_deps_tgrade();
+            let slasher = do_instantiate(deps.as_mut(), VETO_PERIOD);
+            let msg = ExecuteMsg::AddSibling {
+                contract: "sibling".to_owned(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+            set_operator(deps.as_mut(), &slasher, USER1, true).unwrap();
+
+            bond(deps.as_mut(), (12_000, 0), (0, 0), (0, 0), 1);
+            let msg = ExecuteMsg::Redelegate {
+                to_contract: "sibling".to_owned(),
+                amount: coin(5_000, DENOM),
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info(USER1, &[]), msg).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized("Flagged operators cannot redelegate".to_owned())
+            );
+        }
+
+        #[test]
+        fn unflagged_unbond_is_not_vetoable() {
+            let mut deps = mock_deps_tgrade();
+            let slasher = do_instantiate(deps.as_mut(), VETO_PERIOD);
+
+            bond(deps.as_mut(), (12_000, 0), (0, 0), (0, 0), 1);
+            unbond(deps.as_mut(), 5_000, 0, 0, 2, 0);
+
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims.len(), 1);
+            assert_eq!(claims[0].veto_until, None);
+            assert_eq!(claims[0].status(&mock_env().block), ClaimStatus::Matured);
+
+            let err = veto_unbond(deps.as_mut(), mock_env(), &slasher, USER1).unwrap_err();
+            assert_eq!(err, ContractError::NothingToVeto {});
+        }
+
+        #[test]
+        fn veto_restores_pending_claims() {
+            let mut deps = mock_deps_tgrade();
+            let slasher = do_instantiate(deps.as_mut(), VETO_PERIOD);
+            set_operator(deps.as_mut(), &slasher, USER1, true).unwrap();
+
+            bond(deps.as_mut(), (12_000, 3_000), (0, 0), (0, 0), 1);
+            unbond(deps.as_mut(), 14_000, 0, 0, 2, 0);
+            assert_users(deps.as_ref(), None, None, None, None);
+
+            let env = mock_env();
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims.len(), 1);
+            assert_eq!(
+                claims[0].veto_until,
+                Some(Duration::new(VETO_PERIOD).after(&env.block))
+            );
+            assert_eq!(claims[0].status(&env.block), ClaimStatus::Pending);
+
+            // only slashers can veto
+            let err = veto_unbond(deps.as_mut(), env.clone(), USER2, USER1).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized("Sender is not on slashers list".to_owned())
+            );
+
+            let mut env = mock_env();
+            env.block.height += 3;
+            veto_unbond(deps.as_mut(), env, &slasher, USER1).unwrap();
+
+            assert!(get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None).is_empty());
+            assert_stake_liquid(deps.as_ref(), 12_000, 0, 0);
+            assert_stake_vesting(deps.as_ref(), 3_000, 0, 0);
+            assert_users(deps.as_ref(), Some(15), None, None, None);
+        }
+
+        #[test]
+        fn matured_claims_cannot_be_vetoed() {
+            let mut deps = mock_deps_tgrade();
+            let slasher = do_instantiate(deps.as_mut(), VETO_PERIOD);
+            set_operator(deps.as_mut(), &slasher, USER1, true).unwrap();
+
+            bond(deps.as_mut(), (12_000, 0), (0, 0), (0, 0), 1);
+            unbond(deps.as_mut(), 5_000, 0, 0, 2, 0);
+
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(VETO_PERIOD);
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims[0].status(&env.block), ClaimStatus::Matured);
+
+            let err = veto_unbond(deps.as_mut(), env, &slasher, USER1).unwrap_err();
+            assert_eq!(err, ContractError::NothingToVeto {});
+
+            // claim is released as usual after unbonding period
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            let res = execute(
+                deps.as_mut(),
+                env,
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                vec![SubMsg::new(BankMsg::Send {
+                    to_address: USER1.into(),
+                    amount: coins(5_000, DENOM),
+                })]
+            );
+        }
+
+        #[test]
+        fn veto_window_delays_release() {
+            let mut deps = mock_deps_tgrade();
+            let veto_period = UNBONDING_DURATION * 2;
+            let slasher = do_instantiate(deps.as_mut(), veto_period);
+            set_operator(deps.as_mut(), &slasher, USER1, true).unwrap();
+
+            bond(deps.as_mut(), (12_000, 0), (0, 0), (0, 0), 1);
+            unbond(deps.as_mut(), 5_000, 0, 0, 2, 0);
+
+            // unbonding period passed, but claim is still pending
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            let err = execute(
+                deps.as_mut(),
+                env,
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::NothingToClaim {});
+
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(veto_period);
+            execute(
+                deps.as_mut(),
+                env,
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert!(get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None).is_empty());
+        }
+    }
+
+    #[test]
+    fn hooks_fire() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
+        assert!(hooks.is_empty());
+
+        let contract1 = String::from("hook1");
+        let contract2 = String::from("hook2");
+
+        // register 2 hooks
+        let admin_info = mock_info(INIT_ADMIN, &[]);
+        let add_msg = ExecuteMsg::AddHook {
+            addr: contract1.clone(),
+        };
+        let add_msg2 = ExecuteMsg::AddHook {
+            addr: contract2.clone(),
+        };
+        for msg in vec![add_msg, add_msg2] {
+            let _ = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+        }
+
+        // check firing on bond
+        assert_users(deps.as_ref(), None, None, None, None);
+        let info = mock_info(USER1, &coins(13_800, DENOM));
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Bond {
+                vesting_tokens: None,
+            },
+        )
+        .unwrap();
+        assert_users(deps.as_ref(), Some(13), None, None, None);
+
+        // ensure messages for each of the 2 hooks
+        assert_eq!(res.messages.len(), 2);
+        let diff = MemberDiff::new(USER1, None, Some(13));
+        let hook_msg = MemberChangedHookMsg::one(diff);
+        let msg1 = hook_msg
+            .clone()
+            .into_cosmos_msg(contract1.clone())
+            .map(SubMsg::new)
+            .unwrap();
+        let msg2 = hook_msg
+            .into_cosmos_msg(contract2.clone())
+            .map(SubMsg::new)
+            .unwrap();
+        assert_eq!(res.messages, vec![msg1, msg2]);
+
+        // check firing on unbond
+        let msg = ExecuteMsg::Unbond {
+            tokens: coin(7_300, DENOM),
+            tag: None,
+        };
+        let info = mock_info(USER1, &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_users(deps.as_ref(), Some(6), None, None, None);
+
+        // ensure messages for each of the 2 hooks
+        assert_eq!(res.messages.len(), 2);
+        let diff = MemberDiff::new(USER1, Some(13), Some(6));
+        let hook_msg = MemberChangedHookMsg::one(diff);
+        let msg1 = hook_msg
+            .clone()
+            .into_cosmos_msg(contract1)
+            .map(SubMsg::new)
+            .unwrap();
+        let msg2 = hook_msg
+            .into_cosmos_msg(contract2)
+            .map(SubMsg::new)
+            .unwrap();
+        assert_eq!(res.messages, vec![msg1, msg2]);
+    }
+
+    #[test]
+    fn only_bond_valid_coins() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        // cannot bond with 0 coins
+        let info = mock_info(USER1, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Bond {
+                vesting_tokens: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoFunds {});
+
+        // cannot bond with incorrect denom
+        let info = mock_info(USER1, &[coin(500, "FOO")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Bond {
+                vesting_tokens: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MissingDenom(DENOM.to_string()));
+
+        // cannot bond with 2 coins (even if one is correct)
+        let info = mock_info(USER1, &[coin(1234, DENOM), coin(5000, "BAR")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Bond {
+                vesting_tokens: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ExtraDenoms(DENOM.to_string()));
+
+        // can bond with just the proper denom
+        // cannot bond with incorrect denom
+        let info = mock_info(USER1, &[coin(500, DENOM)]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Bond {
+                vesting_tokens: None,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn ensure_bonding_edge_cases_liquid() {
+        // use min_bond 0, tokens_per_points 100
+        let mut deps = mock_deps_tgrade();
+        do_instantiate(deps.as_mut(), Uint128::new(100), Uint128::zero(), 5, 0);
+
+        // setting 50 tokens, gives us Some(0) points
+        // even setting to 1 token
+        bond_liquid(deps.as_mut(), 50, 1, 102, 1);
+        assert_users(deps.as_ref(), Some(0), Some(0), Some(1), None);
+
+        // reducing to 0 token makes us None even with min_bond 0
+        unbond(deps.as_mut(), 49, 1, 102, 2, 0);
+        assert_users(deps.as_ref(), Some(0), None, None, None);
+    }
+
+    #[test]
+    fn ensure_bonding_edge_cases_vesting() {
+        // use min_bond 0, tokens_per_points 100
+        let mut deps = mock_deps_tgrade();
+        do_instantiate(deps.as_mut(), Uint128::new(100), Uint128::zero(), 5, 0);
+
+        // setting 50 tokens, gives us Some(0) points
+        // even setting to 1 token
+        bond_vesting(deps.as_mut(), 50, 1, 102, 1);
+        assert_users(deps.as_ref(), Some(0), Some(0), Some(1), None);
+
+        // reducing to 0 token makes us None even with min_bond 0
+        unbond(deps.as_mut(), 49, 1, 102, 2, 0);
+        assert_users(deps.as_ref(), Some(0), None, None, None);
+    }
+
+    #[test]
+    fn ensure_bonding_edge_cases_mixed() {
+        // use min_bond 0, tokens_per_points 100
+        let mut deps = mock_deps_tgrade();
+        do_instantiate(deps.as_mut(), Uint128::new(100), Uint128::zero(), 5, 0);
+
+        // setting 25 liquid tokens, gives us Some(0) points
+        // even setting to 1 token
+        bond_liquid(deps.as_mut(), 25, 1, 102, 1);
+        assert_users(deps.as_ref(), Some(0), Some(0), Some(1), None);
+
+        // setting other 25 vesting tokens, still gives us Some(0) points
+        // also setting to plus 1 token
+        bond_vesting(deps.as_mut(), 25, 1, 102, 2);
+        assert_users(deps.as_ref(), Some(0), Some(0), Some(2), None);
+
+        // reducing to 0 token makes us None even with min_bond 0
+        unbond(deps.as_mut(), 49, 2, 204, 3, 0);
+        assert_users(deps.as_ref(), Some(0), None, None, None);
+    }
+
+    #[test]
+    fn paginated_claim_query() {
+        let mut deps = mock_deps_tgrade();
+        default_instantiate(deps.as_mut());
+
+        // create some data
+        let mut env = mock_env();
+        let msg = ExecuteMsg::Bond {
+            vesting_tokens: None,
+        };
+        let info = mock_info(USER1, &coins(500, DENOM));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let info = mock_info(USER1, &[]);
+        for _ in 0..10 {
+            env.block.time = env.block.time.plus_seconds(10);
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(10, DENOM),
+                tag: None,
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        // check is number of claims is properly limited
+        let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), Some(6), None);
+        assert_eq!(claims.len(), 6);
+        // check if rest is equal to remainder
+        let next = get_claims(
+            deps.as_ref(),
+            Addr::unchecked(USER1),
+            None,
+            Some(claims[5].release_at),
+        );
+        assert_eq!(next.len(), 4);
+
+        // check if joining and sorting both vectors equal number from start
+        let mut all_claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+        all_claims.sort_by_key(|claim| claim.addr.clone());
+
+        let mut concatenated = [claims, next].concat();
+        concatenated.sort_by_key(|claim| claim.addr.clone());
+        assert_eq!(concatenated, all_claims);
+    }
+
+    mod redelegate {
+        use cosmwasm_std::{SubMsgResponse, SubMsgResult};
+        use cw_controllers::AdminError;
+
+        use super::*;
+
+        const STAKE_A: &str = "stake_a";
+        const STAKE_B: &str = "stake_b";
+
+        fn add_sibling(deps: DepsMut<TgradeQuery>, sender: &str, contract: &str) {
+            let msg = ExecuteMsg::AddSibling {
+                contract: contract.to_owned(),
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg).unwrap();
+        }
+
+        fn redelegate(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            to_contract: &str,
+            amount: u128,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Redelegate {
+                to_contract: to_contract.to_owned(),
+                amount: coin(amount, DENOM),
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
+
+        fn receive_redelegation(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            staker: &str,
+            amount: u128,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::ReceiveRedelegation {
+                staker: staker.to_owned(),
+            };
+            execute(
+                deps,
+                mock_env(),
+                mock_info(sender, &coins(amount, DENOM)),
+                msg,
+            )
+        }
+
+        fn query_siblings(deps: Deps<TgradeQuery>) -> Vec<String> {
+            let raw = query(deps, mock_env(), QueryMsg::ListSiblings {}).unwrap();
+            let res: SiblingsResponse = from_slice(&raw).unwrap();
+            res.siblings
+        }
+
+        #[test]
+        fn only_admin_can_manage_siblings() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+
+            let msg = ExecuteMsg::AddSibling {
+                contract: STAKE_B.to_owned(),
+            };
+            let err = execute(deps.as_mut(), mock_env(), mock_info(USER1, &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+            add_sibling(deps.as_mut(), INIT_ADMIN, STAKE_B);
+            add_sibling(deps.as_mut(), INIT_ADMIN, STAKE_A);
+            assert_eq!(query_siblings(deps.as_ref()), vec![STAKE_A, STAKE_B]);
+
+            let msg = ExecuteMsg::RemoveSibling {
+                contract: STAKE_A.to_owned(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+            assert_eq!(query_siblings(deps.as_ref()), vec![STAKE_B]);
+        }
+
+        #[test]
+        fn redelegate_moves_liquid_stake() {
+            let mut deps_a = mock_deps_tgrade();
+            default_instantiate(deps_a.as_mut());
+            add_sibling(deps_a.as_mut(), INIT_ADMIN, STAKE_B);
+            let mut deps_b = mock_deps_tgrade();
+            default_instantiate(deps_b.as_mut());
+            add_sibling(deps_b.as_mut(), INIT_ADMIN, STAKE_A);
+
+            bond(deps_a.as_mut(), (12_000, 1_000), (0, 0), (0, 0), 1);
+            assert_users(deps_a.as_ref(), Some(13), None, None, None);
+
+            let res = redelegate(deps_a.as_mut(), USER1, STAKE_B, 10_000).unwrap();
+            let receive = res.messages.last().unwrap();
+            assert_eq!(receive.id, REDELEGATE_REPLY_ID);
+            assert_eq!(
+                receive.msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: STAKE_B.to_owned(),
+                    msg: to_binary(&ExecuteMsg::ReceiveRedelegation {
+                        staker: USER1.to_owned()
+                    })
+                    .unwrap(),
+                    funds: coins(10_000, DENOM),
+                })
+            );
+
+            // stake is removed right away, without any claim
+            assert_stake_liquid(deps_a.as_ref(), 2_000, 0, 0);
+            assert_stake_vesting(deps_a.as_ref(), 1_000, 0, 0);
+            assert_users(deps_a.as_ref(), None, None, None, None);
+            assert_eq!(
+                get_claims(deps_a.as_ref(), Addr::unchecked(USER1), None, None),
+                vec![]
+            );
+
+            // sibling bonds received tokens
+            receive_redelegation(deps_b.as_mut(), STAKE_A, USER1, 10_000).unwrap();
+            assert_stake_liquid(deps_b.as_ref(), 10_000, 0, 0);
+            assert_users(deps_b.as_ref(), Some(10), None, None, None);
+
+            // and confirmation clears the pending redelegation
+            let reply_msg = Reply {
+                id: REDELEGATE_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            };
+            let res = reply(deps_a.as_mut(), mock_env(), reply_msg).unwrap();
+            assert_eq!(res.attributes[0].value, "redelegate_confirmed");
+            assert_eq!(
+                PENDING_REDELEGATION
+                    .may_load(deps_a.as_ref().storage)
+                    .unwrap(),
+                None
+            );
+        }
+
+        #[test]
+        fn redelegate_validations() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            add_sibling(deps.as_mut(), INIT_ADMIN, STAKE_B);
+
+            bond(deps.as_mut(), (12_000, 1_000), (0, 0), (0, 0), 1);
+
+            let err = redelegate(deps.as_mut(), USER1, "unknown", 1_000).unwrap_err();
+            assert_eq!(err, ContractError::NotSibling("unknown".to_owned()));
+
+            let err = redelegate(deps.as_mut(), USER1, STAKE_B, 0).unwrap_err();
+            assert_eq!(err, ContractError::ZeroAmount {});
+
+            // vesting stake cannot be redelegated
+            let err = redelegate(deps.as_mut(), USER1, STAKE_B, 12_500).unwrap_err();
+            assert_eq!(err, ContractError::InsufficientLiquidStake {});
+
+            let err = receive_redelegation(deps.as_mut(), "unknown", USER2, 1_000).unwrap_err();
+            assert_eq!(err, ContractError::NotSibling("unknown".to_owned()));
+            assert_stake_liquid(deps.as_ref(), 12_000, 0, 0);
+        }
+    }
+
+    mod auto_release_claims {
+        // Because of tests framework limitations at the point of implementing this test, it is
+        // difficult to actually test reaction for tgrade sudo messages. Instead to check the
+        // auto-release functionality, there are assumptions made:
+        // * Registration to sudo events is correct
+        // * Auto-releasing claims occurs on sudo EndBlock message, and the message is purely
+        // calling the `end_block` function - calling this function in test is simulating actual
+        // end block
+
+        use cosmwasm_std::CosmosMsg;
+
+        use super::*;
+
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, limit: u64) {
+            super::do_instantiate(deps, TOKENS_PER_POINT, MIN_BOND, UNBONDING_DURATION, limit)
+        }
+
+        /// Helper for asserting if expected transfers occurred in response. Panics if any non
+        /// `BankMsg::Send` occurred, or transfers are different than expected.
+        ///
+        /// Transfers are passed in form of pairs `(addr, amount)`, as for all test in this module
+        /// expected denom is fixed
+        #[track_caller]
+        fn assert_sends(response: Response, mut expected_transfers: Vec<(&str, u128)>) {
+            let mut sends: Vec<_> = response
+                .messages
+                .into_iter()
+                .map(|msg| match msg.msg {
+                    // Trick is used here - bank send messages are filtered out, and mapped to triple
+                    // `(addr, amount_sum, msg)` - `addr` and `amount_sum` would be used only to
+                    // properly sort messages, then they would be discarded. As in expected messages
+                    // always only one coin is expected for all send messages, taking sum for sorting
+                    // is good enough - in case of multiple of invalid denoms it would be visible on
+                    // comparison.
+                    //
+                    // Possibly in future it would be possible for another messages to occur - in such
+                    // case instead of returning err and panicking from this function, such messages
+                    // should be filtered out.
+                    CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => Ok((
+                        to_address.clone(),
+                        amount.iter().map(|c| c.amount).sum::<Uint128>(),
+                        BankMsg::Send { to_address, amount },
+                    )),
+                    msg => Err(format!(
+                        "Unexpected message on response, expected only bank send messages: {:?}",
+                        msg
+                    )),
+                })
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+            sends.sort_by_key(|(addr, amount_sum, _)| (addr.clone(), *amount_sum));
+            // Drop  addr and amount_sum for comparison
+            let sends: Vec<_> = sends.into_iter().map(|(_, _, msg)| msg).collect();
+
+            // Tuples are sorted simply first by addresses, then by amount
+            expected_transfers.sort_unstable();
+
+            // Build messages for comparison
+            let expected_transfers: Vec<_> = expected_transfers
+                .into_iter()
+                .map(|(addr, amount)| BankMsg::Send {
+                    to_address: addr.to_owned(),
+                    amount: coins(amount, DENOM),
+                })
+                .collect();
+
+            assert_eq!(sends, expected_transfers);
+        }
+
+        /// Helper for asserting if expected undelegates occurred in response. Panics if any non
+        /// `TgradeMsg::Undelegate` occurred, or undelegates are different than expected.
+        ///
+        /// Transfers are passed in form of pairs `(addr, amount)`, as for all tests in this module
+        /// the expected denom is fixed.
+        #[track_caller]
+        fn assert_undelegates(response: Response, mut expected_transfers: Vec<(&str, u128)>) {
+            let mut undelegates: Vec<_> = response
+                .messages
+                .into_iter()
+                .map(|msg| match msg.msg {
+                    // Trick is used here - undelegate messages are filtered out, and mapped to a triple
+                    // `(addr, amount, msg)` - `addr` and `amount` will be used only to properly
+                    // sort messages, then they will be discarded. As in the expected messages
+                    // always only one coin is expected for all messages, taking the amount for sorting
+                    // is good enough - in the case of multiple or invalid denoms, it'll error on
+                    // comparison.
+                    //
+                    // Possibly in the future it would be possible for another messages to occur -
+                    // in such case instead of returning err and panicking from this function, such
+                    // messages should be filtered out.
+                    CosmosMsg::Custom(TgradeMsg::Undelegate { funds, recipient }) => Ok((
+                        recipient.clone(),
+                        funds.amount,
+                        TgradeMsg::Undelegate { recipient, funds },
+                    )),
+                    msg => Err(format!(
+                        "Unexpected message on response, expected only tgrade undelegate messages: {:?}",
+                        msg
+                    )),
+                })
+                .collect::<Result<_, _>>()
+                .unwrap();
 
-        // check historical queries all work
-        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
-        assert_users(deps.as_ref(), Some(12), Some(7), None, Some(height + 2)); // after first stake
-        assert_users(deps.as_ref(), Some(12), Some(15), Some(5), Some(height + 3));
-        // after second stake
-    }
+            undelegates.sort_by_key(|(addr, amount, _)| (addr.clone(), *amount));
+            // Drop  addr and amount for comparison
+            let undelegates: Vec<_> = undelegates.into_iter().map(|(_, _, msg)| msg).collect();
 
-    #[test]
-    fn try_member_queries() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+            // Tuples are sorted simply first by addresses, then by amounts
+            expected_transfers.sort_unstable();
 
-        bond(deps.as_mut(), (12_000, 0), (7_400, 100), (0, 4_000), 1);
+            // Build messages for comparison
+            let expected_transfers: Vec<_> = expected_transfers
+                .into_iter()
+                .map(|(addr, amount)| TgradeMsg::Undelegate {
+                    funds: coin(amount, DENOM),
+                    recipient: addr.to_owned(),
+                })
+                .collect();
 
-        let member1 = query_member(deps.as_ref(), USER1.into(), None).unwrap();
-        assert_eq!(member1.points, Some(12));
+            assert_eq!(undelegates, expected_transfers);
+        }
 
-        let member2 = query_member(deps.as_ref(), USER2.into(), None).unwrap();
-        assert_eq!(member2.points, Some(7));
+        /// Helper for asserting if expected sends and undelegates occurred in response. Panics if
+        /// any non `Bank::Send` or `TgradeMsg::Undelegate` occurred, or send / undelegates are
+        /// different than expected.
+        ///
+        /// Transfers are passed in form of pairs `(addr, amount)`, as for all tests in this module
+        /// the expected denom is fixed.
+        #[track_caller]
+        fn assert_sends_undelegates(
+            response: Response,
+            expected_sends: Vec<(&str, u128)>,
+            expected_undelegates: Vec<(&str, u128)>,
+        ) {
+            let sends = response
+                .clone()
+                .messages
+                .into_iter()
+                .filter(|msg| matches!(msg.msg, CosmosMsg::Bank(BankMsg::Send { .. })));
+            let undelegates =
+                response.clone().messages.into_iter().filter(|msg| {
+                    matches!(msg.msg, CosmosMsg::Custom(TgradeMsg::Undelegate { .. }))
+                });
 
-        let member3 = query_member(deps.as_ref(), USER3.into(), None).unwrap();
-        assert_eq!(member3.points, None);
+            assert_sends(
+                Response::new().add_submessages(sends.clone()),
+                expected_sends,
+            );
+            assert_undelegates(
+                Response::new().add_submessages(undelegates.clone()),
+                expected_undelegates,
+            );
 
-        let members = list_members(deps.as_ref(), None, None).unwrap().members;
-        assert_eq!(members.len(), 2);
-        // Assert the set is proper
-        assert_eq!(
-            members,
-            vec![
-                Member {
-                    addr: USER1.into(),
-                    points: 12,
-                    start_height: None,
-                },
-                Member {
-                    addr: USER2.into(),
-                    points: 7,
-                    start_height: None,
-                },
-            ]
-        );
+            // Assert there are no other message types
+            assert_eq!(
+                response.messages.len(),
+                sends.count() + undelegates.count(),
+                "Unexpected messages in response, expected only bank send or tgrade undelegate messages"
+            );
+        }
 
-        // Test pagination / limits
-        let members = list_members(deps.as_ref(), None, Some(1)).unwrap().members;
-        assert_eq!(members.len(), 1);
-        // Assert the set is proper
-        assert_eq!(
-            members,
-            vec![Member {
-                addr: USER1.into(),
-                points: 12,
-                start_height: None,
-            },]
-        );
+        #[test]
+        fn single_claim_liquid() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 2);
 
-        // Next page
-        let start_after = Some(members[0].addr.clone());
-        let members = list_members(deps.as_ref(), start_after, Some(1))
-            .unwrap()
-            .members;
-        assert_eq!(members.len(), 1);
-        // Assert the set is proper
-        assert_eq!(
-            members,
-            vec![Member {
-                addr: USER2.into(),
-                points: 7,
-                start_height: None,
-            },]
-        );
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        // Assert there's no more
-        let start_after = Some(members[0].addr.clone());
-        let members = list_members(deps.as_ref(), start_after, Some(1))
-            .unwrap()
-            .members;
-        assert_eq!(members.len(), 0);
-    }
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
 
-    #[test]
-    fn try_list_members_by_points() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 1000)]);
+        }
 
-        bond(deps.as_mut(), (10_000, 1_000), (6_500, 0), (0, 5_000), 1);
+        #[test]
+        fn single_claim_vesting() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 2);
 
-        let members = list_members_by_points(deps.as_ref(), None, None)
-            .unwrap()
-            .members;
-        assert_eq!(members.len(), 3);
-        // Assert the set is sorted by (descending) points
-        assert_eq!(
-            members,
-            vec![
-                Member {
-                    addr: USER1.into(),
-                    points: 11,
-                    start_height: None,
-                },
-                Member {
-                    addr: USER2.into(),
-                    points: 6,
-                    start_height: None,
-                },
-                Member {
-                    addr: USER3.into(),
-                    points: 5,
-                    start_height: None,
-                },
-            ]
-        );
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        // Test pagination / limits
-        let members = list_members_by_points(deps.as_ref(), None, Some(1))
-            .unwrap()
-            .members;
-        assert_eq!(members.len(), 1);
-        // Assert the set is proper
-        assert_eq!(
-            members,
-            vec![Member {
-                addr: USER1.into(),
-                points: 11,
-                start_height: None,
-            },]
-        );
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
 
-        // Next page
-        let last = members.last().unwrap();
-        let start_after = Some(last.clone());
-        let members = list_members_by_points(deps.as_ref(), start_after, None)
-            .unwrap()
-            .members;
-        assert_eq!(members.len(), 2);
-        // Assert the set is proper
-        assert_eq!(
-            members,
-            vec![
-                Member {
-                    addr: USER2.into(),
-                    points: 6,
-                    start_height: None,
-                },
-                Member {
-                    addr: USER3.into(),
-                    points: 5,
-                    start_height: None,
-                },
-            ]
-        );
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 1000)]);
+        }
 
-        // Assert there's no more
-        let last = members.last().unwrap();
-        let start_after = Some(last.clone());
-        let members = list_members_by_points(deps.as_ref(), start_after, Some(1))
-            .unwrap()
-            .members;
-        assert_eq!(members.len(), 0);
-    }
+        #[test]
+        fn single_claim_mixed() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 2);
 
-    #[test]
-    fn unbond_validations() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 1_000, 7_500, 4_000, 1);
+
+            bond_vesting(deps.as_mut(), 11_000, 7_500, 4_000, 2);
+            let height_delta = 3;
+
+            unbond(deps.as_mut(), 1001, 0, 0, height_delta, 0);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            // liquid stakes are claimed first, then vesting ones as needed
+            assert_sends_undelegates(resp, vec![(USER1, 1000)], vec![(USER1, 1)]);
+        }
+
+        #[test]
+        fn multiple_users_claims_liquid() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 4);
+
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
+
+            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
+            unbond(deps.as_mut(), 0, 0, 200, height_delta, 1);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // Zero amount unbonds are rejected
-        let msg = ExecuteMsg::Unbond {
-            tokens: coin(0, DENOM),
-        };
-        let env = mock_env();
-        let info = mock_info(USER1, &[]);
-        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
-        assert_eq!(ContractError::ZeroAmount {}, err);
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 1000), (USER2, 500), (USER3, 200)]);
+        }
 
-        // Invalid denom unbonds are rejected
-        let msg = ExecuteMsg::Unbond {
-            tokens: coin(1234, "INV"),
-        };
-        let env = mock_env();
-        let info = mock_info(USER1, &[]);
-        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
-        assert_eq!(ContractError::InvalidDenom {}, err);
-    }
+        #[test]
+        fn multiple_users_claims_vesting() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 4);
 
-    #[test]
-    fn unbond_stake_update_membership() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
-        let height = mock_env().block.height;
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        // ensure it rounds down, and respects cut-off
-        bond(deps.as_mut(), (0, 12_000), (500, 7_000), (3_000, 3_000), 1);
-        assert_users(deps.as_ref(), Some(12), Some(7), Some(6), None);
+            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
+            unbond(deps.as_mut(), 0, 0, 200, height_delta, 1);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        unbond(deps.as_mut(), 4_500, 2_600, 1_000, 2, 0);
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 1000), (USER2, 500), (USER3, 200)]);
+        }
 
-        // Assert updated points
-        assert_stake_liquid(deps.as_ref(), 0, 0, 2000);
-        assert_stake_vesting(deps.as_ref(), 7_500, 4_900, 3000);
-        assert_users(deps.as_ref(), Some(7), None, Some(5), None);
+        #[test]
+        fn multiple_users_claims_mixed() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 4);
 
-        // Adding a little more returns points
-        bond(deps.as_mut(), (500, 100), (100, 0), (0, 2_222), 3);
+            bond_liquid(deps.as_mut(), 11_000, 7_000, 4_000, 1);
+            bond_vesting(deps.as_mut(), 1_000, 500, 0, 2);
+            let height_delta = 3;
 
-        // Assert updated points
-        assert_stake_liquid(deps.as_ref(), 500, 100, 2000);
-        assert_stake_vesting(deps.as_ref(), 7_600, 4_900, 5_222);
-        assert_users(deps.as_ref(), Some(8), Some(5), Some(7), None);
+            unbond(deps.as_mut(), 12_000, 7_001, 0, height_delta, 0);
+            unbond(deps.as_mut(), 0, 0, 200, height_delta, 1);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // check historical queries all work
-        assert_users(deps.as_ref(), None, None, None, Some(height + 1)); // before first stake
-        assert_users(deps.as_ref(), Some(12), Some(7), Some(6), Some(height + 2)); // after first bond
-        assert_users(deps.as_ref(), Some(7), None, Some(5), Some(height + 3)); // after first unbond
-        assert_users(deps.as_ref(), Some(8), Some(5), Some(7), Some(height + 4)); // after second bond
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends_undelegates(
+                resp,
+                vec![(USER1, 11_000), (USER2, 7_000), (USER3, 200)],
+                vec![(USER1, 1_000), (USER2, 1)],
+            );
+        }
 
-        // error if try to unbond more than stake (USER2 has 5000 staked)
-        let msg = ExecuteMsg::Unbond {
-            tokens: coin(5100, DENOM),
-        };
-        let mut env = mock_env();
-        env.block.height += 5;
-        let info = mock_info(USER2, &[]);
-        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
-        assert_eq!(
-            err,
-            ContractError::Std(StdError::overflow(OverflowError::new(
-                OverflowOperation::Sub,
-                4900,
-                5000,
-            )))
-        );
-    }
+        #[test]
+        fn single_user_multiple_claims_liquid() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 3);
 
-    #[test]
-    fn raw_queries_work() {
-        // add will over-write and remove have no effect
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
-        // Set values as (11, 6, None)
-        bond(deps.as_mut(), (1_000, 10_000), (6_000, 0), (0, 0), 1);
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        // get total from raw key
-        let total_raw = deps.storage.get(TOTAL_KEY.as_bytes()).unwrap();
-        let total: u64 = from_slice(&total_raw).unwrap();
-        assert_eq!(17, total);
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 0, 0, height_delta, 1);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // get member votes from raw key
-        let member2_raw = deps.storage.get(&member_key(USER2)).unwrap();
-        let member2: MemberInfo = from_slice(&member2_raw).unwrap();
-        assert_eq!(6, member2.points);
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 1500)]);
+        }
 
-        // and execute misses
-        let member3_raw = deps.storage.get(&member_key(USER3));
-        assert_eq!(None, member3_raw);
-    }
+        #[test]
+        fn single_user_multiple_claims_vesting() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 3);
 
-    #[track_caller]
-    fn get_claims(
-        deps: Deps<TgradeQuery>,
-        addr: Addr,
-        limit: Option<u32>,
-        start_after: Option<Expiration>,
-    ) -> Vec<Claim> {
-        claims()
-            .query_claims(deps, addr, limit, start_after)
-            .unwrap()
-    }
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-    #[test]
-    fn unbond_claim_workflow() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 0, 0, height_delta, 1);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // create some data
-        bond(deps.as_mut(), (4_000, 7_500), (7_500, 0), (3_000, 1_000), 1);
-        let height_delta = 2;
-        // 4_000 (liquid) and 500 (vesting) will be claimed for USER1
-        unbond(deps.as_mut(), 4_500, 2_600, 0, height_delta, 0);
-        let mut env = mock_env();
-        env.block.height += height_delta;
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 1500)]);
+        }
 
-        // check the claims for each user
-        let expires = Duration::new(UNBONDING_DURATION).after(&env.block);
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
-            vec![Claim::new(
-                Addr::unchecked(USER1),
-                4_000,
-                500,
-                expires,
-                env.block.height,
-            )]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
-            vec![Claim::new(
-                Addr::unchecked(USER2),
-                2_600,
-                0,
-                expires,
-                env.block.height,
-            )]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER3), None, None),
-            vec![]
-        );
+        #[test]
+        fn single_user_multiple_claims_mixed() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 3);
 
-        // do another unbond later on
-        let mut env2 = mock_env();
-        let height_delta = 22;
-        env2.block.height += height_delta;
-        let time_delta = 50;
-        unbond(deps.as_mut(), 0, 1_345, 1_500, height_delta, time_delta);
+            bond_vesting(deps.as_mut(), 11_000, 7_000, 4_000, 1);
+            bond_liquid(deps.as_mut(), 1_000, 500, 4_000, 2);
+            let height_delta = 3;
 
-        // with updated claims
-        let expires2 = Duration::new(UNBONDING_DURATION + time_delta).after(&env2.block);
-        assert_ne!(expires, expires2);
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
-            vec![Claim::new(
-                Addr::unchecked(USER1),
-                4_000,
-                500,
-                expires,
-                env.block.height,
-            )]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
-            vec![
-                Claim::new(Addr::unchecked(USER2), 2_600, 0, expires, env.block.height),
-                Claim::new(
-                    Addr::unchecked(USER2),
-                    1_345,
-                    0,
-                    expires2,
-                    env2.block.height,
-                ),
-            ]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER3), None, None),
-            vec![Claim::new(
-                Addr::unchecked(USER3),
-                1_500,
-                0,
-                expires2,
-                env2.block.height,
-            )]
-        );
+            unbond(deps.as_mut(), 1001, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 0, 0, height_delta, 1);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // nothing can be withdrawn yet
-        let err = execute(
-            deps.as_mut(),
-            env,
-            mock_info(USER1, &[]),
-            ExecuteMsg::Claim {},
-        )
-        .unwrap_err();
-        assert_eq!(err, ContractError::NothingToClaim {});
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends_undelegates(resp, vec![(USER1, 1000)], vec![(USER1, 501)]);
+        }
 
-        // now mature first section, withdraw that
-        let mut env3 = mock_env();
-        env3.block.time = env3.block.time.plus_seconds(UNBONDING_DURATION);
-        // first one can now release
-        let res = execute(
-            deps.as_mut(),
-            env3.clone(),
-            mock_info(USER1, &[]),
-            ExecuteMsg::Claim {},
-        )
-        .unwrap();
-        assert_eq!(
-            res.messages,
-            vec![
-                SubMsg::new(BankMsg::Send {
-                    to_address: USER1.into(),
-                    amount: coins(4_000, DENOM),
-                }),
-                SubMsg::new(TgradeMsg::Undelegate {
-                    funds: coin(500, DENOM),
-                    recipient: USER1.into(),
-                })
-            ]
-        );
+        #[test]
+        fn only_expired_claims_liquid() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 3);
 
-        // second releases partially
-        let res = execute(
-            deps.as_mut(),
-            env3.clone(),
-            mock_info(USER2, &[]),
-            ExecuteMsg::Claim {},
-        )
-        .unwrap();
-        assert_eq!(
-            res.messages,
-            vec![SubMsg::new(BankMsg::Send {
-                to_address: USER2.into(),
-                amount: coins(2_600, DENOM),
-            })]
-        );
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        // but the third one cannot release
-        let err = execute(
-            deps.as_mut(),
-            env3,
-            mock_info(USER3, &[]),
-            ExecuteMsg::Claim {},
-        )
-        .unwrap_err();
-        assert_eq!(err, ContractError::NothingToClaim {});
+            // Claims to be returned
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
 
-        // claims updated properly
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
-            vec![]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
-            vec![Claim::new(
-                Addr::unchecked(USER2),
-                1_345,
-                0,
-                expires2,
-                env2.block.height,
-            )]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER3), None, None),
-            vec![Claim::new(
-                Addr::unchecked(USER3),
-                1_500,
-                0,
-                expires2,
-                env2.block.height,
-            )]
-        );
+            // Clams not yet expired
+            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
+            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
+            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
 
-        // add another few claims for 2
-        unbond(deps.as_mut(), 0, 600, 0, 30, 0);
-        unbond(deps.as_mut(), 0, 1_005, 0, 50, 0);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // ensure second can claim all tokens at once
-        let mut env4 = mock_env();
-        env4.block.time = env4
-            .block
-            .time
-            .plus_seconds(UNBONDING_DURATION + time_delta);
-        let res = execute(
-            deps.as_mut(),
-            env4,
-            mock_info(USER2, &[]),
-            ExecuteMsg::Claim {},
-        )
-        .unwrap();
-        assert_eq!(
-            res.messages,
-            vec![SubMsg::new(BankMsg::Send {
-                to_address: USER2.into(),
-                // 1_345 + 600 + 1_005
-                amount: coins(2_950, DENOM),
-            })]
-        );
-        assert_eq!(
-            get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None),
-            vec![]
-        );
-    }
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 1500), (USER2, 600)]);
+        }
 
-    #[test]
-    fn add_remove_hooks() {
-        // add will over-write and remove have no effect
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+        #[test]
+        fn only_expired_claims_vesting() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 3);
 
-        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
-        assert!(hooks.is_empty());
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        let contract1 = String::from("hook1");
-        let contract2 = String::from("hook2");
+            // Claims to be returned
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
 
-        let add_msg = ExecuteMsg::AddHook {
-            addr: contract1.clone(),
-        };
+            // Clams not yet expired
+            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
+            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
+            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
 
-        // anyone can add the first one, until preauth is consume
-        assert_eq!(1, PREAUTH_HOOKS.get_auth(&deps.storage).unwrap());
-        let user_info = mock_info(USER1, &[]);
-        let _ = execute(deps.as_mut(), mock_env(), user_info, add_msg.clone()).unwrap();
-        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
-        assert_eq!(hooks, vec![contract1.clone()]);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // non-admin cannot add hook without preauth
-        assert_eq!(0, PREAUTH_HOOKS.get_auth(&deps.storage).unwrap());
-        let user_info = mock_info(USER1, &[]);
-        let err = execute(
-            deps.as_mut(),
-            mock_env(),
-            user_info.clone(),
-            add_msg.clone(),
-        )
-        .unwrap_err();
-        assert_eq!(err, PreauthError::NoPreauth {}.into());
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 1500), (USER2, 600)]);
+        }
 
-        // cannot remove a non-registered contract
-        let admin_info = mock_info(INIT_ADMIN, &[]);
-        let remove_msg = ExecuteMsg::RemoveHook {
-            addr: contract2.clone(),
-        };
-        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), remove_msg).unwrap_err();
-        assert_eq!(err, HookError::HookNotRegistered {}.into());
+        #[test]
+        fn claim_returned_once_liquid() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 5);
 
-        // admin can second contract, and it appears in the query
-        let add_msg2 = ExecuteMsg::AddHook {
-            addr: contract2.clone(),
-        };
-        execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg2).unwrap();
-        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
-        assert_eq!(hooks, vec![contract1.clone(), contract2.clone()]);
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        // cannot re-add an existing contract
-        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg).unwrap_err();
-        assert_eq!(err, HookError::HookAlreadyRegistered {}.into());
+            // Claims to be returned
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
 
-        // non-admin cannot remove
-        let remove_msg = ExecuteMsg::RemoveHook { addr: contract1 };
-        let err = execute(deps.as_mut(), mock_env(), user_info, remove_msg.clone()).unwrap_err();
-        assert_eq!(
-            err,
-            ContractError::Unauthorized(
-                "Hook address is not same as sender's and sender is not an admin".to_owned()
-            )
-        );
+            // Clams not yet expired
+            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
 
-        // remove the original
-        execute(deps.as_mut(), mock_env(), admin_info, remove_msg).unwrap();
-        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
-        assert_eq!(hooks, vec![contract2.clone()]);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        // contract can self-remove
-        let contract_info = mock_info(&contract2, &[]);
-        let remove_msg2 = ExecuteMsg::RemoveHook { addr: contract2 };
-        execute(deps.as_mut(), mock_env(), contract_info, remove_msg2).unwrap();
-        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
-        assert_eq!(hooks, Vec::<String>::new());
-    }
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 1500), (USER2, 600)]);
 
-    mod slash {
-        use super::*;
+            // Some additional claims
+            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
+            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
 
-        fn query_is_slasher(deps: Deps<TgradeQuery>, env: Env, addr: String) -> StdResult<bool> {
-            let msg = QueryMsg::IsSlasher { addr };
-            let raw = query(deps, env, msg)?;
-            let is_slasher: bool = from_slice(&raw)?;
-            Ok(is_slasher)
-        }
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
 
-        fn query_list_slashers(deps: Deps<TgradeQuery>, env: Env) -> StdResult<Vec<String>> {
-            let msg = QueryMsg::ListSlashers {};
-            let raw = query(deps, env, msg)?;
-            let slashers: Vec<String> = from_slice(&raw)?;
-            Ok(slashers)
+            // Expected that claims at time offset 2 and 3 are returned (0 and 1 are already
+            // returned, 4 is not yet expired)
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 900), (USER2, 300), (USER3, 400)]);
         }
 
-        fn add_slasher(deps: DepsMut<TgradeQuery>) -> String {
-            let slasher = String::from("slasher");
-            let add_msg = ExecuteMsg::AddSlasher {
-                addr: slasher.clone(),
-            };
-            let user_info = mock_info(USER1, &[]);
-            execute(deps, mock_env(), user_info, add_msg).unwrap();
+        #[test]
+        fn claim_returned_once_vesting() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), 5);
 
-            slasher
-        }
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-        fn remove_slasher(deps: DepsMut<TgradeQuery>, slasher: &str) {
-            let add_msg = ExecuteMsg::RemoveSlasher {
-                addr: slasher.to_string(),
-            };
-            let user_info = mock_info(INIT_ADMIN, &[]);
-            execute(deps, mock_env(), user_info, add_msg).unwrap();
-        }
+            // Claims to be returned
+            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
+            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
 
-        fn slash(
-            deps: DepsMut<TgradeQuery>,
-            slasher: &str,
-            addr: &str,
-            portion: Decimal,
-        ) -> Result<Response, ContractError> {
-            let msg = ExecuteMsg::Slash {
-                addr: addr.to_string(),
-                portion,
-            };
-            let slasher_info = mock_info(slasher, &[]);
+            // Clams not yet expired
+            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
 
-            execute(deps, mock_env(), slasher_info, msg)
-        }
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
 
-        fn assert_burned(res: Response, expected_liquid: &[Coin], expected_vesting: &[Coin]) {
-            // Args checks for robustness
-            assert!(expected_liquid.len() <= 1);
-            assert!(expected_vesting.len() <= 1);
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 1500), (USER2, 600)]);
 
-            // Find all instances of BankMsg::Burn in the response and extract the burned amounts
-            let burned_amounts: Vec<_> = res
-                .messages
-                .iter()
-                .filter_map(|sub_msg| match &sub_msg.msg {
-                    CosmosMsg::Bank(BankMsg::Burn { amount }) => Some(amount),
-                    _ => None,
-                })
-                .collect();
+            // Some additional claims
+            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
+            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
 
-            assert!(
-                burned_amounts.len() == 1 || burned_amounts.len() == 2,
-                "Expected exactly 1 or 2 Bank::Burn message, got {}",
-                burned_amounts.len()
-            );
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
 
-            let mut index = 0;
-            if !expected_liquid.is_empty() {
-                assert_eq!(
-                    burned_amounts[index], &expected_liquid,
-                    "Expected to burn {} liquid, burned {}",
-                    expected_liquid[0], burned_amounts[index][0]
-                );
-                index += 1;
-            }
-            if !expected_vesting.is_empty() {
-                assert_eq!(
-                    burned_amounts[index], &expected_vesting,
-                    "Expected to burn {} vesting, burned {}",
-                    expected_liquid[0], burned_amounts[index][0]
-                );
-            }
+            // Expected that claims at time offset 2 and 3 are returned (0 and 1 are already
+            // returned, 4 is not yet expired)
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 900), (USER2, 300), (USER3, 400)]);
         }
 
         #[test]
-        fn add_remove_slashers() {
+        fn up_to_limit_claims_returned_liquid() {
             let mut deps = mock_deps_tgrade();
-            let env = mock_env();
-            default_instantiate(deps.as_mut());
+            do_instantiate(deps.as_mut(), 2);
 
-            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
-            assert!(slashers.is_empty());
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-            let contract1 = String::from("slasher1");
-            let contract2 = String::from("slasher2");
+            // Claims to be returned
+            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
+            unbond(deps.as_mut(), 0, 600, 0, height_delta, 1);
+            unbond(deps.as_mut(), 200, 0, 0, height_delta, 2);
+            unbond(deps.as_mut(), 0, 0, 300, height_delta, 3);
 
-            let add_msg = ExecuteMsg::AddSlasher {
-                addr: contract1.clone(),
-            };
+            // Even if all claims are already expired, only two of them (time offset 0) should be
+            // returned
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
 
-            // anyone can add the first one, until preauth is consumed
-            assert_eq!(1, PREAUTH_SLASHING.get_auth(&deps.storage).unwrap());
-            let user_info = mock_info(USER1, &[]);
-            let _ = execute(deps.as_mut(), mock_env(), user_info, add_msg.clone()).unwrap();
-            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
-            assert_eq!(slashers, vec![contract1.clone()]);
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 1000), (USER2, 500)]);
 
-            // non-admin cannot add slasher without preauth
-            assert_eq!(0, PREAUTH_SLASHING.get_auth(&deps.storage).unwrap());
-            let user_info = mock_info(USER1, &[]);
-            let err = execute(
-                deps.as_mut(),
-                mock_env(),
-                user_info.clone(),
-                add_msg.clone(),
-            )
-            .unwrap_err();
-            assert_eq!(err, PreauthError::NoPreauth {}.into());
+            // Then on next block next batch is returned (time offset 1 and 2)
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 4);
 
-            // cannot remove a non-registered slasher
-            let admin_info = mock_info(INIT_ADMIN, &[]);
-            let remove_msg = ExecuteMsg::RemoveSlasher {
-                addr: contract2.clone(),
-            };
-            let err =
-                execute(deps.as_mut(), mock_env(), admin_info.clone(), remove_msg).unwrap_err();
-            assert_eq!(
-                err,
-                ContractError::Slasher(SlasherError::SlasherNotRegistered(contract2.clone()))
-            );
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 200), (USER2, 600)]);
 
-            // admin can add a second slasher, and it appears in the query
-            let add_msg2 = ExecuteMsg::AddSlasher {
-                addr: contract2.clone(),
-            };
-            execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg2).unwrap();
-            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
-            assert_eq!(slashers, vec![contract1.clone(), contract2.clone()]);
+            // Some additional claims
+            unbond(deps.as_mut(), 700, 0, 0, height_delta, 5);
+            unbond(deps.as_mut(), 0, 100, 50, height_delta, 6);
 
-            // cannot re-add an existing contract
-            let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), add_msg).unwrap_err();
-            assert_eq!(
-                err,
-                ContractError::Slasher(SlasherError::SlasherAlreadyRegistered(contract1.clone()))
-            );
+            // Claims are returned in batches
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
 
-            // non-admin cannot remove
-            let remove_msg = ExecuteMsg::RemoveSlasher { addr: contract1 };
-            let err =
-                execute(deps.as_mut(), mock_env(), user_info, remove_msg.clone()).unwrap_err();
-            assert_eq!(
-                err,
-                ContractError::Unauthorized(
-                    "Only slasher might remove himself and sender is not an admin".to_owned()
-                )
-            );
+            // offset 3 and 5
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER1, 700), (USER3, 300)]);
 
-            // remove the original
-            execute(deps.as_mut(), mock_env(), admin_info, remove_msg).unwrap();
-            let slashers = query_list_slashers(deps.as_ref(), env.clone()).unwrap();
-            assert_eq!(slashers, vec![contract2.clone()]);
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
 
-            // contract can self-remove
-            let contract_info = mock_info(&contract2, &[]);
-            let remove_msg2 = ExecuteMsg::RemoveSlasher { addr: contract2 };
-            execute(deps.as_mut(), mock_env(), contract_info, remove_msg2).unwrap();
-            let slashers = query_list_slashers(deps.as_ref(), env).unwrap();
-            assert_eq!(slashers, Vec::<String>::new());
+            // offset 6
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_sends(resp, vec![(USER2, 100), (USER3, 50)]);
         }
 
         #[test]
-        fn slashing_nonexisting_member() {
+        fn up_to_limit_claims_returned_vesting() {
             let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
+            do_instantiate(deps.as_mut(), 2);
 
-            // confirm address doesn't return true on slasher query
-            assert!(!query_is_slasher(deps.as_ref(), mock_env(), "slasher".to_owned()).unwrap());
+            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            let height_delta = 2;
 
-            let slasher = add_slasher(deps.as_mut());
-            assert!(query_is_slasher(deps.as_ref(), mock_env(), slasher.clone()).unwrap());
+            // Claims to be returned
+            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
+            unbond(deps.as_mut(), 0, 600, 0, height_delta, 1);
+            unbond(deps.as_mut(), 200, 0, 0, height_delta, 2);
+            unbond(deps.as_mut(), 0, 0, 300, height_delta, 3);
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+            // Even if all claims are already expired, only two of them (time offset 0) should be
+            // returned
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
 
-            bond_vesting(deps.as_mut(), 1_200, 750, 8_000, 2);
-            assert_stake_vesting(deps.as_ref(), 1_200, 750, 8_000);
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 1000), (USER2, 500)]);
 
-            // Trying to slash nonexisting user will result in no-op
-            let res = slash(deps.as_mut(), &slasher, "nonexisting", Decimal::percent(20)).unwrap();
-            assert_eq!(res, Response::new());
-        }
+            // Then on next block next batch is returned (time offset 1 and 2)
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 4);
 
-        #[test]
-        fn slashing_bonded_liquid_tokens_works() {
-            let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
-            let cfg = CONFIG.load(&deps.storage).unwrap();
-            let slasher = add_slasher(deps.as_mut());
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 200), (USER2, 600)]);
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+            // Some additional claims
+            unbond(deps.as_mut(), 700, 0, 0, height_delta, 5);
+            unbond(deps.as_mut(), 0, 100, 50, height_delta, 6);
 
-            // The slasher we added can slash
-            let res1 = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
-            let res2 = slash(deps.as_mut(), &slasher, USER3, Decimal::percent(50)).unwrap();
-            assert_stake_liquid(deps.as_ref(), 9_600, 7_500, 2_000);
+            // Claims are returned in batches
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
 
-            // Tokens are burned
-            assert_burned(res1, &coins(2_400, &cfg.denom), &[]);
-            assert_burned(res2, &coins(2_000, &cfg.denom), &[]);
+            // offset 3 and 5
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER1, 700), (USER3, 300)]);
+
+            let mut env = mock_env();
+            env.block.height += height_delta;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
+
+            // offset 6
+            let resp = end_block(deps.as_mut(), env).unwrap();
+            assert_undelegates(resp, vec![(USER2, 100), (USER3, 50)]);
         }
 
         #[test]
-        fn slashing_bonded_vesting_tokens_works() {
+        fn unbound_with_invalid_denom_fails_liquid() {
             let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
-            let cfg = CONFIG.load(&deps.storage).unwrap();
-            let slasher = add_slasher(deps.as_mut());
+            do_instantiate(deps.as_mut(), 2);
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            assert_stake_vesting(deps.as_ref(), 12_000, 7_500, 4_000);
+            bond_liquid(deps.as_mut(), 5_000, 0, 0, 1);
+            let height_delta = 2;
 
-            // The slasher we added can slash
-            let res1 = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
-            let res2 = slash(deps.as_mut(), &slasher, USER3, Decimal::percent(50)).unwrap();
-            assert_stake_vesting(deps.as_ref(), 9_600, 7_500, 2_000);
+            let mut env = mock_env();
+            env.block.height += height_delta;
 
-            // Tokens are burned
-            assert_burned(res1, &[], &coins(2_400, &cfg.denom));
-            assert_burned(res2, &[], &coins(2_000, &cfg.denom));
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(5_000, "invalid"),
+                tag: None,
+            };
+            let info = mock_info(USER1, &[]);
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+
+            assert_eq!(ContractError::InvalidDenom {}, err);
         }
 
         #[test]
-        fn slashing_bonded_mixed_tokens_works() {
+        fn unbound_with_invalid_denom_fails_vesting() {
             let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
-            let cfg = CONFIG.load(&deps.storage).unwrap();
-            let slasher = add_slasher(deps.as_mut());
+            do_instantiate(deps.as_mut(), 2);
 
-            bond_liquid(deps.as_mut(), 12_000, 1_500, 0, 1);
-            assert_stake_liquid(deps.as_ref(), 12_000, 1_500, 0);
-            bond_vesting(deps.as_mut(), 0, 6_000, 4_000, 1);
-            assert_stake_vesting(deps.as_ref(), 0, 6_000, 4_000);
+            bond_vesting(deps.as_mut(), 5_000, 0, 0, 1);
+            let height_delta = 2;
 
-            // The slasher we added can slash
-            let res1 = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
-            let res2 = slash(deps.as_mut(), &slasher, USER3, Decimal::percent(50)).unwrap();
-            let res3 = slash(deps.as_mut(), &slasher, USER2, Decimal::percent(10)).unwrap();
-            assert_stake_liquid(deps.as_ref(), 9_600, 1_350, 0);
-            assert_stake_vesting(deps.as_ref(), 0, 5_400, 2_000);
+            let mut env = mock_env();
+            env.block.height += height_delta;
 
-            // Tokens are burned
-            assert_burned(res1, &coins(2_400, &cfg.denom), &[]);
-            assert_burned(res2, &[], &coins(2_000, &cfg.denom));
-            assert_burned(res3, &coins(150, &cfg.denom), &coins(600, &cfg.denom));
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(5_000, "invalid"),
+                tag: None,
+            };
+            let info = mock_info(USER1, &[]);
+            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+
+            assert_eq!(ContractError::InvalidDenom {}, err);
         }
+    }
 
-        #[test]
-        fn slashing_stake_update_membership() {
-            let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
-            let slasher = add_slasher(deps.as_mut());
+    mod bond_caps {
+        use super::*;
 
-            // ensure it rounds down, and respects cut-off
-            bond(deps.as_mut(), (0, 12_000), (7_000, 0), (3_000, 4_000), 1);
-            assert_users(deps.as_ref(), Some(12), Some(7), Some(7), None);
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, per_address: u128, total: u128) {
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 0,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: Some(Uint128::new(per_address)),
+                max_total_bond: Some(Uint128::new(total)),
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
 
-            slash(deps.as_mut(), &slasher, USER1, Decimal::percent(50)).unwrap();
-            slash(deps.as_mut(), &slasher, USER2, Decimal::percent(10)).unwrap();
-            slash(deps.as_mut(), &slasher, USER3, Decimal::percent(20)).unwrap();
+        fn try_bond(
+            deps: DepsMut<TgradeQuery>,
+            addr: &str,
+            liquid: u128,
+            vesting: u128,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Bond {
+                vesting_tokens: (vesting != 0).then(|| coin(vesting, DENOM)),
+            };
+            execute(
+                deps,
+                mock_env(),
+                mock_info(addr, &coins(liquid, DENOM)),
+                msg,
+            )
+        }
 
-            // Assert updated points
-            assert_stake_liquid(deps.as_ref(), 0, 6_300, 2_400);
-            assert_stake_vesting(deps.as_ref(), 6_000, 0, 3_200);
-            assert_users(deps.as_ref(), Some(6), Some(6), Some(5), None);
+        fn query_headroom(deps: Deps<TgradeQuery>, addr: &str) -> BondHeadroomResponse {
+            let msg = QueryMsg::BondHeadroom {
+                address: addr.to_owned(),
+            };
+            from_slice(&query(deps, mock_env(), msg).unwrap()).unwrap()
         }
 
         #[test]
-        fn slashing_claims_works() {
+        fn bond_caps_are_enforced() {
             let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
-            let cfg = CONFIG.load(&deps.storage).unwrap();
-            let slasher = add_slasher(deps.as_mut());
+            do_instantiate(deps.as_mut(), 20_000, 50_000);
 
-            // create some data
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            bond_vesting(deps.as_mut(), 1_000, 750, 40, 1);
-            let height_delta = 2;
-            unbond(deps.as_mut(), 13_000, 2_600, 0, height_delta, 0);
-            let mut env = mock_env();
-            env.block.height += height_delta;
+            try_bond(deps.as_mut(), USER1, 15_000, 0).unwrap();
+            let err = try_bond(deps.as_mut(), USER1, 6_000, 0).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::AddressBondCapExceeded {
+                    headroom: Uint128::new(5_000)
+                }
+            );
+            // vesting stake counts towards the cap as well
+            let err = try_bond(deps.as_mut(), USER1, 0, 6_000).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::AddressBondCapExceeded {
+                    headroom: Uint128::new(5_000)
+                }
+            );
+            try_bond(deps.as_mut(), USER1, 0, 5_000).unwrap();
 
-            // check the claims for each user
-            let expires = Duration::new(UNBONDING_DURATION).after(&env.block);
             assert_eq!(
-                get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
-                vec![Claim::new(
-                    Addr::unchecked(USER1),
-                    12_000,
-                    1_000,
-                    expires,
-                    env.block.height,
-                )]
+                query_headroom(deps.as_ref(), USER1),
+                BondHeadroomResponse {
+                    address: Some(Uint128::zero()),
+                    total: Some(Uint128::new(30_000)),
+                    total_bonded: Uint128::new(20_000),
+                }
             );
 
-            let res = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20)).unwrap();
+            try_bond(deps.as_mut(), USER2, 20_000, 0).unwrap();
+            let err = try_bond(deps.as_mut(), USER3, 15_000, 0).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::TotalBondCapExceeded {
+                    headroom: Uint128::new(10_000)
+                }
+            );
 
+            // unbonded tokens free the headroom
+            unbond(deps.as_mut(), 0, 10_000, 0, 1, 0);
             assert_eq!(
-                get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None),
-                vec![Claim::new(
-                    Addr::unchecked(USER1),
-                    9_600,
-                    800,
-                    expires,
-                    env.block.height,
-                )]
+                query_headroom(deps.as_ref(), USER3),
+                BondHeadroomResponse {
+                    address: Some(Uint128::new(20_000)),
+                    total: Some(Uint128::new(20_000)),
+                    total_bonded: Uint128::new(30_000),
+                }
             );
-            assert_burned(res, &coins(2_400, &cfg.denom), &coins(200, &cfg.denom));
+            try_bond(deps.as_mut(), USER3, 15_000, 0).unwrap();
         }
 
         #[test]
-        fn random_user_cannot_slash() {
+        fn total_bonded_initialized_on_migration() {
             let mut deps = mock_deps_tgrade();
             default_instantiate(deps.as_mut());
-            let _slasher = add_slasher(deps.as_mut());
-
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
-
-            bond_vesting(deps.as_mut(), 1_200, 750, 8_000, 2);
-            assert_stake_vesting(deps.as_ref(), 1_200, 750, 8_000);
+            bond(deps.as_mut(), (12_000, 3_000), (7_500, 0), (0, 0), 1);
+
+            // older versions didn't track total bonded tokens
+            TOTAL_BONDED.remove(deps.as_mut().storage);
+
+            let msg = MigrateMsg {
+                tokens_per_point: None,
+                min_bond: None,
+                unbonding_period: None,
+                auto_return_limit: None,
+                unbond_veto_period: None,
+                max_bond_per_address: Some(Uint128::new(20_000)),
+                max_total_bond: Some(Uint128::new(30_000)),
+                undelegations: None,
+                average_stake_period: None,
+                unbonding_interest: None,
+                max_pause_duration: None,
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
 
-            let res = slash(deps.as_mut(), USER2, USER1, Decimal::percent(20));
             assert_eq!(
-                res,
-                Err(ContractError::Unauthorized(
-                    "Sender is not on slashers list".to_owned()
-                ))
+                query_headroom(deps.as_ref(), USER1),
+                BondHeadroomResponse {
+                    address: Some(Uint128::new(5_000)),
+                    total: Some(Uint128::new(7_500)),
+                    total_bonded: Uint128::new(22_500),
+                }
             );
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            // caps can be removed by setting them to 0
+            let msg = MigrateMsg {
+                tokens_per_point: None,
+                min_bond: None,
+                unbonding_period: None,
+                auto_return_limit: None,
+                unbond_veto_period: None,
+                max_bond_per_address: Some(Uint128::zero()),
+                max_total_bond: None,
+                undelegations: None,
+                average_stake_period: None,
+                unbonding_interest: None,
+                max_pause_duration: None,
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            let cfg = CONFIG.load(&deps.storage).unwrap();
+            assert_eq!(cfg.max_bond_per_address, None);
+            assert_eq!(cfg.max_total_bond, Some(Uint128::new(30_000)));
+        }
+    }
+
+    mod exit_fee {
+        use super::*;
+        use cw_controllers::AdminError;
+
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, exit_fee: Option<UnvalidatedExitFee>) {
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 0,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        fn try_unbond(deps: DepsMut<TgradeQuery>, addr: &str, amount: u128) -> Response {
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(amount, DENOM),
+                tag: None,
+            };
+            execute(deps, mock_env(), mock_info(addr, &[]), msg).unwrap()
         }
 
         #[test]
-        fn admin_cannot_slash() {
+        fn exit_fee_is_burned_from_liquid_tokens() {
             let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
-            let _slasher = add_slasher(deps.as_mut());
-
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+            do_instantiate(
+                deps.as_mut(),
+                Some(UnvalidatedExitFee {
+                    portion: Decimal::percent(10),
+                    distribution_contract: None,
+                }),
+            );
+            bond(deps.as_mut(), (12_345, 0), (5_000, 5_000), (0, 0), 1);
 
-            bond_vesting(deps.as_mut(), 1_200, 750, 8_000, 2);
-            assert_stake_vesting(deps.as_ref(), 1_200, 750, 8_000);
+            // 10% of 10_001 is rounded down to 1_000
+            let res = try_unbond(deps.as_mut(), USER1, 10_001);
+            assert_eq!(
+                res.messages,
+                [SubMsg::new(BankMsg::Burn {
+                    amount: coins(1_000, DENOM)
+                })]
+            );
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims[0].amount, Uint128::new(9_001));
+            assert_stake_liquid(deps.as_ref(), 2_344, 5_000, 0);
 
-            let res = slash(deps.as_mut(), INIT_ADMIN, USER1, Decimal::percent(20));
+            // vesting tokens are not subject to the fee
+            let res = try_unbond(deps.as_mut(), USER2, 8_000);
             assert_eq!(
-                res,
-                Err(ContractError::Unauthorized(
-                    "Sender is not on slashers list".to_owned()
-                ))
+                res.messages,
+                [SubMsg::new(BankMsg::Burn {
+                    amount: coins(500, DENOM)
+                })]
             );
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER2), None, None);
+            assert_eq!(claims[0].amount, Uint128::new(4_500));
+            assert_eq!(claims[0].vesting_amount, Some(Uint128::new(3_000)));
         }
 
         #[test]
-        fn removed_slasher_cannot_slash() {
+        fn exit_fee_is_set_by_admin() {
             let mut deps = mock_deps_tgrade();
-            default_instantiate(deps.as_mut());
+            do_instantiate(deps.as_mut(), None);
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
 
-            // Add, then remove a slasher
-            let slasher = add_slasher(deps.as_mut());
-            remove_slasher(deps.as_mut(), &slasher);
+            let exit_fee = UnvalidatedExitFee {
+                portion: Decimal::percent(5),
+                distribution_contract: Some("engagement".to_owned()),
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::SetExitFee {
+                    exit_fee: Some(exit_fee.clone()),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::SetExitFee {
+                    exit_fee: Some(UnvalidatedExitFee {
+                        portion: Decimal::one(),
+                        distribution_contract: None,
+                    }),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::InvalidExitFee {});
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 8_000, 2);
-            assert_stake_vesting(deps.as_ref(), 12_000, 7_500, 8_000);
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::SetExitFee {
+                    exit_fee: Some(exit_fee),
+                },
+            )
+            .unwrap();
 
-            let res = slash(deps.as_mut(), &slasher, USER1, Decimal::percent(20));
+            let res = try_unbond(deps.as_mut(), USER1, 4_000);
             assert_eq!(
-                res,
-                Err(ContractError::Unauthorized(
-                    "Sender is not on slashers list".to_owned()
-                ))
+                res.messages,
+                [SubMsg::new(WasmMsg::Execute {
+                    contract_addr: "engagement".to_owned(),
+                    msg: to_binary(&DistributionMsg::DistributeRewards {}).unwrap(),
+                    funds: coins(200, DENOM),
+                })]
             );
-            assert_stake_liquid(deps.as_ref(), 12_000, 7_500, 4_000);
+
+            // removed fee is not taken anymore
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::SetExitFee { exit_fee: None },
+            )
+            .unwrap();
+            let res = try_unbond(deps.as_mut(), USER1, 1_000);
+            assert_eq!(res.messages, []);
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            let claimed: Uint128 = claims.iter().map(|claim| claim.amount).sum();
+            assert_eq!(claimed, Uint128::new(4_800));
         }
     }
 
-    #[test]
-    fn hooks_fire() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+    mod denom {
+        use super::*;
+        use cosmwasm_std::testing::MockApi;
+        use cw_controllers::AdminError;
 
-        let hooks = HOOKS.list_hooks(&deps.storage).unwrap();
-        assert!(hooks.is_empty());
+        const IBC_HASH: &str = "27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2";
 
-        let contract1 = String::from("hook1");
-        let contract2 = String::from("hook2");
+        fn do_instantiate(
+            deps: DepsMut<TgradeQuery>,
+            denom: &str,
+        ) -> Result<Response, ContractError> {
+            let msg = InstantiateMsg {
+                denom: denom.to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 0,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg)
+        }
 
-        // register 2 hooks
-        let admin_info = mock_info(INIT_ADMIN, &[]);
-        let add_msg = ExecuteMsg::AddHook {
-            addr: contract1.clone(),
-        };
-        let add_msg2 = ExecuteMsg::AddHook {
-            addr: contract2.clone(),
-        };
-        for msg in vec![add_msg, add_msg2] {
-            let _ = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+        fn query_denom(deps: Deps<TgradeQuery>) -> DenomResponse {
+            from_slice(&query(deps, mock_env(), QueryMsg::Denom {}).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn denoms_are_normalized() {
+            let api = MockApi::default();
+            assert_eq!(validate_denom(&api, "utgd").unwrap(), "utgd");
+            assert_eq!(
+                validate_denom(&api, &format!("IBC/{}", IBC_HASH)).unwrap(),
+                format!("ibc/{}", IBC_HASH.to_ascii_uppercase())
+            );
+            assert_eq!(
+                validate_denom(&api, "factory/Creator/ustake").unwrap(),
+                "factory/creator/ustake"
+            );
+
+            let invalid = [
+                "ab",
+                "1stake",
+                "st ake",
+                "ibc/27394fb092",
+                "ibc/zz394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2",
+                "factory/creator",
+                "factory/creator/",
+            ];
+            for denom in invalid {
+                assert_eq!(
+                    validate_denom(&api, denom).unwrap_err(),
+                    ContractError::InvalidStakingDenom(denom.to_owned())
+                );
+            }
         }
 
-        // check firing on bond
-        assert_users(deps.as_ref(), None, None, None, None);
-        let info = mock_info(USER1, &coins(13_800, DENOM));
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            info,
-            ExecuteMsg::Bond {
-                vesting_tokens: None,
-            },
-        )
-        .unwrap();
-        assert_users(deps.as_ref(), Some(13), None, None, None);
+        #[test]
+        fn ibc_denom_is_bonded() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), &format!("ibc/{}", IBC_HASH)).unwrap();
 
-        // ensure messages for each of the 2 hooks
-        assert_eq!(res.messages.len(), 2);
-        let diff = MemberDiff::new(USER1, None, Some(13));
-        let hook_msg = MemberChangedHookMsg::one(diff);
-        let msg1 = hook_msg
-            .clone()
-            .into_cosmos_msg(contract1.clone())
-            .map(SubMsg::new)
-            .unwrap();
-        let msg2 = hook_msg
-            .into_cosmos_msg(contract2.clone())
-            .map(SubMsg::new)
+            let denom = query_denom(deps.as_ref()).denom;
+            assert_eq!(denom, format!("ibc/{}", IBC_HASH.to_ascii_uppercase()));
+
+            // funds come with the canonical denom
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &coins(10_000, &denom)),
+                ExecuteMsg::Bond {
+                    vesting_tokens: None,
+                },
+            )
             .unwrap();
-        assert_eq!(res.messages, vec![msg1, msg2]);
+            assert_eq!(
+                query_staked(deps.as_ref(), USER1.into()).unwrap().liquid,
+                coin(10_000, denom)
+            );
 
-        // check firing on unbond
-        let msg = ExecuteMsg::Unbond {
-            tokens: coin(7_300, DENOM),
-        };
-        let info = mock_info(USER1, &[]);
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_users(deps.as_ref(), Some(6), None, None, None);
+            let err = do_instantiate(mock_deps_tgrade().as_mut(), "ibc/").unwrap_err();
+            assert_eq!(err, ContractError::InvalidStakingDenom("ibc/".to_owned()));
+        }
 
-        // ensure messages for each of the 2 hooks
-        assert_eq!(res.messages.len(), 2);
-        let diff = MemberDiff::new(USER1, Some(13), Some(6));
-        let hook_msg = MemberChangedHookMsg::one(diff);
-        let msg1 = hook_msg
-            .clone()
-            .into_cosmos_msg(contract1)
-            .map(SubMsg::new)
+        #[test]
+        fn metadata_is_set_by_admin() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), DENOM).unwrap();
+            assert_eq!(
+                query_denom(deps.as_ref()),
+                DenomResponse {
+                    denom: DENOM.to_owned(),
+                    metadata: None
+                }
+            );
+
+            let metadata = DenomMetadata {
+                display: "STAKE".to_owned(),
+                exponent: 6,
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::SetDenomMetadata {
+                    metadata: Some(metadata.clone()),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::SetDenomMetadata {
+                    metadata: Some(DenomMetadata {
+                        display: "STAKE".to_owned(),
+                        exponent: 19,
+                    }),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::InvalidDenomMetadata(MAX_DENOM_EXPONENT));
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::SetDenomMetadata {
+                    metadata: Some(metadata.clone()),
+                },
+            )
             .unwrap();
-        let msg2 = hook_msg
-            .into_cosmos_msg(contract2)
-            .map(SubMsg::new)
+            assert_eq!(query_denom(deps.as_ref()).metadata, Some(metadata));
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::SetDenomMetadata { metadata: None },
+            )
             .unwrap();
-        assert_eq!(res.messages, vec![msg1, msg2]);
+            assert_eq!(query_denom(deps.as_ref()).metadata, None);
+        }
     }
 
-    #[test]
-    fn only_bond_valid_coins() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+    mod claim_tags {
+        use super::*;
+        use crate::state::LEGACY_CLAIMS_KEY;
+        use cosmwasm_std::attr;
+        use cw_storage_plus::Map;
 
-        // cannot bond with 0 coins
-        let info = mock_info(USER1, &[]);
-        let err = execute(
-            deps.as_mut(),
-            mock_env(),
-            info,
-            ExecuteMsg::Bond {
-                vesting_tokens: None,
-            },
-        )
-        .unwrap_err();
-        assert_eq!(err, ContractError::NoFunds {});
+        fn try_unbond(
+            deps: DepsMut<TgradeQuery>,
+            amount: u128,
+            tag: Option<&str>,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(amount, DENOM),
+                tag: tag.map(str::to_owned),
+            };
+            execute(deps, mock_env(), mock_info(USER1, &[]), msg)
+        }
 
-        // cannot bond with incorrect denom
-        let info = mock_info(USER1, &[coin(500, "FOO")]);
-        let err = execute(
-            deps.as_mut(),
-            mock_env(),
-            info,
-            ExecuteMsg::Bond {
-                vesting_tokens: None,
-            },
-        )
-        .unwrap_err();
-        assert_eq!(err, ContractError::MissingDenom(DENOM.to_string()));
+        fn tagged_claims(deps: Deps<TgradeQuery>, tag: &str) -> Vec<Claim> {
+            claims()
+                .query_claims(
+                    deps,
+                    Addr::unchecked(USER1),
+                    None,
+                    None,
+                    Some(tag.to_owned()),
+                )
+                .unwrap()
+        }
 
-        // cannot bond with 2 coins (even if one is correct)
-        let info = mock_info(USER1, &[coin(1234, DENOM), coin(5000, "BAR")]);
-        let err = execute(
-            deps.as_mut(),
-            mock_env(),
-            info,
-            ExecuteMsg::Bond {
-                vesting_tokens: None,
-            },
-        )
-        .unwrap_err();
-        assert_eq!(err, ContractError::ExtraDenoms(DENOM.to_string()));
+        #[test]
+        fn claims_with_different_tags_are_not_merged() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
 
-        // can bond with just the proper denom
-        // cannot bond with incorrect denom
-        let info = mock_info(USER1, &[coin(500, DENOM)]);
-        execute(
-            deps.as_mut(),
-            mock_env(),
-            info,
-            ExecuteMsg::Bond {
-                vesting_tokens: None,
-            },
-        )
-        .unwrap();
-    }
+            try_unbond(deps.as_mut(), 1_000, None).unwrap();
+            try_unbond(deps.as_mut(), 2_000, Some("forced-exit")).unwrap();
+            let res = try_unbond(deps.as_mut(), 3_000, Some("forced-exit")).unwrap();
+            assert!(res.attributes.contains(&attr("claim_tag", "forced-exit")));
 
-    #[test]
-    fn ensure_bonding_edge_cases_liquid() {
-        // use min_bond 0, tokens_per_points 100
-        let mut deps = mock_deps_tgrade();
-        do_instantiate(deps.as_mut(), Uint128::new(100), Uint128::zero(), 5, 0);
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims.len(), 2);
+            assert_eq!(claims[0].tag, None);
+            assert_eq!(claims[0].amount, Uint128::new(1_000));
+            assert_eq!(claims[1].tag, Some("forced-exit".to_owned()));
+            assert_eq!(claims[1].amount, Uint128::new(5_000));
 
-        // setting 50 tokens, gives us Some(0) points
-        // even setting to 1 token
-        bond_liquid(deps.as_mut(), 50, 1, 102, 1);
-        assert_users(deps.as_ref(), Some(0), Some(0), Some(1), None);
+            assert_eq!(tagged_claims(deps.as_ref(), "forced-exit"), claims[1..]);
+            assert_eq!(tagged_claims(deps.as_ref(), "unbond"), []);
 
-        // reducing to 0 token makes us None even with min_bond 0
-        unbond(deps.as_mut(), 49, 1, 102, 2, 0);
-        assert_users(deps.as_ref(), Some(0), None, None, None);
-    }
+            // all claims are released together
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            let res = execute(
+                deps.as_mut(),
+                env,
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                [SubMsg::new(BankMsg::Send {
+                    to_address: USER1.into(),
+                    amount: coins(6_000, DENOM),
+                })]
+            );
+            assert!(get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None).is_empty());
+        }
 
-    #[test]
-    fn ensure_bonding_edge_cases_vesting() {
-        // use min_bond 0, tokens_per_points 100
-        let mut deps = mock_deps_tgrade();
-        do_instantiate(deps.as_mut(), Uint128::new(100), Uint128::zero(), 5, 0);
+        #[test]
+        fn invalid_tags_are_rejected() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
 
-        // setting 50 tokens, gives us Some(0) points
-        // even setting to 1 token
-        bond_vesting(deps.as_mut(), 50, 1, 102, 1);
-        assert_users(deps.as_ref(), Some(0), Some(0), Some(1), None);
+            let err = try_unbond(deps.as_mut(), 1_000, Some("")).unwrap_err();
+            assert_eq!(err, ContractError::InvalidClaimTag(MAX_CLAIM_TAG_LENGTH));
+            let tag = "x".repeat(MAX_CLAIM_TAG_LENGTH + 1);
+            let err = try_unbond(deps.as_mut(), 1_000, Some(&tag)).unwrap_err();
+            assert_eq!(err, ContractError::InvalidClaimTag(MAX_CLAIM_TAG_LENGTH));
+        }
 
-        // reducing to 0 token makes us None even with min_bond 0
-        unbond(deps.as_mut(), 49, 1, 102, 2, 0);
-        assert_users(deps.as_ref(), Some(0), None, None, None);
-    }
+        #[test]
+        fn legacy_claims_are_migrated_lazily() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 5_000, 0, 0, 1);
 
-    #[test]
-    fn ensure_bonding_edge_cases_mixed() {
-        // use min_bond 0, tokens_per_points 100
-        let mut deps = mock_deps_tgrade();
-        do_instantiate(deps.as_mut(), Uint128::new(100), Uint128::zero(), 5, 0);
+            // older versions stored claims by `(addr, release_at)`
+            let legacy: Map<(&Addr, u64), Claim> = Map::new(LEGACY_CLAIMS_KEY);
+            let user1 = Addr::unchecked(USER1);
+            let user2 = Addr::unchecked(USER2);
+            let release_at = Duration::new(UNBONDING_DURATION).after(&mock_env().block);
+            let claim1 = Claim::new(user1.clone(), 1_000, 0, release_at, 12_345);
+            let claim2 = Claim::new(user2.clone(), 2_000, 0, release_at, 12_345);
+            for claim in [&claim1, &claim2] {
+                legacy
+                    .save(
+                        deps.as_mut().storage,
+                        (&claim.addr, release_at.as_key()),
+                        claim,
+                    )
+                    .unwrap();
+            }
 
-        // setting 25 liquid tokens, gives us Some(0) points
-        // even setting to 1 token
-        bond_liquid(deps.as_mut(), 25, 1, 102, 1);
-        assert_users(deps.as_ref(), Some(0), Some(0), Some(1), None);
+            let msg = MigrateMsg {
+                tokens_per_point: None,
+                min_bond: None,
+                unbonding_period: None,
+                auto_return_limit: None,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                undelegations: None,
+                average_stake_period: None,
+                unbonding_interest: None,
+                max_pause_duration: None,
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
 
-        // setting other 25 vesting tokens, still gives us Some(0) points
-        // also setting to plus 1 token
-        bond_vesting(deps.as_mut(), 25, 1, 102, 2);
-        assert_users(deps.as_ref(), Some(0), Some(0), Some(2), None);
+            // claims are not moved on migration, but are still returned
+            assert!(!legacy.is_empty(&deps.storage));
+            assert_eq!(
+                get_claims(deps.as_ref(), user1.clone(), None, None),
+                std::slice::from_ref(&claim1)
+            );
+            assert_eq!(
+                get_claims(deps.as_ref(), user2.clone(), None, None),
+                std::slice::from_ref(&claim2)
+            );
 
-        // reducing to 0 token makes us None even with min_bond 0
-        unbond(deps.as_mut(), 49, 2, 204, 3, 0);
-        assert_users(deps.as_ref(), Some(0), None, None, None);
+            // claims of an address are moved once it unbonds, and merged with the new claim
+            try_unbond(deps.as_mut(), 500, None).unwrap();
+            assert_eq!(
+                get_claims(deps.as_ref(), user1.clone(), None, None),
+                [Claim {
+                    amount: Uint128::new(1_500),
+                    ..claim1
+                }]
+            );
+            assert!(!legacy.has(&deps.storage, (&user1, release_at.as_key())));
+            assert!(legacy.has(&deps.storage, (&user2, release_at.as_key())));
+
+            // remaining claims are moved in batches
+            let msg = ExecuteMsg::MigrateClaimBuckets { limit: Some(1) };
+            execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+            assert!(legacy.is_empty(&deps.storage));
+            assert_eq!(
+                get_claims(deps.as_ref(), user2.clone(), None, None),
+                [claim2]
+            );
+            assert_eq!(claims().count(&deps.storage).unwrap(), 2);
+        }
     }
 
-    #[test]
-    fn paginated_claim_query() {
-        let mut deps = mock_deps_tgrade();
-        default_instantiate(deps.as_mut());
+    mod force_unbond {
+        use super::*;
+        use cosmwasm_std::Event;
+        use cw_controllers::AdminError;
 
-        // create some data
-        let mut env = mock_env();
-        let msg = ExecuteMsg::Bond {
-            vesting_tokens: None,
-        };
-        let info = mock_info(USER1, &coins(500, DENOM));
-        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        const CONTROLLER: &str = "trusted_circle";
 
-        let info = mock_info(USER1, &[]);
-        for _ in 0..10 {
-            env.block.time = env.block.time.plus_seconds(10);
-            let msg = ExecuteMsg::Unbond {
-                tokens: coin(10, DENOM),
+        fn add_controller(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::AddUnbondController {
+                addr: CONTROLLER.to_owned(),
             };
-            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
         }
 
-        // check is number of claims is properly limited
-        let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), Some(6), None);
-        assert_eq!(claims.len(), 6);
-        // check if rest is equal to remainder
-        let next = get_claims(
-            deps.as_ref(),
-            Addr::unchecked(USER1),
-            None,
-            Some(claims[5].release_at),
-        );
-        assert_eq!(next.len(), 4);
-
-        // check if joining and sorting both vectors equal number from start
-        let mut all_claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
-        all_claims.sort_by_key(|claim| claim.addr.clone());
+        fn force_unbond(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            addr: &str,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::ForceUnbond {
+                addr: addr.to_owned(),
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
 
-        let mut concatenated = [claims, next].concat();
-        concatenated.sort_by_key(|claim| claim.addr.clone());
-        assert_eq!(concatenated, all_claims);
-    }
+        fn query_controllers(deps: Deps<TgradeQuery>) -> Vec<String> {
+            let raw = query(deps, mock_env(), QueryMsg::ListUnbondControllers {}).unwrap();
+            let res: UnbondControllersResponse = from_slice(&raw).unwrap();
+            res.controllers
+        }
 
-    mod auto_release_claims {
-        // Because of tests framework limitations at the point of implementing this test, it is
-        // difficult to actually test reaction for tgrade sudo messages. Instead to check the
-        // auto-release functionality, there are assumptions made:
-        // * Registration to sudo events is correct
-        // * Auto-releasing claims occurs on sudo EndBlock message, and the message is purely
-        // calling the `end_block` function - calling this function in test is simulating actual
-        // end block
+        #[test]
+        fn only_admin_manages_controllers() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
 
-        use cosmwasm_std::CosmosMsg;
+            let err = add_controller(deps.as_mut(), USER1).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
 
-        use super::*;
+            add_controller(deps.as_mut(), INIT_ADMIN).unwrap();
+            assert_eq!(query_controllers(deps.as_ref()), vec![CONTROLLER]);
 
-        fn do_instantiate(deps: DepsMut<TgradeQuery>, limit: u64) {
-            super::do_instantiate(deps, TOKENS_PER_POINT, MIN_BOND, UNBONDING_DURATION, limit)
+            let msg = ExecuteMsg::RemoveUnbondController {
+                addr: CONTROLLER.to_owned(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+            assert!(query_controllers(deps.as_ref()).is_empty());
         }
 
-        /// Helper for asserting if expected transfers occurred in response. Panics if any non
-        /// `BankMsg::Send` occurred, or transfers are different than expected.
-        ///
-        /// Transfers are passed in form of pairs `(addr, amount)`, as for all test in this module
-        /// expected denom is fixed
-        #[track_caller]
-        fn assert_sends(response: Response, mut expected_transfers: Vec<(&str, u128)>) {
-            let mut sends: Vec<_> = response
-                .messages
-                .into_iter()
-                .map(|msg| match msg.msg {
-                    // Trick is used here - bank send messages are filtered out, and mapped to triple
-                    // `(addr, amount_sum, msg)` - `addr` and `amount_sum` would be used only to
-                    // properly sort messages, then they would be discarded. As in expected messages
-                    // always only one coin is expected for all send messages, taking sum for sorting
-                    // is good enough - in case of multiple of invalid denoms it would be visible on
-                    // comparison.
-                    //
-                    // Possibly in future it would be possible for another messages to occur - in such
-                    // case instead of returning err and panicking from this function, such messages
-                    // should be filtered out.
-                    CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => Ok((
-                        to_address.clone(),
-                        amount.iter().map(|c| c.amount).sum::<Uint128>(),
-                        BankMsg::Send { to_address, amount },
-                    )),
-                    msg => Err(format!(
-                        "Unexpected message on response, expected only bank send messages: {:?}",
-                        msg
-                    )),
-                })
-                .collect::<Result<_, _>>()
-                .unwrap();
+        #[test]
+        fn whole_stake_is_unbonded() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            bond(deps.as_mut(), (10_000, 3_000), (6_000, 0), (0, 0), 1);
+            add_controller(deps.as_mut(), INIT_ADMIN).unwrap();
 
-            sends.sort_by_key(|(addr, amount_sum, _)| (addr.clone(), *amount_sum));
-            // Drop  addr and amount_sum for comparison
-            let sends: Vec<_> = sends.into_iter().map(|(_, _, msg)| msg).collect();
+            let err = force_unbond(deps.as_mut(), USER2, USER1).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Unauthorized("Sender is not an unbond controller".to_owned())
+            );
 
-            // Tuples are sorted simply first by addresses, then by amount
-            expected_transfers.sort_unstable();
+            let res = force_unbond(deps.as_mut(), CONTROLLER, USER1).unwrap();
+            let completion = Duration::new(UNBONDING_DURATION).after(&mock_env().block);
+            assert_eq!(
+                res.events,
+                [Event::new("force_unbond")
+                    .add_attribute("member", USER1)
+                    .add_attribute("controller", CONTROLLER)
+                    .add_attribute("liquid_amount", "10000")
+                    .add_attribute("vesting_amount", "3000")
+                    .add_attribute("completion_time", completion.time().nanos().to_string())]
+            );
 
-            // Build messages for comparison
-            let expected_transfers: Vec<_> = expected_transfers
-                .into_iter()
-                .map(|(addr, amount)| BankMsg::Send {
-                    to_address: addr.to_owned(),
-                    amount: coins(amount, DENOM),
-                })
-                .collect();
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), None);
+            assert_eq!(get_member(deps.as_ref(), USER2.into(), None), Some(6));
+            assert_stake_liquid(deps.as_ref(), 0, 6_000, 0);
+            assert_stake_vesting(deps.as_ref(), 0, 0, 0);
+
+            // no fee is taken and the standard unbonding period applies
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims.len(), 1);
+            assert_eq!(claims[0].amount, Uint128::new(10_000));
+            assert_eq!(claims[0].vesting_amount, Some(Uint128::new(3_000)));
+            assert_eq!(claims[0].release_at, completion);
+            assert_eq!(claims[0].veto_until, None);
+            assert_eq!(claims[0].tag, Some(FORCE_UNBOND_CLAIM_TAG.to_owned()));
+
+            let err = force_unbond(deps.as_mut(), CONTROLLER, USER1).unwrap_err();
+            assert_eq!(err, ContractError::NothingStaked(USER1.to_owned()));
+        }
 
-            assert_eq!(sends, expected_transfers);
+        #[test]
+        fn locked_stake_is_unbonded() {
+            let mut deps = mock_deps_tgrade();
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 1,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![LockupTier {
+                    period: Duration::new(1_000),
+                    multiplier: Decimal::percent(200),
+                }],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
+            let msg = ExecuteMsg::Lock {
+                tokens: coin(4_000, DENOM),
+                period: 1_000,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(USER1, &[]), msg).unwrap();
+            add_controller(deps.as_mut(), INIT_ADMIN).unwrap();
+
+            force_unbond(deps.as_mut(), CONTROLLER, USER1).unwrap();
+
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), None);
+            let lockups = query_lockups(deps.as_ref(), USER1.to_owned()).unwrap();
+            assert!(lockups.lockups.is_empty());
+            assert!(LOCKUP_EXPIRATIONS.is_empty(&deps.storage));
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims[0].amount, Uint128::new(10_000));
         }
+    }
 
-        /// Helper for asserting if expected undelegates occurred in response. Panics if any non
-        /// `TgradeMsg::Undelegate` occurred, or undelegates are different than expected.
-        ///
-        /// Transfers are passed in form of pairs `(addr, amount)`, as for all tests in this module
-        /// the expected denom is fixed.
-        #[track_caller]
-        fn assert_undelegates(response: Response, mut expected_transfers: Vec<(&str, u128)>) {
-            let mut undelegates: Vec<_> = response
-                .messages
-                .into_iter()
-                .map(|msg| match msg.msg {
-                    // Trick is used here - undelegate messages are filtered out, and mapped to a triple
-                    // `(addr, amount, msg)` - `addr` and `amount` will be used only to properly
-                    // sort messages, then they will be discarded. As in the expected messages
-                    // always only one coin is expected for all messages, taking the amount for sorting
-                    // is good enough - in the case of multiple or invalid denoms, it'll error on
-                    // comparison.
-                    //
-                    // Possibly in the future it would be possible for another messages to occur -
-                    // in such case instead of returning err and panicking from this function, such
-                    // messages should be filtered out.
-                    CosmosMsg::Custom(TgradeMsg::Undelegate { funds, recipient }) => Ok((
-                        recipient.clone(),
-                        funds.amount,
-                        TgradeMsg::Undelegate { recipient, funds },
-                    )),
-                    msg => Err(format!(
-                        "Unexpected message on response, expected only tgrade undelegate messages: {:?}",
-                        msg
-                    )),
-                })
-                .collect::<Result<_, _>>()
-                .unwrap();
+    mod payout_address {
+        use super::*;
 
-            undelegates.sort_by_key(|(addr, amount, _)| (addr.clone(), *amount));
-            // Drop  addr and amount for comparison
-            let undelegates: Vec<_> = undelegates.into_iter().map(|(_, _, msg)| msg).collect();
+        const TREASURY: &str = "treasury";
 
-            // Tuples are sorted simply first by addresses, then by amounts
-            expected_transfers.sort_unstable();
+        fn set_payout_address(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            addr: Option<&str>,
+            require_confirmation: bool,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::SetPayoutAddress {
+                addr: addr.map(str::to_owned),
+                require_confirmation,
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
 
-            // Build messages for comparison
-            let expected_transfers: Vec<_> = expected_transfers
-                .into_iter()
-                .map(|(addr, amount)| TgradeMsg::Undelegate {
-                    funds: coin(amount, DENOM),
-                    recipient: addr.to_owned(),
-                })
-                .collect();
+        fn confirm_payout_address(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            staker: &str,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::ConfirmPayoutAddress {
+                staker: staker.to_owned(),
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
 
-            assert_eq!(undelegates, expected_transfers);
+        fn query_payout_address(deps: Deps<TgradeQuery>, staker: &str) -> PayoutAddressResponse {
+            let msg = QueryMsg::PayoutAddress {
+                staker: staker.to_owned(),
+            };
+            from_slice(&query(deps, mock_env(), msg).unwrap()).unwrap()
         }
 
-        /// Helper for asserting if expected sends and undelegates occurred in response. Panics if
-        /// any non `Bank::Send` or `TgradeMsg::Undelegate` occurred, or send / undelegates are
-        /// different than expected.
-        ///
-        /// Transfers are passed in form of pairs `(addr, amount)`, as for all tests in this module
-        /// the expected denom is fixed.
-        #[track_caller]
-        fn assert_sends_undelegates(
-            response: Response,
-            expected_sends: Vec<(&str, u128)>,
-            expected_undelegates: Vec<(&str, u128)>,
-        ) {
-            let sends = response
-                .clone()
-                .messages
-                .into_iter()
-                .filter(|msg| matches!(msg.msg, CosmosMsg::Bank(BankMsg::Send { .. })));
-            let undelegates =
-                response.clone().messages.into_iter().filter(|msg| {
-                    matches!(msg.msg, CosmosMsg::Custom(TgradeMsg::Undelegate { .. }))
-                });
+        fn matured_env() -> Env {
+            let mut env = mock_env();
+            env.block.height += 2;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            env
+        }
 
-            assert_sends(
-                Response::new().add_submessages(sends.clone()),
-                expected_sends,
+        #[test]
+        fn claims_paid_to_payout_address() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+
+            bond(deps.as_mut(), (1_000, 6_000), (7_500, 0), (4_000, 0), 1);
+            set_payout_address(deps.as_mut(), USER1, Some(TREASURY), false).unwrap();
+            assert_eq!(
+                query_payout_address(deps.as_ref(), USER1),
+                PayoutAddressResponse {
+                    payout_address: Some(TREASURY.to_owned()),
+                    pending: None,
+                }
             );
-            assert_undelegates(
-                Response::new().add_submessages(undelegates.clone()),
-                expected_undelegates,
+
+            unbond(deps.as_mut(), 2_000, 0, 0, 2, 0);
+            let res = execute(
+                deps.as_mut(),
+                matured_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            // Only liquid tokens go to the payout address, vesting ones return to the staker
+            assert_eq!(
+                res.messages,
+                vec![
+                    SubMsg::new(BankMsg::Send {
+                        to_address: TREASURY.to_owned(),
+                        amount: coins(1_000, DENOM),
+                    }),
+                    SubMsg::new(TgradeMsg::Undelegate {
+                        funds: coin(1_000, DENOM),
+                        recipient: USER1.to_owned(),
+                    }),
+                ]
             );
 
-            // Assert there are no other message types
+            // Resetting the address pays out to the staker again
+            set_payout_address(deps.as_mut(), USER1, None, false).unwrap();
             assert_eq!(
-                response.messages.len(),
-                sends.count() + undelegates.count(),
-                "Unexpected messages in response, expected only bank send or tgrade undelegate messages"
+                query_payout_address(deps.as_ref(), USER1).payout_address,
+                None
+            );
+            bond_liquid(deps.as_mut(), 1_000, 0, 0, 2);
+            unbond(deps.as_mut(), 1_000, 0, 0, 2, 0);
+            let res = execute(
+                deps.as_mut(),
+                matured_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                vec![SubMsg::new(BankMsg::Send {
+                    to_address: USER1.to_owned(),
+                    amount: coins(1_000, DENOM),
+                })]
             );
         }
 
         #[test]
-        fn single_claim_liquid() {
+        fn auto_returned_claims_paid_to_payout_address() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+            do_instantiate(
+                deps.as_mut(),
+                TOKENS_PER_POINT,
+                MIN_BOND,
+                UNBONDING_DURATION,
+                2,
+            );
 
             bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
-
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            set_payout_address(deps.as_mut(), USER1, Some(TREASURY), false).unwrap();
+            unbond(deps.as_mut(), 1_000, 500, 0, 2, 0);
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 1000)]);
+            let res = end_block(deps.as_mut(), matured_env()).unwrap();
+            assert_eq!(
+                res.messages,
+                vec![
+                    SubMsg::new(BankMsg::Send {
+                        to_address: TREASURY.to_owned(),
+                        amount: coins(1_000, DENOM),
+                    }),
+                    SubMsg::new(BankMsg::Send {
+                        to_address: USER2.to_owned(),
+                        amount: coins(500, DENOM),
+                    }),
+                ]
+            );
         }
 
         #[test]
-        fn single_claim_vesting() {
+        fn confirmation_required() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+            default_instantiate(deps.as_mut());
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
+            set_payout_address(deps.as_mut(), USER1, Some(TREASURY), true).unwrap();
+            assert_eq!(
+                query_payout_address(deps.as_ref(), USER1),
+                PayoutAddressResponse {
+                    payout_address: None,
+                    pending: Some(TREASURY.to_owned()),
+                }
+            );
 
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            // Not confirmed yet, so the claim is paid to the staker
+            unbond(deps.as_mut(), 1_000, 0, 0, 2, 0);
+            let res = execute(
+                deps.as_mut(),
+                matured_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                vec![SubMsg::new(BankMsg::Send {
+                    to_address: USER1.to_owned(),
+                    amount: coins(1_000, DENOM),
+                })]
+            );
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 1000)]);
+            // Only the pending address can confirm
+            let err = confirm_payout_address(deps.as_mut(), USER2, USER1).unwrap_err();
+            assert_eq!(err, ContractError::NoPendingPayoutAddress(USER1.to_owned()));
+            let err = confirm_payout_address(deps.as_mut(), TREASURY, USER2).unwrap_err();
+            assert_eq!(err, ContractError::NoPendingPayoutAddress(USER2.to_owned()));
+
+            confirm_payout_address(deps.as_mut(), TREASURY, USER1).unwrap();
+            assert_eq!(
+                query_payout_address(deps.as_ref(), USER1),
+                PayoutAddressResponse {
+                    payout_address: Some(TREASURY.to_owned()),
+                    pending: None,
+                }
+            );
+            let err = confirm_payout_address(deps.as_mut(), TREASURY, USER1).unwrap_err();
+            assert_eq!(err, ContractError::NoPendingPayoutAddress(USER1.to_owned()));
+        }
+    }
+
+    mod lockups {
+        use super::*;
+        use crate::msg::LockupResponse;
+
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, lockup_tiers: Vec<LockupTier>) {
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 1,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers,
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        fn tiers() -> Vec<LockupTier> {
+            vec![
+                LockupTier {
+                    period: Duration::new(1_000),
+                    multiplier: Decimal::percent(150),
+                },
+                LockupTier {
+                    period: Duration::new(2_000),
+                    multiplier: Decimal::percent(200),
+                },
+            ]
+        }
+
+        fn lock(
+            deps: DepsMut<TgradeQuery>,
+            env: Env,
+            amount: u128,
+            period: u64,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Lock {
+                tokens: coin(amount, DENOM),
+                period,
+            };
+            execute(deps, env, mock_info(USER1, &[]), msg)
+        }
+
+        fn try_unbond(
+            deps: DepsMut<TgradeQuery>,
+            env: Env,
+            amount: u128,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(amount, DENOM),
+                tag: None,
+            };
+            execute(deps, env, mock_info(USER1, &[]), msg)
         }
 
         #[test]
-        fn single_claim_mixed() {
+        fn locked_stake_earns_multiplied_points_until_expiry() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+            do_instantiate(deps.as_mut(), tiers());
+            bond_liquid(deps.as_mut(), 12_000, 0, 0, 1);
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(12));
 
-            bond_liquid(deps.as_mut(), 1_000, 7_500, 4_000, 1);
+            let env = mock_env();
+            lock(deps.as_mut(), env.clone(), 4_000, 1_000).unwrap();
+            lock(deps.as_mut(), env.clone(), 2_000, 2_000).unwrap();
+            // 12_000 + 4_000 * 0.5 + 2_000 * 1.0
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(16));
 
-            bond_vesting(deps.as_mut(), 11_000, 7_500, 4_000, 2);
-            let height_delta = 3;
+            // locked stake cannot be unbonded
+            let err = try_unbond(deps.as_mut(), env.clone(), 7_000).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InsufficientUnlockedStake {
+                    unlocked: Uint128::new(6_000)
+                }
+            );
+            try_unbond(deps.as_mut(), env.clone(), 6_000).unwrap();
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(10));
 
-            unbond(deps.as_mut(), 1001, 0, 0, height_delta, 0);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            let lockups = query_lockups(deps.as_ref(), USER1.to_owned()).unwrap();
+            assert_eq!(
+                lockups.lockups[0],
+                LockupResponse {
+                    id: 1,
+                    amount: Uint128::new(4_000),
+                    multiplier: Decimal::percent(150),
+                    locked_until: Duration::new(1_000).after(&env.block),
+                }
+            );
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            // liquid stakes are claimed first, then vesting ones as needed
-            assert_sends_undelegates(resp, vec![(USER1, 1000)], vec![(USER1, 1)]);
+            // first lockup expires, its multiplier is not applied anymore
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(999);
+            end_block(deps.as_mut(), env.clone()).unwrap();
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(10));
+            env.block.time = env.block.time.plus_seconds(1);
+            end_block(deps.as_mut(), env.clone()).unwrap();
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(8));
+
+            let lockups = query_lockups(deps.as_ref(), USER1.to_owned()).unwrap();
+            assert_eq!(lockups.lockups.len(), 1);
+            assert_eq!(lockups.lockups[0].id, 2);
+
+            // only the stake of the second lockup is still locked
+            try_unbond(deps.as_mut(), env.clone(), 4_001).unwrap_err();
+            try_unbond(deps.as_mut(), env, 4_000).unwrap();
         }
 
         #[test]
-        fn multiple_users_claims_liquid() {
+        fn invalid_lockups_are_rejected() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 4);
+            do_instantiate(deps.as_mut(), tiers());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            let err = lock(deps.as_mut(), mock_env(), 1_000, 1_500).unwrap_err();
+            assert_eq!(err, ContractError::UnknownLockupPeriod(1_500));
+            let err = lock(deps.as_mut(), mock_env(), 0, 1_000).unwrap_err();
+            assert_eq!(err, ContractError::ZeroAmount {});
 
-            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
-            unbond(deps.as_mut(), 0, 0, 200, height_delta, 1);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+            lock(deps.as_mut(), mock_env(), 8_000, 1_000).unwrap();
+            let err = lock(deps.as_mut(), mock_env(), 3_000, 2_000).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InsufficientUnlockedStake {
+                    unlocked: Uint128::new(2_000)
+                }
+            );
+        }
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 1000), (USER2, 500), (USER3, 200)]);
+        #[test]
+        fn invalid_lockup_tiers_are_rejected() {
+            let mut deps = mock_deps_tgrade();
+            let mut tiers = tiers();
+            tiers[1].multiplier = Decimal::percent(90);
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: None,
+                preauths_hooks: 0,
+                preauths_slashing: 0,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: tiers,
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            let err =
+                instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidLockupTiers {});
         }
 
         #[test]
-        fn multiple_users_claims_vesting() {
+        fn slashing_reduces_lockups() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 4);
+            do_instantiate(deps.as_mut(), tiers());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
+            lock(deps.as_mut(), mock_env(), 4_000, 2_000).unwrap();
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(14));
+
+            let msg = ExecuteMsg::AddSlasher {
+                addr: "slasher".to_owned(),
+                max_portion: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(USER1, &[]), msg).unwrap();
+            let msg = ExecuteMsg::Slash {
+                addr: USER1.to_owned(),
+                portion: Decimal::percent(50),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("slasher", &[]), msg).unwrap();
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            let lockups = query_lockups(deps.as_ref(), USER1.to_owned()).unwrap();
+            assert_eq!(lockups.lockups[0].amount, Uint128::new(2_000));
+            assert_eq!(get_member(deps.as_ref(), USER1.into(), None), Some(7));
+        }
+    }
 
-            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
-            unbond(deps.as_mut(), 0, 0, 200, height_delta, 1);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+    mod stats {
+        use super::*;
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 1000), (USER2, 500), (USER3, 200)]);
+        fn query_stats(deps: Deps<TgradeQuery>) -> StatsResponse {
+            let raw = query(deps, mock_env(), QueryMsg::Stats {}).unwrap();
+            from_slice(&raw).unwrap()
         }
 
         #[test]
-        fn multiple_users_claims_mixed() {
+        fn stats_follow_contract_activity() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 4);
+            do_instantiate(
+                deps.as_mut(),
+                TOKENS_PER_POINT,
+                MIN_BOND,
+                UNBONDING_DURATION,
+                1,
+            );
+            assert_eq!(
+                query_stats(deps.as_ref()),
+                StatsResponse {
+                    total_bonds: Uint128::zero(),
+                    total_unbonds: Uint128::zero(),
+                    total_slashed: Uint128::zero(),
+                    claims_outstanding: 0,
+                    claims_auto_returned: 0,
+                }
+            );
 
-            bond_liquid(deps.as_mut(), 11_000, 7_000, 4_000, 1);
-            bond_vesting(deps.as_mut(), 1_000, 500, 0, 2);
-            let height_delta = 3;
+            bond(deps.as_mut(), (10_000, 2_000), (6_000, 0), (0, 0), 1);
+            unbond(deps.as_mut(), 1_000, 500, 0, 2, 0);
 
-            unbond(deps.as_mut(), 12_000, 7_001, 0, height_delta, 0);
-            unbond(deps.as_mut(), 0, 0, 200, height_delta, 1);
+            let msg = ExecuteMsg::AddSlasher {
+                addr: "slasher".to_owned(),
+                max_portion: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+            let msg = ExecuteMsg::Slash {
+                addr: USER2.to_owned(),
+                portion: Decimal::percent(50),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("slasher", &[]), msg).unwrap();
+
+            // Only one claim is returned because of the auto return limit
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+            env.block.height += 2;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION);
+            end_block(deps.as_mut(), env).unwrap();
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends_undelegates(
-                resp,
-                vec![(USER1, 11_000), (USER2, 7_000), (USER3, 200)],
-                vec![(USER1, 1_000), (USER2, 1)],
+            // Slashing takes half of both the remaining stake and the claim of USER2
+            assert_eq!(
+                query_stats(deps.as_ref()),
+                StatsResponse {
+                    total_bonds: Uint128::new(18_000),
+                    total_unbonds: Uint128::new(1_500),
+                    total_slashed: Uint128::new(3_000),
+                    claims_outstanding: 1,
+                    claims_auto_returned: 1,
+                }
             );
         }
 
         #[test]
-        fn single_user_multiple_claims_liquid() {
+        fn claims_counted_on_migration() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 3);
+            default_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 6_000, 0, 1);
+            unbond(deps.as_mut(), 1_000, 500, 0, 2, 0);
+            unbond(deps.as_mut(), 1_000, 0, 0, 3, 10);
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            // Older versions didn't count claims
+            deps.storage.remove(b"tagged_claims__count");
+            assert_eq!(query_stats(deps.as_ref()).claims_outstanding, 0);
 
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 0, 0, height_delta, 1);
+            claims().init_count(deps.as_mut().storage).unwrap();
+            assert_eq!(query_stats(deps.as_ref()).claims_outstanding, 3);
+        }
+    }
+
+    mod average_stake {
+        use super::*;
+
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, average_stake_period: Option<u64>) {
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 1,
+                auto_return_limit: 0,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
+
+        fn query_average(deps: Deps<TgradeQuery>, time_delta: u64) -> AverageStakeResponse {
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+            env.block.time = env.block.time.plus_seconds(time_delta);
+            let msg = QueryMsg::AverageStake {
+                address: USER1.to_owned(),
+            };
+            from_slice(&query(deps, env, msg).unwrap()).unwrap()
+        }
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 1500)]);
+        #[test]
+        fn average_follows_stake_over_time() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), Some(1_000));
+
+            // freshly bonded stake doesn't count into the average yet
+            bond(deps.as_mut(), (6_000, 4_000), (0, 0), (0, 0), 1);
+            let resp = query_average(deps.as_ref(), 0);
+            assert_eq!(resp.stake, Uint128::new(10_000));
+            assert_eq!(resp.average, Some(Uint128::zero()));
+
+            // half of the period moves the average halfway to the stake
+            let resp = query_average(deps.as_ref(), 500);
+            assert_eq!(resp.average, Some(Uint128::new(5_000)));
+
+            // the stake held so far is accounted on every change
+            unbond(deps.as_mut(), 6_000, 0, 0, 2, 500);
+            let resp = query_average(deps.as_ref(), 500);
+            assert_eq!(resp.stake, Uint128::new(4_000));
+            assert_eq!(resp.average, Some(Uint128::new(5_000)));
+
+            let resp = query_average(deps.as_ref(), 750);
+            assert_eq!(resp.average, Some(Uint128::new(4_750)));
+
+            // after the whole period the average reaches the stake
+            let resp = query_average(deps.as_ref(), 5_000);
+            assert_eq!(resp.average, Some(Uint128::new(4_000)));
         }
 
         #[test]
-        fn single_user_multiple_claims_vesting() {
+        fn average_not_tracked_by_default() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 3);
+            do_instantiate(deps.as_mut(), None);
+
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
+            let resp = query_average(deps.as_ref(), 500);
+            assert_eq!(resp.stake, Uint128::new(10_000));
+            assert_eq!(resp.average, None);
+            assert!(STAKE_AVERAGES
+                .may_load(&deps.storage, &Addr::unchecked(USER1))
+                .unwrap()
+                .is_none());
+        }
+    }
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+    mod unbonding_interest {
+        use super::*;
+        use cosmwasm_std::CosmosMsg;
 
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 0, 0, height_delta, 1);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+        /// A tenth of a year, so 10% yearly interest makes 1% over the unbonding period
+        const UNBONDING_PERIOD: u64 = 3_153_600;
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 1500)]);
+        fn do_instantiate(deps: DepsMut<TgradeQuery>, unbonding_interest: Option<Decimal>) {
+            let msg = InstantiateMsg {
+                denom: "stake".to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_PERIOD,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 1,
+                auto_return_limit: 10,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest,
+                denom_metadata: None,
+                max_pause_duration: None,
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
         }
 
-        #[test]
-        fn single_user_multiple_claims_mixed() {
-            let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 3);
+        fn fund_pool(deps: DepsMut<TgradeQuery>, amount: u128) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::FundInterestPool {};
+            execute(
+                deps,
+                mock_env(),
+                mock_info("funder", &coins(amount, DENOM)),
+                msg,
+            )
+        }
 
-            bond_vesting(deps.as_mut(), 11_000, 7_000, 4_000, 1);
-            bond_liquid(deps.as_mut(), 1_000, 500, 4_000, 2);
-            let height_delta = 3;
+        fn query_pool(deps: Deps<TgradeQuery>) -> InterestPoolResponse {
+            let raw = query(deps, mock_env(), QueryMsg::InterestPool {}).unwrap();
+            from_slice(&raw).unwrap()
+        }
 
-            unbond(deps.as_mut(), 1001, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 0, 0, height_delta, 1);
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+        fn query_payouts(deps: Deps<TgradeQuery>, addr: &str) -> Vec<Uint128> {
+            let msg = QueryMsg::Claims {
+                address: addr.to_owned(),
+                limit: None,
+                start_after: None,
+                tag: None,
+            };
+            let raw = query(deps, mock_env(), msg).unwrap();
+            let resp: ClaimsResponse = from_slice(&raw).unwrap();
+            resp.payouts
+        }
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends_undelegates(resp, vec![(USER1, 1000)], vec![(USER1, 501)]);
+        fn released_env() -> Env {
+            let mut env = mock_env();
+            env.block.height += 10;
+            env.block.time = env.block.time.plus_seconds(UNBONDING_PERIOD);
+            env
         }
 
         #[test]
-        fn only_expired_claims_liquid() {
+        fn interest_paid_from_pool_on_claim() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 3);
-
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            do_instantiate(deps.as_mut(), Some(Decimal::percent(10)));
 
-            // Claims to be returned
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
+            bond(deps.as_mut(), (10_000, 2_000), (0, 0), (0, 0), 1);
+            unbond(deps.as_mut(), 12_000, 0, 0, 2, 0);
 
-            // Clams not yet expired
-            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
-            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
-            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
+            // interest accrues on liquid tokens only, but is not covered by the pool yet
+            assert_eq!(query_payouts(deps.as_ref(), USER1), [Uint128::new(10_000)]);
 
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+            fund_pool(deps.as_mut(), 50).unwrap();
+            assert_eq!(query_payouts(deps.as_ref(), USER1), [Uint128::new(10_050)]);
+            fund_pool(deps.as_mut(), 100).unwrap();
+            assert_eq!(query_payouts(deps.as_ref(), USER1), [Uint128::new(10_100)]);
+            assert_eq!(
+                query_pool(deps.as_ref()),
+                InterestPoolResponse {
+                    rate: Some(Decimal::percent(10)),
+                    balance: coin(150, DENOM),
+                }
+            );
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 1500), (USER2, 600)]);
+            let res = execute(
+                deps.as_mut(),
+                released_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                [
+                    SubMsg::new(BankMsg::Send {
+                        to_address: USER1.to_owned(),
+                        amount: coins(10_100, DENOM),
+                    }),
+                    SubMsg::new(TgradeMsg::Undelegate {
+                        funds: coin(2_000, DENOM),
+                        recipient: USER1.to_owned(),
+                    }),
+                ]
+            );
+            assert_eq!(query_pool(deps.as_ref()).balance, coin(50, DENOM));
         }
 
         #[test]
-        fn only_expired_claims_vesting() {
+        fn auto_returned_claims_are_limited_by_pool() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 3);
+            do_instantiate(deps.as_mut(), Some(Decimal::percent(10)));
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            bond_liquid(deps.as_mut(), 10_000, 5_000, 0, 1);
+            unbond(deps.as_mut(), 10_000, 5_000, 0, 2, 0);
+            fund_pool(deps.as_mut(), 120).unwrap();
 
-            // Claims to be returned
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
+            // user1 accrued 100 and user2 50, but only 20 is left for the latter
+            let res = end_block(deps.as_mut(), released_env()).unwrap();
+            let sends: Vec<_> = res
+                .messages
+                .into_iter()
+                .map(|msg| match msg.msg {
+                    CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                        (to_address, amount[0].amount.u128())
+                    }
+                    msg => panic!("Unexpected message: {:?}", msg),
+                })
+                .collect();
+            assert_eq!(
+                sends,
+                [(USER1.to_owned(), 10_100), (USER2.to_owned(), 5_020)]
+            );
+            assert_eq!(query_pool(deps.as_ref()).balance, coin(0, DENOM));
+        }
 
-            // Clams not yet expired
-            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
-            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
-            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
+        #[test]
+        fn interest_not_accrued_while_disabled() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut(), Some(Decimal::zero()));
+            assert_eq!(query_pool(deps.as_ref()).rate, None);
+
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
+            unbond(deps.as_mut(), 5_000, 0, 0, 2, 0);
+            fund_pool(deps.as_mut(), 1_000).unwrap();
+
+            // enabling the interest later doesn't apply to claims created before
+            let msg = MigrateMsg {
+                tokens_per_point: None,
+                min_bond: None,
+                unbonding_period: None,
+                auto_return_limit: None,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                undelegations: None,
+                average_stake_period: None,
+                unbonding_interest: Some(Decimal::percent(10)),
+                max_pause_duration: None,
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims[0].interest_since, None);
+            assert_eq!(query_payouts(deps.as_ref(), USER1), [Uint128::new(5_000)]);
 
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
-
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 1500), (USER2, 600)]);
+            env.block.time = env.block.time.plus_seconds(10);
+            let msg = ExecuteMsg::Unbond {
+                tokens: coin(5_000, DENOM),
+                tag: None,
+            };
+            execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+            let claims = get_claims(deps.as_ref(), Addr::unchecked(USER1), None, None);
+            assert_eq!(claims[1].interest_since, Some(env.block.time));
+            assert_eq!(
+                query_payouts(deps.as_ref(), USER1),
+                [Uint128::new(5_000), Uint128::new(5_050)]
+            );
         }
 
         #[test]
-        fn claim_returned_once_liquid() {
+        fn pool_requires_staking_tokens() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 5);
+            do_instantiate(deps.as_mut(), Some(Decimal::percent(10)));
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("funder", &[]),
+                ExecuteMsg::FundInterestPool {},
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::NoFunds {});
 
-            // Claims to be returned
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("funder", &coins(100, "other")),
+                ExecuteMsg::FundInterestPool {},
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::MissingDenom(DENOM.to_string()));
+        }
+    }
 
-            // Clams not yet expired
-            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
+    mod claim_buckets {
+        use super::*;
+        use crate::claim::ClaimBucket;
+        use cw_storage_plus::Map;
+
+        fn query_buckets(
+            deps: Deps<TgradeQuery>,
+            start_after: Option<Timestamp>,
+        ) -> UnbondingBucketsResponse {
+            let msg = QueryMsg::UnbondingBuckets {
+                start_after,
+                limit: None,
+            };
+            from_slice(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        }
 
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
+        fn bucket(start: Timestamp, claims: u64, amount: u128) -> UnbondingBucket {
+            UnbondingBucket {
+                start,
+                end: start.plus_seconds(CLAIM_BUCKET_SECONDS),
+                claims,
+                amount: amount.into(),
+                vesting_amount: Uint128::zero(),
+            }
+        }
+
+        /// Start of the bucket of claims created by unbonding at the mock env block time
+        fn first_bucket_start() -> Timestamp {
+            let release = mock_env().block.time.plus_seconds(UNBONDING_DURATION);
+            Timestamp::from_seconds(release.seconds() / CLAIM_BUCKET_SECONDS * CLAIM_BUCKET_SECONDS)
+        }
+
+        fn total_bucketed(deps: Deps<TgradeQuery>) -> (u64, u128) {
+            query_buckets(deps, None)
+                .buckets
+                .iter()
+                .fold((0, 0), |(claims, amount), bucket| {
+                    (claims + bucket.claims, amount + bucket.amount.u128())
+                })
+        }
+
+        #[test]
+        fn claims_are_bucketed() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 6_000, 0, 1);
+            unbond(deps.as_mut(), 1_000, 500, 0, 2, 0);
+            unbond(deps.as_mut(), 2_000, 0, 0, 3, 10);
+            unbond(deps.as_mut(), 0, 1_000, 0, 4, CLAIM_BUCKET_SECONDS);
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 1500), (USER2, 600)]);
+            let first = first_bucket_start();
+            let second = first.plus_seconds(CLAIM_BUCKET_SECONDS);
+            assert_eq!(
+                query_buckets(deps.as_ref(), None),
+                UnbondingBucketsResponse {
+                    buckets: vec![bucket(first, 3, 3_500), bucket(second, 1, 1_000)],
+                    complete: true,
+                }
+            );
+            assert_eq!(
+                query_buckets(deps.as_ref(), Some(first)).buckets,
+                [bucket(second, 1, 1_000)]
+            );
 
-            // Some additional claims
-            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
-            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
+            // Slashed claims are slashed in their buckets
+            let msg = ExecuteMsg::AddSlasher {
+                addr: "slasher".to_owned(),
+                max_portion: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(INIT_ADMIN, &[]), msg).unwrap();
+            let msg = ExecuteMsg::Slash {
+                addr: USER2.to_owned(),
+                portion: Decimal::percent(50),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("slasher", &[]), msg).unwrap();
+            assert_eq!(
+                query_buckets(deps.as_ref(), None).buckets,
+                [bucket(first, 3, 3_250), bucket(second, 1, 500)]
+            );
 
+            // Claimed claims are removed from their buckets
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
-
-            // Expected that claims at time offset 2 and 3 are returned (0 and 1 are already
-            // returned, 4 is not yet expired)
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 900), (USER2, 300), (USER3, 400)]);
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 10);
+            execute(
+                deps.as_mut(),
+                env,
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            assert_eq!(
+                query_buckets(deps.as_ref(), None).buckets,
+                [bucket(first, 1, 250), bucket(second, 1, 500)]
+            );
         }
 
         #[test]
-        fn claim_returned_once_vesting() {
+        fn claims_are_moved_to_buckets_lazily() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 5);
+            do_instantiate(
+                deps.as_mut(),
+                TOKENS_PER_POINT,
+                MIN_BOND,
+                UNBONDING_DURATION,
+                10,
+            );
+            bond_liquid(deps.as_mut(), 10_000, 6_000, 0, 1);
+            unbond(deps.as_mut(), 1_000, 500, 0, 2, 0);
+            unbond(deps.as_mut(), 2_000, 0, 0, 3, CLAIM_BUCKET_SECONDS);
+
+            // Older versions didn't keep the buckets
+            let buckets: Map<u64, ClaimBucket> = Map::new("tagged_claims__buckets");
+            let keys = buckets
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            for key in keys {
+                buckets.remove(deps.as_mut().storage, key);
+            }
+            claims().init_buckets(deps.as_mut().storage).unwrap();
+            assert_eq!(
+                query_buckets(deps.as_ref(), None),
+                UnbondingBucketsResponse {
+                    buckets: vec![],
+                    complete: false,
+                }
+            );
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            let msg = ExecuteMsg::MigrateClaimBuckets { limit: Some(1) };
+            let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap();
+            assert_eq!(
+                res.attributes,
+                [
+                    ("action", "migrate_claim_buckets"),
+                    ("migrated", "1"),
+                    ("complete", "false"),
+                ]
+            );
+            assert!(!query_buckets(deps.as_ref(), None).complete);
+            assert_eq!(total_bucketed(deps.as_ref()).0, 1);
 
-            // Claims to be returned
-            unbond(deps.as_mut(), 1000, 0, 0, height_delta, 0);
-            unbond(deps.as_mut(), 500, 600, 0, height_delta, 1);
+            // Claims created in the meantime are bucketed exactly once
+            unbond(deps.as_mut(), 500, 500, 0, 4, 20);
 
-            // Clams not yet expired
-            unbond(deps.as_mut(), 200, 300, 400, height_delta, 2);
+            // Remaining claims are moved on the end block
+            end_block(deps.as_mut(), mock_env()).unwrap();
+            let resp = query_buckets(deps.as_ref(), None);
+            assert!(resp.complete);
+            assert_eq!(total_bucketed(deps.as_ref()), (5, 4_500));
+            assert_eq!(resp.buckets[0], bucket(first_bucket_start(), 4, 2_500),);
 
+            // Expired claims are released from their buckets
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 1);
-
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 1500), (USER2, 600)]);
-
-            // Some additional claims
-            unbond(deps.as_mut(), 700, 0, 0, height_delta, 3);
-            unbond(deps.as_mut(), 0, 100, 50, height_delta, 4);
+            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 20);
+            end_block(deps.as_mut(), env).unwrap();
+            assert_eq!(total_bucketed(deps.as_ref()), (1, 2_000));
 
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
-
-            // Expected that claims at time offset 2 and 3 are returned (0 and 1 are already
-            // returned, 4 is not yet expired)
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 900), (USER2, 300), (USER3, 400)]);
+            env.block.time = env
+                .block
+                .time
+                .plus_seconds(UNBONDING_DURATION + CLAIM_BUCKET_SECONDS);
+            end_block(deps.as_mut(), env).unwrap();
+            assert_eq!(total_bucketed(deps.as_ref()), (0, 0));
+            assert_eq!(claims().count(&deps.storage).unwrap(), 0);
         }
+    }
 
-        #[test]
-        fn up_to_limit_claims_returned_liquid() {
-            let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+    mod pause {
+        use super::*;
+        use cw_controllers::AdminError;
 
-            bond_liquid(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+        const MAX_PAUSE: u64 = 1_000;
 
-            // Claims to be returned
-            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
-            unbond(deps.as_mut(), 0, 600, 0, height_delta, 1);
-            unbond(deps.as_mut(), 200, 0, 0, height_delta, 2);
-            unbond(deps.as_mut(), 0, 0, 300, height_delta, 3);
+        fn do_instantiate(deps: DepsMut<TgradeQuery>) {
+            let msg = InstantiateMsg {
+                denom: DENOM.to_owned(),
+                tokens_per_point: TOKENS_PER_POINT,
+                min_bond: MIN_BOND,
+                unbonding_period: UNBONDING_DURATION,
+                admin: Some(INIT_ADMIN.into()),
+                preauths_hooks: 0,
+                preauths_slashing: 0,
+                auto_return_limit: 10,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                exit_fee: None,
+                lockup_tiers: vec![],
+                average_stake_period: None,
+                unbonding_interest: None,
+                denom_metadata: None,
+                max_pause_duration: Some(MAX_PAUSE),
+            };
+            instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+        }
 
-            // Even if all claims are already expired, only two of them (time offset 0) should be
-            // returned
+        fn env_at(time_delta: u64) -> Env {
             let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
-
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 1000), (USER2, 500)]);
+            env.block.time = env.block.time.plus_seconds(time_delta);
+            env
+        }
 
-            // Then on next block next batch is returned (time offset 1 and 2)
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 4);
+        fn pause(
+            deps: DepsMut<TgradeQuery>,
+            sender: &str,
+            actions: &[PausableAction],
+            duration: impl Into<Option<u64>>,
+        ) -> Result<Response, ContractError> {
+            let msg = ExecuteMsg::Pause {
+                actions: actions.to_vec(),
+                duration: duration.into(),
+            };
+            execute(deps, mock_env(), mock_info(sender, &[]), msg)
+        }
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 200), (USER2, 600)]);
+        fn query_paused(deps: Deps<TgradeQuery>, env: Env) -> PausedResponse {
+            from_slice(&query(deps, env, QueryMsg::Paused {}).unwrap()).unwrap()
+        }
 
-            // Some additional claims
-            unbond(deps.as_mut(), 700, 0, 0, height_delta, 5);
-            unbond(deps.as_mut(), 0, 100, 50, height_delta, 6);
+        fn bond_msg() -> ExecuteMsg {
+            ExecuteMsg::Bond {
+                vesting_tokens: None,
+            }
+        }
 
-            // Claims are returned in batches
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
+        fn unbond_msg() -> ExecuteMsg {
+            ExecuteMsg::Unbond {
+                tokens: coin(1_000, DENOM),
+                tag: None,
+            }
+        }
 
-            // offset 3 and 5
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER1, 700), (USER3, 300)]);
+        #[test]
+        fn only_admin_can_pause() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut());
 
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
+            let err = pause(deps.as_mut(), USER1, &[PausableAction::Bond], None).unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
 
-            // offset 6
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_sends(resp, vec![(USER2, 100), (USER3, 50)]);
+            pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Bond], None).unwrap();
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::Unpause {
+                    actions: vec![PausableAction::Bond],
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
         }
 
         #[test]
-        fn up_to_limit_claims_returned_vesting() {
+        fn pausing_has_to_be_enabled() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+            default_instantiate(deps.as_mut());
 
-            bond_vesting(deps.as_mut(), 12_000, 7_500, 4_000, 1);
-            let height_delta = 2;
+            let err = pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Bond], None).unwrap_err();
+            assert_eq!(err, ContractError::PausingDisabled {});
+            assert_eq!(
+                query_paused(deps.as_ref(), mock_env()),
+                PausedResponse {
+                    bond: None,
+                    unbond: None,
+                    claim: None,
+                    max_pause_duration: None,
+                }
+            );
+        }
 
-            // Claims to be returned
-            unbond(deps.as_mut(), 1000, 500, 0, height_delta, 0);
-            unbond(deps.as_mut(), 0, 600, 0, height_delta, 1);
-            unbond(deps.as_mut(), 200, 0, 0, height_delta, 2);
-            unbond(deps.as_mut(), 0, 0, 300, height_delta, 3);
+        #[test]
+        fn invalid_pauses() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut());
 
-            // Even if all claims are already expired, only two of them (time offset 0) should be
-            // returned
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 3);
+            let err = pause(deps.as_mut(), INIT_ADMIN, &[], None).unwrap_err();
+            assert_eq!(err, ContractError::NoPauseActions {});
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 1000), (USER2, 500)]);
+            for duration in [0, MAX_PAUSE + 1] {
+                let err = pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Bond], duration)
+                    .unwrap_err();
+                assert_eq!(err, ContractError::InvalidPauseDuration { max: MAX_PAUSE });
+            }
 
-            // Then on next block next batch is returned (time offset 1 and 2)
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 4);
+            // Pause cannot be extended by pausing again
+            pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Unbond], 10).unwrap();
+            let err = pause(
+                deps.as_mut(),
+                INIT_ADMIN,
+                &[PausableAction::Bond, PausableAction::Unbond],
+                None,
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::AlreadyPaused("unbond".to_owned()));
+        }
 
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 200), (USER2, 600)]);
+        #[test]
+        fn actions_are_paused_independently() {
+            let mut deps = mock_deps_tgrade();
+            do_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
 
-            // Some additional claims
-            unbond(deps.as_mut(), 700, 0, 0, height_delta, 5);
-            unbond(deps.as_mut(), 0, 100, 50, height_delta, 6);
+            let res = pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Bond], 100).unwrap();
+            let until = mock_env().block.time.plus_seconds(100);
+            assert_eq!(
+                res.events,
+                [Event::new("pause")
+                    .add_attribute("action", "bond")
+                    .add_attribute("until", until.nanos().to_string())]
+            );
+            assert_eq!(
+                query_paused(deps.as_ref(), mock_env()),
+                PausedResponse {
+                    bond: Some(Expiration::at_timestamp(until)),
+                    unbond: None,
+                    claim: None,
+                    max_pause_duration: Some(MAX_PAUSE),
+                }
+            );
 
-            // Claims are returned in batches
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER2, &coins(10_000, DENOM)),
+                bond_msg(),
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::Paused {
+                    action: "bond".to_owned(),
+                    until,
+                }
+            );
 
-            // offset 3 and 5
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER1, 700), (USER3, 300)]);
+            // Unbonding is still possible
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &[]),
+                unbond_msg(),
+            )
+            .unwrap();
 
-            let mut env = mock_env();
-            env.block.height += height_delta;
-            env.block.time = env.block.time.plus_seconds(UNBONDING_DURATION + 6);
+            // Unpausing bonding doesn't affect other actions
+            let res = pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Unbond], None).unwrap();
+            assert_eq!(res.events.len(), 1);
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                ExecuteMsg::Unpause {
+                    actions: vec![PausableAction::Bond],
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                res.events,
+                [Event::new("unpause").add_attribute("action", "bond")]
+            );
 
-            // offset 6
-            let resp = end_block(deps.as_mut(), env).unwrap();
-            assert_undelegates(resp, vec![(USER2, 100), (USER3, 50)]);
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER2, &coins(10_000, DENOM)),
+                bond_msg(),
+            )
+            .unwrap();
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &[]),
+                unbond_msg(),
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::Paused { action, .. } if action == "unbond"));
         }
 
         #[test]
-        fn unbound_with_invalid_denom_fails_liquid() {
+        fn pause_expires() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+            do_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
 
-            bond_liquid(deps.as_mut(), 5_000, 0, 0, 1);
-            let height_delta = 2;
+            pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Bond], None).unwrap();
 
-            let mut env = mock_env();
-            env.block.height += height_delta;
+            let env = env_at(MAX_PAUSE);
+            assert_eq!(query_paused(deps.as_ref(), env.clone()).bond, None);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(USER1, &coins(1_000, DENOM)),
+                bond_msg(),
+            )
+            .unwrap();
 
-            let msg = ExecuteMsg::Unbond {
-                tokens: coin(5_000, "invalid"),
+            // Expired pause doesn't stop pausing again
+            let msg = ExecuteMsg::Pause {
+                actions: vec![PausableAction::Bond],
+                duration: None,
             };
-            let info = mock_info(USER1, &[]);
-            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
-
-            assert_eq!(ContractError::InvalidDenom {}, err);
+            execute(deps.as_mut(), env, mock_info(INIT_ADMIN, &[]), msg).unwrap();
         }
 
         #[test]
-        fn unbound_with_invalid_denom_fails_vesting() {
+        fn paused_claims_are_not_released() {
             let mut deps = mock_deps_tgrade();
-            do_instantiate(deps.as_mut(), 2);
+            do_instantiate(deps.as_mut());
+            bond_liquid(deps.as_mut(), 10_000, 0, 0, 1);
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(USER1, &[]),
+                unbond_msg(),
+            )
+            .unwrap();
 
-            bond_vesting(deps.as_mut(), 5_000, 0, 0, 1);
-            let height_delta = 2;
+            pause(
+                deps.as_mut(),
+                INIT_ADMIN,
+                &[PausableAction::Claim],
+                UNBONDING_DURATION * 2,
+            )
+            .unwrap();
 
-            let mut env = mock_env();
-            env.block.height += height_delta;
+            // Claim is mature, but neither claimed nor returned automatically
+            let env = env_at(UNBONDING_DURATION);
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(USER1, &[]),
+                ExecuteMsg::Claim {},
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::Paused { action, .. } if action == "claim"));
+            let res = sudo(deps.as_mut(), env, TgradeSudoMsg::EndBlock {}).unwrap();
+            assert!(res.messages.is_empty());
 
-            let msg = ExecuteMsg::Unbond {
-                tokens: coin(5_000, "invalid"),
+            // Released on the first end block after the pause ends
+            let res = sudo(
+                deps.as_mut(),
+                env_at(UNBONDING_DURATION * 2),
+                TgradeSudoMsg::EndBlock {},
+            )
+            .unwrap();
+            assert_eq!(
+                res.messages,
+                [SubMsg::new(BankMsg::Send {
+                    to_address: USER1.to_owned(),
+                    amount: coins(1_000, DENOM),
+                })]
+            );
+        }
+
+        #[test]
+        fn migration_enables_pausing() {
+            let mut deps = mock_deps_tgrade();
+            default_instantiate(deps.as_mut());
+
+            let msg = MigrateMsg {
+                tokens_per_point: None,
+                min_bond: None,
+                unbonding_period: None,
+                auto_return_limit: None,
+                unbond_veto_period: None,
+                max_bond_per_address: None,
+                max_total_bond: None,
+                average_stake_period: None,
+                unbonding_interest: None,
+                max_pause_duration: Some(MAX_PAUSE),
+                undelegations: None,
             };
-            let info = mock_info(USER1, &[]);
-            let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
 
-            assert_eq!(ContractError::InvalidDenom {}, err);
+            pause(deps.as_mut(), INIT_ADMIN, &[PausableAction::Claim], None).unwrap();
         }
     }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp, Uint128};
 use thiserror::Error;
 
 use cw_controllers::AdminError;
@@ -27,6 +27,9 @@ pub enum ContractError {
     #[error("No claims that can be released currently")]
     NothingToClaim {},
 
+    #[error("No pending claims that can be vetoed")]
+    NothingToVeto {},
+
     #[error("Must send '{0}' to stake")]
     MissingDenom(String),
 
@@ -44,4 +47,79 @@ pub enum ContractError {
 
     #[error("Unrecognized sudo message")]
     UnknownSudoMsg {},
+
+    #[error("Not a registered sibling contract: {0}")]
+    NotSibling(String),
+
+    #[error("Nothing staked by {0}")]
+    NothingStaked(String),
+
+    #[error("Insufficient liquid stake to redelegate")]
+    InsufficientLiquidStake {},
+
+    #[error("Unrecognised reply id: {0}")]
+    UnrecognisedReply(u64),
+
+    #[error("Invalid permit key, expected 33 or 65 bytes secp256k1 public key")]
+    InvalidPermitKey {},
+
+    #[error("No payout address of {0} pending confirmation by the sender")]
+    NoPendingPayoutAddress(String),
+
+    #[error("No permit key registered for {0}")]
+    NoPermitKey(String),
+
+    #[error("Permit expired")]
+    PermitExpired {},
+
+    #[error("Invalid permit nonce, expected {expected}, got {got}")]
+    InvalidPermitNonce { expected: u64, got: u64 },
+
+    #[error("Invalid permit signature")]
+    InvalidPermitSignature {},
+
+    #[error("Bond exceeds the per address cap, only {headroom} more can be bonded")]
+    AddressBondCapExceeded { headroom: Uint128 },
+
+    #[error("Bond exceeds the total cap, only {headroom} more can be bonded")]
+    TotalBondCapExceeded { headroom: Uint128 },
+
+    #[error("Invalid staking denom: {0}")]
+    InvalidStakingDenom(String),
+
+    #[error("Invalid denom metadata, display has to be non-empty and exponent at most {0}")]
+    InvalidDenomMetadata(u32),
+
+    #[error("Exit fee portion has to be between 0 and 1 (exclusive)")]
+    InvalidExitFee {},
+
+    #[error("Claim tag has to be between 1 and {0} bytes long")]
+    InvalidClaimTag(usize),
+
+    #[error("Invalid lockup tiers, periods have to be unique and greater than zero, and multipliers at least 1.0")]
+    InvalidLockupTiers {},
+
+    #[error("No lockup tier with period of {0} seconds")]
+    UnknownLockupPeriod(u64),
+
+    #[error("Insufficient unlocked liquid stake, only {unlocked} is not locked")]
+    InsufficientUnlockedStake { unlocked: Uint128 },
+
+    #[error("Too many lockups, at most {0} are allowed per address")]
+    TooManyLockups(usize),
+
+    #[error("Pausing is not enabled")]
+    PausingDisabled {},
+
+    #[error("No actions to pause or unpause")]
+    NoPauseActions {},
+
+    #[error("Pause duration has to be between 1 and {max} seconds")]
+    InvalidPauseDuration { max: u64 },
+
+    #[error("Action '{0}' is already paused")]
+    AlreadyPaused(String),
+
+    #[error("Action '{action}' is paused until {until}")]
+    Paused { action: String, until: Timestamp },
 }
@@ -1,9 +1,12 @@
-use cosmwasm_std::{Coin, Decimal, Uint128};
+use cosmwasm_std::{to_vec, Api, Binary, Coin, Decimal, StdResult, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tg_utils::{Duration, Expiration};
 
 pub use crate::claim::Claim;
+use crate::error::ContractError;
+use crate::state::{DenomMetadata, ExitFee, LockupTier, PausableAction};
 use tg4::Member;
 
 const fn default_auto_return_limit() -> u64 {
@@ -31,6 +34,65 @@ pub struct InstantiateMsg {
     /// Setting this to 0 disables auto returning claims.
     #[serde(default = "default_auto_return_limit")]
     pub auto_return_limit: u64,
+    /// Veto window in seconds applied to unbonding of flagged validator operators. During this
+    /// window slashers can cancel the unbonding. Not set (or 0) disables the veto window.
+    #[serde(default)]
+    pub unbond_veto_period: Option<u64>,
+    /// Maximum stake (liquid and vesting) a single address can bond. Not set (or 0) means
+    /// no limit.
+    #[serde(default)]
+    pub max_bond_per_address: Option<Uint128>,
+    /// Maximum amount of tokens which can be bonded in the contract in total. Not set (or 0)
+    /// means no limit.
+    #[serde(default)]
+    pub max_total_bond: Option<Uint128>,
+    /// Fee taken from liquid tokens on unbonding. Not set means no fee.
+    #[serde(default)]
+    pub exit_fee: Option<UnvalidatedExitFee>,
+    /// Lockup periods (in seconds) stakers can opt into, each granting a points multiplier on
+    /// the locked stake. Empty disables lockups.
+    #[serde(default)]
+    pub lockup_tiers: Vec<LockupTier>,
+    /// Time in seconds over which the moving average of members' stake follows their stake.
+    /// Not set (or 0) disables tracking the average.
+    #[serde(default)]
+    pub average_stake_period: Option<u64>,
+    /// Yearly interest rate paid on liquid tokens during the unbonding period, funded from the
+    /// interest pool. Not set (or 0) disables the interest.
+    #[serde(default)]
+    pub unbonding_interest: Option<Decimal>,
+    /// How frontends should render amounts of `denom`. Can be changed by the admin later.
+    #[serde(default)]
+    pub denom_metadata: Option<DenomMetadata>,
+    /// Longest time in seconds the admin can pause bonding, unbonding or claims for. Not set
+    /// (or 0) disables pausing.
+    #[serde(default)]
+    pub max_pause_duration: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct UnvalidatedExitFee {
+    /// Portion of the unbonded liquid tokens taken as a fee, between (0.0, 1.0)
+    pub portion: Decimal,
+    /// Contract the fee is sent to with `DistributeRewards` (eg. tg4-engagement). The fee is
+    /// burned if not set.
+    #[serde(default)]
+    pub distribution_contract: Option<String>,
+}
+
+impl UnvalidatedExitFee {
+    pub fn validate(self, api: &dyn Api) -> Result<ExitFee, ContractError> {
+        if self.portion.is_zero() || self.portion >= Decimal::one() {
+            return Err(ContractError::InvalidExitFee {});
+        }
+        Ok(ExitFee {
+            portion: self.portion,
+            distribution_contract: self
+                .distribution_contract
+                .map(|addr| api.addr_validate(&addr))
+                .transpose()?,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -43,11 +105,21 @@ pub enum ExecuteMsg {
     /// The sender immediately loses points from these tokens, and can claim them
     /// back to his wallet after `unbonding_period`.
     /// Tokens will be unbonded from the liquid stake first, and then from the vesting stake
-    /// if available.
-    Unbond { tokens: Coin },
+    /// if available. If `exit_fee` is configured, it is taken from the liquid tokens right away.
+    /// The optional `tag` (eg. "forced-exit") is recorded in the created claim, so its origin
+    /// can be distinguished later.
+    Unbond {
+        tokens: Coin,
+        #[serde(default)]
+        tag: Option<String>,
+    },
     /// Claim is used to claim your native and vesting tokens that you previously "unbonded"
     /// after the contract-defined waiting period (eg. 1 week)
     Claim {},
+    /// Locks `tokens` of the sender's liquid stake for `period` seconds, which has to match one
+    /// of the `lockup_tiers`. Locked stake earns points multiplied by the tier multiplier, but
+    /// cannot be unbonded or redelegated until the lockup expires.
+    Lock { tokens: Coin, period: u64 },
 
     /// Change the admin
     UpdateAdmin { admin: Option<String> },
@@ -55,8 +127,13 @@ pub enum ExecuteMsg {
     AddHook { addr: String },
     /// Remove a hook. Must be called by Admin
     RemoveHook { addr: String },
-    /// Add a new slasher. Must be called by Admin
-    AddSlasher { addr: String },
+    /// Add a new slasher. Must be called by Admin.
+    /// `max_portion` limits how much the slasher can slash at once, up to 100% if not set.
+    AddSlasher {
+        addr: String,
+        #[serde(default)]
+        max_portion: Option<Decimal>,
+    },
     /// Remove a slasher. Must be called by Admin
     RemoveSlasher { addr: String },
     Slash {
@@ -64,6 +141,110 @@ pub enum ExecuteMsg {
         // between (0.0, 1.0]
         portion: Decimal,
     },
+    /// Flags or unflags the staker as a validator operator. Unbonding of flagged stakers is
+    /// subject to `unbond_veto_period`. Must be called by a slasher
+    SetOperator { addr: String, operator: bool },
+    /// Cancels all pending (still in veto window) unbonding claims of the staker, restoring
+    /// them as a stake. Must be called by a slasher
+    VetoUnbond { addr: String },
+    /// Moves `amount` of the sender's liquid stake to the sibling `to_contract` right away,
+    /// without going through the unbonding period
+    Redelegate { to_contract: String, amount: Coin },
+    /// Bonds the tokens sent with the message as the `staker`'s liquid stake. Must be called by
+    /// a sibling contract as part of its `Redelegate`
+    ReceiveRedelegation { staker: String },
+    /// Registers a sibling tg4-stake contract stake can be redelegated to and from.
+    /// Must be called by Admin
+    AddSibling { contract: String },
+    /// Removes a sibling contract. Must be called by Admin
+    RemoveSibling { contract: String },
+    /// Registers the secp256k1 public key (compressed or uncompressed) signing the sender's claim
+    /// permits. `None` removes the key, so no permits are accepted anymore.
+    SetPermitKey { pubkey: Option<Binary> },
+    /// Claims released tokens on behalf of `permit.owner`, authorized by the owner's signature
+    /// of `ClaimPermitSignDoc`. Can be sent by anyone (eg. relayer paying the gas), tokens are
+    /// always sent to the owner (or its payout address).
+    ClaimWithPermit {
+        permit: ClaimPermit,
+        signature: Binary,
+    },
+    /// Sets or removes (if `None`) the fee taken on unbonding. Must be called by Admin
+    SetExitFee {
+        exit_fee: Option<UnvalidatedExitFee>,
+    },
+    /// Registers a controller allowed to force-unbond members. Must be called by Admin
+    AddUnbondController { addr: String },
+    /// Removes an unbond controller. Must be called by Admin
+    RemoveUnbondController { addr: String },
+    /// Unbonds the whole stake of `addr`, including locked and vesting stake, without exit fee
+    /// or veto window. Claims are created with the standard unbonding period. Must be called
+    /// by an unbond controller
+    ForceUnbond { addr: String },
+    /// Sets the address receiving liquid tokens of the sender's matured claims, both claimed
+    /// and auto-returned. Vesting tokens are always returned to the staker. `None` resets
+    /// payouts to the staker. With `require_confirmation` the address becomes active only
+    /// once it sends `ConfirmPayoutAddress`.
+    SetPayoutAddress {
+        addr: Option<String>,
+        #[serde(default)]
+        require_confirmation: bool,
+    },
+    /// Confirms the sender as payout address of `staker`
+    ConfirmPayoutAddress { staker: String },
+    /// Sets or removes (if `None`) display metadata of the staking denom. Must be called by Admin
+    SetDenomMetadata { metadata: Option<DenomMetadata> },
+    /// Adds staking tokens sent with the message to the pool funding the unbonding interest.
+    /// Can be sent by anyone
+    FundInterestPool {},
+    /// Moves up to `limit` claims created before claim tags were introduced to the tagged
+    /// claims, and claims created before claims buckets were introduced into their buckets. It
+    /// also happens on every end block, this is for contracts not being end blockers. Can be
+    /// sent by anyone
+    MigrateClaimBuckets { limit: Option<u64> },
+    /// Pauses the given actions for `duration` seconds, `max_pause_duration` if not set. Actions
+    /// already paused cannot be paused again until their pause ends, so funds cannot be locked
+    /// for longer than `max_pause_duration`. Must be called by Admin
+    Pause {
+        actions: Vec<PausableAction>,
+        #[serde(default)]
+        duration: Option<u64>,
+    },
+    /// Ends the pause of the given actions before its expiration. Must be called by Admin
+    Unpause { actions: Vec<PausableAction> },
+}
+
+/// Authorizes a single claim on behalf of the `owner`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ClaimPermit {
+    pub owner: String,
+    /// Has to match the current permit nonce of the owner, which is incremented on every use
+    pub nonce: u64,
+    /// Permit cannot be used after this time
+    pub deadline: Timestamp,
+}
+
+/// Document the permit key signs. Chain id and contract address are included, so a permit is
+/// valid only on a single contract instance.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ClaimPermitSignDoc {
+    pub chain_id: String,
+    pub contract: String,
+    pub permit: ClaimPermit,
+}
+
+impl ClaimPermitSignDoc {
+    /// sha256 of JSON-encoded sign doc, which is what the secp256k1 signature is verified against
+    pub fn hash(&self) -> StdResult<Vec<u8>> {
+        Ok(Sha256::digest(&to_vec(self)?).to_vec())
+    }
+}
+
+/// Messages sent by this contract to an external contract
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributionMsg {
+    /// Message sent to the exit fee `distribution_contract` with the fee taken on unbonding
+    DistributeRewards {},
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -71,11 +252,14 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Returns config
     Configuration {},
-    /// Claims shows the tokens in process of unbonding for this address
+    /// Claims shows the tokens in process of unbonding for this address.
+    /// If `tag` is set, only claims with this tag are returned.
     Claims {
         address: String,
         limit: Option<u32>,
         start_after: Option<Expiration>,
+        #[serde(default)]
+        tag: Option<String>,
     },
     /// Shows the number of liquid and vesting tokens currently staked by this address.
     /// Returns StakedResponse.
@@ -87,8 +271,9 @@ pub enum QueryMsg {
     /// Return AdminResponse
     Admin {},
     /// Returns TotalPointsResponse. This is the amount of tokens bonded divided by
-    /// tokens_per_point.
-    TotalPoints {},
+    /// tokens_per_point. If at_height is set, returns total points at the beginning of
+    /// the given block.
+    TotalPoints { at_height: Option<u64> },
     /// Returns MemberListResponse
     ListMembers {
         start_after: Option<String>,
@@ -112,6 +297,60 @@ pub enum QueryMsg {
     IsSlasher { addr: String },
     /// Returns all active slashers as a vector of addresses.
     ListSlashers {},
+    /// Returns the maximum portion (Option<Decimal>) the address can slash at once, `None` if
+    /// it is not a slasher
+    SlasherLimit { addr: String },
+    /// Returns information (bool) about whether a given address is flagged as a validator operator
+    IsOperator { addr: String },
+    /// Returns SiblingsResponse
+    ListSiblings {},
+    /// Returns UnbondControllersResponse
+    ListUnbondControllers {},
+    /// Deterministic, address-ordered dump of bonded stakes, meant for off-chain snapshot tooling.
+    /// Pass the `hash` of the previous page as `prev_hash` to chain the rolling hash over the
+    /// whole dump. Returns ExportStakesResponse.
+    ExportStakes {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        #[serde(default)]
+        prev_hash: Option<Binary>,
+    },
+    /// Raw, address-ordered listing of all stake entries (including zeroed ones), along with the
+    /// stored totals, so indexers can rebuild the state. Unlike member listings, stakes are not
+    /// converted to points. Returns ListAllStakesResponse.
+    ListAllStakes {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns PermitInfoResponse
+    PermitInfo { owner: String },
+    /// Returns PayoutAddressResponse
+    PayoutAddress { staker: String },
+    /// Returns how many more tokens can be bonded by the address and in total.
+    /// Returns BondHeadroomResponse.
+    BondHeadroom { address: String },
+    /// Returns all lockup positions of the address, including already expired ones which were
+    /// not released yet. Returns LockupsResponse.
+    Lockups { address: String },
+    /// Returns the time weighted moving average of the address stake (liquid and vesting) as
+    /// of the current block, to be used instead of the instantaneous stake for rewards.
+    /// Returns AverageStakeResponse.
+    AverageStake { address: String },
+    /// Returns lifetime activity counters of the contract. Returns StatsResponse.
+    Stats {},
+    /// Returns the unbonding interest rate and the pool funding it.
+    /// Returns InterestPoolResponse.
+    InterestPool {},
+    /// Returns the canonical staking denom with its display metadata. Returns DenomResponse.
+    Denom {},
+    /// Returns claims not released yet, summarized by release time windows, ordered by the
+    /// release time. Returns UnbondingBucketsResponse.
+    UnbondingBuckets {
+        start_after: Option<Timestamp>,
+        limit: Option<u32>,
+    },
+    /// Returns actions currently paused. Returns PausedResponse.
+    Paused {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -120,6 +359,99 @@ pub struct StakedResponse {
     pub vesting: Coin,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LockupResponse {
+    pub id: u64,
+    pub amount: Uint128,
+    pub multiplier: Decimal,
+    pub locked_until: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct LockupsResponse {
+    pub lockups: Vec<LockupResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct StatsResponse {
+    /// All tokens (liquid and vesting) ever bonded
+    pub total_bonds: Uint128,
+    /// All tokens (liquid and vesting) ever unbonded, including forced unbondings
+    pub total_unbonds: Uint128,
+    /// All tokens ever slashed, from both stakes and claims
+    pub total_slashed: Uint128,
+    /// Number of claims not released yet
+    pub claims_outstanding: u64,
+    /// Number of claims released automatically at the end of block
+    pub claims_auto_returned: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct UnbondingBucket {
+    /// Start of the release time window, inclusive
+    pub start: Timestamp,
+    /// End of the release time window, exclusive
+    pub end: Timestamp,
+    /// Number of claims released within the window
+    pub claims: u64,
+    /// Liquid tokens released within the window
+    pub amount: Uint128,
+    /// Vesting tokens released within the window
+    pub vesting_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct UnbondingBucketsResponse {
+    pub buckets: Vec<UnbondingBucket>,
+    /// False while claims created before the buckets were introduced are still being moved to
+    /// buckets, so they are not included yet
+    pub complete: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InterestPoolResponse {
+    /// Yearly interest rate paid during the unbonding period, `None` if disabled
+    pub rate: Option<Decimal>,
+    /// Tokens left in the pool for paying the interest
+    pub balance: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DenomResponse {
+    /// Staking denom in its canonical form, as it appears in bank balances
+    pub denom: String,
+    /// Display metadata set by the admin, `None` if not configured
+    pub metadata: Option<DenomMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PausedResponse {
+    /// Times until which bonding, unbonding and claims are paused, `None` if not paused
+    pub bond: Option<Expiration>,
+    pub unbond: Option<Expiration>,
+    pub claim: Option<Expiration>,
+    /// Longest time in seconds actions can be paused for, `None` if pausing is disabled
+    pub max_pause_duration: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AverageStakeResponse {
+    /// Current stake (liquid and vesting)
+    pub stake: Uint128,
+    /// Moving average of the stake, `None` if the average is not tracked
+    pub average: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BondHeadroomResponse {
+    /// Tokens the address can still bond, `None` if there is no per address cap
+    pub address: Option<Uint128>,
+    /// Tokens which can still be bonded in total, `None` if there is no total cap
+    pub total: Option<Uint128>,
+    /// Tokens currently bonded in the contract
+    pub total_bonded: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct PreauthResponse {
     pub preauths_hooks: u64,
@@ -133,6 +465,10 @@ pub struct UnbondingPeriodResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct ClaimsResponse {
     pub claims: Vec<Claim>,
+    /// Liquid tokens projected to be paid out for each of `claims` on release, including the
+    /// unbonding interest at the current rate, as far as the current interest pool covers it
+    #[serde(default)]
+    pub payouts: Vec<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -148,9 +484,73 @@ pub struct MigrateMsg {
     pub min_bond: Option<Uint128>,
     pub unbonding_period: Option<u64>,
     pub auto_return_limit: Option<u64>,
+    /// Setting this to 0 disables the veto window
+    pub unbond_veto_period: Option<u64>,
+    /// Setting this to 0 removes the per address bond cap
+    pub max_bond_per_address: Option<Uint128>,
+    /// Setting this to 0 removes the total bond cap
+    pub max_total_bond: Option<Uint128>,
+    /// Setting this to 0 stops tracking the average stake
+    pub average_stake_period: Option<u64>,
+    /// Setting this to 0 disables the unbonding interest
+    pub unbonding_interest: Option<Decimal>,
+    /// Setting this to 0 disables pausing
+    pub max_pause_duration: Option<u64>,
     pub undelegations: Option<Vec<Undelegation>>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct StakeEntry {
+    pub addr: String,
+    pub liquid: Uint128,
+    pub vesting: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ExportStakesResponse {
+    /// Height at which the export was taken
+    pub height: u64,
+    pub stakes: Vec<StakeEntry>,
+    /// sha256 of `prev_hash` (if any) followed by JSON-encoded `stakes`. After the last page
+    /// this commits to the whole ordered dump.
+    pub hash: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ListAllStakesResponse {
+    /// Sum of all liquid and vesting stakes
+    pub total_bonded: Uint128,
+    /// Total points of all members
+    pub total_points: u64,
+    pub stakes: Vec<StakeEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PermitInfoResponse {
+    /// Key registered to sign claim permits, if any
+    pub pubkey: Option<Binary>,
+    /// Nonce the next permit has to use
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PayoutAddressResponse {
+    /// Address receiving liquid tokens of matured claims, the staker itself if not set
+    pub payout_address: Option<String>,
+    /// Payout address waiting for its confirmation
+    pub pending: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct UnbondControllersResponse {
+    pub controllers: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SiblingsResponse {
+    pub siblings: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,15 +2,30 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::claim::Claims;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Decimal, Empty, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
 use cw_storage_plus::{Item, Map};
-use tg_utils::Duration;
+use tg_utils::{Duration, Expiration};
 
 /// Builds a claims map as it cannot be done in const time
 pub fn claims() -> Claims<'static> {
-    Claims::new("claims", "claims__release")
+    Claims::new(
+        "tagged_claims",
+        "tagged_claims__release",
+        "tagged_claims__count",
+        "tagged_claims__buckets",
+        "tagged_claims__bucket_cursor",
+        LEGACY_CLAIMS_KEY,
+        LEGACY_CLAIMS_RELEASE_KEY,
+    )
 }
 
+/// Namespaces of claims stored before claim tags were introduced, they are moved to `claims()`
+/// lazily
+pub const LEGACY_CLAIMS_KEY: &str = "claims";
+pub const LEGACY_CLAIMS_RELEASE_KEY: &str = "claims__release";
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct Config {
     /// denom of the token to stake
@@ -21,8 +36,247 @@ pub struct Config {
     pub unbonding_period: Duration,
     /// limits of how much claims can be automatically returned at end of block
     pub auto_return_limit: u64,
+    /// veto window (in seconds) after unbonding by a flagged validator operator, during which
+    /// a slasher can cancel the unbonding
+    #[serde(default)]
+    pub unbond_veto_period: Option<Duration>,
+    /// maximum stake (liquid and vesting) a single address can have bonded
+    #[serde(default)]
+    pub max_bond_per_address: Option<Uint128>,
+    /// maximum amount of tokens bonded in the contract in total
+    #[serde(default)]
+    pub max_total_bond: Option<Uint128>,
+    /// fee taken from the liquid tokens being unbonded
+    #[serde(default)]
+    pub exit_fee: Option<ExitFee>,
+    /// lockup periods stakers can opt into, with points multipliers they grant
+    #[serde(default)]
+    pub lockup_tiers: Vec<LockupTier>,
+    /// time over which the moving average of members' stake follows their stake, not tracked
+    /// if not set
+    #[serde(default)]
+    pub average_stake_period: Option<Duration>,
+    /// yearly interest rate paid on liquid tokens during the unbonding period, disabled if
+    /// not set
+    #[serde(default)]
+    pub unbonding_interest: Option<Decimal>,
+    /// display metadata of the staking denom, set by the admin
+    #[serde(default)]
+    pub denom_metadata: Option<DenomMetadata>,
+    /// longest time the admin can pause bonding, unbonding or claims for, pausing is disabled
+    /// if not set
+    #[serde(default)]
+    pub max_pause_duration: Option<Duration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct LockupTier {
+    /// time in seconds the stake is locked for
+    pub period: Duration,
+    /// points multiplier of the locked stake, at least 1.0
+    pub multiplier: Decimal,
+}
+
+/// How frontends should render amounts of the staking denom
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DenomMetadata {
+    /// name of the denom shown to users (eg. "TGD" for "utgd")
+    pub display: String,
+    /// decimal places between the staking denom and the display unit (eg. 6 for "utgd")
+    pub exponent: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ExitFee {
+    /// portion of the unbonded liquid tokens taken as a fee, between (0.0, 1.0)
+    pub portion: Decimal,
+    /// contract the fee is sent to with `DistributeRewards`, the fee is burned if not set
+    pub distribution_contract: Option<Addr>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const STAKE: Map<&Addr, Uint128> = Map::new("stake");
 pub const STAKE_VESTING: Map<&Addr, Uint128> = Map::new("vesting_stake");
+/// Sum of all liquid and vesting stakes, kept for cheap checks of the total bond cap
+pub const TOTAL_BONDED: Item<Uint128> = Item::new("total_bonded");
+
+/// Tokens held for paying the unbonding interest, separately from the stake
+pub const INTEREST_POOL: Item<Uint128> = Item::new("interest_pool");
+
+/// Takes up to `interest` from the interest pool, returning the amount actually covered
+pub fn take_interest(storage: &mut dyn Storage, interest: Uint128) -> StdResult<Uint128> {
+    if interest.is_zero() {
+        return Ok(interest);
+    }
+    let pool = INTEREST_POOL.may_load(storage)?.unwrap_or_default();
+    let taken = interest.min(pool);
+    INTEREST_POOL.save(storage, &(pool - taken))?;
+    Ok(taken)
+}
+
+/// Lifetime counters of the contract activity
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Stats {
+    /// All tokens (liquid and vesting) ever bonded
+    pub total_bonds: Uint128,
+    /// All tokens (liquid and vesting) ever unbonded, including forced unbondings
+    pub total_unbonds: Uint128,
+    /// All tokens ever slashed, from both stakes and claims
+    pub total_slashed: Uint128,
+    /// Number of claims released automatically at the end of block
+    pub claims_auto_returned: u64,
+}
+
+pub const STATS: Item<Stats> = Item::new("stats");
+
+/// Operations which can be paused by the admin independently of each other
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PausableAction {
+    /// `Bond` and receiving redelegations
+    Bond,
+    /// `Unbond` and redelegating to siblings
+    Unbond,
+    /// `Claim`, `ClaimWithPermit` and returning claims at the end of block
+    Claim,
+}
+
+impl PausableAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PausableAction::Bond => "bond",
+            PausableAction::Unbond => "unbond",
+            PausableAction::Claim => "claim",
+        }
+    }
+}
+
+/// Times until which actions are paused. Pauses are not cleaned up on expiration, so they have
+/// to be checked against the current block.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Pauses {
+    pub bond: Option<Expiration>,
+    pub unbond: Option<Expiration>,
+    pub claim: Option<Expiration>,
+}
+
+impl Pauses {
+    /// Time until which `action` is paused, `None` if it is not paused at `block`
+    pub fn paused_until(&self, action: PausableAction, block: &BlockInfo) -> Option<Expiration> {
+        let pause = match action {
+            PausableAction::Bond => self.bond,
+            PausableAction::Unbond => self.unbond,
+            PausableAction::Claim => self.claim,
+        };
+        pause.filter(|until| !until.is_expired(block))
+    }
+
+    pub fn set(&mut self, action: PausableAction, until: Option<Expiration>) {
+        match action {
+            PausableAction::Bond => self.bond = until,
+            PausableAction::Unbond => self.unbond = until,
+            PausableAction::Claim => self.claim = until,
+        }
+    }
+}
+
+pub const PAUSES: Item<Pauses> = Item::new("pauses");
+
+/// Fails if `action` is currently paused
+pub fn assert_not_paused(
+    storage: &dyn Storage,
+    action: PausableAction,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    let pauses = PAUSES.may_load(storage)?.unwrap_or_default();
+    match pauses.paused_until(action, block) {
+        Some(until) => Err(ContractError::Paused {
+            action: action.as_str().to_owned(),
+            until: until.time(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Updates the contract stats, treating missing ones (of older versions) as zeroed
+pub fn update_stats(storage: &mut dyn Storage, action: impl FnOnce(&mut Stats)) -> StdResult<()> {
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    action(&mut stats);
+    STATS.save(storage, &stats)
+}
+
+/// Liquid stake locked for a fixed period, which cannot be unbonded (or redelegated) until
+/// `locked_until`. Locked tokens are still part of `STAKE`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Lockup {
+    pub amount: Uint128,
+    pub multiplier: Decimal,
+    pub locked_until: Expiration,
+}
+
+/// Maximum number of lockup positions a single staker can have at once
+pub const MAX_LOCKUPS_PER_ADDRESS: usize = 10;
+
+/// Lockup positions keyed by `(staker, lockup id)`
+pub const LOCKUPS: Map<(&Addr, u64), Lockup> = Map::new("lockups");
+/// Lockup positions by `(expiration, staker, lockup id)`, so expired ones can be released at
+/// the end of block
+pub const LOCKUP_EXPIRATIONS: Map<(u64, &Addr, u64), Empty> = Map::new("lockup_expirations");
+/// Id of the last created lockup position
+pub const LOCKUP_ID: Item<u64> = Item::new("lockup_id");
+
+/// Time weighted moving average of the member's stake (liquid and vesting)
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct StakeAverage {
+    /// Average as of `updated_at`
+    pub average: Uint128,
+    /// Stake held since `updated_at`
+    pub stake: Uint128,
+    /// Time in seconds of the last stake change
+    pub updated_at: u64,
+}
+
+impl StakeAverage {
+    /// Average at `time` (in seconds). Over every interval the average moves toward the stake
+    /// held by `elapsed / period` of the difference, reaching it after the whole `period`.
+    pub fn average_at(&self, time: u64, period: Duration) -> Uint128 {
+        let elapsed = time.saturating_sub(self.updated_at).min(period.seconds());
+        let weight = Decimal::from_ratio(elapsed, period.seconds());
+        if self.stake >= self.average {
+            self.average + (self.stake - self.average) * weight
+        } else {
+            self.average - (self.average - self.stake) * weight
+        }
+    }
+}
+
+/// Members' stake averages, only tracked if `average_stake_period` is set. Averages start from
+/// zero on the first stake change after tracking is enabled.
+pub const STAKE_AVERAGES: Map<&Addr, StakeAverage> = Map::new("stake_averages");
+
+/// Stakers flagged as validator operators by a slasher
+pub const OPERATORS: Map<&Addr, Empty> = Map::new("operators");
+/// Sibling tg4-stake contracts stake can be redelegated to and from without unbonding
+pub const SIBLINGS: Map<&Addr, Empty> = Map::new("siblings");
+/// Contracts (eg. trusted circle or governance) allowed to force-unbond members' stake
+pub const UNBOND_CONTROLLERS: Map<&Addr, Empty> = Map::new("unbond_controllers");
+
+/// Addresses receiving liquid tokens of matured claims instead of the staker
+pub const PAYOUT_ADDRESSES: Map<&Addr, Addr> = Map::new("payout_addresses");
+/// Payout addresses waiting for confirmation by the payout address itself
+pub const PENDING_PAYOUT_ADDRESSES: Map<&Addr, Addr> = Map::new("pending_payout_addresses");
+
+/// Keys signing claim permits of stakers
+pub const PERMIT_KEYS: Map<&Addr, Binary> = Map::new("permit_keys");
+/// Nonces of the next claim permits, so every permit can be used only once
+pub const PERMIT_NONCES: Map<&Addr, u64> = Map::new("permit_nonces");
+
+/// Redelegation waiting for the confirmation of the receiving sibling
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PendingRedelegation {
+    pub staker: Addr,
+    pub to_contract: Addr,
+    pub amount: Uint128,
+}
+
+pub const PENDING_REDELEGATION: Item<PendingRedelegation> = Item::new("pending_redelegation");
@@ -5,8 +5,8 @@ use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, s
 use cosmwasm_std::Empty;
 
 use tg_voting_contract::msg::ProposalCreationResponse;
-use tg_voting_contract::state::{ProposalListResponse, ProposalResponse};
-use tgrade_community_pool::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use tg_voting_contract::state::{ProposalContentRules, ProposalListResponse, ProposalResponse};
+use tgrade_community_pool::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -17,8 +17,10 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
+    export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
     export_schema_with_title(&schema_for!(Empty), &out_dir, "Proposal");
     export_schema(&schema_for!(ProposalResponse<Empty>), &out_dir);
     export_schema(&schema_for!(ProposalCreationResponse), &out_dir);
     export_schema(&schema_for!(ProposalListResponse<Empty>), &out_dir);
+    export_schema(&schema_for!(ProposalContentRules), &out_dir);
 }
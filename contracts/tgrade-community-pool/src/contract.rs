@@ -1,22 +1,23 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, BankMsg, Binary, Coin, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo,
-    StdResult,
+    to_binary, BankMsg, Binary, Coin, CustomQuery, Deps, DepsMut, Env, MessageInfo, StdResult,
 };
 
 use cw2::set_contract_version;
-use cw_utils::ensure_from_older_version;
 use tg_bindings::{TgradeMsg, TgradeQuery};
 
-use crate::msg::{ExecuteMsg, InstantiateMsg, Proposal, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, Proposal, QueryMsg};
 use crate::ContractError;
 
-use tg_voting_contract::state::CONFIG as VOTING_CONFIG;
+use tg_voting_contract::state::{CONFIG as VOTING_CONFIG, MIGRATIONS};
 use tg_voting_contract::{
-    close as execute_close, execute_text, list_proposals, list_text_proposals, list_voters,
-    list_votes, list_votes_by_voter, mark_executed, propose, query_group_contract, query_proposal,
-    query_rules, query_vote, query_voter, reverse_proposals, vote as execute_vote,
+    close as execute_close, execute_text, index_open_proposals, list_proposals,
+    list_proposals_by_tag, list_text_proposals, list_voters, list_votes, list_votes_by_voter,
+    mark_executed, override_config, proposal_data, propose, query_group_contract, query_proposal,
+    query_proposal_content_rules, query_rules, query_vote, query_voter, reverse_proposals,
+    update_rules, vote as execute_vote, vote_batch as execute_vote_batch, ConfigOverrides,
+    InstantiateConfig,
 };
 
 pub type Response = cosmwasm_std::Response<TgradeMsg>;
@@ -34,7 +35,11 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    tg_voting_contract::instantiate(deps, msg.rules, &msg.group_addr).map_err(ContractError::from)
+    let config = InstantiateConfig {
+        content_rules: msg.content_rules,
+        ..InstantiateConfig::new(msg.rules, msg.group_addr)
+    };
+    tg_voting_contract::instantiate(deps, config).map_err(ContractError::from)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -49,11 +54,26 @@ pub fn execute(
             title,
             description,
             proposal,
-        } => execute_propose(deps, env, info, title, description, proposal),
+            tags,
+            idempotency_key,
+        } => execute_propose(
+            deps,
+            env,
+            info,
+            title,
+            description,
+            proposal,
+            tags,
+            idempotency_key,
+        ),
         ExecuteMsg::Vote { proposal_id, vote } => {
             execute_vote::<Proposal, TgradeQuery>(deps, env, info, proposal_id, vote)
                 .map_err(ContractError::from)
         }
+        ExecuteMsg::VoteBatch { votes, mode } => {
+            execute_vote_batch::<Proposal, TgradeQuery>(deps, env, info, votes, mode)
+                .map_err(ContractError::from)
+        }
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => {
             execute_close::<Proposal, TgradeQuery>(deps, env, info, proposal_id)
@@ -64,6 +84,7 @@ pub fn execute(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose<Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
@@ -71,14 +92,31 @@ pub fn execute_propose<Q: CustomQuery>(
     title: String,
     description: String,
     proposal: Proposal,
+    tags: Vec<String>,
+    idempotency_key: Option<String>,
 ) -> Result<Response, ContractError> {
     use Proposal::*;
 
-    if let SendProposal { to_addr, .. } = &proposal {
-        deps.api.addr_validate(to_addr)?;
+    match &proposal {
+        SendProposal { to_addr, .. } => {
+            deps.api.addr_validate(to_addr)?;
+        }
+        UpdateRules(msg) => msg.rules.validate()?,
+        Text {} => {}
     }
 
-    propose(deps, env, info, title, description, proposal).map_err(ContractError::from)
+    propose(
+        deps,
+        env,
+        info,
+        title,
+        description,
+        proposal,
+        tags,
+        vec![],
+        idempotency_key,
+    )
+    .map_err(ContractError::from)
 }
 
 pub fn execute_send_proposal(to_address: String, amount: Coin) -> Result<Response, ContractError> {
@@ -107,7 +145,8 @@ pub fn execute_execute<Q: CustomQuery>(
     use Proposal::*;
 
     // anyone can trigger this if the vote passed
-    let prop = mark_executed::<Proposal>(deps.storage, env, proposal_id)?;
+    let prop = mark_executed::<Proposal>(deps.storage, env.clone(), proposal_id)?;
+    let data = proposal_data(proposal_id, &prop)?;
 
     // dispatch all proposed messages
     let resp = match prop.proposal {
@@ -116,12 +155,16 @@ pub fn execute_execute<Q: CustomQuery>(
             execute_text(deps, proposal_id, prop)?;
             Response::default()
         }
+        UpdateRules(msg) => {
+            Response::new().add_event(update_rules::<Proposal, Q>(deps, &env, msg)?)
+        }
     };
 
     let resp = resp
         .add_attribute("action", "execute")
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("sender", info.sender.to_string());
+        .add_attribute("sender", info.sender.to_string())
+        .set_data(data);
 
     Ok(resp)
 }
@@ -159,6 +202,7 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
 
     match msg {
         Rules {} => to_binary(&query_rules(deps)?),
+        ProposalContentRules {} => to_binary(&query_proposal_content_rules(deps)?),
         Proposal { proposal_id } => to_binary(
             &query_proposal::<crate::msg::Proposal, TgradeQuery>(deps, env, proposal_id)?,
         ),
@@ -171,6 +215,17 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
                 align_limit(limit),
             )?)
         }
+        ListProposalsByTag {
+            tag,
+            start_after,
+            limit,
+        } => to_binary(&list_proposals_by_tag::<crate::msg::Proposal, TgradeQuery>(
+            deps,
+            env,
+            tag,
+            start_after,
+            align_limit(limit),
+        )?),
         ReverseProposals {
             start_before,
             limit,
@@ -210,9 +265,36 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::new())
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let mut migration = MIGRATIONS.start(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        env.block.height,
+    )?;
+    override_config(
+        deps.branch(),
+        ConfigOverrides {
+            limits: msg.limits,
+            pruning: msg.pruning,
+            content_rules: msg.content_rules,
+            auto_close: msg.auto_close,
+            execution_period: msg.execution_period,
+            vote_bonus: msg.vote_bonus,
+            power_provider: msg.power_provider,
+            power_boost_caps: msg.power_boost_caps,
+        },
+    )?;
+
+    let mut res = Response::new();
+    // Older versions didn't index proposals by expiration nor count open ones
+    if migration.step("open_proposals_index", "<=0.17.1")? {
+        let indexed = index_open_proposals::<Proposal>(deps.storage, &env.block)?;
+        res = res.add_attribute("indexed_proposals", indexed.to_string());
+    }
+
+    MIGRATIONS.finish(deps.storage, migration)?;
+    Ok(res)
 }
 
 #[cfg(test)]
@@ -232,6 +314,9 @@ mod tests {
             quorum: Decimal::percent(50),
             threshold: Decimal::percent(50),
             allow_end_early: false,
+            abstain_non_voters: false,
+            proposer_policy: None,
+            tie_break: None,
         };
         let group_addr = "group_addr";
         instantiate(
@@ -244,6 +329,7 @@ mod tests {
             InstantiateMsg {
                 rules,
                 group_addr: group_addr.to_owned(),
+                content_rules: Default::default(),
             },
         )
         .unwrap();
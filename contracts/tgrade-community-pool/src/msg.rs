@@ -3,8 +3,13 @@ use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Coin;
 use tg3::Vote;
+use tg_utils::Duration;
 
-use tg_voting_contract::state::VotingRules;
+use tg_voting_contract::msg::{UpdateRulesMsg, VoteBatchMode};
+use tg_voting_contract::state::{
+    AutoClose, BallotPruning, PowerBoostCaps, ProposalContentRules, ProposalLimits, VoteBonusCaps,
+    VotingRules,
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -12,6 +17,30 @@ pub struct InstantiateMsg {
     pub rules: VotingRules,
     // this is the group contract that contains the member list
     pub group_addr: String,
+    /// Validation of proposal title and description, disabled by default
+    #[serde(default)]
+    pub content_rules: ProposalContentRules,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct MigrateMsg {
+    /// Replaces limits on proposal payload size, number of dispatched messages and number of
+    /// open proposals, left unchanged if not set
+    pub limits: Option<ProposalLimits>,
+    /// Enables (or replaces) pruning of ballots of old finalized proposals
+    pub pruning: Option<BallotPruning>,
+    /// Replaces validation of proposal title and description, left unchanged if not set
+    pub content_rules: Option<ProposalContentRules>,
+    /// Enables (or replaces) closing of expired proposals in end block
+    pub auto_close: Option<AutoClose>,
+    /// Sets time after proposal expiration in which a passed proposal can still be executed
+    pub execution_period: Option<Duration>,
+    /// Enables (or replaces) caps on bonus points proposals can grant to selected voters
+    pub vote_bonus: Option<VoteBonusCaps>,
+    /// Sets the contract queried at vote time for points added to the voter's group points
+    pub power_provider: Option<String>,
+    /// Replaces caps on boosts returned by the power provider, left unchanged if not set
+    pub power_boost_caps: Option<PowerBoostCaps>,
 }
 
 /// The type of proposal to vote on
@@ -27,6 +56,9 @@ pub enum Proposal {
     },
     /// An open text proposal with no actual logic executed when it passes
     Text {},
+    /// Replaces the voting rules of this contract. Open proposals keep the rules they were
+    /// created with, unless `apply_to_open_proposals` is set.
+    UpdateRules(UpdateRulesMsg),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -36,11 +68,25 @@ pub enum ExecuteMsg {
         title: String,
         description: String,
         proposal: Proposal,
+        /// Labels to filter proposals by, see `ListProposalsByTag`
+        #[serde(default)]
+        tags: Vec<String>,
+        /// Client chosen key making proposing idempotent: if the sender already created a
+        /// proposal with the same key, its id is returned instead of creating a duplicate
+        #[serde(default)]
+        idempotency_key: Option<String>,
     },
     Vote {
         proposal_id: u64,
         vote: Vote,
     },
+    /// Votes on multiple proposals at once. By default any failed vote fails the whole batch,
+    /// in `best_effort` mode failed votes are skipped and reported in the response attributes
+    VoteBatch {
+        votes: Vec<(u64, Vote)>,
+        #[serde(default)]
+        mode: VoteBatchMode,
+    },
     Execute {
         proposal_id: u64,
     },
@@ -62,6 +108,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Return VotingRules
     Rules {},
+    /// Returns ProposalContentRules
+    ProposalContentRules {},
     /// Returns ProposalResponse
     Proposal { proposal_id: u64 },
     /// Returns ProposalListResponse
@@ -69,6 +117,12 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    /// Returns ProposalListResponse of proposals carrying the tag
+    ListProposalsByTag {
+        tag: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     /// Returns ProposalListResponse
     ReverseProposals {
         start_before: Option<u64>,
@@ -2,7 +2,9 @@ mod suite;
 
 use crate::msg::Proposal;
 use crate::multitest::suite::{created_proposal_id, SuiteBuilder};
-use cosmwasm_std::{coin, Addr};
+use cosmwasm_std::{coin, Addr, Decimal};
+use tg_voting_contract::msg::UpdateRulesMsg;
+use tg_voting_contract::state::RulesBuilder;
 
 #[test]
 fn community_pool_can_withdraw_engagement_rewards() {
@@ -72,3 +74,30 @@ fn send_proposal() {
     assert_eq!(suite.token_balance(Addr::unchecked(receiver)).unwrap(), 40);
     assert_eq!(suite.token_balance(suite.contract.clone()).unwrap(), 60);
 }
+
+#[test]
+fn update_rules_proposal() {
+    let voter = "voter";
+
+    let mut suite = SuiteBuilder::new().with_group_member(voter, 1).build();
+
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(60))
+        .build();
+    let resp = suite
+        .propose(
+            voter,
+            "Rules",
+            "Update rules proposal",
+            Proposal::UpdateRules(UpdateRulesMsg {
+                rules: rules.clone(),
+                apply_to_open_proposals: false,
+            }),
+        )
+        .unwrap();
+
+    let proposal_id = created_proposal_id(&resp).unwrap();
+    suite.execute(voter, proposal_id).unwrap();
+
+    assert_eq!(suite.query_rules().unwrap(), rules);
+}
@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result as AnyResult};
 
-use cosmwasm_std::{coin, Addr, CosmosMsg, StdResult};
+use cosmwasm_std::{coin, Addr, CosmosMsg, StdResult, Uint128};
 use cw_multi_test::{AppResponse, Contract, ContractWrapper, CosmosRouter, Executor};
 use tg4::{Member, Tg4ExecuteMsg};
 use tg_bindings::{TgradeMsg, TgradeQuery};
@@ -8,7 +8,7 @@ use tg_bindings_test::TgradeApp;
 
 use tg_voting_contract::state::{RulesBuilder, VotingRules};
 
-use crate::msg::{ExecuteMsg, Proposal};
+use crate::msg::{ExecuteMsg, Proposal, QueryMsg};
 
 fn contract_validator_proposals() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
     let contract = ContractWrapper::new(
@@ -88,6 +88,9 @@ impl SuiteBuilder {
                     preauths_slashing: 1,
                     halflife: None,
                     denom: "ENGAGEMENT".to_owned(),
+                    min_distribution: Uint128::zero(),
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
                 &[],
                 "engagement",
@@ -107,6 +110,9 @@ impl SuiteBuilder {
                     preauths_slashing: 1,
                     halflife: None,
                     denom: self.group_token.clone(),
+                    min_distribution: Uint128::zero(),
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
                 &[],
                 "group",
@@ -122,6 +128,7 @@ impl SuiteBuilder {
                 &crate::msg::InstantiateMsg {
                     group_addr: group_contract.to_string(),
                     rules: self.rules,
+                    content_rules: Default::default(),
                 },
                 &[],
                 "validator-proposals",
@@ -265,6 +272,8 @@ impl Suite {
                 title: title.to_owned(),
                 description: description.to_owned(),
                 proposal,
+                tags: vec![],
+                idempotency_key: None,
             },
             &[],
         )
@@ -279,6 +288,12 @@ impl Suite {
         )
     }
 
+    pub fn query_rules(&self) -> StdResult<VotingRules> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::Rules {})
+    }
+
     /// Shortcut for querying distributable token balance of contract
     pub fn token_balance(&self, owner: Addr) -> StdResult<u128> {
         let amount = self
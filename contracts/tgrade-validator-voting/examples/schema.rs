@@ -3,9 +3,13 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
-use tg_voting_contract::msg::ProposalCreationResponse;
-use tg_voting_contract::state::{ProposalListResponse, ProposalResponse};
-use tgrade_validator_voting::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ValidatorProposal};
+use tg_voting_contract::msg::{ProposalCreationResponse, PrunableProposalsResponse};
+use tg_voting_contract::state::{
+    BallotPruning, ProposalContentRules, ProposalLimits, ProposalListResponse, ProposalResponse,
+};
+use tgrade_validator_voting::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpgradePlanResponse, ValidatorProposal,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -16,9 +20,15 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(QueryMsg), &out_dir, "QueryMsg");
+    export_schema_with_title(&schema_for!(MigrateMsg), &out_dir, "MigrateMsg");
     export_schema(&schema_for!(ValidatorProposal), &out_dir);
     export_schema(&schema_for!(ProposalResponse<ValidatorProposal>), &out_dir);
     export_schema(&schema_for!(ProposalCreationResponse), &out_dir);
+    export_schema(&schema_for!(ProposalLimits), &out_dir);
+    export_schema(&schema_for!(ProposalContentRules), &out_dir);
+    export_schema(&schema_for!(BallotPruning), &out_dir);
+    export_schema(&schema_for!(PrunableProposalsResponse), &out_dir);
+    export_schema(&schema_for!(UpgradePlanResponse), &out_dir);
     export_schema(
         &schema_for!(ProposalListResponse<ValidatorProposal>),
         &out_dir,
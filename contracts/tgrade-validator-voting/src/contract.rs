@@ -1,24 +1,34 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo, StdResult, WasmMsg,
+    to_binary, Binary, CustomQuery, Deps, DepsMut, Env, MessageInfo, Reply, StdResult, WasmMsg,
 };
 
 use cw2::set_contract_version;
-use cw_utils::ensure_from_older_version;
 use tg_bindings::{
     request_privileges, BlockParams, ConsensusParams, EvidenceParams, GovProposal, Privilege,
     PrivilegeChangeMsg, TgradeMsg, TgradeQuery, TgradeSudoMsg,
 };
 
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ValidatorProposal};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UpgradePlanResponse, ValidatorProposal,
+};
+use crate::state::{UpgradePlan, UPGRADE_PLAN, UPGRADE_RULES};
 use crate::ContractError;
 
+use tg_voting_contract::state::MIGRATIONS;
 use tg_voting_contract::{
-    close as execute_close, execute_text, list_proposals, list_text_proposals, list_voters,
-    list_votes, list_votes_by_voter, mark_executed, propose as execute_propose,
-    query_group_contract, query_proposal, query_rules, query_vote, query_voter, reverse_proposals,
-    vote as execute_vote,
+    check_proposal_template, close as execute_close, close_expired, execute_text,
+    execute_with_results, execution_reply, index_open_proposals, list_proposal_templates,
+    list_proposals, list_proposals_by_tag, list_text_proposals, list_voters, list_votes,
+    list_votes_by_voter, mark_executed, override_config, proposal_data, propose as execute_propose,
+    prune_ballots, query_auto_close, query_ballot_pruning, query_execution_result,
+    query_group_contract, query_open_proposals, query_power_boosts, query_power_provider,
+    query_proposal, query_proposal_content_rules, query_proposal_limits, query_proposal_template,
+    query_prunable_proposals, query_rules, query_vote, query_vote_bonuses, query_voter,
+    reverse_proposals, set_proposal_template, update_rules, validate_proposal_limits,
+    vote as execute_vote, vote_batch as execute_vote_batch, ConfigOverrides, InstantiateConfig,
+    EXECUTION_REPLY_ID,
 };
 
 pub type Response = cosmwasm_std::Response<TgradeMsg>;
@@ -36,7 +46,24 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    tg_voting_contract::instantiate(deps, msg.rules, &msg.group_addr).map_err(ContractError::from)
+    if let Some(upgrade_rules) = msg.upgrade_rules {
+        upgrade_rules.validate()?;
+        UPGRADE_RULES.save(deps.storage, &upgrade_rules)?;
+    }
+    let config = InstantiateConfig {
+        rules: msg.rules,
+        group_addr: msg.group_addr,
+        limits: msg.limits,
+        pruning: msg.pruning,
+        content_rules: msg.content_rules,
+        auto_close: msg.auto_close,
+        legacy_total_points: msg.legacy_total_points,
+        execution_period: msg.execution_period,
+        vote_bonus: msg.vote_bonus,
+        power_provider: msg.power_provider,
+        power_boost_caps: msg.power_boost_caps,
+    };
+    tg_voting_contract::instantiate(deps, config).map_err(ContractError::from)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -53,20 +80,43 @@ pub fn execute(
             title,
             description,
             proposal,
+            tags,
+            vote_bonuses,
+            template,
+            idempotency_key,
         } => {
             proposal.validate(deps.as_ref(), &env, &title, &description)?;
-            execute_propose(deps, env, info, title, description, proposal)
-                .map_err(ContractError::from)
+            validate_proposal_limits(deps.as_ref(), &proposal, proposal.messages_count())?;
+            if let Some(template) = template {
+                check_proposal_template(deps.as_ref(), &template, &title, &description, &proposal)?;
+            }
+            execute_propose(
+                deps,
+                env,
+                info,
+                title,
+                description,
+                proposal,
+                tags,
+                vote_bonuses,
+                idempotency_key,
+            )
+            .map_err(ContractError::from)
         }
         Vote { proposal_id, vote } => {
             execute_vote::<ValidatorProposal, TgradeQuery>(deps, env, info, proposal_id, vote)
                 .map_err(ContractError::from)
         }
+        VoteBatch { votes, mode } => {
+            execute_vote_batch::<ValidatorProposal, TgradeQuery>(deps, env, info, votes, mode)
+                .map_err(ContractError::from)
+        }
         Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         Close { proposal_id } => {
             execute_close::<ValidatorProposal, TgradeQuery>(deps, env, info, proposal_id)
                 .map_err(ContractError::from)
         }
+        PruneBallots {} => prune_ballots(deps, env, info).map_err(ContractError::from),
     }
 }
 
@@ -78,19 +128,40 @@ pub fn execute_execute<Q: CustomQuery>(
 ) -> Result<Response, ContractError> {
     use ValidatorProposal::*;
     // anyone can trigger this if the vote passed
-    let proposal = mark_executed::<ValidatorProposal>(deps.storage, env, proposal_id)?;
+    let proposal = mark_executed::<ValidatorProposal>(deps.storage, env.clone(), proposal_id)?;
 
-    let mut res = Response::new();
+    let mut res = Response::new().set_data(proposal_data(proposal_id, &proposal)?);
 
     match proposal.proposal {
         RegisterUpgrade { name, height, info } => {
+            // Voting could take longer than expected
+            if height <= env.block.height {
+                return Err(ContractError::UpgradeHeightPassed(height));
+            }
+            let plan = UpgradePlan {
+                name: name.clone(),
+                height,
+                info: info.clone(),
+                proposal_id,
+            };
+            UPGRADE_PLAN.save(deps.storage, &plan)?;
             res = res.add_message(TgradeMsg::ExecuteGovProposal {
                 title: proposal.title,
                 description: proposal.description,
                 proposal: GovProposal::RegisterUpgrade { name, height, info },
             })
         }
-        CancelUpgrade {} => {
+        CancelUpgrade { name } => {
+            if let Some(name) = name {
+                let plan = UPGRADE_PLAN
+                    .may_load(deps.storage)?
+                    .filter(|plan| plan.height > env.block.height)
+                    .ok_or(ContractError::NoUpgradePlan {})?;
+                if plan.name != name {
+                    return Err(ContractError::UpgradePlanMismatch(plan.name));
+                }
+            }
+            UPGRADE_PLAN.remove(deps.storage);
             res = res.add_message(TgradeMsg::ExecuteGovProposal {
                 title: proposal.title,
                 description: proposal.description,
@@ -184,6 +255,30 @@ pub fn execute_execute<Q: CustomQuery>(
                 proposal: GovProposal::ClearContractAdmin { contract },
             })
         }
+        ExecuteContracts {
+            executions,
+            isolate_failures,
+        } => {
+            let msgs = executions.into_iter().map(|execution| {
+                WasmMsg::Execute {
+                    contract_addr: execution.contract,
+                    msg: execution.msg,
+                    funds: vec![],
+                }
+                .into()
+            });
+            res = if isolate_failures {
+                res.add_submessages(execute_with_results(deps.storage, proposal_id, msgs)?)
+            } else {
+                res.add_messages(msgs)
+            }
+        }
+        SetProposalTemplate { name, template } => {
+            set_proposal_template(deps.storage, &name, template)?
+        }
+        UpdateRules(msg) => {
+            res = res.add_event(update_rules::<ValidatorProposal, Q>(deps, &env, msg)?);
+        }
     };
 
     Ok(res
@@ -219,6 +314,17 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
                 align_limit(limit),
             )?)
         }
+        ListProposalsByTag {
+            tag,
+            start_after,
+            limit,
+        } => to_binary(&list_proposals_by_tag::<ValidatorProposal, TgradeQuery>(
+            deps,
+            env,
+            tag,
+            start_after,
+            align_limit(limit),
+        )?),
         ReverseProposals {
             start_before,
             limit,
@@ -254,42 +360,113 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
         ListTextProposals { start_after, limit } => {
             to_binary(&list_text_proposals(deps, start_after, align_limit(limit))?)
         }
+        ProposalLimits {} => to_binary(&query_proposal_limits(deps)?),
+        OpenProposals { proposer } => to_binary(&query_open_proposals(deps, env, proposer)?),
+        ProposalContentRules {} => to_binary(&query_proposal_content_rules(deps)?),
+        BallotPruning {} => to_binary(&query_ballot_pruning(deps)?),
+        PrunableProposals {} => to_binary(&query_prunable_proposals(deps, env)?),
+        AutoClose {} => to_binary(&query_auto_close(deps)?),
+        VoteBonuses { proposal_id } => to_binary(&query_vote_bonuses(deps, proposal_id)?),
+        PowerProvider {} => to_binary(&query_power_provider(deps)?),
+        PowerBoosts { proposal_id } => to_binary(&query_power_boosts(deps, proposal_id)?),
+        ExecutionResult { proposal_id } => to_binary(&query_execution_result(deps, proposal_id)?),
+        ProposalTemplate { name } => to_binary(&query_proposal_template(deps, name)?),
+        ListProposalTemplates { start_after, limit } => to_binary(&list_proposal_templates(
+            deps,
+            start_after,
+            align_limit(limit),
+        )?),
+        UpgradeRules {} => to_binary(&UPGRADE_RULES.may_load(deps.storage)?),
+        UpgradePlan {} => to_binary(&query_upgrade_plan(deps, env)?),
+    }
+}
+
+fn query_upgrade_plan<Q: CustomQuery>(deps: Deps<Q>, env: Env) -> StdResult<UpgradePlanResponse> {
+    let plan = UPGRADE_PLAN
+        .may_load(deps.storage)?
+        .filter(|plan| plan.height > env.block.height);
+    Ok(UpgradePlanResponse { plan })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut<TgradeQuery>, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        EXECUTION_REPLY_ID => execution_reply(deps.storage, msg).map_err(ContractError::from),
+        _ => Err(ContractError::UnrecognisedReply(msg.id)),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn sudo(
-    _deps: DepsMut<TgradeQuery>,
-    _env: Env,
+    deps: DepsMut<TgradeQuery>,
+    env: Env,
     msg: TgradeSudoMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        TgradeSudoMsg::PrivilegeChange(change) => Ok(privilege_change(change)),
+        TgradeSudoMsg::PrivilegeChange(change) => privilege_change(deps.as_ref(), change),
+        TgradeSudoMsg::EndBlock {} => {
+            close_expired::<ValidatorProposal, TgradeQuery>(deps, &env).map_err(ContractError::from)
+        }
         _ => Err(ContractError::UnsupportedSudoType {}),
     }
 }
 
-fn privilege_change(change: PrivilegeChangeMsg) -> Response {
+fn privilege_change(
+    deps: Deps<TgradeQuery>,
+    change: PrivilegeChangeMsg,
+) -> Result<Response, ContractError> {
     match change {
         PrivilegeChangeMsg::Promoted {} => {
-            let msgs = request_privileges(&[
+            let mut privileges = vec![
                 Privilege::GovProposalExecutor,
                 Privilege::ConsensusParamChanger,
-            ]);
-            Response::new().add_submessages(msgs)
+            ];
+            // End blocker is only needed to close expired proposals
+            if query_auto_close(deps)?.is_some() {
+                privileges.push(Privilege::EndBlocker);
+            }
+            let msgs = request_privileges(&privileges);
+            Ok(Response::new().add_submessages(msgs))
         }
-        PrivilegeChangeMsg::Demoted {} => Response::new(),
+        PrivilegeChangeMsg::Demoted {} => Ok(Response::new()),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-    ensure_from_older_version(
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let mut migration = MIGRATIONS.start(
         deps.storage,
         "crates.io:tgrade_validator_voting_proposals",
         CONTRACT_VERSION,
+        env.block.height,
+    )?;
+    if let Some(upgrade_rules) = msg.upgrade_rules {
+        upgrade_rules.validate()?;
+        UPGRADE_RULES.save(deps.storage, &upgrade_rules)?;
+    }
+    override_config(
+        deps.branch(),
+        ConfigOverrides {
+            limits: msg.limits,
+            pruning: msg.pruning,
+            content_rules: msg.content_rules,
+            auto_close: msg.auto_close,
+            execution_period: msg.execution_period,
+            vote_bonus: msg.vote_bonus,
+            power_provider: msg.power_provider,
+            power_boost_caps: msg.power_boost_caps,
+        },
     )?;
-    Ok(Response::new())
+
+    let mut res = Response::new();
+    // Older versions didn't index proposals by expiration nor count open ones
+    if migration.step("open_proposals_index", "<=0.17.1")? {
+        let indexed = index_open_proposals::<ValidatorProposal>(deps.storage, &env.block)?;
+        res = res.add_attribute("indexed_proposals", indexed.to_string());
+    }
+
+    MIGRATIONS.finish(deps.storage, migration)?;
+    Ok(res)
 }
 
 #[cfg(test)]
@@ -299,8 +476,12 @@ mod tests {
         testing::{mock_env, mock_info},
         Addr, CosmosMsg, Decimal, SubMsg,
     };
-    use tg_utils::Expiration;
-    use tg_voting_contract::state::{proposals, Proposal, Votes, VotingRules};
+    use cosmwasm_std::{Order, QuerierWrapper};
+    use tg_utils::{Duration, Expiration};
+    use tg_voting_contract::state::{
+        proposals, Proposal, ProposalContentRules, ProposalLimits, Votes, VotingRules,
+        PROPOSALS_BY_EXPIRATION,
+    };
 
     use super::*;
     use tg3::Status;
@@ -310,9 +491,41 @@ mod tests {
     #[derive(serde::Serialize)]
     struct DummyMigrateMsg {}
 
+    fn instantiate_contract(deps: DepsMut<TgradeQuery>) {
+        instantiate(
+            deps,
+            mock_env(),
+            mock_info("sender", &[]),
+            InstantiateMsg {
+                rules: VotingRules {
+                    voting_period: 1,
+                    quorum: Decimal::percent(50),
+                    threshold: Decimal::percent(50),
+                    allow_end_early: true,
+                    abstain_non_voters: false,
+                    proposer_policy: None,
+                    tie_break: None,
+                },
+                group_addr: "group_addr".to_owned(),
+                limits: Default::default(),
+                pruning: None,
+                auto_close: None,
+                legacy_total_points: false,
+                execution_period: None,
+                content_rules: Default::default(),
+                vote_bonus: None,
+                upgrade_rules: None,
+                power_provider: None,
+                power_boost_caps: None,
+            },
+        )
+        .unwrap();
+    }
+
     #[test]
     fn register_migrate() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -336,6 +549,9 @@ mod tests {
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -343,7 +559,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -362,6 +581,7 @@ mod tests {
     #[test]
     fn register_cancel_upgrade() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -373,13 +593,16 @@ mod tests {
                     created_by: "mock_person".to_owned(),
                     start_height: env.block.height,
                     expires: Expiration::at_timestamp(env.block.time.plus_seconds(66666)),
-                    proposal: ValidatorProposal::CancelUpgrade {},
+                    proposal: ValidatorProposal::CancelUpgrade { name: None },
                     status: Status::Passed,
                     rules: VotingRules {
                         voting_period: 1,
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -387,7 +610,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -408,6 +634,7 @@ mod tests {
     #[test]
     fn register_pin_codes() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -426,6 +653,9 @@ mod tests {
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -433,7 +663,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -454,6 +687,7 @@ mod tests {
     #[test]
     fn register_unpin_codes() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -472,6 +706,9 @@ mod tests {
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -479,7 +716,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -500,6 +740,7 @@ mod tests {
     #[test]
     fn update_consensus_block_params() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -521,6 +762,9 @@ mod tests {
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -528,7 +772,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -551,6 +798,7 @@ mod tests {
     #[test]
     fn change_params() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -573,6 +821,9 @@ mod tests {
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -580,7 +831,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -605,6 +859,7 @@ mod tests {
     #[test]
     fn update_consensus_evidence_params() {
         let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
         let env = mock_env();
         proposals()
             .save(
@@ -627,6 +882,9 @@ mod tests {
                         quorum: Decimal::percent(50),
                         threshold: Decimal::percent(40),
                         allow_end_early: true,
+                        abstain_non_voters: false,
+                        proposer_policy: None,
+                        tie_break: None,
                     },
                     total_points: 20,
                     votes: Votes {
@@ -634,7 +892,10 @@ mod tests {
                         no: 0,
                         abstain: 0,
                         veto: 0,
+                        absent: 0,
                     },
+                    tags: vec![],
+                    tie_break: None,
                 },
             )
             .unwrap();
@@ -664,6 +925,9 @@ mod tests {
             quorum: Decimal::percent(50),
             threshold: Decimal::percent(50),
             allow_end_early: false,
+            abstain_non_voters: false,
+            proposer_policy: None,
+            tie_break: None,
         };
         let group_addr = "group_addr";
         instantiate(
@@ -676,6 +940,16 @@ mod tests {
             InstantiateMsg {
                 rules,
                 group_addr: group_addr.to_owned(),
+                limits: Default::default(),
+                pruning: None,
+                auto_close: None,
+                legacy_total_points: false,
+                execution_period: None,
+                content_rules: Default::default(),
+                vote_bonus: None,
+                upgrade_rules: None,
+                power_provider: None,
+                power_boost_caps: None,
             },
         )
         .unwrap();
@@ -684,4 +958,222 @@ mod tests {
             from_slice(&query(deps.as_ref(), env, QueryMsg::GroupContract {}).unwrap()).unwrap();
         assert_eq!(query, Addr::unchecked(group_addr));
     }
+
+    #[test]
+    fn zero_proposal_limits_are_rejected() {
+        let mut deps = mock_deps_tgrade();
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("sender", &[]),
+            InstantiateMsg {
+                rules: VotingRules {
+                    voting_period: 1,
+                    quorum: Decimal::percent(50),
+                    threshold: Decimal::percent(50),
+                    allow_end_early: false,
+                    abstain_non_voters: false,
+                    proposer_policy: None,
+                    tie_break: None,
+                },
+                group_addr: "group_addr".to_owned(),
+                limits: ProposalLimits {
+                    max_payload_bytes: None,
+                    max_messages: Some(0),
+                    max_open_proposals: None,
+                    max_open_proposals_per_proposer: None,
+                },
+                pruning: None,
+                auto_close: None,
+                legacy_total_points: false,
+                execution_period: None,
+                content_rules: Default::default(),
+                vote_bonus: None,
+                upgrade_rules: None,
+                power_provider: None,
+                power_boost_caps: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Voting(tg_voting_contract::ContractError::InvalidProposalLimits {})
+        );
+    }
+
+    #[test]
+    fn inverted_proposal_content_rules_are_rejected() {
+        let mut deps = mock_deps_tgrade();
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("sender", &[]),
+            InstantiateMsg {
+                rules: VotingRules {
+                    voting_period: 1,
+                    quorum: Decimal::percent(50),
+                    threshold: Decimal::percent(50),
+                    allow_end_early: false,
+                    abstain_non_voters: false,
+                    proposer_policy: None,
+                    tie_break: None,
+                },
+                group_addr: "group_addr".to_owned(),
+                limits: Default::default(),
+                pruning: None,
+                auto_close: None,
+                legacy_total_points: false,
+                execution_period: None,
+                content_rules: ProposalContentRules {
+                    min_title_length: Some(10),
+                    max_title_length: Some(5),
+                    ..ProposalContentRules::default()
+                },
+                vote_bonus: None,
+                upgrade_rules: None,
+                power_provider: None,
+                power_boost_caps: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Voting(
+                tg_voting_contract::ContractError::InvalidProposalContentRules {}
+            )
+        );
+    }
+
+    #[test]
+    fn migration_indexes_open_proposals() {
+        let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
+        let env = mock_env();
+        let expires = Expiration::at_timestamp(env.block.time.plus_seconds(100));
+
+        // Proposals stored by a version not indexing them by expiration, the last one stored as
+        // open has already passed
+        for (id, status, yes) in [
+            (1, Status::Open, 5),
+            (2, Status::Executed, 5),
+            (3, Status::Open, 5),
+            (4, Status::Open, 15),
+        ] {
+            let proposal = Proposal {
+                title: "Text".to_owned(),
+                description: "Text proposal".to_owned(),
+                created_by: "mock_person".to_owned(),
+                start_height: env.block.height,
+                expires,
+                proposal: ValidatorProposal::Text {},
+                status,
+                rules: VotingRules {
+                    voting_period: 1,
+                    quorum: Decimal::percent(50),
+                    threshold: Decimal::percent(40),
+                    allow_end_early: true,
+                    abstain_non_voters: false,
+                    proposer_policy: None,
+                    tie_break: None,
+                },
+                total_points: 20,
+                votes: Votes::yes(yes),
+                tags: vec![],
+                tie_break: None,
+            };
+            proposals().save(&mut deps.storage, id, &proposal).unwrap();
+        }
+
+        // Deployed contracts were stored under the legacy name
+        set_contract_version(
+            &mut deps.storage,
+            "crates.io:tgrade_validator_voting_proposals",
+            "0.1.0",
+        )
+        .unwrap();
+        let deps_mut = DepsMut {
+            storage: &mut deps.storage,
+            api: &deps.api,
+            querier: QuerierWrapper::new(&deps.querier),
+        };
+        let res = migrate(deps_mut, env.clone(), MigrateMsg::default()).unwrap();
+        assert_eq!(res.attributes, [("indexed_proposals", "3")]);
+
+        let indexed = PROPOSALS_BY_EXPIRATION
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            indexed,
+            [
+                (expires.as_key(), 1),
+                (expires.as_key(), 3),
+                (expires.as_key(), 4)
+            ]
+        );
+
+        // Only proposals still open for voting are counted against the open proposals limits
+        let open = query_open_proposals(deps.as_ref(), env.clone(), Some("mock_person".to_owned()))
+            .unwrap();
+        assert_eq!([open.open, open.open_by_proposer.unwrap()], [2, 2]);
+
+        // Indexing is a one-time step, not repeated by later migrations
+        let deps_mut = DepsMut {
+            storage: &mut deps.storage,
+            api: &deps.api,
+            querier: QuerierWrapper::new(&deps.querier),
+        };
+        let res = migrate(deps_mut, env.clone(), MigrateMsg::default()).unwrap();
+        assert!(res.attributes.is_empty());
+        let open =
+            query_open_proposals(deps.as_ref(), env, Some("mock_person".to_owned())).unwrap();
+        assert_eq!([open.open, open.open_by_proposer.unwrap()], [2, 2]);
+    }
+
+    #[test]
+    fn migration_overrides_config() {
+        let mut deps = mock_deps_tgrade();
+        instantiate_contract(deps.as_mut());
+        let env = mock_env();
+        set_contract_version(
+            &mut deps.storage,
+            "crates.io:tgrade_validator_voting_proposals",
+            "0.1.0",
+        )
+        .unwrap();
+
+        let limits = ProposalLimits {
+            max_open_proposals: Some(3),
+            ..ProposalLimits::default()
+        };
+        let deps_mut = DepsMut {
+            storage: &mut deps.storage,
+            api: &deps.api,
+            querier: QuerierWrapper::new(&deps.querier),
+        };
+        let msg = MigrateMsg {
+            limits: Some(limits.clone()),
+            ..MigrateMsg::default()
+        };
+        migrate(deps_mut, env.clone(), msg).unwrap();
+        assert_eq!(query_proposal_limits(deps.as_ref()).unwrap(), limits);
+        // Settings not overridden are left unchanged
+        assert_eq!(query_auto_close(deps.as_ref()).unwrap(), None);
+
+        // Overrides are validated as on instantiation
+        let deps_mut = DepsMut {
+            storage: &mut deps.storage,
+            api: &deps.api,
+            querier: QuerierWrapper::new(&deps.querier),
+        };
+        let msg = MigrateMsg {
+            execution_period: Some(Duration::new(0)),
+            ..MigrateMsg::default()
+        };
+        let err = migrate(deps_mut, env, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Voting(tg_voting_contract::ContractError::InvalidExecutionPeriod {})
+        );
+    }
 }
@@ -48,11 +48,35 @@ pub enum ContractError {
     #[error("Invalid upgrade height: {0}")]
     InvalidUpgradeHeight(u64),
 
+    #[error("Upgrade height {height} is too close, it has to be at least {min_height}")]
+    UpgradeHeightTooClose { height: u64, min_height: u64 },
+
+    #[error("Upgrade height {0} already passed")]
+    UpgradeHeightPassed(u64),
+
+    #[error("Invalid upgrade rules: block time cannot be zero")]
+    InvalidUpgradeRules {},
+
+    #[error("No upgrade is scheduled")]
+    NoUpgradePlan {},
+
+    #[error("Scheduled upgrade is {0}")]
+    UpgradePlanMismatch(String),
+
     #[error("Invalid consensus params: All cannot be none")]
     InvalidConsensusParams {},
 
     #[error("Empty new admin")]
     EmptyAdmin {},
+
+    #[error("Empty contract executions list")]
+    EmptyExecutions {},
+
+    #[error("Execute message cannot be an empty string")]
+    ExecuteMsgCannotBeEmptyString {},
+
+    #[error("Unrecognised reply id: {0}")]
+    UnrecognisedReply(u64),
 }
 
 impl From<tg_voting_contract::ContractError> for ContractError {
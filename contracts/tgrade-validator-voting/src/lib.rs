@@ -3,6 +3,7 @@ pub mod error;
 pub mod msg;
 #[cfg(test)]
 mod multitest;
+pub mod state;
 pub mod validate;
 
 use error::ContractError;
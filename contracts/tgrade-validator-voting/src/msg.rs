@@ -4,16 +4,83 @@ use serde::{Deserialize, Serialize};
 use cosmwasm_std::Binary;
 use tg3::Vote;
 use tg_bindings::ParamChange;
+use tg_utils::Duration;
 
-use tg_voting_contract::state::VotingRules;
+use crate::state::{UpgradePlan, UpgradeRules};
+use tg_voting_contract::msg::{UpdateRulesMsg, VoteBatchMode};
+use tg_voting_contract::state::{
+    AutoClose, BallotPruning, PowerBoostCaps, ProposalContentRules, ProposalLimits,
+    ProposalTemplate, VoteBonus, VoteBonusCaps, VotingRules,
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct InstantiateMsg {
     pub rules: VotingRules,
     // this is the group contract that contains the member list
     pub group_addr: String,
+    /// Limits on proposal payload size, number of dispatched messages and number of open
+    /// proposals
+    #[serde(default)]
+    pub limits: ProposalLimits,
+    /// Pruning of ballots of old finalized proposals, disabled if not set
+    #[serde(default)]
+    pub pruning: Option<BallotPruning>,
+    /// Validation of proposal title and description, disabled by default
+    #[serde(default)]
+    pub content_rules: ProposalContentRules,
+    /// Closing of expired proposals in end block, disabled if not set
+    #[serde(default)]
+    pub auto_close: Option<AutoClose>,
+    /// Take proposal total points from group's current total, instead of the total at proposal
+    /// start height. Only needed for groups not keeping history of total points.
+    #[serde(default)]
+    pub legacy_total_points: bool,
+    /// Time after proposal expiration in which a passed proposal can still be executed.
+    /// Passed proposals can be executed any time if not set.
+    #[serde(default)]
+    pub execution_period: Option<Duration>,
+    /// Caps on bonus points proposals can grant to selected voters, bonuses are not allowed
+    /// if not set
+    #[serde(default)]
+    pub vote_bonus: Option<VoteBonusCaps>,
+    /// Constraints on heights of proposed upgrades. If not set, upgrade height only cannot be
+    /// in the past.
+    #[serde(default)]
+    pub upgrade_rules: Option<UpgradeRules>,
+    /// Contract queried at vote time for points added to the voter's group points, see
+    /// `tg_voting_contract::msg::PowerProviderQueryMsg`. No boosts if not set.
+    #[serde(default)]
+    pub power_provider: Option<String>,
+    /// Caps on boosts returned by the power provider, required if `power_provider` is set
+    #[serde(default)]
+    pub power_boost_caps: Option<PowerBoostCaps>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct MigrateMsg {
+    /// Sets constraints on heights of proposed upgrades, left unchanged if not set
+    pub upgrade_rules: Option<UpgradeRules>,
+    /// Replaces limits on proposal payload size, number of dispatched messages and number of
+    /// open proposals, left unchanged if not set
+    pub limits: Option<ProposalLimits>,
+    /// Enables (or replaces) pruning of ballots of old finalized proposals
+    pub pruning: Option<BallotPruning>,
+    /// Replaces validation of proposal title and description, left unchanged if not set
+    pub content_rules: Option<ProposalContentRules>,
+    /// Enables (or replaces) closing of expired proposals in end block
+    pub auto_close: Option<AutoClose>,
+    /// Sets time after proposal expiration in which a passed proposal can still be executed
+    pub execution_period: Option<Duration>,
+    /// Enables (or replaces) caps on bonus points proposals can grant to selected voters
+    pub vote_bonus: Option<VoteBonusCaps>,
+    /// Sets the contract queried at vote time for points added to the voter's group points
+    pub power_provider: Option<String>,
+    /// Replaces caps on boosts returned by the power provider, left unchanged if not set
+    pub power_boost_caps: Option<PowerBoostCaps>,
+}
+
+// Proposals are much larger than other messages, but boxing them would only complicate handling
+#[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -21,17 +88,40 @@ pub enum ExecuteMsg {
         title: String,
         description: String,
         proposal: ValidatorProposal,
+        /// Labels to filter proposals by, see `ListProposalsByTag`
+        #[serde(default)]
+        tags: Vec<String>,
+        /// Bonus points added to listed voters' group points for this proposal only,
+        /// see `vote_bonus` in `InstantiateMsg`
+        #[serde(default)]
+        vote_bonuses: Vec<VoteBonus>,
+        /// Name of the proposal template the proposal is checked against
+        #[serde(default)]
+        template: Option<String>,
+        /// Client chosen key making proposing idempotent: if the sender already created a
+        /// proposal with the same key, its id is returned instead of creating a duplicate
+        #[serde(default)]
+        idempotency_key: Option<String>,
     },
     Vote {
         proposal_id: u64,
         vote: Vote,
     },
+    /// Votes on multiple proposals at once. By default any failed vote fails the whole batch,
+    /// in `best_effort` mode failed votes are skipped and reported in the response attributes
+    VoteBatch {
+        votes: Vec<(u64, Vote)>,
+        #[serde(default)]
+        mode: VoteBatchMode,
+    },
     Execute {
         proposal_id: u64,
     },
     Close {
         proposal_id: u64,
     },
+    /// Removes ballots of proposals finalized long enough ago. Can be called by anyone
+    PruneBallots {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -49,7 +139,12 @@ pub enum ValidatorProposal {
         /// such as a git commit that validators could automatically upgrade to
         info: String,
     },
-    CancelUpgrade {},
+    /// Cancels the scheduled upgrade. If `name` is set, the upgrade scheduled by this contract
+    /// has to have this name, so the proposal never cancels a different upgrade than intended.
+    CancelUpgrade {
+        #[serde(default)]
+        name: Option<String>,
+    },
     /// all code ids that should be pinned in cache for high performance
     PinCodes(Vec<u64>),
     /// all code ids that should be removed from cache to free space
@@ -83,10 +178,14 @@ pub enum ValidatorProposal {
     Text {},
     /// Defines a proposal to change one or more parameters.
     ChangeParams(Vec<ParamChange>),
+    /// Promotes the contract to a privileged one, so it can request privileges (eg. EndBlocker)
+    /// when notified about the promotion. The contract has to opt in by having this contract as
+    /// its admin.
     PromoteToPrivilegedContract {
         /// The contract address to be promoted
         contract: String,
     },
+    /// Demotes the privileged contract, removing all its privileges
     DemotePrivilegedContract {
         /// The contract address to be demoted
         contract: String,
@@ -101,6 +200,32 @@ pub enum ValidatorProposal {
         /// The contract address to be cleared
         contract: String,
     },
+    /// Executes messages on other contracts. By default all executions are atomic - a single
+    /// failure reverts all of them. With `isolate_failures` every execution is dispatched as an
+    /// independent submessage, so failed ones are reverted alone, and their outcomes are recorded
+    /// (see `ExecutionResult` query).
+    ExecuteContracts {
+        executions: Vec<ContractExecution>,
+        #[serde(default)]
+        isolate_failures: bool,
+    },
+    /// Registers a named proposal template, replacing the previous one with the same name.
+    /// No template removes it.
+    SetProposalTemplate {
+        name: String,
+        template: Option<ProposalTemplate>,
+    },
+    /// Replaces the voting rules of this contract. Open proposals keep the rules they were
+    /// created with, unless `apply_to_open_proposals` is set.
+    UpdateRules(UpdateRulesMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ContractExecution {
+    /// The contract address to be executed
+    pub contract: String,
+    /// Encoded execute message
+    pub msg: Binary,
 }
 
 // We can also add this as a tg3 extension
@@ -116,6 +241,12 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    /// Returns ProposalListResponse of proposals carrying the tag
+    ListProposalsByTag {
+        tag: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     /// Returns ProposalListResponse
     ReverseProposals {
         start_before: Option<u64>,
@@ -149,4 +280,42 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    /// Returns ProposalLimits
+    ProposalLimits {},
+    /// Returns OpenProposalsResponse, with numbers of open proposals in total and of the
+    /// `proposer` if set
+    OpenProposals { proposer: Option<String> },
+    /// Returns ProposalContentRules
+    ProposalContentRules {},
+    /// Returns Option<BallotPruning>
+    BallotPruning {},
+    /// Returns PrunableProposalsResponse
+    PrunableProposals {},
+    /// Returns Option<AutoClose>
+    AutoClose {},
+    /// Returns VoteBonusesResponse
+    VoteBonuses { proposal_id: u64 },
+    /// Returns Option<Addr>
+    PowerProvider {},
+    /// Returns PowerBoostsResponse
+    PowerBoosts { proposal_id: u64 },
+    /// Returns ExecutionResultResponse
+    ExecutionResult { proposal_id: u64 },
+    /// Returns Option<ProposalTemplate>
+    ProposalTemplate { name: String },
+    /// Returns ProposalTemplateListResponse
+    ListProposalTemplates {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns Option<UpgradeRules>
+    UpgradeRules {},
+    /// Returns UpgradePlanResponse
+    UpgradePlan {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct UpgradePlanResponse {
+    /// Upgrade scheduled by this contract, if its height is not reached yet
+    pub plan: Option<UpgradePlan>,
 }
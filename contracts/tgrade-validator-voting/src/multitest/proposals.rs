@@ -1,7 +1,14 @@
-use cosmwasm_std::Decimal;
+use crate::msg::ValidatorProposal;
+use cosmwasm_std::{coins, Decimal};
+use tg3::Status;
 use tg_bindings_test::UpgradePlan;
-use tg_voting_contract::state::RulesBuilder;
+use tg_voting_contract::msg::UpdateRulesMsg;
+use tg_voting_contract::state::{
+    MessageExecutionResult, ProposalLimits, ProposalTemplate, RulesBuilder,
+};
+use tg_voting_contract::ContractError;
 
+use super::hackatom;
 use super::suite::{get_proposal_id, SuiteBuilder};
 
 #[test]
@@ -98,7 +105,7 @@ fn cancel_upgrade() {
         Some(UpgradePlan::new("v2", 333, "detailed info"))
     );
 
-    let proposal = suite.propose_cancel_upgrade("member").unwrap();
+    let proposal = suite.propose_cancel_upgrade("member", None).unwrap();
     let proposal_id = get_proposal_id(&proposal).unwrap();
     suite.execute("member", proposal_id).unwrap();
 
@@ -106,6 +113,109 @@ fn cancel_upgrade() {
     assert_eq!(suite.check_upgrade().unwrap(), None);
 }
 
+#[test]
+fn upgrade_height_validation() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .with_upgrade_rules(5, 100)
+        .build();
+
+    // Voting takes 14 days, which is 241920 blocks of 5 seconds
+    let min_height = suite.app.block_info().height + 241_920 + 100;
+    let err = suite
+        .propose_upgrade("member", "v2", min_height - 1, "detailed info")
+        .unwrap_err();
+    assert_eq!(
+        crate::ContractError::UpgradeHeightTooClose {
+            height: min_height - 1,
+            min_height
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .propose_upgrade("member", "v2", min_height, "detailed info")
+        .unwrap();
+}
+
+#[test]
+fn upgrade_height_passed_during_voting() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    let height = suite.app.block_info().height + 10;
+    let proposal = suite
+        .propose_upgrade("member", "v2", height, "detailed info")
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+
+    suite.app.advance_blocks(10);
+    let err = suite.execute("member", proposal_id).unwrap_err();
+    assert_eq!(
+        crate::ContractError::UpgradeHeightPassed(height),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.check_upgrade().unwrap(), None);
+}
+
+#[test]
+fn cancel_upgrade_by_name() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    // Nothing to cancel yet
+    let err = suite.propose_cancel_upgrade("member", "v2").unwrap_err();
+    assert_eq!(
+        crate::ContractError::NoUpgradePlan {},
+        err.downcast().unwrap()
+    );
+
+    let proposal = suite
+        .propose_upgrade("member", "v2", 333, "detailed info")
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+    assert_eq!(
+        suite.query_upgrade_plan().unwrap(),
+        Some(crate::state::UpgradePlan {
+            name: "v2".to_owned(),
+            height: 333,
+            info: "detailed info".to_owned(),
+            proposal_id,
+        })
+    );
+
+    let err = suite.propose_cancel_upgrade("member", "v3").unwrap_err();
+    assert_eq!(
+        crate::ContractError::UpgradePlanMismatch("v2".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let proposal = suite.propose_cancel_upgrade("member", "v2").unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+
+    assert_eq!(suite.query_upgrade_plan().unwrap(), None);
+    assert_eq!(suite.check_upgrade().unwrap(), None);
+}
+
 #[test]
 fn change_params() {
     let rules = RulesBuilder::new()
@@ -130,3 +240,255 @@ fn change_params() {
         Some(vec![("foo/bar".to_string(), "baz".to_string())])
     );
 }
+
+#[test]
+fn proposal_limits() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+    let limits = ProposalLimits {
+        max_payload_bytes: Some(64),
+        max_messages: None,
+        max_open_proposals: None,
+        max_open_proposals_per_proposer: None,
+    };
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .with_proposal_limits(limits.clone())
+        .build();
+
+    assert_eq!(suite.query_proposal_limits().unwrap(), limits);
+
+    // Small enough payload is accepted
+    suite.propose_pin("member", &[1, 2, 3]).unwrap();
+
+    // Payload exceeding the limit is rejected
+    let code_ids: Vec<u64> = (1..=30).collect();
+    let err = suite.propose_pin("member", &code_ids).unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        crate::error::ContractError::Voting(ContractError::ProposalPayloadTooLarge { max: 64, .. })
+    ));
+}
+
+#[test]
+fn execute_contracts_atomically() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    let owner = suite.owner.clone();
+    let hackatom_id = suite.app.store_code(hackatom::contract());
+    let hackatom = suite.instantiate_hackatom_contract(owner, hackatom_id, "beneficiary");
+    suite.fund(&hackatom, &coins(100, "utgd"));
+
+    // Failing execution reverts the whole proposal execution
+    let proposal = suite
+        .propose_execute_contracts("member", &[hackatom.as_str(), "nobody"], false)
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap_err();
+    assert_eq!(
+        suite.query_proposal_status(proposal_id).unwrap(),
+        Status::Passed
+    );
+    assert_eq!(suite.balance("beneficiary", "utgd").unwrap(), 0);
+
+    let proposal = suite
+        .propose_execute_contracts("member", &[hackatom.as_str()], false)
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+    assert_eq!(suite.balance("beneficiary", "utgd").unwrap(), 100);
+    // Outcomes are only recorded for isolated executions
+    assert_eq!(suite.query_execution_result(proposal_id).unwrap(), None);
+}
+
+#[test]
+fn execute_contracts_with_isolated_failures() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    let owner = suite.owner.clone();
+    let hackatom_id = suite.app.store_code(hackatom::contract());
+    let hackatom = suite.instantiate_hackatom_contract(owner, hackatom_id, "beneficiary");
+    suite.fund(&hackatom, &coins(100, "utgd"));
+
+    let proposal = suite
+        .propose_execute_contracts("member", &["nobody", hackatom.as_str()], true)
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+
+    // Failed execution doesn't revert the successful one
+    assert_eq!(
+        suite.query_proposal_status(proposal_id).unwrap(),
+        Status::Executed
+    );
+    assert_eq!(suite.balance("beneficiary", "utgd").unwrap(), 100);
+
+    let results = suite.query_execution_result(proposal_id).unwrap().unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(!results[0].success);
+    assert!(results[0].error.is_some());
+    assert_eq!(
+        results[1],
+        MessageExecutionResult {
+            success: true,
+            error: None,
+        }
+    );
+}
+
+#[test]
+fn execute_contracts_validation() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    let err = suite
+        .propose_execute_contracts("member", &[], true)
+        .unwrap_err();
+    assert_eq!(
+        crate::error::ContractError::EmptyExecutions {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn proposal_templates() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    let template = ProposalTemplate {
+        title_prefix: "[Pin] ".to_owned(),
+        description: "## Reason\n".to_owned(),
+        required_sections: vec!["## Reason".to_owned()],
+        allowed_kinds: vec!["pin_codes".to_owned()],
+    };
+
+    // Templates are registered by governance
+    let proposal = suite
+        .propose(
+            "member",
+            "proposal title",
+            "proposal description",
+            ValidatorProposal::SetProposalTemplate {
+                name: "pin".to_owned(),
+                template: Some(template.clone()),
+            },
+        )
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+    assert_eq!(
+        suite.query_proposal_template("pin").unwrap(),
+        Some(template)
+    );
+
+    let err = suite
+        .propose_with_template(
+            "member",
+            "pin",
+            "[Pin] codes",
+            "## Reason\nspeed",
+            ValidatorProposal::UnpinCodes(vec![1]),
+        )
+        .unwrap_err();
+    assert_eq!(
+        crate::error::ContractError::Voting(ContractError::TemplateKindNotAllowed(
+            "unpin_codes".to_owned()
+        )),
+        err.downcast().unwrap()
+    );
+
+    let proposal = suite
+        .propose_with_template(
+            "member",
+            "pin",
+            "[Pin] codes",
+            "## Reason\nspeed",
+            ValidatorProposal::PinCodes(vec![1]),
+        )
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+    assert!(suite.check_pinned(1).unwrap());
+}
+
+#[test]
+fn update_rules() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_group_member("member", 1)
+        .with_voting_rules(rules)
+        .build();
+
+    let new_rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(60))
+        .with_quorum(Decimal::percent(40))
+        .build();
+
+    // Rules are updated by governance
+    let proposal = suite
+        .propose(
+            "member",
+            "proposal title",
+            "proposal description",
+            ValidatorProposal::UpdateRules(UpdateRulesMsg {
+                rules: new_rules.clone(),
+                apply_to_open_proposals: false,
+            }),
+        )
+        .unwrap();
+    let proposal_id = get_proposal_id(&proposal).unwrap();
+    suite.execute("member", proposal_id).unwrap();
+    assert_eq!(suite.query_rules().unwrap(), new_rules);
+
+    // Invalid rules are rejected on proposing
+    let invalid = RulesBuilder::new()
+        .with_threshold(Decimal::percent(40))
+        .build();
+    let err = suite
+        .propose(
+            "member",
+            "proposal title",
+            "proposal description",
+            ValidatorProposal::UpdateRules(UpdateRulesMsg {
+                rules: invalid,
+                apply_to_open_proposals: false,
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        crate::error::ContractError::Voting(ContractError::InvalidThreshold(Decimal::percent(40))),
+        err.downcast().unwrap()
+    );
+}
@@ -1,6 +1,6 @@
 use anyhow::Result as AnyResult;
 
-use cosmwasm_std::{to_binary, Addr, ContractInfoResponse, Decimal};
+use cosmwasm_std::{to_binary, Addr, Coin, ContractInfoResponse, Decimal, Uint128};
 use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
 use tg3::Status;
 use tg4::{Member, Tg4ExecuteMsg};
@@ -9,7 +9,11 @@ use tg_bindings_test::{TgradeApp, UpgradePlan};
 
 use crate::msg::ValidatorProposal;
 use crate::msg::*;
-use tg_voting_contract::state::{ProposalResponse, VotingRules};
+use crate::state::UpgradeRules;
+use tg_voting_contract::msg::ExecutionResultResponse;
+use tg_voting_contract::state::{
+    MessageExecutionResult, ProposalLimits, ProposalResponse, ProposalTemplate, VotingRules,
+};
 use tg_voting_contract::ContractError;
 
 pub fn get_proposal_id(response: &AppResponse) -> Result<u64, std::num::ParseIntError> {
@@ -22,7 +26,8 @@ fn contract_validator_proposals() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
         crate::contract::instantiate,
         crate::contract::query,
     )
-    .with_sudo(crate::contract::sudo);
+    .with_sudo(crate::contract::sudo)
+    .with_reply(crate::contract::reply);
 
     Box::new(contract)
 }
@@ -41,6 +46,8 @@ pub struct SuiteBuilder {
     engagement_members: Vec<Member>,
     group_members: Vec<Member>,
     rules: VotingRules,
+    limits: ProposalLimits,
+    upgrade_rules: Option<UpgradeRules>,
 }
 
 impl SuiteBuilder {
@@ -53,7 +60,12 @@ impl SuiteBuilder {
                 quorum: Decimal::zero(),
                 threshold: Decimal::zero(),
                 allow_end_early: false,
+                abstain_non_voters: false,
+                proposer_policy: None,
+                tie_break: None,
             },
+            limits: ProposalLimits::default(),
+            upgrade_rules: None,
         }
     }
 
@@ -71,6 +83,19 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_proposal_limits(mut self, limits: ProposalLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_upgrade_rules(mut self, block_time: u64, margin_blocks: u64) -> Self {
+        self.upgrade_rules = Some(UpgradeRules {
+            block_time,
+            margin_blocks,
+        });
+        self
+    }
+
     #[track_caller]
     pub fn build(self) -> Suite {
         let owner = Addr::unchecked("owner");
@@ -91,6 +116,9 @@ impl SuiteBuilder {
                     preauths_slashing: 1,
                     halflife: None,
                     denom: "ENGAGEMENT".to_owned(),
+                    min_distribution: Uint128::zero(),
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
                 &[],
                 "engagement",
@@ -110,6 +138,9 @@ impl SuiteBuilder {
                     preauths_slashing: 1,
                     halflife: None,
                     denom: "GROUP".to_owned(),
+                    min_distribution: Uint128::zero(),
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
                 &[],
                 "group",
@@ -125,6 +156,16 @@ impl SuiteBuilder {
                 &crate::msg::InstantiateMsg {
                     group_addr: group_contract.to_string(),
                     rules: self.rules,
+                    limits: self.limits,
+                    pruning: None,
+                    auto_close: None,
+                    legacy_total_points: false,
+                    execution_period: None,
+                    content_rules: Default::default(),
+                    vote_bonus: None,
+                    upgrade_rules: self.upgrade_rules,
+                    power_provider: None,
+                    power_boost_caps: None,
                 },
                 &[],
                 "validator-proposals",
@@ -177,6 +218,34 @@ impl Suite {
                 title: title.to_owned(),
                 description: description.to_owned(),
                 proposal,
+                tags: vec![],
+                vote_bonuses: vec![],
+                template: None,
+                idempotency_key: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn propose_with_template(
+        &mut self,
+        executor: &str,
+        template: &str,
+        title: &str,
+        description: &str,
+        proposal: ValidatorProposal,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.contract.clone(),
+            &ExecuteMsg::Propose {
+                title: title.to_owned(),
+                description: description.to_owned(),
+                proposal,
+                tags: vec![],
+                vote_bonuses: vec![],
+                template: Some(template.to_owned()),
+                idempotency_key: None,
             },
             &[],
         )
@@ -201,6 +270,32 @@ impl Suite {
         )
     }
 
+    /// Proposes executing hackatom `ExecuteMsg` on all given contracts
+    pub fn propose_execute_contracts(
+        &mut self,
+        executor: &str,
+        contracts: &[&str],
+        isolate_failures: bool,
+    ) -> AnyResult<AppResponse> {
+        let msg = to_binary(&crate::multitest::hackatom::ExecuteMsg {})?;
+        let executions = contracts
+            .iter()
+            .map(|contract| ContractExecution {
+                contract: contract.to_string(),
+                msg: msg.clone(),
+            })
+            .collect();
+        self.propose(
+            executor,
+            "proposal title",
+            "proposal description",
+            ValidatorProposal::ExecuteContracts {
+                executions,
+                isolate_failures,
+            },
+        )
+    }
+
     pub fn propose_pin(&mut self, executor: &str, code_ids: &[u64]) -> AnyResult<AppResponse> {
         self.propose(
             executor,
@@ -238,12 +333,18 @@ impl Suite {
         )
     }
 
-    pub fn propose_cancel_upgrade(&mut self, executor: &str) -> AnyResult<AppResponse> {
+    pub fn propose_cancel_upgrade(
+        &mut self,
+        executor: &str,
+        name: impl Into<Option<&'static str>>,
+    ) -> AnyResult<AppResponse> {
         self.propose(
             executor,
             "proposal title",
             "proposal description",
-            ValidatorProposal::CancelUpgrade {},
+            ValidatorProposal::CancelUpgrade {
+                name: name.into().map(str::to_owned),
+            },
         )
     }
 
@@ -299,6 +400,66 @@ impl Suite {
         Ok(prop.status)
     }
 
+    pub fn query_upgrade_plan(&self) -> Result<Option<crate::state::UpgradePlan>, ContractError> {
+        let resp: UpgradePlanResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::UpgradePlan {})?;
+        Ok(resp.plan)
+    }
+
+    pub fn query_rules(&self) -> Result<VotingRules, ContractError> {
+        let rules = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::Rules {})?;
+        Ok(rules)
+    }
+
+    pub fn query_proposal_template(
+        &self,
+        name: &str,
+    ) -> Result<Option<ProposalTemplate>, ContractError> {
+        let template = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ProposalTemplate {
+                name: name.to_owned(),
+            },
+        )?;
+        Ok(template)
+    }
+
+    pub fn query_execution_result(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<Vec<MessageExecutionResult>>, ContractError> {
+        let resp: ExecutionResultResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ExecutionResult { proposal_id },
+        )?;
+        Ok(resp.results)
+    }
+
+    pub fn fund(&mut self, addr: &Addr, funds: &[Coin]) {
+        self.app
+            .init_modules(|router, _, storage| {
+                router.bank.init_balance(storage, addr, funds.to_vec())
+            })
+            .unwrap();
+    }
+
+    pub fn balance(&self, addr: &str, denom: &str) -> Result<u128, ContractError> {
+        Ok(self.app.wrap().query_balance(addr, denom)?.amount.u128())
+    }
+
+    pub fn query_proposal_limits(&self) -> Result<ProposalLimits, ContractError> {
+        let limits: ProposalLimits = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::ProposalLimits {})?;
+        Ok(limits)
+    }
+
     pub fn instantiate_hackatom_contract(
         &mut self,
         owner: Addr,
@@ -0,0 +1,48 @@
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ContractError;
+
+/// Constraints on heights of upgrades scheduled with `RegisterUpgrade` proposals, so the
+/// upgrade height cannot be reached before the proposal voting is over
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct UpgradeRules {
+    /// Expected time between blocks in seconds, used to convert the voting period to blocks
+    pub block_time: u64,
+    /// Additional blocks required between the end of voting and the upgrade height, giving
+    /// time to execute the proposal and to prepare the upgrade
+    #[serde(default)]
+    pub margin_blocks: u64,
+}
+
+impl UpgradeRules {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.block_time == 0 {
+            return Err(ContractError::InvalidUpgradeRules {});
+        }
+        Ok(())
+    }
+
+    /// Lowest upgrade height which can be proposed at `height`, with voting lasting
+    /// `voting_period` seconds
+    pub fn min_height(&self, height: u64, voting_period: u64) -> u64 {
+        let voting_blocks = (voting_period + self.block_time - 1) / self.block_time;
+        height
+            .saturating_add(voting_blocks)
+            .saturating_add(self.margin_blocks)
+    }
+}
+
+/// Upgrade scheduled by an executed `RegisterUpgrade` proposal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct UpgradePlan {
+    pub name: String,
+    pub height: u64,
+    pub info: String,
+    /// Proposal which scheduled the upgrade
+    pub proposal_id: u64,
+}
+
+pub const UPGRADE_RULES: Item<UpgradeRules> = Item::new("upgrade_rules");
+pub const UPGRADE_PLAN: Item<UpgradePlan> = Item::new("upgrade_plan");
@@ -4,8 +4,11 @@ use cosmwasm_std::{
 };
 
 use tg_bindings::TgradeQuery;
+use tg_voting_contract::query_rules;
+use tg_voting_contract::state::validate_template_name;
 
 use crate::msg::ValidatorProposal;
+use crate::state::{UPGRADE_PLAN, UPGRADE_RULES};
 use crate::ContractError;
 
 impl ValidatorProposal {
@@ -54,7 +57,7 @@ impl ValidatorProposal {
                 };
                 // Migrate contract needs confirming that migration sender (validator voting contract) is an admin
                 // of target contract
-                confirm_admin_in_contract(deps, env, contract.clone())?;
+                confirm_admin_in_contract(deps, env, contract.clone(), "migrate")?;
             }
             ValidatorProposal::RegisterUpgrade {
                 name,
@@ -67,6 +70,25 @@ impl ValidatorProposal {
                 if height < &env.block.height {
                     return Err(ContractError::InvalidUpgradeHeight(*height));
                 }
+                if let Some(rules) = UPGRADE_RULES.may_load(deps.storage)? {
+                    let voting_period = query_rules(deps)?.voting_period_secs();
+                    let min_height = rules.min_height(env.block.height, voting_period);
+                    if *height < min_height {
+                        return Err(ContractError::UpgradeHeightTooClose {
+                            height: *height,
+                            min_height,
+                        });
+                    }
+                }
+            }
+            ValidatorProposal::CancelUpgrade { name: Some(name) } => {
+                let plan = UPGRADE_PLAN
+                    .may_load(deps.storage)?
+                    .filter(|plan| plan.height >= env.block.height)
+                    .ok_or(ContractError::NoUpgradePlan {})?;
+                if &plan.name != name {
+                    return Err(ContractError::UpgradePlanMismatch(plan.name));
+                }
             }
             ValidatorProposal::UpdateConsensusBlockParams { max_bytes, max_gas } => {
                 if max_bytes.is_none() && max_gas.is_none() {
@@ -91,21 +113,56 @@ impl ValidatorProposal {
                     return Err(ContractError::EmptyAdmin {});
                 }
             }
+            ValidatorProposal::PromoteToPrivilegedContract { contract } => {
+                // Contract opts in for privileges by putting itself under governance control, so
+                // a misbehaving privileged contract can always be migrated
+                confirm_admin_in_contract(deps, env, contract.clone(), "promote")?;
+            }
+            ValidatorProposal::DemotePrivilegedContract { contract } => {
+                // Demoting doesn't need the contract consent, but it has to exist
+                query_contract_info(deps, contract.clone())?;
+            }
+            ValidatorProposal::ExecuteContracts { executions, .. } => {
+                if executions.is_empty() {
+                    return Err(ContractError::EmptyExecutions {});
+                }
+                for execution in executions {
+                    deps.api.addr_validate(&execution.contract)?;
+                    if execution.msg.is_empty() {
+                        return Err(ContractError::ExecuteMsgCannotBeEmptyString {});
+                    }
+                }
+            }
+            ValidatorProposal::SetProposalTemplate { name, template } => {
+                validate_template_name(name)?;
+                if let Some(template) = template {
+                    template.validate()?;
+                }
+            }
+            ValidatorProposal::UpdateRules(msg) => msg.rules.validate()?,
             ValidatorProposal::ClearContractAdmin { .. }
-            | ValidatorProposal::PromoteToPrivilegedContract { .. }
-            | ValidatorProposal::DemotePrivilegedContract { .. }
-            | ValidatorProposal::CancelUpgrade {}
+            | ValidatorProposal::CancelUpgrade { name: None }
             | ValidatorProposal::Text {} => {}
         }
         Ok(())
     }
+
+    /// Number of messages dispatched when this proposal is executed
+    pub fn messages_count(&self) -> usize {
+        match self {
+            ValidatorProposal::Text {}
+            | ValidatorProposal::SetProposalTemplate { .. }
+            | ValidatorProposal::UpdateRules(_) => 0,
+            ValidatorProposal::ExecuteContracts { executions, .. } => executions.len(),
+            _ => 1,
+        }
+    }
 }
 
-fn confirm_admin_in_contract<Q: CustomQuery>(
+fn query_contract_info<Q: CustomQuery>(
     deps: Deps<Q>,
-    env: &Env,
     contract_addr: String,
-) -> Result<(), ContractError> {
+) -> Result<ContractInfoResponse, ContractError> {
     use ContractError::*;
 
     let contract_query = QueryRequest::<Empty>::Wasm(WasmQuery::ContractInfo { contract_addr });
@@ -120,8 +177,17 @@ fn confirm_admin_in_contract<Q: CustomQuery>(
         SystemResult::Ok(ContractResult::Ok(value)) => Ok(value),
     }?;
 
-    let response = from_slice::<Option<ContractInfoResponse>>(&response)?
-        .ok_or_else(|| Contract("Contract query provided no results!".to_owned()))?;
+    from_slice::<Option<ContractInfoResponse>>(&response)?
+        .ok_or_else(|| Contract("Contract query provided no results!".to_owned()))
+}
+
+fn confirm_admin_in_contract<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: &Env,
+    contract_addr: String,
+    action: &str,
+) -> Result<(), ContractError> {
+    let response = query_contract_info(deps, contract_addr)?;
 
     if let Some(admin) = response.admin {
         if admin == env.contract.address {
@@ -129,9 +195,10 @@ fn confirm_admin_in_contract<Q: CustomQuery>(
         }
     }
 
-    Err(Unauthorized(
-        "Validator Proposal contract is not an admin of contract proposed to migrate".to_owned(),
-    ))
+    Err(ContractError::Unauthorized(format!(
+        "Validator Proposal contract is not an admin of contract proposed to {}",
+        action
+    )))
 }
 
 #[cfg(test)]
@@ -409,6 +476,45 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn validate_privileged_contract_works() {
+        let env = mock_env();
+        let promote = ValidatorProposal::PromoteToPrivilegedContract {
+            contract: MIGRATE_CONTRACT.to_owned(),
+        };
+        let demote = ValidatorProposal::DemotePrivilegedContract {
+            contract: MIGRATE_CONTRACT.to_owned(),
+        };
+
+        // Contract which didn't opt in (admin is some other contract) cannot be promoted
+        let deps = custom_mock_deps_tgrade(MIGRATE_CONTRACT, Some("other_contract"));
+        let res = promote.validate(deps.as_ref(), &env, "title", "description");
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized(
+                "Validator Proposal contract is not an admin of contract proposed to promote"
+                    .to_owned()
+            )
+        );
+        // but it can be demoted
+        demote
+            .validate(deps.as_ref(), &env, "title", "description")
+            .unwrap();
+
+        // Contract under this contract administration can be promoted
+        let deps = custom_mock_deps_tgrade(MIGRATE_CONTRACT, Some(MOCK_CONTRACT_ADDR));
+        promote
+            .validate(deps.as_ref(), &env, "title", "description")
+            .unwrap();
+
+        // Non existing contracts are rejected
+        let deps = custom_mock_deps_tgrade("other_contract", Some(MOCK_CONTRACT_ADDR));
+        let res = promote.validate(deps.as_ref(), &env, "title", "description");
+        assert!(matches!(res.unwrap_err(), ContractError::System(_)));
+        let res = demote.validate(deps.as_ref(), &env, "title", "description");
+        assert!(matches!(res.unwrap_err(), ContractError::System(_)));
+    }
+
     #[test]
     fn validate_register_upgrade_works() {
         let deps = mock_deps_tgrade();
@@ -455,7 +561,7 @@ mod tests {
         let env = mock_env();
 
         // Valid
-        let proposal = ValidatorProposal::CancelUpgrade {};
+        let proposal = ValidatorProposal::CancelUpgrade { name: None };
 
         proposal
             .validate(deps.as_ref(), &env, "title", "description")
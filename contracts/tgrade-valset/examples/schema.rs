@@ -4,8 +4,10 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
 pub use tgrade_valset::msg::{
-    DistributionMsg, EpochResponse, ExecuteMsg, InstantiateMsg, ListActiveValidatorsResponse,
-    ListValidatorResponse, QueryMsg, RewardsDistribution, RewardsInstantiateMsg, ValidatorResponse,
+    DelegatesResponse, DistributionMsg, EpochResponse, ExecuteMsg, ExitStatusResponse,
+    InstantiateMsg, ListActiveValidatorsResponse, ListValidatorResponse, MetricsResponse,
+    OperationalMetadataResponse, PenaltyHookMsg, PenaltyHooksResponse, QueryMsg,
+    RewardsDistribution, RewardsInstantiateMsg, ValidatorResponse,
 };
 pub use tgrade_valset::state::{Config, ValsetState};
 
@@ -22,9 +24,15 @@ fn main() {
     export_schema(&schema_for!(EpochResponse), &out_dir);
     export_schema(&schema_for!(ListValidatorResponse), &out_dir);
     export_schema(&schema_for!(ValidatorResponse), &out_dir);
+    export_schema(&schema_for!(OperationalMetadataResponse), &out_dir);
     export_schema(&schema_for!(ListActiveValidatorsResponse), &out_dir);
+    export_schema(&schema_for!(PenaltyHooksResponse), &out_dir);
+    export_schema(&schema_for!(DelegatesResponse), &out_dir);
+    export_schema(&schema_for!(ExitStatusResponse), &out_dir);
+    export_schema(&schema_for!(MetricsResponse), &out_dir);
 
     export_schema(&schema_for!(DistributionMsg), &out_dir);
+    export_schema(&schema_for!(PenaltyHookMsg), &out_dir);
     export_schema(&schema_for!(RewardsInstantiateMsg), &out_dir);
     export_schema(&schema_for!(RewardsDistribution), &out_dir);
     export_schema(&schema_for!(Config), &out_dir);
@@ -1,39 +1,50 @@
 use std::cmp::{max, min};
 use std::collections::BTreeSet;
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, BlockInfo, Coin, CustomQuery, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, QueryRequest, Reply, StdError, StdResult, Timestamp, WasmMsg,
+    to_binary, to_vec, Addr, Api, Binary, BlockInfo, Coin, CustomQuery, Decimal, Deps, DepsMut,
+    Empty, Env, Event, MessageInfo, Order, QueryRequest, Reply, StdError, StdResult, Storage,
+    Timestamp, WasmMsg,
 };
 
 use cw2::set_contract_version;
 use cw_controllers::AdminError;
 use cw_storage_plus::Bound;
-use cw_utils::{ensure_from_older_version, maybe_addr, parse_reply_instantiate_data};
+use cw_utils::{maybe_addr, parse_reply_instantiate_data};
+use sha2::{Digest, Sha256};
 
 use tg4::{Member, Tg4Contract};
 use tg_bindings::{
-    request_privileges, Ed25519Pubkey, Evidence, EvidenceType, Privilege, PrivilegeChangeMsg,
-    Pubkey, TgradeMsg, TgradeQuery, TgradeSudoMsg, ToAddress, ValidatorDiff, ValidatorUpdate,
+    request_privileges, Evidence, Privilege, PrivilegeChangeMsg, Pubkey, PubkeyType, TgradeMsg,
+    TgradeQuery, TgradeSudoMsg, ToAddress, ValidatedPubkey, ValidatorDiff, ValidatorUpdate,
     ValidatorVoteResponse,
 };
 use tg_utils::{Duration, JailingDuration, SlashMsg, ADMIN};
 
 use crate::error::ContractError;
 use crate::msg::{
-    EpochResponse, ExecuteMsg, InstantiateMsg, InstantiateResponse, JailingEnd, JailingPeriod,
-    ListActiveValidatorsResponse, ListValidatorResponse, ListValidatorSlashingResponse, MigrateMsg,
-    OperatorResponse, QueryMsg, RewardsDistribution, RewardsInstantiateMsg, ValidatorMetadata,
+    key_proof_message, validate_extra_epoch_rewards, validate_pubkey, validate_pubkey_types,
+    DelegatesResponse, EpochResponse, ExecuteMsg, ExitStatusResponse, IbcRetryQueueResponse,
+    InstantiateMsg, InstantiateResponse, InvariantsResponse, JailEndType, JailReason, JailingEnd,
+    JailingPeriod, KeyRotationInfo, KeyRotationResponse, ListActiveValidatorsResponse,
+    ListValidatorResponse, ListValidatorSlashingResponse, MetricsResponse, MigrateMsg,
+    OperationalMetadataResponse, OperatorResponse, PenaltyHookMsg, PenaltyHooksResponse,
+    PenaltyReason, QueryMsg, RewardsDistribution, RewardsInstantiateMsg, ValidatorMetadata,
     ValidatorResponse,
 };
-use crate::rewards::pay_block_rewards;
+use crate::rewards::{pay_block_rewards, simulate_rewards};
 use crate::state::{
-    export, import, operators, Config, DistributionContract, EpochInfo, OperatorInfo,
-    ValidatorInfo, ValidatorSlashing, ValsetState, BLOCK_SIGNERS, CONFIG, EPOCH, JAIL, VALIDATORS,
-    VALIDATOR_SLASHING, VALIDATOR_START_HEIGHT,
+    add_coins, export, import, operators, recount, reindex_jails, remove_jail, reset_counters,
+    save_jail, Config, DistributionContract, EpochInfo, EvidencePolicy, IbcDistribution,
+    KeyRotation, OperatorExit, OperatorInfo, ScalingCurve, ValidatorInfo, ValidatorSlashing,
+    ValsetState, BLOCK_SIGNERS, CONFIG, COUNTERS, EPOCH, EVIDENCE_OFFENSES, EXITS,
+    EXPECTED_GROUP_HASH, IBC_PENDING_TRANSFERS, IBC_RETRY_QUEUE, JAIL, JAIL_BY_END, KEY_ROTATIONS,
+    KEY_ROTATION_DELAY, LAST_PAYOUT, MEMBERSHIP_QUERY_FAILURES, MIGRATIONS, OPERATOR_DELEGATES,
+    PENALTY_HOOKS, PREAUTH_PENALTY_HOOKS, PROBATION, VALIDATORS, VALIDATOR_SLASHING,
+    VALIDATOR_START_HEIGHT,
 };
 
 // version info for migration info
@@ -41,6 +52,8 @@ pub(crate) const CONTRACT_NAME: &str = "crates.io:tgrade-valset";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const REWARDS_INIT_REPLY_ID: u64 = 1;
+const PENALTY_HOOK_REPLY_ID: u64 = 2;
+pub(crate) const IBC_TRANSFER_REPLY_ID: u64 = 3;
 
 /// Missed blocks interval a validator can be jailed for.
 pub const MISSED_BLOCKS: u64 = 1000;
@@ -67,6 +80,7 @@ pub fn instantiate(
     membership
         .total_points(&deps.querier)
         .map_err(|_| ContractError::InvalidTg4Contract {})?;
+    let pubkey_types = msg.pubkey_types();
     let distribution_contracts = msg.distribution_contracts.validate(deps.api)?;
 
     let cfg = Config {
@@ -74,7 +88,9 @@ pub fn instantiate(
         min_points: msg.min_points,
         max_validators: msg.max_validators,
         scaling: msg.scaling,
+        scaling_curve: msg.scaling_curve,
         epoch_reward: msg.epoch_reward,
+        extra_epoch_rewards: msg.extra_epoch_rewards,
         fee_percentage: msg.fee_percentage,
         auto_unjail: msg.auto_unjail,
         double_sign_slash_ratio: msg.double_sign_slash_ratio,
@@ -83,6 +99,14 @@ pub fn instantiate(
         validator_group: Addr::unchecked(""),
         verify_validators: msg.verify_validators,
         offline_jail_duration: msg.offline_jail_duration,
+        probation_epochs: msg.probation_epochs,
+        pubkey_types,
+        ibc_distribution: msg.ibc_distribution,
+        exit_cooldown: msg.exit_cooldown,
+        evidence_policy: msg.evidence_policy,
+        keep_validators_on_membership_failure: msg.keep_validators_on_membership_failure,
+        skip_payout_on_group_drift: msg.skip_payout_on_group_drift,
+        require_key_proof: msg.require_key_proof,
     };
     CONFIG.save(deps.storage, &cfg)?;
 
@@ -96,17 +120,38 @@ pub fn instantiate(
 
     VALIDATORS.save(deps.storage, &vec![])?;
 
+    PREAUTH_PENALTY_HOOKS.set_auth(deps.storage, msg.preauths_penalty_hooks)?;
+
     for op in msg.initial_keys.into_iter() {
         let oper = deps.api.addr_validate(&op.operator)?;
-        let pubkey: Ed25519Pubkey = op.validator_pubkey.try_into()?;
+        let pubkey = ValidatedPubkey::try_from(op.validator_pubkey)?;
         op.metadata.validate()?;
         let info = OperatorInfo {
             pubkey,
             metadata: op.metadata,
             active_validator: false,
+            payout: None,
+        };
+        operators().save(deps.storage, &oper, &info)?;
+    }
+
+    let mut genesis_members = vec![];
+    for val in msg.genesis_validators.into_iter() {
+        let oper = deps.api.addr_validate(&val.operator)?;
+        let info = OperatorInfo {
+            pubkey: ValidatedPubkey::try_from(&val.validator_pubkey)?,
+            metadata: val.metadata(),
+            active_validator: false,
+            payout: None,
         };
         operators().save(deps.storage, &oper, &info)?;
+        genesis_members.push(Member {
+            addr: oper.into(),
+            points: val.points,
+            start_height: None,
+        });
     }
+    reset_counters(deps.storage)?;
 
     if let Some(admin) = &msg.admin {
         let admin = deps.api.addr_validate(admin)?;
@@ -129,20 +174,34 @@ pub fn instantiate(
 
     let add_slasher = SlashMsg::AddSlasher {
         addr: env.contract.address.to_string(),
+        max_portion: None,
     };
     let add_slasher_msg = WasmMsg::Execute {
-        contract_addr: msg.membership,
+        contract_addr: msg.membership.clone(),
         msg: to_binary(&add_slasher)?,
         funds: vec![],
     };
 
-    let resp = Response::new()
+    let mut resp = Response::new()
         .add_submessage(SubMsg::reply_on_success(
             instantiate_rewards_msg,
             REWARDS_INIT_REPLY_ID,
         ))
         .add_submessage(SubMsg::new(add_slasher_msg));
 
+    if !genesis_members.is_empty() {
+        // seed membership, so genesis validators make it to the first validator set
+        let update_members = RewardsDistribution::UpdateMembers {
+            remove: vec![],
+            add: genesis_members,
+        };
+        resp = resp.add_message(WasmMsg::Execute {
+            contract_addr: msg.membership,
+            msg: to_binary(&update_members)?,
+            funds: vec![],
+        });
+    }
+
     Ok(resp)
 }
 
@@ -165,37 +224,76 @@ pub fn execute(
             min_points,
             max_validators,
             scaling,
+            scaling_curve,
             epoch_reward,
+            extra_epoch_rewards,
             fee_percentage,
             auto_unjail,
             double_sign_slash_ratio,
             distribution_contracts,
             verify_validators,
             offline_jail_duration,
+            probation_epochs,
+            pubkey_types,
+            evidence_policy,
+            keep_validators_on_membership_failure,
+            skip_payout_on_group_drift,
+            require_key_proof,
         } => execute_update_config(
             deps,
             info,
             min_points,
             max_validators,
             scaling,
+            scaling_curve,
             epoch_reward,
+            extra_epoch_rewards,
             fee_percentage,
             auto_unjail,
             double_sign_slash_ratio,
             distribution_contracts,
             verify_validators,
             offline_jail_duration,
+            probation_epochs,
+            pubkey_types,
+            evidence_policy,
+            keep_validators_on_membership_failure,
+            skip_payout_on_group_drift,
+            require_key_proof,
         ),
 
-        ExecuteMsg::RegisterValidatorKey { pubkey, metadata } => {
-            execute_register_validator_key(deps, env, info, pubkey, metadata)
+        ExecuteMsg::RegisterValidatorKey {
+            pubkey,
+            metadata,
+            proof,
+        } => execute_register_validator_key(deps, env, info, pubkey, metadata, proof),
+        ExecuteMsg::RotateValidatorKey { pubkey, proof } => {
+            execute_rotate_validator_key(deps, env, info, pubkey, proof)
+        }
+        ExecuteMsg::UpdateMetadata(metadata) => {
+            let operator = info.sender.clone();
+            execute_update_metadata(deps, env, info, operator, metadata)
+        }
+        ExecuteMsg::UpdateOperatorMetadata { operator, metadata } => {
+            let operator = deps.api.addr_validate(&operator)?;
+            execute_update_metadata(deps, env, info, operator, metadata)
+        }
+        ExecuteMsg::AddDelegate { delegate } => execute_add_delegate(deps, info, delegate),
+        ExecuteMsg::RemoveDelegate { delegate } => execute_remove_delegate(deps, info, delegate),
+        ExecuteMsg::SetPayoutAddress { payout } => execute_set_payout_address(deps, info, payout),
+        ExecuteMsg::SetIbcDistribution { distribution } => {
+            execute_set_ibc_distribution(deps, info, distribution)
         }
-        ExecuteMsg::UpdateMetadata(metadata) => execute_update_metadata(deps, env, info, metadata),
+        ExecuteMsg::BeginExit {} => execute_begin_exit(deps, env, info),
+        ExecuteMsg::PurgeOperator { operator } => execute_purge_operator(deps, env, operator),
         ExecuteMsg::Jail { operator, duration } => {
             execute_jail(deps, env, info, operator, duration)
         }
         ExecuteMsg::Unjail { operator } => execute_unjail(deps, env, info, operator),
+        ExecuteMsg::Untombstone { operator } => execute_untombstone(deps, env, info, operator),
         ExecuteMsg::Slash { addr, portion } => execute_slash(deps, env, info, addr, portion),
+        ExecuteMsg::AddPenaltyHook { addr } => execute_add_penalty_hook(deps, info, addr),
+        ExecuteMsg::RemovePenaltyHook { addr } => execute_remove_penalty_hook(deps, info, addr),
         #[cfg(feature = "integration")]
         ExecuteMsg::SimulateValidatorSet { validators } => {
             execute_simulate_validators(deps, info, validators)
@@ -210,29 +308,50 @@ fn execute_update_config<Q: CustomQuery>(
     min_points: Option<u64>,
     max_validators: Option<u32>,
     scaling: Option<u32>,
+    scaling_curve: Option<ScalingCurve>,
     epoch_reward: Option<Coin>,
+    extra_epoch_rewards: Option<Vec<Coin>>,
     fee_percentage: Option<Decimal>,
     auto_unjail: Option<bool>,
     double_sign_slash_ratio: Option<Decimal>,
     distribution_contracts: Option<Vec<DistributionContract>>,
     verify_validators: Option<bool>,
     offline_jail_duration: Option<Duration>,
+    probation_epochs: Option<u64>,
+    pubkey_types: Option<Vec<PubkeyType>>,
+    evidence_policy: Option<EvidencePolicy>,
+    keep_validators_on_membership_failure: Option<bool>,
+    skip_payout_on_group_drift: Option<bool>,
+    require_key_proof: Option<bool>,
 ) -> Result<Response, ContractError> {
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    CONFIG.update::<_, StdError>(deps.storage, |mut cfg| {
+    CONFIG.update::<_, ContractError>(deps.storage, |mut cfg| {
         if let Some(min_points) = min_points {
             cfg.min_points = min_points;
         }
         if let Some(max_validators) = max_validators {
             cfg.max_validators = max_validators;
         }
+        if scaling.is_some() && scaling_curve.is_some() {
+            return Err(ContractError::ConflictingScaling {});
+        }
         if let Some(scaling) = scaling {
             cfg.scaling = Option::from(scaling);
+            cfg.scaling_curve = None;
+        }
+        if let Some(curve) = scaling_curve {
+            curve.validate()?;
+            cfg.scaling = None;
+            cfg.scaling_curve = Some(curve);
         }
         if let Some(epoch_reward) = epoch_reward {
             cfg.epoch_reward = epoch_reward;
         }
+        if let Some(extra_epoch_rewards) = extra_epoch_rewards {
+            cfg.extra_epoch_rewards = extra_epoch_rewards;
+        }
+        validate_extra_epoch_rewards(&cfg.epoch_reward, &cfg.extra_epoch_rewards)?;
         if let Some(fee_percentage) = fee_percentage {
             cfg.fee_percentage = fee_percentage;
         }
@@ -244,6 +363,9 @@ fn execute_update_config<Q: CustomQuery>(
         }
         if let Some(distribution_contracts) = distribution_contracts {
             cfg.distribution_contracts = distribution_contracts;
+            if let Some(ibc_distribution) = &cfg.ibc_distribution {
+                ibc_distribution.validate(cfg.distribution_ratio())?;
+            }
         }
         if let Some(verify_validators) = verify_validators {
             cfg.verify_validators = verify_validators;
@@ -251,6 +373,26 @@ fn execute_update_config<Q: CustomQuery>(
         if let Some(offline_jail_duration) = offline_jail_duration {
             cfg.offline_jail_duration = offline_jail_duration;
         }
+        if let Some(probation_epochs) = probation_epochs {
+            cfg.probation_epochs = probation_epochs;
+        }
+        if let Some(pubkey_types) = pubkey_types {
+            validate_pubkey_types(&pubkey_types)?;
+            cfg.pubkey_types = pubkey_types;
+        }
+        if let Some(evidence_policy) = evidence_policy {
+            evidence_policy.validate()?;
+            cfg.evidence_policy = Some(evidence_policy);
+        }
+        if let Some(keep) = keep_validators_on_membership_failure {
+            cfg.keep_validators_on_membership_failure = keep;
+        }
+        if let Some(skip) = skip_payout_on_group_drift {
+            cfg.skip_payout_on_group_drift = skip;
+        }
+        if let Some(require) = require_key_proof {
+            cfg.require_key_proof = require;
+        }
         Ok(cfg)
     })?;
 
@@ -263,46 +405,168 @@ fn execute_update_config<Q: CustomQuery>(
 
 fn execute_register_validator_key<Q: CustomQuery>(
     deps: DepsMut<Q>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     pubkey: Pubkey,
     metadata: ValidatorMetadata,
+    proof: Option<Binary>,
 ) -> Result<Response, ContractError> {
     metadata.validate()?;
 
-    let pubkey: Ed25519Pubkey = pubkey.try_into()?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let pubkey = validate_pubkey(&pubkey, &cfg.pubkey_types)?;
+    if cfg.require_key_proof {
+        verify_key_proof(deps.api, &env, &info.sender, &pubkey, proof)?;
+    }
+    if is_rotated_pubkey(deps.storage, &pubkey)? {
+        return Err(ContractError::PubkeyInUse {});
+    }
     let moniker = metadata.moniker.clone();
 
     let operator = OperatorInfo {
         pubkey,
         metadata,
         active_validator: false,
+        payout: None,
     };
     match operators().may_load(deps.storage, &info.sender)? {
         Some(_) => return Err(ContractError::OperatorRegistered {}),
-        None => operators().save(deps.storage, &info.sender, &operator)?,
+        None if is_payout_taken(deps.storage, &info.sender, &info.sender)? => {
+            return Err(ContractError::PayoutAddressTaken(info.sender.to_string()))
+        }
+        None => {
+            operators().save(deps.storage, &info.sender, &operator)?;
+            COUNTERS.update::<_, StdError>(deps.storage, |mut counters| {
+                counters.operators += 1;
+                Ok(counters)
+            })?;
+        }
     };
 
     let res = Response::new()
         .add_attribute("action", "register_validator_key")
         .add_attribute("operator", &info.sender)
-        .add_attribute("pubkey_type", "ed25519")
+        .add_attribute("pubkey_type", operator.pubkey.key_type().as_str())
         .add_attribute("pubkey_value", operator.pubkey.to_base64())
         .add_attribute("moniker", moniker);
 
     Ok(res)
 }
 
+/// Verifies `proof` is a signature of `key_proof_message` for `operator` made with `pubkey`
+fn verify_key_proof(
+    api: &dyn Api,
+    env: &Env,
+    operator: &Addr,
+    pubkey: &ValidatedPubkey,
+    proof: Option<Binary>,
+) -> Result<(), ContractError> {
+    let proof = proof.ok_or(ContractError::MissingKeyProof {})?;
+    let message = key_proof_message(
+        &env.block.chain_id,
+        env.contract.address.as_str(),
+        operator.as_str(),
+        pubkey,
+    );
+    let verified = match pubkey {
+        ValidatedPubkey::Ed25519(_) => api
+            .ed25519_verify(&message, &proof, &pubkey.to_vec())
+            .map_err(|_| ContractError::InvalidKeyProof {})?,
+        ValidatedPubkey::Secp256k1(_) => api
+            .secp256k1_verify(&Sha256::digest(&message), &proof, &pubkey.to_vec())
+            .map_err(|_| ContractError::InvalidKeyProof {})?,
+    };
+    if !verified {
+        return Err(ContractError::InvalidKeyProof {});
+    }
+    Ok(())
+}
+
+/// Checks if `pubkey` is one of the keys of a rotation in progress
+fn is_rotated_pubkey(storage: &dyn Storage, pubkey: &ValidatedPubkey) -> StdResult<bool> {
+    for rotation in KEY_ROTATIONS.range(storage, None, None, Order::Ascending) {
+        if rotation?.1.matches(pubkey) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn execute_rotate_validator_key<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    pubkey: Pubkey,
+    proof: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let pubkey = validate_pubkey(&pubkey, &cfg.pubkey_types)?;
+    if cfg.require_key_proof {
+        verify_key_proof(deps.api, &env, &info.sender, &pubkey, proof)?;
+    }
+
+    let operator = operators()
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::OperatorNotRegistered(info.sender.to_string()))?;
+    if let Some(rotation) = KEY_ROTATIONS.may_load(deps.storage, &info.sender)? {
+        return Err(ContractError::KeyRotationInProgress(
+            rotation.activation_epoch,
+        ));
+    }
+    if operators()
+        .idx
+        .pubkey
+        .item(deps.storage, pubkey.to_vec())?
+        .is_some()
+        || is_rotated_pubkey(deps.storage, &pubkey)?
+    {
+        return Err(ContractError::PubkeyInUse {});
+    }
+
+    let activation_epoch = EPOCH.load(deps.storage)?.current_epoch + KEY_ROTATION_DELAY;
+    let res = Response::new()
+        .add_attribute("action", "rotate_validator_key")
+        .add_attribute("operator", &info.sender)
+        .add_attribute("pubkey_type", pubkey.key_type().as_str())
+        .add_attribute("pubkey_value", pubkey.to_base64())
+        .add_attribute("activation_epoch", activation_epoch.to_string());
+
+    let rotation = KeyRotation {
+        old_pubkey: operator.pubkey,
+        new_pubkey: pubkey,
+        activation_epoch,
+    };
+    KEY_ROTATIONS.save(deps.storage, &info.sender, &rotation)?;
+
+    Ok(res)
+}
+
+/// Checks if `sender` is allowed to act on behalf of `operator`
+fn is_operator_or_delegate(
+    storage: &dyn Storage,
+    operator: &Addr,
+    sender: &Addr,
+) -> StdResult<bool> {
+    Ok(operator == sender || OPERATOR_DELEGATES.has(storage, (operator, sender)))
+}
+
 fn execute_update_metadata<Q: CustomQuery>(
     deps: DepsMut<Q>,
     _env: Env,
     info: MessageInfo,
+    operator: Addr,
     metadata: ValidatorMetadata,
 ) -> Result<Response, ContractError> {
+    if !is_operator_or_delegate(deps.storage, &operator, &info.sender)? {
+        return Err(ContractError::Unauthorized(
+            "Sender is neither the operator nor its delegate".to_owned(),
+        ));
+    }
+
     metadata.validate()?;
     let moniker = metadata.moniker.clone();
 
-    operators().update(deps.storage, &info.sender, |info| match info {
+    operators().update(deps.storage, &operator, |info| match info {
         Some(mut old) => {
             old.metadata = metadata;
             Ok(old)
@@ -314,11 +578,215 @@ fn execute_update_metadata<Q: CustomQuery>(
 
     let res = Response::new()
         .add_attribute("action", "update_metadata")
-        .add_attribute("operator", &info.sender)
+        .add_attribute("operator", &operator)
         .add_attribute("moniker", moniker);
     Ok(res)
 }
 
+fn execute_add_delegate<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegate {});
+    }
+    if operators().may_load(deps.storage, &info.sender)?.is_none() {
+        return Err(ContractError::Unauthorized(
+            "No operator info found".to_owned(),
+        ));
+    }
+
+    OPERATOR_DELEGATES.save(deps.storage, (&info.sender, &delegate), &Empty {})?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_delegate")
+        .add_attribute("operator", &info.sender)
+        .add_attribute("delegate", delegate);
+    Ok(res)
+}
+
+fn execute_remove_delegate<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    delegate: String,
+) -> Result<Response, ContractError> {
+    let delegate_addr = deps.api.addr_validate(&delegate)?;
+    if !OPERATOR_DELEGATES.has(deps.storage, (&info.sender, &delegate_addr)) {
+        return Err(ContractError::NotDelegate(delegate));
+    }
+
+    OPERATOR_DELEGATES.remove(deps.storage, (&info.sender, &delegate_addr));
+
+    let res = Response::new()
+        .add_attribute("action", "remove_delegate")
+        .add_attribute("operator", &info.sender)
+        .add_attribute("delegate", delegate);
+    Ok(res)
+}
+
+/// Checks if `payout` is another registered operator, or is already used as a payout address by
+/// an operator other than `operator`
+fn is_payout_taken(storage: &dyn Storage, payout: &Addr, operator: &Addr) -> StdResult<bool> {
+    if payout != operator && operators().has(storage, payout) {
+        return Ok(true);
+    }
+    let taken = operators()
+        .idx
+        .payout
+        .prefix(payout.as_bytes().to_vec())
+        .keys(storage, None, None, Order::Ascending)
+        .any(|op| op.map_or(true, |op| &op != operator));
+    Ok(taken)
+}
+
+fn execute_set_payout_address<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    payout: Option<String>,
+) -> Result<Response, ContractError> {
+    let payout = maybe_addr(deps.api, payout)?.filter(|payout| payout != &info.sender);
+    let mut operator = operators()
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::Unauthorized("No operator info found".to_owned()))?;
+    if let Some(payout) = &payout {
+        if is_payout_taken(deps.storage, payout, &info.sender)? {
+            return Err(ContractError::PayoutAddressTaken(payout.to_string()));
+        }
+    }
+
+    let old_payout = operator.payout_addr(&info.sender);
+    operator.payout = payout;
+    let new_payout = operator.payout_addr(&info.sender);
+    operators().save(deps.storage, &info.sender, &operator)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_payout_address")
+        .add_attribute("operator", &info.sender)
+        .add_attribute("payout", &new_payout);
+
+    // Active validator points are moved to the new payout address right away, so rewards of
+    // the current epoch are already paid there
+    let validator = VALIDATORS
+        .load(deps.storage)?
+        .into_iter()
+        .find(|v| v.operator == info.sender);
+    if let Some(validator) = validator.filter(|_| old_payout != new_payout) {
+        let update_members = RewardsDistribution::UpdateMembers {
+            add: vec![Member {
+                addr: new_payout.to_string(),
+                points: validator.power,
+                start_height: None,
+            }],
+            remove: vec![old_payout.to_string()],
+        };
+        res = res.add_message(WasmMsg::Execute {
+            contract_addr: CONFIG.load(deps.storage)?.validator_group.to_string(),
+            msg: to_binary(&update_members)?,
+            funds: vec![],
+        });
+    }
+
+    Ok(res)
+}
+
+fn execute_set_ibc_distribution<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    distribution: Option<IbcDistribution>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let mut res = Response::new()
+        .add_attribute("action", "set_ibc_distribution")
+        .add_attribute("sender", &info.sender);
+    match &distribution {
+        Some(distribution) => {
+            distribution.validate(cfg.distribution_ratio())?;
+            res = res
+                .add_attribute("channel_id", &distribution.channel_id)
+                .add_attribute("remote_address", &distribution.remote_address)
+                .add_attribute("ratio", distribution.ratio.to_string());
+        }
+        None => IBC_RETRY_QUEUE.remove(deps.storage),
+    }
+    cfg.ibc_distribution = distribution;
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(res)
+}
+
+fn execute_begin_exit<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if !operators().has(deps.storage, &info.sender) {
+        return Err(ContractError::OperatorNotRegistered(
+            info.sender.to_string(),
+        ));
+    }
+    if EXITS.has(deps.storage, &info.sender) {
+        return Err(ContractError::AlreadyExiting(info.sender.to_string()));
+    }
+
+    let exit = OperatorExit {
+        begun_at: env.block.time,
+        settled_epoch: None,
+        purgeable_at: None,
+    };
+    EXITS.save(deps.storage, &info.sender, &exit)?;
+
+    let res = Response::new()
+        .add_attribute("action", "begin_exit")
+        .add_attribute("operator", &info.sender);
+
+    Ok(res)
+}
+
+fn execute_purge_operator<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    let exit = EXITS
+        .may_load(deps.storage, &operator)?
+        .ok_or_else(|| ContractError::NotExiting(operator.to_string()))?;
+    if !exit.is_purgeable(&env.block) {
+        return Err(ContractError::ExitCooldownNotOver(operator.to_string()));
+    }
+    // Purging would let a jailed operator register again with a clean record
+    if JAIL.has(deps.storage, &operator) {
+        return Err(ContractError::PurgeJailedForbidden(operator.to_string()));
+    }
+
+    operators().remove(deps.storage, &operator)?;
+    COUNTERS.update::<_, StdError>(deps.storage, |mut counters| {
+        counters.operators -= 1;
+        Ok(counters)
+    })?;
+    let delegates = OPERATOR_DELEGATES
+        .prefix(&operator)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for delegate in delegates {
+        OPERATOR_DELEGATES.remove(deps.storage, (&operator, &delegate));
+    }
+    KEY_ROTATIONS.remove(deps.storage, &operator);
+    PROBATION.remove(deps.storage, &operator);
+    VALIDATOR_START_HEIGHT.remove(deps.storage, &operator);
+    EXITS.remove(deps.storage, &operator);
+
+    let res = Response::new()
+        .add_attribute("action", "purge_operator")
+        .add_attribute("operator", operator.as_str());
+
+    Ok(res)
+}
+
 fn execute_jail<Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
@@ -328,14 +796,25 @@ fn execute_jail<Q: CustomQuery>(
 ) -> Result<Response, ContractError> {
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    let expiration = JailingPeriod::from_duration(duration, &env.block);
+    let expiration = JailingPeriod::from_duration(duration, JailReason::Admin, &env.block);
 
-    JAIL.save(
+    save_jail(
         deps.storage,
         &deps.api.addr_validate(&operator)?,
         &expiration,
     )?;
 
+    let hooks = penalty_hooks_msgs(
+        deps.storage,
+        &PenaltyHookMsg::ValidatorPenalty {
+            operator: operator.clone(),
+            reason: PenaltyReason::Jailed,
+            jailed_until: Some(expiration.end.clone()),
+            slash_portion: None,
+            height: env.block.height,
+        },
+    )?;
+
     let until_attr = match expiration.end {
         JailingEnd::Until(expires) => Timestamp::from(expires).to_string(),
         JailingEnd::Forever {} => "forever".to_owned(),
@@ -344,7 +823,8 @@ fn execute_jail<Q: CustomQuery>(
     let res = Response::new()
         .add_attribute("action", "jail")
         .add_attribute("operator", &operator)
-        .add_attribute("until", until_attr);
+        .add_attribute("until", until_attr)
+        .add_submessages(hooks);
 
     Ok(res)
 }
@@ -361,7 +841,7 @@ fn execute_unjail<Q: CustomQuery>(
 
     let is_admin = ADMIN.is_admin(deps.as_ref(), &info.sender)?;
 
-    if operator != &info.sender && !is_admin {
+    if !is_admin && !is_operator_or_delegate(deps.storage, operator, &info.sender)? {
         return Err(AdminError::NotAdmin {}.into());
     }
 
@@ -372,7 +852,7 @@ fn execute_unjail<Q: CustomQuery>(
         }
 
         if expiration.is_expired(&env.block) || is_admin {
-            JAIL.remove(deps.storage, operator);
+            remove_jail(deps.storage, operator)?;
         } else {
             return Err(ContractError::JailDidNotExpire {});
         }
@@ -385,6 +865,45 @@ fn execute_unjail<Q: CustomQuery>(
     Ok(res)
 }
 
+fn execute_untombstone<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    let jail = match JAIL.may_load(deps.storage, &operator_addr)? {
+        Some(jail) if jail.is_forever() => jail,
+        _ => return Err(ContractError::NotTombstoned(operator)),
+    };
+    remove_jail(deps.storage, &operator_addr)?;
+
+    // Tombstoned validator didn't sign any blocks, so without resetting the last signed height
+    // it would be jailed again for being offline on the very next epoch.
+    if let Some(info) = operators().may_load(deps.storage, &operator_addr)? {
+        BLOCK_SIGNERS.save(deps.storage, &info.pubkey.to_address(), &env.block.height)?;
+    }
+
+    let slashing_events = VALIDATOR_SLASHING
+        .may_load(deps.storage, &operator_addr)?
+        .map_or(0, |slashing| slashing.len());
+
+    let evt = Event::new("untombstone")
+        .add_attribute("operator", &operator)
+        .add_attribute("tombstoned_at", jail.start.seconds().to_string())
+        .add_attribute("slashing_events", slashing_events.to_string())
+        .add_attribute("height", env.block.height.to_string());
+
+    let res = Response::new()
+        .add_attribute("action", "untombstone")
+        .add_attribute("operator", &operator)
+        .add_event(evt);
+
+    Ok(res)
+}
+
 fn store_slashing_event<Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: &Env,
@@ -417,6 +936,17 @@ fn execute_slash<Q: CustomQuery>(
 
     let config = CONFIG.load(deps.storage)?;
 
+    let hooks = penalty_hooks_msgs(
+        deps.storage,
+        &PenaltyHookMsg::ValidatorPenalty {
+            operator: operator.clone(),
+            reason: PenaltyReason::Slashed,
+            jailed_until: None,
+            slash_portion: Some(portion),
+            height: env.block.height,
+        },
+    )?;
+
     let slash_msg = SlashMsg::Slash {
         addr: operator,
         portion,
@@ -429,11 +959,70 @@ fn execute_slash<Q: CustomQuery>(
         funds: vec![],
     };
 
-    let resp = Response::new().add_submessage(SubMsg::new(slash_msg));
+    let resp = Response::new()
+        .add_submessage(SubMsg::new(slash_msg))
+        .add_submessages(hooks);
 
     Ok(resp)
 }
 
+fn execute_add_penalty_hook<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    hook: String,
+) -> Result<Response, ContractError> {
+    // custom guard: using a preauth OR being admin
+    if !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+        PREAUTH_PENALTY_HOOKS.use_auth(deps.storage)?;
+    }
+
+    PENALTY_HOOKS.add_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_penalty_hook")
+        .add_attribute("hook", hook)
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+fn execute_remove_penalty_hook<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    hook: String,
+) -> Result<Response, ContractError> {
+    // custom guard: self-removal OR being admin
+    let hook_addr = deps.api.addr_validate(&hook)?;
+    if info.sender != hook_addr && !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized(
+            "Hook address is not same as sender's or sender is not an admin".to_owned(),
+        ));
+    }
+
+    PENALTY_HOOKS.remove_hook(deps.storage, hook_addr)?;
+
+    let res = Response::new()
+        .add_attribute("action", "remove_penalty_hook")
+        .add_attribute("hook", hook)
+        .add_attribute("sender", info.sender);
+    Ok(res)
+}
+
+/// Prepares `msg` to be sent to all penalty hooks. Hooks are called from the end and begin
+/// blockers, so their failures are caught and ignored in `reply`.
+fn penalty_hooks_msgs(storage: &dyn Storage, msg: &PenaltyHookMsg) -> StdResult<Vec<SubMsg>> {
+    let msg = to_binary(msg)?;
+    PENALTY_HOOKS.prepare_hooks(storage, |hook| {
+        Ok(SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: hook.to_string(),
+                msg: msg.clone(),
+                funds: vec![],
+            },
+            PENALTY_HOOK_REPLY_ID,
+        ))
+    })
+}
+
 #[cfg(feature = "integration")]
 fn execute_simulate_validators<Q: CustomQuery>(
     deps: DepsMut<Q>,
@@ -464,6 +1053,10 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> Result<Binary,
         Configuration {} => Ok(to_binary(&CONFIG.load(deps.storage)?)?),
         Epoch {} => Ok(to_binary(&query_epoch(deps, env)?)?),
         Validator { operator } => Ok(to_binary(&query_validator_key(deps, env, operator)?)?),
+        KeyRotation { operator } => Ok(to_binary(&query_key_rotation(deps, operator)?)?),
+        OperationalMetadata { operator } => {
+            Ok(to_binary(&query_operational_metadata(deps, operator)?)?)
+        }
         ListValidators { start_after, limit } => Ok(to_binary(&list_validator_keys(
             deps,
             env,
@@ -475,18 +1068,166 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> Result<Binary,
             start_after,
             limit,
         )?)?),
-        ListJailedValidators { start_after, limit } => Ok(to_binary(&list_jailed_validators(
+        ListJailedValidators {
+            start_after,
+            limit,
+            reason,
+            end,
+        } => Ok(to_binary(&list_jailed_validators(
+            deps,
+            env,
+            start_after,
+            limit,
+            reason,
+            end,
+        )?)?),
+        ListJailedValidatorsByEnd {
+            start_after,
+            limit,
+            reason,
+            end,
+        } => Ok(to_binary(&list_jailed_validators_by_end(
             deps,
             env,
             start_after,
             limit,
+            reason,
+            end,
         )?)?),
         SimulateActiveValidators {} => Ok(to_binary(&simulate_active_validators(deps, env)?)?),
         ListValidatorSlashing { operator } => {
             Ok(to_binary(&list_validator_slashing(deps, env, operator)?)?)
         }
         Admin {} => Ok(to_binary(&ADMIN.query_admin(deps)?)?),
+        ListPenaltyHooks {} => Ok(to_binary(&PenaltyHooksResponse {
+            hooks: PENALTY_HOOKS.list_hooks(deps.storage)?,
+            preauths: PREAUTH_PENALTY_HOOKS.get_auth(deps.storage)?,
+        })?),
+        ListDelegates { operator } => {
+            let operator = deps.api.addr_validate(&operator)?;
+            let delegates = OPERATOR_DELEGATES
+                .prefix(&operator)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .map(|delegate| delegate.map(String::from))
+                .collect::<StdResult<_>>()?;
+            Ok(to_binary(&DelegatesResponse { delegates })?)
+        }
+        ExitStatus { operator } => {
+            let operator = deps.api.addr_validate(&operator)?;
+            let exit = EXITS.may_load(deps.storage, &operator)?;
+            let purgeable = matches!(&exit, Some(exit) if exit.is_purgeable(&env.block));
+            Ok(to_binary(&ExitStatusResponse { exit, purgeable })?)
+        }
+        Metrics {} => Ok(to_binary(&query_metrics(deps)?)?),
+        MigrationHistory {} => Ok(to_binary(&MIGRATIONS.query(deps.storage)?)?),
+        IbcRetryQueue {} => Ok(to_binary(&IbcRetryQueueResponse {
+            transfers: IBC_RETRY_QUEUE.may_load(deps.storage)?.unwrap_or_default(),
+        })?),
+        VerifyInvariants {} => Ok(to_binary(&verify_invariants(deps)?)?),
+        SimulateRewards { validators, epochs } => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(to_binary(&simulate_rewards(
+                deps, &env, &config, validators, epochs,
+            )?)?)
+        }
+    }
+}
+
+fn query_metrics<Q: CustomQuery>(deps: Deps<Q>) -> Result<MetricsResponse, ContractError> {
+    let counters = COUNTERS.load(deps.storage)?;
+    Ok(MetricsResponse {
+        operators: counters.operators,
+        active_validators: VALIDATORS.load(deps.storage)?.len() as u64,
+        jailed: counters.jailed,
+        tombstoned: counters.tombstoned,
+        current_epoch: EPOCH.load(deps.storage)?.current_epoch,
+        last_payout: LAST_PAYOUT.may_load(deps.storage)?,
+        membership_query_failures: MEMBERSHIP_QUERY_FAILURES
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+    })
+}
+
+fn verify_invariants<Q: CustomQuery>(deps: Deps<Q>) -> Result<InvariantsResponse, ContractError> {
+    let mut violations = vec![];
+
+    let counters = COUNTERS.load(deps.storage)?;
+    let recounted = recount(deps.storage)?;
+    if counters != recounted {
+        violations.push(format!(
+            "counters {:?} differ from recounted {:?}",
+            counters, recounted
+        ));
+    }
+
+    // jail end index has to list exactly the jails
+    let indexed = JAIL_BY_END
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (end, operator) in &indexed {
+        match JAIL.may_load(deps.storage, operator)? {
+            Some(period) if period.end_key() == *end => (),
+            _ => violations.push(format!("jail end index entry of {} is stale", operator)),
+        }
+    }
+    for jail in JAIL.range(deps.storage, None, None, Order::Ascending) {
+        let (operator, period) = jail?;
+        if !JAIL_BY_END.has(deps.storage, (period.end_key(), &operator)) {
+            violations.push(format!("jail of {} is missing in the end index", operator));
+        }
+    }
+
+    // active validators have to be registered operators flagged as active
+    let validators = VALIDATORS.load(deps.storage)?;
+    for (idx, validator) in validators.iter().enumerate() {
+        if validators[..idx]
+            .iter()
+            .any(|v| v.operator == validator.operator)
+        {
+            violations.push(format!("validator {} listed twice", validator.operator));
+        }
+        match operators().may_load(deps.storage, &validator.operator)? {
+            None => violations.push(format!(
+                "validator {} is not a registered operator",
+                validator.operator
+            )),
+            Some(op) => {
+                if !op.active_validator {
+                    violations.push(format!(
+                        "validator {} is not flagged as active",
+                        validator.operator
+                    ));
+                }
+                if Pubkey::from(op.pubkey) != validator.validator_pubkey {
+                    violations.push(format!(
+                        "validator {} pubkey differs from the operator pubkey",
+                        validator.operator
+                    ));
+                }
+            }
+        }
+    }
+    for op in operators().range(deps.storage, None, None, Order::Ascending) {
+        let (operator, info) = op?;
+        if info.active_validator && validators.iter().all(|v| v.operator != operator) {
+            violations.push(format!(
+                "operator {} flagged as active is not a validator",
+                operator
+            ));
+        }
     }
+
+    let expected = expected_group_hash(deps.storage, &validators)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    match reported_group_hash(deps, &cfg.validator_group) {
+        Ok(reported) if reported == expected => (),
+        Ok(_) => violations.push(
+            "validator group members differ from validators payout addresses and power".to_owned(),
+        ),
+        Err(err) => violations.push(format!("cannot query validator group: {}", err)),
+    }
+
+    Ok(InvariantsResponse { violations })
 }
 
 fn query_epoch<Q: CustomQuery>(deps: Deps<Q>, env: Env) -> Result<EpochResponse, ContractError> {
@@ -520,12 +1261,38 @@ fn query_validator_key<Q: CustomQuery>(
     let jailed_until = JAIL
         .may_load(deps.storage, &operator_addr)?
         .filter(|expires| !(cfg.auto_unjail && expires.is_expired(&env.block)));
+    let probation_until = PROBATION.may_load(deps.storage, &operator_addr)?;
 
     Ok(ValidatorResponse {
-        validator: info.map(|i| OperatorResponse::from_info(i, operator, jailed_until)),
+        validator: info
+            .map(|i| OperatorResponse::from_info(i, operator, jailed_until, probation_until)),
     })
 }
 
+fn query_key_rotation<Q: CustomQuery>(
+    deps: Deps<Q>,
+    operator: String,
+) -> Result<KeyRotationResponse, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    let rotation = KEY_ROTATIONS
+        .may_load(deps.storage, &operator)?
+        .map(|rotation| KeyRotationInfo {
+            old_pubkey: rotation.old_pubkey.into(),
+            new_pubkey: rotation.new_pubkey.into(),
+            activation_epoch: rotation.activation_epoch,
+        });
+    Ok(KeyRotationResponse { rotation })
+}
+
+fn query_operational_metadata<Q: CustomQuery>(
+    deps: Deps<Q>,
+    operator: String,
+) -> Result<OperationalMetadataResponse, ContractError> {
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    let info = operators().may_load(deps.storage, &operator_addr)?;
+    Ok(info.map(|i| i.metadata.operational()).unwrap_or_default())
+}
+
 // settings for pagination
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 30;
@@ -549,6 +1316,7 @@ fn list_validator_keys<Q: CustomQuery>(
             let jailed_until = JAIL
                 .may_load(deps.storage, &Addr::unchecked(&operator))?
                 .filter(|expires| !(cfg.auto_unjail && expires.is_expired(&env.block)));
+            let probation_until = PROBATION.may_load(deps.storage, &operator)?;
 
             Ok(OperatorResponse {
                 operator: operator.into(),
@@ -556,6 +1324,8 @@ fn list_validator_keys<Q: CustomQuery>(
                 pubkey: info.pubkey.into(),
                 jailed_until,
                 active_validator: info.active_validator,
+                payout: info.payout,
+                probation_until,
             })
         })
         .take(limit)
@@ -597,6 +1367,8 @@ fn list_jailed_validators<Q: CustomQuery>(
     env: Env,
     start_after: Option<String>,
     limit: Option<u32>,
+    reason: Option<JailReason>,
+    end: Option<JailEndType>,
 ) -> Result<ListValidatorResponse, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
@@ -605,33 +1377,93 @@ fn list_jailed_validators<Q: CustomQuery>(
 
     let validators = JAIL
         .range(deps.storage, start, None, Order::Ascending)
+        .filter(|jail| match jail {
+            Ok((_, period)) => is_listed_jail(&cfg, &env, period, reason, end),
+            Err(_) => true,
+        })
+        .take(limit)
         .map(|jail| {
-            let (addr, jailing_period) = jail?;
-            if !(cfg.auto_unjail && jailing_period.is_expired(&env.block)) {
-                Ok(Some((addr, jailing_period)))
-            } else {
-                Ok(None)
-            }
+            let (addr, period) = jail?;
+            jailed_operator_response(deps, addr, period)
         })
-        .collect::<Result<Vec<Option<_>>, ContractError>>()?
-        .into_iter()
-        .flatten()
-        .map(|(addr, jailing_period)| {
-            let info = operators().load(deps.storage, &Addr::unchecked(&addr))?;
-            Ok(OperatorResponse {
-                operator: addr.into(),
-                metadata: info.metadata,
-                pubkey: info.pubkey.into(),
-                jailed_until: Some(jailing_period),
-                active_validator: info.active_validator,
-            })
+        .collect::<Result<Vec<OperatorResponse>, ContractError>>()?;
+
+    Ok(ListValidatorResponse { validators })
+}
+
+fn list_jailed_validators_by_end<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    reason: Option<JailReason>,
+    end: Option<JailEndType>,
+) -> Result<ListValidatorResponse, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = match maybe_addr(deps.api, start_after)? {
+        Some(operator) => {
+            let period = JAIL
+                .may_load(deps.storage, &operator)?
+                .ok_or_else(|| ContractError::NotJailed(operator.to_string()))?;
+            Some((period.end_key(), operator))
+        }
+        None => None,
+    };
+    let start = start_after
+        .as_ref()
+        .map(|(end_key, operator)| Bound::exclusive((*end_key, operator)));
+
+    let validators = JAIL_BY_END
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(|key| -> Result<_, ContractError> {
+            let (_, addr) = key?;
+            let period = JAIL.load(deps.storage, &addr)?;
+            Ok((addr, period))
+        })
+        .filter(|jail| match jail {
+            Ok((_, period)) => is_listed_jail(&cfg, &env, period, reason, end),
+            Err(_) => true,
         })
         .take(limit)
+        .map(|jail| {
+            let (addr, period) = jail?;
+            jailed_operator_response(deps, addr, period)
+        })
         .collect::<Result<Vec<OperatorResponse>, ContractError>>()?;
 
     Ok(ListValidatorResponse { validators })
 }
 
+/// Expired jails are not listed with auto unjail enabled, as they are lifted on the next epoch
+fn is_listed_jail(
+    cfg: &Config,
+    env: &Env,
+    period: &JailingPeriod,
+    reason: Option<JailReason>,
+    end: Option<JailEndType>,
+) -> bool {
+    !(cfg.auto_unjail && period.is_expired(&env.block)) && period.matches(reason, end)
+}
+
+fn jailed_operator_response<Q: CustomQuery>(
+    deps: Deps<Q>,
+    addr: Addr,
+    period: JailingPeriod,
+) -> Result<OperatorResponse, ContractError> {
+    let info = operators().load(deps.storage, &addr)?;
+    let probation_until = PROBATION.may_load(deps.storage, &addr)?;
+    Ok(OperatorResponse {
+        operator: addr.into(),
+        metadata: info.metadata,
+        pubkey: info.pubkey.into(),
+        jailed_until: Some(period),
+        active_validator: info.active_validator,
+        payout: info.payout,
+        probation_until,
+    })
+}
+
 fn simulate_active_validators<Q: CustomQuery>(
     deps: Deps<Q>,
     env: Env,
@@ -739,22 +1571,24 @@ fn end_block(deps: DepsMut<TgradeQuery>, env: Env) -> Result<Response, ContractE
     epoch.current_epoch = cur_epoch;
     EPOCH.save(deps.storage, &epoch)?;
 
+    let mut penalty_hooks = vec![];
     if cfg.verify_validators {
         let expiration = JailingPeriod::from_duration(
             JailingDuration::Duration(cfg.offline_jail_duration),
+            JailReason::Offline,
             &env.block,
         );
 
         VALIDATORS
             .load(deps.storage)?
             .iter()
-            .flat_map(|v| match Ed25519Pubkey::try_from(&v.validator_pubkey) {
+            .flat_map(|v| match ValidatedPubkey::try_from(&v.validator_pubkey) {
                 Ok(pubkey) => Some((v, pubkey)),
-                _ => None, // Silently ignore wrong / different type pubkeys
+                _ => None, // Silently ignore wrong / unsupported type pubkeys
             })
-            .try_for_each(|(v, ed25519_pubkey)| {
+            .try_for_each(|(v, pubkey)| -> StdResult<_> {
                 let operator_addr = &v.operator;
-                let validator_addr = ed25519_pubkey.to_address();
+                let validator_addr = pubkey.to_address();
                 let mut height = BLOCK_SIGNERS.may_load(deps.storage, &validator_addr)?;
                 if height.is_none() {
                     // Not a block signer yet, check their validator start height instead
@@ -764,31 +1598,116 @@ fn end_block(deps: DepsMut<TgradeQuery>, env: Env) -> Result<Response, ContractE
                     Some(h) if h > env.block.height.saturating_sub(MISSED_BLOCKS) => Ok(()),
                     _ => {
                         // validator is inactive for at least MISSED_BLOCKS, jail!
-                        JAIL.save(deps.storage, operator_addr, &expiration)
+                        save_jail(deps.storage, operator_addr, &expiration)?;
+                        penalty_hooks.extend(penalty_hooks_msgs(
+                            deps.storage,
+                            &PenaltyHookMsg::ValidatorPenalty {
+                                operator: operator_addr.to_string(),
+                                reason: PenaltyReason::Offline,
+                                jailed_until: Some(expiration.end.clone()),
+                                slash_portion: None,
+                                height: env.block.height,
+                            },
+                        )?);
+                        Ok(())
                     }
                 }
             })?;
     }
 
+    update_key_rotations(deps.storage, &env, &cfg, cur_epoch)?;
+    // exiting operators are left out of the new validator set below, and rewards paid on this
+    // epoch boundary are their last ones
+    settle_exits(deps.storage, &env, &cfg, cur_epoch)?;
+
     // calculate and store new validator set
-    let (validators, auto_unjail) = calculate_validators(deps.as_ref(), &env)?;
+    let mut membership_failure = None;
+    let (validators, auto_unjail) = match calculate_validators(deps.as_ref(), &env) {
+        Ok(res) => res,
+        // membership contract may be broken (eg. by a migration), which should not halt
+        // validator updates - the current set is kept until it is fixed
+        Err(ContractError::Std(err)) if cfg.keep_validators_on_membership_failure => {
+            let failures = MEMBERSHIP_QUERY_FAILURES
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            MEMBERSHIP_QUERY_FAILURES.save(deps.storage, &failures)?;
+            membership_failure = Some(
+                Event::new("membership_query_failure")
+                    .add_attribute("membership", cfg.membership.addr())
+                    .add_attribute("error", err.to_string())
+                    .add_attribute("failures", failures.to_string()),
+            );
+            (VALIDATORS.load(deps.storage)?, vec![])
+        }
+        Err(err) => return Err(err),
+    };
 
     // auto unjailing
     for addr in &auto_unjail {
-        JAIL.remove(deps.storage, addr)
+        remove_jail(deps.storage, addr)?;
     }
 
+    // validator group is not updated yet, so it should still match the previous epoch update
+    let group_drift = match EXPECTED_GROUP_HASH.may_load(deps.storage)? {
+        Some(expected) => {
+            let event = Event::new("validator_group_drift")
+                .add_attribute("validator_group", cfg.validator_group.as_str())
+                .add_attribute("expected_hash", expected.to_base64());
+            // failing group should not halt validator updates, it is reported as drift instead
+            let event = match reported_group_hash(deps.as_ref(), &cfg.validator_group) {
+                Ok(reported) if reported == expected => None,
+                Ok(reported) => Some(event.add_attribute("reported_hash", reported.to_base64())),
+                Err(err) => Some(event.add_attribute("error", err.to_string())),
+            };
+            event.map(|event| {
+                event.add_attribute("payout_skipped", cfg.skip_payout_on_group_drift.to_string())
+            })
+        }
+        None => None,
+    };
+
     let old_validators = VALIDATORS.load(deps.storage)?;
 
     // determine the diff to send back to tendermint
-    let (diff, add, remove) = calculate_diff(validators.clone(), old_validators);
+    let (diff, mut add, remove) = calculate_diff(validators.clone(), old_validators);
+    update_probation(deps.storage, &cfg, cur_epoch, &validators, &mut add)?;
+    let expected_hash = expected_group_hash(deps.storage, &validators)?;
+    EXPECTED_GROUP_HASH.save(deps.storage, &expected_hash)?;
+    // validator group members are payout addresses of the operators
     let update_members = RewardsDistribution::UpdateMembers {
-        add: add.clone(),
-        remove: remove.clone(),
+        add: add
+            .iter()
+            .map(|member| {
+                Ok(Member {
+                    addr: payout_addr(deps.storage, &member.addr)?.into(),
+                    ..member.clone()
+                })
+            })
+            .collect::<StdResult<_>>()?,
+        remove: remove
+            .iter()
+            .map(|operator| payout_addr(deps.storage, operator).map(String::from))
+            .collect::<StdResult<_>>()?,
     };
 
     VALIDATORS.save(deps.storage, &validators)?;
 
+    // Store starting heights of new validators
+    for member in &add {
+        let addr = Addr::unchecked(member.addr.clone());
+        if VALIDATOR_START_HEIGHT
+            .may_load(deps.storage, &addr)?
+            .is_none()
+        {
+            // see https://github.com/confio/tgrade-contracts/pull/309#discussion_r748164514
+            // for details
+            // Validator is added on an epoch boundary only. But next block contains proof of
+            // the new validator
+            VALIDATOR_START_HEIGHT.save(deps.storage, &addr, &(env.block.height + 1))?;
+        }
+    }
+
     // update operators list with info about whether or not they're active validators
     for op in add {
         operators().update::<_, StdError>(deps.storage, &Addr::unchecked(op.addr), |op| {
@@ -805,29 +1724,10 @@ fn end_block(deps: DepsMut<TgradeQuery>, env: Env) -> Result<Response, ContractE
         })?;
     }
 
-    // Store starting heights of new validators
-    match &update_members {
-        RewardsDistribution::UpdateMembers { add, .. } => {
-            for member in add {
-                let addr = Addr::unchecked(member.addr.clone());
-                if VALIDATOR_START_HEIGHT
-                    .may_load(deps.storage, &addr)?
-                    .is_none()
-                {
-                    // see https://github.com/confio/tgrade-contracts/pull/309#discussion_r748164514
-                    // for details
-                    // Validator is added on an epoch boundary only. But next block contains proof of
-                    // the new validator
-                    VALIDATOR_START_HEIGHT.save(deps.storage, &addr, &(env.block.height + 1))?;
-                }
-            }
-        }
-        _ => unreachable!(),
-    }
-
     // provide payment if there are rewards to give
     let mut res = Response::new().set_data(to_binary(&diff)?);
-    if pay_epochs > 0 {
+    let skip_payout = group_drift.is_some() && cfg.skip_payout_on_group_drift;
+    if pay_epochs > 0 && !skip_payout {
         res.messages = pay_block_rewards(deps, env, pay_epochs, &cfg)?
     };
 
@@ -837,9 +1737,166 @@ fn end_block(deps: DepsMut<TgradeQuery>, env: Env) -> Result<Response, ContractE
         funds: vec![],
     }));
 
+    let mut res = res.add_submessages(penalty_hooks);
+    if let Some(event) = membership_failure {
+        res = res.add_event(event);
+    }
+    if let Some(event) = group_drift {
+        res = res.add_event(event);
+    }
+
     Ok(res)
 }
 
+/// Hash of `validator_group` members sorted by address, paired with their points
+fn group_hash(mut members: Vec<(String, u64)>) -> StdResult<Binary> {
+    members.sort();
+    Ok(Binary::from(Sha256::digest(&to_vec(&members)?).as_slice()))
+}
+
+/// Hash of `validator_group` members expected after updating it to `validators`: payout
+/// addresses of the validators with their power, zeroed for validators on probation. Validators
+/// without operator info stored (as after a broken import) are their own payout address.
+fn expected_group_hash(storage: &dyn Storage, validators: &[ValidatorInfo]) -> StdResult<Binary> {
+    let members = validators
+        .iter()
+        .map(|validator| {
+            let operator = &validator.operator;
+            let addr = match operators().may_load(storage, operator)? {
+                Some(info) => info.payout_addr(operator),
+                None => operator.clone(),
+            };
+            let points = if PROBATION.has(storage, operator) {
+                0
+            } else {
+                validator.power
+            };
+            Ok((addr.into(), points))
+        })
+        .collect::<StdResult<_>>()?;
+    group_hash(members)
+}
+
+/// Hash of members `validator_group` reports, see `expected_group_hash`
+fn reported_group_hash<Q: CustomQuery>(deps: Deps<Q>, validator_group: &Addr) -> StdResult<Binary> {
+    let group = Tg4Contract::new(validator_group.clone());
+    let mut members = vec![];
+    let mut last = None;
+    loop {
+        let page = group.list_members(&deps.querier, last, QUERY_LIMIT)?;
+        last = match page.last() {
+            Some(member) => Some(member.addr.clone()),
+            None => break,
+        };
+        members.extend(page.into_iter().map(|member| (member.addr, member.points)));
+    }
+    group_hash(members)
+}
+
+/// Activates key rotations due at `epoch`, and stops tracking rotations activated on an earlier
+/// epoch.
+fn update_key_rotations(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cfg: &Config,
+    epoch: u64,
+) -> StdResult<()> {
+    let rotations = KEY_ROTATIONS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (operator, rotation) in rotations {
+        let mut info = operators().load(storage, &operator)?;
+        if info.pubkey != rotation.new_pubkey {
+            if rotation.activation_epoch <= epoch {
+                info.pubkey = rotation.new_pubkey.clone();
+                operators().save(storage, &operator, &info)?;
+                if cfg.verify_validators {
+                    // The new key starts signing with the next block, same as a new validator
+                    BLOCK_SIGNERS.save(
+                        storage,
+                        &rotation.new_pubkey.to_address(),
+                        &(env.block.height + 1),
+                    )?;
+                }
+            }
+        } else if rotation.activation_epoch < epoch {
+            KEY_ROTATIONS.remove(storage, &operator);
+        }
+    }
+    Ok(())
+}
+
+/// Marks exits requested since the last epoch boundary as settled on `epoch`, starting their
+/// cooldown.
+fn settle_exits(storage: &mut dyn Storage, env: &Env, cfg: &Config, epoch: u64) -> StdResult<()> {
+    let pending = EXITS
+        .range(storage, None, None, Order::Ascending)
+        .filter(|exit| !matches!(exit, Ok((_, exit)) if exit.settled_epoch.is_some()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let cooldown = cfg.exit_cooldown.map(|d| d.seconds()).unwrap_or_default();
+    for (operator, mut exit) in pending {
+        exit.settled_epoch = Some(epoch);
+        exit.purgeable_at = Some(env.block.time.plus_seconds(cooldown));
+        EXITS.save(storage, &operator, &exit)?;
+    }
+    Ok(())
+}
+
+/// Starts probation of validators active for the first time, and ends probations which are
+/// over. Adjusts validator group members to be added, so validators on probation have no points
+/// (and receive no rewards), and those finishing probation get their points back.
+fn update_probation(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    epoch: u64,
+    validators: &[ValidatorInfo],
+    add: &mut Vec<Member>,
+) -> StdResult<()> {
+    if cfg.probation_epochs > 0 {
+        for member in add.iter() {
+            let addr = Addr::unchecked(&member.addr);
+            if !VALIDATOR_START_HEIGHT.has(storage, &addr) {
+                PROBATION.save(storage, &addr, &(epoch + cfg.probation_epochs))?;
+            }
+        }
+    }
+
+    let finished = PROBATION
+        .range(storage, None, None, Order::Ascending)
+        .filter(|probation| !matches!(probation, Ok((_, until)) if *until > epoch))
+        .map(|probation| probation.map(|(addr, _)| addr))
+        .collect::<StdResult<Vec<_>>>()?;
+    for addr in finished {
+        PROBATION.remove(storage, &addr);
+        // validators with unchanged power are not in `add`, but their points need an update
+        if add.iter().all(|member| member.addr != addr.as_str()) {
+            if let Some(validator) = validators.iter().find(|v| v.operator == addr) {
+                add.push(Member {
+                    addr: addr.to_string(),
+                    points: validator.power,
+                    start_height: None,
+                });
+            }
+        }
+    }
+
+    for member in add.iter_mut() {
+        if PROBATION.has(storage, &Addr::unchecked(&member.addr)) {
+            member.points = 0;
+        }
+    }
+    Ok(())
+}
+
+/// Address which is a member of the validator group on behalf of `operator`
+fn payout_addr(storage: &dyn Storage, operator: &str) -> StdResult<Addr> {
+    let operator = Addr::unchecked(operator);
+    let info = operators().load(storage, &operator)?;
+    Ok(info.payout_addr(&operator))
+}
+
 const QUERY_LIMIT: Option<u32> = Some(30);
 
 /// Selects validators to be used for incoming epoch. Returns vector of validators info paired
@@ -851,7 +1908,7 @@ fn calculate_validators<Q: CustomQuery>(
     let cfg = CONFIG.load(deps.storage)?;
 
     let min_points = max(cfg.min_points, 1);
-    let scaling: u64 = cfg.scaling.unwrap_or(1).into();
+    let curve = cfg.scaling_curve();
 
     // get all validators from the contract, filtered
     let mut validators = vec![];
@@ -893,11 +1950,16 @@ fn calculate_validators<Q: CustomQuery>(
                     _ => return None,
                 };
 
+                // exiting operators are never selected again
+                if EXITS.has(deps.storage, &m_addr) {
+                    return None;
+                }
+
                 operators().load(deps.storage, &m_addr).ok().map(|op| {
                     Ok(ValidatorInfo {
                         operator: m_addr,
                         validator_pubkey: op.pubkey.into(),
-                        power: m.points * scaling,
+                        power: curve.power(m.points),
                     })
                 })
             })
@@ -919,6 +1981,8 @@ fn calculate_validators<Q: CustomQuery>(
 /// The diffs are calculated by computing two (slightly different) differences:
 /// - In `cur` but not in `old` (comparing by `operator` and `power`) => update with `cur` (handles additions and updates).
 /// - In `old` but not in `cur` (comparing by `validator_pubkey` only) => update with `old`, set power to zero (handles removals).
+///   Operators which rotated their key are removed from Tendermint with the old key, but not
+///   from the members.
 ///
 /// Uses `validator_pubkey` instead of `operator`, to use the derived `Ord` and `PartialOrd` impls for it.
 /// `operators` and `pubkeys` are one-to-one, so this is legit.
@@ -962,6 +2026,8 @@ fn calculate_diff(
         .map(|vi| (&vi.validator_pubkey, &vi.operator))
         .collect();
 
+    let cur_operators: BTreeSet<_> = cur_vals.iter().map(|vi| &vi.operator).collect();
+
     let (removed_diff, remove): (Vec<_>, Vec<_>) = old
         .difference(&cur)
         .map(|&(pubkey, operator)| {
@@ -969,11 +2035,12 @@ fn calculate_diff(
                 pubkey: pubkey.clone(),
                 power: 0,
             };
-            let member = operator.to_string();
+            let member = (!cur_operators.contains(operator)).then(|| operator.to_string());
 
             (update, member)
         })
         .unzip();
+    let remove = remove.into_iter().flatten().collect();
 
     // Compute, map and append removals to diffs
     diffs.extend(removed_diff);
@@ -984,10 +2051,15 @@ fn calculate_diff(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
     deps: DepsMut<TgradeQuery>,
-    _env: Env,
+    env: Env,
     msg: MigrateMsg,
 ) -> Result<Response, ContractError> {
-    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let mut migration = MIGRATIONS.start(
+        deps.storage,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        env.block.height,
+    )?;
 
     CONFIG.update::<_, StdError>(deps.storage, |mut cfg| {
         if let Some(min_points) = msg.min_points {
@@ -1002,9 +2074,33 @@ pub fn migrate(
         if let Some(verify_validators) = msg.verify_validators {
             cfg.verify_validators = verify_validators;
         }
+        if let Some(exit_cooldown) = msg.exit_cooldown {
+            cfg.exit_cooldown = Some(exit_cooldown);
+        }
         Ok(cfg)
     })?;
 
+    if migration.step("metrics_counters", "<=0.17.1")? && COUNTERS.may_load(deps.storage)?.is_none()
+    {
+        reset_counters(deps.storage)?;
+    }
+
+    // Re-save operators to populate the payout address index for entries stored before it existed
+    if migration.step("payout_address_index", "<=0.17.1")? {
+        let ops = operators()
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (addr, op) in ops {
+            operators().replace(deps.storage, &addr, Some(&op), Some(&op))?;
+        }
+    }
+
+    if migration.step("jail_end_index", "<=0.17.1")? {
+        reindex_jails(deps.storage)?;
+    }
+
+    MIGRATIONS.finish(deps.storage, migration)?;
+
     Ok(Response::new())
 }
 
@@ -1032,22 +2128,27 @@ mod evidence {
                         return Ok(None);
                     }
                     let operator = operators().load(deps.storage, &addr)?;
-                    let hash = Binary::from(operator.pubkey.to_address());
-                    if hash == suspect.address {
-                        return Ok(Some(addr));
+                    // During key rotation, both the old and the new key are matched
+                    let mut pubkeys = vec![operator.pubkey];
+                    if let Some(rotation) = KEY_ROTATIONS.may_load(deps.storage, &addr)? {
+                        pubkeys.extend([rotation.old_pubkey, rotation.new_pubkey]);
                     }
-                    Ok(None)
+                    let matches = pubkeys
+                        .iter()
+                        .any(|pubkey| pubkey.to_address().as_slice() == suspect.address.as_slice());
+                    Ok(matches.then_some(addr))
                 })
                 .transpose()
             })
             .transpose()
     }
 
-    pub fn slash_validator_msg(config: &Config, addr: String) -> Result<SubMsg, ContractError> {
-        let slash_msg = SlashMsg::Slash {
-            addr,
-            portion: config.double_sign_slash_ratio,
-        };
+    pub fn slash_validator_msg(
+        config: &Config,
+        addr: String,
+        portion: Decimal,
+    ) -> Result<SubMsg, ContractError> {
+        let slash_msg = SlashMsg::Slash { addr, portion };
         let slash_msg = to_binary(&slash_msg)?;
 
         Ok(SubMsg::new(WasmMsg::Execute {
@@ -1071,40 +2172,68 @@ fn begin_block(
     }
 
     let config = CONFIG.load(deps.storage)?;
+    let policy = config.evidence_policy();
 
     let mut response = Response::new();
 
     evidences
         .iter()
-        .flat_map(|e| match e.evidence_type {
-            EvidenceType::DuplicateVote => Some((e.validator.clone(), e.height)),
-            _ => None,
+        .flat_map(|e| {
+            policy
+                .slash_ratio(&e.evidence_type)
+                .map(|ratio| (e.validator.clone(), e.height, ratio))
         })
-        .map(|(validator, evidence_height)| {
+        .map(|(validator, evidence_height, slash_ratio)| {
             // If there's match between evidence validator's hash and one from list of validators,
             // then jail and slash that validator
             if let Some(validator) =
                 evidence::find_matching_validator(deps.as_ref(), &validator, evidence_height)?
             {
-                let sub_msg = evidence::slash_validator_msg(&config, validator.to_string())?;
-                store_slashing_event(
-                    deps.branch(),
-                    &env,
-                    validator.clone(),
-                    config.double_sign_slash_ratio,
-                )?;
+                let offenses = EVIDENCE_OFFENSES
+                    .may_load(deps.storage, &validator)?
+                    .unwrap_or_default();
+                EVIDENCE_OFFENSES.save(deps.storage, &validator, &(offenses + 1))?;
+
+                let slash_portion = if policy.jail_only_first_offense && offenses == 0 {
+                    None
+                } else {
+                    Some(slash_ratio).filter(|ratio| !ratio.is_zero())
+                };
+                if let Some(portion) = slash_portion {
+                    let sub_msg =
+                        evidence::slash_validator_msg(&config, validator.to_string(), portion)?;
+                    store_slashing_event(deps.branch(), &env, validator.clone(), portion)?;
+                    response = response.clone().add_submessage(sub_msg);
+                }
+
+                let jailing = JailingPeriod::from_duration(
+                    policy.jail_duration.clone(),
+                    JailReason::Evidence,
+                    &env.block,
+                );
+                save_jail(deps.storage, &validator, &jailing)?;
 
-                JAIL.save(
+                let hooks = penalty_hooks_msgs(
                     deps.storage,
-                    &validator,
-                    &JailingPeriod::from_duration(JailingDuration::Forever {}, &env.block),
+                    &PenaltyHookMsg::ValidatorPenalty {
+                        operator: validator.to_string(),
+                        reason: PenaltyReason::DoubleSign,
+                        jailed_until: Some(jailing.end),
+                        slash_portion,
+                        height: env.block.height,
+                    },
                 )?;
 
+                let action = if slash_portion.is_some() {
+                    "slash_and_jail"
+                } else {
+                    "jail"
+                };
                 response = response
                     .clone()
-                    .add_attribute("action", "slash_and_jail")
+                    .add_attribute("action", action)
                     .add_attribute("validator", validator.as_str())
-                    .add_submessage(sub_msg);
+                    .add_submessages(hooks);
             }
             Ok(())
         })
@@ -1117,10 +2246,48 @@ fn begin_block(
 pub fn reply(deps: DepsMut<TgradeQuery>, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         REWARDS_INIT_REPLY_ID => rewards_instantiate_reply(deps, env, msg),
+        PENALTY_HOOK_REPLY_ID => Ok(penalty_hook_reply(msg)),
+        IBC_TRANSFER_REPLY_ID => ibc_transfer_reply(deps, msg),
         _ => Err(ContractError::UnrecognisedReply(msg.id)),
     }
 }
 
+/// Replies come in the dispatch order of the transfers. Funds of a failed transfer are still in
+/// the contract, so they are queued to be sent with the next payout.
+fn ibc_transfer_reply<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let mut pending = IBC_PENDING_TRANSFERS.load(deps.storage)?;
+    if pending.is_empty() {
+        return Err(ContractError::UnrecognisedReply(msg.id));
+    }
+    let transfer = pending.remove(0);
+    IBC_PENDING_TRANSFERS.save(deps.storage, &pending)?;
+
+    let err = match msg.result.into_result() {
+        Ok(_) => return Ok(Response::new()),
+        Err(err) => err,
+    };
+
+    let mut queue = IBC_RETRY_QUEUE.may_load(deps.storage)?.unwrap_or_default();
+    add_coins(&mut queue, [transfer.clone()]);
+    IBC_RETRY_QUEUE.save(deps.storage, &queue)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "ibc_transfer_failed")
+        .add_attribute("amount", transfer.to_string())
+        .add_attribute("error", err))
+}
+
+/// Failing penalty hook should never block jailing or slashing, so the error is only reported.
+fn penalty_hook_reply(msg: Reply) -> Response {
+    let err = msg.result.into_result().err().unwrap_or_default();
+    Response::new()
+        .add_attribute("action", "penalty_hook_failed")
+        .add_attribute("error", err)
+}
+
 pub fn rewards_instantiate_reply<Q: CustomQuery>(
     deps: DepsMut<Q>,
     _env: Env,
@@ -1151,7 +2318,12 @@ pub fn rewards_instantiate_reply<Q: CustomQuery>(
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::state::{default_pubkey_types, Counters};
     use crate::test_helpers::{addrs, valid_validator};
+    use cosmwasm_std::coin;
+    use cosmwasm_std::testing::mock_env;
+    use tg_bindings::ValidatorVote;
+    use tg_bindings_test::{mock_deps_tgrade_with, TgradeDeps, TgradeFixtures};
 
     // Number of validators for tests
     const VALIDATORS: usize = 32;
@@ -1455,4 +2627,98 @@ mod test {
                 .collect::<Vec<_>>()
         );
     }
+
+    fn vote(validator: &ValidatorInfo, voted: bool) -> ValidatorVote {
+        let pubkey = ValidatedPubkey::try_from(&validator.validator_pubkey).unwrap();
+        ValidatorVote {
+            address: Binary::from(pubkey.to_address()),
+            power: validator.power,
+            voted,
+        }
+    }
+
+    #[test]
+    fn end_block_jails_validators_not_signing_blocks() {
+        let signing = valid_validator("signing", 1);
+        let offline = valid_validator("offline", 1);
+        let fixtures = TgradeFixtures::default()
+            .with_validator_votes(vec![vote(&signing, true), vote(&offline, false)]);
+        let mut deps = mock_deps_tgrade_with(fixtures);
+
+        // the first epoch ends in 100 seconds, and it is not paid, so rewards are not queried
+        let mut env = mock_env();
+        let epoch_length = env.block.time.seconds() + 100;
+        CONFIG
+            .save(
+                &mut deps.storage,
+                &Config {
+                    membership: Tg4Contract::new(Addr::unchecked("membership")),
+                    min_points: 1,
+                    max_validators: 10,
+                    scaling: None,
+                    epoch_reward: coin(100, "usdc"),
+                    extra_epoch_rewards: vec![],
+                    fee_percentage: Decimal::zero(),
+                    auto_unjail: false,
+                    double_sign_slash_ratio: Decimal::percent(50),
+                    distribution_contracts: vec![],
+                    validator_group: Addr::unchecked("validator_group"),
+                    verify_validators: true,
+                    offline_jail_duration: Duration::new(3600),
+                    probation_epochs: 0,
+                    pubkey_types: default_pubkey_types(),
+                    ibc_distribution: None,
+                    exit_cooldown: None,
+                    evidence_policy: None,
+                    // the mocked membership cannot be queried, the current set is kept
+                    keep_validators_on_membership_failure: true,
+                    skip_payout_on_group_drift: false,
+                    require_key_proof: false,
+                    scaling_curve: None,
+                },
+            )
+            .unwrap();
+        EPOCH
+            .save(
+                &mut deps.storage,
+                &EpochInfo {
+                    epoch_length,
+                    current_epoch: 0,
+                    last_update_time: 0,
+                    last_update_height: 0,
+                },
+            )
+            .unwrap();
+        crate::state::VALIDATORS
+            .save(&mut deps.storage, &vec![signing.clone(), offline.clone()])
+            .unwrap();
+        COUNTERS
+            .save(&mut deps.storage, &Counters::default())
+            .unwrap();
+
+        // Signers are recorded on every block, validators are verified on epoch boundaries only
+        sudo(
+            deps.as_mut(),
+            env.clone(),
+            TgradeSudoMsg::EndWithValidatorUpdate {},
+        )
+        .unwrap();
+        let signed = |deps: &TgradeDeps, validator: &ValidatorInfo| {
+            let pubkey = ValidatedPubkey::try_from(&validator.validator_pubkey).unwrap();
+            BLOCK_SIGNERS
+                .may_load(&deps.storage, &pubkey.to_address())
+                .unwrap()
+        };
+        assert_eq!(
+            [signed(&deps, &signing), signed(&deps, &offline)],
+            [Some(env.block.height), None]
+        );
+        assert!(!JAIL.has(&deps.storage, &offline.operator));
+
+        env.block.height += 1;
+        env.block.time = env.block.time.plus_seconds(100);
+        sudo(deps.as_mut(), env, TgradeSudoMsg::EndWithValidatorUpdate {}).unwrap();
+        assert!(!JAIL.has(&deps.storage, &signing.operator));
+        assert!(JAIL.has(&deps.storage, &offline.operator));
+    }
 }
@@ -2,7 +2,8 @@ use cosmwasm_std::StdError;
 use thiserror::Error;
 
 use cw_controllers::AdminError;
-use tg_bindings::Ed25519PubkeyConversionError;
+use tg_bindings::{Ed25519PubkeyConversionError, PubkeyConversionError};
+use tg_utils::{HookError, PreauthError};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -12,9 +13,54 @@ pub enum ContractError {
     #[error("{0}")]
     AdminError(#[from] AdminError),
 
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("{0}")]
+    Preauth(#[from] PreauthError),
+
     #[error("Operator is already registered, cannot change Tendermint pubkey")]
     OperatorRegistered {},
 
+    #[error("Pubkey is already used by an operator")]
+    PubkeyInUse {},
+
+    #[error("Proof of possession of the validator key is required")]
+    MissingKeyProof {},
+
+    #[error("Invalid proof of possession of the validator key")]
+    InvalidKeyProof {},
+
+    #[error("Operator {0} is not registered")]
+    OperatorNotRegistered(String),
+
+    #[error("Key rotation is already in progress, activating on epoch {0}")]
+    KeyRotationInProgress(u64),
+
+    #[error("Operator {0} is already exiting")]
+    AlreadyExiting(String),
+
+    #[error("Operator {0} is not exiting")]
+    NotExiting(String),
+
+    #[error("Exit of operator {0} is not settled or its cooldown is not over")]
+    ExitCooldownNotOver(String),
+
+    #[error("Operator {0} is not jailed")]
+    NotJailed(String),
+
+    #[error("Cannot purge jailed operator {0}")]
+    PurgeJailedForbidden(String),
+
+    #[error("Operator cannot be its own delegate")]
+    SelfDelegate {},
+
+    #[error("{0} is not a delegate of the operator")]
+    NotDelegate(String),
+
+    #[error("Address {0} is already used as a payout address by another operator")]
+    PayoutAddressTaken(String),
+
     #[error("Received system callback we didn't expect")]
     UnsupportedSudoType {},
 
@@ -27,6 +73,9 @@ pub enum ContractError {
     #[error("You must use a valid denom for the block reward (> 2 chars)")]
     InvalidRewardDenom {},
 
+    #[error("Invalid extra epoch reward: {0}")]
+    InvalidExtraReward(String),
+
     #[error("Min_points must be greater than zero")]
     InvalidMinPoints {},
 
@@ -36,18 +85,35 @@ pub enum ContractError {
     #[error("Scaling must be unset or greater than zero")]
     InvalidScaling {},
 
-    #[error("Tendermint pubkey must be 32 bytes long")]
+    #[error("Only one of scaling and scaling curve can be set")]
+    ConflictingScaling {},
+
+    #[error(
+        "Invalid Tendermint pubkey, expected 32 bytes ed25519 or 33 bytes compressed secp256k1 key"
+    )]
     InvalidPubkey {},
 
+    #[error("Pubkey type {0} is not allowed")]
+    PubkeyTypeNotAllowed(String),
+
+    #[error("At least one pubkey type has to be allowed")]
+    NoPubkeyTypes {},
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
     #[error("No validators")]
     NoValidators {},
 
+    #[error("Evidence policy slash ratios cannot exceed 100%")]
+    InvalidEvidencePolicy {},
+
     #[error("The sum of distribution contract ratios exceeds 100%")]
     InvalidRewardsRatio {},
 
+    #[error("Invalid IBC distribution: {0}")]
+    InvalidIbcDistribution(String),
+
     #[error("No distribution contract")]
     NoDistributionContract {},
 
@@ -66,6 +132,25 @@ pub enum ContractError {
     #[error("Cannot unjail validator who's been jailed forever")]
     UnjailFromJailForeverForbidden {},
 
+    #[error("Validator is not tombstoned: {0}")]
+    NotTombstoned(String),
+
+    #[error("Too many genesis validators: {count}, max validators is {max}")]
+    TooManyGenesisValidators { count: usize, max: u32 },
+
+    #[error("Genesis validator {0} specified more than once")]
+    DuplicatedGenesisValidator(String),
+
+    #[error("Genesis validator {operator} has {points} points, at least {min} required")]
+    GenesisValidatorPointsTooLow {
+        operator: String,
+        points: u64,
+        min: u64,
+    },
+
+    #[error("Total power of genesis validators exceeds Tendermint limit")]
+    GenesisPowerTooHigh {},
+
     #[error("Jail did not yet expire")]
     JailDidNotExpire {},
 
@@ -78,6 +163,15 @@ pub enum ContractError {
 
     #[error("Invalid metadata - website needs to start with http:// or https://")]
     InvalidMetadataWebsitePrefix {},
+
+    #[error("Invalid metadata - rpc_endpoint needs to start with http://, https:// or tcp://")]
+    InvalidMetadataRpcEndpointPrefix {},
+
+    #[error("Invalid metadata - p2p_address needs to be in <node_id>@<host>:<port> format")]
+    InvalidMetadataP2pAddress {},
+
+    #[error("Invalid metadata - pgp_fingerprint needs to be 40 hex characters")]
+    InvalidMetadataPgpFingerprint {},
 }
 
 impl From<Ed25519PubkeyConversionError> for ContractError {
@@ -85,3 +179,9 @@ impl From<Ed25519PubkeyConversionError> for ContractError {
         ContractError::InvalidPubkey {}
     }
 }
+
+impl From<PubkeyConversionError> for ContractError {
+    fn from(_err: PubkeyConversionError) -> Self {
+        ContractError::InvalidPubkey {}
+    }
+}
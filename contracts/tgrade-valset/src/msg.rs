@@ -1,15 +1,19 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::convert::TryFrom;
 use std::ops::Add;
 
 use tg4::Member;
-use tg_bindings::{Ed25519Pubkey, Pubkey};
+use tg_bindings::{Pubkey, PubkeyType, ValidatedPubkey};
 use tg_utils::{Duration, Expiration, JailingDuration};
 
 use crate::error::ContractError;
-use crate::state::{DistributionContract, OperatorInfo, ValidatorInfo, ValidatorSlashing};
-use cosmwasm_std::{Addr, Api, BlockInfo, Coin, Decimal, Timestamp};
+use crate::state::{
+    default_pubkey_types, DistributionContract, EvidencePolicy, IbcDistribution, OperatorExit,
+    OperatorInfo, PayoutTotals, ScalingCurve, ValidatorInfo, ValidatorSlashing,
+};
+use cosmwasm_std::{Addr, Api, Binary, BlockInfo, Coin, Decimal, Timestamp, Uint128};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct InstantiateMsg {
@@ -37,14 +41,30 @@ pub struct InstantiateMsg {
     /// each month.
     /// Ensure this is sensible in relation to the total token supply.
     pub epoch_reward: Coin,
+    /// Fixed budget of other tokens (eg. IBC tokens) paid out at each epoch together with
+    /// `epoch_reward`. These are not minted, but taken from this contract balance, so it has to be
+    /// funded. If the balance is insufficient, only what is available is paid.
+    #[serde(default)]
+    pub extra_epoch_rewards: Vec<Coin>,
 
     /// Initial operators and validator keys registered.
     /// If you do not set this, the validators need to register themselves before
     /// making this privileged/calling the EndBlockers, so that we have a non-empty validator set
     pub initial_keys: Vec<OperatorInitInfo>,
 
+    /// Validators registered as operators (as `initial_keys`), and additionally added to
+    /// `membership` with their initial points on instantiation, so the first validator set
+    /// doesn't need any additional setup. Requires this contract to be the admin of `membership`,
+    /// which has to accept `UpdateMembers` (eg. tg4-engagement).
+    #[serde(default)]
+    pub genesis_validators: Vec<GenesisValidator>,
+
     /// A scaling factor to multiply cw4-group points to produce the Tendermint validator power
     pub scaling: Option<u32>,
+    /// Function producing the Tendermint validator power from cw4-group points, instead of the
+    /// constant `scaling` multiplier. Only one of them can be set.
+    #[serde(default)]
+    pub scaling_curve: Option<ScalingCurve>,
 
     /// Percentage of total accumulated fees that is subtracted from tokens minted as rewards.
     /// 50% by default. To disable this feature just set it to 0 (which effectively means that fees
@@ -99,6 +119,52 @@ pub struct InstantiateMsg {
     /// The duration to jail a validator for in case they don't sign their first epoch
     /// boundary block. After the period, they have to pass verification again, ad infinitum.
     pub offline_jail_duration: Duration,
+
+    /// Number of penalty hooks which can be added by anyone, not only the admin.
+    #[serde(default)]
+    pub preauths_penalty_hooks: u64,
+
+    /// Number of epochs validators spend on probation after becoming active for the first time.
+    /// They sign blocks as usual, but receive no rewards until the probation is over.
+    /// 0 (default) disables probation.
+    #[serde(default)]
+    pub probation_epochs: u64,
+
+    /// Types of Tendermint pubkeys operators can register, only ed25519 if not set
+    #[serde(default)]
+    pub pubkey_types: Option<Vec<PubkeyType>>,
+
+    /// Part of the reward sent over IBC to an address on another chain
+    #[serde(default)]
+    pub ibc_distribution: Option<IbcDistribution>,
+
+    /// Time after the exit of an operator is settled, after which its record can be purged.
+    /// If not set, the record can be purged right after the settlement.
+    #[serde(default)]
+    pub exit_cooldown: Option<Duration>,
+
+    /// Response to misbehaviour evidence: slash ratios per evidence type, jail duration, and
+    /// whether first offenses are only jailed. If not set, double signing is slashed by
+    /// `double_sign_slash_ratio` and jailed forever, while other evidence is ignored.
+    #[serde(default)]
+    pub evidence_policy: Option<EvidencePolicy>,
+
+    /// If set, failing to query `membership` on the epoch boundary keeps the previous validator
+    /// set (emitting a `membership_query_failure` event) instead of failing the end block
+    #[serde(default)]
+    pub keep_validators_on_membership_failure: bool,
+
+    /// On every epoch boundary members of `validator_group` and their points are checked
+    /// against the ones expected after the previous update, emitting a `validator_group_drift` event on mismatch.
+    /// If set, rewards are not paid while the drift lasts.
+    #[serde(default)]
+    pub skip_payout_on_group_drift: bool,
+
+    /// If set, `RegisterValidatorKey` and `RotateValidatorKey` require a `proof`: an `ed25519`
+    /// signature of `key_proof_message` made with the consensus key, so no operator can
+    /// register a key of someone else. Genesis validators and `initial_keys` are not checked.
+    #[serde(default)]
+    pub require_key_proof: bool,
 }
 
 impl InstantiateMsg {
@@ -115,17 +181,128 @@ impl InstantiateMsg {
         if self.scaling == Some(0) {
             return Err(ContractError::InvalidScaling {});
         }
+        if let Some(curve) = &self.scaling_curve {
+            if self.scaling.is_some() {
+                return Err(ContractError::ConflictingScaling {});
+            }
+            curve.validate()?;
+        }
         // Current denom regexp in the SDK is [a-zA-Z][a-zA-Z0-9/]{2,127}
         if self.epoch_reward.denom.len() < 2 || self.epoch_reward.denom.len() > 127 {
             return Err(ContractError::InvalidRewardDenom {});
         }
+        validate_extra_epoch_rewards(&self.epoch_reward, &self.extra_epoch_rewards)?;
+        if let Some(ibc_distribution) = &self.ibc_distribution {
+            ibc_distribution.validate(self.distribution_contracts.sum_ratios())?;
+        }
+        if let Some(evidence_policy) = &self.evidence_policy {
+            evidence_policy.validate()?;
+        }
+        let pubkey_types = self.pubkey_types();
+        validate_pubkey_types(&pubkey_types)?;
         for op in self.initial_keys.iter() {
-            op.validate()?
+            op.validate(&pubkey_types)?
+        }
+        self.validate_genesis_validators(&pubkey_types)
+    }
+
+    /// Scaling curve in effect, falling back to the `scaling` multiplier
+    pub fn scaling_curve(&self) -> ScalingCurve {
+        self.scaling_curve
+            .clone()
+            .unwrap_or_else(|| ScalingCurve::legacy(self.scaling))
+    }
+
+    pub fn pubkey_types(&self) -> Vec<PubkeyType> {
+        self.pubkey_types
+            .clone()
+            .unwrap_or_else(default_pubkey_types)
+    }
+
+    fn validate_genesis_validators(
+        &self,
+        pubkey_types: &[PubkeyType],
+    ) -> Result<(), ContractError> {
+        let count = self.genesis_validators.len();
+        if count > self.max_validators as usize {
+            return Err(ContractError::TooManyGenesisValidators {
+                count,
+                max: self.max_validators,
+            });
+        }
+
+        let mut operators: BTreeSet<_> = self.initial_keys.iter().map(|op| &op.operator).collect();
+        let curve = self.scaling_curve();
+        let mut total_power = 0u64;
+        for val in self.genesis_validators.iter() {
+            val.validate(pubkey_types)?;
+            if !operators.insert(&val.operator) {
+                return Err(ContractError::DuplicatedGenesisValidator(
+                    val.operator.clone(),
+                ));
+            }
+            if val.points < self.min_points {
+                return Err(ContractError::GenesisValidatorPointsTooLow {
+                    operator: val.operator.clone(),
+                    points: val.points,
+                    min: self.min_points,
+                });
+            }
+            total_power = total_power
+                .checked_add(curve.power(val.points))
+                .ok_or(ContractError::GenesisPowerTooHigh {})?;
+        }
+
+        if total_power > MAX_TOTAL_POWER {
+            return Err(ContractError::GenesisPowerTooHigh {});
         }
         Ok(())
     }
 }
 
+pub fn validate_pubkey_types(pubkey_types: &[PubkeyType]) -> Result<(), ContractError> {
+    if pubkey_types.is_empty() {
+        return Err(ContractError::NoPubkeyTypes {});
+    }
+    Ok(())
+}
+
+/// Validates the pubkey format, and that its type is one of `pubkey_types`
+pub fn validate_pubkey(
+    pubkey: &Pubkey,
+    pubkey_types: &[PubkeyType],
+) -> Result<ValidatedPubkey, ContractError> {
+    let pubkey = ValidatedPubkey::try_from(pubkey)?;
+    let key_type = pubkey.key_type();
+    if !pubkey_types.contains(&key_type) {
+        return Err(ContractError::PubkeyTypeNotAllowed(
+            key_type.as_str().to_owned(),
+        ));
+    }
+    Ok(pubkey)
+}
+
+/// Message the consensus key signs to prove its possession by `operator`. It is bound to the
+/// chain and the valset contract, so the proof can't be replayed elsewhere.
+pub fn key_proof_message(
+    chain_id: &str,
+    valset: &str,
+    operator: &str,
+    pubkey: &ValidatedPubkey,
+) -> Vec<u8> {
+    format!(
+        "tgrade-valset key proof: chain {} valset {} operator {} pubkey {}",
+        chain_id,
+        valset,
+        operator,
+        pubkey.to_base64()
+    )
+    .into_bytes()
+}
+
+/// Tendermint limit of the total voting power of the validator set
+pub const MAX_TOTAL_POWER: u64 = (i64::MAX / 8) as u64;
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -142,13 +319,21 @@ pub enum ExecuteMsg {
         /// If there are more validators than slots, we select the top N by membership points
         /// descending.
         max_validators: Option<u32>,
-        /// A scaling factor to multiply tg4-engagement points to produce the tendermint validator power
+        /// A scaling factor to multiply tg4-engagement points to produce the tendermint validator power.
+        /// Replaces `scaling_curve`.
         scaling: Option<u32>,
+        /// Function producing the tendermint validator power from tg4-engagement points.
+        /// Replaces `scaling`.
+        #[serde(default)]
+        scaling_curve: Option<ScalingCurve>,
         /// Total reward paid out each epoch. This will be split among all validators during the last
         /// epoch.
         /// (epoch_reward.amount * 86_400 * 30 / epoch_length) is reward tokens to mint each month.
         /// Ensure this is sensible in relation to the total token supply.
         epoch_reward: Option<Coin>,
+        /// Fixed budget of other tokens paid out each epoch from this contract balance, in addition
+        /// to `epoch_reward`.
+        extra_epoch_rewards: Option<Vec<Coin>>,
         /// Percentage of total accumulated fees which is subtracted from tokens minted as a rewards.
         /// 50% as default. To disable this feature just set it to 0 (which effectively means that fees
         /// doesn't affect the per epoch reward).
@@ -176,6 +361,32 @@ pub enum ExecuteMsg {
         /// if `verify_validators` is enabled.
         /// After the jailing period, they will be jailed again if not signing blocks, ad infinitum.
         offline_jail_duration: Option<Duration>,
+
+        /// Number of epochs validators spend on probation after becoming active for the first
+        /// time. Doesn't affect validators already on probation.
+        #[serde(default)]
+        probation_epochs: Option<u64>,
+
+        /// Types of Tendermint pubkeys operators can register. Doesn't affect already
+        /// registered keys.
+        #[serde(default)]
+        pubkey_types: Option<Vec<PubkeyType>>,
+
+        /// Replaces the response to misbehaviour evidence, see `InstantiateMsg::evidence_policy`
+        #[serde(default)]
+        evidence_policy: Option<EvidencePolicy>,
+
+        /// See `InstantiateMsg::keep_validators_on_membership_failure`
+        #[serde(default)]
+        keep_validators_on_membership_failure: Option<bool>,
+
+        /// See `InstantiateMsg::skip_payout_on_group_drift`
+        #[serde(default)]
+        skip_payout_on_group_drift: Option<bool>,
+
+        /// See `InstantiateMsg::require_key_proof`
+        #[serde(default)]
+        require_key_proof: Option<bool>,
     },
     /// Links info.sender (operator) to this Tendermint consensus key.
     /// The operator cannot re-register another key.
@@ -184,8 +395,60 @@ pub enum ExecuteMsg {
         pubkey: Pubkey,
         /// Additional metadata assigned to this validator
         metadata: ValidatorMetadata,
+        /// Signature of `key_proof_message` made with the consensus key, required if
+        /// `require_key_proof` is set
+        #[serde(default)]
+        proof: Option<Binary>,
+    },
+    /// Rotates info.sender (operator) to a new Tendermint consensus key. The new key is
+    /// activated on the second epoch boundary from now, so the chain has caught up with the
+    /// change before it is applied. Evidence is matched against both keys until the epoch
+    /// after activation. Only one rotation can be in progress at a time.
+    RotateValidatorKey {
+        pubkey: Pubkey,
+        /// Signature of `key_proof_message` made with the new consensus key, required if
+        /// `require_key_proof` is set
+        #[serde(default)]
+        proof: Option<Binary>,
     },
     UpdateMetadata(ValidatorMetadata),
+    /// Updates metadata of the given operator. Can be executed by the operator or one of its
+    /// delegates.
+    UpdateOperatorMetadata {
+        operator: String,
+        metadata: ValidatorMetadata,
+    },
+    /// Authorizes `delegate` to update metadata and unjail on behalf of info.sender (operator).
+    /// Delegates cannot change the validator key.
+    AddDelegate {
+        delegate: String,
+    },
+    /// Revokes authorization of `delegate` to act on behalf of info.sender (operator).
+    RemoveDelegate {
+        delegate: String,
+    },
+    /// Sets the address receiving block rewards of info.sender (operator), so the operator key
+    /// can be kept in cold storage. Unset payout means rewards go to the operator itself.
+    /// Payout address cannot be shared with other operators.
+    SetPayoutAddress {
+        payout: Option<String>,
+    },
+    /// Sets the part of the reward sent over IBC, `None` disables it (dropping transfers queued
+    /// for retry, so their funds are paid out as regular rewards). Can be executed only by the
+    /// admin.
+    SetIbcDistribution {
+        distribution: Option<IbcDistribution>,
+    },
+    /// Starts the exit of info.sender (operator). It is not selected for the validator set
+    /// anymore, and on the next epoch boundary it is removed from the active validators with its
+    /// pending rewards paid out. After `exit_cooldown` passes the record can be purged.
+    BeginExit {},
+    /// Removes the record of an operator whose exit is settled and past the cooldown, so its
+    /// address and pubkey can be registered again. Can be executed by anyone. Jailed operators
+    /// cannot be purged.
+    PurgeOperator {
+        operator: String,
+    },
     /// Jails validator. Can be executed only by the admin.
     Jail {
         /// Operator which should be jailed
@@ -193,19 +456,34 @@ pub enum ExecuteMsg {
         /// Duration for how long validator is jailed
         duration: JailingDuration,
     },
-    /// Unjails validator. Admin can unjail anyone anytime, others can unjail only themselves and
-    /// only if the jail period passed.
+    /// Unjails validator. Admin can unjail anyone anytime, others can unjail only themselves (or
+    /// operators they are delegates of) and only if the jail period passed.
     Unjail {
         /// Address to unjail. Optional, as if not provided it is assumed to be the sender of the
         /// message (for convenience when unjailing self after the jail period).
         operator: Option<String>,
     },
+    /// Lifts the forever jail (tombstone) of a validator. Can be executed only by the admin,
+    /// so governance can reverse an erroneous tombstoning. Slashing history is kept.
+    Untombstone {
+        /// Operator which should be untombstoned
+        operator: String,
+    },
     /// To be called by admin only. Slashes a given address (by forwarding slash to both rewards
     /// contract and engagement contract)
     Slash {
         addr: String,
         portion: Decimal,
     },
+    /// Adds a contract to be informed about every validator jailing and slashing with
+    /// `PenaltyHookMsg`. Must be called by the admin, or uses one of the preauths.
+    AddPenaltyHook {
+        addr: String,
+    },
+    /// Removes a penalty hook. Must be called by the admin or the hook itself.
+    RemovePenaltyHook {
+        addr: String,
+    },
 
     /// This will update the validator set with the passed list.
     /// Used for testing validators storage.
@@ -226,6 +504,11 @@ pub enum QueryMsg {
     /// Returns the validator key and associated metadata (if present) for the given operator.
     /// Returns ValidatorResponse
     Validator { operator: String },
+    /// Returns KeyRotationResponse - validator key rotation of the operator in transition
+    KeyRotation { operator: String },
+    /// Returns only the operational metadata (endpoints and contact) of the given operator.
+    /// Returns OperationalMetadataResponse
+    OperationalMetadata { operator: String },
     /// Paginate over all operators, using operator address as pagination.
     /// Returns ListValidatorsResponse
     ListValidators {
@@ -239,10 +522,29 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
 
-    /// Returns ListValidatorsResponse
+    /// Returns ListValidatorsResponse, ordered by operator address
     ListJailedValidators {
         start_after: Option<String>,
         limit: Option<u32>,
+        /// Only list validators jailed for this reason
+        #[serde(default)]
+        reason: Option<JailReason>,
+        /// Only list validators with this kind of jail end
+        #[serde(default)]
+        end: Option<JailEndType>,
+    },
+
+    /// Returns ListValidatorsResponse, ordered by the jail end, soonest first. Validators jailed
+    /// forever come last. `start_after` is the last operator of the previous page.
+    ListJailedValidatorsByEnd {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// Only list validators jailed for this reason
+        #[serde(default)]
+        reason: Option<JailReason>,
+        /// Only list validators with this kind of jail end
+        #[serde(default)]
+        end: Option<JailEndType>,
     },
 
     /// This will calculate who the new validators would be if
@@ -256,6 +558,37 @@ pub enum QueryMsg {
 
     /// Returns cw_controllers::AdminResponse
     Admin {},
+
+    /// Returns PenaltyHooksResponse
+    ListPenaltyHooks {},
+
+    /// Returns ExitStatusResponse - progress of the exit of the given operator
+    ExitStatus { operator: String },
+
+    /// Returns DelegatesResponse - addresses allowed to act on behalf of the operator
+    ListDelegates { operator: String },
+
+    /// Returns MetricsResponse - compact counters for chain telemetry
+    Metrics {},
+
+    /// Returns MigrationHistoryResponse - all migrations of this contract with the steps applied
+    MigrationHistory {},
+
+    /// Returns IbcRetryQueueResponse - funds of failed IBC transfers, sent again with the next
+    /// payout
+    IbcRetryQueue {},
+
+    /// Returns InvariantsResponse - recomputes aggregates kept in the state and reports
+    /// discrepancies. Meant for watchdogs, it is expensive as it scans all operators.
+    VerifyInvariants {},
+
+    /// Returns SimulateRewardsResponse - rewards which would be paid after `epochs` epochs if
+    /// the active validators were the given operators with the given powers. Fees and extra
+    /// rewards are taken from the current contract balance.
+    SimulateRewards {
+        validators: Vec<(String, u64)>,
+        epochs: u64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -292,7 +625,7 @@ impl UnvalidatedDistributionContracts {
         self.inner.into_iter().map(|c| c.validate(api)).collect()
     }
 
-    fn sum_ratios(&self) -> Decimal {
+    pub fn sum_ratios(&self) -> Decimal {
         self.inner
             .iter()
             .map(|c| c.ratio)
@@ -300,6 +633,26 @@ impl UnvalidatedDistributionContracts {
     }
 }
 
+/// Extra rewards have to be non-zero amounts of distinct valid denoms, other than the epoch
+/// reward denom
+pub fn validate_extra_epoch_rewards(
+    epoch_reward: &Coin,
+    extra_epoch_rewards: &[Coin],
+) -> Result<(), ContractError> {
+    let mut denoms = BTreeSet::new();
+    for reward in extra_epoch_rewards {
+        if reward.amount.is_zero()
+            || reward.denom.len() < 2
+            || reward.denom.len() > 127
+            || reward.denom == epoch_reward.denom
+            || !denoms.insert(&reward.denom)
+        {
+            return Err(ContractError::InvalidExtraReward(reward.denom.clone()));
+        }
+    }
+    Ok(())
+}
+
 pub fn default_fee_percentage() -> Decimal {
     Decimal::zero()
 }
@@ -331,11 +684,27 @@ pub struct ValidatorMetadata {
 
     /// The validator's (optional) details
     pub details: Option<String>,
+
+    /// The validator's (optional) public RPC endpoint (http://, https:// or tcp:// URL)
+    #[serde(default)]
+    pub rpc_endpoint: Option<String>,
+
+    /// The validator's (optional) p2p address, in the `<node_id>@<host>:<port>` format
+    #[serde(default)]
+    pub p2p_address: Option<String>,
+
+    /// The validator's (optional) operational contact PGP key fingerprint (40 hex characters)
+    #[serde(default)]
+    pub pgp_fingerprint: Option<String>,
 }
 
 pub const MIN_MONIKER_LENGTH: usize = 3;
 pub const MIN_METADATA_SIZE: usize = 1;
 pub const MAX_METADATA_SIZE: usize = 256;
+/// Length of the hex-encoded Tendermint node id
+pub const NODE_ID_LENGTH: usize = 40;
+/// Length of the hex-encoded PGP v4 key fingerprint
+pub const PGP_FINGERPRINT_LENGTH: usize = 40;
 
 impl ValidatorMetadata {
     pub fn validate(&self) -> Result<(), ContractError> {
@@ -384,8 +753,94 @@ impl ValidatorMetadata {
                 });
             }
         }
+        if let Some(rpc_endpoint) = &self.rpc_endpoint {
+            if rpc_endpoint.is_empty() || rpc_endpoint.len() > MAX_METADATA_SIZE {
+                return Err(ContractError::InvalidMetadata {
+                    data: "rpc_endpoint",
+                    min: MIN_METADATA_SIZE,
+                    max: MAX_METADATA_SIZE,
+                });
+            } else if !["https://", "http://", "tcp://"]
+                .iter()
+                .any(|prefix| rpc_endpoint.starts_with(prefix))
+            {
+                return Err(ContractError::InvalidMetadataRpcEndpointPrefix {});
+            }
+        }
+        if let Some(p2p_address) = &self.p2p_address {
+            if p2p_address.is_empty() || p2p_address.len() > MAX_METADATA_SIZE {
+                return Err(ContractError::InvalidMetadata {
+                    data: "p2p_address",
+                    min: MIN_METADATA_SIZE,
+                    max: MAX_METADATA_SIZE,
+                });
+            } else if !is_valid_p2p_address(p2p_address) {
+                return Err(ContractError::InvalidMetadataP2pAddress {});
+            }
+        }
+        if let Some(pgp_fingerprint) = &self.pgp_fingerprint {
+            if pgp_fingerprint.len() != PGP_FINGERPRINT_LENGTH || !is_hex(pgp_fingerprint) {
+                return Err(ContractError::InvalidMetadataPgpFingerprint {});
+            }
+        }
         Ok(())
     }
+
+    /// Operational part of the metadata, meant for node operators tooling
+    pub fn operational(&self) -> OperationalMetadataResponse {
+        OperationalMetadataResponse {
+            rpc_endpoint: self.rpc_endpoint.clone(),
+            p2p_address: self.p2p_address.clone(),
+            pgp_fingerprint: self.pgp_fingerprint.clone(),
+        }
+    }
+}
+
+fn is_hex(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Checks Tendermint `<node_id>@<host>:<port>` p2p address format
+fn is_valid_p2p_address(addr: &str) -> bool {
+    let (node_id, host_port) = match addr.split_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let (host, port) = match host_port.rsplit_once(':') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    node_id.len() == NODE_ID_LENGTH
+        && is_hex(node_id)
+        && !host.is_empty()
+        && !host.contains(char::is_whitespace)
+        && matches!(port.parse::<u16>(), Ok(port) if port != 0)
+}
+
+/// Compact specification of a validator set up on instantiation
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct GenesisValidator {
+    pub operator: String,
+    pub validator_pubkey: Pubkey,
+    /// Points assigned to the operator in `membership`
+    pub points: u64,
+    /// If not set, operator address is used as a moniker
+    #[serde(default)]
+    pub metadata: Option<ValidatorMetadata>,
+}
+
+impl GenesisValidator {
+    pub fn metadata(&self) -> ValidatorMetadata {
+        self.metadata.clone().unwrap_or_else(|| ValidatorMetadata {
+            moniker: self.operator.clone(),
+            ..ValidatorMetadata::default()
+        })
+    }
+
+    pub fn validate(&self, pubkey_types: &[PubkeyType]) -> Result<(), ContractError> {
+        validate_pubkey(&self.validator_pubkey, pubkey_types)?;
+        self.metadata().validate()
+    }
 }
 
 /// Maps an sdk address to a Tendermint pubkey.
@@ -397,8 +852,8 @@ pub struct OperatorInitInfo {
 }
 
 impl OperatorInitInfo {
-    pub fn validate(&self) -> Result<(), ContractError> {
-        Ed25519Pubkey::try_from(&self.validator_pubkey)?;
+    pub fn validate(&self, pubkey_types: &[PubkeyType]) -> Result<(), ContractError> {
+        validate_pubkey(&self.validator_pubkey, pubkey_types)?;
         self.metadata.validate()
     }
 }
@@ -416,6 +871,76 @@ pub struct EpochResponse {
     pub next_update_time: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct InvariantsResponse {
+    /// Descriptions of all broken invariants, empty if the state is consistent
+    pub violations: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct KeyRotationResponse {
+    pub rotation: Option<KeyRotationInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct KeyRotationInfo {
+    pub old_pubkey: Pubkey,
+    pub new_pubkey: Pubkey,
+    /// Epoch the new key is used from
+    pub activation_epoch: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct SimulateRewardsResponse {
+    /// Reward tokens which would be minted
+    pub minted: Uint128,
+    /// Collected fees added to the reward
+    pub fees: Uint128,
+    /// Extra rewards paid from the contract balance
+    pub extra: Vec<Coin>,
+    /// Rewards sent over IBC, not including transfers queued for retry
+    pub ibc: Vec<Coin>,
+    /// Rewards of every distribution contract, in the config order
+    pub distribution_contracts: Vec<DistributionContractPayout>,
+    /// Rewards of every simulated validator, in the query order
+    pub validators: Vec<ValidatorPayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DistributionContractPayout {
+    pub contract: Addr,
+    pub rewards: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ValidatorPayout {
+    pub operator: Addr,
+    pub rewards: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct IbcRetryQueueResponse {
+    pub transfers: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MetricsResponse {
+    /// Number of registered operators
+    pub operators: u64,
+    /// Number of validators in the current active set
+    pub active_validators: u64,
+    /// Number of operators jailed for a limited time
+    pub jailed: u64,
+    /// Number of operators jailed forever
+    pub tombstoned: u64,
+    pub current_epoch: u64,
+    /// Totals of the last rewards payout, unset if there was none yet
+    pub last_payout: Option<PayoutTotals>,
+    /// Number of epochs the validator set was kept because querying membership failed
+    #[serde(default)]
+    pub membership_query_failures: u64,
+}
+
 // data behind one operator
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct OperatorResponse {
@@ -424,6 +949,12 @@ pub struct OperatorResponse {
     pub metadata: ValidatorMetadata,
     pub jailed_until: Option<JailingPeriod>,
     pub active_validator: bool,
+    /// Address receiving the operator share of block rewards, if different from the operator
+    #[serde(default)]
+    pub payout: Option<Addr>,
+    /// If set, the validator is on probation (receiving no rewards) until this epoch starts
+    #[serde(default)]
+    pub probation_until: Option<u64>,
 }
 
 impl OperatorResponse {
@@ -431,6 +962,7 @@ impl OperatorResponse {
         info: OperatorInfo,
         operator: String,
         jailed_until: impl Into<Option<JailingPeriod>>,
+        probation_until: Option<u64>,
     ) -> Self {
         OperatorResponse {
             operator,
@@ -438,6 +970,8 @@ impl OperatorResponse {
             metadata: info.metadata,
             jailed_until: jailed_until.into(),
             active_validator: info.active_validator,
+            payout: info.payout,
+            probation_until,
         }
     }
 }
@@ -446,6 +980,28 @@ impl OperatorResponse {
 pub struct JailingPeriod {
     pub start: Timestamp,
     pub end: JailingEnd,
+    /// Why the operator was jailed. Unset for jails recorded before reasons were stored.
+    #[serde(default)]
+    pub reason: Option<JailReason>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum JailReason {
+    /// Validator was not signing blocks
+    Offline,
+    /// Double sign evidence was reported for the validator
+    Evidence,
+    /// Jailed by the admin
+    Admin,
+}
+
+/// Kind of the jail end, used for filtering jailed validators
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum JailEndType {
+    Until,
+    Forever,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -456,16 +1012,36 @@ pub enum JailingEnd {
 }
 
 impl JailingPeriod {
-    pub fn from_duration(duration: JailingDuration, block: &BlockInfo) -> Self {
+    pub fn from_duration(duration: JailingDuration, reason: JailReason, block: &BlockInfo) -> Self {
         Self {
             start: block.time,
             end: match duration {
                 JailingDuration::Duration(duration) => JailingEnd::Until(duration.after(block)),
                 JailingDuration::Forever {} => JailingEnd::Forever {},
             },
+            reason: Some(reason),
+        }
+    }
+
+    /// Nanoseconds of the jail end, `u64::MAX` for jails forever, so they sort last
+    pub fn end_key(&self) -> u64 {
+        match self.end {
+            JailingEnd::Until(expires) => Timestamp::from(expires).nanos(),
+            JailingEnd::Forever {} => u64::MAX,
         }
     }
 
+    /// Checks the jail against the optional reason and end type filters
+    pub fn matches(&self, reason: Option<JailReason>, end: Option<JailEndType>) -> bool {
+        let reason_matches = reason.is_none() || reason == self.reason;
+        let end_matches = match end {
+            None => true,
+            Some(JailEndType::Forever) => self.is_forever(),
+            Some(JailEndType::Until) => !self.is_forever(),
+        };
+        reason_matches && end_matches
+    }
+
     pub fn is_forever(&self) -> bool {
         matches!(self.end, JailingEnd::Forever {})
     }
@@ -484,6 +1060,14 @@ pub struct ValidatorResponse {
     pub validator: Option<OperatorResponse>,
 }
 
+/// All fields are unset if no validator registered
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct OperationalMetadataResponse {
+    pub rpc_endpoint: Option<String>,
+    pub p2p_address: Option<String>,
+    pub pgp_fingerprint: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct ListValidatorResponse {
     pub validators: Vec<OperatorResponse>,
@@ -518,6 +1102,55 @@ pub enum DistributionMsg {
     DistributeRewards {},
 }
 
+/// Message sent to every registered penalty hook when a validator is jailed or slashed, so
+/// external contracts (e.g. delegator insurance) can react on it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyHookMsg {
+    ValidatorPenalty {
+        operator: String,
+        reason: PenaltyReason,
+        /// Set if the validator was jailed
+        jailed_until: Option<JailingEnd>,
+        /// Portion of the validator's stake slashed, set if the validator was slashed
+        slash_portion: Option<Decimal>,
+        height: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyReason {
+    /// Jailed by the admin
+    Jailed,
+    /// Slashed by the admin
+    Slashed,
+    /// Jailed for not signing blocks
+    Offline,
+    /// Jailed forever and slashed for double signing
+    DoubleSign,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegatesResponse {
+    pub delegates: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ExitStatusResponse {
+    /// Exit of the operator, `None` if it is not exiting
+    pub exit: Option<OperatorExit>,
+    /// Whether the operator record can be purged now
+    pub purgeable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PenaltyHooksResponse {
+    pub hooks: Vec<String>,
+    /// Number of hooks which can still be added by anyone
+    pub preauths: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct RewardsInstantiateMsg {
@@ -549,6 +1182,7 @@ pub struct MigrateMsg {
     pub max_validators: Option<u32>,
     pub distribution_contracts: Option<Vec<DistributionContract>>,
     pub verify_validators: Option<bool>,
+    pub exit_cooldown: Option<Duration>,
 }
 
 #[cfg(test)]
@@ -560,8 +1194,9 @@ mod test {
 
     #[test]
     fn validate_operator_key() {
-        valid_operator("foo").validate().unwrap();
-        let err = invalid_operator().validate().unwrap_err();
+        let pubkey_types = default_pubkey_types();
+        valid_operator("foo").validate(&pubkey_types).unwrap();
+        let err = invalid_operator().validate(&pubkey_types).unwrap_err();
         assert_eq!(err, ContractError::InvalidPubkey {});
     }
 
@@ -574,8 +1209,11 @@ mod test {
             max_validators: 20,
             epoch_length: 5000,
             epoch_reward: coin(7777, "foobar"),
+            extra_epoch_rewards: vec![],
             initial_keys: vec![valid_operator("foo"), valid_operator("bar")],
+            genesis_validators: vec![],
             scaling: None,
+            scaling_curve: None,
             fee_percentage: Decimal::zero(),
             auto_unjail: false,
             double_sign_slash_ratio: Decimal::percent(50),
@@ -583,6 +1221,15 @@ mod test {
             validator_group_code_id: 0,
             verify_validators: false,
             offline_jail_duration: Duration::new(0),
+            preauths_penalty_hooks: 0,
+            probation_epochs: 0,
+            pubkey_types: None,
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         };
         proper.validate().unwrap();
 
@@ -597,6 +1244,26 @@ mod test {
         let err = invalid.validate().unwrap_err();
         assert_eq!(err, ContractError::InvalidScaling {});
 
+        // with scaling curve also works
+        let mut with_curve = proper.clone();
+        with_curve.scaling_curve = Some(ScalingCurve::Sqrt { multiplier: 10 });
+        with_curve.validate().unwrap();
+
+        // fails on both scaling and scaling curve
+        let mut invalid = with_curve.clone();
+        invalid.scaling = Some(10);
+        let err = invalid.validate().unwrap_err();
+        assert_eq!(err, ContractError::ConflictingScaling {});
+
+        // fails on curve capped at 0
+        let mut invalid = proper.clone();
+        invalid.scaling_curve = Some(ScalingCurve::CappedLinear {
+            multiplier: 1,
+            cap: 0,
+        });
+        let err = invalid.validate().unwrap_err();
+        assert_eq!(err, ContractError::InvalidScaling {});
+
         // fails on 0 min points
         let mut invalid = proper.clone();
         invalid.min_points = 0;
@@ -627,10 +1294,79 @@ mod test {
         assert_eq!(err, ContractError::InvalidPubkey {});
 
         // fails if no denom set for reward
-        let mut invalid = proper;
+        let mut invalid = proper.clone();
         invalid.epoch_reward.denom = "".into();
         let err = invalid.validate().unwrap_err();
         assert_eq!(err, ContractError::InvalidRewardDenom {});
+
+        // extra rewards in distinct denoms work
+        let mut with_extra = proper.clone();
+        with_extra.extra_epoch_rewards = vec![coin(10, "ibc/usdc"), coin(5, "ibc/atom")];
+        with_extra.validate().unwrap();
+
+        // fails on extra reward in the epoch reward denom, repeated, or zero
+        for extra in [
+            vec![coin(10, "foobar")],
+            vec![coin(10, "ibc/usdc"), coin(5, "ibc/usdc")],
+            vec![coin(0, "ibc/usdc")],
+        ] {
+            let mut invalid = proper.clone();
+            invalid.extra_epoch_rewards = extra.clone();
+            let err = invalid.validate().unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidExtraReward(extra.last().unwrap().denom.clone())
+            );
+        }
+
+        let genesis = |operator: &str, points| GenesisValidator {
+            operator: operator.to_owned(),
+            validator_pubkey: valid_operator(operator).validator_pubkey,
+            points,
+            metadata: None,
+        };
+
+        let mut with_genesis = proper.clone();
+        with_genesis.genesis_validators = vec![genesis("genesis1", 5), genesis("genesis2", 10)];
+        with_genesis.validate().unwrap();
+
+        // fails if validator wouldn't make it to the validator set
+        let mut invalid = with_genesis.clone();
+        invalid.genesis_validators[1].points = 4;
+        let err = invalid.validate().unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::GenesisValidatorPointsTooLow {
+                operator: "genesis2".to_owned(),
+                points: 4,
+                min: 5
+            }
+        );
+
+        // fails on more validators than allowed
+        let mut invalid = with_genesis.clone();
+        invalid.max_validators = 1;
+        let err = invalid.validate().unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TooManyGenesisValidators { count: 2, max: 1 }
+        );
+
+        // fails if operator is already in initial keys
+        let mut invalid = with_genesis.clone();
+        invalid.genesis_validators.push(genesis("foo", 5));
+        let err = invalid.validate().unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DuplicatedGenesisValidator("foo".to_owned())
+        );
+
+        // fails if total power exceeds Tendermint limit
+        let mut invalid = with_genesis;
+        invalid.genesis_validators[1].points = MAX_TOTAL_POWER / 10;
+        invalid.scaling = Some(10);
+        let err = invalid.validate().unwrap_err();
+        assert_eq!(err, ContractError::GenesisPowerTooHigh {});
     }
 
     #[test]
@@ -641,6 +1377,7 @@ mod test {
             website: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
             security_contact: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
             details: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
+            ..ValidatorMetadata::default()
         };
         let resp = meta.validate().unwrap_err();
         assert_eq!(
@@ -760,4 +1497,75 @@ mod test {
         let resp = meta.validate().unwrap_err();
         assert_eq!(ContractError::InvalidMetadataWebsitePrefix {}, resp);
     }
+
+    #[test]
+    fn validate_operational_metadata() {
+        let node_id = "0123456789abcdef0123456789ABCDEF01234567";
+        let meta = ValidatorMetadata {
+            moniker: "example".to_owned(),
+            rpc_endpoint: Some("tcp://127.0.0.1:26657".to_owned()),
+            p2p_address: Some(format!("{}@seed.example.com:26656", node_id)),
+            pgp_fingerprint: Some(node_id.to_owned()),
+            ..ValidatorMetadata::default()
+        };
+        meta.validate().unwrap();
+
+        let invalid = ValidatorMetadata {
+            rpc_endpoint: Some(String::new()),
+            ..meta.clone()
+        };
+        assert_eq!(
+            ContractError::InvalidMetadata {
+                data: "rpc_endpoint",
+                min: MIN_METADATA_SIZE,
+                max: MAX_METADATA_SIZE,
+            },
+            invalid.validate().unwrap_err()
+        );
+
+        let invalid = ValidatorMetadata {
+            rpc_endpoint: Some("ws://127.0.0.1:26657".to_owned()),
+            ..meta.clone()
+        };
+        assert_eq!(
+            ContractError::InvalidMetadataRpcEndpointPrefix {},
+            invalid.validate().unwrap_err()
+        );
+
+        let invalid_p2p = [
+            "seed.example.com:26656".to_owned(),
+            format!("{}@seed.example.com", node_id),
+            format!("{}@:26656", node_id),
+            format!("{}@seed.example.com:0", node_id),
+            format!("{}@seed.example.com:65536", node_id),
+            format!("{}@seed example.com:26656", node_id),
+            format!("{}@seed.example.com:26656", &node_id[1..]),
+            format!("{}x@seed.example.com:26656", &node_id[1..]),
+        ];
+        for p2p_address in invalid_p2p {
+            let invalid = ValidatorMetadata {
+                p2p_address: Some(p2p_address),
+                ..meta.clone()
+            };
+            assert_eq!(
+                ContractError::InvalidMetadataP2pAddress {},
+                invalid.validate().unwrap_err()
+            );
+        }
+
+        for pgp_fingerprint in [
+            "",
+            &node_id[1..],
+            "g123456789abcdef0123456789abcdef01234567",
+        ] {
+            let invalid = ValidatorMetadata {
+                pgp_fingerprint: Some(pgp_fingerprint.to_owned()),
+                ..meta.clone()
+            };
+            assert_eq!(
+                ContractError::InvalidMetadataPgpFingerprint {},
+                invalid.validate().unwrap_err()
+            );
+        }
+    }
 }
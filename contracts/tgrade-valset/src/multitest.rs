@@ -1,11 +1,24 @@
 mod admin;
 mod contract;
+mod delegates;
 mod double_sign;
+mod exit;
 mod export_import;
+mod group_drift;
 mod helpers;
+mod ibc_distribution;
+mod invariants;
 mod jailing;
+mod key_proof;
+mod key_rotation;
+mod membership_failure;
+mod metrics;
 mod migration;
+mod payout;
+mod penalty_hooks;
+mod probation;
 mod rewards_split;
+mod scaling;
 mod slashing;
 mod stake;
 mod suite;
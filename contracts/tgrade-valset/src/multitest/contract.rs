@@ -1,6 +1,7 @@
 use crate::error::ContractError;
 use crate::msg::{
-    EpochResponse, ValidatorMetadata, MAX_METADATA_SIZE, MIN_METADATA_SIZE, MIN_MONIKER_LENGTH,
+    EpochResponse, OperationalMetadataResponse, ValidatorMetadata, MAX_METADATA_SIZE,
+    MIN_METADATA_SIZE, MIN_MONIKER_LENGTH,
 };
 use crate::state::Config;
 
@@ -8,6 +9,7 @@ use super::helpers::{addr_to_pubkey, assert_active_validators, assert_operators,
 use super::suite::SuiteBuilder;
 use assert_matches::assert_matches;
 use cosmwasm_std::{coin, Decimal};
+use tg_bindings::{Pubkey, PubkeyType};
 use tg_utils::Duration;
 
 #[test]
@@ -33,7 +35,9 @@ fn initialization() {
             min_points: 5,
             max_validators: 10,
             epoch_reward: coin(100, "eth"),
+            extra_epoch_rewards: vec![],
             scaling: None,
+            scaling_curve: None,
             fee_percentage: Decimal::zero(),
             auto_unjail: false,
             double_sign_slash_ratio: Decimal::percent(50),
@@ -43,6 +47,14 @@ fn initialization() {
             validator_group: config.validator_group.clone(),
             verify_validators: false,
             offline_jail_duration: Duration::new(0),
+            probation_epochs: 0,
+            pubkey_types: vec![PubkeyType::Ed25519],
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         }
     );
 
@@ -76,6 +88,27 @@ fn initialization() {
     }
 }
 
+#[test]
+fn genesis_validators_seed_membership() {
+    let members = vec!["member1", "member2"];
+
+    let suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 8]))
+        .with_operators(&members)
+        .with_genesis_validators(&[("genesis1", 5), ("genesis2", 3)])
+        .with_min_points(3)
+        .build();
+
+    // Genesis validators are members right away, so they are in the very first validator set
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[("genesis1", 5), ("genesis2", 3), (members[1], 8)],
+    );
+
+    let genesis = suite.validator("genesis1").unwrap().validator.unwrap();
+    assert_eq!(genesis.metadata.moniker, "genesis1");
+}
+
 #[test]
 fn validators_query_pagination() {
     let members = vec!["member1", "member2", "member3", "member4", "member5"];
@@ -160,6 +193,7 @@ fn update_metadata() {
         website: Some("https://www.funny.boy.rs".to_owned()),
         security_contact: Some("funny@boy.rs".to_owned()),
         details: Some("Comedian".to_owned()),
+        ..ValidatorMetadata::default()
     };
 
     suite.update_metadata(members[0], &meta).unwrap();
@@ -173,6 +207,7 @@ fn update_metadata() {
         website: Some("https://www.empty.one.rs".to_owned()),
         security_contact: Some("empty@one.rs".to_owned()),
         details: Some("Ghost".to_owned()),
+        ..ValidatorMetadata::default()
     };
 
     // Update with invalid meta (empty moniker) fails
@@ -200,6 +235,58 @@ fn update_metadata() {
     );
 }
 
+#[test]
+fn update_operational_metadata() {
+    let members = vec!["member1"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2]))
+        .with_operators(&members)
+        .build();
+
+    // Nothing set by default, also for unknown operators
+    assert_eq!(
+        suite.operational_metadata(members[0]).unwrap(),
+        OperationalMetadataResponse::default()
+    );
+    assert_eq!(
+        suite.operational_metadata("invalid").unwrap(),
+        OperationalMetadataResponse::default()
+    );
+
+    let meta = ValidatorMetadata {
+        moniker: "funny boy".to_owned(),
+        rpc_endpoint: Some("https://rpc.funny.boy.rs:26657".to_owned()),
+        p2p_address: Some(format!("{}@p2p.funny.boy.rs:26656", "ab".repeat(20))),
+        pgp_fingerprint: Some("0123456789ABCDEF0123456789abcdef01234567".to_owned()),
+        ..ValidatorMetadata::default()
+    };
+    suite.update_metadata(members[0], &meta).unwrap();
+
+    assert_eq!(
+        suite.operational_metadata(members[0]).unwrap(),
+        OperationalMetadataResponse {
+            rpc_endpoint: meta.rpc_endpoint.clone(),
+            p2p_address: meta.p2p_address.clone(),
+            pgp_fingerprint: meta.pgp_fingerprint.clone(),
+        }
+    );
+
+    // Invalid p2p address is rejected, and nothing changes
+    let invalid_meta = ValidatorMetadata {
+        p2p_address: Some("p2p.funny.boy.rs:26656".to_owned()),
+        ..meta.clone()
+    };
+    let resp = suite
+        .update_metadata(members[0], &invalid_meta)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMetadataP2pAddress {},
+        resp.downcast().unwrap()
+    );
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(resp.validator.unwrap().metadata, meta);
+}
+
 #[test]
 fn list_validators() {
     let members = vec!["member1", "member2", "member3", "member4"];
@@ -289,6 +376,7 @@ fn register_key_invalid_metadata() {
         website: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
         security_contact: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
         details: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
+        ..ValidatorMetadata::default()
     };
     let pubkey = addr_to_pubkey(members[0]);
     let resp = suite
@@ -323,6 +411,60 @@ fn register_key_invalid_metadata() {
     );
 }
 
+#[test]
+fn register_secp256k1_key() {
+    let members = ["member1", "member2"];
+
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[5, 8]))
+        .with_operators(&members[..1])
+        .with_min_points(5)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    let mut key = vec![2u8];
+    key.extend([7u8; 32]);
+    let pubkey = Pubkey::Secp256k1(key.into());
+    let meta = ValidatorMetadata {
+        moniker: "secp".to_owned(),
+        ..ValidatorMetadata::default()
+    };
+
+    // only ed25519 keys are accepted by default
+    let err = suite
+        .register_validator_key(members[1], pubkey.clone(), meta.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PubkeyTypeNotAllowed("secp256k1".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // uncompressed keys are rejected
+    suite
+        .update_pubkey_types(&admin, vec![PubkeyType::Ed25519, PubkeyType::Secp256k1])
+        .unwrap();
+    let err = suite
+        .register_validator_key(
+            members[1],
+            Pubkey::Secp256k1(vec![4u8; 65].into()),
+            meta.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidPubkey {}, err.downcast().unwrap());
+
+    suite
+        .register_validator_key(members[1], pubkey.clone(), meta.clone())
+        .unwrap();
+    let operator = suite.validator(members[1]).unwrap().validator.unwrap();
+    assert_eq!(operator.pubkey, pubkey);
+
+    suite.advance_epoch().unwrap();
+    let active = suite.list_active_validators(None, None).unwrap();
+    assert_active_validators(&active, &[(members[0], 5), (members[1], 8)]);
+    let validator = active.iter().find(|v| v.operator == members[1]).unwrap();
+    assert_eq!(validator.validator_pubkey, pubkey);
+}
+
 #[test]
 fn update_metadata_invalid_metadata() {
     let members = vec!["member1"];
@@ -339,6 +481,7 @@ fn update_metadata_invalid_metadata() {
         website: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
         security_contact: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
         details: Some((0..MAX_METADATA_SIZE + 1).map(|_| "X").collect::<String>()),
+        ..ValidatorMetadata::default()
     };
     let resp = suite.update_metadata(members[0], &meta).unwrap_err();
     assert_eq!(
@@ -390,7 +533,7 @@ mod instantiate {
         let stake_id = app.store_code(contract_stake());
         let admin = "steakhouse owner".to_owned();
         let msg = tg4_stake::msg::InstantiateMsg {
-            denom: "james bond denom".to_owned(),
+            denom: "james-bond-denom".to_owned(),
             tokens_per_point: Uint128::new(10),
             min_bond: Uint128::new(1),
             unbonding_period: 1234,
@@ -398,6 +541,15 @@ mod instantiate {
             preauths_hooks: 0,
             preauths_slashing: 1,
             auto_return_limit: 0,
+            unbond_veto_period: None,
+            max_bond_per_address: None,
+            max_total_bond: None,
+            exit_fee: None,
+            lockup_tiers: vec![],
+            average_stake_period: None,
+            unbonding_interest: None,
+            denom_metadata: None,
+            max_pause_duration: None,
         };
         let stake_addr = app
             .instantiate_contract(
@@ -428,8 +580,11 @@ mod instantiate {
             max_validators: 120,
             epoch_length: 10,
             epoch_reward: coin(1, "denom"),
+            extra_epoch_rewards: vec![],
             initial_keys: [member].to_vec(),
+            genesis_validators: vec![],
             scaling: None,
+            scaling_curve: None,
             fee_percentage: Decimal::zero(),
             auto_unjail: false,
             double_sign_slash_ratio: Decimal::percent(50),
@@ -437,6 +592,15 @@ mod instantiate {
             validator_group_code_id: 1,
             verify_validators: false,
             offline_jail_duration: Duration::new(0),
+            preauths_penalty_hooks: 0,
+            probation_epochs: 0,
+            pubkey_types: None,
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         };
 
         let err = app
@@ -0,0 +1,130 @@
+use crate::error::ContractError;
+use crate::msg::{JailingEnd, ValidatorMetadata};
+
+use super::helpers::{assert_operators, members_init};
+use super::suite::SuiteBuilder;
+use cw_controllers::AdminError;
+use tg_utils::Duration;
+
+#[test]
+fn operator_manages_delegates() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    suite.add_delegate(members[0], "ops2").unwrap();
+    suite.add_delegate(members[0], "ops1").unwrap();
+    suite.add_delegate(members[1], "ops3").unwrap();
+    assert_eq!(suite.list_delegates(members[0]).unwrap(), ["ops1", "ops2"]);
+    assert_eq!(suite.list_delegates(members[1]).unwrap(), ["ops3"]);
+
+    // Only registered operators can have delegates
+    let err = suite.add_delegate("someone", "ops1").unwrap_err();
+    assert_eq!(
+        ContractError::Unauthorized("No operator info found".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Operator is not its own delegate
+    let err = suite.add_delegate(members[0], members[0]).unwrap_err();
+    assert_eq!(ContractError::SelfDelegate {}, err.downcast().unwrap());
+
+    suite.remove_delegate(members[0], "ops2").unwrap();
+    assert_eq!(suite.list_delegates(members[0]).unwrap(), ["ops1"]);
+
+    // Delegates of other operators cannot be removed
+    let err = suite.remove_delegate(members[0], "ops3").unwrap_err();
+    assert_eq!(
+        ContractError::NotDelegate("ops3".to_owned()),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.list_delegates(members[1]).unwrap(), ["ops3"]);
+}
+
+#[test]
+fn delegate_can_update_metadata() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    suite.add_delegate(members[0], "ops").unwrap();
+
+    let meta = ValidatorMetadata {
+        moniker: "operated".to_owned(),
+        ..ValidatorMetadata::default()
+    };
+
+    // Operator itself can use the message too
+    suite
+        .update_operator_metadata(members[0], members[0], &meta)
+        .unwrap();
+
+    let meta = ValidatorMetadata {
+        moniker: "delegated".to_owned(),
+        ..ValidatorMetadata::default()
+    };
+    suite
+        .update_operator_metadata("ops", members[0], &meta)
+        .unwrap();
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(resp.validator.unwrap().metadata, meta);
+
+    // Delegate cannot act on behalf of other operators
+    let err = suite
+        .update_operator_metadata("ops", members[1], &meta)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Unauthorized("Sender is neither the operator nor its delegate".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Removed delegate is not authorized anymore
+    suite.remove_delegate(members[0], "ops").unwrap();
+    let err = suite
+        .update_operator_metadata("ops", members[0], &meta)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Unauthorized("Sender is neither the operator nor its delegate".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn delegate_can_unjail() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    suite.add_delegate(members[0], "ops").unwrap();
+    suite.jail(&admin, members[0], Duration::new(3600)).unwrap();
+    suite.jail(&admin, members[1], Duration::new(3600)).unwrap();
+
+    let jailed_until = JailingEnd::Until(Duration::new(3600).after(&suite.app().block_info()));
+
+    // Delegate cannot unjail before the jail period passed
+    let err = suite.unjail("ops", members[0]).unwrap_err();
+    assert_eq!(ContractError::JailDidNotExpire {}, err.downcast().unwrap());
+
+    suite.advance_seconds(3800).unwrap();
+
+    // Delegate cannot unjail other operators
+    let err = suite.unjail("ops", members[1]).unwrap_err();
+    assert_eq!(
+        ContractError::AdminError(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+
+    suite.unjail("ops", members[0]).unwrap();
+
+    assert_operators(
+        &suite.list_validators(None, None).unwrap(),
+        &[(members[0], None), (members[1], Some(jailed_until))],
+    )
+}
@@ -1,11 +1,15 @@
 use cosmwasm_std::coin;
 use cosmwasm_std::{Binary, Decimal};
+use cw_controllers::AdminError;
 use tg_bindings::{Ed25519Pubkey, Evidence, EvidenceType, ToAddress, Validator};
+use tg_utils::{Duration, Expiration, JailingDuration};
 
 use super::helpers::{addr_to_pubkey, assert_operators};
 use super::suite::SuiteBuilder;
-use crate::msg::{JailingEnd, ValidatorMetadata};
+use crate::error::ContractError;
+use crate::msg::{JailReason, JailingEnd, ValidatorMetadata};
 use crate::multitest::helpers::members_init;
+use crate::state::EvidencePolicy;
 use crate::test_helpers::mock_pubkey;
 
 use std::convert::TryFrom;
@@ -60,6 +64,11 @@ fn evidence_slash_and_jail() {
             (members[1].0, None),
         ],
     );
+    let jailed = suite.validator(members[0].0).unwrap().validator.unwrap();
+    assert_eq!(
+        jailed.jailed_until.unwrap().reason,
+        Some(JailReason::Evidence)
+    );
 
     suite.advance_epoch().unwrap();
 
@@ -206,6 +215,7 @@ fn evidence_with_not_matching_date() {
         website: Some("https://www.funny.boy.rs".to_owned()),
         security_contact: Some("funny@boy.rs".to_owned()),
         details: Some("Comedian".to_owned()),
+        ..ValidatorMetadata::default()
     };
     let pubkey = addr_to_pubkey(members[2].0);
     suite
@@ -233,3 +243,116 @@ fn evidence_with_not_matching_date() {
         ],
     );
 }
+
+#[test]
+fn evidence_policy_jails_first_offense_only() {
+    let member_addrs = vec![
+        "reallylongaddresstofit32charact1",
+        "reallylongaddresstofit32charact2",
+    ];
+    let members = members_init(&member_addrs, &[10, 10]);
+
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members)
+        .with_operators_pubkeys(&member_addrs)
+        .with_evidence_policy(EvidencePolicy {
+            duplicate_vote_slash_ratio: Decimal::percent(20),
+            light_client_attack_slash_ratio: None,
+            jail_duration: JailingDuration::Duration(Duration::new(3600)),
+            jail_only_first_offense: true,
+        })
+        .build();
+
+    let evidence = create_evidence_for_member(members[0], suite.height() + 1);
+    suite.next_block_with_evidence(vec![evidence]).unwrap();
+
+    // First offense is jailed for the configured time, without slashing
+    let jailed_until = Expiration::at_timestamp(suite.timestamp().plus_seconds(3600));
+    assert_operators(
+        &suite.list_validators(None, None).unwrap(),
+        &[
+            (members[0].0, Some(JailingEnd::Until(jailed_until))),
+            (members[1].0, None),
+        ],
+    );
+    let slashing = suite.list_validator_slashing(members[0].0).unwrap();
+    assert_eq!(slashing.slashing, []);
+    assert!(!slashing.tombstoned);
+
+    suite.advance_seconds(3600).unwrap();
+    suite.unjail(members[0].0, None).unwrap();
+
+    // Second offense is slashed as well
+    let evidence = create_evidence_for_member(members[0], suite.height());
+    suite.next_block_with_evidence(vec![evidence]).unwrap();
+    let slashing = suite.list_validator_slashing(members[0].0).unwrap();
+    assert_eq!(slashing.slashing.len(), 1);
+    assert_eq!(slashing.slashing[0].portion, Decimal::percent(20));
+}
+
+#[test]
+fn light_client_attack_is_handled_if_configured() {
+    let member_addrs = vec![
+        "reallylongaddresstofit32charact1",
+        "reallylongaddresstofit32charact2",
+    ];
+    let members = members_init(&member_addrs, &[10, 10]);
+
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members)
+        .with_operators_pubkeys(&member_addrs)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    // Ignored by the legacy policy
+    let mut evidence = create_evidence_for_member(members[0], suite.height() + 1);
+    evidence.evidence_type = EvidenceType::LightClientAttack;
+    suite
+        .next_block_with_evidence(vec![evidence.clone()])
+        .unwrap();
+    assert_operators(
+        &suite.list_validators(None, None).unwrap(),
+        &[(members[0].0, None), (members[1].0, None)],
+    );
+
+    let policy = EvidencePolicy {
+        duplicate_vote_slash_ratio: Decimal::percent(50),
+        light_client_attack_slash_ratio: Some(Decimal::percent(150)),
+        jail_duration: JailingDuration::Forever {},
+        jail_only_first_offense: false,
+    };
+    let err = suite
+        .update_evidence_policy(members[0].0, policy.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AdminError(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .update_evidence_policy(&admin, policy.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidEvidencePolicy {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_evidence_policy(
+            &admin,
+            EvidencePolicy {
+                light_client_attack_slash_ratio: Some(Decimal::percent(30)),
+                ..policy
+            },
+        )
+        .unwrap();
+    suite.next_block_with_evidence(vec![evidence]).unwrap();
+    assert_operators(
+        &suite.list_validators(None, None).unwrap(),
+        &[
+            (members[0].0, Some(JailingEnd::Forever {})),
+            (members[1].0, None),
+        ],
+    );
+    let slashing = suite.list_validator_slashing(members[0].0).unwrap();
+    assert_eq!(slashing.slashing[0].portion, Decimal::percent(30));
+}
@@ -0,0 +1,122 @@
+use cosmwasm_std::coin;
+use tg_utils::{Duration, JailingDuration};
+
+use super::helpers::{assert_active_validators, members_init};
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+
+#[test]
+fn exiting_operator_is_settled_and_purged() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_exit_cooldown(1000)
+        .build();
+
+    suite.begin_exit(members[0]).unwrap();
+    let status = suite.exit_status(members[0]).unwrap();
+    let exit = status.exit.unwrap();
+    assert_eq!(exit.begun_at, suite.timestamp());
+    assert_eq!(exit.settled_epoch, None);
+    assert!(!status.purgeable);
+
+    // Still validating until the epoch boundary
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 2), (members[1], 3)],
+    );
+
+    // Rewards of the last epoch are paid out before leaving the validator set
+    suite.advance_epoch().unwrap();
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[1], 3)],
+    );
+    let resp = suite.validator(members[0]).unwrap();
+    assert!(!resp.validator.unwrap().active_validator);
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 400);
+
+    let epoch = suite.epoch().unwrap().current_epoch;
+    let status = suite.exit_status(members[0]).unwrap();
+    let exit = status.exit.unwrap();
+    assert_eq!(exit.settled_epoch, Some(epoch));
+    assert_eq!(
+        exit.purgeable_at,
+        Some(suite.timestamp().plus_seconds(1000))
+    );
+    assert!(!status.purgeable);
+
+    let err = suite.purge_operator("anyone", members[0]).unwrap_err();
+    assert_eq!(
+        ContractError::ExitCooldownNotOver(members[0].to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Nothing more is earned after the exit
+    suite.advance_epoch().unwrap();
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 400);
+    // 600 + 1000, less a token lost to rounding of the points based distribution
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 1599);
+
+    suite.advance_seconds(1000).unwrap();
+    assert!(suite.exit_status(members[0]).unwrap().purgeable);
+    suite.purge_operator("anyone", members[0]).unwrap();
+
+    assert_eq!(suite.validator(members[0]).unwrap().validator, None);
+    assert_eq!(suite.exit_status(members[0]).unwrap().exit, None);
+    assert_eq!(suite.metrics().unwrap().operators, 1);
+    assert_eq!(suite.verify_invariants().unwrap(), [] as [String; 0]);
+}
+
+#[test]
+fn exit_errors() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    let err = suite.begin_exit("someone").unwrap_err();
+    assert_eq!(
+        ContractError::OperatorNotRegistered("someone".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite.purge_operator("anyone", members[0]).unwrap_err();
+    assert_eq!(
+        ContractError::NotExiting(members[0].to_owned()),
+        err.downcast().unwrap()
+    );
+
+    suite.begin_exit(members[0]).unwrap();
+    let err = suite.begin_exit(members[0]).unwrap_err();
+    assert_eq!(
+        ContractError::AlreadyExiting(members[0].to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // No cooldown configured, purgeable right after the settlement, but not when jailed
+    let admin = suite.admin().to_owned();
+    suite
+        .jail(
+            &admin,
+            members[0],
+            JailingDuration::Duration(Duration::new(3600)),
+        )
+        .unwrap();
+    suite.advance_epoch().unwrap();
+    assert!(suite.exit_status(members[0]).unwrap().purgeable);
+    let err = suite.purge_operator("anyone", members[0]).unwrap_err();
+    assert_eq!(
+        ContractError::PurgeJailedForbidden(members[0].to_owned()),
+        err.downcast().unwrap()
+    );
+
+    suite.unjail(&admin, members[0]).unwrap();
+    suite.purge_operator("anyone", members[0]).unwrap();
+}
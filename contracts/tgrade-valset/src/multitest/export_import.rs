@@ -9,6 +9,7 @@ use crate::state::{
 use cosmwasm_std::{coin, Addr, Decimal};
 use cw2::ContractVersion;
 use tg4::Tg4Contract;
+use tg_bindings::PubkeyType;
 use tg_utils::Duration;
 
 #[test]
@@ -61,14 +62,24 @@ fn export_works() {
             min_points: 3,
             max_validators: 6,
             scaling: None,
+            scaling_curve: None,
             epoch_reward: coin(100, "usdc"),
+            extra_epoch_rewards: vec![],
             fee_percentage: Default::default(),
             auto_unjail: false,
             double_sign_slash_ratio: Decimal::percent(50),
             distribution_contracts: vec![],
             validator_group: suite.validator_group.clone(),
             verify_validators: false,
-            offline_jail_duration: Duration::new(0)
+            offline_jail_duration: Duration::new(0),
+            probation_epochs: 0,
+            pubkey_types: vec![PubkeyType::Ed25519],
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         }
     );
 
@@ -109,7 +120,9 @@ fn import_works() {
             min_points: 30,
             max_validators: 60,
             scaling: None,
+            scaling_curve: None,
             epoch_reward: coin(200, "usdc"),
+            extra_epoch_rewards: vec![],
             fee_percentage: Default::default(),
             auto_unjail: true,
             double_sign_slash_ratio: Decimal::percent(100),
@@ -117,6 +130,14 @@ fn import_works() {
             validator_group: Addr::unchecked("validator_group"),
             verify_validators: true,
             offline_jail_duration: Duration::new(86400),
+            probation_epochs: 0,
+            pubkey_types: vec![PubkeyType::Ed25519],
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         },
         epoch: EpochInfo {
             epoch_length: 1000,
@@ -130,6 +151,8 @@ fn import_works() {
             metadata: Default::default(),
             active_validator: false,
             jailed_until: None,
+            payout: None,
+            probation_until: None,
         }],
         validators: vec![ValidatorInfo {
             validator_pubkey: addr_to_pubkey(member_addr),
@@ -176,7 +199,9 @@ fn import_deletes_existing_entries() {
             min_points: 30,
             max_validators: 60,
             scaling: None,
+            scaling_curve: None,
             epoch_reward: coin(200, "usdc"),
+            extra_epoch_rewards: vec![],
             fee_percentage: Default::default(),
             auto_unjail: true,
             double_sign_slash_ratio: Decimal::percent(100),
@@ -184,6 +209,14 @@ fn import_deletes_existing_entries() {
             validator_group: Addr::unchecked("validator_group"),
             verify_validators: true,
             offline_jail_duration: Duration::new(86400),
+            probation_epochs: 0,
+            pubkey_types: vec![PubkeyType::Ed25519],
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         },
         epoch: EpochInfo {
             epoch_length: 1000,
@@ -197,6 +230,8 @@ fn import_deletes_existing_entries() {
             metadata: Default::default(),
             active_validator: false,
             jailed_until: None,
+            payout: None,
+            probation_until: None,
         }],
         validators: vec![],
         validators_start_height: vec![],
@@ -0,0 +1,127 @@
+use cosmwasm_std::coin;
+use cw_multi_test::AppResponse;
+
+use super::helpers::members_init;
+use super::suite::{Suite, SuiteBuilder};
+
+/// Advances to the next epoch, returning if the drift alarm was raised
+fn advance_epoch_alarmed(suite: &mut Suite) -> bool {
+    let epoch_length = suite.epoch().unwrap().epoch_length;
+    suite.app().advance_seconds(epoch_length);
+    let (responses, _) = suite.app().end_block().unwrap();
+    suite.app().begin_block(vec![]).unwrap();
+    responses
+        .iter()
+        .flat_map(|resp: &AppResponse| &resp.events)
+        .any(|event| event.ty == "wasm-validator_group_drift")
+}
+
+fn group_balance(suite: &Suite) -> u128 {
+    suite
+        .balance(&suite.validator_group.clone(), "usdc")
+        .unwrap()
+}
+
+#[test]
+fn drift_raises_alarm() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    assert!(!advance_epoch_alarmed(&mut suite));
+    assert!(!advance_epoch_alarmed(&mut suite));
+
+    suite
+        .drift_validator_group(&[("intruder", 5)], &[])
+        .unwrap();
+    let paid = group_balance(&suite);
+    assert!(advance_epoch_alarmed(&mut suite));
+    // Rewards are still paid by default
+    assert_eq!(group_balance(&suite), paid + 1000);
+}
+
+#[test]
+fn drift_with_unchanged_total_raises_alarm() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    assert!(!advance_epoch_alarmed(&mut suite));
+    let validators = suite.list_active_validators(None, None).unwrap();
+    let power = |member: &str| {
+        validators
+            .iter()
+            .find(|v| v.operator == member)
+            .unwrap()
+            .power
+    };
+    let (power1, power2) = (power(members[0]), power(members[1]));
+
+    // Points moved between members
+    suite
+        .drift_validator_group(&[(members[0], power2), (members[1], power1)], &[])
+        .unwrap();
+    assert!(advance_epoch_alarmed(&mut suite));
+    suite
+        .drift_validator_group(&[(members[0], power1), (members[1], power2)], &[])
+        .unwrap();
+    assert!(!advance_epoch_alarmed(&mut suite));
+
+    // Member swapped for another one with the same points
+    suite
+        .drift_validator_group(&[("intruder", power1)], &[members[0]])
+        .unwrap();
+    assert!(advance_epoch_alarmed(&mut suite));
+}
+
+#[test]
+fn drift_skips_payout() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_skip_payout_on_group_drift()
+        .build();
+
+    assert!(!advance_epoch_alarmed(&mut suite));
+    assert!(!advance_epoch_alarmed(&mut suite));
+    let paid = group_balance(&suite);
+
+    suite
+        .drift_validator_group(&[("intruder", 5)], &[])
+        .unwrap();
+    assert!(advance_epoch_alarmed(&mut suite));
+    assert!(advance_epoch_alarmed(&mut suite));
+    assert_eq!(group_balance(&suite), paid);
+
+    // Payouts resume once the drift is fixed
+    suite.drift_validator_group(&[], &["intruder"]).unwrap();
+    assert!(!advance_epoch_alarmed(&mut suite));
+    assert_eq!(group_balance(&suite), paid + 1000);
+}
+
+#[test]
+fn failing_group_query_raises_alarm() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_skip_payout_on_group_drift()
+        .build();
+
+    assert!(!advance_epoch_alarmed(&mut suite));
+    assert!(!advance_epoch_alarmed(&mut suite));
+    let paid = group_balance(&suite);
+
+    // End block doesn't fail, but the payout is skipped
+    suite.break_validator_group().unwrap();
+    assert!(advance_epoch_alarmed(&mut suite));
+    assert_eq!(group_balance(&suite), paid);
+}
@@ -0,0 +1,159 @@
+use cosmwasm_std::{coin, Decimal};
+use cw_controllers::AdminError;
+use tg_utils::Duration;
+
+use crate::error::ContractError;
+use crate::state::{DistributionContract, IbcDistribution};
+
+use super::helpers::members_init;
+use super::suite::SuiteBuilder;
+
+fn ibc_distribution(ratio: u64) -> IbcDistribution {
+    IbcDistribution {
+        channel_id: "channel-0".to_owned(),
+        remote_address: "remote".to_owned(),
+        ratio: Decimal::percent(ratio),
+        timeout: Duration::new(600),
+    }
+}
+
+#[test]
+fn failed_transfers_are_retried() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+    let admin = suite.admin().to_owned();
+    let valset = suite.valset.clone();
+
+    suite
+        .set_ibc_distribution(&admin, Some(ibc_distribution(20)))
+        .unwrap();
+    assert_eq!(
+        suite.config().unwrap().ibc_distribution,
+        Some(ibc_distribution(20))
+    );
+
+    // IBC transfers always fail in multitest, so their funds stay in the contract
+    suite.advance_epoch().unwrap();
+    let payout = suite.metrics().unwrap().last_payout.unwrap();
+    assert_eq!(payout.ibc, vec![coin(200, "usdc")]);
+    assert_eq!(payout.validators.u128(), 800);
+    assert_eq!(suite.ibc_retry_queue().unwrap(), vec![coin(200, "usdc")]);
+    assert_eq!(suite.balance(&valset, "usdc").unwrap(), 200);
+
+    // Queued funds are not counted as fees, but sent together with the new IBC part
+    suite.advance_epoch().unwrap();
+    let payout = suite.metrics().unwrap().last_payout.unwrap();
+    assert_eq!(payout.fees.u128(), 0);
+    assert_eq!(payout.ibc, vec![coin(400, "usdc")]);
+    assert_eq!(suite.ibc_retry_queue().unwrap(), vec![coin(400, "usdc")]);
+
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 640);
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 960);
+}
+
+#[test]
+fn disabling_drops_retry_queue() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+    let admin = suite.admin().to_owned();
+
+    suite
+        .set_ibc_distribution(&admin, Some(ibc_distribution(20)))
+        .unwrap();
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.ibc_retry_queue().unwrap(), vec![coin(200, "usdc")]);
+
+    suite.set_ibc_distribution(&admin, None).unwrap();
+    assert_eq!(suite.config().unwrap().ibc_distribution, None);
+    assert_eq!(suite.ibc_retry_queue().unwrap(), vec![]);
+
+    // Funds of the dropped transfers are paid out as fees
+    suite.advance_epoch().unwrap();
+    let payout = suite.metrics().unwrap().last_payout.unwrap();
+    assert_eq!(payout.fees.u128(), 200);
+    assert_eq!(payout.ibc, vec![]);
+    assert_eq!(payout.validators.u128(), 1200);
+}
+
+#[test]
+fn invalid_distribution_rejected() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_distribution(Decimal::percent(60), &[("engagement", 10)], None)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    let err = suite
+        .set_ibc_distribution(members[0], Some(ibc_distribution(20)))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AdminError(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .set_ibc_distribution(&admin, Some(ibc_distribution(0)))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidIbcDistribution("zero ratio".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .set_ibc_distribution(
+            &admin,
+            Some(IbcDistribution {
+                channel_id: String::new(),
+                ..ibc_distribution(20)
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidIbcDistribution("empty channel id".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Together with distribution contracts it would exceed the whole reward
+    let err = suite
+        .set_ibc_distribution(&admin, Some(ibc_distribution(50)))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidRewardsRatio {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .set_ibc_distribution(&admin, Some(ibc_distribution(40)))
+        .unwrap();
+
+    // Distribution contracts cannot be raised over the IBC part either
+    let contract = suite.distribution_contract(0);
+    let err = suite
+        .update_config(
+            &admin,
+            None,
+            None,
+            vec![DistributionContract {
+                contract,
+                ratio: Decimal::percent(70),
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidRewardsRatio {},
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,76 @@
+use cosmwasm_std::{coin, Addr};
+use tg_utils::{Duration, JailingDuration};
+
+use super::helpers::{addr_to_pubkey, members_init};
+use super::suite::SuiteBuilder;
+use crate::msg::OperatorResponse;
+use crate::state::ValidatorInfo;
+
+#[test]
+fn invariants_hold_through_validator_changes() {
+    let members = vec!["member1", "member2", "member3"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3, 5]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_probation_epochs(1)
+        .build();
+    let admin = suite.admin().to_owned();
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+
+    suite
+        .jail(&admin, members[0], JailingDuration::Forever {})
+        .unwrap();
+    suite.jail(&admin, members[1], Duration::new(3600)).unwrap();
+    suite
+        .set_payout_address(members[2], Some("payout"))
+        .unwrap();
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+
+    // unjailed validator comes back on probation
+    suite.untombstone(&admin, members[0]).unwrap();
+    suite.unjail(&admin, members[1]).unwrap();
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+}
+
+#[test]
+fn inconsistent_state_is_reported() {
+    let member = "reallylongaddresstofit32charact1";
+    let mut suite = SuiteBuilder::new().with_operators(&[member]).build();
+
+    // imported validator is not flagged as active, and its power is not in the validator group
+    let mut state = suite.export().unwrap();
+    state.operators = vec![OperatorResponse {
+        operator: member.to_owned(),
+        pubkey: addr_to_pubkey(member),
+        metadata: Default::default(),
+        active_validator: false,
+        jailed_until: None,
+        payout: None,
+        probation_until: None,
+    }];
+    state.validators = vec![ValidatorInfo {
+        validator_pubkey: addr_to_pubkey(member),
+        operator: Addr::unchecked(member),
+        power: 10,
+    }];
+    suite.import(state).unwrap();
+
+    assert_eq!(
+        suite.verify_invariants().unwrap(),
+        vec![
+            format!("validator {} is not flagged as active", member),
+            "validator group members differ from validators payout addresses and power".to_owned(),
+        ]
+    );
+}
@@ -1,9 +1,9 @@
 use crate::error::ContractError;
-use crate::msg::{JailingEnd, ValidatorResponse};
+use crate::msg::{JailEndType, JailReason, JailingEnd, ValidatorResponse};
 
 use super::helpers::{assert_active_validators, assert_operators, members_init};
 use super::suite::SuiteBuilder;
-use cosmwasm_std::{StdResult, Timestamp};
+use cosmwasm_std::{Event, StdResult, Timestamp};
 use cw_controllers::AdminError;
 use tg_utils::{Duration, Expiration, JailingDuration};
 
@@ -117,6 +117,65 @@ fn admin_can_unjail_almost_anyone() {
     )
 }
 
+#[test]
+fn only_admin_can_untombstone() {
+    let members = vec!["member1", "member2", "member3", "member4"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3, 5, 8]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    suite
+        .jail(&admin, members[1], JailingDuration::Forever {})
+        .unwrap();
+    suite.jail(&admin, members[2], Duration::new(3600)).unwrap();
+
+    let jailed_until = JailingEnd::Until(Duration::new(3600).after(&suite.app().block_info()));
+
+    suite.next_block().unwrap();
+
+    // Non-admin cannot untombstone, not even themselves
+    let err = suite.untombstone(members[1], members[1]).unwrap_err();
+    assert_eq!(
+        ContractError::AdminError(AdminError::NotAdmin {}),
+        err.downcast().unwrap(),
+    );
+
+    // Only validators jailed forever can be untombstoned
+    let err = suite.untombstone(&admin, members[2]).unwrap_err();
+    assert_eq!(
+        ContractError::NotTombstoned(members[2].to_owned()),
+        err.downcast().unwrap(),
+    );
+    let err = suite.untombstone(&admin, members[3]).unwrap_err();
+    assert_eq!(
+        ContractError::NotTombstoned(members[3].to_owned()),
+        err.downcast().unwrap(),
+    );
+
+    let resp = suite.untombstone(&admin, members[1]).unwrap();
+    resp.assert_event(
+        &Event::new("wasm-untombstone")
+            .add_attribute("operator", members[1])
+            .add_attribute("slashing_events", "0"),
+    );
+
+    let slashing = suite.list_validator_slashing(members[1]).unwrap();
+    assert!(!slashing.tombstoned);
+    assert_eq!(slashing.jailed_until, None);
+
+    assert_operators(
+        &suite.list_validators(None, None).unwrap(),
+        &[
+            (members[0], None),
+            (members[1], None),
+            (members[2], Some(jailed_until)),
+            (members[3], None),
+        ],
+    )
+}
+
 #[test]
 fn anyone_can_unjail_self_after_period() {
     let members = vec!["member1", "member2", "member3", "member4"];
@@ -412,6 +471,97 @@ fn list_jailed_validators_with_pagination() {
     assert_eq!(operators[1].operator, members[4]);
 }
 
+#[test]
+fn list_jailed_validators_filtered() {
+    let members = vec!["member1", "member2", "member3"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3, 5]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    suite
+        .jail(&admin, members[0], JailingDuration::Forever {})
+        .unwrap();
+    suite.jail(&admin, members[1], Duration::new(3600)).unwrap();
+
+    let operators = suite.list_jailed_validators_filtered(None, None).unwrap();
+    assert_eq!(operators.len(), 2);
+    assert_eq!(
+        operators[1].jailed_until.as_ref().unwrap().reason,
+        Some(JailReason::Admin)
+    );
+
+    let operators = suite
+        .list_jailed_validators_filtered(JailReason::Admin, JailEndType::Forever)
+        .unwrap();
+    assert_eq!(operators.len(), 1);
+    assert_eq!(operators[0].operator, members[0]);
+
+    let operators = suite
+        .list_jailed_validators_filtered(None, JailEndType::Until)
+        .unwrap();
+    assert_eq!(operators.len(), 1);
+    assert_eq!(operators[0].operator, members[1]);
+
+    let operators = suite
+        .list_jailed_validators_filtered(JailReason::Offline, None)
+        .unwrap();
+    assert_eq!(operators, []);
+}
+
+#[test]
+fn list_jailed_validators_by_end() {
+    let members = vec!["member1", "member2", "member3", "member4", "member5"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3, 5, 8, 10]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    suite
+        .jail(&admin, members[0], JailingDuration::Forever {})
+        .unwrap();
+    suite.jail(&admin, members[1], Duration::new(7200)).unwrap();
+    suite.jail(&admin, members[2], Duration::new(3600)).unwrap();
+    suite.jail(&admin, members[3], Duration::new(7200)).unwrap();
+    suite.jail(&admin, members[4], Duration::new(1800)).unwrap();
+
+    let operators = suite
+        .list_jailed_validators_by_end(None, 3, None, None)
+        .unwrap();
+    let listed: Vec<_> = operators.iter().map(|op| op.operator.as_str()).collect();
+    assert_eq!(listed, [members[4], members[2], members[1]]);
+
+    let operators = suite
+        .list_jailed_validators_by_end(members[1].to_owned(), None, None, None)
+        .unwrap();
+    let listed: Vec<_> = operators.iter().map(|op| op.operator.as_str()).collect();
+    assert_eq!(listed, [members[3], members[0]]);
+
+    let operators = suite
+        .list_jailed_validators_by_end(None, None, JailReason::Admin, JailEndType::Until)
+        .unwrap();
+    assert_eq!(operators.len(), 4);
+
+    // Rejailing moves the operator in the ordering
+    suite
+        .jail(&admin, members[4], Duration::new(10800))
+        .unwrap();
+    suite.unjail(&admin, members[2]).unwrap();
+    let operators = suite
+        .list_jailed_validators_by_end(None, None, None, JailEndType::Until)
+        .unwrap();
+    let listed: Vec<_> = operators.iter().map(|op| op.operator.as_str()).collect();
+    assert_eq!(listed, [members[1], members[3], members[4]]);
+
+    let err = suite
+        .list_jailed_validators_by_end(members[2].to_owned(), None, None, None)
+        .unwrap_err();
+    assert!(err.to_string().contains("is not jailed"), "{}", err);
+    assert_eq!(suite.verify_invariants().unwrap(), [] as [String; 0]);
+}
+
 #[test]
 fn jailing_duration_start_is_provided() {
     let members = vec!["member1", "member2"];
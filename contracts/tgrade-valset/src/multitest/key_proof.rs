@@ -0,0 +1,175 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::Binary;
+use ed25519_zebra::{SigningKey, VerificationKey};
+use tg_bindings::{Pubkey, PubkeyType, ValidatedPubkey};
+
+use super::helpers::members_init;
+use super::suite::{Suite, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::key_proof_message;
+use crate::test_helpers::mock_metadata;
+
+fn signing_key(seed: u8) -> (SigningKey, Pubkey) {
+    let key = SigningKey::from([seed; 32]);
+    let pubkey = Pubkey::Ed25519(Binary::from(VerificationKey::from(&key).as_ref().to_vec()));
+    (key, pubkey)
+}
+
+fn key_proof(suite: &mut Suite, key: &SigningKey, pubkey: &Pubkey, operator: &str) -> Binary {
+    let message = key_proof_message(
+        &suite.app().block_info().chain_id,
+        suite.valset.as_str(),
+        operator,
+        &ValidatedPubkey::try_from(pubkey).unwrap(),
+    );
+    Binary::from(<[u8; 64]>::from(key.sign(&message)).to_vec())
+}
+
+#[test]
+fn register_requires_proof() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_require_key_proof()
+        .build();
+
+    let (key, pubkey) = signing_key(1);
+
+    let err = suite
+        .register_validator_key(members[0], pubkey.clone(), mock_metadata(members[0]))
+        .unwrap_err();
+    assert_eq!(ContractError::MissingKeyProof {}, err.downcast().unwrap());
+
+    // Proof made for another operator can't be used to hijack the key
+    let proof = key_proof(&mut suite, &key, &pubkey, members[0]);
+    let err = suite
+        .register_validator_key_with_proof(
+            members[1],
+            pubkey.clone(),
+            mock_metadata(members[1]),
+            proof.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidKeyProof {}, err.downcast().unwrap());
+
+    suite
+        .register_validator_key_with_proof(
+            members[0],
+            pubkey.clone(),
+            mock_metadata(members[0]),
+            proof,
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .validator(members[0])
+            .unwrap()
+            .validator
+            .unwrap()
+            .pubkey,
+        pubkey
+    );
+}
+
+#[test]
+fn rotate_requires_proof() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_require_key_proof()
+        .build();
+
+    let (key, pubkey) = signing_key(2);
+    let (other_key, _) = signing_key(3);
+
+    let err = suite
+        .rotate_validator_key(members[0], pubkey.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::MissingKeyProof {}, err.downcast().unwrap());
+
+    // Proof has to be signed with the new key
+    let proof = key_proof(&mut suite, &other_key, &pubkey, members[0]);
+    let err = suite
+        .rotate_validator_key_with_proof(members[0], pubkey.clone(), proof)
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidKeyProof {}, err.downcast().unwrap());
+
+    let proof = key_proof(&mut suite, &key, &pubkey, members[0]);
+    suite
+        .rotate_validator_key_with_proof(members[0], pubkey, proof)
+        .unwrap();
+}
+
+#[test]
+fn register_secp256k1_key_with_proof() {
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+    use k256::ecdsa::{Signature, SigningKey};
+    use sha2::{Digest, Sha256};
+
+    let members = vec!["member1"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2]))
+        .with_require_key_proof()
+        .build();
+    let admin = suite.admin().to_owned();
+    suite
+        .update_pubkey_types(&admin, vec![PubkeyType::Ed25519, PubkeyType::Secp256k1])
+        .unwrap();
+
+    let key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+    let pubkey = Pubkey::Secp256k1(Binary::from(key.verifying_key().to_bytes().as_slice()));
+    let message = key_proof_message(
+        &suite.app().block_info().chain_id,
+        suite.valset.as_str(),
+        members[0],
+        &ValidatedPubkey::try_from(&pubkey).unwrap(),
+    );
+    let sign = |message: &[u8]| {
+        let signature: Signature = key.sign_prehash(&Sha256::digest(message)).unwrap();
+        Binary::from(signature.as_ref())
+    };
+
+    // Proof has to be made over the key proof message
+    let err = suite
+        .register_validator_key_with_proof(
+            members[0],
+            pubkey.clone(),
+            mock_metadata(members[0]),
+            sign(b"other message"),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidKeyProof {}, err.downcast().unwrap());
+
+    suite
+        .register_validator_key_with_proof(
+            members[0],
+            pubkey.clone(),
+            mock_metadata(members[0]),
+            sign(&message),
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .validator(members[0])
+            .unwrap()
+            .validator
+            .unwrap()
+            .pubkey,
+        pubkey
+    );
+}
+
+#[test]
+fn proof_not_required_by_default() {
+    let members = vec!["member1"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2]))
+        .build();
+
+    let (_, pubkey) = signing_key(1);
+    suite
+        .register_validator_key(members[0], pubkey, mock_metadata(members[0]))
+        .unwrap();
+}
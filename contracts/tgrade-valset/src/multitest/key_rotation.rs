@@ -0,0 +1,169 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::{coin, Binary};
+use tg_bindings::{
+    Ed25519Pubkey, Evidence, EvidenceType, Pubkey, ToAddress, Validator, ValidatorUpdate,
+};
+
+use super::helpers::{assert_active_validators, members_init};
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::msg::{JailingEnd, KeyRotationInfo};
+use crate::test_helpers::{mock_metadata, mock_pubkey};
+
+fn evidence_for(pubkey: Pubkey, height: u64) -> Evidence {
+    let address = Ed25519Pubkey::try_from(pubkey).unwrap().to_address();
+    Evidence {
+        evidence_type: EvidenceType::DuplicateVote,
+        validator: Validator {
+            address: Binary::from(address.to_vec()),
+            power: 10,
+        },
+        height,
+        time: 3,
+        total_voting_power: 20,
+    }
+}
+
+#[test]
+fn new_key_activated_after_delay() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    let old_key = mock_pubkey(members[0].as_bytes());
+    let new_key = mock_pubkey(b"rotated");
+    let epoch = suite.epoch().unwrap().current_epoch;
+
+    suite
+        .rotate_validator_key(members[0], new_key.clone())
+        .unwrap();
+    assert_eq!(
+        suite.key_rotation(members[0]).unwrap(),
+        Some(KeyRotationInfo {
+            old_pubkey: old_key.clone(),
+            new_pubkey: new_key.clone(),
+            activation_epoch: epoch + 2,
+        })
+    );
+
+    let err = suite
+        .rotate_validator_key(members[0], mock_pubkey(b"another"))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::KeyRotationInProgress(epoch + 2),
+        err.downcast().unwrap()
+    );
+
+    // Next epoch still uses the old key
+    suite.advance_epoch().unwrap();
+    let validator = suite.validator(members[0]).unwrap().validator.unwrap();
+    assert_eq!(validator.pubkey, old_key);
+
+    // Tendermint replaces the old key with the new one
+    let diff = suite.advance_epoch().unwrap().unwrap();
+    assert_eq!(
+        diff.diffs,
+        vec![
+            ValidatorUpdate {
+                pubkey: new_key.clone(),
+                power: 2,
+            },
+            ValidatorUpdate {
+                pubkey: old_key,
+                power: 0,
+            },
+        ]
+    );
+    let validator = suite.validator(members[0]).unwrap().validator.unwrap();
+    assert_eq!(validator.pubkey, new_key);
+    assert!(validator.active_validator);
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 2), (members[1], 3)],
+    );
+    assert!(suite.key_rotation(members[0]).unwrap().is_some());
+    assert_eq!(suite.verify_invariants().unwrap(), Vec::<String>::new());
+
+    // Rotated operator still gets rewards
+    suite.advance_epoch().unwrap();
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 1200);
+
+    // Transition is over, another rotation can be started
+    assert_eq!(suite.key_rotation(members[0]).unwrap(), None);
+    suite
+        .rotate_validator_key(members[0], mock_pubkey(b"another"))
+        .unwrap();
+}
+
+#[test]
+fn evidence_of_old_key_matched_during_transition() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    let old_key = mock_pubkey(members[0].as_bytes());
+    suite
+        .rotate_validator_key(members[0], mock_pubkey(b"rotated"))
+        .unwrap();
+    suite.advance_epoch().unwrap();
+    suite.advance_epoch().unwrap();
+
+    // Evidence reported right after the activation, before the transition ends on the next
+    // epoch boundary
+    let evidence = evidence_for(old_key, suite.height());
+    suite.app().begin_block(vec![evidence]).unwrap();
+
+    let validator = suite.validator(members[0]).unwrap().validator.unwrap();
+    assert_eq!(
+        validator.jailed_until.map(|jail| jail.end),
+        Some(JailingEnd::Forever {})
+    );
+}
+
+#[test]
+fn rotation_requires_unused_key() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    let err = suite
+        .rotate_validator_key("stranger", mock_pubkey(b"rotated"))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OperatorNotRegistered("stranger".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .rotate_validator_key(members[0], mock_pubkey(members[1].as_bytes()))
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyInUse {}, err.downcast().unwrap());
+
+    suite
+        .rotate_validator_key(members[0], mock_pubkey(b"rotated"))
+        .unwrap();
+    let err = suite
+        .rotate_validator_key(members[1], mock_pubkey(b"rotated"))
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyInUse {}, err.downcast().unwrap());
+
+    // Keys pending activation cannot be registered either
+    let err = suite
+        .register_validator_key(
+            "stranger",
+            mock_pubkey(b"rotated"),
+            mock_metadata("stranger"),
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyInUse {}, err.downcast().unwrap());
+}
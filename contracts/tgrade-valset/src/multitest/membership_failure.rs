@@ -0,0 +1,37 @@
+use super::helpers::{assert_active_validators, members_init};
+use super::suite::SuiteBuilder;
+
+#[test]
+fn broken_membership_fails_end_block_by_default() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    suite.break_membership().unwrap();
+    suite.advance_epoch().unwrap_err();
+}
+
+#[test]
+fn broken_membership_keeps_validators() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_keep_validators_on_membership_failure()
+        .build();
+
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.metrics().unwrap().membership_query_failures, 0);
+
+    suite.break_membership().unwrap();
+    suite.advance_epoch().unwrap();
+    suite.advance_epoch().unwrap();
+
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 2), (members[1], 3)],
+    );
+    assert_eq!(suite.metrics().unwrap().membership_query_failures, 2);
+}
@@ -0,0 +1,70 @@
+use cosmwasm_std::{coin, Uint128};
+use tg_utils::{Duration, JailingDuration};
+
+use super::helpers::members_init;
+use super::suite::SuiteBuilder;
+
+#[test]
+fn metrics_follow_jailing() {
+    let members = vec!["member1", "member2", "member3"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3, 5]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    let metrics = suite.metrics().unwrap();
+    assert_eq!(metrics.operators, 3);
+    assert_eq!(metrics.active_validators, 3);
+    assert_eq!(metrics.jailed, 0);
+    assert_eq!(metrics.tombstoned, 0);
+
+    suite
+        .jail(&admin, members[1], JailingDuration::Forever {})
+        .unwrap();
+    suite.jail(&admin, members[2], Duration::new(3600)).unwrap();
+    let metrics = suite.metrics().unwrap();
+    assert_eq!(metrics.jailed, 1);
+    assert_eq!(metrics.tombstoned, 1);
+
+    // Jailing already jailed operator moves it between counters
+    suite
+        .jail(&admin, members[2], JailingDuration::Forever {})
+        .unwrap();
+    let metrics = suite.metrics().unwrap();
+    assert_eq!(metrics.jailed, 0);
+    assert_eq!(metrics.tombstoned, 2);
+
+    suite.untombstone(&admin, members[1]).unwrap();
+    suite.advance_epoch().unwrap();
+    let metrics = suite.metrics().unwrap();
+    assert_eq!(metrics.jailed, 0);
+    assert_eq!(metrics.tombstoned, 1);
+    assert_eq!(metrics.active_validators, 2);
+}
+
+#[test]
+fn metrics_report_last_payout() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    let metrics = suite.metrics().unwrap();
+    assert_eq!(metrics.last_payout, None);
+    let epoch = metrics.current_epoch;
+
+    suite.advance_epoch().unwrap();
+
+    let metrics = suite.metrics().unwrap();
+    assert_eq!(metrics.current_epoch, epoch + 1);
+    let payout = metrics.last_payout.unwrap();
+    assert_eq!(payout.height, suite.height());
+    assert_eq!(payout.epochs, 1);
+    assert_eq!(payout.minted, Uint128::new(1000));
+    assert_eq!(payout.fees, Uint128::zero());
+    assert_eq!(payout.distribution, Uint128::zero());
+    assert_eq!(payout.validators, Uint128::new(1000));
+}
@@ -2,6 +2,7 @@ use super::suite::SuiteBuilder;
 use crate::msg::MigrateMsg;
 use crate::state::DistributionContract;
 use cosmwasm_std::{Addr, Decimal};
+use tg_utils::MigrationRecord;
 
 #[test]
 fn migration_can_alter_cfg() {
@@ -26,6 +27,7 @@ fn migration_can_alter_cfg() {
                     ratio: Decimal::percent(50),
                 }]),
                 verify_validators: Some(true),
+                exit_cooldown: None,
             },
         )
         .unwrap();
@@ -42,3 +44,44 @@ fn migration_can_alter_cfg() {
         }]
     );
 }
+
+#[test]
+fn migration_steps_are_recorded_once() {
+    let mut suite = SuiteBuilder::new().build();
+    let admin = suite.admin().to_string();
+    assert_eq!(suite.migration_history().unwrap().migrations, vec![]);
+
+    let msg = MigrateMsg {
+        min_points: None,
+        max_validators: None,
+        distribution_contracts: None,
+        verify_validators: None,
+        exit_cooldown: None,
+    };
+    suite.migrate(&admin, &msg).unwrap();
+    let height = suite.app().block_info().height;
+    suite.migrate(&admin, &msg).unwrap();
+
+    let version = env!("CARGO_PKG_VERSION").to_owned();
+    assert_eq!(
+        suite.migration_history().unwrap().migrations,
+        vec![
+            MigrationRecord {
+                from_version: version.clone(),
+                to_version: version.clone(),
+                height,
+                steps: vec![
+                    "metrics_counters".to_owned(),
+                    "payout_address_index".to_owned(),
+                    "jail_end_index".to_owned()
+                ],
+            },
+            MigrationRecord {
+                from_version: version.clone(),
+                to_version: version,
+                height,
+                steps: vec![],
+            },
+        ]
+    );
+}
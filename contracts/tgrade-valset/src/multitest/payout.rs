@@ -0,0 +1,112 @@
+use cosmwasm_std::{coin, Addr};
+
+use crate::error::ContractError;
+
+use super::helpers::members_init;
+use super::suite::SuiteBuilder;
+
+#[test]
+fn payout_address_receives_rewards() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    suite
+        .set_payout_address(members[0], Some("payout"))
+        .unwrap();
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(
+        resp.validator.unwrap().payout,
+        Some(Addr::unchecked("payout"))
+    );
+
+    suite.advance_epoch().unwrap();
+
+    suite.withdraw_validation_reward("payout").unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+
+    assert_eq!(suite.token_balance("payout").unwrap(), 400);
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 0);
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 600);
+}
+
+#[test]
+fn active_validator_changes_payout_address() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    suite.advance_epoch().unwrap();
+
+    // Already active validator is moved to the payout address immediately
+    suite
+        .set_payout_address(members[0], Some("payout"))
+        .unwrap();
+    suite.advance_epoch().unwrap();
+
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward("payout").unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 400);
+    assert_eq!(suite.token_balance("payout").unwrap(), 400);
+
+    // Resetting the payout address sends rewards to the operator again
+    suite.set_payout_address(members[0], None).unwrap();
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(resp.validator.unwrap().payout, None);
+    suite.advance_epoch().unwrap();
+
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 800);
+}
+
+#[test]
+fn payout_address_cannot_be_shared() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    suite
+        .set_payout_address(members[0], Some("payout"))
+        .unwrap();
+
+    // Payout address of other operator
+    let err = suite
+        .set_payout_address(members[1], Some("payout"))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PayoutAddressTaken("payout".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Other operator itself
+    let err = suite
+        .set_payout_address(members[1], Some(members[0]))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PayoutAddressTaken(members[0].to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Only registered operators can set a payout address
+    let err = suite
+        .set_payout_address("someone", Some("payout2"))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Unauthorized("No operator info found".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Released payout address can be taken by other operator
+    suite.set_payout_address(members[0], None).unwrap();
+    suite
+        .set_payout_address(members[1], Some("payout"))
+        .unwrap();
+}
@@ -0,0 +1,224 @@
+use cosmwasm_std::{
+    from_slice, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+    StdError, StdResult,
+};
+use cw_controllers::AdminError;
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use tg_bindings::{TgradeMsg, TgradeQuery};
+use tg_utils::{Duration, HookError, JailingDuration, PreauthError};
+
+use super::helpers::members_init;
+use super::suite::{Suite, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::PenaltyHookMsg;
+
+type Response = cosmwasm_std::Response<TgradeMsg>;
+
+/// Hook reporting received penalties as `penalty` events
+fn contract_hook() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    fn execute(
+        _deps: DepsMut<TgradeQuery>,
+        _env: Env,
+        _info: MessageInfo,
+        msg: PenaltyHookMsg,
+    ) -> StdResult<Response> {
+        let PenaltyHookMsg::ValidatorPenalty {
+            operator,
+            reason,
+            slash_portion,
+            ..
+        } = msg;
+        let reason: String = from_slice(&to_binary(&reason)?)?;
+        let mut evt = Event::new("penalty")
+            .add_attribute("operator", operator)
+            .add_attribute("reason", reason);
+        if let Some(portion) = slash_portion {
+            evt = evt.add_attribute("portion", portion.to_string());
+        }
+        Ok(Response::new().add_event(evt))
+    }
+
+    fn instantiate(
+        _deps: DepsMut<TgradeQuery>,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps<TgradeQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("not implemented"))
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/// Hook failing on every call
+fn contract_failing_hook() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    fn execute(
+        _deps: DepsMut<TgradeQuery>,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: PenaltyHookMsg,
+    ) -> StdResult<Response> {
+        Err(StdError::generic_err("hook failure"))
+    }
+
+    fn instantiate(
+        _deps: DepsMut<TgradeQuery>,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps<TgradeQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("not implemented"))
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn instantiate_hook(
+    suite: &mut Suite,
+    contract: Box<dyn Contract<TgradeMsg, TgradeQuery>>,
+) -> String {
+    let admin = suite.admin().to_owned();
+    let app = suite.app();
+    let code_id = app.store_code(contract);
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(admin),
+        &Empty {},
+        &[],
+        "hook",
+        None,
+    )
+    .unwrap()
+    .to_string()
+}
+
+#[test]
+fn admin_or_preauth_can_add_hooks() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_preauths_penalty_hooks(1)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    let resp = suite.list_penalty_hooks().unwrap();
+    assert!(resp.hooks.is_empty());
+    assert_eq!(resp.preauths, 1);
+
+    // Admin doesn't use preauths
+    suite.add_penalty_hook(&admin, "hook1").unwrap();
+    let err = suite.add_penalty_hook(&admin, "hook1").unwrap_err();
+    assert_eq!(
+        ContractError::Hook(HookError::HookAlreadyRegistered {}),
+        err.downcast().unwrap()
+    );
+
+    // Anyone can use the preauth
+    suite.add_penalty_hook(members[0], "hook2").unwrap();
+    let err = suite.add_penalty_hook(members[0], "hook3").unwrap_err();
+    assert_eq!(
+        ContractError::Preauth(PreauthError::NoPreauth {}),
+        err.downcast().unwrap()
+    );
+
+    let resp = suite.list_penalty_hooks().unwrap();
+    assert_eq!(resp.hooks, vec!["hook1".to_owned(), "hook2".to_owned()]);
+    assert_eq!(resp.preauths, 0);
+
+    // Hook can be removed only by itself or admin
+    let err = suite.remove_penalty_hook(members[0], "hook2").unwrap_err();
+    assert_eq!(
+        ContractError::Unauthorized(
+            "Hook address is not same as sender's or sender is not an admin".to_owned()
+        ),
+        err.downcast().unwrap()
+    );
+    suite.remove_penalty_hook("hook2", "hook2").unwrap();
+    suite.remove_penalty_hook(&admin, "hook1").unwrap();
+
+    let resp = suite.list_penalty_hooks().unwrap();
+    assert!(resp.hooks.is_empty());
+}
+
+#[test]
+fn no_preauths_by_default() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+
+    let err = suite.add_penalty_hook(members[0], "hook").unwrap_err();
+    assert_eq!(
+        ContractError::Preauth(PreauthError::NoPreauth {}),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .jail(members[0], members[1], Duration::new(3600))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AdminError(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn hooks_are_informed_about_jailing_and_slashing() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    let hook = instantiate_hook(&mut suite, contract_hook());
+    suite.add_penalty_hook(&admin, &hook).unwrap();
+
+    let resp = suite
+        .jail(&admin, members[0], JailingDuration::Forever {})
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-penalty")
+            .add_attribute("operator", members[0])
+            .add_attribute("reason", "jailed"),
+    );
+
+    let resp = suite
+        .slash(&admin, members[1], Decimal::percent(10))
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-penalty")
+            .add_attribute("operator", members[1])
+            .add_attribute("reason", "slashed")
+            .add_attribute("portion", "0.1"),
+    );
+}
+
+#[test]
+fn failing_hook_does_not_block_jailing() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .build();
+    let admin = suite.admin().to_owned();
+
+    let hook = instantiate_hook(&mut suite, contract_failing_hook());
+    suite.add_penalty_hook(&admin, &hook).unwrap();
+
+    let resp = suite.jail(&admin, members[0], Duration::new(3600)).unwrap();
+    resp.assert_event(&Event::new("wasm").add_attribute("action", "penalty_hook_failed"));
+
+    let slashing = suite.list_validator_slashing(members[0]).unwrap();
+    assert!(slashing.jailed_until.is_some());
+}
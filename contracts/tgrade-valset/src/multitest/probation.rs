@@ -0,0 +1,55 @@
+use cosmwasm_std::coin;
+
+use super::helpers::members_init;
+use super::suite::SuiteBuilder;
+
+#[test]
+fn new_validators_start_on_probation() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_probation_epochs(1)
+        .build();
+
+    let epoch = suite.epoch().unwrap().current_epoch;
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(resp.validator.unwrap().probation_until, Some(epoch + 1));
+
+    // Validators on probation earn nothing, reward stays in the contract
+    suite.advance_epoch().unwrap();
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 0);
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 0);
+
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(resp.validator.unwrap().probation_until, None);
+
+    // Probation is over, kept reward is paid out together with the new one
+    suite.advance_epoch().unwrap();
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 800);
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 1200);
+}
+
+#[test]
+fn no_probation_by_default() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[2, 3]))
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    let resp = suite.validator(members[0]).unwrap();
+    assert_eq!(resp.validator.unwrap().probation_until, None);
+
+    suite.advance_epoch().unwrap();
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 400);
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 600);
+}
@@ -1,5 +1,6 @@
 use super::suite::SuiteBuilder;
-use cosmwasm_std::{coin, Decimal};
+use crate::msg::{DistributionContractPayout, ValidatorPayout};
+use cosmwasm_std::{coin, Addr, Decimal};
 
 use tg_utils::JailingDuration;
 
@@ -268,3 +269,140 @@ fn jailed_validators_not_rewarded() {
     assert_eq!(suite.token_balance(engagement[0]).unwrap(), 240);
     assert_eq!(suite.token_balance(engagement[1]).unwrap(), 560);
 }
+
+#[test]
+fn extra_rewards_split_from_budget() {
+    let engagement = ["dist1", "dist2"];
+    let members = ["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&[(members[0], 2), (members[1], 3)])
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_extra_epoch_reward(coin(500, "ibc/atom"))
+        .with_distribution(
+            Decimal::percent(40),
+            &[(engagement[0], 3), (engagement[1], 7)],
+            None,
+        )
+        .build();
+    suite.fund_valset(coin(800, "ibc/atom")).unwrap();
+
+    let distribution = suite.distribution_contract(0);
+    let validator_group = suite.validator_group.clone();
+    let valset = suite.valset.clone();
+
+    // Extra reward is split the same way as the minted reward:
+    // * distribution: 0.4 * 500 = 200
+    // * validators: 0.6 * 500 = 300
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.balance(&distribution, "ibc/atom").unwrap(), 200);
+    assert_eq!(suite.balance(&validator_group, "ibc/atom").unwrap(), 300);
+    assert_eq!(suite.balance(&distribution, "usdc").unwrap(), 400);
+    assert_eq!(suite.balance(&validator_group, "usdc").unwrap(), 600);
+    assert_eq!(suite.balance(&valset, "ibc/atom").unwrap(), 300);
+
+    // Only the remaining budget is paid when the balance is insufficient
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.balance(&distribution, "ibc/atom").unwrap(), 320);
+    assert_eq!(suite.balance(&validator_group, "ibc/atom").unwrap(), 480);
+    assert_eq!(suite.balance(&valset, "ibc/atom").unwrap(), 0);
+    let payout = suite.metrics().unwrap().last_payout.unwrap();
+    assert_eq!(payout.extra, [coin(300, "ibc/atom")]);
+
+    // Nothing more to pay
+    suite.advance_epoch().unwrap();
+    assert_eq!(suite.balance(&validator_group, "ibc/atom").unwrap(), 480);
+    let payout = suite.metrics().unwrap().last_payout.unwrap();
+    assert_eq!(payout.extra, []);
+    assert_eq!(
+        suite.config().unwrap().extra_epoch_rewards,
+        [coin(500, "ibc/atom")]
+    );
+}
+
+#[test]
+fn simulated_rewards_match_payout() {
+    let engagement = ["dist1", "dist2"];
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&[(members[0], 2), (members[1], 3)])
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .with_distribution(
+            Decimal::percent(40),
+            &[(engagement[0], 3), (engagement[1], 7)],
+            None,
+        )
+        .build();
+
+    let simulation = suite
+        .simulate_rewards(&[(members[0], 2), (members[1], 3)], 1)
+        .unwrap();
+    assert_eq!(simulation.minted.u128(), 1000);
+    assert_eq!(simulation.fees.u128(), 0);
+    assert_eq!(
+        simulation.distribution_contracts,
+        vec![DistributionContractPayout {
+            contract: suite.distribution_contract(0),
+            rewards: vec![coin(400, "usdc")],
+        }]
+    );
+    assert_eq!(
+        simulation.validators,
+        vec![
+            ValidatorPayout {
+                operator: Addr::unchecked(members[0]),
+                rewards: vec![coin(240, "usdc")],
+            },
+            ValidatorPayout {
+                operator: Addr::unchecked(members[1]),
+                rewards: vec![coin(360, "usdc")],
+            },
+        ]
+    );
+
+    suite.advance_epoch().unwrap();
+    suite.withdraw_validation_reward(members[0]).unwrap();
+    suite.withdraw_validation_reward(members[1]).unwrap();
+    assert_eq!(suite.token_balance(members[0]).unwrap(), 240);
+    assert_eq!(suite.token_balance(members[1]).unwrap(), 360);
+}
+
+#[test]
+fn simulate_rewards_for_hypothetical_powers() {
+    let members = vec!["member1", "member2"];
+    let suite = SuiteBuilder::new()
+        .with_engagement(&[(members[0], 2), (members[1], 3)])
+        .with_operators(&members)
+        .with_epoch_reward(coin(1000, "usdc"))
+        .build();
+
+    // New operators and multiple epochs can be simulated, rewards are rounded down
+    let simulation = suite
+        .simulate_rewards(&[(members[0], 1), ("newcomer", 2)], 2)
+        .unwrap();
+    assert_eq!(simulation.minted.u128(), 2000);
+    assert_eq!(
+        simulation.validators,
+        vec![
+            ValidatorPayout {
+                operator: Addr::unchecked(members[0]),
+                rewards: vec![coin(666, "usdc")],
+            },
+            ValidatorPayout {
+                operator: Addr::unchecked("newcomer"),
+                rewards: vec![coin(1333, "usdc")],
+            },
+        ]
+    );
+
+    // Without any power validators get nothing
+    let simulation = suite.simulate_rewards(&[(members[0], 0)], 1).unwrap();
+    assert_eq!(
+        simulation.validators,
+        vec![ValidatorPayout {
+            operator: Addr::unchecked(members[0]),
+            rewards: vec![],
+        }]
+    );
+}
@@ -0,0 +1,126 @@
+use super::helpers::{assert_active_validators, members_init};
+use super::suite::SuiteBuilder;
+use crate::error::ContractError;
+use crate::state::ScalingCurve;
+
+#[test]
+fn legacy_scaling_is_linear() {
+    let members = vec!["member1", "member2"];
+    let suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[4, 100]))
+        .with_operators(&members)
+        .with_scaling(3)
+        .build();
+
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 12), (members[1], 300)],
+    );
+    assert_eq!(
+        suite.config().unwrap().scaling_curve(),
+        ScalingCurve::Linear { multiplier: 3 }
+    );
+}
+
+#[test]
+fn sqrt_curve_dampens_large_stakes() {
+    let members = vec!["member1", "member2", "member3"];
+    let suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[4, 100, 110]))
+        .with_operators(&members)
+        .with_scaling_curve(ScalingCurve::Sqrt { multiplier: 10 })
+        .build();
+
+    // Square root is rounded down
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 20), (members[1], 100), (members[2], 100)],
+    );
+}
+
+#[test]
+fn capped_linear_curve_limits_power() {
+    let members = vec!["member1", "member2"];
+    let suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[4, 100]))
+        .with_operators(&members)
+        .with_scaling_curve(ScalingCurve::CappedLinear {
+            multiplier: 2,
+            cap: 50,
+        })
+        .build();
+
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 8), (members[1], 50)],
+    );
+}
+
+#[test]
+fn update_switches_curve_on_next_epoch() {
+    let members = vec!["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_engagement(&members_init(&members, &[4, 100]))
+        .with_operators(&members)
+        .with_scaling(2)
+        .build();
+    let admin = suite.admin().to_string();
+
+    suite
+        .update_scaling(&admin, None, ScalingCurve::Sqrt { multiplier: 1 })
+        .unwrap();
+    let cfg = suite.config().unwrap();
+    assert_eq!(cfg.scaling, None);
+    assert_eq!(
+        cfg.scaling_curve,
+        Some(ScalingCurve::Sqrt { multiplier: 1 })
+    );
+
+    // Active validators are recalculated only on the epoch boundary
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 8), (members[1], 200)],
+    );
+    suite.advance_epoch().unwrap();
+    assert_active_validators(
+        &suite.list_active_validators(None, None).unwrap(),
+        &[(members[0], 2), (members[1], 10)],
+    );
+
+    // Going back to the plain multiplier drops the curve
+    suite.update_scaling(&admin, 5, None).unwrap();
+    let cfg = suite.config().unwrap();
+    assert_eq!(cfg.scaling, Some(5));
+    assert_eq!(cfg.scaling_curve, None);
+}
+
+#[test]
+fn invalid_curve_updates_are_rejected() {
+    let mut suite = SuiteBuilder::new().build();
+    let admin = suite.admin().to_string();
+
+    let err = suite
+        .update_scaling(&admin, 2, ScalingCurve::Sqrt { multiplier: 1 })
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ConflictingScaling {},
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .update_scaling(&admin, None, ScalingCurve::Linear { multiplier: 0 })
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidScaling {}, err.downcast().unwrap());
+
+    let err = suite
+        .update_scaling(
+            &admin,
+            None,
+            ScalingCurve::CappedLinear {
+                multiplier: 1,
+                cap: 0,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidScaling {}, err.downcast().unwrap());
+}
@@ -1,5 +1,6 @@
 #![cfg(test)]
 use cosmwasm_std::{coin, Addr, Decimal};
+use tg_bindings::PubkeyType;
 use tg_utils::Duration;
 
 use crate::multitest::suite::SuiteBuilder;
@@ -30,7 +31,9 @@ fn init_and_query_state() {
             min_points: 5,
             max_validators: 10,
             scaling: None,
+            scaling_curve: None,
             epoch_reward,
+            extra_epoch_rewards: vec![],
             fee_percentage: Decimal::zero(),
             auto_unjail: false,
             double_sign_slash_ratio: Decimal::percent(50),
@@ -38,6 +41,14 @@ fn init_and_query_state() {
             validator_group: cfg.validator_group.clone(),
             verify_validators: false,
             offline_jail_duration: Duration::new(0),
+            probation_epochs: 0,
+            pubkey_types: vec![PubkeyType::Ed25519],
+            ibc_distribution: None,
+            exit_cooldown: None,
+            evidence_policy: None,
+            keep_validators_on_membership_failure: false,
+            skip_payout_on_group_drift: false,
+            require_key_proof: false,
         }
     );
 
@@ -1,19 +1,23 @@
 use super::helpers::addr_to_pubkey;
-use crate::state::{Config, DistributionContract, ValsetState};
+use crate::state::{
+    Config, DistributionContract, EvidencePolicy, IbcDistribution, ScalingCurve, ValsetState,
+};
 use crate::test_helpers::{mock_metadata, mock_pubkey};
 use crate::{msg::*, state::ValidatorInfo};
 use anyhow::{bail, Result as AnyResult};
 use cosmwasm_std::{
-    coin, from_binary, Addr, BlockInfo, Coin, CosmosMsg, Decimal, StdResult, Timestamp, Uint128,
+    coin, from_binary, Addr, Binary, BlockInfo, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, Response, StdError, StdResult, Timestamp, Uint128,
 };
 use cw_multi_test::{next_block, AppResponse, Contract, ContractWrapper, CosmosRouter, Executor};
 use derivative::Derivative;
 use tg4::{AdminResponse, Member};
 use tg_bindings::{
-    Evidence, Pubkey, TgradeMsg, TgradeQuery, TgradeSudoMsg, ValidatorDiff, ValidatorVote,
+    Evidence, Pubkey, PubkeyType, TgradeMsg, TgradeQuery, TgradeSudoMsg, ValidatorDiff,
+    ValidatorVote,
 };
 use tg_bindings_test::TgradeApp;
-use tg_utils::{Duration, JailingDuration};
+use tg_utils::{Duration, JailingDuration, MigrationHistoryResponse};
 
 use crate::msg::OperatorInitInfo;
 
@@ -48,6 +52,23 @@ pub fn contract_valset() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
     Box::new(contract)
 }
 
+/// Membership replacement failing on every query, simulating a broken migration
+pub fn contract_broken_membership() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    let contract = ContractWrapper::new(
+        tg4_engagement::contract::execute,
+        tg4_engagement::contract::instantiate,
+        |_: Deps<TgradeQuery>, _: Env, _: Empty| -> StdResult<Binary> {
+            Err(StdError::generic_err("membership is broken"))
+        },
+    )
+    .with_migrate(
+        |_: DepsMut<TgradeQuery>, _: Env, _: Empty| -> StdResult<Response<TgradeMsg>> {
+            Ok(Response::new())
+        },
+    );
+    Box::new(contract)
+}
+
 #[derive(Debug, Clone)]
 struct DistributionConfig {
     members: Vec<Member>,
@@ -90,8 +111,12 @@ pub struct SuiteBuilder {
     /// Base epoch reward, 100uscd by default
     #[derivative(Default(value = "coin(100, \"usdc\")"))]
     epoch_reward: Coin,
+    /// Extra epoch rewards paid from the valset balance, none by default
+    extra_epoch_rewards: Vec<Coin>,
     /// Validators points scaling
     scaling: Option<u32>,
+    /// Validators points scaling curve, overrides `scaling` if set
+    scaling_curve: Option<ScalingCurve>,
     /// Factor determining how accumulated fees affects base epoch reward
     fee_percentage: Decimal,
     /// Flag determining if jailed operators should be automatically unjailed
@@ -103,6 +128,21 @@ pub struct SuiteBuilder {
     /// Funds to add on init per address
     init_funds: Vec<(String, Vec<Coin>)>,
     verify_validators: Option<Duration>,
+    /// Number of penalty hooks which can be added by non-admin
+    preauths_penalty_hooks: u64,
+    /// Number of epochs newly activated validators spend on probation
+    probation_epochs: u64,
+    /// Cooldown after exit settlement before the operator can be purged
+    exit_cooldown: Option<Duration>,
+    /// Response to misbehaviour evidence, the legacy one if not set
+    evidence_policy: Option<EvidencePolicy>,
+    /// Keep the validator set if membership query fails on the epoch boundary
+    keep_validators_on_membership_failure: bool,
+    /// Skip rewards payout if validator group drifted from the validator set
+    skip_payout_on_group_drift: bool,
+    require_key_proof: bool,
+    /// Validators added to membership by valset on instantiation
+    genesis_validators: Vec<GenesisValidator>,
 }
 
 impl SuiteBuilder {
@@ -177,6 +217,11 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_extra_epoch_reward(mut self, reward: Coin) -> Self {
+        self.extra_epoch_rewards.push(reward);
+        self
+    }
+
     pub fn with_distribution(
         mut self,
         reward_ratio: Decimal,
@@ -219,6 +264,65 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_preauths_penalty_hooks(mut self, preauths: u64) -> Self {
+        self.preauths_penalty_hooks = preauths;
+        self
+    }
+
+    pub fn with_probation_epochs(mut self, epochs: u64) -> Self {
+        self.probation_epochs = epochs;
+        self
+    }
+
+    pub fn with_exit_cooldown(mut self, seconds: u64) -> Self {
+        self.exit_cooldown = Some(Duration::new(seconds));
+        self
+    }
+
+    pub fn with_evidence_policy(mut self, policy: EvidencePolicy) -> Self {
+        self.evidence_policy = Some(policy);
+        self
+    }
+
+    pub fn with_keep_validators_on_membership_failure(mut self) -> Self {
+        self.keep_validators_on_membership_failure = true;
+        self
+    }
+
+    pub fn with_skip_payout_on_group_drift(mut self) -> Self {
+        self.skip_payout_on_group_drift = true;
+        self
+    }
+
+    pub fn with_scaling(mut self, scaling: u32) -> Self {
+        self.scaling = Some(scaling);
+        self
+    }
+
+    pub fn with_scaling_curve(mut self, curve: ScalingCurve) -> Self {
+        self.scaling_curve = Some(curve);
+        self
+    }
+
+    pub fn with_require_key_proof(mut self) -> Self {
+        self.require_key_proof = true;
+        self
+    }
+
+    /// Works only with tg4_engagement membership, which admin is handed over to valset.
+    pub fn with_genesis_validators(mut self, validators: &[(&str, u64)]) -> Self {
+        self.genesis_validators = validators
+            .iter()
+            .map(|(addr, points)| GenesisValidator {
+                operator: (*addr).to_owned(),
+                validator_pubkey: mock_pubkey(addr.as_bytes()),
+                points: *points,
+                metadata: None,
+            })
+            .collect();
+        self
+    }
+
     pub fn build(mut self) -> Suite {
         let admin = Addr::unchecked("admin");
         let denom = self.epoch_reward.denom.clone();
@@ -253,6 +357,9 @@ impl SuiteBuilder {
                         preauths_slashing: 1,
                         halflife: None,
                         denom: denom.clone(),
+                        min_distribution: Uint128::zero(),
+                        distribution_interval: None,
+                        withdrawal_stream_period: None,
                     },
                     &[],
                     "group",
@@ -277,6 +384,15 @@ impl SuiteBuilder {
                         preauths_hooks: 0,
                         preauths_slashing: 1,
                         auto_return_limit: 0,
+                        unbond_veto_period: None,
+                        max_bond_per_address: None,
+                        max_total_bond: None,
+                        exit_fee: None,
+                        lockup_tiers: vec![],
+                        average_stake_period: None,
+                        unbonding_interest: None,
+                        denom_metadata: None,
+                        max_pause_duration: None,
                     },
                     &[],
                     "group",
@@ -321,6 +437,9 @@ impl SuiteBuilder {
                         preauths_slashing: 1,
                         halflife: config.halflife,
                         denom: denom.clone(),
+                        min_distribution: Uint128::zero(),
+                        distribution_interval: None,
+                        withdrawal_stream_period: None,
                     },
                     &[],
                     "distribution",
@@ -330,6 +449,21 @@ impl SuiteBuilder {
             })
             .collect();
 
+        if !self.genesis_validators.is_empty() {
+            // valset seeds membership with genesis validators, so it has to be its admin;
+            // it is going to be the next instantiated contract
+            let valset = format!("contract{}", 1 + distribution_contracts.len());
+            app.execute_contract(
+                admin.clone(),
+                membership.clone(),
+                &tg4_engagement::ExecuteMsg::UpdateAdmin {
+                    admin: Some(valset),
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
         let valset_id = app.store_code(contract_valset());
         let distribution_contract_instantiation_info = distribution_contracts
             .iter()
@@ -351,8 +485,11 @@ impl SuiteBuilder {
                     max_validators: self.max_validators,
                     epoch_length: self.epoch_length,
                     epoch_reward: self.epoch_reward,
+                    extra_epoch_rewards: self.extra_epoch_rewards.clone(),
                     initial_keys: operators.clone(),
+                    genesis_validators: self.genesis_validators.clone(),
                     scaling: self.scaling,
+                    scaling_curve: self.scaling_curve,
                     fee_percentage: self.fee_percentage,
                     auto_unjail: self.auto_unjail,
                     double_sign_slash_ratio: self.double_sign_slash_ratio,
@@ -364,6 +501,16 @@ impl SuiteBuilder {
                     offline_jail_duration: self
                         .verify_validators
                         .unwrap_or_else(|| Duration::new(0)),
+                    preauths_penalty_hooks: self.preauths_penalty_hooks,
+                    probation_epochs: self.probation_epochs,
+                    pubkey_types: None,
+                    ibc_distribution: None,
+                    exit_cooldown: self.exit_cooldown,
+                    evidence_policy: self.evidence_policy,
+                    keep_validators_on_membership_failure: self
+                        .keep_validators_on_membership_failure,
+                    skip_payout_on_group_drift: self.skip_payout_on_group_drift,
+                    require_key_proof: self.require_key_proof,
                 },
                 &[],
                 "valset",
@@ -542,6 +689,17 @@ impl Suite {
         )
     }
 
+    pub fn untombstone(&mut self, executor: &str, operator: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::Untombstone {
+                operator: operator.to_owned(),
+            },
+            &[],
+        )
+    }
+
     pub fn register_validator_key(
         &mut self,
         executor: &str,
@@ -551,11 +709,106 @@ impl Suite {
         self.app.execute_contract(
             Addr::unchecked(executor),
             self.valset.clone(),
-            &ExecuteMsg::RegisterValidatorKey { pubkey, metadata },
+            &ExecuteMsg::RegisterValidatorKey {
+                pubkey,
+                metadata,
+                proof: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn register_validator_key_with_proof(
+        &mut self,
+        executor: &str,
+        pubkey: Pubkey,
+        metadata: ValidatorMetadata,
+        proof: Binary,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::RegisterValidatorKey {
+                pubkey,
+                metadata,
+                proof: Some(proof),
+            },
+            &[],
+        )
+    }
+
+    pub fn rotate_validator_key(
+        &mut self,
+        executor: &str,
+        pubkey: Pubkey,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::RotateValidatorKey {
+                pubkey,
+                proof: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn rotate_validator_key_with_proof(
+        &mut self,
+        executor: &str,
+        pubkey: Pubkey,
+        proof: Binary,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::RotateValidatorKey {
+                pubkey,
+                proof: Some(proof),
+            },
+            &[],
+        )
+    }
+
+    pub fn begin_exit(&mut self, executor: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::BeginExit {},
+            &[],
+        )
+    }
+
+    pub fn purge_operator(&mut self, executor: &str, operator: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::PurgeOperator {
+                operator: operator.to_owned(),
+            },
             &[],
         )
     }
 
+    pub fn exit_status(&self, operator: &str) -> StdResult<ExitStatusResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.valset.clone(),
+            &QueryMsg::ExitStatus {
+                operator: operator.to_owned(),
+            },
+        )
+    }
+
+    pub fn key_rotation(&self, operator: &str) -> StdResult<Option<KeyRotationInfo>> {
+        let resp: KeyRotationResponse = self.app.wrap().query_wasm_smart(
+            self.valset.clone(),
+            &QueryMsg::KeyRotation {
+                operator: operator.to_owned(),
+            },
+        )?;
+        Ok(resp.rotation)
+    }
+
     pub fn update_metadata(
         &mut self,
         executor: &str,
@@ -569,6 +822,126 @@ impl Suite {
         )
     }
 
+    pub fn update_operator_metadata(
+        &mut self,
+        executor: &str,
+        operator: &str,
+        metadata: &ValidatorMetadata,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::UpdateOperatorMetadata {
+                operator: operator.to_owned(),
+                metadata: metadata.clone(),
+            },
+            &[],
+        )
+    }
+
+    pub fn add_delegate(&mut self, executor: &str, delegate: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::AddDelegate {
+                delegate: delegate.to_owned(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_payout_address(
+        &mut self,
+        executor: &str,
+        payout: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::SetPayoutAddress {
+                payout: payout.map(str::to_owned),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_ibc_distribution(
+        &mut self,
+        executor: &str,
+        distribution: Option<IbcDistribution>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::SetIbcDistribution { distribution },
+            &[],
+        )
+    }
+
+    pub fn ibc_retry_queue(&self) -> StdResult<Vec<Coin>> {
+        let resp: IbcRetryQueueResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.valset.clone(), &QueryMsg::IbcRetryQueue {})?;
+        Ok(resp.transfers)
+    }
+
+    pub fn remove_delegate(&mut self, executor: &str, delegate: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::RemoveDelegate {
+                delegate: delegate.to_owned(),
+            },
+            &[],
+        )
+    }
+
+    pub fn list_delegates(&self, operator: &str) -> StdResult<Vec<String>> {
+        let resp: DelegatesResponse = self.app.wrap().query_wasm_smart(
+            self.valset.clone(),
+            &QueryMsg::ListDelegates {
+                operator: operator.to_owned(),
+            },
+        )?;
+        Ok(resp.delegates)
+    }
+
+    pub fn migration_history(&self) -> StdResult<MigrationHistoryResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.valset.clone(), &QueryMsg::MigrationHistory {})
+    }
+
+    pub fn metrics(&self) -> StdResult<MetricsResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.valset.clone(), &QueryMsg::Metrics {})
+    }
+
+    pub fn simulate_rewards(
+        &self,
+        validators: &[(&str, u64)],
+        epochs: u64,
+    ) -> StdResult<SimulateRewardsResponse> {
+        let validators = validators
+            .iter()
+            .map(|(operator, power)| (operator.to_string(), *power))
+            .collect();
+        self.app.wrap().query_wasm_smart(
+            self.valset.clone(),
+            &QueryMsg::SimulateRewards { validators, epochs },
+        )
+    }
+
+    pub fn verify_invariants(&self) -> StdResult<Vec<String>> {
+        let resp: InvariantsResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.valset.clone(), &QueryMsg::VerifyInvariants {})?;
+        Ok(resp.violations)
+    }
+
     pub fn update_admin(
         &mut self,
         executor: &str,
@@ -598,13 +971,150 @@ impl Suite {
                 min_points: min_points.into(),
                 max_validators: max_validators.into(),
                 scaling: None,
+                scaling_curve: None,
                 epoch_reward: None,
+                extra_epoch_rewards: None,
                 fee_percentage: None,
                 auto_unjail: None,
                 double_sign_slash_ratio: None,
                 distribution_contracts: distribution_contracts.into(),
                 verify_validators: None,
                 offline_jail_duration: None,
+                probation_epochs: None,
+                pubkey_types: None,
+                evidence_policy: None,
+                keep_validators_on_membership_failure: None,
+                skip_payout_on_group_drift: None,
+                require_key_proof: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_extra_epoch_rewards(
+        &mut self,
+        executor: &str,
+        extra_epoch_rewards: Vec<Coin>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::UpdateConfig {
+                min_points: None,
+                max_validators: None,
+                scaling: None,
+                scaling_curve: None,
+                epoch_reward: None,
+                extra_epoch_rewards: Some(extra_epoch_rewards),
+                fee_percentage: None,
+                auto_unjail: None,
+                double_sign_slash_ratio: None,
+                distribution_contracts: None,
+                verify_validators: None,
+                offline_jail_duration: None,
+                probation_epochs: None,
+                pubkey_types: None,
+                evidence_policy: None,
+                keep_validators_on_membership_failure: None,
+                skip_payout_on_group_drift: None,
+                require_key_proof: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_evidence_policy(
+        &mut self,
+        executor: &str,
+        evidence_policy: EvidencePolicy,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::UpdateConfig {
+                min_points: None,
+                max_validators: None,
+                scaling: None,
+                scaling_curve: None,
+                epoch_reward: None,
+                extra_epoch_rewards: None,
+                fee_percentage: None,
+                auto_unjail: None,
+                double_sign_slash_ratio: None,
+                distribution_contracts: None,
+                verify_validators: None,
+                offline_jail_duration: None,
+                probation_epochs: None,
+                pubkey_types: None,
+                evidence_policy: Some(evidence_policy),
+                keep_validators_on_membership_failure: None,
+                skip_payout_on_group_drift: None,
+                require_key_proof: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_scaling(
+        &mut self,
+        executor: &str,
+        scaling: impl Into<Option<u32>>,
+        scaling_curve: impl Into<Option<ScalingCurve>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::UpdateConfig {
+                min_points: None,
+                max_validators: None,
+                scaling: scaling.into(),
+                scaling_curve: scaling_curve.into(),
+                epoch_reward: None,
+                extra_epoch_rewards: None,
+                fee_percentage: None,
+                auto_unjail: None,
+                double_sign_slash_ratio: None,
+                distribution_contracts: None,
+                verify_validators: None,
+                offline_jail_duration: None,
+                probation_epochs: None,
+                pubkey_types: None,
+                evidence_policy: None,
+                keep_validators_on_membership_failure: None,
+                skip_payout_on_group_drift: None,
+                require_key_proof: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_pubkey_types(
+        &mut self,
+        executor: &str,
+        pubkey_types: Vec<PubkeyType>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::UpdateConfig {
+                min_points: None,
+                max_validators: None,
+                scaling: None,
+                scaling_curve: None,
+                epoch_reward: None,
+                extra_epoch_rewards: None,
+                fee_percentage: None,
+                auto_unjail: None,
+                double_sign_slash_ratio: None,
+                distribution_contracts: None,
+                verify_validators: None,
+                offline_jail_duration: None,
+                probation_epochs: None,
+                pubkey_types: Some(pubkey_types),
+                evidence_policy: None,
+                keep_validators_on_membership_failure: None,
+                skip_payout_on_group_drift: None,
+                require_key_proof: None,
             },
             &[],
         )
@@ -662,6 +1172,30 @@ impl Suite {
         )
     }
 
+    /// Updates the validator group bypassing valset, so it drifts from the validator set
+    pub fn drift_validator_group(
+        &mut self,
+        add: &[(&str, u64)],
+        remove: &[&str],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.valset.clone(),
+            self.validator_group.clone(),
+            &tg4_engagement::msg::ExecuteMsg::UpdateMembers {
+                add: add
+                    .iter()
+                    .map(|(addr, points)| Member {
+                        addr: (*addr).to_owned(),
+                        points: *points,
+                        start_height: None,
+                    })
+                    .collect(),
+                remove: remove.iter().map(|addr| (*addr).to_owned()).collect(),
+            },
+            &[],
+        )
+    }
+
     pub fn mint_rewards(&mut self, amount: u128) -> AnyResult<AppResponse> {
         let block_info = self.app.block_info();
         let denom = self.denom.clone();
@@ -682,6 +1216,54 @@ impl Suite {
         })
     }
 
+    /// Mints tokens of any denom to the valset contract, eg. to fund extra epoch rewards
+    pub fn fund_valset(&mut self, funds: Coin) -> AnyResult<AppResponse> {
+        let block_info = self.app.block_info();
+        let admin = Addr::unchecked(&self.admin);
+        let recipient = self.valset.to_string();
+        self.app.init_modules(move |router, api, storage| {
+            router.execute(
+                api,
+                storage,
+                &block_info,
+                admin,
+                CosmosMsg::Custom(TgradeMsg::MintTokens {
+                    denom: funds.denom,
+                    amount: funds.amount,
+                    recipient,
+                }),
+            )
+        })
+    }
+
+    pub fn add_penalty_hook(&mut self, executor: &str, addr: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::AddPenaltyHook {
+                addr: addr.to_owned(),
+            },
+            &[],
+        )
+    }
+
+    pub fn remove_penalty_hook(&mut self, executor: &str, addr: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.valset.clone(),
+            &ExecuteMsg::RemovePenaltyHook {
+                addr: addr.to_owned(),
+            },
+            &[],
+        )
+    }
+
+    pub fn list_penalty_hooks(&self) -> StdResult<PenaltyHooksResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.valset.clone(), &QueryMsg::ListPenaltyHooks {})
+    }
+
     pub fn query_admin(&self) -> StdResult<Option<String>> {
         let resp: AdminResponse = self
             .app
@@ -733,6 +1315,46 @@ impl Suite {
             &QueryMsg::ListJailedValidators {
                 start_after: start_after.into(),
                 limit: limit.into(),
+                reason: None,
+                end: None,
+            },
+        )?;
+
+        Ok(resp.validators)
+    }
+
+    pub fn list_jailed_validators_filtered(
+        &self,
+        reason: impl Into<Option<JailReason>>,
+        end: impl Into<Option<JailEndType>>,
+    ) -> StdResult<Vec<OperatorResponse>> {
+        let resp: ListValidatorResponse = self.app.wrap().query_wasm_smart(
+            self.valset.clone(),
+            &QueryMsg::ListJailedValidators {
+                start_after: None,
+                limit: None,
+                reason: reason.into(),
+                end: end.into(),
+            },
+        )?;
+
+        Ok(resp.validators)
+    }
+
+    pub fn list_jailed_validators_by_end(
+        &self,
+        start_after: impl Into<Option<String>>,
+        limit: impl Into<Option<u32>>,
+        reason: impl Into<Option<JailReason>>,
+        end: impl Into<Option<JailEndType>>,
+    ) -> StdResult<Vec<OperatorResponse>> {
+        let resp: ListValidatorResponse = self.app.wrap().query_wasm_smart(
+            self.valset.clone(),
+            &QueryMsg::ListJailedValidatorsByEnd {
+                start_after: start_after.into(),
+                limit: limit.into(),
+                reason: reason.into(),
+                end: end.into(),
             },
         )?;
 
@@ -769,6 +1391,16 @@ impl Suite {
         Ok(amount.into())
     }
 
+    /// Shortcut for querying balance of any denom
+    pub fn balance(&self, owner: &Addr, denom: &str) -> StdResult<u128> {
+        let amount = self.app.wrap().query_balance(owner, denom)?.amount;
+        Ok(amount.into())
+    }
+
+    pub fn distribution_contract(&self, idx: usize) -> Addr {
+        self.distribution_contracts[idx].clone()
+    }
+
     /// Queries valset contract for its config
     pub fn config(&self) -> StdResult<Config> {
         self.app
@@ -793,6 +1425,15 @@ impl Suite {
         )
     }
 
+    pub fn operational_metadata(&self, addr: &str) -> StdResult<OperationalMetadataResponse> {
+        self.app.wrap().query_wasm_smart(
+            &self.valset,
+            &QueryMsg::OperationalMetadata {
+                operator: addr.to_owned(),
+            },
+        )
+    }
+
     /// Bonds some tokens.
     /// Only works when the membership contract is tg4_stake. Will error otherwise.
     pub fn bond(&mut self, addr: &Addr, stake: &[Coin]) -> AnyResult<AppResponse> {
@@ -812,7 +1453,7 @@ impl Suite {
         self.app.execute_contract(
             addr.clone(),
             self.membership.clone(),
-            &tg4_stake::msg::ExecuteMsg::Unbond { tokens },
+            &tg4_stake::msg::ExecuteMsg::Unbond { tokens, tag: None },
             &[],
         )
     }
@@ -828,12 +1469,30 @@ impl Suite {
         )
     }
 
+    /// Migrates membership contract to one failing on every query
+    pub fn break_membership(&mut self) -> AnyResult<AppResponse> {
+        let code_id = self.app.store_code(contract_broken_membership());
+        self.app.migrate_contract(
+            Addr::unchecked(&self.admin),
+            self.membership.clone(),
+            &Empty {},
+            code_id,
+        )
+    }
+
+    /// Migrates validator group contract to one failing on every query
+    pub fn break_validator_group(&mut self) -> AnyResult<AppResponse> {
+        let code_id = self.app.store_code(contract_broken_membership());
+        self.app.migrate_contract(
+            Addr::unchecked(&self.admin),
+            self.validator_group.clone(),
+            &Empty {},
+            code_id,
+        )
+    }
+
     pub fn set_votes(&mut self, votes: &[ValidatorVote]) -> AnyResult<()> {
-        Ok(self
-            .app
-            .init_modules(|router, _api, storage| -> StdResult<()> {
-                router.custom.set_votes(storage, votes.to_vec())
-            })?)
+        self.app.set_validator_votes(votes.to_vec())
     }
 
     pub fn export(&mut self) -> AnyResult<ValsetState> {
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Decimal};
+use cosmwasm_std::{coin, Addr, Decimal};
 use cw_controllers::AdminError;
 
 use crate::error::ContractError;
@@ -90,3 +90,28 @@ fn non_admin_cannot_update_cfg() {
         err.downcast().unwrap(),
     );
 }
+
+#[test]
+fn update_extra_epoch_rewards() {
+    let mut suite = SuiteBuilder::new()
+        .with_epoch_reward(coin(100, "usdc"))
+        .build();
+    let admin = suite.admin().to_string();
+
+    suite
+        .update_extra_epoch_rewards(&admin, vec![coin(10, "ibc/atom")])
+        .unwrap();
+    let cfg = suite.config().unwrap();
+    assert_eq!(cfg.extra_epoch_rewards, vec![coin(10, "ibc/atom")]);
+
+    // Extra reward cannot be paid in the epoch reward denom
+    let err = suite
+        .update_extra_epoch_rewards(&admin, vec![coin(10, "usdc")])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidExtraReward("usdc".to_owned()),
+        err.downcast().unwrap(),
+    );
+    let cfg = suite.config().unwrap();
+    assert_eq!(cfg.extra_epoch_rewards, vec![coin(10, "ibc/atom")]);
+}
@@ -1,6 +1,7 @@
 use std::convert::TryInto;
 
 use crate::contract::MISSED_BLOCKS;
+use crate::msg::JailReason;
 use cosmwasm_std::Binary;
 use tg_bindings::{Ed25519Pubkey, ToAddress, ValidatorVote};
 
@@ -118,7 +119,10 @@ fn verify_validators_jailing() {
     let info1 = suite.validator(members[0]).unwrap().validator.unwrap();
     let info2 = suite.validator(members[1]).unwrap().validator.unwrap();
     assert!(info1.jailed_until.is_none());
-    assert!(info2.jailed_until.is_some());
+    assert_eq!(
+        info2.jailed_until.unwrap().reason,
+        Some(JailReason::Offline)
+    );
     assert!(!info2.active_validator);
 }
 
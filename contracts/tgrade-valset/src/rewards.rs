@@ -1,11 +1,23 @@
-use crate::msg::{DistributionMsg, RewardsDistribution};
-use crate::state::Config;
+use crate::contract::IBC_TRANSFER_REPLY_ID;
+use crate::msg::{
+    DistributionContractPayout, DistributionMsg, RewardsDistribution, SimulateRewardsResponse,
+    ValidatorPayout,
+};
+use crate::state::{
+    add_coins, Config, PayoutTotals, IBC_PENDING_TRANSFERS, IBC_RETRY_QUEUE, LAST_PAYOUT,
+};
 use cosmwasm_std::{
-    coins, to_binary, Coin, CustomQuery, DepsMut, Env, StdResult, SubMsg, Uint128, WasmMsg,
+    coin, to_binary, Coin, CustomQuery, Deps, DepsMut, Env, IbcMsg, IbcTimeout, StdResult, SubMsg,
+    Uint128, WasmMsg,
 };
+use std::cmp::min;
+use tg4::Tg4Contract;
 use tg_bindings::TgradeMsg;
 
-/// Ensure you pass in non-empty pay-validators, it will panic if total validator points is 0
+/// If total validator points is 0 (eg. all validators are on probation), the validators part of
+/// the reward is kept in the contract, and paid out together with the next epoch reward.
+/// IBC transfers which failed to be dispatched are retried here, their funds are not counted as
+/// fees.
 /// This handles all deps and calls into pure functions
 pub fn pay_block_rewards<Q: CustomQuery>(
     deps: DepsMut<Q>,
@@ -13,63 +25,270 @@ pub fn pay_block_rewards<Q: CustomQuery>(
     pay_epochs: u64,
     config: &Config,
 ) -> StdResult<Vec<SubMsg<TgradeMsg>>> {
-    // calculate the desired block reward
-    let mut block_reward = config.epoch_reward.clone();
-    block_reward.amount = Uint128::new(block_reward.amount.u128() * (pay_epochs as u128));
-    let denom = block_reward.denom.clone();
-
-    // query existing balance
-    let balances = deps.querier.query_all_balances(&env.contract.address)?;
-    let fees_amount = get_fees_amount(&balances, &denom);
-
-    let amount = block_reward
-        .amount
-        .saturating_sub(config.fee_percentage * fees_amount);
-    block_reward.amount = amount + fees_amount;
+    // funds queued for retry are part of the balance, but not available for rewards
+    let retry = match config.ibc_distribution {
+        Some(_) => IBC_RETRY_QUEUE.may_load(deps.storage)?.unwrap_or_default(),
+        None => vec![],
+    };
+    let balances = available_balances(deps.as_ref(), &env, &retry)?;
+    let validator_points =
+        Tg4Contract::new(config.validator_group.clone()).total_points(&deps.querier)?;
 
-    let mut reward_pool = block_reward.amount;
+    let split = split_rewards(config, pay_epochs, &balances, validator_points);
+    let denom = &config.epoch_reward.denom;
 
     // create the distribution messages
     let mut messages = vec![];
 
     // create a minting action if needed (and do this first)
-    if amount > Uint128::zero() {
+    if split.minted > Uint128::zero() {
         let minting = SubMsg::new(TgradeMsg::MintTokens {
-            denom,
-            amount,
-            recipient: env.contract.address.into(),
+            denom: denom.clone(),
+            amount: split.minted,
+            recipient: env.contract.address.to_string(),
         });
         messages.push(minting);
     }
 
-    for contract in &config.distribution_contracts {
-        let reward = block_reward.amount * contract.ratio;
-        if reward > Uint128::zero() {
-            reward_pool -= reward;
+    // IBC part is sent first, together with transfers to retry
+    let mut ibc = split.ibc;
+    if let Some(ibc_distribution) = &config.ibc_distribution {
+        add_coins(&mut ibc, retry);
+        IBC_RETRY_QUEUE.remove(deps.storage);
+
+        let timeout = IbcTimeout::with_timestamp(
+            env.block
+                .time
+                .plus_seconds(ibc_distribution.timeout.seconds()),
+        );
+        for amount in &ibc {
+            let transfer = IbcMsg::Transfer {
+                channel_id: ibc_distribution.channel_id.clone(),
+                to_address: ibc_distribution.remote_address.clone(),
+                amount: amount.clone(),
+                timeout: timeout.clone(),
+            };
+            messages.push(SubMsg::reply_always(transfer, IBC_TRANSFER_REPLY_ID));
+        }
+        IBC_PENDING_TRANSFERS.save(deps.storage, &ibc)?;
+    }
+
+    for (contract, funds) in config
+        .distribution_contracts
+        .iter()
+        .zip(split.distributions)
+    {
+        if !funds.is_empty() {
             messages.push(SubMsg::new(WasmMsg::Execute {
                 contract_addr: contract.contract.to_string(),
                 msg: to_binary(&DistributionMsg::DistributeRewards {})?,
-                funds: coins(reward.into(), &block_reward.denom),
+                funds,
             }));
         }
     }
 
-    // After rewarding all non-validators, the remainder goes to validators.
-    if reward_pool > Uint128::zero() {
+    let validators = get_amount(&split.validators, denom);
+    if !split.validators.is_empty() {
         messages.push(SubMsg::new(WasmMsg::Execute {
             contract_addr: config.validator_group.to_string(),
             msg: to_binary(&RewardsDistribution::DistributeRewards {})?,
-            funds: coins(reward_pool.into(), &block_reward.denom),
+            funds: split.validators,
         }));
     }
 
+    let totals = PayoutTotals {
+        height: env.block.height,
+        epochs: pay_epochs,
+        minted: split.minted,
+        fees: split.fees,
+        distribution: split.distribution,
+        validators,
+        extra: split.extra,
+        ibc,
+    };
+    LAST_PAYOUT.save(deps.storage, &totals)?;
+
     Ok(messages)
 }
 
-fn get_fees_amount(coins: &[Coin], denom: &str) -> Uint128 {
+/// Computes rewards which would be paid after `epochs` epochs if the active validators had the
+/// given powers. Validator rewards are split proportionally to their power, rounding down.
+/// Fees and extra rewards are taken from the current contract balance.
+pub fn simulate_rewards<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: &Env,
+    config: &Config,
+    validators: Vec<(String, u64)>,
+    epochs: u64,
+) -> StdResult<SimulateRewardsResponse> {
+    let retry = match config.ibc_distribution {
+        Some(_) => IBC_RETRY_QUEUE.may_load(deps.storage)?.unwrap_or_default(),
+        None => vec![],
+    };
+    let balances = available_balances(deps, env, &retry)?;
+    let validator_points = validators.iter().map(|(_, power)| power).sum();
+
+    let split = split_rewards(config, epochs, &balances, validator_points);
+
+    let distribution_contracts = config
+        .distribution_contracts
+        .iter()
+        .zip(split.distributions)
+        .map(|(contract, rewards)| DistributionContractPayout {
+            contract: contract.contract.clone(),
+            rewards,
+        })
+        .collect();
+
+    let validator_rewards = split.validators;
+    let validators = validators
+        .into_iter()
+        .map(|(operator, power)| {
+            let operator = deps.api.addr_validate(&operator)?;
+            let rewards = validator_rewards
+                .iter()
+                .map(|reward| {
+                    let amount = reward.amount.multiply_ratio(power, validator_points);
+                    coin(amount.u128(), &reward.denom)
+                })
+                .filter(|reward| !reward.amount.is_zero())
+                .collect();
+            Ok(ValidatorPayout { operator, rewards })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(SimulateRewardsResponse {
+        minted: split.minted,
+        fees: split.fees,
+        extra: split.extra,
+        ibc: split.ibc,
+        distribution_contracts,
+        validators,
+    })
+}
+
+/// Contract balance without funds queued for IBC retry
+fn available_balances<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: &Env,
+    retry: &[Coin],
+) -> StdResult<Vec<Coin>> {
+    let mut balances = deps.querier.query_all_balances(&env.contract.address)?;
+    for balance in &mut balances {
+        balance.amount = balance
+            .amount
+            .saturating_sub(get_amount(retry, &balance.denom));
+    }
+    Ok(balances)
+}
+
+/// Reward of a single payout split between its recipients
+#[derive(Debug, PartialEq, Eq)]
+pub struct RewardSplit {
+    /// Reward tokens to be minted
+    pub minted: Uint128,
+    /// Collected fees added to the reward
+    pub fees: Uint128,
+    /// Extra rewards paid from the contract balance
+    pub extra: Vec<Coin>,
+    /// Part of the rewards sent over IBC, without transfers to retry
+    pub ibc: Vec<Coin>,
+    /// Funds sent to every distribution contract, in the config order
+    pub distributions: Vec<Vec<Coin>>,
+    /// Part of the epoch reward sent to distribution contracts
+    pub distribution: Uint128,
+    /// Funds sent to the validator group, empty if it has no points
+    pub validators: Vec<Coin>,
+}
+
+/// Splits the reward for `pay_epochs` epochs, given the available contract balance and total
+/// points of the validator group
+pub fn split_rewards(
+    config: &Config,
+    pay_epochs: u64,
+    balances: &[Coin],
+    validator_points: u64,
+) -> RewardSplit {
+    // calculate the desired block reward
+    let mut block_reward = config.epoch_reward.clone();
+    block_reward.amount = Uint128::new(block_reward.amount.u128() * (pay_epochs as u128));
+
+    let fees = get_amount(balances, &block_reward.denom);
+    let minted = block_reward
+        .amount
+        .saturating_sub(config.fee_percentage * fees);
+    block_reward.amount = minted + fees;
+
+    // extra rewards are not minted, but paid from the contract balance up to what is available
+    let extra: Vec<Coin> = config
+        .extra_epoch_rewards
+        .iter()
+        .filter_map(|reward| {
+            let budget = Uint128::new(reward.amount.u128() * (pay_epochs as u128));
+            let paid = min(budget, get_amount(balances, &reward.denom));
+            (!paid.is_zero()).then(|| coin(paid.u128(), &reward.denom))
+        })
+        .collect();
+
+    // every distribution contract gets its ratio of each reward coin
+    let rewards: Vec<Coin> = std::iter::once(block_reward.clone())
+        .chain(extra.iter().cloned())
+        .collect();
+    let mut reward_pool = rewards.clone();
+
+    let mut ibc = vec![];
+    if let Some(ibc_distribution) = &config.ibc_distribution {
+        for (reward, pool) in rewards.iter().zip(reward_pool.iter_mut()) {
+            let part = reward.amount * ibc_distribution.ratio;
+            if part > Uint128::zero() {
+                pool.amount -= part;
+                ibc.push(coin(part.u128(), &reward.denom));
+            }
+        }
+    }
+
+    let mut distributions = vec![];
+    let mut distribution = Uint128::zero();
+    for contract in &config.distribution_contracts {
+        let mut funds = vec![];
+        for (reward, pool) in rewards.iter().zip(reward_pool.iter_mut()) {
+            let part = reward.amount * contract.ratio;
+            if part > Uint128::zero() {
+                pool.amount -= part;
+                funds.push(coin(part.u128(), &reward.denom));
+            }
+        }
+        distribution += block_reward.amount * contract.ratio;
+        distributions.push(sorted(funds));
+    }
+
+    // After rewarding all non-validators, the remainder goes to validators.
+    reward_pool.retain(|reward| reward.amount > Uint128::zero());
+    if validator_points == 0 {
+        reward_pool.clear();
+    }
+
+    RewardSplit {
+        minted,
+        fees,
+        extra,
+        ibc,
+        distributions,
+        distribution,
+        validators: sorted(reward_pool),
+    }
+}
+
+fn get_amount(coins: &[Coin], denom: &str) -> Uint128 {
     coins
         .iter()
         .find(|coin| coin.denom == denom)
         .map(|coin| coin.amount)
         .unwrap_or_else(Uint128::zero)
 }
+
+/// Funds sent with a message have to be sorted by denom
+fn sorted(mut funds: Vec<Coin>) -> Vec<Coin> {
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    funds
+}
@@ -3,15 +3,18 @@ use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
 use cosmwasm_std::Order::Ascending;
-use cosmwasm_std::{to_binary, Addr, Coin, Decimal, Deps, DepsMut, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, BlockInfo, Coin, Decimal, Deps, DepsMut, Empty, Response, StdResult,
+    Storage, Timestamp, Uint128,
+};
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 use tg4::Tg4Contract;
-use tg_utils::{Duration, ADMIN};
+use tg_utils::{Duration, Hooks, JailingDuration, MigrationHistory, Preauth, ADMIN};
 
 use crate::error::ContractError;
 use crate::msg::{default_fee_percentage, JailingPeriod, OperatorResponse, ValidatorMetadata};
-use tg_bindings::{Ed25519Pubkey, Pubkey, TgradeMsg, TgradeQuery};
+use tg_bindings::{EvidenceType, Pubkey, PubkeyType, TgradeMsg, TgradeQuery, ValidatedPubkey};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct Config {
@@ -32,6 +35,10 @@ pub struct Config {
     /// (epoch_reward.amount * 86_400 * 30 / epoch_length) is reward tokens to mint each month.
     /// Ensure this is sensible in relation to the total token supply.
     pub epoch_reward: Coin,
+    /// Fixed budget of other tokens paid out each epoch from this contract balance, in addition to
+    /// `epoch_reward`.
+    #[serde(default)]
+    pub extra_epoch_rewards: Vec<Coin>,
     /// Percentage of total accumulated fees which is subtracted from tokens minted as a rewards.
     /// 50% as default. To disable this feature just set it to 0 (which effectively means that fees
     /// doesn't affect the per epoch reward).
@@ -63,6 +70,187 @@ pub struct Config {
     /// The duration to jail a validator for in case they don't sign any blocks for a period of time.
     /// After the jailing period, they will be jailed again if not signing, ad infinitum.
     pub offline_jail_duration: Duration,
+
+    /// Number of epochs validators spend on probation after becoming active for the first time.
+    /// On probation they are members of `validator_group` with no points, so they receive no
+    /// rewards. 0 disables probation.
+    #[serde(default)]
+    pub probation_epochs: u64,
+
+    /// Types of Tendermint pubkeys operators can register
+    #[serde(default = "default_pubkey_types")]
+    pub pubkey_types: Vec<PubkeyType>,
+
+    /// Part of the reward sent over IBC to an address on another chain. Its ratio is taken
+    /// together with `distribution_contracts` ratios, before the validators part.
+    #[serde(default)]
+    pub ibc_distribution: Option<IbcDistribution>,
+
+    /// Time after the exit of an operator is settled, after which its record can be purged.
+    /// If unset, the record can be purged right after the settlement.
+    #[serde(default)]
+    pub exit_cooldown: Option<Duration>,
+
+    /// Response to misbehaviour evidence. If unset, double signing is punished according to
+    /// `double_sign_slash_ratio` with jailing forever.
+    #[serde(default)]
+    pub evidence_policy: Option<EvidencePolicy>,
+
+    /// If set, a failing `membership` query doesn't fail the end block. The previous validator
+    /// set is kept instead, and the failure is counted.
+    #[serde(default)]
+    pub keep_validators_on_membership_failure: bool,
+
+    /// If set, rewards are not paid on epochs when `validator_group` members or their points
+    /// differ from the ones expected after the previous epoch update, until governance fixes
+    /// the drift.
+    #[serde(default)]
+    pub skip_payout_on_group_drift: bool,
+
+    /// If set, registering or rotating a validator key requires a proof of possession of the key
+    #[serde(default)]
+    pub require_key_proof: bool,
+
+    /// Function producing the tendermint validator power from membership points. Replaces
+    /// `scaling` if set.
+    #[serde(default)]
+    pub scaling_curve: Option<ScalingCurve>,
+}
+
+/// Function mapping membership points to the Tendermint validator power. All curves are
+/// non-decreasing, so validators with more points never get less power.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ScalingCurve {
+    /// `points * multiplier`
+    Linear { multiplier: u32 },
+    /// `floor(sqrt(points)) * multiplier`, flattening differences between big members
+    Sqrt { multiplier: u32 },
+    /// `min(points * multiplier, cap)`
+    CappedLinear { multiplier: u32, cap: u64 },
+}
+
+impl ScalingCurve {
+    /// Curve of contracts configured with the constant `scaling` multiplier only
+    pub fn legacy(scaling: Option<u32>) -> Self {
+        ScalingCurve::Linear {
+            multiplier: scaling.unwrap_or(1),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match *self {
+            ScalingCurve::Linear { multiplier }
+            | ScalingCurve::Sqrt { multiplier }
+            | ScalingCurve::CappedLinear { multiplier, .. }
+                if multiplier == 0 =>
+            {
+                Err(ContractError::InvalidScaling {})
+            }
+            ScalingCurve::CappedLinear { cap: 0, .. } => Err(ContractError::InvalidScaling {}),
+            _ => Ok(()),
+        }
+    }
+
+    /// Tendermint validator power of a member with given points
+    pub fn power(&self, points: u64) -> u64 {
+        match *self {
+            ScalingCurve::Linear { multiplier } => points.saturating_mul(multiplier.into()),
+            ScalingCurve::Sqrt { multiplier } => isqrt(points).saturating_mul(multiplier.into()),
+            ScalingCurve::CappedLinear { multiplier, cap } => {
+                points.saturating_mul(multiplier.into()).min(cap)
+            }
+        }
+    }
+}
+
+/// Integer square root, rounded down
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method, starting above the root so the sequence decreases monotonically
+    let mut x = n;
+    let mut y = x / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// How validators are punished for misbehaviour evidence reported by Tendermint
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct EvidencePolicy {
+    /// Portion of the bonded tokens slashed for double signing (duplicate vote evidence)
+    pub duplicate_vote_slash_ratio: Decimal,
+    /// Portion of the bonded tokens slashed for light client attacks. Such evidence is ignored
+    /// if not set.
+    #[serde(default)]
+    pub light_client_attack_slash_ratio: Option<Decimal>,
+    /// How long offenders are jailed
+    pub jail_duration: JailingDuration,
+    /// If set, the first offense of a validator is punished by jailing only, without slashing
+    #[serde(default)]
+    pub jail_only_first_offense: bool,
+}
+
+impl EvidencePolicy {
+    /// Policy of contracts not configuring one: double signing is slashed by
+    /// `double_sign_slash_ratio` and jailed forever, other evidence is ignored
+    pub fn legacy(double_sign_slash_ratio: Decimal) -> Self {
+        Self {
+            duplicate_vote_slash_ratio: double_sign_slash_ratio,
+            light_client_attack_slash_ratio: None,
+            jail_duration: JailingDuration::Forever {},
+            jail_only_first_offense: false,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let mut ratios = std::iter::once(self.duplicate_vote_slash_ratio)
+            .chain(self.light_client_attack_slash_ratio);
+        if ratios.any(|ratio| ratio > Decimal::one()) {
+            return Err(ContractError::InvalidEvidencePolicy {});
+        }
+        Ok(())
+    }
+
+    /// Portion slashed for the evidence type, `None` if the evidence is ignored
+    pub fn slash_ratio(&self, evidence_type: &EvidenceType) -> Option<Decimal> {
+        match evidence_type {
+            EvidenceType::DuplicateVote => Some(self.duplicate_vote_slash_ratio),
+            EvidenceType::LightClientAttack => self.light_client_attack_slash_ratio,
+        }
+    }
+}
+
+impl Config {
+    /// Scaling curve in effect, falling back to the `scaling` multiplier
+    pub fn scaling_curve(&self) -> ScalingCurve {
+        self.scaling_curve
+            .clone()
+            .unwrap_or_else(|| ScalingCurve::legacy(self.scaling))
+    }
+
+    /// Evidence policy in effect, falling back to the `double_sign_slash_ratio` based one
+    pub fn evidence_policy(&self) -> EvidencePolicy {
+        self.evidence_policy
+            .clone()
+            .unwrap_or_else(|| EvidencePolicy::legacy(self.double_sign_slash_ratio))
+    }
+
+    /// Sum of `distribution_contracts` ratios
+    pub fn distribution_ratio(&self) -> Decimal {
+        self.distribution_contracts
+            .iter()
+            .map(|c| c.ratio)
+            .fold(Decimal::zero(), |sum, ratio| sum + ratio)
+    }
+}
+
+pub fn default_pubkey_types() -> Vec<PubkeyType> {
+    vec![PubkeyType::Ed25519]
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -71,6 +259,50 @@ pub struct DistributionContract {
     pub ratio: Decimal,
 }
 
+/// Reward distribution through an ics20 transfer
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct IbcDistribution {
+    /// Channel on this chain the transfer is sent over
+    pub channel_id: String,
+    /// Address on the remote chain receiving the rewards
+    pub remote_address: String,
+    /// The ratio of every reward coin sent
+    pub ratio: Decimal,
+    /// Time after which the transfer times out. Funds of timed out transfers are refunded to this
+    /// contract, and paid out again with the next epoch rewards.
+    pub timeout: Duration,
+}
+
+impl IbcDistribution {
+    /// Validates the fields, and the ratio summed with `distribution_contracts` ratios
+    pub fn validate(&self, distribution_contracts_ratio: Decimal) -> Result<(), ContractError> {
+        if self.channel_id.is_empty() {
+            return Err(ContractError::InvalidIbcDistribution(
+                "empty channel id".to_owned(),
+            ));
+        }
+        if self.remote_address.is_empty() {
+            return Err(ContractError::InvalidIbcDistribution(
+                "empty remote address".to_owned(),
+            ));
+        }
+        if self.ratio.is_zero() {
+            return Err(ContractError::InvalidIbcDistribution(
+                "zero ratio".to_owned(),
+            ));
+        }
+        if self.timeout.seconds() == 0 {
+            return Err(ContractError::InvalidIbcDistribution(
+                "zero timeout".to_owned(),
+            ));
+        }
+        if self.ratio + distribution_contracts_ratio > Decimal::one() {
+            return Err(ContractError::InvalidRewardsRatio {});
+        }
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct EpochInfo {
     /// Number of seconds in one epoch. We update the Tendermint validator set only once per epoch.
@@ -109,6 +341,36 @@ pub const BLOCK_SIGNERS: Map<&[u8], u64> = Map::new("block_signers");
 /// appear in this map, he was never in the validator set.
 pub const VALIDATOR_START_HEIGHT: Map<&Addr, u64> = Map::new("start_height");
 
+/// Map of operators on probation to the epoch their probation ends with. If operator doesn't
+/// appear in this map, he is not on probation.
+pub const PROBATION: Map<&Addr, u64> = Map::new("probation");
+
+/// Number of epochs after the one a key rotation is registered in, the new key is activated with
+pub const KEY_ROTATION_DELAY: u64 = 2;
+
+/// Validator key rotation of an operator. The new key replaces the operator key on the first
+/// epoch boundary reaching `activation_epoch`. Until the boundary after that, both keys are
+/// tracked, so evidence signed with either of them is matched to the operator.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct KeyRotation {
+    pub old_pubkey: ValidatedPubkey,
+    pub new_pubkey: ValidatedPubkey,
+    pub activation_epoch: u64,
+}
+
+impl KeyRotation {
+    pub fn matches(&self, pubkey: &ValidatedPubkey) -> bool {
+        &self.old_pubkey == pubkey || &self.new_pubkey == pubkey
+    }
+}
+
+/// Key rotations in transition, by operator address
+pub const KEY_ROTATIONS: Map<&Addr, KeyRotation> = Map::new("key_rotations");
+
+/// Number of evidence based penalties per operator address, so the first offense can be told
+/// apart (see `EvidencePolicy::jail_only_first_offense`)
+pub const EVIDENCE_OFFENSES: Map<&Addr, u32> = Map::new("evidence_offenses");
+
 /// Map of slashing events per operator address.
 pub const VALIDATOR_SLASHING: Map<&Addr, Vec<ValidatorSlashing>> = Map::new("validator_slashing");
 
@@ -116,14 +378,203 @@ pub const VALIDATOR_SLASHING: Map<&Addr, Vec<ValidatorSlashing>> = Map::new("val
 /// is not jailed
 pub const JAIL: Map<&Addr, JailingPeriod> = Map::new("jail");
 
+/// Jailed operators ordered by the jail end, keyed by `(JailingPeriod::end_key, operator)`.
+/// Kept in sync with `JAIL` by `save_jail` and `remove_jail`.
+pub const JAIL_BY_END: Map<(u64, &Addr), Empty> = Map::new("jail_by_end");
+
+/// Counters maintained on every change, so metrics are available without range scans
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct Counters {
+    /// Number of registered operators
+    pub operators: u64,
+    /// Number of operators jailed for a limited time (including expired, not yet unjailed ones)
+    pub jailed: u64,
+    /// Number of operators jailed forever
+    pub tombstoned: u64,
+}
+
+impl Counters {
+    fn count_jail(&mut self, period: &JailingPeriod) {
+        if period.is_forever() {
+            self.tombstoned += 1;
+        } else {
+            self.jailed += 1;
+        }
+    }
+
+    fn uncount_jail(&mut self, period: &JailingPeriod) {
+        if period.is_forever() {
+            self.tombstoned -= 1;
+        } else {
+            self.jailed -= 1;
+        }
+    }
+}
+
+pub const COUNTERS: Item<Counters> = Item::new("counters");
+
+/// Totals of the last block rewards payout
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PayoutTotals {
+    pub height: u64,
+    /// Number of epochs paid
+    pub epochs: u64,
+    /// Newly minted reward tokens
+    pub minted: Uint128,
+    /// Collected fees added to the reward
+    pub fees: Uint128,
+    /// Part of the reward sent to `distribution_contracts`
+    pub distribution: Uint128,
+    /// Part of the reward sent to validators
+    pub validators: Uint128,
+    /// Extra rewards paid from the contract balance, split the same way as the minted reward
+    #[serde(default)]
+    pub extra: Vec<Coin>,
+    /// Rewards sent over IBC, including retried transfers
+    #[serde(default)]
+    pub ibc: Vec<Coin>,
+}
+
+pub const LAST_PAYOUT: Item<PayoutTotals> = Item::new("last_payout");
+
+/// Hash of `(address, points)` pairs of members `validator_group` is expected to report, sorted
+/// by address. Set on every epoch boundary
+pub const EXPECTED_GROUP_HASH: Item<Binary> = Item::new("expected_group_hash");
+
+/// Number of epochs the validator set was kept because querying `membership` failed
+pub const MEMBERSHIP_QUERY_FAILURES: Item<u64> = Item::new("membership_query_failures");
+
+/// IBC transfers dispatched with the last payout and waiting for their replies, in dispatch order
+pub const IBC_PENDING_TRANSFERS: Item<Vec<Coin>> = Item::new("ibc_pending_transfers");
+/// Funds of IBC transfers which failed to be dispatched. They are kept in the contract balance
+/// and sent with the next payout.
+pub const IBC_RETRY_QUEUE: Item<Vec<Coin>> = Item::new("ibc_retry_queue");
+
+/// Adds coins to the list, merging amounts of the same denom
+pub fn add_coins(coins: &mut Vec<Coin>, added: impl IntoIterator<Item = Coin>) {
+    for coin in added {
+        match coins.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount += coin.amount,
+            None => coins.push(coin),
+        }
+    }
+}
+
+/// Jails the operator, keeping the jail counters up to date
+pub fn save_jail(storage: &mut dyn Storage, addr: &Addr, period: &JailingPeriod) -> StdResult<()> {
+    let mut counters = COUNTERS.load(storage)?;
+    if let Some(old) = JAIL.may_load(storage, addr)? {
+        counters.uncount_jail(&old);
+        JAIL_BY_END.remove(storage, (old.end_key(), addr));
+    }
+    counters.count_jail(period);
+    JAIL.save(storage, addr, period)?;
+    JAIL_BY_END.save(storage, (period.end_key(), addr), &Empty {})?;
+    COUNTERS.save(storage, &counters)
+}
+
+/// Unjails the operator, keeping the jail counters up to date
+pub fn remove_jail(storage: &mut dyn Storage, addr: &Addr) -> StdResult<()> {
+    if let Some(old) = JAIL.may_load(storage, addr)? {
+        let mut counters = COUNTERS.load(storage)?;
+        counters.uncount_jail(&old);
+        JAIL.remove(storage, addr);
+        JAIL_BY_END.remove(storage, (old.end_key(), addr));
+        COUNTERS.save(storage, &counters)?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the jail end index from `JAIL`. Used when jails were stored without the index.
+pub fn reindex_jails(storage: &mut dyn Storage) -> StdResult<()> {
+    let keys = JAIL_BY_END
+        .keys(storage, None, None, Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (end, addr) in keys {
+        JAIL_BY_END.remove(storage, (end, &addr));
+    }
+    let jails = JAIL
+        .range(storage, None, None, Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (addr, period) in jails {
+        JAIL_BY_END.save(storage, (period.end_key(), &addr), &Empty {})?;
+    }
+    Ok(())
+}
+
+/// Recalculates all counters from scratch. Used only when the whole state is (re)initialized,
+/// as it ranges over all operators.
+pub fn reset_counters(storage: &mut dyn Storage) -> StdResult<()> {
+    let counters = recount(storage)?;
+    COUNTERS.save(storage, &counters)
+}
+
+/// Counters recomputed from scratch with range scans
+pub fn recount(storage: &dyn Storage) -> StdResult<Counters> {
+    let mut counters = Counters {
+        operators: operators().keys(storage, None, None, Ascending).count() as u64,
+        ..Counters::default()
+    };
+    for jail in JAIL.range(storage, None, None, Ascending) {
+        let (_, period) = jail?;
+        counters.count_jail(&period);
+    }
+    Ok(counters)
+}
+
+/// Secondary addresses authorized to act on behalf of operators, keyed by `(operator, delegate)`.
+/// Delegates can update the operator metadata and unjail it, but cannot change its validator key.
+pub const OPERATOR_DELEGATES: Map<(&Addr, &Addr), Empty> = Map::new("operator_delegates");
+
+/// Exit of an operator from the validator set
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct OperatorExit {
+    /// Time the exit was requested at
+    pub begun_at: Timestamp,
+    /// Epoch on which the operator was removed from the validator set and its pending rewards
+    /// were paid out. Unset until the next epoch boundary after the exit was requested.
+    pub settled_epoch: Option<u64>,
+    /// Time after which the operator record can be purged, set on settlement
+    pub purgeable_at: Option<Timestamp>,
+}
+
+impl OperatorExit {
+    pub fn is_purgeable(&self, block: &BlockInfo) -> bool {
+        matches!(self.purgeable_at, Some(at) if at <= block.time)
+    }
+}
+
+/// Operators which requested to leave the validator set. They are never selected as validators
+/// again.
+pub const EXITS: Map<&Addr, OperatorExit> = Map::new("exits");
+
+/// Contracts informed about validators being jailed or slashed
+pub const PENALTY_HOOKS: Hooks = Hooks::new("penalty_hooks");
+/// Number of penalty hooks which can be added by anyone
+pub const PREAUTH_PENALTY_HOOKS: Preauth = Preauth::new("preauth_penalty_hooks");
+
+/// Contract migrations together with the migration steps applied by them
+pub const MIGRATIONS: MigrationHistory = MigrationHistory::new("migrations");
+
 /// This stores the info for an operator. Both their Tendermint key as well as
 /// their metadata.
 #[derive(Serialize, Deserialize, Clone, JsonSchema, Debug, PartialEq, Eq)]
 pub struct OperatorInfo {
-    pub pubkey: Ed25519Pubkey,
+    pub pubkey: ValidatedPubkey,
     pub metadata: ValidatorMetadata,
     /// Is this currently an active validator?
     pub active_validator: bool,
+    /// Address receiving the operator share of block rewards. If unset, the operator itself is
+    /// the payout address.
+    #[serde(default)]
+    pub payout: Option<Addr>,
+}
+
+impl OperatorInfo {
+    /// Address which is a member of the `validator_group` on behalf of `operator`
+    pub fn payout_addr(&self, operator: &Addr) -> Addr {
+        self.payout.clone().unwrap_or_else(|| operator.clone())
+    }
 }
 
 /// This defines the stored and returned data for a slashing event.
@@ -139,17 +590,29 @@ pub struct ValidatorSlashing {
 pub fn operators<'a>() -> IndexedMap<'a, &'a Addr, OperatorInfo, OperatorIndexes<'a>> {
     let indexes = OperatorIndexes {
         pubkey: UniqueIndex::new(|d| d.pubkey.to_vec(), "operators__pubkey"),
+        payout: MultiIndex::new(
+            |pk, d| {
+                d.payout
+                    .as_ref()
+                    .map(|payout| payout.as_bytes().to_vec())
+                    .unwrap_or_else(|| pk.to_vec())
+            },
+            "operators",
+            "operators__payout",
+        ),
     };
     IndexedMap::new("operators", indexes)
 }
 
 pub struct OperatorIndexes<'a> {
     pub pubkey: UniqueIndex<'a, Vec<u8>, OperatorInfo>,
+    /// Payout address of the operator (the operator itself if no payout address is set)
+    pub payout: MultiIndex<'a, Vec<u8>, OperatorInfo, &'a Addr>,
 }
 
 impl<'a> IndexList<OperatorInfo> for OperatorIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<OperatorInfo>> + '_> {
-        let v: Vec<&dyn Index<OperatorInfo>> = vec![&self.pubkey];
+        let v: Vec<&dyn Index<OperatorInfo>> = vec![&self.pubkey, &self.payout];
         Box::new(v.into_iter())
     }
 }
@@ -201,10 +664,12 @@ pub fn export(deps: Deps<TgradeQuery>) -> Result<Response<TgradeMsg>, ContractEr
         .map(|r| {
             let (operator, info) = r?;
             let jailed = JAIL.may_load(deps.storage, &operator)?;
+            let probation = PROBATION.may_load(deps.storage, &operator)?;
             Ok(OperatorResponse::from_info(
                 info,
                 operator.to_string(),
                 jailed,
+                probation,
             ))
         })
         .collect::<StdResult<_>>()?;
@@ -267,19 +732,32 @@ pub fn import(
     for jail in jails.iter() {
         JAIL.remove(deps.storage, jail);
     }
+    // Delete all existing probations
+    let probations = PROBATION
+        .keys(deps.storage, None, None, Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for probation in probations.iter() {
+        PROBATION.remove(deps.storage, probation);
+    }
     // Import operators
     for op in state.operators {
         let info = OperatorInfo {
-            pubkey: Ed25519Pubkey::try_from(op.pubkey)?,
+            pubkey: ValidatedPubkey::try_from(op.pubkey)?,
             metadata: op.metadata,
             active_validator: op.active_validator,
+            payout: op.payout,
         };
         let addr = Addr::unchecked(&op.operator);
         operators().save(deps.storage, &addr, &info)?;
         op.jailed_until
             .map(|jp| JAIL.save(deps.storage, &addr, &jp))
             .transpose()?;
+        op.probation_until
+            .map(|epoch| PROBATION.save(deps.storage, &addr, &epoch))
+            .transpose()?;
     }
+    reset_counters(deps.storage)?;
+    reindex_jails(deps.storage)?;
 
     // Validator start height items
     // Delete all existing start heights
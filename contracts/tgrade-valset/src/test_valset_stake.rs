@@ -85,6 +85,11 @@ fn instantiate_stake(app: &mut BasicApp<TgradeMsg>) -> Addr {
         preauths_hooks: 0,
         preauths_slashing: 1,
         auto_return_limit: 0,
+        unbond_veto_period: None,
+        max_bond_per_address: None,
+        max_total_bond: None,
+        exit_fee: None,
+        lockup_tiers: vec![],
     };
     app.instantiate_contract(
         stake_id,
@@ -115,6 +120,7 @@ fn init_msg(
         max_validators,
         epoch_length: EPOCH_LENGTH,
         epoch_reward: epoch_reward(),
+        extra_epoch_rewards: vec![],
         initial_keys: members,
         scaling: None,
         fee_percentage: Decimal::zero(),
@@ -171,6 +177,7 @@ fn init_and_query_state() {
             max_validators: 10,
             scaling: None,
             epoch_reward: epoch_reward(),
+            extra_epoch_rewards: vec![],
             fee_percentage: Decimal::zero(),
             auto_unjail: false,
             double_sign_slash_ratio: Decimal::percent(50),
@@ -61,7 +61,9 @@ pub fn mock_instantiate_msg(admin: Addr) -> InstantiateMsg {
         max_validators: 100,
         epoch_length: 120,
         epoch_reward: coin(100, "utgd"),
+        extra_epoch_rewards: vec![],
         initial_keys: vec![],
+        genesis_validators: vec![],
         scaling: None,
         fee_percentage: Decimal::percent(0),
         auto_unjail: false,
@@ -70,6 +72,7 @@ pub fn mock_instantiate_msg(admin: Addr) -> InstantiateMsg {
         validator_group_code_id: 0,
         verify_validators: false,
         offline_jail_duration: Duration::new(86400),
+        preauths_penalty_hooks: 0,
     }
 }
 
@@ -1,17 +1,19 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, CustomQuery, Decimal, Deps, DepsMut, Env,
-    MessageInfo, StdResult, Uint128,
+    coins, from_slice, to_binary, Addr, BankMsg, Binary, CosmosMsg, CustomQuery, Deps, DepsMut,
+    Env, MessageInfo, StdResult, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use serde::de::IgnoredAny;
+use std::collections::BTreeMap;
 
 use crate::error::ContractError;
 use crate::msg::{
     AccountInfoResponse, CanExecuteResponse, ExecuteMsg, InstantiateMsg, IsHandedOverResponse,
-    QueryMsg, TokenInfoResponse,
+    ProjectedReleaseResponse, QueryMsg, TokenInfoResponse, VestingScheduleResponse,
 };
-use crate::state::{VestingAccount, VestingPlan, VESTING_ACCOUNT};
+use crate::state::{AllowedMsg, VestingAccount, VestingPlan, VESTING_ACCOUNT};
 use tg_bindings::{TgradeMsg, TgradeQuery};
 
 pub type Response = cosmwasm_std::Response<TgradeMsg>;
@@ -39,6 +41,7 @@ fn create_vesting_account<Q: CustomQuery>(
     msg: InstantiateMsg,
 ) -> Result<(), ContractError> {
     let initial_tokens = cw_utils::must_pay(&info, msg.denom.as_str())?;
+    validate_vesting_plan(&msg.vesting_plan, initial_tokens)?;
     let account = VestingAccount {
         denom: msg.denom,
         recipient: msg.recipient,
@@ -49,12 +52,39 @@ fn create_vesting_account<Q: CustomQuery>(
         paid_tokens: Uint128::zero(),
         initial_tokens,
         handed_over: false,
+        engagement_contract: msg.engagement_contract,
+        rewards_tokens: Uint128::zero(),
+        allowed_msgs: msg.allowed_msgs,
     };
     VESTING_ACCOUNT.save(deps.storage, &account)?;
 
     Ok(())
 }
 
+fn validate_vesting_plan(plan: &VestingPlan, initial: Uint128) -> Result<(), ContractError> {
+    if let VestingPlan::CliffLinear {
+        cliff_at,
+        start_at,
+        end_at,
+        cliff_amount,
+    } = plan
+    {
+        if *cliff_amount > initial {
+            return Err(ContractError::CliffAmountTooHigh {
+                cliff_amount: *cliff_amount,
+                initial,
+            });
+        }
+        if start_at.time() >= end_at.time()
+            || cliff_at.time() < start_at.time()
+            || cliff_at.time() > end_at.time()
+        {
+            return Err(ContractError::InvalidCliffSchedule);
+        }
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<TgradeQuery>,
@@ -68,6 +98,15 @@ pub fn execute(
         ExecuteMsg::FreezeTokens { amount } => freeze_tokens(deps, info.sender, amount),
         ExecuteMsg::UnfreezeTokens { amount } => unfreeze_tokens(deps, info.sender, amount),
         ExecuteMsg::ChangeOperator { address } => change_operator(deps, info.sender, address),
+        ExecuteMsg::SetEngagementContract { contract } => {
+            set_engagement_contract(deps, info.sender, contract)
+        }
+        ExecuteMsg::SetAllowedMsgs { allowed_msgs } => {
+            set_allowed_msgs(deps, info.sender, allowed_msgs)
+        }
+        ExecuteMsg::WithdrawEngagementRewards {} => {
+            withdraw_engagement_rewards(deps, env, info.sender)
+        }
         ExecuteMsg::HandOver {} => hand_over(deps, env, info.sender),
         _ => Err(ContractError::NotImplemented),
     }
@@ -108,49 +147,34 @@ fn allowed_release<Q: CustomQuery>(
     env: &Env,
     plan: &VestingPlan,
 ) -> Result<Uint128, ContractError> {
+    Ok(allowed_release_at(deps, env, plan, env.block.time)?)
+}
+
+/// Returns amount of tokens that would be allowed to be released at given point of time,
+/// assuming current balance, frozen and released amounts stay unchanged
+fn allowed_release_at<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: &Env,
+    plan: &VestingPlan,
+    at: Timestamp,
+) -> StdResult<Uint128> {
     let token_info = token_info(deps, env)?;
 
     // In order to allow releasing any extra tokens sent to the account AFTER vesting
     // account has been initialized, correct amount is calculated by doing query of
     // contract's balance.
-
-    match plan {
-        VestingPlan::Discrete {
-            release_at: release,
-        } => {
-            if release.is_expired(&env.block) {
-                let current = deps
-                    .querier
-                    .query_balance(&env.contract.address, token_info.denom)?;
-                // If end_at timestamp is already met, release all available tokens
-                Ok(current.amount - token_info.frozen)
-            } else {
-                Ok(Uint128::zero())
-            }
-        }
-        VestingPlan::Continuous { start_at, end_at } => {
-            if !start_at.is_expired(&env.block) {
-                // If start_at timestamp is not met, release nothing
-                Ok(Uint128::zero())
-            } else if end_at.is_expired(&env.block) {
-                let current = deps
-                    .querier
-                    .query_balance(&env.contract.address, token_info.denom)?;
-                // If end_at timestamp is already met, release all available tokens
-                Ok(current.amount - token_info.frozen)
-            } else {
-                // If current timestamp is in between start_at and end_at, relase
-                // tokens by linear ratio: tokens * ((current_time - start_time) / (end_time - start_time))
-                // and subtract already released or frozen tokens
-                Ok((token_info.initial
-                    * Decimal::from_ratio(
-                        env.block.time.seconds() - start_at.time().seconds(),
-                        end_at.time().seconds() - start_at.time().seconds(),
-                    ))
+    // Engagement rewards withdrawn into the account are vested from the start.
+    if plan.is_expired(at) {
+        // If end_at timestamp is already met, release all available tokens
+        Ok(token_info.balance.saturating_sub(token_info.frozen))
+    } else {
+        // Otherwise release tokens vested so far according to the plan plus rewards,
+        // and subtract already released or frozen tokens
+        Ok(
+            (plan.vested_at(token_info.initial, at) + token_info.rewards)
                 .saturating_sub(token_info.released)
-                .saturating_sub(token_info.frozen))
-            }
-        }
+                .saturating_sub(token_info.frozen),
+        )
     }
 }
 
@@ -160,16 +184,44 @@ fn execute_msg<Q: CustomQuery>(
     msgs: Vec<CosmosMsg<TgradeMsg>>,
 ) -> Result<Response, ContractError> {
     let account = VESTING_ACCOUNT.load(deps.storage)?;
-    if !account.handed_over {
-        return Err(ContractError::HandOverNotCompleted);
+    if account.handed_over {
+        require_recipient(&sender, &account)?;
+    } else {
+        // Before hand over the Operator can only send messages allowed by the Oversight
+        if require_operator(&sender, &account).is_err() {
+            return Err(ContractError::HandOverNotCompleted);
+        }
+        if let Some(idx) = msgs
+            .iter()
+            .position(|msg| !is_allowed_msg(&account.allowed_msgs, msg))
+        {
+            return Err(ContractError::MsgNotAllowed(idx));
+        }
     }
-    require_recipient(&sender, &account)?;
 
     Ok(Response::new()
         .add_messages(msgs)
         .add_attribute("action", "execute"))
 }
 
+/// Checks if `msg` is a contract execution of one of the `allowed` methods
+fn is_allowed_msg(allowed: &[AllowedMsg], msg: &CosmosMsg<TgradeMsg>) -> bool {
+    let (contract_addr, msg) = match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => (contract_addr, msg),
+        _ => return false,
+    };
+    // Method is the only top level key of the JSON message
+    let method = match from_slice::<BTreeMap<String, IgnoredAny>>(msg) {
+        Ok(obj) if obj.len() == 1 => obj.into_keys().next().unwrap_or_default(),
+        _ => return false,
+    };
+    allowed
+        .iter()
+        .any(|allowed| allowed.allows(contract_addr, &method))
+}
+
 fn release_tokens<Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
@@ -195,7 +247,9 @@ fn freeze_tokens<Q: CustomQuery>(
     let mut account = VESTING_ACCOUNT.load(deps.storage)?;
     require_oversight(&sender, &account)?;
 
-    let available_to_freeze = account.initial_tokens - account.frozen_tokens - account.paid_tokens;
+    let available_to_freeze = account.initial_tokens + account.rewards_tokens
+        - account.frozen_tokens
+        - account.paid_tokens;
     if let Some(requested_amount) = requested_amount {
         let final_frozen = std::cmp::min(requested_amount, available_to_freeze);
         helpers::freeze_tokens(final_frozen, sender, &mut account, deps.storage)
@@ -236,6 +290,94 @@ fn change_operator<Q: CustomQuery>(
         .add_attribute("sender", sender))
 }
 
+fn set_engagement_contract<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    sender: Addr,
+    contract: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let mut account = VESTING_ACCOUNT.load(deps.storage)?;
+    require_oversight(&sender, &account)?;
+
+    let contract = contract
+        .map(|contract| deps.api.addr_validate(contract.as_str()))
+        .transpose()?;
+    account.engagement_contract = contract.clone();
+    VESTING_ACCOUNT.save(deps.storage, &account)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_engagement_contract")
+        .add_attribute("sender", sender);
+    if let Some(contract) = contract {
+        res = res.add_attribute("engagement_contract", contract);
+    }
+    Ok(res)
+}
+
+fn set_allowed_msgs<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    sender: Addr,
+    allowed_msgs: Vec<AllowedMsg>,
+) -> Result<Response, ContractError> {
+    let mut account = VESTING_ACCOUNT.load(deps.storage)?;
+    require_oversight(&sender, &account)?;
+
+    for allowed in &allowed_msgs {
+        deps.api.addr_validate(allowed.contract.as_str())?;
+    }
+    account.allowed_msgs = allowed_msgs;
+    VESTING_ACCOUNT.save(deps.storage, &account)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_allowed_msgs")
+        .add_attribute("sender", sender))
+}
+
+/// Rewards withdrawal is executed in the same transaction, so the amount queried upfront is the
+/// amount transferred to the account.
+fn withdraw_engagement_rewards<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    let mut account = VESTING_ACCOUNT.load(deps.storage)?;
+    require_operator(&sender, &account)?;
+    let engagement = account
+        .engagement_contract
+        .clone()
+        .ok_or(ContractError::NoEngagementContract)?;
+
+    let rewards: tg4_engagement::msg::RewardsResponse = deps.querier.query_wasm_smart(
+        &engagement,
+        &tg4_engagement::msg::QueryMsg::WithdrawableRewards {
+            owner: env.contract.address.to_string(),
+        },
+    )?;
+    let rewards = rewards.rewards;
+    if rewards.denom != account.denom {
+        return Err(ContractError::RewardsDenomMismatch(rewards.denom));
+    }
+    if rewards.amount.is_zero() {
+        return Err(ContractError::NoRewards);
+    }
+
+    account.rewards_tokens += rewards.amount;
+    VESTING_ACCOUNT.save(deps.storage, &account)?;
+
+    let msg = WasmMsg::Execute {
+        contract_addr: engagement.to_string(),
+        msg: to_binary(&tg4_engagement::msg::ExecuteMsg::WithdrawRewards {
+            owner: None,
+            receiver: None,
+        })?,
+        funds: vec![],
+    };
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_engagement_rewards")
+        .add_attribute("tokens", rewards.amount.to_string())
+        .add_attribute("sender", sender)
+        .add_message(msg))
+}
+
 fn hand_over<Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
@@ -348,6 +490,10 @@ pub fn query(deps: Deps<TgradeQuery>, env: Env, msg: QueryMsg) -> StdResult<Bina
         QueryMsg::TokenInfo {} => to_binary(&token_info(deps, &env)?),
         QueryMsg::IsHandedOver {} => to_binary(&is_handed_over(deps)?),
         QueryMsg::CanExecute { sender } => to_binary(&can_execute(deps, sender)?),
+        QueryMsg::ProjectedRelease { at_time } => {
+            to_binary(&projected_release(deps, &env, at_time)?)
+        }
+        QueryMsg::VestingSchedule {} => to_binary(&vesting_schedule(deps, &env)?),
     }
 }
 
@@ -359,6 +505,8 @@ fn account_info<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<AccountInfoResponse>
         operator: account.operator,
         oversight: account.oversight,
         vesting_plan: account.vesting_plan,
+        engagement_contract: account.engagement_contract,
+        allowed_msgs: account.allowed_msgs,
     };
     Ok(info)
 }
@@ -377,6 +525,7 @@ fn token_info<Q: CustomQuery>(deps: Deps<Q>, env: &Env) -> StdResult<TokenInfoRe
         frozen: account.frozen_tokens,
         released: account.paid_tokens,
         balance,
+        rewards: account.rewards_tokens,
     };
     Ok(info)
 }
@@ -399,14 +548,42 @@ fn can_execute<Q: CustomQuery>(deps: Deps<Q>, sender: String) -> StdResult<CanEx
     }
 }
 
+fn projected_release<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: &Env,
+    at_time: Timestamp,
+) -> StdResult<ProjectedReleaseResponse> {
+    let account = VESTING_ACCOUNT.load(deps.storage)?;
+    let amount = allowed_release_at(deps, env, &account.vesting_plan, at_time)?;
+    Ok(ProjectedReleaseResponse {
+        at_time,
+        releasable: amount,
+    })
+}
+
+fn vesting_schedule<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: &Env,
+) -> StdResult<VestingScheduleResponse> {
+    let account = VESTING_ACCOUNT.load(deps.storage)?;
+    let plan = &account.vesting_plan;
+    let release_start = plan.release_start();
+    Ok(VestingScheduleResponse {
+        release_start,
+        vested_at_start: plan.vested_at(account.initial_tokens, release_start),
+        release_end: plan.release_end(),
+        vested: plan.vested_at(account.initial_tokens, env.block.time),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use assert_matches::assert_matches;
 
-    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
-    use cosmwasm_std::{from_binary, Coin, MessageInfo, OwnedDeps, Timestamp};
-    use tg_bindings_test::mock_deps_tgrade;
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::{from_binary, Coin, MessageInfo, Timestamp};
+    use tg_bindings_test::{mock_deps_tgrade, TgradeDeps};
     use tg_utils::Expiration;
 
     const OWNER: &str = "owner";
@@ -460,6 +637,8 @@ mod tests {
                 operator: self.operator,
                 oversight: self.oversight,
                 vesting_plan: self.vesting_plan,
+                engagement_contract: None,
+                allowed_msgs: vec![],
             };
 
             instantiate(
@@ -478,7 +657,7 @@ mod tests {
     }
 
     struct Suite {
-        deps: OwnedDeps<MockStorage, MockApi, MockQuerier, TgradeQuery>,
+        deps: TgradeDeps,
         env: Env,
     }
 
@@ -765,6 +944,8 @@ mod tests {
             vesting_plan: VestingPlan::Discrete {
                 release_at: Expiration::at_timestamp(Timestamp::from_seconds(DEFAULT_RELEASE)),
             },
+            engagement_contract: None,
+            allowed_msgs: vec![],
         };
 
         assert_matches!(
@@ -778,6 +959,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn instantiate_with_invalid_cliff_plan() {
+        let mut deps = mock_deps_tgrade();
+        let owner = mock_info(OWNER, &coins(100, VESTING_DENOM));
+
+        let instantiate_message = |cliff_at, cliff_amount| InstantiateMsg {
+            denom: VESTING_DENOM.to_string(),
+            recipient: Addr::unchecked(RECIPIENT),
+            operator: Addr::unchecked(OPERATOR),
+            oversight: Addr::unchecked(OVERSIGHT),
+            vesting_plan: VestingPlan::CliffLinear {
+                cliff_at: Expiration::at_timestamp(Timestamp::from_seconds(cliff_at)),
+                start_at: Expiration::at_timestamp(Timestamp::from_seconds(DEFAULT_RELEASE)),
+                end_at: Expiration::at_timestamp(Timestamp::from_seconds(DEFAULT_RELEASE + 200)),
+                cliff_amount: Uint128::new(cliff_amount),
+            },
+            engagement_contract: None,
+            allowed_msgs: vec![],
+        };
+
+        assert_eq!(
+            instantiate(
+                deps.as_mut().branch(),
+                mock_env(),
+                owner.clone(),
+                instantiate_message(DEFAULT_RELEASE + 50, 101)
+            ),
+            Err(ContractError::CliffAmountTooHigh {
+                cliff_amount: Uint128::new(101),
+                initial: Uint128::new(100)
+            })
+        );
+        assert_eq!(
+            instantiate(
+                deps.as_mut().branch(),
+                mock_env(),
+                owner.clone(),
+                instantiate_message(DEFAULT_RELEASE - 1, 50)
+            ),
+            Err(ContractError::InvalidCliffSchedule)
+        );
+        assert_eq!(
+            instantiate(
+                deps.as_mut().branch(),
+                mock_env(),
+                owner.clone(),
+                instantiate_message(DEFAULT_RELEASE + 201, 50)
+            ),
+            Err(ContractError::InvalidCliffSchedule)
+        );
+        instantiate(
+            deps.as_mut().branch(),
+            mock_env(),
+            owner,
+            instantiate_message(DEFAULT_RELEASE + 50, 100),
+        )
+        .unwrap();
+    }
+
     #[test]
     fn get_account_info() {
         let suite = SuiteBuilder::default().build();
@@ -790,7 +1030,9 @@ mod tests {
                 oversight: Addr::unchecked(OVERSIGHT),
                 vesting_plan: VestingPlan::Discrete {
                     release_at: Expiration::at_timestamp(Timestamp::from_seconds(DEFAULT_RELEASE)),
-                }
+                },
+                engagement_contract: None,
+                allowed_msgs: vec![],
             })
         );
     }
@@ -808,6 +1050,7 @@ mod tests {
                 released: Uint128::zero(),
                 // because no tokens were actually sent in UT
                 balance: Uint128::zero(),
+                rewards: Uint128::zero(),
             })
         );
     }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 
 use thiserror::Error;
 
@@ -39,6 +39,27 @@ pub enum ContractError {
     #[error("Amount of tokens in operation must be higher then zero")]
     ZeroTokensNotAllowed,
 
+    #[error("No engagement contract configured for this account")]
+    NoEngagementContract,
+
+    #[error("Engagement rewards are paid in {0}, not in the vesting denom")]
+    RewardsDenomMismatch(String),
+
+    #[error("No engagement rewards to withdraw")]
+    NoRewards,
+
+    #[error("Message {0} is not allowed before hand over")]
+    MsgNotAllowed(usize),
+
+    #[error("Cliff amount {cliff_amount} exceeds initial tokens {initial}")]
+    CliffAmountTooHigh {
+        cliff_amount: Uint128,
+        initial: Uint128,
+    },
+
+    #[error("Cliff vesting requires start_at < end_at and cliff_at between them")]
+    InvalidCliffSchedule,
+
     // TODO: Temporary error to not panic at unimplemented parts - remove when done
     #[error("Not available - implementation is not finished")]
     NotImplemented,
@@ -1,9 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, CosmosMsg, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, Timestamp, Uint128};
 
-use crate::state::VestingPlan;
+use crate::state::{AllowedMsg, VestingPlan};
 use tg_bindings::TgradeMsg;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -20,13 +20,20 @@ pub struct InstantiateMsg {
     /// recipient.
     pub oversight: Addr,
     pub vesting_plan: VestingPlan,
+    /// tg4-engagement contract this account collects rewards from, if it is a member there
+    #[serde(default)]
+    pub engagement_contract: Option<Addr>,
+    /// Contract executions the Operator may send with `Execute` before hand over
+    #[serde(default)]
+    pub allowed_msgs: Vec<AllowedMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Execute regular messages allowing to use vesting account as fully
-    /// functional "proxy account"
+    /// functional "proxy account". Before hand over, only the Operator (or Oversight) can
+    /// execute, and only contract executions allowed by the Oversight.
     Execute {
         msgs: Vec<CosmosMsg<TgradeMsg>>,
     },
@@ -52,6 +59,18 @@ pub enum ExecuteMsg {
     ChangeOperator {
         address: Addr,
     },
+    /// Oversight is able to change the tg4-engagement contract rewards are withdrawn from.
+    SetEngagementContract {
+        contract: Option<Addr>,
+    },
+    /// Oversight is able to set contract executions the Operator may send before hand over,
+    /// replacing the previous ones.
+    SetAllowedMsgs {
+        allowed_msgs: Vec<AllowedMsg>,
+    },
+    /// Withdraws rewards accrued by this account in the configured tg4-engagement contract.
+    /// Rewards are not subject to the vesting schedule and can be released right away.
+    WithdrawEngagementRewards {},
     /// Once end time of the contract has passed, hand over can be performed.
     /// It will burn all frozen tokens and set Oversight and Operator's addresses
     /// to the Reciepient's key. This marks the contract as Liberated
@@ -70,6 +89,11 @@ pub enum QueryMsg {
     TokenInfo {},
     /// Shows whether hand over procedure has been completed or not
     IsHandedOver {},
+    /// Computes amount of tokens that would be allowed to be released at given
+    /// point of time, assuming current balance, frozen and released amounts
+    ProjectedRelease { at_time: Timestamp },
+    /// Shows when initial tokens are vested according to the vesting plan
+    VestingSchedule {},
 }
 
 /// Response for CanExecute query
@@ -86,6 +110,12 @@ pub struct AccountInfoResponse {
     pub oversight: Addr,
     /// Timestamps for current discrete or continuous vesting plan
     pub vesting_plan: VestingPlan,
+    /// tg4-engagement contract rewards are withdrawn from
+    #[serde(default)]
+    pub engagement_contract: Option<Addr>,
+    /// Contract executions the Operator may send before hand over
+    #[serde(default)]
+    pub allowed_msgs: Vec<AllowedMsg>,
 }
 
 /// Response for TokenInfo query
@@ -100,6 +130,9 @@ pub struct TokenInfoResponse {
     pub released: Uint128,
     /// Amount of all tokens from current contract
     pub balance: Uint128,
+    /// Amount of engagement rewards withdrawn into the account
+    #[serde(default)]
+    pub rewards: Uint128,
 }
 
 /// Response for IsLiberated query
@@ -109,3 +142,24 @@ pub struct IsHandedOverResponse {
     /// "liberated" status
     pub is_handed_over: bool,
 }
+
+/// Response for ProjectedRelease query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ProjectedReleaseResponse {
+    pub at_time: Timestamp,
+    /// Amount of tokens that could be released at `at_time`
+    pub releasable: Uint128,
+}
+
+/// Response for VestingSchedule query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VestingScheduleResponse {
+    /// First point of time any of the initial tokens are vested
+    pub release_start: Timestamp,
+    /// Amount of initial tokens vested at `release_start` (eg. the cliff amount)
+    pub vested_at_start: Uint128,
+    /// Point of time all of the initial tokens are vested
+    pub release_end: Timestamp,
+    /// Amount of initial tokens vested so far
+    pub vested: Uint128,
+}
@@ -380,3 +380,309 @@ mod handover {
         assert_eq!(balance.amount, Uint128::new(50));
     }
 }
+
+mod projected_release {
+    use super::*;
+
+    #[test]
+    fn discrete() {
+        let suite = SuiteBuilder::new()
+            .with_tokens(100)
+            .with_vesting_plan_in_seconds_from_start(None, 100)
+            .build();
+
+        let now = suite.app.block_info().time;
+        assert_eq!(suite.projected_release(now).unwrap(), Uint128::zero());
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(99)).unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(100)).unwrap(),
+            Uint128::new(100)
+        );
+    }
+
+    #[test]
+    fn continuous_with_frozen_and_released_tokens() {
+        let mut suite = SuiteBuilder::new()
+            .with_tokens(100)
+            // plan starts 100s from genesis block and ends after additional 200s
+            .with_vesting_plan_in_seconds_from_start(Some(100), 300)
+            .build();
+
+        let oversight = suite.oversight.clone();
+        let operator = suite.operator.clone();
+        let now = suite.app.block_info().time;
+
+        // before start nothing, halfway through half, after end everything
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(50)).unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(200)).unwrap(),
+            Uint128::new(50)
+        );
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(400)).unwrap(),
+            Uint128::new(100)
+        );
+
+        // release 20 tokens a quarter through and freeze another 10
+        suite.app.advance_seconds(150);
+        suite.release_tokens(&operator, 20).unwrap();
+        suite.freeze_tokens(&oversight, 10).unwrap();
+
+        // projection accounts for current released and frozen amounts
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(200)).unwrap(),
+            Uint128::new(20)
+        );
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(400)).unwrap(),
+            Uint128::new(70)
+        );
+
+        // matches what can actually be released once the time comes
+        suite.app.advance_seconds(50);
+        suite.release_tokens(&operator, None).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.released, Uint128::new(40));
+    }
+}
+
+mod cliff_linear {
+    use super::*;
+
+    #[test]
+    fn nothing_before_cliff_then_lump_and_linear() {
+        let mut suite = SuiteBuilder::new()
+            .with_tokens(1000)
+            // linear between 100s and 500s from genesis block, cliff at 200s releasing 200 tokens
+            .with_cliff_vesting_plan_in_seconds_from_start(200, 100, 500, 200)
+            .build();
+
+        let operator = suite.operator.clone();
+        let now = suite.app.block_info().time;
+
+        // nothing before the cliff, even if linear release already started
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(150)).unwrap(),
+            Uint128::zero()
+        );
+        // at the cliff: cliff amount plus 800 * 100 / 400 vested lineary
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(200)).unwrap(),
+            Uint128::new(400)
+        );
+        // three quarters through: 200 + 800 * 300 / 400
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(400)).unwrap(),
+            Uint128::new(800)
+        );
+        assert_eq!(
+            suite.projected_release(now.plus_seconds(500)).unwrap(),
+            Uint128::new(1000)
+        );
+
+        suite.app.advance_seconds(150);
+        let err = suite.release_tokens(&operator, 1).unwrap_err();
+        assert_eq!(
+            ContractError::NotEnoughTokensAvailable,
+            err.downcast().unwrap()
+        );
+
+        suite.app.advance_seconds(50);
+        suite.release_tokens(&operator, None).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.released, Uint128::new(400));
+
+        suite.app.advance_seconds(300);
+        suite.release_tokens(&operator, None).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.released, token_info.initial);
+    }
+
+    #[test]
+    fn vesting_schedule() {
+        let mut suite = SuiteBuilder::new()
+            .with_tokens(1000)
+            .with_cliff_vesting_plan_in_seconds_from_start(200, 100, 500, 200)
+            .build();
+
+        let now = suite.app.block_info().time;
+        let schedule = suite.vesting_schedule().unwrap();
+        assert_eq!(schedule.release_start, now.plus_seconds(200));
+        assert_eq!(schedule.vested_at_start, Uint128::new(400));
+        assert_eq!(schedule.release_end, now.plus_seconds(500));
+        assert_eq!(schedule.vested, Uint128::zero());
+
+        suite.app.advance_seconds(400);
+        let schedule = suite.vesting_schedule().unwrap();
+        assert_eq!(schedule.vested, Uint128::new(800));
+    }
+}
+
+mod engagement_rewards {
+    use super::*;
+
+    #[test]
+    fn withdrawn_rewards_released_before_vesting() {
+        let mut suite = SuiteBuilder::new()
+            .with_tokens(1000)
+            .with_vesting_plan_in_seconds_from_start(None, 1000)
+            .with_engagement(10)
+            .build();
+
+        let operator = suite.operator.clone();
+        let recipient = suite.recipient.clone();
+
+        suite.distribute_engagement_rewards(200).unwrap();
+
+        let err = suite.withdraw_engagement_rewards(&recipient).unwrap_err();
+        assert_eq!(ContractError::RequireOperator, err.downcast().unwrap());
+
+        suite.withdraw_engagement_rewards(&operator).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.rewards, Uint128::new(200));
+        assert_eq!(token_info.balance, Uint128::new(1200));
+
+        // nothing left to withdraw
+        let err = suite.withdraw_engagement_rewards(&operator).unwrap_err();
+        assert_eq!(ContractError::NoRewards, err.downcast().unwrap());
+
+        // only rewards can be released before the vesting ends
+        let err = suite.release_tokens(&operator, 201).unwrap_err();
+        assert_eq!(
+            ContractError::NotEnoughTokensAvailable,
+            err.downcast().unwrap()
+        );
+        suite.release_tokens(&operator, None).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.released, Uint128::new(200));
+        assert_eq!(
+            suite
+                .app
+                .wrap()
+                .query_balance(&recipient, &suite.denom)
+                .unwrap(),
+            coin(200, &suite.denom)
+        );
+
+        // vesting tokens are released as usual
+        suite.app.advance_seconds(1000);
+        suite.release_tokens(&operator, None).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.released, Uint128::new(1200));
+        assert_eq!(token_info.balance, Uint128::zero());
+    }
+
+    #[test]
+    fn rewards_added_to_continuous_release() {
+        let mut suite = SuiteBuilder::new()
+            .with_tokens(1000)
+            .with_vesting_plan_in_seconds_from_start(Some(0), 1000)
+            .with_engagement(10)
+            .build();
+
+        let operator = suite.operator.clone();
+
+        suite.distribute_engagement_rewards(100).unwrap();
+        suite.withdraw_engagement_rewards(&operator).unwrap();
+
+        // half of the initial tokens vested, plus rewards
+        suite.app.advance_seconds(500);
+        suite.release_tokens(&operator, None).unwrap();
+        let token_info = suite.token_info().unwrap();
+        assert_eq!(token_info.released, Uint128::new(600));
+    }
+
+    #[test]
+    fn no_engagement_contract() {
+        let mut suite = SuiteBuilder::new().with_tokens(100).build();
+
+        let operator = suite.operator.clone();
+        let oversight = suite.oversight.clone();
+
+        let err = suite.withdraw_engagement_rewards(&operator).unwrap_err();
+        assert_eq!(ContractError::NoEngagementContract, err.downcast().unwrap());
+
+        let err = suite
+            .set_engagement_contract(&operator, Some(operator.clone()))
+            .unwrap_err();
+        assert_eq!(ContractError::RequireOversight, err.downcast().unwrap());
+        suite.set_engagement_contract(&oversight, None).unwrap();
+    }
+}
+
+mod allowed_msgs {
+    use super::*;
+    use crate::state::AllowedMsg;
+    use cosmwasm_std::{to_binary, WasmMsg};
+
+    #[test]
+    fn operator_executes_allowed_msgs_before_hand_over() {
+        let mut suite = SuiteBuilder::new()
+            .with_tokens(1000)
+            .with_vesting_plan_in_seconds_from_start(None, 1000)
+            .with_engagement(10)
+            .build();
+
+        let operator = suite.operator.clone();
+        let oversight = suite.oversight.clone();
+        let recipient = suite.recipient.clone();
+        let engagement = suite.engagement.clone().unwrap();
+
+        let allowed = vec![AllowedMsg {
+            contract: engagement.clone(),
+            methods: vec!["distribute_rewards".to_owned()],
+        }];
+        let err = suite
+            .set_allowed_msgs(&operator, allowed.clone())
+            .unwrap_err();
+        assert_eq!(ContractError::RequireOversight, err.downcast().unwrap());
+        suite.set_allowed_msgs(&oversight, allowed.clone()).unwrap();
+        assert_eq!(suite.account_info().unwrap().allowed_msgs, allowed);
+
+        // unvested tokens can be sent with an allowed execution
+        let distribute = CosmosMsg::<TgradeMsg>::Wasm(WasmMsg::Execute {
+            contract_addr: engagement.to_string(),
+            msg: to_binary(&tg4_engagement::msg::ExecuteMsg::DistributeRewards { sender: None })
+                .unwrap(),
+            funds: coins(300, &suite.denom),
+        });
+        suite.execute(&operator, distribute.clone()).unwrap();
+        assert_eq!(suite.token_info().unwrap().balance, Uint128::new(700));
+
+        // other methods of the same contract are not allowed
+        let withdraw = CosmosMsg::<TgradeMsg>::Wasm(WasmMsg::Execute {
+            contract_addr: engagement.to_string(),
+            msg: to_binary(&tg4_engagement::msg::ExecuteMsg::WithdrawRewards {
+                owner: None,
+                receiver: Some(recipient.to_string()),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        let err = suite.execute(&operator, withdraw).unwrap_err();
+        assert_eq!(ContractError::MsgNotAllowed(0), err.downcast().unwrap());
+
+        // neither are other messages
+        let transfer = CosmosMsg::<TgradeMsg>::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(100, &suite.denom),
+        });
+        let err = suite.execute(&operator, transfer).unwrap_err();
+        assert_eq!(ContractError::MsgNotAllowed(0), err.downcast().unwrap());
+
+        // recipient has to wait for hand over
+        let err = suite.execute(&recipient, distribute.clone()).unwrap_err();
+        assert_eq!(ContractError::HandOverNotCompleted, err.downcast().unwrap());
+
+        // removing the allowance stops executions
+        suite.set_allowed_msgs(&oversight, vec![]).unwrap();
+        let err = suite.execute(&operator, distribute).unwrap_err();
+        assert_eq!(ContractError::MsgNotAllowed(0), err.downcast().unwrap());
+    }
+}
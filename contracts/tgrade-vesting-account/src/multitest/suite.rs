@@ -2,6 +2,7 @@ use crate::{error::ContractError, msg::*, state::*};
 
 use cosmwasm_std::{coin, Addr, CosmosMsg, Timestamp, Uint128};
 use cw_multi_test::{AppResponse, Contract, ContractWrapper, CosmosRouter, Executor};
+use tg4::Member;
 use tg_bindings::{TgradeMsg, TgradeQuery};
 use tg_bindings_test::TgradeApp;
 use tg_utils::Expiration;
@@ -19,6 +20,16 @@ pub fn contract_vesting() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
     Box::new(contract)
 }
 
+fn contract_engagement() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    let contract = ContractWrapper::new(
+        tg4_engagement::contract::execute,
+        tg4_engagement::contract::instantiate,
+        tg4_engagement::contract::query,
+    );
+
+    Box::new(contract)
+}
+
 pub struct SuiteBuilder {
     recipient: String,
     operator: String,
@@ -26,6 +37,8 @@ pub struct SuiteBuilder {
     denom: String,
     vesting_plan: VestingPlan,
     initial_tokens: u128,
+    /// Engagement points of the vesting account, no engagement contract if `None`
+    engagement_points: Option<u64>,
     owner: String,
     app: TgradeApp,
 }
@@ -46,6 +59,7 @@ impl SuiteBuilder {
                 release_at: Expiration::at_timestamp(Timestamp::from_seconds(1)),
             },
             initial_tokens: 0u128,
+            engagement_points: None,
             owner: default_owner.to_owned(),
             app,
         }
@@ -56,6 +70,12 @@ impl SuiteBuilder {
         self
     }
 
+    /// Makes the vesting account a member of a tg4-engagement contract with given points
+    pub fn with_engagement(mut self, points: u64) -> Self {
+        self.engagement_points = Some(points);
+        self
+    }
+
     pub fn with_vesting_plan_in_seconds_from_start(
         mut self,
         start_at: Option<u64>,
@@ -82,6 +102,30 @@ impl SuiteBuilder {
         self
     }
 
+    /// Sets a cliff plan with tokens vested lineary between `start_at` and `end_at`,
+    /// but not released before `cliff_at`.
+    pub fn with_cliff_vesting_plan_in_seconds_from_start(
+        mut self,
+        cliff_at: u64,
+        start_at: u64,
+        end_at: u64,
+        cliff_amount: u128,
+    ) -> Self {
+        // see with_vesting_plan_in_seconds_from_start
+        let initial_time = 5;
+        let block_info = self.app.block_info();
+        let at = |seconds: u64| {
+            Expiration::at_timestamp(block_info.time.plus_seconds(seconds + initial_time))
+        };
+        self.vesting_plan = VestingPlan::CliffLinear {
+            cliff_at: at(cliff_at),
+            start_at: at(start_at),
+            end_at: at(end_at),
+            cliff_amount: Uint128::new(cliff_amount),
+        };
+        self
+    }
+
     #[track_caller]
     pub fn build(mut self) -> Suite {
         let owner = Addr::unchecked(self.owner.clone());
@@ -107,6 +151,30 @@ impl SuiteBuilder {
             })
             .unwrap();
 
+        let engagement = self.engagement_points.map(|_| {
+            let engagement_id = self.app.store_code(contract_engagement());
+            self.app
+                .instantiate_contract(
+                    engagement_id,
+                    owner.clone(),
+                    &tg4_engagement::msg::InstantiateMsg {
+                        admin: Some(owner.to_string()),
+                        members: vec![],
+                        preauths_hooks: 0,
+                        preauths_slashing: 0,
+                        halflife: None,
+                        denom: denom.clone(),
+                        min_distribution: Uint128::zero(),
+                        distribution_interval: None,
+                        withdrawal_stream_period: None,
+                    },
+                    &[],
+                    "engagement",
+                    Some(owner.to_string()),
+                )
+                .unwrap()
+        });
+
         let contract_id = self.app.store_code(contract_vesting());
         let recipient = Addr::unchecked(self.recipient);
         let operator = Addr::unchecked(self.operator);
@@ -122,6 +190,8 @@ impl SuiteBuilder {
                     operator: operator.clone(),
                     oversight: oversight.clone(),
                     vesting_plan: self.vesting_plan,
+                    engagement_contract: engagement.clone(),
+                    allowed_msgs: vec![],
                 },
                 &[coin(self.initial_tokens, denom.clone())],
                 "vesting",
@@ -129,6 +199,24 @@ impl SuiteBuilder {
             )
             .unwrap();
 
+        if let (Some(engagement), Some(points)) = (&engagement, self.engagement_points) {
+            self.app
+                .execute_contract(
+                    owner.clone(),
+                    engagement.clone(),
+                    &tg4_engagement::msg::ExecuteMsg::UpdateMembers {
+                        remove: vec![],
+                        add: vec![Member {
+                            addr: contract.to_string(),
+                            points,
+                            start_height: None,
+                        }],
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
         // process initial genesis block
         self.app.next_block().unwrap();
 
@@ -140,6 +228,7 @@ impl SuiteBuilder {
             operator,
             oversight,
             denom,
+            engagement,
         }
     }
 }
@@ -156,6 +245,8 @@ pub struct Suite {
     pub operator: Addr,
     pub oversight: Addr,
     pub denom: String,
+    /// tg4-engagement contract the vesting account is a member of
+    pub engagement: Option<Addr>,
 }
 
 impl Suite {
@@ -226,6 +317,60 @@ impl Suite {
         )
     }
 
+    /// Mints tokens and distributes them as engagement rewards
+    pub fn distribute_engagement_rewards(&mut self, amount: u128) -> AnyResult<AppResponse> {
+        self.mint_tokens(amount)?;
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.engagement.clone().unwrap(),
+            &tg4_engagement::msg::ExecuteMsg::DistributeRewards { sender: None },
+            &[coin(amount, &self.denom)],
+        )
+    }
+
+    pub fn withdraw_engagement_rewards(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.contract.clone(),
+            &ExecuteMsg::WithdrawEngagementRewards {},
+            &[],
+        )
+    }
+
+    pub fn set_engagement_contract(
+        &mut self,
+        sender: &Addr,
+        contract: Option<Addr>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.contract.clone(),
+            &ExecuteMsg::SetEngagementContract { contract },
+            &[],
+        )
+    }
+
+    pub fn set_allowed_msgs(
+        &mut self,
+        sender: &Addr,
+        allowed_msgs: Vec<AllowedMsg>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.contract.clone(),
+            &ExecuteMsg::SetAllowedMsgs { allowed_msgs },
+            &[],
+        )
+    }
+
+    pub fn account_info(&self) -> Result<AccountInfoResponse, ContractError> {
+        let resp: AccountInfoResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::AccountInfo {})?;
+        Ok(resp)
+    }
+
     pub fn handover(&mut self, sender: &Addr) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             sender.clone(),
@@ -252,6 +397,22 @@ impl Suite {
         Ok(resp)
     }
 
+    pub fn projected_release(&self, at_time: Timestamp) -> Result<Uint128, ContractError> {
+        let resp: ProjectedReleaseResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ProjectedRelease { at_time },
+        )?;
+        Ok(resp.releasable)
+    }
+
+    pub fn vesting_schedule(&self) -> Result<VestingScheduleResponse, ContractError> {
+        let resp: VestingScheduleResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::VestingSchedule {})?;
+        Ok(resp)
+    }
+
     fn is_handed_over(&self) -> Result<IsHandedOverResponse, ContractError> {
         let resp: IsHandedOverResponse = self
             .app
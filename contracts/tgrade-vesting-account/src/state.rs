@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
 use cw_storage_plus::Item;
 use tg_utils::Expiration;
 
@@ -9,6 +9,8 @@ use tg_utils::Expiration;
 /// until given point of time.
 /// If account is continuous, then tokens will be released lineary
 /// starting at pre-defined point.
+/// If account has a cliff, then nothing is released until the cliff, when
+/// `cliff_amount` becomes available at once, and the rest is released lineary until the end.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub enum VestingPlan {
     Discrete {
@@ -19,6 +21,15 @@ pub enum VestingPlan {
         /// end_at allows linear interpolation between these points.
         end_at: Expiration,
     },
+    CliffLinear {
+        cliff_at: Expiration,
+        /// start_at and end_at define the linear release of tokens above `cliff_amount`.
+        /// Tokens vested this way before the cliff become available at the cliff.
+        start_at: Expiration,
+        end_at: Expiration,
+        /// Number of initial tokens released at the cliff.
+        cliff_amount: Uint128,
+    },
 }
 
 impl VestingPlan {
@@ -26,6 +37,65 @@ impl VestingPlan {
         match self {
             VestingPlan::Discrete { release_at } => release_at.is_expired_time(timestamp),
             VestingPlan::Continuous { end_at, .. } => end_at.is_expired_time(timestamp),
+            VestingPlan::CliffLinear { end_at, .. } => end_at.is_expired_time(timestamp),
+        }
+    }
+
+    /// Returns the first point of time any of the initial tokens are vested.
+    pub fn release_start(&self) -> Timestamp {
+        match self {
+            VestingPlan::Discrete { release_at } => release_at.time(),
+            VestingPlan::Continuous { start_at, .. } => start_at.time(),
+            VestingPlan::CliffLinear { cliff_at, .. } => cliff_at.time(),
+        }
+    }
+
+    /// Returns the point of time all of the initial tokens are vested.
+    pub fn release_end(&self) -> Timestamp {
+        match self {
+            VestingPlan::Discrete { release_at } => release_at.time(),
+            VestingPlan::Continuous { end_at, .. } => end_at.time(),
+            VestingPlan::CliffLinear { end_at, .. } => end_at.time(),
+        }
+    }
+
+    /// Returns amount of `initial` tokens vested at given point of time.
+    pub fn vested_at(&self, initial: Uint128, timestamp: Timestamp) -> Uint128 {
+        if self.is_expired(timestamp) {
+            return initial;
+        }
+        match self {
+            VestingPlan::Discrete { .. } => Uint128::zero(),
+            VestingPlan::Continuous { start_at, end_at } => {
+                if !start_at.is_expired_time(timestamp) {
+                    Uint128::zero()
+                } else {
+                    // tokens * ((current_time - start_time) / (end_time - start_time))
+                    initial
+                        * Decimal::from_ratio(
+                            timestamp.seconds() - start_at.time().seconds(),
+                            end_at.time().seconds() - start_at.time().seconds(),
+                        )
+                }
+            }
+            VestingPlan::CliffLinear {
+                cliff_at,
+                start_at,
+                end_at,
+                cliff_amount,
+            } => {
+                if !cliff_at.is_expired_time(timestamp) {
+                    Uint128::zero()
+                } else {
+                    // cliff_amount + (tokens - cliff_amount) * ((current_time - start_time) / (end_time - start_time))
+                    *cliff_amount
+                        + initial.saturating_sub(*cliff_amount)
+                            * Decimal::from_ratio(
+                                timestamp.seconds() - start_at.time().seconds(),
+                                end_at.time().seconds() - start_at.time().seconds(),
+                            )
+                }
+            }
         }
     }
 }
@@ -45,6 +115,30 @@ pub struct VestingAccount {
     pub initial_tokens: Uint128,
     /// Has hand over been completed
     pub handed_over: bool,
+    /// tg4-engagement contract rewards are withdrawn from
+    #[serde(default)]
+    pub engagement_contract: Option<Addr>,
+    /// Number of engagement rewards withdrawn into the account. They are vested from the start.
+    #[serde(default)]
+    pub rewards_tokens: Uint128,
+    /// Messages the Operator may execute before hand over, eg. to stake vesting tokens
+    #[serde(default)]
+    pub allowed_msgs: Vec<AllowedMsg>,
+}
+
+/// Contract execution the Operator may send on behalf of the account before hand over
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AllowedMsg {
+    pub contract: Addr,
+    /// Allowed execute methods, which are the top level keys of the JSON messages
+    /// (eg. `bond` for `{"bond": {}}`)
+    pub methods: Vec<String>,
+}
+
+impl AllowedMsg {
+    pub fn allows(&self, contract: &str, method: &str) -> bool {
+        self.contract == contract && self.methods.iter().any(|m| m == method)
+    }
 }
 
 pub const VESTING_ACCOUNT: Item<VestingAccount> = Item::new("vesting_account");
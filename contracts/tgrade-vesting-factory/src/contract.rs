@@ -0,0 +1,251 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_binary, Addr, Binary, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Reply, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::{maybe_addr, parse_reply_instantiate_data};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AccountResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, ListAccountsResponse, QueryMsg,
+    VestingAccountInfo, VestingAccountSpec,
+};
+use crate::state::{Config, VestingAccountRecord, ACCOUNTS, CONFIG, RECIPIENT_ACCOUNTS};
+use tg_bindings::{TgradeMsg, TgradeQuery};
+use tgrade_vesting_account::msg::{
+    AccountInfoResponse, InstantiateMsg as VestingInstantiateMsg, QueryMsg as VestingQueryMsg,
+};
+
+pub type Response = cosmwasm_std::Response<TgradeMsg>;
+pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:tgrade-vesting-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const ACCOUNT_INIT_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut<TgradeQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let config = Config {
+        vesting_code_id: msg.vesting_code_id,
+        admin: maybe_addr(deps.api, msg.admin)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut<TgradeQuery>,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateAccounts { denom, accounts } => {
+            create_accounts(deps, info, denom, accounts)
+        }
+    }
+}
+
+fn create_accounts<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    denom: String,
+    accounts: Vec<VestingAccountSpec>,
+) -> Result<Response, ContractError> {
+    if accounts.is_empty() {
+        return Err(ContractError::NoAccounts);
+    }
+
+    let sent = cw_utils::must_pay(&info, &denom)?;
+    let total = accounts
+        .iter()
+        .map(|account| account.amount)
+        .sum::<Uint128>();
+    if sent != total {
+        return Err(ContractError::FundsMismatch { sent, total });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let admin = config.admin.as_ref().map(Addr::to_string);
+
+    let submsgs = accounts
+        .into_iter()
+        .map(|account| {
+            if account.amount.is_zero() {
+                return Err(ContractError::ZeroTokensNotAllowed(account.recipient));
+            }
+
+            let init_msg = VestingInstantiateMsg {
+                denom: denom.clone(),
+                recipient: deps.api.addr_validate(&account.recipient)?,
+                operator: deps.api.addr_validate(&account.operator)?,
+                oversight: deps.api.addr_validate(&account.oversight)?,
+                vesting_plan: account.vesting_plan,
+                engagement_contract: None,
+                allowed_msgs: vec![],
+            };
+            let instantiate_msg = WasmMsg::Instantiate {
+                admin: admin.clone(),
+                code_id: config.vesting_code_id,
+                msg: to_binary(&init_msg)?,
+                funds: coins(account.amount.u128(), &denom),
+                label: format!("vesting_account_{}", account.recipient),
+            };
+            Ok(SubMsg::reply_on_success(
+                instantiate_msg,
+                ACCOUNT_INIT_REPLY_ID,
+            ))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    let res = Response::new()
+        .add_attribute("action", "create_accounts")
+        .add_attribute("sender", info.sender)
+        .add_attribute("accounts", submsgs.len().to_string())
+        .add_submessages(submsgs);
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut<TgradeQuery>, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        ACCOUNT_INIT_REPLY_ID => account_instantiate_reply(deps, env, msg),
+        _ => Err(ContractError::UnrecognisedReply(msg.id)),
+    }
+}
+
+/// Registers newly created vesting account. Recipient is queried from the account itself,
+/// so no information has to be kept between the instantiation and its reply.
+fn account_instantiate_reply<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    let id = msg.id;
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|err| ContractError::ReplyParseFailure {
+            id,
+            err: err.to_string(),
+        })?;
+    let contract = deps.api.addr_validate(&res.contract_address)?;
+
+    let info: AccountInfoResponse = deps
+        .querier
+        .query_wasm_smart(&contract, &VestingQueryMsg::AccountInfo {})?;
+
+    let record = VestingAccountRecord {
+        recipient: info.recipient,
+        created_at: env.block.height,
+    };
+    ACCOUNTS.save(deps.storage, &contract, &record)?;
+    RECIPIENT_ACCOUNTS.save(deps.storage, (&record.recipient, &contract), &Empty {})?;
+
+    let res = Response::new()
+        .add_attribute("vesting_account", contract)
+        .add_attribute("recipient", record.recipient);
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<TgradeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Account { contract } => to_binary(&query_account(deps, contract)?),
+        QueryMsg::ListAccounts { start_after, limit } => {
+            to_binary(&list_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::ListAccountsByRecipient {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&list_accounts_by_recipient(
+            deps,
+            recipient,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+fn query_config<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        vesting_code_id: config.vesting_code_id,
+        admin: config.admin,
+    })
+}
+
+fn query_account<Q: CustomQuery>(deps: Deps<Q>, contract: String) -> StdResult<AccountResponse> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let account = ACCOUNTS
+        .may_load(deps.storage, &contract)?
+        .map(|record| account_info(contract, record));
+    Ok(AccountResponse { account })
+}
+
+fn account_info(contract: Addr, record: VestingAccountRecord) -> VestingAccountInfo {
+    VestingAccountInfo {
+        contract,
+        recipient: record.recipient,
+        created_at: record.created_at,
+    }
+}
+
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 30;
+
+fn list_accounts<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = maybe_addr(deps.api, start_after)?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let accounts = ACCOUNTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| item.map(|(contract, record)| account_info(contract, record)))
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListAccountsResponse { accounts })
+}
+
+fn list_accounts_by_recipient<Q: CustomQuery>(
+    deps: Deps<Q>,
+    recipient: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let start_after = maybe_addr(deps.api, start_after)?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let accounts = RECIPIENT_ACCOUNTS
+        .prefix(&recipient)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(|contract| {
+            let contract = contract?;
+            let record = ACCOUNTS.load(deps.storage, &contract)?;
+            Ok(account_info(contract, record))
+        })
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListAccountsResponse { accounts })
+}
@@ -0,0 +1,27 @@
+use cosmwasm_std::{StdError, Uint128};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("No vesting accounts to create")]
+    NoAccounts,
+
+    #[error("Amount of tokens for vesting account of {0} must be higher than zero")]
+    ZeroTokensNotAllowed(String),
+
+    #[error("Sent funds ({sent}) don't match total amount of vesting accounts ({total})")]
+    FundsMismatch { sent: Uint128, total: Uint128 },
+
+    #[error("Error parsing reply (id {id}): {err}")]
+    ReplyParseFailure { id: u64, err: String },
+
+    #[error("Unrecognised reply id: {0}")]
+    UnrecognisedReply(u64),
+}
@@ -0,0 +1,6 @@
+pub mod contract;
+pub mod error;
+pub mod msg;
+#[cfg(test)]
+mod multitest;
+pub mod state;
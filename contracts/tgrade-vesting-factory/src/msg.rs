@@ -0,0 +1,84 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+
+use tgrade_vesting_account::state::VestingPlan;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// Code id of tgrade-vesting-account contract to be instantiated
+    pub vesting_code_id: u64,
+    /// Admin of created vesting account contracts. If not set, accounts are not migratable.
+    #[serde(default)]
+    pub admin: Option<String>,
+}
+
+/// Single vesting account to be created by the factory
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct VestingAccountSpec {
+    pub recipient: String,
+    pub operator: String,
+    pub oversight: String,
+    pub vesting_plan: VestingPlan,
+    /// Amount of tokens to be vested on this account
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Instantiates vesting account for every spec. Funds sent with the message
+    /// have to be exactly the sum of all accounts amounts in the given denom.
+    CreateAccounts {
+        denom: String,
+        accounts: Vec<VestingAccountSpec>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns ConfigResponse
+    Config {},
+    /// Returns AccountResponse
+    Account { contract: String },
+    /// Returns ListAccountsResponse
+    ListAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns ListAccountsResponse with accounts created for given recipient
+    ListAccountsByRecipient {
+        recipient: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ConfigResponse {
+    pub vesting_code_id: u64,
+    pub admin: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VestingAccountInfo {
+    pub contract: Addr,
+    pub recipient: Addr,
+    /// Height of the block in which account was created
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AccountResponse {
+    /// None if contract was not created by this factory
+    pub account: Option<VestingAccountInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ListAccountsResponse {
+    pub accounts: Vec<VestingAccountInfo>,
+}
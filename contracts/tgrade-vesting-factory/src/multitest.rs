@@ -0,0 +1,129 @@
+mod suite;
+
+use crate::error::ContractError;
+use suite::{account_spec, SuiteBuilder, DENOM};
+
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_utils::PaymentError;
+
+#[test]
+fn accounts_created_in_batch() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(600)
+        .with_admin("admin")
+        .build();
+    let config = suite.config().unwrap();
+    assert_eq!(config.vesting_code_id, suite.vesting_code_id);
+    assert_eq!(config.admin, Some(Addr::unchecked("admin")));
+
+    suite
+        .create_funded_accounts(vec![
+            account_spec("recipient1", 100),
+            account_spec("recipient2", 200),
+            account_spec("recipient1", 300),
+        ])
+        .unwrap();
+
+    let accounts = suite.list_accounts(None, None).unwrap();
+    assert_eq!(accounts.len(), 3);
+    let created_at = suite.app.block_info().height;
+    for (account, (recipient, amount)) in accounts.iter().zip(vec![
+        ("recipient1", 100),
+        ("recipient2", 200),
+        ("recipient1", 300),
+    ]) {
+        assert_eq!(account.recipient, recipient);
+        assert_eq!(account.created_at, created_at);
+
+        let info = suite.vesting_account_info(&account.contract).unwrap();
+        assert_eq!(info.recipient, recipient);
+        assert_eq!(info.operator, "operator");
+        assert_eq!(info.oversight, "oversight");
+
+        let tokens = suite.vesting_token_info(&account.contract).unwrap();
+        assert_eq!(tokens.denom, DENOM);
+        assert_eq!(tokens.initial, Uint128::new(amount));
+        assert_eq!(tokens.balance, Uint128::new(amount));
+
+        assert_eq!(
+            suite.contract_admin(&account.contract),
+            Some(Addr::unchecked("admin"))
+        );
+    }
+
+    let by_recipient = suite.list_accounts_by_recipient("recipient1").unwrap();
+    assert_eq!(by_recipient, [accounts[0].clone(), accounts[2].clone()]);
+    let by_recipient = suite.list_accounts_by_recipient("recipient2").unwrap();
+    assert_eq!(by_recipient, [accounts[1].clone()]);
+    assert_eq!(suite.list_accounts_by_recipient("recipient3").unwrap(), []);
+
+    assert_eq!(
+        suite.account(accounts[1].contract.as_str()).unwrap(),
+        Some(accounts[1].clone())
+    );
+    assert_eq!(suite.account(suite.contract.as_str()).unwrap(), None);
+
+    // paginated listing
+    let page = suite
+        .list_accounts(Some(accounts[0].contract.as_str()), Some(1))
+        .unwrap();
+    assert_eq!(page, [accounts[1].clone()]);
+}
+
+#[test]
+fn funds_have_to_match_accounts() {
+    let mut suite = SuiteBuilder::new().with_funds(1000).build();
+    let owner = suite.owner.to_string();
+
+    let err = suite
+        .create_accounts(&owner, vec![], &[coin(100, DENOM)])
+        .unwrap_err();
+    assert_eq!(ContractError::NoAccounts, err.downcast().unwrap());
+
+    let err = suite
+        .create_accounts(&owner, vec![account_spec("recipient", 100)], &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PaymentError(PaymentError::NoFunds {}),
+        err.downcast().unwrap()
+    );
+
+    let accounts = vec![
+        account_spec("recipient1", 100),
+        account_spec("recipient2", 200),
+    ];
+    let err = suite
+        .create_accounts(&owner, accounts.clone(), &[coin(250, DENOM)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FundsMismatch {
+            sent: Uint128::new(250),
+            total: Uint128::new(300)
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .create_accounts(&owner, accounts, &[coin(350, DENOM)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FundsMismatch {
+            sent: Uint128::new(350),
+            total: Uint128::new(300)
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .create_funded_accounts(vec![
+            account_spec("recipient1", 100),
+            account_spec("recipient2", 0),
+        ])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ZeroTokensNotAllowed("recipient2".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Nothing was created
+    assert_eq!(suite.list_accounts(None, None).unwrap(), []);
+}
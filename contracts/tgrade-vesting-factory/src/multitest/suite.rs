@@ -0,0 +1,239 @@
+use crate::{error::ContractError, msg::*};
+
+use cosmwasm_std::{coin, Addr, Coin, CosmosMsg, Timestamp, Uint128};
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, CosmosRouter, Executor};
+use tg_bindings::{TgradeMsg, TgradeQuery};
+use tg_bindings_test::TgradeApp;
+use tg_utils::Expiration;
+use tgrade_vesting_account::msg::{
+    AccountInfoResponse, QueryMsg as VestingQueryMsg, TokenInfoResponse,
+};
+use tgrade_vesting_account::state::VestingPlan;
+
+use anyhow::Result as AnyResult;
+
+pub const DENOM: &str = "DENOM";
+
+pub fn contract_factory() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+
+    Box::new(contract)
+}
+
+pub fn contract_vesting() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    let contract = ContractWrapper::new(
+        tgrade_vesting_account::contract::execute,
+        tgrade_vesting_account::contract::instantiate,
+        tgrade_vesting_account::contract::query,
+    );
+
+    Box::new(contract)
+}
+
+/// Creates spec of account with discrete vesting plan
+pub fn account_spec(recipient: &str, amount: u128) -> VestingAccountSpec {
+    VestingAccountSpec {
+        recipient: recipient.to_owned(),
+        operator: "operator".to_owned(),
+        oversight: "oversight".to_owned(),
+        vesting_plan: VestingPlan::Discrete {
+            release_at: Expiration::at_timestamp(Timestamp::from_seconds(1)),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+pub struct SuiteBuilder {
+    owner: String,
+    admin: Option<String>,
+    funds: u128,
+    app: TgradeApp,
+}
+
+impl SuiteBuilder {
+    pub fn new() -> SuiteBuilder {
+        let default_owner = "owner";
+        let mut app = TgradeApp::new(default_owner);
+        app.back_to_genesis();
+        SuiteBuilder {
+            owner: default_owner.to_owned(),
+            admin: None,
+            funds: 0,
+            app,
+        }
+    }
+
+    pub fn with_funds(mut self, amount: u128) -> Self {
+        self.funds = amount;
+        self
+    }
+
+    pub fn with_admin(mut self, admin: &str) -> Self {
+        self.admin = Some(admin.to_owned());
+        self
+    }
+
+    #[track_caller]
+    pub fn build(mut self) -> Suite {
+        let owner = Addr::unchecked(self.owner.clone());
+        let block_info = self.app.block_info();
+        let funds = Uint128::new(self.funds);
+
+        self.app
+            .init_modules(|router, api, storage| -> AnyResult<()> {
+                router.execute(
+                    api,
+                    storage,
+                    &block_info,
+                    owner.clone(),
+                    CosmosMsg::Custom(TgradeMsg::MintTokens {
+                        denom: DENOM.to_owned(),
+                        amount: funds,
+                        recipient: owner.to_string(),
+                    }),
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        let vesting_code_id = self.app.store_code(contract_vesting());
+        let factory_id = self.app.store_code(contract_factory());
+        let contract = self
+            .app
+            .instantiate_contract(
+                factory_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    vesting_code_id,
+                    admin: self.admin,
+                },
+                &[],
+                "vesting_factory",
+                None,
+            )
+            .unwrap();
+
+        // process initial genesis block
+        self.app.next_block().unwrap();
+
+        Suite {
+            app: self.app,
+            contract,
+            owner,
+            vesting_code_id,
+        }
+    }
+}
+
+pub struct Suite {
+    pub app: TgradeApp,
+    /// Vesting factory contract address
+    pub contract: Addr,
+    pub owner: Addr,
+    pub vesting_code_id: u64,
+}
+
+impl Suite {
+    pub fn create_accounts(
+        &mut self,
+        sender: &str,
+        accounts: Vec<VestingAccountSpec>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &ExecuteMsg::CreateAccounts {
+                denom: DENOM.to_owned(),
+                accounts,
+            },
+            funds,
+        )
+    }
+
+    /// Creates accounts from the owner, sending exactly the required funds
+    pub fn create_funded_accounts(
+        &mut self,
+        accounts: Vec<VestingAccountSpec>,
+    ) -> AnyResult<AppResponse> {
+        let total: u128 = accounts.iter().map(|account| account.amount.u128()).sum();
+        let owner = self.owner.to_string();
+        self.create_accounts(&owner, accounts, &[coin(total, DENOM)])
+    }
+
+    pub fn config(&self) -> Result<ConfigResponse, ContractError> {
+        let resp: ConfigResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::Config {})?;
+        Ok(resp)
+    }
+
+    pub fn account(&self, contract: &str) -> Result<Option<VestingAccountInfo>, ContractError> {
+        let resp: AccountResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::Account {
+                contract: contract.to_owned(),
+            },
+        )?;
+        Ok(resp.account)
+    }
+
+    pub fn list_accounts(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<VestingAccountInfo>, ContractError> {
+        let resp: ListAccountsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ListAccounts {
+                start_after: start_after.map(str::to_owned),
+                limit,
+            },
+        )?;
+        Ok(resp.accounts)
+    }
+
+    pub fn list_accounts_by_recipient(
+        &self,
+        recipient: &str,
+    ) -> Result<Vec<VestingAccountInfo>, ContractError> {
+        let resp: ListAccountsResponse = self.app.wrap().query_wasm_smart(
+            self.contract.clone(),
+            &QueryMsg::ListAccountsByRecipient {
+                recipient: recipient.to_owned(),
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(resp.accounts)
+    }
+
+    pub fn vesting_account_info(
+        &self,
+        contract: &Addr,
+    ) -> Result<AccountInfoResponse, ContractError> {
+        let resp: AccountInfoResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(contract.clone(), &VestingQueryMsg::AccountInfo {})?;
+        Ok(resp)
+    }
+
+    pub fn vesting_token_info(&self, contract: &Addr) -> Result<TokenInfoResponse, ContractError> {
+        let resp: TokenInfoResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(contract.clone(), &VestingQueryMsg::TokenInfo {})?;
+        Ok(resp)
+    }
+
+    pub fn contract_admin(&self, contract: &Addr) -> Option<Addr> {
+        self.app.contract_data(contract).unwrap().admin
+    }
+}
@@ -0,0 +1,27 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    /// Code id of tgrade-vesting-account contract used to create accounts
+    pub vesting_code_id: u64,
+    /// Admin set on created vesting account contracts, allowing to migrate them
+    pub admin: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VestingAccountRecord {
+    pub recipient: Addr,
+    /// Height of the block in which account was created
+    pub created_at: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// All vesting accounts created by the factory, indexed by their contract address
+pub const ACCOUNTS: Map<&Addr, VestingAccountRecord> = Map::new("accounts");
+/// Index of created vesting accounts by (recipient, contract address)
+pub const RECIPIENT_ACCOUNTS: Map<(&Addr, &Addr), Empty> = Map::new("recipient_accounts");
@@ -1,6 +1,6 @@
 mod multitest;
 
 pub use multitest::{
-    mock_deps_tgrade, Privileges, TgradeApp, TgradeAppWrapped, TgradeDeps, TgradeError,
-    TgradeModule, UpgradePlan, BLOCK_TIME,
+    mock_deps_tgrade, mock_deps_tgrade_with, Privileges, TgradeApp, TgradeAppWrapped, TgradeDeps,
+    TgradeError, TgradeFixtures, TgradeModule, UpgradePlan, BLOCK_TIME,
 };
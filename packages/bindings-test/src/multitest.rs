@@ -13,8 +13,8 @@ use std::marker::PhantomData;
 
 use cosmwasm_std::Order::Ascending;
 use cosmwasm_std::{
-    from_slice, to_binary, Addr, Api, Binary, BlockInfo, Coin, CustomQuery, Empty, Order, Querier,
-    QuerierResult, StdError, StdResult, Storage, Timestamp,
+    from_slice, to_binary, Addr, Api, Binary, BlockInfo, Coin, ContractResult, CustomQuery, Empty,
+    Order, Querier, QuerierResult, StdError, StdResult, Storage, SystemResult, Timestamp,
 };
 use cw_multi_test::{
     App, AppResponse, BankKeeper, BankSudo, BasicAppBuilder, CosmosRouter, Executor, Module,
@@ -48,13 +48,60 @@ const ADMIN_PRIVILEGES: &[Privilege] = &[
     Privilege::ConsensusParamChanger,
 ];
 
-pub type TgradeDeps = OwnedDeps<MockStorage, MockApi, MockQuerier, TgradeQuery>;
+pub type TgradeDeps = OwnedDeps<MockStorage, MockApi, MockQuerier<TgradeQuery>, TgradeQuery>;
 
+/// Answers to Tgrade custom queries returned by the querier of `mock_deps_tgrade_with`
+#[derive(Clone, Debug, Default)]
+pub struct TgradeFixtures {
+    /// Returned on `TgradeQuery::ValidatorVotes`
+    pub validator_votes: Vec<ValidatorVote>,
+    /// Returned on `TgradeQuery::ListPrivileged`, privileges not listed here have no contracts
+    pub privileged: Vec<(Privilege, Vec<Addr>)>,
+}
+
+impl TgradeFixtures {
+    pub fn with_validator_votes(mut self, votes: Vec<ValidatorVote>) -> Self {
+        self.validator_votes = votes;
+        self
+    }
+
+    pub fn with_privileged(mut self, privilege: Privilege, contracts: Vec<Addr>) -> Self {
+        self.privileged.retain(|(p, _)| *p != privilege);
+        self.privileged.push((privilege, contracts));
+        self
+    }
+
+    fn answer(&self, query: &TgradeQuery) -> StdResult<Binary> {
+        match query {
+            TgradeQuery::ValidatorVotes {} => to_binary(&ValidatorVoteResponse {
+                votes: self.validator_votes.clone(),
+            }),
+            TgradeQuery::ListPrivileged(privilege) => {
+                let privileged = self
+                    .privileged
+                    .iter()
+                    .find(|(p, _)| p == privilege)
+                    .map(|(_, contracts)| contracts.clone())
+                    .unwrap_or_default();
+                to_binary(&ListPrivilegedResponse { privileged })
+            }
+        }
+    }
+}
+
+/// Mock dependencies answering Tgrade custom queries with empty results
 pub fn mock_deps_tgrade() -> TgradeDeps {
+    mock_deps_tgrade_with(TgradeFixtures::default())
+}
+
+/// Mock dependencies answering Tgrade custom queries with given fixtures
+pub fn mock_deps_tgrade_with(fixtures: TgradeFixtures) -> TgradeDeps {
     OwnedDeps {
         storage: MockStorage::default(),
         api: MockApi::default(),
-        querier: MockQuerier::default(),
+        querier: MockQuerier::new(&[]).with_custom_handler(move |query| {
+            SystemResult::Ok(ContractResult::from(fixtures.answer(query)))
+        }),
         custom_query_type: PhantomData,
     }
 }
@@ -430,6 +477,31 @@ impl TgradeApp {
         self.execute(Addr::unchecked(owner), msg.into())
     }
 
+    pub fn demote(&mut self, owner: &str, contract: &str) -> AnyResult<AppResponse> {
+        let msg = TgradeMsg::ExecuteGovProposal {
+            title: "Demote Contract".to_string(),
+            description: "Demote Contract".to_string(),
+            proposal: GovProposal::DemotePrivilegedContract {
+                contract: contract.to_string(),
+            },
+        };
+        self.execute(Addr::unchecked(owner), msg.into())
+    }
+
+    /// Privileges currently held by the contract, empty if it is not privileged
+    pub fn privileges(&self, contract: &str) -> AnyResult<Privileges> {
+        let privileges = self.read_module(|_, _, storage| {
+            PRIVILEGES.may_load(storage, &Addr::unchecked(contract))
+        })?;
+        Ok(privileges.unwrap_or_default())
+    }
+
+    /// Sets the response of `TgradeQuery::ValidatorVotes` from now on
+    pub fn set_validator_votes(&mut self, votes: Vec<ValidatorVote>) -> AnyResult<()> {
+        self.init_modules(|router, _, storage| router.custom.set_votes(storage, votes))?;
+        Ok(())
+    }
+
     /// This reverses to genesis (based on current time/height)
     pub fn back_to_genesis(&mut self) {
         self.update_block(|block| {
@@ -472,6 +544,22 @@ impl TgradeApp {
         Ok(diff)
     }
 
+    /// Calls `next_block` the given number of times, so all the end and begin blockers are run
+    /// for every block in between. Returns validator diffs of the blocks which had one.
+    pub fn run_blocks(&mut self, blocks: u64) -> AnyResult<Vec<ValidatorDiff>> {
+        let mut diffs = vec![];
+        for _ in 0..blocks {
+            diffs.extend(self.next_block()?);
+        }
+        Ok(diffs)
+    }
+
+    /// Like `run_blocks`, but runs as many blocks as needed for at least `seconds` to pass
+    pub fn run_seconds(&mut self, seconds: u64) -> AnyResult<Vec<ValidatorDiff>> {
+        let blocks = (seconds + BLOCK_TIME - 1) / BLOCK_TIME;
+        self.run_blocks(blocks)
+    }
+
     /// Returns a list of all contracts that have the requested privilege
     pub fn with_privilege(&self, requested: Privilege) -> AnyResult<Vec<Addr>> {
         let ListPrivilegedResponse { privileged } = self
@@ -550,9 +638,52 @@ impl UpgradePlan {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::coin;
+    use cosmwasm_std::{coin, QueryRequest};
     use cw_multi_test::Executor;
 
+    #[test]
+    fn mock_deps_answer_custom_queries() {
+        let votes = vec![ValidatorVote {
+            address: Binary::from(b"validator"),
+            power: 10,
+            voted: true,
+        }];
+        let fixtures = TgradeFixtures::default()
+            .with_validator_votes(votes.clone())
+            .with_privileged(Privilege::EndBlocker, vec![Addr::unchecked("foo")])
+            .with_privileged(Privilege::EndBlocker, vec![Addr::unchecked("bar")]);
+        let deps = mock_deps_tgrade_with(fixtures);
+        let querier = deps.as_ref().querier;
+
+        let resp: ValidatorVoteResponse = querier
+            .query(&QueryRequest::Custom(TgradeQuery::ValidatorVotes {}))
+            .unwrap();
+        assert_eq!(resp.votes, votes);
+
+        let resp: ListPrivilegedResponse = querier
+            .query(&QueryRequest::Custom(TgradeQuery::ListPrivileged(
+                Privilege::EndBlocker,
+            )))
+            .unwrap();
+        assert_eq!(resp.privileged, [Addr::unchecked("bar")]);
+
+        let resp: ListPrivilegedResponse = querier
+            .query(&QueryRequest::Custom(TgradeQuery::ListPrivileged(
+                Privilege::BeginBlocker,
+            )))
+            .unwrap();
+        assert!(resp.privileged.is_empty());
+
+        // no fixtures, empty answers
+        let deps = mock_deps_tgrade();
+        let resp: ValidatorVoteResponse = deps
+            .as_ref()
+            .querier
+            .query(&QueryRequest::Custom(TgradeQuery::ValidatorVotes {}))
+            .unwrap();
+        assert_eq!(resp, ValidatorVoteResponse::default());
+    }
+
     #[test]
     fn init_and_owner_mints_tokens() {
         let owner = Addr::unchecked("govner");
@@ -586,5 +717,130 @@ mod tests {
         assert_eq!(end, mintable);
     }
 
+    mod lifecycle {
+        use super::*;
+        use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+        use cw_multi_test::{Contract, ContractWrapper};
+        use tg_bindings::request_privileges;
+
+        const BLOCKS: Item<(u64, u64)> = Item::new("blocks");
+
+        fn instantiate(
+            deps: DepsMut<TgradeQuery>,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response<TgradeMsg>> {
+            BLOCKS.save(deps.storage, &(0, 0))?;
+            Ok(Response::new())
+        }
+
+        fn execute(
+            _deps: DepsMut<TgradeQuery>,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response<TgradeMsg>> {
+            Ok(Response::new())
+        }
+
+        fn query(deps: Deps<TgradeQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&BLOCKS.load(deps.storage)?)
+        }
+
+        /// Counts begin and end blocks it is called on, once promoted
+        fn sudo(
+            deps: DepsMut<TgradeQuery>,
+            _env: Env,
+            msg: TgradeSudoMsg<Empty>,
+        ) -> StdResult<Response<TgradeMsg>> {
+            let mut res = Response::new();
+            match msg {
+                TgradeSudoMsg::PrivilegeChange(PrivilegeChangeMsg::Promoted {}) => {
+                    res = res.add_submessages(request_privileges(&[
+                        Privilege::BeginBlocker,
+                        Privilege::EndBlocker,
+                    ]));
+                }
+                TgradeSudoMsg::BeginBlock { .. } => {
+                    BLOCKS.update(deps.storage, |(begin, end)| -> StdResult<_> {
+                        Ok((begin + 1, end))
+                    })?;
+                }
+                TgradeSudoMsg::EndBlock {} => {
+                    BLOCKS.update(deps.storage, |(begin, end)| -> StdResult<_> {
+                        Ok((begin, end + 1))
+                    })?;
+                }
+                _ => {}
+            }
+            Ok(res)
+        }
+
+        fn contract_blocks() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query).with_sudo(sudo))
+        }
+
+        #[test]
+        fn promoted_contract_is_called_every_block() {
+            let owner = "govner";
+            let mut app = TgradeApp::new(owner);
+            let code_id = app.store_code(contract_blocks());
+            let contract = app
+                .instantiate_contract(
+                    code_id,
+                    Addr::unchecked(owner),
+                    &Empty {},
+                    &[],
+                    "blocks",
+                    None,
+                )
+                .unwrap();
+            let blocks = |app: &TgradeApp| -> (u64, u64) {
+                app.wrap().query_wasm_smart(&contract, &Empty {}).unwrap()
+            };
+
+            // not privileged yet
+            app.run_blocks(2).unwrap();
+            assert_eq!(blocks(&app), (0, 0));
+
+            app.promote(owner, contract.as_str()).unwrap();
+            assert_eq!(
+                app.privileges(contract.as_str()).unwrap(),
+                vec![Privilege::BeginBlocker, Privilege::EndBlocker]
+            );
+            let height = app.block_info().height;
+            app.run_blocks(3).unwrap();
+            assert_eq!(blocks(&app), (3, 3));
+            assert_eq!(app.block_info().height, height + 3);
+
+            // 11 seconds need 3 blocks
+            app.run_seconds(11).unwrap();
+            assert_eq!(blocks(&app), (6, 6));
+
+            app.demote(owner, contract.as_str()).unwrap();
+            assert_eq!(app.privileges(contract.as_str()).unwrap(), vec![]);
+            app.run_blocks(2).unwrap();
+            assert_eq!(blocks(&app), (6, 6));
+        }
+
+        #[test]
+        fn validator_votes_can_be_set() {
+            let mut app = TgradeApp::new("govner");
+            let votes = vec![ValidatorVote {
+                address: Binary::from(b"validator".as_slice()),
+                power: 10,
+                voted: true,
+            }];
+            app.set_validator_votes(votes.clone()).unwrap();
+
+            let res: ValidatorVoteResponse = app
+                .wrap()
+                .query(&TgradeQuery::ValidatorVotes {}.into())
+                .unwrap();
+            assert_eq!(res.votes, votes);
+        }
+    }
+
     // TODO: Delegate / Undelegate tests
 }
@@ -0,0 +1,102 @@
+//! Typed builders of events emitted by Tgrade contracts, so all of them use the same,
+//! documented attribute keys indexers can rely on.
+
+use cosmwasm_std::{Addr, Decimal, Event, Timestamp, Uint128};
+
+/// Emitted when stake or points of a member are slashed
+pub const SLASH_EVENT: &str = "slash";
+/// Emitted when funds are distributed among members
+pub const DISTRIBUTION_EVENT: &str = "distribution";
+/// Emitted when the points halflife is applied
+pub const HALFLIFE_EVENT: &str = "halflife";
+/// Emitted when the whole stake of a member is unbonded by an unbond controller
+pub const FORCE_UNBOND_EVENT: &str = "force_unbond";
+
+/// Address the event is about
+pub const ADDR_KEY: &str = "addr";
+/// Portion (`Decimal`) of the stake or points affected
+pub const PORTION_KEY: &str = "portion";
+/// Amount of tokens or points affected
+pub const AMOUNT_KEY: &str = "amount";
+/// Denom of the affected tokens
+pub const DENOM_KEY: &str = "denom";
+/// Block height the event refers to
+pub const HEIGHT_KEY: &str = "height";
+/// Total points reduction
+pub const REDUCTION_KEY: &str = "reduction";
+/// Member the event is about, when the sender acts on its behalf
+pub const MEMBER_KEY: &str = "member";
+/// Contract acting on behalf of the member
+pub const CONTROLLER_KEY: &str = "controller";
+/// Amount of liquid tokens affected
+pub const LIQUID_AMOUNT_KEY: &str = "liquid_amount";
+/// Amount of vesting tokens affected
+pub const VESTING_AMOUNT_KEY: &str = "vesting_amount";
+/// Time (in nanoseconds) the unbonded tokens are released at
+pub const COMPLETION_TIME_KEY: &str = "completion_time";
+
+/// `portion` of the stake (or points) of `addr` was slashed, which is `amount` in total
+pub fn slash(addr: impl Into<String>, portion: Decimal, amount: Uint128) -> Event {
+    Event::new(SLASH_EVENT)
+        .add_attribute(ADDR_KEY, addr)
+        .add_attribute(PORTION_KEY, portion.to_string())
+        .add_attribute(AMOUNT_KEY, amount)
+}
+
+/// `amount` of `denom` was distributed among members
+pub fn distribution(denom: impl Into<String>, amount: Uint128) -> Event {
+    Event::new(DISTRIBUTION_EVENT)
+        .add_attribute(DENOM_KEY, denom)
+        .add_attribute(AMOUNT_KEY, amount)
+}
+
+/// Halflife applied at `height` reduced total points by `reduction`
+pub fn halflife(height: u64, reduction: u64) -> Event {
+    Event::new(HALFLIFE_EVENT)
+        .add_attribute(HEIGHT_KEY, height.to_string())
+        .add_attribute(REDUCTION_KEY, reduction.to_string())
+}
+
+/// `controller` unbonded the whole stake of `member`, released at `completion_time`
+pub fn force_unbond(
+    member: &Addr,
+    controller: &Addr,
+    liquid_amount: Uint128,
+    vesting_amount: Uint128,
+    completion_time: Timestamp,
+) -> Event {
+    Event::new(FORCE_UNBOND_EVENT)
+        .add_attribute(MEMBER_KEY, member)
+        .add_attribute(CONTROLLER_KEY, controller)
+        .add_attribute(LIQUID_AMOUNT_KEY, liquid_amount)
+        .add_attribute(VESTING_AMOUNT_KEY, vesting_amount)
+        .add_attribute(COMPLETION_TIME_KEY, completion_time.nanos().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slash_event() {
+        let event = slash("member", Decimal::percent(50), Uint128::new(100));
+        assert_eq!(
+            event,
+            Event::new("slash")
+                .add_attribute("addr", "member")
+                .add_attribute("portion", "0.5")
+                .add_attribute("amount", "100")
+        );
+    }
+
+    #[test]
+    fn distribution_event() {
+        let event = distribution("utgd", Uint128::new(1_000));
+        assert_eq!(
+            event,
+            Event::new("distribution")
+                .add_attribute("denom", "utgd")
+                .add_attribute("amount", "1000")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod events;
 mod gov;
 mod hooks;
 mod msg;
@@ -8,11 +9,13 @@ mod validator;
 pub use gov::{GovProposal, ParamChange, ProtoAny};
 pub use hooks::{request_privileges, Privilege, PrivilegeMsg};
 pub use msg::{BlockParams, ConsensusParams, EvidenceParams, TgradeMsg};
-pub use query::{ListPrivilegedResponse, TgradeQuery, ValidatorVoteResponse};
+pub use query::{
+    ListPrivilegedResponse, TgradeQuery, ValidatorVoteResponse, VoteHistory, VoteWindow,
+};
 pub use sudo::{Evidence, EvidenceType, PrivilegeChangeMsg, TgradeSudoMsg, ValidatorDiff};
 pub use validator::{
-    Ed25519Pubkey, Ed25519PubkeyConversionError, Pubkey, ToAddress, Validator, ValidatorUpdate,
-    ValidatorVote,
+    Ed25519Pubkey, Ed25519PubkeyConversionError, Pubkey, PubkeyConversionError, PubkeyType,
+    Secp256k1Pubkey, ToAddress, ValidatedPubkey, Validator, ValidatorUpdate, ValidatorVote,
 };
 
 // This is a signal, such that any contract that imports these helpers will only run on the tgrade
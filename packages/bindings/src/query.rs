@@ -1,7 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, CustomQuery};
+use cosmwasm_std::{Addr, Binary, CustomQuery, Decimal, StdResult, Storage};
+use cw_storage_plus::Map;
 
 use crate::hooks::Privilege;
 use crate::validator::ValidatorVote;
@@ -28,3 +29,202 @@ pub struct ListPrivilegedResponse {
     // we can guarantee correctly formatted addresses from the Go runtime, use Addr here
     pub privileged: Vec<Addr>,
 }
+
+/// Votes of a single validator in its last `size` recorded blocks, as a ring buffer
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteWindow {
+    /// Bit per recorded block, set if the validator signed it. Block number `n` (counting from
+    /// the first recorded one) is stored at position `n % size`.
+    pub signed: Binary,
+    /// Number of blocks the window covers
+    pub size: u32,
+    /// Number of blocks ever recorded
+    pub total: u64,
+    /// Height of the latest recorded block
+    pub last_height: u64,
+}
+
+impl VoteWindow {
+    pub fn new(size: u32) -> Self {
+        VoteWindow {
+            signed: vec![0; (size as usize + 7) / 8].into(),
+            size,
+            total: 0,
+            last_height: 0,
+        }
+    }
+
+    /// Records the vote of the block at `height`, overwriting the oldest one if the window is
+    /// full. Heights not above the latest recorded one are ignored, so a block is never counted
+    /// twice.
+    pub fn record(&mut self, height: u64, voted: bool) {
+        if self.size == 0 || (self.total > 0 && height <= self.last_height) {
+            return;
+        }
+
+        let pos = (self.total % self.size as u64) as usize;
+        let mut signed = self.signed.to_vec();
+        if voted {
+            signed[pos / 8] |= 1 << (pos % 8);
+        } else {
+            signed[pos / 8] &= !(1 << (pos % 8));
+        }
+        self.signed = signed.into();
+        self.total += 1;
+        self.last_height = height;
+    }
+
+    /// Number of blocks currently in the window
+    pub fn recorded(&self) -> u64 {
+        self.total.min(self.size as u64)
+    }
+
+    /// Number of blocks in the window signed by the validator
+    pub fn signed_blocks(&self) -> u64 {
+        self.signed
+            .iter()
+            .map(|byte| byte.count_ones() as u64)
+            .sum()
+    }
+
+    /// Number of blocks in the window missed by the validator
+    pub fn missed_blocks(&self) -> u64 {
+        self.recorded() - self.signed_blocks()
+    }
+
+    /// Ratio of signed blocks in the window, `None` if nothing was recorded yet
+    pub fn uptime(&self) -> Option<Decimal> {
+        match self.recorded() {
+            0 => None,
+            recorded => Some(Decimal::from_ratio(self.signed_blocks(), recorded)),
+        }
+    }
+}
+
+/// Storage helper aggregating `ValidatorVotes` query results into a `VoteWindow` per validator
+/// address, so contracts can check uptime over the last blocks without rolling their own
+/// bookkeeping. Only blocks the history is fed with are recorded, so windows measure uptime over
+/// recorded blocks, not over a range of heights - contracts jailing validators for not signing
+/// since some height (like tgrade-valset) are better off storing the last signed height.
+pub struct VoteHistory<'a> {
+    windows: Map<'a, &'a [u8], VoteWindow>,
+    /// Size of windows created for validators seen for the first time
+    size: u32,
+}
+
+impl<'a> VoteHistory<'a> {
+    pub const fn new(namespace: &'a str, size: u32) -> Self {
+        VoteHistory {
+            windows: Map::new(namespace),
+            size,
+        }
+    }
+
+    /// Records votes of the block at `height`. Validators not listed are left untouched, so
+    /// their windows cover only blocks they were part of the validator set in.
+    pub fn record(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        votes: &[ValidatorVote],
+    ) -> StdResult<()> {
+        for vote in votes {
+            let address = vote.address.as_slice();
+            let mut window = self
+                .windows
+                .may_load(storage, address)?
+                .unwrap_or_else(|| VoteWindow::new(self.size));
+            window.record(height, vote.voted);
+            self.windows.save(storage, address, &window)?;
+        }
+        Ok(())
+    }
+
+    pub fn window(&self, storage: &dyn Storage, address: &[u8]) -> StdResult<Option<VoteWindow>> {
+        self.windows.may_load(storage, address)
+    }
+
+    /// Forgets the history of the validator, eg. when it leaves the validator set for good
+    pub fn remove(&self, storage: &mut dyn Storage, address: &[u8]) {
+        self.windows.remove(storage, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    fn vote(address: &[u8], voted: bool) -> ValidatorVote {
+        ValidatorVote {
+            address: address.into(),
+            power: 1,
+            voted,
+        }
+    }
+
+    #[test]
+    fn window_wraps_around() {
+        let mut window = VoteWindow::new(3);
+        assert_eq!(window.uptime(), None);
+
+        window.record(1, false);
+        window.record(2, true);
+        assert_eq!(window.recorded(), 2);
+        assert_eq!(window.signed_blocks(), 1);
+        assert_eq!(window.uptime(), Some(Decimal::percent(50)));
+
+        window.record(3, true);
+        // Overwrites the missed block 1
+        window.record(4, true);
+        assert_eq!(window.recorded(), 3);
+        assert_eq!(window.missed_blocks(), 0);
+        assert_eq!(window.uptime(), Some(Decimal::one()));
+
+        window.record(5, false);
+        window.record(6, false);
+        assert_eq!(window.signed_blocks(), 1);
+        assert_eq!(window.missed_blocks(), 2);
+    }
+
+    #[test]
+    fn window_ignores_recorded_heights() {
+        let mut window = VoteWindow::new(10);
+        window.record(5, true);
+        window.record(5, false);
+        window.record(4, false);
+        assert_eq!(window.recorded(), 1);
+        assert_eq!(window.uptime(), Some(Decimal::one()));
+    }
+
+    #[test]
+    fn history_tracks_validators_separately() {
+        let history = VoteHistory::new("votes", 4);
+        let mut storage = MockStorage::new();
+
+        history
+            .record(
+                &mut storage,
+                1,
+                &[vote(b"val1", true), vote(b"val2", false)],
+            )
+            .unwrap();
+        history
+            .record(&mut storage, 2, &[vote(b"val1", false)])
+            .unwrap();
+
+        let val1 = history.window(&storage, b"val1").unwrap().unwrap();
+        assert_eq!(val1.recorded(), 2);
+        assert_eq!(val1.last_height, 2);
+        assert_eq!(val1.uptime(), Some(Decimal::percent(50)));
+
+        let val2 = history.window(&storage, b"val2").unwrap().unwrap();
+        assert_eq!(val2.recorded(), 1);
+        assert_eq!(val2.last_height, 1);
+        assert_eq!(val2.missed_blocks(), 1);
+
+        history.remove(&mut storage, b"val2");
+        assert_eq!(history.window(&storage, b"val2").unwrap(), None);
+        assert_eq!(history.window(&storage, b"val3").unwrap(), None);
+    }
+}
@@ -4,6 +4,7 @@ use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 
+use ripemd160::Ripemd160;
 use sha2::{Digest, Sha256};
 
 use cosmwasm_std::Binary;
@@ -87,7 +88,7 @@ impl Ord for Pubkey {
 /// This type is known to have the correct length, which serves as a minimal validation. This
 /// does not mean it is a valid curve point though.
 ///
-/// Similar types `struct Secp256k1Pubkey([u8; 33])` and `struct Sr25519Pubkey([u8; 32])`
+/// See [Secp256k1Pubkey] for secp256k1 keys. A similar type `struct Sr25519Pubkey([u8; 32])`
 /// should be created on demand.
 ///
 /// ## Examples
@@ -182,6 +183,181 @@ impl TryFrom<Pubkey> for Ed25519Pubkey {
     }
 }
 
+/// A secp256k1 public key in the 33 bytes compressed format.
+///
+/// This type is known to have the correct length and prefix, which serves as a minimal
+/// validation. This does not mean it is a valid curve point though.
+///
+/// ## Examples
+///
+/// ```
+/// # use hex_literal::hex;
+/// use std::convert::TryFrom;
+/// use tg_bindings::{Pubkey, Secp256k1Pubkey, ToAddress};
+///
+/// let pubkey = Pubkey::Secp256k1(hex!("02950e1cdfcb133d6024109fd489f734eeb4502418e538c28481f22bce276f248c").into());
+/// let secp256k1_pubkey = Secp256k1Pubkey::try_from(pubkey).unwrap();
+/// let address = secp256k1_pubkey.to_address();
+/// assert_eq!(address, hex!("7C2BB42A8BE69791EC763E51F5A49BCD41E82237"));
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+pub struct Secp256k1Pubkey(Binary);
+
+impl Secp256k1Pubkey {
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Returns the base64 encoded raw pubkey data.
+    pub fn to_base64(&self) -> String {
+        self.0.to_base64()
+    }
+}
+
+impl ToAddress for Secp256k1Pubkey {
+    fn to_address(&self) -> [u8; 20] {
+        let hash = Ripemd160::digest(&Sha256::digest(&self.0));
+        hash.into()
+    }
+}
+
+impl From<Secp256k1Pubkey> for Pubkey {
+    fn from(secp: Secp256k1Pubkey) -> Self {
+        Pubkey::Secp256k1(secp.0)
+    }
+}
+
+impl<'a> TryFrom<&'a Pubkey> for Secp256k1Pubkey {
+    type Error = PubkeyConversionError;
+
+    fn try_from(pubkey: &'a Pubkey) -> Result<Self, Self::Error> {
+        match pubkey {
+            Pubkey::Secp256k1(data) => match data.as_slice() {
+                [0x02 | 0x03, ..] if data.len() == 33 => Ok(Secp256k1Pubkey(data.clone())),
+                _ => Err(PubkeyConversionError::InvalidData),
+            },
+            _ => Err(PubkeyConversionError::UnsupportedType),
+        }
+    }
+}
+
+impl TryFrom<Pubkey> for Secp256k1Pubkey {
+    type Error = PubkeyConversionError;
+
+    fn try_from(pubkey: Pubkey) -> Result<Self, Self::Error> {
+        Secp256k1Pubkey::try_from(&pubkey)
+    }
+}
+
+/// Types of validator pubkeys which can be validated as a [ValidatedPubkey]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PubkeyType {
+    Ed25519,
+    Secp256k1,
+}
+
+impl PubkeyType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PubkeyType::Ed25519 => "ed25519",
+            PubkeyType::Secp256k1 => "secp256k1",
+        }
+    }
+}
+
+/// A validator pubkey of one of the supported types, with the same minimal validation as the
+/// type specific pubkeys.
+///
+/// It is serialized as the wrapped key, so an `Ed25519Pubkey` stored before can be read as
+/// a `ValidatedPubkey`.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ValidatedPubkey {
+    Ed25519(Ed25519Pubkey),
+    Secp256k1(Secp256k1Pubkey),
+}
+
+impl ValidatedPubkey {
+    pub fn key_type(&self) -> PubkeyType {
+        match self {
+            ValidatedPubkey::Ed25519(_) => PubkeyType::Ed25519,
+            ValidatedPubkey::Secp256k1(_) => PubkeyType::Secp256k1,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        match self {
+            ValidatedPubkey::Ed25519(pubkey) => pubkey.to_vec(),
+            ValidatedPubkey::Secp256k1(pubkey) => pubkey.to_vec(),
+        }
+    }
+
+    /// Returns the base64 encoded raw pubkey data.
+    pub fn to_base64(&self) -> String {
+        match self {
+            ValidatedPubkey::Ed25519(pubkey) => pubkey.to_base64(),
+            ValidatedPubkey::Secp256k1(pubkey) => pubkey.to_base64(),
+        }
+    }
+}
+
+impl ToAddress for ValidatedPubkey {
+    fn to_address(&self) -> [u8; 20] {
+        match self {
+            ValidatedPubkey::Ed25519(pubkey) => pubkey.to_address(),
+            ValidatedPubkey::Secp256k1(pubkey) => pubkey.to_address(),
+        }
+    }
+}
+
+impl From<ValidatedPubkey> for Pubkey {
+    fn from(pubkey: ValidatedPubkey) -> Self {
+        match pubkey {
+            ValidatedPubkey::Ed25519(pubkey) => pubkey.into(),
+            ValidatedPubkey::Secp256k1(pubkey) => pubkey.into(),
+        }
+    }
+}
+
+impl From<Ed25519Pubkey> for ValidatedPubkey {
+    fn from(pubkey: Ed25519Pubkey) -> Self {
+        ValidatedPubkey::Ed25519(pubkey)
+    }
+}
+
+#[derive(Debug)]
+pub enum PubkeyConversionError {
+    /// Pubkey type is not supported by `ValidatedPubkey`
+    UnsupportedType,
+    /// Pubkey has invalid length or format
+    InvalidData,
+}
+
+impl<'a> TryFrom<&'a Pubkey> for ValidatedPubkey {
+    type Error = PubkeyConversionError;
+
+    fn try_from(pubkey: &'a Pubkey) -> Result<Self, Self::Error> {
+        match pubkey {
+            Pubkey::Ed25519(_) => Ed25519Pubkey::try_from(pubkey)
+                .map(ValidatedPubkey::Ed25519)
+                .map_err(|_| PubkeyConversionError::InvalidData),
+            Pubkey::Secp256k1(_) => {
+                Secp256k1Pubkey::try_from(pubkey).map(ValidatedPubkey::Secp256k1)
+            }
+            _ => Err(PubkeyConversionError::UnsupportedType),
+        }
+    }
+}
+
+impl TryFrom<Pubkey> for ValidatedPubkey {
+    type Error = PubkeyConversionError;
+
+    fn try_from(pubkey: Pubkey) -> Result<Self, Self::Error> {
+        ValidatedPubkey::try_from(&pubkey)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +391,39 @@ mod tests {
         let address = pubkey.to_address();
         assert_eq!(address, hex!("0CDA3F47EF3C4906693B170EF650EB968C5F4B2C"))
     }
+
+    #[test]
+    fn secp256k1pubkey_address() {
+        // Test values from https://github.com/informalsystems/tendermint-rs/blob/v0.18.1/tendermint/src/account.rs#L153-L192
+        let pubkey = Pubkey::Secp256k1(
+            hex!("02950E1CDFCB133D6024109FD489F734EEB4502418E538C28481F22BCE276F248C").into(),
+        );
+        let pubkey = Secp256k1Pubkey::try_from(pubkey).unwrap();
+        let address = pubkey.to_address();
+        assert_eq!(address, hex!("7C2BB42A8BE69791EC763E51F5A49BCD41E82237"))
+    }
+
+    #[test]
+    fn secp256k1pubkey_requires_compressed_format() {
+        let uncompressed = Pubkey::Secp256k1([4u8; 65].to_vec().into());
+        Secp256k1Pubkey::try_from(uncompressed).unwrap_err();
+        let wrong_prefix = Pubkey::Secp256k1([4u8; 33].to_vec().into());
+        Secp256k1Pubkey::try_from(wrong_prefix).unwrap_err();
+    }
+
+    #[test]
+    fn validated_pubkey_reads_stored_ed25519_pubkey() {
+        let pubkey = Ed25519Pubkey([7u8; 32]);
+        let stored = cosmwasm_std::to_vec(&pubkey).unwrap();
+        let validated: ValidatedPubkey = cosmwasm_std::from_slice(&stored).unwrap();
+        assert_eq!(validated, ValidatedPubkey::Ed25519(pubkey));
+        assert_eq!(validated.key_type(), PubkeyType::Ed25519);
+
+        let pubkey =
+            ValidatedPubkey::try_from(Pubkey::Secp256k1([2u8; 33].to_vec().into())).unwrap();
+        let stored = cosmwasm_std::to_vec(&pubkey).unwrap();
+        let read: ValidatedPubkey = cosmwasm_std::from_slice(&stored).unwrap();
+        assert_eq!(read, pubkey);
+        assert_eq!(read.key_type(), PubkeyType::Secp256k1);
+    }
 }
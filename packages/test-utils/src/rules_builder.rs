@@ -34,6 +34,9 @@ impl RulesBuilder {
             quorum: self.quorum,
             threshold: self.threshold,
             allow_end_early: self.allow_end_early,
+            abstain_non_voters: false,
+            proposer_policy: None,
+            tie_break: None,
         }
     }
 }
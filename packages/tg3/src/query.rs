@@ -59,6 +59,9 @@ pub enum Status {
     Passed = 4,
     /// voting is over it passed, and the proposal was executed
     Executed = 5,
+    /// voting is over and it did pass, but it was not executed within the execution period, so
+    /// it cannot be executed anymore
+    PassedExpired = 6,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -11,7 +11,7 @@ use crate::msg::Tg4ExecuteMsg;
 use crate::query::HooksResponse;
 use crate::{
     member_key, AdminResponse, Member, MemberInfo, MemberListResponse, MemberResponse, Tg4QueryMsg,
-    TOTAL_KEY,
+    TotalPointsResponse, TOTAL_KEY,
 };
 
 pub type SubMsg = cosmwasm_std::SubMsg<TgradeMsg>;
@@ -93,6 +93,19 @@ impl Tg4Contract {
         querier.query(&query)
     }
 
+    /// Read the total points at the beginning of the given block
+    pub fn total_points_at_height<Q: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        height: u64,
+    ) -> StdResult<u64> {
+        let query = self.encode_smart_query(Tg4QueryMsg::TotalPoints {
+            at_height: Some(height),
+        })?;
+        let res: TotalPointsResponse = querier.query(&query)?;
+        Ok(res.points)
+    }
+
     /// Check if this address is a member, and if so, with which points
     pub fn is_member<Q: CustomQuery>(
         &self,
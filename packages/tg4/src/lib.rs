@@ -9,5 +9,5 @@ pub use crate::msg::Tg4ExecuteMsg;
 pub use crate::query::{
     member_key, AdminResponse, HooksResponse, Member, MemberInfo, MemberListResponse,
     MemberResponse, Tg4QueryMsg, TotalPointsResponse, MEMBERS_CHANGELOG, MEMBERS_CHECKPOINTS,
-    MEMBERS_KEY, TOTAL_KEY,
+    MEMBERS_KEY, TOTAL_CHANGELOG, TOTAL_CHECKPOINTS, TOTAL_KEY,
 };
@@ -6,8 +6,9 @@ use serde::{Deserialize, Serialize};
 pub enum Tg4QueryMsg {
     /// Return AdminResponse
     Admin {},
-    /// Return TotalPointsResponse
-    TotalPoints {},
+    /// Return TotalPointsResponse. If at_height is set, returns total points at the
+    /// beginning of the given block.
+    TotalPoints { at_height: Option<u64> },
     /// Returns MemberListResponse.
     /// The result is sorted by address ascending
     ListMembers {
@@ -103,6 +104,8 @@ pub struct HooksResponse {
 
 /// TOTAL_KEY is meant for raw queries
 pub const TOTAL_KEY: &str = "total";
+pub const TOTAL_CHECKPOINTS: &str = "total__checkpoints";
+pub const TOTAL_CHANGELOG: &str = "total__changelog";
 pub const MEMBERS_KEY: &str = "members";
 pub const MEMBERS_CHECKPOINTS: &str = "members__checkpoints";
 pub const MEMBERS_CHANGELOG: &str = "members__changelog";
@@ -1,6 +1,7 @@
 mod hooks;
 mod jailing;
 mod member_indexes;
+mod migrations;
 mod preauth;
 mod slashers;
 mod time;
@@ -8,6 +9,7 @@ mod time;
 pub use hooks::{HookError, Hooks};
 pub use jailing::{JailMsg, JailingDuration};
 pub use member_indexes::{members, ADMIN, HOOKS, PREAUTH_HOOKS, PREAUTH_SLASHING, SLASHERS, TOTAL};
+pub use migrations::{Migration, MigrationHistory, MigrationHistoryResponse, MigrationRecord};
 pub use preauth::{Preauth, PreauthError};
 pub use slashers::{validate_portion, SlashMsg, SlasherError, Slashers};
 pub use time::{Duration, Expiration};
@@ -1,18 +1,24 @@
 use cosmwasm_std::Addr;
 
 use cw_controllers::Admin;
-use cw_storage_plus::{Index, IndexList, IndexedSnapshotMap, Item, MultiIndex, Strategy};
+use cw_storage_plus::{Index, IndexList, IndexedSnapshotMap, MultiIndex, SnapshotItem, Strategy};
 
-use tg4::{MemberInfo, TOTAL_KEY};
+use tg4::{MemberInfo, TOTAL_CHANGELOG, TOTAL_CHECKPOINTS, TOTAL_KEY};
 
 use crate::{Hooks, Preauth, Slashers};
 
 pub const ADMIN: Admin = Admin::new("admin");
 pub const HOOKS: Hooks = Hooks::new("tg4-hooks");
 pub const PREAUTH_HOOKS: Preauth = Preauth::new("tg4-preauth");
-pub const SLASHERS: Slashers = Slashers::new("tg4-slashers");
+pub const SLASHERS: Slashers = Slashers::new("tg4-slashers", "tg4-slasher-limits");
 pub const PREAUTH_SLASHING: Preauth = Preauth::new("tg4-preauth_slashing");
-pub const TOTAL: Item<u64> = Item::new(TOTAL_KEY);
+/// Total points, snapshotted every block so it can be queried at given height
+pub const TOTAL: SnapshotItem<u64> = SnapshotItem::new(
+    TOTAL_KEY,
+    TOTAL_CHECKPOINTS,
+    TOTAL_CHANGELOG,
+    Strategy::EveryBlock,
+);
 
 pub struct MemberIndexes<'a> {
     // Points (multi-)index (deserializing the (hidden) pk to Addr)
@@ -0,0 +1,178 @@
+use schemars::JsonSchema;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use cw_utils::ensure_from_older_version;
+
+/// Single contract migration with the steps applied during it
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MigrationRecord {
+    /// Contract version before the migration
+    pub from_version: String,
+    /// Contract version after the migration
+    pub to_version: String,
+    /// Block height the migration was executed at
+    pub height: u64,
+    /// Names of the steps applied, in order of execution
+    pub steps: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MigrationHistoryResponse {
+    pub migrations: Vec<MigrationRecord>,
+}
+
+// store all migrations in one item. Contracts are migrated rarely enough for it to stay small.
+pub struct MigrationHistory<'a>(Item<'a, Vec<MigrationRecord>>);
+
+impl<'a> MigrationHistory<'a> {
+    pub const fn new(history_key: &'a str) -> Self {
+        MigrationHistory(Item::new(history_key))
+    }
+
+    /// Verifies the stored contract name and version (see `ensure_from_older_version`), bumps
+    /// the stored version, and returns the migration to run steps against.
+    pub fn start(
+        &self,
+        storage: &mut dyn Storage,
+        contract_name: &str,
+        contract_version: &str,
+        height: u64,
+    ) -> StdResult<Migration> {
+        let from_version = ensure_from_older_version(storage, contract_name, contract_version)?;
+        let to_version = contract_version
+            .parse()
+            .map_err(|_| StdError::generic_err("Invalid contract version"))?;
+        let applied = self
+            .query(storage)?
+            .migrations
+            .into_iter()
+            .flat_map(|migration| migration.steps)
+            .collect();
+
+        Ok(Migration {
+            from_version,
+            to_version,
+            height,
+            applied,
+            steps: vec![],
+        })
+    }
+
+    /// Records the migration in history, together with all steps applied during it.
+    pub fn finish(&self, storage: &mut dyn Storage, migration: Migration) -> StdResult<()> {
+        let mut history = self.0.may_load(storage)?.unwrap_or_default();
+        history.push(MigrationRecord {
+            from_version: migration.from_version.to_string(),
+            to_version: migration.to_version.to_string(),
+            height: migration.height,
+            steps: migration.steps,
+        });
+        self.0.save(storage, &history)
+    }
+
+    pub fn query(&self, storage: &dyn Storage) -> StdResult<MigrationHistoryResponse> {
+        let migrations = self.0.may_load(storage)?.unwrap_or_default();
+        Ok(MigrationHistoryResponse { migrations })
+    }
+}
+
+/// Migration in progress. Steps are checked in the order they are called in the contract
+/// `migrate` entry point.
+pub struct Migration {
+    from_version: Version,
+    to_version: Version,
+    height: u64,
+    /// Steps applied by any previous migration
+    applied: Vec<String>,
+    /// Steps applied by this migration
+    steps: Vec<String>,
+}
+
+impl Migration {
+    /// Version of the contract being migrated from
+    pub fn from_version(&self) -> &Version {
+        &self.from_version
+    }
+
+    /// Returns whether the step has to be run: it was never applied before, and the version
+    /// migrated from matches `applies_to` (eg. `"<=0.17.1"`). When true, the step is marked as
+    /// applied, so the caller is expected to run it right away.
+    pub fn step(&mut self, name: &str, applies_to: &str) -> StdResult<bool> {
+        let req: VersionReq = applies_to
+            .parse()
+            .map_err(|_| StdError::generic_err(format!("Invalid version bound: {}", applies_to)))?;
+
+        let applied = self.applied.iter().chain(&self.steps).any(|s| s == name);
+        if applied || !req.matches(&self.from_version) {
+            return Ok(false);
+        }
+
+        self.steps.push(name.to_owned());
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    const MIGRATIONS: MigrationHistory = MigrationHistory::new("migrations");
+
+    #[test]
+    fn steps_run_once_for_matching_versions() {
+        let mut storage = MockStorage::new();
+        cw2::set_contract_version(&mut storage, "contract", "0.16.0").unwrap();
+
+        let mut migration = MIGRATIONS
+            .start(&mut storage, "contract", "0.17.0", 10)
+            .unwrap();
+        assert_eq!(migration.from_version(), &Version::new(0, 16, 0));
+        assert!(migration.step("first", "<0.17.0").unwrap());
+        assert!(!migration.step("newer", "<0.16.0").unwrap());
+        // step registered twice is only run once
+        assert!(!migration.step("first", "<0.17.0").unwrap());
+        MIGRATIONS.finish(&mut storage, migration).unwrap();
+
+        // already applied steps are skipped, even if the version still matches
+        let mut migration = MIGRATIONS
+            .start(&mut storage, "contract", "0.17.1", 20)
+            .unwrap();
+        assert!(!migration.step("first", "<=0.17.0").unwrap());
+        assert!(migration.step("second", "<=0.17.0").unwrap());
+        MIGRATIONS.finish(&mut storage, migration).unwrap();
+
+        let history = MIGRATIONS.query(&storage).unwrap();
+        assert_eq!(
+            history.migrations,
+            vec![
+                MigrationRecord {
+                    from_version: "0.16.0".to_owned(),
+                    to_version: "0.17.0".to_owned(),
+                    height: 10,
+                    steps: vec!["first".to_owned()],
+                },
+                MigrationRecord {
+                    from_version: "0.17.0".to_owned(),
+                    to_version: "0.17.1".to_owned(),
+                    height: 20,
+                    steps: vec!["second".to_owned()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_bound_fails() {
+        let mut storage = MockStorage::new();
+        cw2::set_contract_version(&mut storage, "contract", "0.16.0").unwrap();
+
+        let mut migration = MIGRATIONS
+            .start(&mut storage, "contract", "0.17.0", 10)
+            .unwrap();
+        migration.step("first", "not a version").unwrap_err();
+    }
+}
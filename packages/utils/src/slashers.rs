@@ -3,28 +3,47 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Storage};
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
-// store all slasher addresses in one item.
-pub struct Slashers<'a>(Item<'a, Vec<Addr>>);
+// store all slasher addresses in one item, and limits of slashers which have them in a map.
+pub struct Slashers<'a> {
+    slashers: Item<'a, Vec<Addr>>,
+    limits: Map<'a, &'a Addr, Decimal>,
+}
 
 impl<'a> Slashers<'a> {
-    pub const fn new(storage_key: &'a str) -> Self {
-        Slashers(Item::new(storage_key))
+    pub const fn new(storage_key: &'a str, limits_key: &'a str) -> Self {
+        Slashers {
+            slashers: Item::new(storage_key),
+            limits: Map::new(limits_key),
+        }
     }
 
     pub fn instantiate(&self, storage: &mut dyn Storage) -> StdResult<()> {
-        self.0.save(storage, &vec![])
+        self.slashers.save(storage, &vec![])
     }
 
-    pub fn add_slasher(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), SlasherError> {
-        let mut slashers = self.0.load(storage)?;
-        if !slashers.iter().any(|h| h == &addr) {
-            slashers.push(addr);
-        } else {
+    /// Registers the slasher. `max_portion` limits how much it can slash at once, no limit
+    /// (up to 100%) if not set.
+    pub fn add_slasher(
+        &self,
+        storage: &mut dyn Storage,
+        addr: Addr,
+        max_portion: Option<Decimal>,
+    ) -> Result<(), SlasherError> {
+        if let Some(max_portion) = max_portion {
+            validate_portion(max_portion)?;
+        }
+
+        let mut slashers = self.slashers.load(storage)?;
+        if slashers.iter().any(|h| h == &addr) {
             return Err(SlasherError::SlasherAlreadyRegistered(addr.to_string()));
         }
-        Ok(self.0.save(storage, &slashers)?)
+        if let Some(max_portion) = max_portion {
+            self.limits.save(storage, &addr, &max_portion)?;
+        }
+        slashers.push(addr);
+        Ok(self.slashers.save(storage, &slashers)?)
     }
 
     pub fn remove_slasher(
@@ -32,24 +51,49 @@ impl<'a> Slashers<'a> {
         storage: &mut dyn Storage,
         addr: Addr,
     ) -> Result<(), SlasherError> {
-        let mut slashers = self.0.load(storage)?;
+        let mut slashers = self.slashers.load(storage)?;
         if let Some(p) = slashers.iter().position(|x| x == &addr) {
             slashers.remove(p);
         } else {
             return Err(SlasherError::SlasherNotRegistered(addr.to_string()));
         }
-        Ok(self.0.save(storage, &slashers)?)
+        self.limits.remove(storage, &addr);
+        Ok(self.slashers.save(storage, &slashers)?)
     }
 
     pub fn is_slasher(&self, storage: &dyn Storage, addr: &Addr) -> StdResult<bool> {
-        let slashers = self.0.load(storage)?;
+        let slashers = self.slashers.load(storage)?;
         Ok(slashers.contains(addr))
     }
 
     pub fn list_slashers(&self, storage: &dyn Storage) -> StdResult<Vec<String>> {
-        let slashers = self.0.load(storage)?;
+        let slashers = self.slashers.load(storage)?;
         Ok(slashers.into_iter().map(String::from).collect())
     }
+
+    /// Returns the maximum portion the address can slash, `None` if it is not a slasher
+    pub fn slasher_limit(&self, storage: &dyn Storage, addr: &Addr) -> StdResult<Option<Decimal>> {
+        if !self.is_slasher(storage, addr)? {
+            return Ok(None);
+        }
+        let limit = self.limits.may_load(storage, addr)?;
+        Ok(Some(limit.unwrap_or_else(Decimal::one)))
+    }
+
+    /// Validates the portion slashed by the given slasher, both against the (0, 1] range and
+    /// the slasher limit. Checking if `addr` is a slasher at all is up to the caller.
+    pub fn validate_slash(
+        &self,
+        storage: &dyn Storage,
+        addr: &Addr,
+        portion: Decimal,
+    ) -> Result<(), SlasherError> {
+        validate_portion(portion)?;
+        match self.limits.may_load(storage, addr)? {
+            Some(max) if portion > max => Err(SlasherError::PortionAboveLimit { portion, max }),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// A common (sort of) interface for adding/removing slashers and slashing.
@@ -67,6 +111,7 @@ impl<'a> Slashers<'a> {
 ///
 /// let slash_msg = to_binary(&SlashMsg::AddSlasher {
 ///     addr: "some_other_contract".to_string(),
+///     max_portion: None,
 /// }).unwrap();
 ///
 /// let res = Response::new()
@@ -75,8 +120,13 @@ impl<'a> Slashers<'a> {
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum SlashMsg {
-    /// Adds slasher for contract if there are enough `slasher_preauths` left
-    AddSlasher { addr: String },
+    /// Adds slasher for contract if there are enough `slasher_preauths` left.
+    /// `max_portion` limits how much the slasher can slash at once.
+    AddSlasher {
+        addr: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_portion: Option<Decimal>,
+    },
     /// Removes slasher for contract
     RemoveSlasher { addr: String },
     /// Slash engagement points from address
@@ -96,6 +146,9 @@ pub enum SlasherError {
 
     #[error("Invalid portion {0}, must be (0, 1]")]
     InvalidPortion(Decimal),
+
+    #[error("Portion {portion} exceeds the slasher limit {max}")]
+    PortionAboveLimit { portion: Decimal, max: Decimal },
 }
 
 pub fn validate_portion(portion: Decimal) -> Result<(), SlasherError> {
@@ -3,7 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
-use tg_voting_contract::msg::ProposalCreationResponse;
+use tg_voting_contract::msg::{
+    ExecutionResultResponse, PowerBoostResponse, PowerProviderQueryMsg, ProposalCreationResponse,
+    ProposalDataResponse, UpdateRulesMsg, VoteBatchResponse,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -16,4 +19,30 @@ fn main() {
         &out_dir,
         "ProposalCreationResponse",
     );
+    export_schema_with_title(
+        &schema_for!(ProposalDataResponse),
+        &out_dir,
+        "ProposalDataResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(VoteBatchResponse),
+        &out_dir,
+        "VoteBatchResponse",
+    );
+    export_schema_with_title(
+        &schema_for!(ExecutionResultResponse),
+        &out_dir,
+        "ExecutionResultResponse",
+    );
+    export_schema_with_title(&schema_for!(UpdateRulesMsg), &out_dir, "UpdateRulesMsg");
+    export_schema_with_title(
+        &schema_for!(PowerProviderQueryMsg),
+        &out_dir,
+        "PowerProviderQueryMsg",
+    );
+    export_schema_with_title(
+        &schema_for!(PowerBoostResponse),
+        &out_dir,
+        "PowerBoostResponse",
+    );
 }
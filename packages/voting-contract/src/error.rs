@@ -18,6 +18,106 @@ pub enum ContractError {
     #[error("Invalid voting period, must be 1-365 days: {0}")]
     InvalidVotingPeriod(u32),
 
+    #[error("Invalid proposer policy, minimal points must be greater than zero and allow list cannot be empty")]
+    InvalidProposerPolicy {},
+
+    #[error("Sender is not allowed to create proposals")]
+    NotAllowedToPropose {},
+
+    #[error("Invalid proposal limits, must be greater than zero")]
+    InvalidProposalLimits {},
+
+    #[error("Proposal payload too large: {size} bytes, max {max}")]
+    ProposalPayloadTooLarge { size: usize, max: u32 },
+
+    #[error("Too many proposal messages: {count}, max {max}")]
+    TooManyProposalMessages { count: usize, max: u32 },
+
+    #[error("Too many open proposals, max {max}")]
+    TooManyOpenProposals { max: u32 },
+
+    #[error("Too many open proposals of the proposer, max {max}")]
+    TooManyOpenProposalsByProposer { max: u32 },
+
+    #[error("Invalid proposal content rules, minimal lengths cannot exceed maximal ones, and maximal lengths must be greater than zero")]
+    InvalidProposalContentRules {},
+
+    #[error("Proposal {field} length {len} out of allowed range: min {min}, max {max:?}")]
+    InvalidProposalContentLength {
+        field: &'static str,
+        len: usize,
+        min: u32,
+        max: Option<u32>,
+    },
+
+    #[error("Proposal {field} contains control characters")]
+    ProposalControlCharacters { field: &'static str },
+
+    #[error("Too many proposal tags: {count}, max {max}")]
+    TooManyTags { count: usize, max: usize },
+
+    #[error("Invalid proposal tag '{0}', must be 1-32 lowercase letters, digits, '-' or '_'")]
+    InvalidTag(String),
+
+    #[error("Duplicated proposal tag '{0}'")]
+    DuplicatedTag(String),
+
+    #[error("Proposal idempotency key has to be between 1 and {0} bytes long")]
+    InvalidIdempotencyKey(usize),
+
+    #[error(
+        "Invalid proposal template name '{0}', must be 1-32 lowercase letters, digits, '-' or '_'"
+    )]
+    InvalidTemplateName(String),
+
+    #[error("Invalid proposal template, required sections and allowed kinds cannot be empty, and description has to contain all required sections")]
+    InvalidProposalTemplate {},
+
+    #[error("Unknown proposal template '{0}'")]
+    UnknownProposalTemplate(String),
+
+    #[error("Proposal title has to start with '{prefix}'")]
+    TemplateTitleMismatch { prefix: String },
+
+    #[error("Proposal description is missing required section '{0}'")]
+    TemplateSectionMissing(String),
+
+    #[error("Proposal kind '{0}' is not allowed by the template")]
+    TemplateKindNotAllowed(String),
+
+    #[error("Invalid ballot pruning config, values must be greater than zero")]
+    InvalidBallotPruning {},
+
+    #[error("Ballot pruning is not enabled")]
+    BallotPruningDisabled {},
+
+    #[error("Invalid auto close config, values must be greater than zero")]
+    InvalidAutoClose {},
+
+    #[error("Invalid vote bonus caps, values must be greater than zero")]
+    InvalidVoteBonusCaps {},
+
+    #[error("Vote bonuses are not enabled")]
+    VoteBonusDisabled {},
+
+    #[error("Too many bonus voters: {count}, max {max}")]
+    TooManyBonusVoters { count: usize, max: u32 },
+
+    #[error("Invalid vote bonus of {points} points, must be between 1 and {max}")]
+    InvalidVoteBonus { points: u64, max: u64 },
+
+    #[error("Duplicated bonus voter {0}")]
+    DuplicatedBonusVoter(String),
+
+    #[error("Invalid power boost caps, values must be greater than zero")]
+    InvalidPowerBoostCaps {},
+
+    #[error("Power boost caps are required when a power provider is set")]
+    MissingPowerBoostCaps {},
+
+    #[error("Vote batch cannot be empty")]
+    EmptyVoteBatch {},
+
     #[error("Proposal is not open")]
     NotOpen {},
 
@@ -35,4 +135,10 @@ pub enum ContractError {
 
     #[error("Proposal must have passed and not yet been executed")]
     WrongExecuteStatus {},
+
+    #[error("Execution period of the passed proposal is over")]
+    ExecutionPeriodExpired {},
+
+    #[error("Execution period must be greater than zero")]
+    InvalidExecutionPeriod {},
 }
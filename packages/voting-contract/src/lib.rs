@@ -10,15 +10,27 @@ use serde::Serialize;
 
 use ballots::ballots;
 pub use error::ContractError;
+use msg::{
+    NamedProposalTemplate, OpenProposalsResponse, PowerBoost, PowerBoostResponse,
+    PowerBoostsResponse, PowerProviderQueryMsg, ProposalDataResponse, ProposalTemplateListResponse,
+    UpdateRulesMsg, VoteBatchMode, VoteBatchResponse, VoteBonusesResponse,
+};
 use state::{
-    next_id, proposals, Config, Proposal, ProposalListResponse, ProposalResponse,
-    TextProposalListResponse, Votes, VotingRules, CONFIG, TEXT_PROPOSALS,
+    count_open_proposal, next_id, open_proposals, proposals, uncount_expired_proposals,
+    uncount_open_proposal, validate_idempotency_key, validate_tags, validate_template_name,
+    AutoClose, BallotPruning, Config, MessageExecutionResult, PowerBoostCaps, Proposal,
+    ProposalContentRules, ProposalLimits, ProposalListResponse, ProposalResponse, ProposalTemplate,
+    TextProposalListResponse, VoteBonus, VoteBonusCaps, Votes, VotingRules, CONFIG,
+    EXECUTING_PROPOSAL, EXECUTION_RESULTS, FINALIZED_PROPOSALS, POWER_BOOSTS,
+    PROPOSALS_BY_EXPIRATION, PROPOSALS_BY_IDEMPOTENCY_KEY, PROPOSALS_BY_TAG, PROPOSAL_TEMPLATES,
+    TEXT_PROPOSALS, VOTE_BONUSES,
 };
 
 use cosmwasm_std::{
-    Addr, BlockInfo, CustomQuery, Deps, DepsMut, Env, MessageInfo, Order, StdResult, Storage,
+    to_binary, to_vec, Addr, Binary, BlockInfo, CosmosMsg, CustomQuery, Deps, DepsMut, Empty, Env,
+    Event, MessageInfo, Order, Reply, StdResult, Storage, SubMsg, SubMsgResult, Uint64,
 };
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, PrefixBound};
 use cw_utils::maybe_addr;
 use tg3::{
     Status, Vote, VoteInfo, VoteListResponse, VoteResponse, VoterDetail, VoterListResponse,
@@ -26,32 +38,139 @@ use tg3::{
 };
 use tg4::{Member, Tg4Contract};
 use tg_bindings::TgradeMsg;
-use tg_utils::Expiration;
+use tg_utils::{Duration, Expiration};
 
 type Response = cosmwasm_std::Response<TgradeMsg>;
 
+/// Reply id of messages dispatched by `execute_with_results`. Contracts using it have to route
+/// replies with this id to `execution_reply`. Chosen high so it doesn't collide with contracts' own
+/// reply ids.
+pub const EXECUTION_REPLY_ID: u64 = u64::MAX;
+
+/// Settings of the voting contract provided on instantiation, see `Config` for their meaning
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstantiateConfig {
+    pub rules: VotingRules,
+    pub group_addr: String,
+    pub limits: ProposalLimits,
+    pub pruning: Option<BallotPruning>,
+    pub content_rules: ProposalContentRules,
+    pub auto_close: Option<AutoClose>,
+    pub legacy_total_points: bool,
+    pub execution_period: Option<Duration>,
+    pub vote_bonus: Option<VoteBonusCaps>,
+    pub power_provider: Option<String>,
+    pub power_boost_caps: Option<PowerBoostCaps>,
+}
+
+impl InstantiateConfig {
+    /// Config with all optional features disabled
+    pub fn new(rules: VotingRules, group_addr: impl Into<String>) -> Self {
+        InstantiateConfig {
+            rules,
+            group_addr: group_addr.into(),
+            limits: ProposalLimits::default(),
+            pruning: None,
+            content_rules: ProposalContentRules::default(),
+            auto_close: None,
+            legacy_total_points: false,
+            execution_period: None,
+            vote_bonus: None,
+            power_provider: None,
+            power_boost_caps: None,
+        }
+    }
+}
+
 pub fn instantiate<Q: CustomQuery>(
     deps: DepsMut<Q>,
-    rules: VotingRules,
-    group_addr: &str,
+    config: InstantiateConfig,
 ) -> Result<Response, ContractError> {
-    let group_contract = Tg4Contract(deps.api.addr_validate(group_addr).map_err(|_| {
+    let group_addr = config.group_addr;
+    let group_contract = Tg4Contract(deps.api.addr_validate(&group_addr).map_err(|_| {
         ContractError::InvalidGroup {
-            addr: group_addr.to_owned(),
+            addr: group_addr.clone(),
         }
     })?);
+    let power_provider = config
+        .power_provider
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
 
     let cfg = Config {
-        rules,
+        rules: config.rules,
         group_contract,
+        limits: config.limits,
+        pruning: config.pruning,
+        content: config.content_rules,
+        auto_close: config.auto_close,
+        legacy_total_points: config.legacy_total_points,
+        execution_period: config.execution_period,
+        vote_bonus: config.vote_bonus,
+        power_provider,
+        power_boost_caps: config.power_boost_caps,
     };
-
-    cfg.rules.validate()?;
+    cfg.validate()?;
     CONFIG.save(deps.storage, &cfg)?;
 
     Ok(Response::default())
 }
 
+/// Settings of the voting contract which can be changed on migration, the ones not set are left
+/// unchanged
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigOverrides {
+    pub limits: Option<ProposalLimits>,
+    pub pruning: Option<BallotPruning>,
+    pub content_rules: Option<ProposalContentRules>,
+    pub auto_close: Option<AutoClose>,
+    pub execution_period: Option<Duration>,
+    pub vote_bonus: Option<VoteBonusCaps>,
+    pub power_provider: Option<String>,
+    pub power_boost_caps: Option<PowerBoostCaps>,
+}
+
+/// Applies `overrides` to the stored config, meant to be called from contracts' `migrate`.
+/// The resulting config is validated as on instantiation.
+pub fn override_config<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    overrides: ConfigOverrides,
+) -> Result<Config, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+    if let Some(limits) = overrides.limits {
+        cfg.limits = limits;
+    }
+    if let Some(pruning) = overrides.pruning {
+        cfg.pruning = Some(pruning);
+    }
+    if let Some(content_rules) = overrides.content_rules {
+        cfg.content = content_rules;
+    }
+    if let Some(auto_close) = overrides.auto_close {
+        cfg.auto_close = Some(auto_close);
+    }
+    if let Some(execution_period) = overrides.execution_period {
+        cfg.execution_period = Some(execution_period);
+    }
+    if let Some(vote_bonus) = overrides.vote_bonus {
+        cfg.vote_bonus = Some(vote_bonus);
+    }
+    if let Some(power_provider) = overrides.power_provider {
+        cfg.power_provider = Some(deps.api.addr_validate(&power_provider)?);
+    }
+    if let Some(power_boost_caps) = overrides.power_boost_caps {
+        cfg.power_boost_caps = Some(power_boost_caps);
+    }
+    cfg.validate()?;
+    CONFIG.save(deps.storage, &cfg)?;
+    Ok(cfg)
+}
+
+/// Creates a new proposal. `vote_bonuses` grant listed voters bonus points on top of their group
+/// points, for this proposal only. They are checked against `vote_bonus` caps of the config.
+/// If the sender already created a proposal with the same `idempotency_key`, its id is returned
+/// (with `existing` set in the response data) and no new proposal is created.
+#[allow(clippy::too_many_arguments)]
 pub fn propose<P, Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
@@ -59,22 +178,104 @@ pub fn propose<P, Q: CustomQuery>(
     title: String,
     description: String,
     proposal: P,
+    tags: Vec<String>,
+    vote_bonuses: Vec<VoteBonus>,
+    idempotency_key: Option<String>,
 ) -> Result<Response, ContractError>
 where
     P: DeserializeOwned + Serialize,
 {
     let cfg = CONFIG.load(deps.storage)?;
 
+    if let Some(key) = &idempotency_key {
+        validate_idempotency_key(key)?;
+        let existing = PROPOSALS_BY_IDEMPOTENCY_KEY.may_load(deps.storage, (&info.sender, key))?;
+        if let Some(id) = existing {
+            let prop: Proposal<P> = proposals().load(deps.storage, id)?;
+            let data = ProposalDataResponse {
+                existing: true,
+                ..proposal_data_response(id, &prop)
+            };
+            return Ok(Response::new()
+                .add_attribute("action", "propose")
+                .add_attribute("sender", info.sender)
+                .add_attribute("proposal_id", id.to_string())
+                .add_attribute("existing", "true")
+                .set_data(to_binary(&data)?));
+        }
+    }
+
+    cfg.content.check(&title, &description)?;
+    validate_tags(&tags)?;
+    let bonuses = match &cfg.vote_bonus {
+        _ if vote_bonuses.is_empty() => vec![],
+        Some(caps) => {
+            caps.check(&vote_bonuses)?;
+            // Bonus points are counted in total points, so only members able to vote may get them
+            vote_bonuses
+                .into_iter()
+                .map(|bonus| {
+                    let voter = deps.api.addr_validate(&bonus.voter)?;
+                    cfg.group_contract
+                        .is_voting_member(&deps.querier, voter.as_str())?;
+                    Ok((voter, bonus.points))
+                })
+                .collect::<StdResult<Vec<_>>>()?
+        }
+        None => return Err(ContractError::VoteBonusDisabled {}),
+    };
+    let bonus_of = |addr: &Addr| {
+        bonuses
+            .iter()
+            .find(|(voter, _)| voter == addr)
+            .map_or(0, |(_, points)| *points)
+    };
+
     // Only members of the multisig can create a proposal
     // Additional check if points >= 1
     let vote_power = cfg
         .group_contract
         .is_voting_member(&deps.querier, info.sender.as_str())?;
 
+    if let Some(policy) = &cfg.rules.proposer_policy {
+        if !policy.allows(info.sender.as_str(), vote_power) {
+            return Err(ContractError::NotAllowedToPropose {});
+        }
+    }
+
+    uncount_expired_proposals(deps.storage, &env.block)?;
+    if cfg.limits.limits_open_proposals() {
+        let (open, open_by_proposer) =
+            open_proposals(deps.storage, &env.block, Some(&info.sender))?;
+        cfg.limits.check_open(open, open_by_proposer)?;
+    }
+
+    // total points are taken at the same height as voting power of members, so
+    // membership changes in the proposal creation block don't skew the quorum
+    let total_points = if cfg.legacy_total_points {
+        cfg.group_contract.total_points(&deps.querier)?
+    } else {
+        cfg.group_contract
+            .total_points_at_height(&deps.querier, env.block.height)?
+    };
+
     // calculate expiry time
     let expires =
         Expiration::at_timestamp(env.block.time.plus_seconds(cfg.rules.voting_period_secs()));
 
+    let id = next_id(deps.storage)?;
+    let proposer_boost = power_boost(deps.as_ref(), &cfg, id, &info.sender, vote_power)?;
+    let proposer_power = add_points(vote_power, bonus_of(&info.sender))?;
+    let proposer_power = add_points(proposer_power, proposer_boost.unwrap_or_default())?;
+
+    // bonuses and boosts are counted in the total, so votes can never exceed it
+    let total_points = bonuses
+        .iter()
+        .try_fold(total_points, |total, (_, points)| {
+            add_points(total, *points)
+        })?;
+    let total_points = add_points(total_points, proposer_boost.unwrap_or_default())?;
+
     // create a proposal
     let mut prop = Proposal {
         title,
@@ -84,25 +285,165 @@ where
         expires,
         proposal,
         status: Status::Open,
-        votes: Votes::yes(vote_power),
+        votes: Votes::yes(proposer_power),
         rules: cfg.rules,
-        total_points: cfg.group_contract.total_points(&deps.querier)?,
+        total_points,
+        tags,
+        tie_break: None,
     };
-    prop.update_status(&env.block);
-    let id = next_id(deps.storage)?;
+    prop.update_status(id, &env.block);
     proposals().save(deps.storage, id, &prop)?;
+    PROPOSALS_BY_EXPIRATION.save(deps.storage, (expires.as_key(), id), &Empty {})?;
+    if prop.status == Status::Open {
+        count_open_proposal(deps.storage, id, &expires, &info.sender)?;
+    }
+    for tag in &prop.tags {
+        PROPOSALS_BY_TAG.save(deps.storage, (tag, id), &Empty {})?;
+    }
+    for (voter, points) in &bonuses {
+        VOTE_BONUSES.save(deps.storage, (id, voter), points)?;
+    }
+    if let Some(key) = &idempotency_key {
+        PROPOSALS_BY_IDEMPOTENCY_KEY.save(deps.storage, (&info.sender, key), &id)?;
+    }
 
     // add the first yes vote from voter
-    ballots().create_ballot(deps.storage, &info.sender, id, vote_power, Vote::Yes)?;
-
-    let resp = msg::ProposalCreationResponse { proposal_id: id };
+    ballots().create_ballot(deps.storage, &info.sender, id, proposer_power, Vote::Yes)?;
+    if let Some(boost) = proposer_boost {
+        POWER_BOOSTS.save(deps.storage, (id, &info.sender), &boost)?;
+    }
 
     Ok(Response::new()
         .add_attribute("action", "propose")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", id.to_string())
         .add_attribute("status", format!("{:?}", prop.status))
-        .set_data(cosmwasm_std::to_binary(&resp)?))
+        .set_data(proposal_data(id, &prop)?))
+}
+
+/// Response data describing the proposal state, see `ProposalDataResponse`. Set by all the
+/// proposal entry points here; contracts should set it on their `Execute` responses as well.
+pub fn proposal_data<P>(proposal_id: u64, proposal: &Proposal<P>) -> StdResult<Binary> {
+    to_binary(&proposal_data_response(proposal_id, proposal))
+}
+
+fn proposal_data_response<P>(proposal_id: u64, proposal: &Proposal<P>) -> ProposalDataResponse {
+    ProposalDataResponse {
+        proposal_id,
+        status: proposal.status,
+        votes: proposal.votes.clone(),
+        total_points: proposal.total_points,
+        existing: false,
+    }
+}
+
+/// Registers the named proposal template, replacing the previous one with the same name.
+/// `None` removes the template. It is up to the contract who is allowed to manage templates.
+pub fn set_proposal_template(
+    storage: &mut dyn Storage,
+    name: &str,
+    template: Option<ProposalTemplate>,
+) -> Result<(), ContractError> {
+    validate_template_name(name)?;
+    match template {
+        Some(template) => {
+            template.validate()?;
+            PROPOSAL_TEMPLATES.save(storage, name, &template)?;
+        }
+        None => PROPOSAL_TEMPLATES.remove(storage, name),
+    }
+    Ok(())
+}
+
+/// Replaces the voting rules. New proposals are always created with the current rules, and
+/// open ones keep the rules they were created with unless `apply_to_open_proposals` is set.
+/// Returns the `update_rules` event with JSON encoded rules `before` and `after` the update, to
+/// be added to the response. It is up to the contract who is allowed to update the rules.
+pub fn update_rules<P, Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: &Env,
+    msg: UpdateRulesMsg,
+) -> Result<Event, ContractError>
+where
+    P: Serialize + DeserializeOwned,
+{
+    let UpdateRulesMsg {
+        rules,
+        apply_to_open_proposals,
+    } = msg;
+    rules.validate()?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let before = String::from_utf8_lossy(&to_vec(&cfg.rules)?).into_owned();
+    let after = String::from_utf8_lossy(&to_vec(&rules)?).into_owned();
+    cfg.rules = rules.clone();
+    CONFIG.save(deps.storage, &cfg)?;
+
+    let mut event = Event::new("update_rules")
+        .add_attribute("before", before)
+        .add_attribute("after", after);
+
+    if apply_to_open_proposals {
+        // all proposals not finalized yet are indexed by expiration, those expiring exactly now
+        // are expired already
+        let pending = PROPOSALS_BY_EXPIRATION
+            .prefix_range(
+                deps.storage,
+                Some(PrefixBound::exclusive(env.block.time.nanos())),
+                None,
+                Order::Ascending,
+            )
+            .map(|item| item.map(|((_, proposal_id), _)| proposal_id))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut updated = 0u32;
+        for proposal_id in pending {
+            let mut prop = proposals::<P>().load(deps.storage, proposal_id)?;
+            if prop.current_status(proposal_id, &env.block) != Status::Open {
+                continue;
+            }
+            prop.rules = rules.clone();
+            proposals::<P>().save(deps.storage, proposal_id, &prop)?;
+            if prop.current_status(proposal_id, &env.block) != Status::Open {
+                uncount_open_proposal(deps.storage, proposal_id, &prop.expires)?;
+            }
+            updated += 1;
+        }
+        event = event.add_attribute("updated_proposals", updated.to_string());
+    }
+
+    Ok(event)
+}
+
+/// Checks a proposal about to be created against the named template
+pub fn check_proposal_template<P, Q: CustomQuery>(
+    deps: Deps<Q>,
+    name: &str,
+    title: &str,
+    description: &str,
+    proposal: &P,
+) -> Result<(), ContractError>
+where
+    P: Serialize,
+{
+    PROPOSAL_TEMPLATES
+        .may_load(deps.storage, name)?
+        .ok_or_else(|| ContractError::UnknownProposalTemplate(name.to_owned()))?
+        .check(title, description, proposal)
+}
+
+/// Checks a proposal about to be created against the configured limits.
+/// `messages` is the number of messages the proposal dispatches when executed.
+pub fn validate_proposal_limits<P, Q: CustomQuery>(
+    deps: Deps<Q>,
+    proposal: &P,
+    messages: usize,
+) -> Result<(), ContractError>
+where
+    P: Serialize,
+{
+    let cfg = CONFIG.load(deps.storage)?;
+    cfg.limits.check(proposal, messages)
 }
 
 pub fn vote<P, Q: CustomQuery>(
@@ -130,23 +471,121 @@ where
     // use a snapshot of "start of proposal"
     // Must be a member of voting group and have voting power >= 1
     let cfg = CONFIG.load(deps.storage)?;
-    let vote_power =
+    let points =
         cfg.group_contract
             .was_voting_member(&deps.querier, &info.sender, prop.start_height)?;
+    let bonus = VOTE_BONUSES
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .unwrap_or_default();
+    let boost = power_boost(deps.as_ref(), &cfg, proposal_id, &info.sender, points)?;
+    let vote_power = add_points(points, bonus)?;
+    let vote_power = add_points(vote_power, boost.unwrap_or_default())?;
+    // boosts are counted in the total, so votes can never exceed it
+    prop.total_points = add_points(prop.total_points, boost.unwrap_or_default())?;
 
-    // cast vote if no vote previously cast
+    // cast vote if no vote previously cast. Nothing is written before the ballot is created,
+    // so a failing vote leaves the state untouched (see `vote_batch`)
     ballots().create_ballot(deps.storage, &info.sender, proposal_id, vote_power, vote)?;
+    if let Some(boost) = boost {
+        POWER_BOOSTS.save(deps.storage, (proposal_id, &info.sender), &boost)?;
+    }
 
     // update vote tally
     prop.votes.add_vote(vote, vote_power);
-    prop.update_status(&env.block);
+    prop.update_status(proposal_id, &env.block);
     proposals::<P>().save(deps.storage, proposal_id, &prop)?;
+    if prop.status != Status::Open {
+        uncount_open_proposal(deps.storage, proposal_id, &prop.expires)?;
+    }
 
     Ok(Response::new()
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("status", format!("{:?}", prop.status)))
+        .add_attribute("status", format!("{:?}", prop.status))
+        .set_data(proposal_data(proposal_id, &prop)?))
+}
+
+/// Points the configured power provider adds to `points` of `voter`, clamped to the configured
+/// caps. `None` if there is no power provider. Boosts are recorded with the ballot, so they
+/// don't change afterwards.
+fn power_boost<Q: CustomQuery>(
+    deps: Deps<Q>,
+    cfg: &Config,
+    proposal_id: u64,
+    voter: &Addr,
+    points: u64,
+) -> StdResult<Option<u64>> {
+    let provider = match &cfg.power_provider {
+        Some(provider) => provider,
+        None => return Ok(None),
+    };
+    let resp: PowerBoostResponse = deps.querier.query_wasm_smart(
+        provider,
+        &PowerProviderQueryMsg::PowerBoost {
+            proposal_id,
+            voter: voter.to_string(),
+            points,
+        },
+    )?;
+    let max_boost = cfg
+        .power_boost_caps
+        .as_ref()
+        .map_or(0, |caps| caps.max_points);
+    Ok(Some(resp.boost.min(max_boost)))
+}
+
+/// Adds voting points, failing instead of overflowing
+fn add_points(points: u64, more: u64) -> StdResult<u64> {
+    Ok(Uint64::new(points).checked_add(Uint64::new(more))?.u64())
+}
+
+/// Casts votes on multiple proposals at once. In `AllOrNothing` mode the first failing vote
+/// fails the whole batch, in `BestEffort` mode failing votes are skipped. The outcome of every
+/// single vote is reported as `proposal_<id>` attribute, and proposals voted on are set as
+/// `VoteBatchResponse` data.
+pub fn vote_batch<P, Q: CustomQuery>(
+    mut deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<(u64, Vote)>,
+    mode: VoteBatchMode,
+) -> Result<Response, ContractError>
+where
+    P: Serialize + DeserializeOwned,
+{
+    if votes.is_empty() {
+        return Err(ContractError::EmptyVoteBatch {});
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "vote_batch")
+        .add_attribute("sender", &info.sender);
+    let mut failed = 0u32;
+    let mut voted = vec![];
+
+    for (proposal_id, vote) in votes {
+        // `vote` only writes once the ballot is created, so a failing vote leaves no partial
+        // state behind and it is safe to just skip it
+        let outcome =
+            match self::vote::<P, Q>(deps.branch(), env.clone(), info.clone(), proposal_id, vote) {
+                Ok(_) => {
+                    let prop = proposals::<P>().load(deps.storage, proposal_id)?;
+                    voted.push(proposal_data_response(proposal_id, &prop));
+                    format!("{:?}", prop.status)
+                }
+                Err(err) if mode == VoteBatchMode::BestEffort => {
+                    failed += 1;
+                    format!("failed: {}", err)
+                }
+                Err(err) => return Err(err),
+            };
+        res = res.add_attribute(format!("proposal_{}", proposal_id), outcome);
+    }
+
+    Ok(res
+        .add_attribute("failed", failed.to_string())
+        .set_data(to_binary(&VoteBatchResponse { proposals: voted })?))
 }
 
 /// Checks if a given proposal is passed and can then be executed, and returns it.
@@ -163,16 +602,23 @@ where
 {
     let mut proposal = proposals::<P>().load(storage, proposal_id)?;
     // Update Status
-    proposal.update_status(&env.block);
+    proposal.update_status(proposal_id, &env.block);
     // We allow execution even after the proposal "expiration" as long as all votes come in before
-    // that point. If it was approved on time, it can be executed any time.
-    if proposal.current_status(&env.block) != Status::Passed {
-        return Err(ContractError::WrongExecuteStatus {});
+    // that point. If it was approved on time, it can be executed until the execution period after
+    // expiration passes (any time if there is no execution period configured).
+    let execution_period = CONFIG.load(storage)?.execution_period;
+    match proposal.execution_status(proposal_id, &env.block, execution_period) {
+        Status::Passed => (),
+        Status::PassedExpired => return Err(ContractError::ExecutionPeriodExpired {}),
+        _ => return Err(ContractError::WrongExecuteStatus {}),
     }
 
     // Set it to executed
     proposal.status = Status::Executed;
     proposals::<P>().save(storage, proposal_id, &proposal)?;
+    uncount_open_proposal(storage, proposal_id, &proposal.expires)?;
+    FINALIZED_PROPOSALS.save(storage, (env.block.height, proposal_id), &Empty {})?;
+    PROPOSALS_BY_EXPIRATION.remove(storage, (proposal.expires.as_key(), proposal_id));
     Ok(proposal)
 }
 
@@ -189,6 +635,51 @@ where
     Ok(())
 }
 
+/// Wraps messages of a proposal being executed, so the outcome of every single message is recorded
+/// instead of a failing message reverting the whole execution. Should be called after
+/// `mark_executed`, the returned submessages have to be added to the execution response.
+pub fn execute_with_results<T>(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    msgs: impl IntoIterator<Item = CosmosMsg<T>>,
+) -> StdResult<Vec<SubMsg<T>>> {
+    EXECUTING_PROPOSAL.save(storage, &proposal_id)?;
+    EXECUTION_RESULTS.save(storage, proposal_id, &vec![])?;
+
+    Ok(msgs
+        .into_iter()
+        .map(|msg| SubMsg::reply_always(msg, EXECUTION_REPLY_ID))
+        .collect())
+}
+
+/// Records the outcome of a message dispatched by `execute_with_results`. Replies come in the
+/// dispatch order, so results are just appended.
+pub fn execution_reply(storage: &mut dyn Storage, reply: Reply) -> Result<Response, ContractError> {
+    let proposal_id = EXECUTING_PROPOSAL.load(storage)?;
+    let result = match reply.result {
+        SubMsgResult::Ok(_) => MessageExecutionResult {
+            success: true,
+            error: None,
+        },
+        SubMsgResult::Err(err) => MessageExecutionResult {
+            success: false,
+            error: Some(err),
+        },
+    };
+
+    let success = result.success;
+    EXECUTION_RESULTS.update(storage, proposal_id, |results| -> StdResult<_> {
+        let mut results = results.unwrap_or_default();
+        results.push(result);
+        Ok(results)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execution_result")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("success", success.to_string()))
+}
+
 pub fn close<P, Q: CustomQuery>(
     deps: DepsMut<Q>,
     env: Env,
@@ -206,7 +697,7 @@ where
         return Err(ContractError::NotOpen {});
     }
 
-    prop.update_status(&env.block);
+    prop.update_status(proposal_id, &env.block);
 
     if [Status::Executed, Status::Passed]
         .iter()
@@ -220,11 +711,265 @@ where
 
     prop.status = Status::Rejected;
     proposals::<P>().save(deps.storage, proposal_id, &prop)?;
+    FINALIZED_PROPOSALS.save(deps.storage, (env.block.height, proposal_id), &Empty {})?;
+    PROPOSALS_BY_EXPIRATION.remove(deps.storage, (prop.expires.as_key(), proposal_id));
+    uncount_open_proposal(deps.storage, proposal_id, &prop.expires)?;
 
     Ok(Response::new()
         .add_attribute("action", "close")
         .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .set_data(proposal_data(proposal_id, &prop)?))
+}
+
+/// Closes expired proposals which didn't pass, up to `AutoClose::max_proposals` at once, every
+/// `AutoClose::interval_blocks` blocks. Meant to be called from the end blocker of the contract,
+/// does nothing if auto closing is not configured.
+pub fn close_expired<P, Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: &Env,
+) -> Result<Response, ContractError>
+where
+    P: Serialize + DeserializeOwned,
+{
+    let res = Response::new();
+    let auto_close = match CONFIG.load(deps.storage)?.auto_close {
+        Some(auto_close) if env.block.height % auto_close.interval_blocks == 0 => auto_close,
+        _ => return Ok(res),
+    };
+
+    // proposals expiring exactly now are expired already
+    let expired = PROPOSALS_BY_EXPIRATION
+        .prefix_range(
+            deps.storage,
+            None,
+            Some(PrefixBound::inclusive(env.block.time.nanos())),
+            Order::Ascending,
+        )
+        .take(auto_close.max_proposals as usize)
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut closed = 0u32;
+    for (expires, proposal_id) in expired {
+        PROPOSALS_BY_EXPIRATION.remove(deps.storage, (expires, proposal_id));
+
+        // passed proposals are still to be executed, so they are just not tracked anymore
+        let mut prop = proposals::<P>().load(deps.storage, proposal_id)?;
+        uncount_open_proposal(deps.storage, proposal_id, &prop.expires)?;
+        if prop.status != Status::Open
+            || prop.current_status(proposal_id, &env.block) != Status::Rejected
+        {
+            continue;
+        }
+
+        prop.status = Status::Rejected;
+        proposals::<P>().save(deps.storage, proposal_id, &prop)?;
+        FINALIZED_PROPOSALS.save(deps.storage, (env.block.height, proposal_id), &Empty {})?;
+        closed += 1;
+    }
+
+    Ok(res
+        .add_attribute("action", "close_expired")
+        .add_attribute("closed", closed.to_string()))
+}
+
+/// Indexes open proposals by expiration and counts those still open for voting at `block`.
+/// Versions before the index and the counters were introduced didn't maintain them, so their
+/// open proposals would never be closed automatically nor counted against the open proposals
+/// limits. It scans all proposals, so it is meant to be run once, as a migration step. Returns
+/// the number of proposals indexed.
+pub fn index_open_proposals<P>(storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<u32>
+where
+    P: Serialize + DeserializeOwned,
+{
+    let open = proposals::<P>()
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, prop)) if prop.status != Status::Open))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (proposal_id, prop) in &open {
+        PROPOSALS_BY_EXPIRATION.save(storage, (prop.expires.as_key(), *proposal_id), &Empty {})?;
+        // proposals stored as open may have passed or expired already
+        if prop.current_status(*proposal_id, block) == Status::Open {
+            let proposer = Addr::unchecked(&prop.created_by);
+            count_open_proposal(storage, *proposal_id, &prop.expires, &proposer)?;
+        }
+    }
+    Ok(open.len() as u32)
+}
+
+/// Removes ballots of proposals finalized at least `retention_blocks` ago, oldest first. Up to
+/// `max_ballots_per_call` ballots are removed at once, so it may take several calls to prune all
+/// of them. Anyone can call this.
+pub fn prune_ballots<Q: CustomQuery>(
+    deps: DepsMut<Q>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let pruning = cfg.pruning.ok_or(ContractError::BallotPruningDisabled {})?;
+
+    let mut ballots_left = pruning.max_ballots_per_call as usize;
+    let mut ballots_pruned = 0;
+    let mut proposals_pruned = 0;
+
+    if let Some(cutoff) = env.block.height.checked_sub(pruning.retention_blocks) {
+        while ballots_left > 0 {
+            let next = FINALIZED_PROPOSALS
+                .prefix_range(
+                    deps.storage,
+                    None,
+                    Some(PrefixBound::inclusive(cutoff)),
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()?;
+            let (height, proposal_id) = match next {
+                Some((key, _)) => key,
+                None => break,
+            };
+
+            // take one more ballot than allowed, to know if the proposal is fully pruned
+            let voters = ballots()
+                .ballots
+                .prefix(proposal_id)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .take(ballots_left + 1)
+                .collect::<StdResult<Vec<_>>>()?;
+            let done = voters.len() <= ballots_left;
+
+            for voter in voters.iter().take(ballots_left) {
+                ballots()
+                    .ballots
+                    .remove(deps.storage, (proposal_id, voter))?;
+                POWER_BOOSTS.remove(deps.storage, (proposal_id, voter));
+                ballots_pruned += 1;
+            }
+            ballots_left -= voters.len().min(ballots_left);
+
+            if !done {
+                break;
+            }
+            FINALIZED_PROPOSALS.remove(deps.storage, (height, proposal_id));
+            proposals_pruned += 1;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_ballots")
+        .add_attribute("sender", info.sender)
+        .add_attribute("ballots_pruned", ballots_pruned.to_string())
+        .add_attribute("proposals_pruned", proposals_pruned.to_string()))
+}
+
+/// Maximum number of prunable proposals counted by `query_prunable_proposals`
+pub const MAX_PRUNABLE_COUNT: u64 = 100;
+
+/// Returns number of finalized proposals which ballots can be pruned at the current height,
+/// counting at most `MAX_PRUNABLE_COUNT` of them, with `more` set if there are more.
+pub fn query_prunable_proposals<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+) -> StdResult<msg::PrunableProposalsResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let cutoff = cfg
+        .pruning
+        .and_then(|pruning| env.block.height.checked_sub(pruning.retention_blocks));
+
+    let count = match cutoff {
+        Some(cutoff) => FINALIZED_PROPOSALS
+            .prefix_range(
+                deps.storage,
+                None,
+                Some(PrefixBound::inclusive(cutoff)),
+                Order::Ascending,
+            )
+            .take(MAX_PRUNABLE_COUNT as usize + 1)
+            .count() as u64,
+        None => 0,
+    };
+
+    Ok(msg::PrunableProposalsResponse {
+        count: count.min(MAX_PRUNABLE_COUNT),
+        more: count > MAX_PRUNABLE_COUNT,
+    })
+}
+
+pub fn query_ballot_pruning<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<Option<BallotPruning>> {
+    let cfg = CONFIG.load(deps.storage)?;
+    Ok(cfg.pruning)
+}
+
+pub fn query_vote_bonuses<Q: CustomQuery>(
+    deps: Deps<Q>,
+    proposal_id: u64,
+) -> StdResult<VoteBonusesResponse> {
+    let bonuses = VOTE_BONUSES
+        .prefix(proposal_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|bonus| {
+            bonus.map(|(voter, points)| VoteBonus {
+                voter: voter.into(),
+                points,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(VoteBonusesResponse {
+        proposal_id,
+        bonuses,
+    })
+}
+
+pub fn query_power_provider<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<Option<Addr>> {
+    let cfg = CONFIG.load(deps.storage)?;
+    Ok(cfg.power_provider)
+}
+
+pub fn query_power_boosts<Q: CustomQuery>(
+    deps: Deps<Q>,
+    proposal_id: u64,
+) -> StdResult<PowerBoostsResponse> {
+    let boosts = POWER_BOOSTS
+        .prefix(proposal_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|boost| {
+            boost.map(|(voter, boost)| PowerBoost {
+                voter: voter.into(),
+                boost,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(PowerBoostsResponse {
+        proposal_id,
+        boosts,
+    })
+}
+
+pub fn query_proposal_template<Q: CustomQuery>(
+    deps: Deps<Q>,
+    name: String,
+) -> StdResult<Option<ProposalTemplate>> {
+    PROPOSAL_TEMPLATES.may_load(deps.storage, &name)
+}
+
+pub fn list_proposal_templates<Q: CustomQuery>(
+    deps: Deps<Q>,
+    start_after: Option<String>,
+    limit: usize,
+) -> StdResult<ProposalTemplateListResponse> {
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let templates = PROPOSAL_TEMPLATES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|template| template.map(|(name, template)| NamedProposalTemplate { name, template }))
+        .collect::<StdResult<_>>()?;
+    Ok(ProposalTemplateListResponse { templates })
+}
+
+pub fn query_auto_close<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<Option<AutoClose>> {
+    let cfg = CONFIG.load(deps.storage)?;
+    Ok(cfg.auto_close)
 }
 
 pub fn query_rules<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<VotingRules> {
@@ -232,6 +977,45 @@ pub fn query_rules<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<VotingRules> {
     Ok(cfg.rules)
 }
 
+pub fn query_proposal_limits<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<ProposalLimits> {
+    let cfg = CONFIG.load(deps.storage)?;
+    Ok(cfg.limits)
+}
+
+pub fn query_open_proposals<Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    proposer: Option<String>,
+) -> StdResult<OpenProposalsResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let proposer = maybe_addr(deps.api, proposer)?;
+    let (open, open_by_proposer) = open_proposals(deps.storage, &env.block, proposer.as_ref())?;
+    Ok(OpenProposalsResponse {
+        open,
+        open_by_proposer: proposer.map(|_| open_by_proposer),
+        max_open_proposals: cfg.limits.max_open_proposals,
+        max_open_proposals_per_proposer: cfg.limits.max_open_proposals_per_proposer,
+    })
+}
+
+pub fn query_proposal_content_rules<Q: CustomQuery>(
+    deps: Deps<Q>,
+) -> StdResult<ProposalContentRules> {
+    let cfg = CONFIG.load(deps.storage)?;
+    Ok(cfg.content)
+}
+
+pub fn query_execution_result<Q: CustomQuery>(
+    deps: Deps<Q>,
+    proposal_id: u64,
+) -> StdResult<msg::ExecutionResultResponse> {
+    let results = EXECUTION_RESULTS.may_load(deps.storage, proposal_id)?;
+    Ok(msg::ExecutionResultResponse {
+        proposal_id,
+        results,
+    })
+}
+
 pub fn query_proposal<P, Q: CustomQuery>(
     deps: Deps<Q>,
     env: Env,
@@ -241,7 +1025,12 @@ where
     P: Serialize + DeserializeOwned,
 {
     let prop = proposals().load(deps.storage, id)?;
-    let status = prop.current_status(&env.block);
+    let execution_period = CONFIG.load(deps.storage)?.execution_period;
+    let status = prop.execution_status(id, &env.block, execution_period);
+    let tie_break = prop
+        .tie_break
+        .clone()
+        .or_else(|| prop.tie_break(id, &env.block));
     let rules = prop.rules;
     Ok(ProposalResponse {
         id,
@@ -254,15 +1043,19 @@ where
         rules,
         total_points: prop.total_points,
         votes: prop.votes,
+        tags: prop.tags,
+        tie_break,
     })
 }
 
 fn map_proposal<P>(
     block: &BlockInfo,
+    execution_period: Option<Duration>,
     item: StdResult<(u64, Proposal<P>)>,
 ) -> StdResult<ProposalResponse<P>> {
     let (id, prop) = item?;
-    let status = prop.current_status(block);
+    let status = prop.execution_status(id, block, execution_period);
+    let tie_break = prop.tie_break.clone().or_else(|| prop.tie_break(id, block));
     Ok(ProposalResponse {
         id,
         title: prop.title,
@@ -274,6 +1067,8 @@ fn map_proposal<P>(
         rules: prop.rules,
         total_points: prop.total_points,
         votes: prop.votes,
+        tags: prop.tags,
+        tie_break,
     })
 }
 
@@ -286,11 +1081,39 @@ pub fn list_proposals<P, Q: CustomQuery>(
 where
     P: Serialize + DeserializeOwned,
 {
+    let execution_period = CONFIG.load(deps.storage)?.execution_period;
     let start = start_after.map(Bound::exclusive);
     let props: StdResult<Vec<_>> = proposals()
         .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
-        .map(|p| map_proposal(&env.block, p))
+        .map(|p| map_proposal(&env.block, execution_period, p))
+        .collect();
+
+    Ok(ProposalListResponse { proposals: props? })
+}
+
+/// Lists proposals carrying the `tag`, in ascending id order
+pub fn list_proposals_by_tag<P, Q: CustomQuery>(
+    deps: Deps<Q>,
+    env: Env,
+    tag: String,
+    start_after: Option<u64>,
+    limit: usize,
+) -> StdResult<ProposalListResponse<P>>
+where
+    P: Serialize + DeserializeOwned,
+{
+    let execution_period = CONFIG.load(deps.storage)?.execution_period;
+    let start = start_after.map(Bound::exclusive);
+    let props: StdResult<Vec<_>> = PROPOSALS_BY_TAG
+        .prefix(&tag)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| {
+            let id = id?;
+            let prop = proposals().load(deps.storage, id)?;
+            map_proposal(&env.block, execution_period, Ok((id, prop)))
+        })
         .collect();
 
     Ok(ProposalListResponse { proposals: props? })
@@ -320,11 +1143,12 @@ pub fn reverse_proposals<P, Q: CustomQuery>(
 where
     P: Serialize + DeserializeOwned,
 {
+    let execution_period = CONFIG.load(deps.storage)?.execution_period;
     let end = start_before.map(Bound::exclusive);
     let props: StdResult<Vec<_>> = proposals()
         .range(deps.storage, None, end, Order::Descending)
         .take(limit)
-        .map(|p| map_proposal(&env.block, p))
+        .map(|p| map_proposal(&env.block, execution_period, p))
         .collect();
 
     Ok(ProposalListResponse { proposals: props? })
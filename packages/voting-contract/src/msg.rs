@@ -1,7 +1,135 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use tg3::Status;
+
+use crate::state::{MessageExecutionResult, ProposalTemplate, VoteBonus, Votes, VotingRules};
+
+/// Subset of `ProposalDataResponse` set by `propose`, kept for compatibility
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct ProposalCreationResponse {
     pub proposal_id: u64,
 }
+
+/// Data (JSON encoded) set on responses of `propose`, `vote`, `execute` and `close`, so their
+/// results can be parsed without scraping events
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalDataResponse {
+    pub proposal_id: u64,
+    /// Status of the proposal after the call
+    pub status: Status,
+    /// Tally after the call
+    pub votes: Votes,
+    pub total_points: u64,
+    /// Set if `propose` returned the proposal created before with the same idempotency key,
+    /// instead of creating a new one
+    #[serde(default)]
+    pub existing: bool,
+}
+
+/// Data (JSON encoded) set on `vote_batch` responses
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteBatchResponse {
+    /// Proposals which were voted on, failed votes are reported only in the attributes
+    pub proposals: Vec<ProposalDataResponse>,
+}
+
+/// Replacement of the voting rules, to be embedded in messages (or proposals) of contracts using
+/// this package, see `update_rules`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct UpdateRulesMsg {
+    pub rules: VotingRules,
+    /// If set, open proposals switch to the new rules, keeping their expiration. Otherwise they
+    /// are finished with the rules they were created with.
+    #[serde(default)]
+    pub apply_to_open_proposals: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PrunableProposalsResponse {
+    /// Number of finalized proposals which ballots can be pruned now, at most
+    /// `MAX_PRUNABLE_COUNT`
+    pub count: u64,
+    /// Set if more proposals than `count` can be pruned
+    #[serde(default)]
+    pub more: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct NamedProposalTemplate {
+    pub name: String,
+    pub template: ProposalTemplate,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalTemplateListResponse {
+    pub templates: Vec<NamedProposalTemplate>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ExecutionResultResponse {
+    pub proposal_id: u64,
+    /// Outcomes of proposal messages in dispatch order, `None` if the proposal was not executed
+    /// with results recording
+    pub results: Option<Vec<MessageExecutionResult>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteBonusesResponse {
+    pub proposal_id: u64,
+    /// Bonuses granted by the proposal, sorted by voter address
+    pub bonuses: Vec<VoteBonus>,
+}
+
+/// Query the configured power provider contract has to answer, to boost voting power of `voter`
+/// voting on `proposal_id` with `points` from the group
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerProviderQueryMsg {
+    /// Returns PowerBoostResponse
+    PowerBoost {
+        proposal_id: u64,
+        voter: String,
+        points: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PowerBoostResponse {
+    /// Points added to the voter's points
+    pub boost: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PowerBoost {
+    pub voter: String,
+    pub boost: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PowerBoostsResponse {
+    pub proposal_id: u64,
+    /// Boosts of voters who voted on the proposal so far, sorted by voter address
+    pub boosts: Vec<PowerBoost>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct OpenProposalsResponse {
+    /// Number of proposals open for voting
+    pub open: u32,
+    /// Number of open proposals created by the queried proposer, if any
+    pub open_by_proposer: Option<u32>,
+    pub max_open_proposals: Option<u32>,
+    pub max_open_proposals_per_proposer: Option<u32>,
+}
+
+/// How a batch of votes should handle failures of single votes
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteBatchMode {
+    /// Any failed vote fails the whole batch
+    #[default]
+    AllOrNothing,
+    /// Failed votes are skipped and reported in the response attributes
+    BestEffort,
+}
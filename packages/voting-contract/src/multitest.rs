@@ -1,12 +1,26 @@
 use suite::SuiteBuilder;
 
+mod abstain_non_voters;
+mod auto_close;
 mod closing;
 mod contracts;
 mod early_end;
+mod execution;
+mod execution_period;
 mod group_change;
+mod power_boost;
 mod proposing;
+mod pruning;
 mod queries;
+mod response_data;
+mod rules_update;
 mod suite;
+mod tags;
+mod templates;
+mod tie_break;
+mod total_points;
+mod vote_batch;
+mod vote_bonus;
 mod voting;
 
 #[test]
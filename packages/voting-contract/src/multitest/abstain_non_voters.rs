@@ -0,0 +1,100 @@
+use super::suite::{get_proposal_id, SuiteBuilder};
+use cosmwasm_std::Decimal;
+use tg3::{Status, Vote};
+
+use crate::state::RulesBuilder;
+
+#[test]
+fn non_voters_count_for_quorum_on_expiration() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(50))
+        .with_abstain_non_voters(true)
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_member("dave", 4)
+        .with_rules(rules.clone())
+        .build();
+
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+
+    // Quorum is not reached before expiration
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Open);
+
+    // After expiration non-voters count as abstaining, so the quorum is reached
+    // and the threshold is computed from the cast votes only
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Passed);
+
+    suite.execute_proposal("alice", proposal_id).unwrap();
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Executed);
+    assert_eq!(prop.votes.yes, 3);
+    assert_eq!(prop.votes.absent, 7);
+}
+
+#[test]
+fn non_voters_do_not_count_for_threshold() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(50))
+        .with_abstain_non_voters(true)
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_member("dave", 4)
+        .with_rules(rules.clone())
+        .build();
+
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.vote("carol", proposal_id, Vote::No).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Rejected);
+
+    suite.close("bob", proposal_id).unwrap();
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Rejected);
+    assert_eq!(prop.votes.absent, 6);
+}
+
+#[test]
+fn non_voters_do_not_count_without_rule() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(50))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_member("dave", 4)
+        .with_rules(rules.clone())
+        .build();
+
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Rejected);
+
+    suite.close("bob", proposal_id).unwrap();
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.votes.absent, 0);
+}
@@ -0,0 +1,120 @@
+use cosmwasm_std::Decimal;
+use tg3::{Status, Vote};
+
+use crate::multitest::suite::{get_proposal_id, SuiteBuilder};
+use crate::state::{AutoClose, RulesBuilder};
+
+#[test]
+fn end_block_without_config_does_nothing() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(35))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules.clone())
+        .build();
+    assert_eq!(suite.query_auto_close().unwrap(), None);
+
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    suite.end_block().unwrap();
+
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Rejected);
+    // Proposal is still open in storage, so it can be closed manually
+    suite.close("anybody", proposal_id).unwrap();
+}
+
+#[test]
+fn expired_proposals_are_closed_in_end_block() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(35))
+        .build();
+
+    let auto_close = AutoClose {
+        max_proposals: 2,
+        interval_blocks: 1,
+    };
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules.clone())
+        .with_auto_close(auto_close.clone())
+        .build();
+    assert_eq!(suite.query_auto_close().unwrap(), Some(auto_close));
+
+    let response = suite.propose("bob", "passed", "").unwrap();
+    let passed = get_proposal_id(&response).unwrap();
+    let response = suite.propose("alice", "first rejected", "").unwrap();
+    let first = get_proposal_id(&response).unwrap();
+    let response = suite.propose("alice", "second rejected", "").unwrap();
+    let second = get_proposal_id(&response).unwrap();
+    suite.vote("bob", second, Vote::No).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs() / 2);
+    let response = suite.propose("alice", "open", "").unwrap();
+    let open = get_proposal_id(&response).unwrap();
+
+    // Nothing expired yet
+    let response = suite.end_block().unwrap();
+    assert_eq!(response.custom_attrs(1)[1], ("closed", "0"));
+
+    // Passed proposal is skipped, but counts towards the limit
+    suite.app.advance_seconds(rules.voting_period_secs() / 2);
+    let response = suite.end_block().unwrap();
+    assert_eq!(response.custom_attrs(1)[1], ("closed", "1"));
+    // Closed proposals are finalized, so they cannot be closed again
+    suite.close("anybody", first).unwrap_err();
+
+    let response = suite.end_block().unwrap();
+    assert_eq!(response.custom_attrs(1)[1], ("closed", "1"));
+    suite.close("anybody", second).unwrap_err();
+    assert_eq!(
+        suite.query_proposal(second).unwrap().status,
+        Status::Rejected
+    );
+
+    // Passed proposal can still be executed, and not expired one stays open
+    assert_eq!(suite.query_proposal(passed).unwrap().status, Status::Passed);
+    suite.execute_proposal("anybody", passed).unwrap();
+    assert_eq!(suite.query_proposal(open).unwrap().status, Status::Open);
+}
+
+#[test]
+fn expired_proposals_are_closed_on_interval() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(35))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules.clone())
+        .with_auto_close(AutoClose {
+            max_proposals: 10,
+            interval_blocks: 5,
+        })
+        .build();
+
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.app.advance_seconds(rules.voting_period_secs());
+
+    // Move to the block right before the interval
+    let height = suite.app.block_info().height;
+    suite.app.advance_blocks(4 - height % 5);
+    let response = suite.end_block().unwrap();
+    assert!(!response.events.iter().any(|ev| ev.ty == "wasm"));
+
+    suite.app.advance_blocks(1);
+    let response = suite.end_block().unwrap();
+    assert_eq!(response.custom_attrs(1)[1], ("closed", "1"));
+    suite.close("anybody", proposal_id).unwrap_err();
+}
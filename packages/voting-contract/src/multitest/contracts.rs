@@ -1,3 +1,4 @@
+pub mod power_provider;
 pub mod voting;
 
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo};
@@ -17,3 +18,13 @@ pub fn engagement_contract() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
 
     Box::new(contract)
 }
+
+pub fn power_provider_contract() -> Box<dyn Contract<TgradeMsg, TgradeQuery>> {
+    let contract = ContractWrapper::<_, _, _, _, _, _, _, TgradeQuery>::new(
+        power_provider::execute,
+        power_provider::instantiate,
+        power_provider::query,
+    );
+
+    Box::new(contract)
+}
@@ -0,0 +1,45 @@
+//! Power provider granting fixed boosts set per voter, for testing boosts lookup
+
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, StdResult};
+use cw_storage_plus::Map;
+use tg_bindings::{TgradeMsg, TgradeQuery};
+
+use super::*;
+use crate::msg::{PowerBoostResponse, PowerProviderQueryMsg};
+
+const BOOSTS: Map<&str, u64> = Map::new("boosts");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    SetBoost { voter: String, boost: u64 },
+}
+
+pub fn instantiate(
+    _deps: DepsMut<TgradeQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<cosmwasm_std::Response<TgradeMsg>> {
+    Ok(cosmwasm_std::Response::new())
+}
+
+pub fn execute(
+    deps: DepsMut<TgradeQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<cosmwasm_std::Response<TgradeMsg>> {
+    match msg {
+        ExecuteMsg::SetBoost { voter, boost } => BOOSTS.save(deps.storage, &voter, &boost)?,
+    }
+    Ok(cosmwasm_std::Response::new())
+}
+
+pub fn query(deps: Deps<TgradeQuery>, _env: Env, msg: PowerProviderQueryMsg) -> StdResult<Binary> {
+    match msg {
+        PowerProviderQueryMsg::PowerBoost { voter, .. } => to_binary(&PowerBoostResponse {
+            boost: BOOSTS.may_load(deps.storage, &voter)?.unwrap_or_default(),
+        }),
+    }
+}
@@ -1,11 +1,22 @@
 use crate::{
-    execute_text, list_proposals, list_text_proposals, list_voters, list_votes,
-    list_votes_by_voter, propose, query_group_contract, query_proposal, query_rules, query_vote,
-    query_voter, reverse_proposals, state::VotingRules, ContractError, Response,
+    check_proposal_template, close_expired, execute_text, execute_with_results, execution_reply,
+    list_proposal_templates, list_proposals, list_proposals_by_tag, list_text_proposals,
+    list_voters, list_votes, list_votes_by_voter,
+    msg::{UpdateRulesMsg, VoteBatchMode},
+    propose, prune_ballots, query_auto_close, query_execution_result, query_group_contract,
+    query_open_proposals, query_power_boosts, query_power_provider, query_proposal,
+    query_proposal_content_rules, query_proposal_template, query_prunable_proposals, query_rules,
+    query_vote, query_vote_bonuses, query_voter, reverse_proposals, set_proposal_template,
+    state::{
+        AutoClose, BallotPruning, PowerBoostCaps, ProposalContentRules, ProposalLimits,
+        ProposalTemplate, VoteBonus, VoteBonusCaps, VotingRules,
+    },
+    update_rules, ContractError, InstantiateConfig, Response, EXECUTION_REPLY_ID,
 };
-use cosmwasm_std::{from_slice, to_binary, CustomQuery};
+use cosmwasm_std::{from_slice, to_binary, CosmosMsg, CustomQuery};
 use tg3::Vote;
-use tg_bindings::TgradeQuery;
+use tg_bindings::{TgradeQuery, TgradeSudoMsg};
+use tg_utils::Duration;
 
 use super::*;
 
@@ -14,6 +25,24 @@ use super::*;
 pub struct InstantiateMsg {
     pub rules: VotingRules,
     pub group_addr: String,
+    #[serde(default)]
+    pub limits: ProposalLimits,
+    #[serde(default)]
+    pub pruning: Option<BallotPruning>,
+    #[serde(default)]
+    pub content_rules: ProposalContentRules,
+    #[serde(default)]
+    pub auto_close: Option<AutoClose>,
+    #[serde(default)]
+    pub legacy_total_points: bool,
+    #[serde(default)]
+    pub execution_period: Option<Duration>,
+    #[serde(default)]
+    pub vote_bonus: Option<VoteBonusCaps>,
+    #[serde(default)]
+    pub power_provider: Option<String>,
+    #[serde(default)]
+    pub power_boost_caps: Option<PowerBoostCaps>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -23,23 +52,48 @@ pub enum ExecuteMsg {
         title: String,
         description: String,
         proposal: Proposal,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        vote_bonuses: Vec<VoteBonus>,
+        #[serde(default)]
+        template: Option<String>,
+        #[serde(default)]
+        idempotency_key: Option<String>,
+    },
+    /// Anyone can manage templates of this contract
+    SetProposalTemplate {
+        name: String,
+        template: Option<ProposalTemplate>,
     },
+    /// Anyone can update rules of this contract
+    UpdateRules(UpdateRulesMsg),
     Vote {
         proposal_id: u64,
         vote: Vote,
     },
+    VoteBatch {
+        votes: Vec<(u64, Vote)>,
+        #[serde(default)]
+        mode: VoteBatchMode,
+    },
     Execute {
         proposal_id: u64,
     },
     Close {
         proposal_id: u64,
     },
+    PruneBallots {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Proposal {
     Text {},
+    /// Messages dispatched with results recording
+    Messages {
+        msgs: Vec<CosmosMsg<TgradeMsg>>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -47,6 +101,8 @@ pub enum Proposal {
 pub enum QueryMsg {
     /// Return VotingRules
     Rules {},
+    /// Returns ProposalContentRules
+    ProposalContentRules {},
     /// Returns ProposalResponse
     Proposal { proposal_id: u64 },
     /// Returns ProposalListResponse
@@ -55,6 +111,12 @@ pub enum QueryMsg {
         limit: usize,
     },
     /// Returns ProposalListResponse
+    ListProposalsByTag {
+        tag: String,
+        start_after: Option<u64>,
+        limit: usize,
+    },
+    /// Returns ProposalListResponse
     ReverseProposals {
         start_before: Option<u64>,
         limit: usize,
@@ -87,6 +149,27 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: usize,
     },
+    /// Returns PrunableProposalsResponse
+    PrunableProposals {},
+    /// Returns ExecutionResultResponse
+    ExecutionResult { proposal_id: u64 },
+    /// Returns Option<AutoClose>
+    AutoClose {},
+    /// Returns VoteBonusesResponse
+    VoteBonuses { proposal_id: u64 },
+    /// Returns OpenProposalsResponse
+    OpenProposals { proposer: Option<String> },
+    /// Returns Option<Addr>
+    PowerProvider {},
+    /// Returns PowerBoostsResponse
+    PowerBoosts { proposal_id: u64 },
+    /// Returns Option<ProposalTemplate>
+    ProposalTemplate { name: String },
+    /// Returns ProposalTemplateListResponse
+    ListProposalTemplates {
+        start_after: Option<String>,
+        limit: usize,
+    },
 }
 
 pub struct VotingContract;
@@ -101,7 +184,20 @@ impl Contract<TgradeMsg, TgradeQuery> for VotingContract {
     ) -> anyhow::Result<cosmwasm_std::Response<TgradeMsg>> {
         let msg: InstantiateMsg = from_slice(&msg)?;
 
-        crate::instantiate(deps, msg.rules, &msg.group_addr).map_err(anyhow::Error::from)
+        let config = InstantiateConfig {
+            rules: msg.rules,
+            group_addr: msg.group_addr,
+            limits: msg.limits,
+            pruning: msg.pruning,
+            content_rules: msg.content_rules,
+            auto_close: msg.auto_close,
+            legacy_total_points: msg.legacy_total_points,
+            execution_period: msg.execution_period,
+            vote_bonus: msg.vote_bonus,
+            power_provider: msg.power_provider,
+            power_boost_caps: msg.power_boost_caps,
+        };
+        crate::instantiate(deps, config).map_err(anyhow::Error::from)
     }
 
     fn execute(
@@ -119,14 +215,57 @@ impl Contract<TgradeMsg, TgradeQuery> for VotingContract {
                 title,
                 description,
                 proposal,
-            } => propose(deps, env, info, title, description, proposal),
+                tags,
+                vote_bonuses,
+                template,
+                idempotency_key,
+            } => {
+                if let Some(template) = template {
+                    check_proposal_template(
+                        deps.as_ref(),
+                        &template,
+                        &title,
+                        &description,
+                        &proposal,
+                    )?;
+                }
+                propose(
+                    deps,
+                    env,
+                    info,
+                    title,
+                    description,
+                    proposal,
+                    tags,
+                    vote_bonuses,
+                    idempotency_key,
+                )
+            }
+            SetProposalTemplate { name, template } => {
+                set_proposal_template(deps.storage, &name, template).map(|_| {
+                    Response::new()
+                        .add_attribute("action", "set_proposal_template")
+                        .add_attribute("name", name)
+                })
+            }
+            UpdateRules(msg) => {
+                update_rules::<Proposal, TgradeQuery>(deps, &env, msg).map(|event| {
+                    Response::new()
+                        .add_attribute("action", "update_rules")
+                        .add_event(event)
+                })
+            }
             Vote { proposal_id, vote } => {
                 crate::vote::<Proposal, TgradeQuery>(deps, env, info, proposal_id, vote)
             }
+            VoteBatch { votes, mode } => {
+                crate::vote_batch::<Proposal, TgradeQuery>(deps, env, info, votes, mode)
+            }
             Execute { proposal_id } => execute(deps, env, info, proposal_id),
             Close { proposal_id } => {
                 crate::close::<Proposal, TgradeQuery>(deps, env, info, proposal_id)
             }
+            PruneBallots {} => prune_ballots(deps, env, info),
         }
         .map_err(anyhow::Error::from)
     }
@@ -137,6 +276,7 @@ impl Contract<TgradeMsg, TgradeQuery> for VotingContract {
         use QueryMsg::*;
         match msg {
             Rules {} => to_binary(&query_rules(deps)?),
+            ProposalContentRules {} => to_binary(&query_proposal_content_rules(deps)?),
             ListVoters { start_after, limit } => to_binary(&list_voters(deps, start_after, limit)?),
             Proposal { proposal_id } => to_binary(&query_proposal::<self::Proposal, TgradeQuery>(
                 deps,
@@ -150,6 +290,17 @@ impl Contract<TgradeMsg, TgradeQuery> for VotingContract {
             >(
                 deps, env, start_after, limit
             )?),
+            ListProposalsByTag {
+                tag,
+                start_after,
+                limit,
+            } => to_binary(&list_proposals_by_tag::<self::Proposal, TgradeQuery>(
+                deps,
+                env,
+                tag,
+                start_after,
+                limit,
+            )?),
             ReverseProposals {
                 start_before,
                 limit,
@@ -174,26 +325,49 @@ impl Contract<TgradeMsg, TgradeQuery> for VotingContract {
             ListTextProposals { start_after, limit } => {
                 to_binary(&list_text_proposals(deps, start_after, limit)?)
             }
+            PrunableProposals {} => to_binary(&query_prunable_proposals(deps, env)?),
+            ExecutionResult { proposal_id } => {
+                to_binary(&query_execution_result(deps, proposal_id)?)
+            }
+            AutoClose {} => to_binary(&query_auto_close(deps)?),
+            VoteBonuses { proposal_id } => to_binary(&query_vote_bonuses(deps, proposal_id)?),
+            OpenProposals { proposer } => to_binary(&query_open_proposals(deps, env, proposer)?),
+            PowerProvider {} => to_binary(&query_power_provider(deps)?),
+            PowerBoosts { proposal_id } => to_binary(&query_power_boosts(deps, proposal_id)?),
+            ProposalTemplate { name } => to_binary(&query_proposal_template(deps, name)?),
+            ListProposalTemplates { start_after, limit } => {
+                to_binary(&list_proposal_templates(deps, start_after, limit)?)
+            }
         }
         .map_err(anyhow::Error::from)
     }
 
     fn sudo(
         &self,
-        _deps: DepsMut<TgradeQuery>,
-        _env: Env,
-        _msg: Vec<u8>,
+        deps: DepsMut<TgradeQuery>,
+        env: Env,
+        msg: Vec<u8>,
     ) -> anyhow::Result<cosmwasm_std::Response<TgradeMsg>> {
-        unimplemented!()
+        let msg: TgradeSudoMsg = from_slice(&msg)?;
+
+        match msg {
+            TgradeSudoMsg::EndBlock {} => {
+                close_expired::<Proposal, TgradeQuery>(deps, &env).map_err(anyhow::Error::from)
+            }
+            _ => Err(anyhow::anyhow!("Unsupported sudo message")),
+        }
     }
 
     fn reply(
         &self,
-        _deps: DepsMut<TgradeQuery>,
+        deps: DepsMut<TgradeQuery>,
         _env: Env,
-        _msg: cosmwasm_std::Reply,
+        msg: cosmwasm_std::Reply,
     ) -> anyhow::Result<cosmwasm_std::Response<TgradeMsg>> {
-        unimplemented!()
+        match msg.id {
+            EXECUTION_REPLY_ID => execution_reply(deps.storage, msg).map_err(anyhow::Error::from),
+            id => Err(anyhow::anyhow!("Unknown reply id: {}", id)),
+        }
     }
 
     fn migrate(
@@ -214,10 +388,19 @@ fn execute<Q: CustomQuery>(
 ) -> Result<Response, ContractError> {
     // anyone can trigger this if the vote passed
     let prop = crate::mark_executed::<Proposal>(deps.storage, env, proposal_id)?;
-    execute_text(deps, proposal_id, prop)?;
+    let data = crate::proposal_data(proposal_id, &prop)?;
+    let msgs = match prop.proposal.clone() {
+        Proposal::Text {} => {
+            execute_text(deps, proposal_id, prop)?;
+            vec![]
+        }
+        Proposal::Messages { msgs } => execute_with_results(deps.storage, proposal_id, msgs)?,
+    };
 
     Ok(Response::new()
+        .add_submessages(msgs)
         .add_attribute("action", "execute")
         .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .set_data(data))
 }
@@ -0,0 +1,78 @@
+use cosmwasm_std::{coins, Addr, BankMsg};
+use cw_multi_test::Executor;
+use tg_bindings::TgradeMsg;
+
+use crate::multitest::suite::{get_proposal_id, SuiteBuilder};
+use crate::state::MessageExecutionResult;
+
+#[test]
+fn message_results_are_recorded() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    let owner = suite.owner.clone();
+    suite
+        .app
+        .execute(
+            owner,
+            TgradeMsg::MintTokens {
+                denom: "utgd".to_owned(),
+                amount: 100u128.into(),
+                recipient: suite.voting.to_string(),
+            }
+            .into(),
+        )
+        .unwrap();
+
+    let send = |amount| {
+        BankMsg::Send {
+            to_address: "bob".to_owned(),
+            amount: coins(amount, "utgd"),
+        }
+        .into()
+    };
+
+    // second message fails, as all funds are already sent
+    let proposal = suite
+        .propose_messages("alice", vec![send(100), send(100)])
+        .unwrap();
+    let id = get_proposal_id(&proposal).unwrap();
+
+    let res = suite.query_execution_result(id).unwrap();
+    assert_eq!(res.results, None);
+
+    suite.execute_proposal("alice", id).unwrap();
+
+    let res = suite.query_execution_result(id).unwrap();
+    let results = res.results.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[0],
+        MessageExecutionResult {
+            success: true,
+            error: None
+        }
+    );
+    assert!(!results[1].success);
+    assert!(results[1].error.is_some());
+
+    // successful message is not reverted by the failing one
+    let balance = suite
+        .app
+        .wrap()
+        .query_balance(Addr::unchecked("bob"), "utgd")
+        .unwrap();
+    assert_eq!(balance.amount.u128(), 100);
+}
+
+#[test]
+fn text_proposals_have_no_results() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    let proposal = suite
+        .propose_and_execute("alice", "text", "proposal")
+        .unwrap();
+    let id = get_proposal_id(&proposal).unwrap();
+
+    let res = suite.query_execution_result(id).unwrap();
+    assert_eq!(res.results, None);
+}
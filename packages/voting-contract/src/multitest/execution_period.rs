@@ -0,0 +1,55 @@
+use tg3::Status;
+use tg_utils::Duration;
+
+use crate::multitest::suite::{get_proposal_id, SuiteBuilder};
+use crate::state::RulesBuilder;
+use crate::ContractError;
+
+#[test]
+fn passed_proposal_executable_within_execution_period() {
+    let rules = RulesBuilder::new().build();
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_rules(rules.clone())
+        .with_execution_period(Duration::new(100))
+        .build();
+
+    let first = suite.propose("alice", "first", "").unwrap();
+    let first = get_proposal_id(&first).unwrap();
+    let second = suite.propose("alice", "second", "").unwrap();
+    let second = get_proposal_id(&second).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs() + 99);
+    let prop = suite.query_proposal(first).unwrap();
+    assert_eq!(prop.status, Status::Passed);
+    suite.execute_proposal("alice", first).unwrap();
+
+    suite.app.advance_seconds(1);
+    let prop = suite.query_proposal(second).unwrap();
+    assert_eq!(prop.status, Status::PassedExpired);
+    let props = suite.list_proposals(None, None).unwrap();
+    assert_eq!(props[1].status, Status::PassedExpired);
+
+    let err = suite.execute_proposal("alice", second).unwrap_err();
+    assert_eq!(
+        ContractError::ExecutionPeriodExpired {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn passed_proposal_executable_any_time_without_execution_period() {
+    let rules = RulesBuilder::new().build();
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_rules(rules.clone())
+        .build();
+
+    let proposal = suite.propose("alice", "proposal", "").unwrap();
+    let proposal = get_proposal_id(&proposal).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs() * 100);
+    let prop = suite.query_proposal(proposal).unwrap();
+    assert_eq!(prop.status, Status::Passed);
+    suite.execute_proposal("alice", proposal).unwrap();
+}
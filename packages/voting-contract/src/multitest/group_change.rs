@@ -115,3 +115,49 @@ fn new_proposals_follow_updated_membership() {
     let proposal_status = suite.query_proposal(proposal_id).unwrap().status;
     assert_eq!(proposal_status, Status::Passed);
 }
+
+#[test]
+fn rejoined_member_votes_with_points_at_proposal_start() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("eve", 5)
+        .with_rules(rules)
+        .build();
+
+    let owner = suite.owner.clone();
+    let response = suite
+        .propose("alice", "great proposal", "proposal")
+        .unwrap();
+    let proposal_id: u64 = get_proposal_id(&response).unwrap();
+
+    // Bob leaves and rejoins the group with more points
+    suite.modify_members(owner.as_str(), &[], &["bob"]).unwrap();
+    suite.app.update_block(|block| block.height += 1);
+    suite
+        .modify_members(owner.as_str(), &[("bob", 10)], &[])
+        .unwrap();
+
+    // Bob's ballot and the tally use his points from the proposal start
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+    let proposal = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(proposal.votes.yes, 3);
+    assert_eq!(proposal.status, Status::Open);
+
+    let votes = suite.list_votes(proposal_id, None, None).unwrap();
+    let points: Vec<_> = votes
+        .iter()
+        .map(|vote| (vote.voter.as_str(), vote.points))
+        .collect();
+    assert_eq!(points, [("alice", 1), ("bob", 2)]);
+    let votes = suite.list_votes_by_voter("bob", None, None).unwrap();
+    assert_eq!(votes[0].points, 2);
+
+    // Still only one ballot
+    let err = suite.vote("bob", proposal_id, Vote::No).unwrap_err();
+    assert_eq!(ContractError::AlreadyVoted {}, err.downcast().unwrap());
+}
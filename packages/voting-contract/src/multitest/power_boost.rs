@@ -0,0 +1,154 @@
+use cosmwasm_std::Decimal;
+use tg3::{Status, Vote};
+
+use crate::msg::PowerBoost;
+use crate::multitest::suite::SuiteBuilder;
+use crate::state::{RulesBuilder, Votes};
+
+#[test]
+fn boost_added_to_voting_power() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(40))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 5)
+        .with_rules(rules)
+        .with_power_provider(10)
+        .build();
+
+    suite.set_power_boost("alice", 1).unwrap();
+    suite.set_power_boost("bob", 4).unwrap();
+
+    suite.propose("alice", "boosted", "proposal").unwrap();
+    let prop = suite.query_proposal(1).unwrap();
+    // Boosts are added to the total as they are recorded
+    assert_eq!(prop.total_points, 9);
+    assert_eq!(prop.votes, Votes::yes(2));
+
+    suite.vote("carol", 1, Vote::No).unwrap();
+    suite.vote("bob", 1, Vote::Yes).unwrap();
+    let prop = suite.query_proposal(1).unwrap();
+    assert_eq!(prop.votes.yes, 8);
+    assert_eq!(prop.votes.no, 5);
+    assert_eq!(prop.total_points, 13);
+    assert_eq!(prop.status, Status::Passed);
+    assert_eq!(suite.query_vote_info(1, "bob").unwrap().unwrap().points, 6);
+
+    assert_eq!(
+        suite.query_power_boosts(1).unwrap(),
+        [
+            PowerBoost {
+                voter: "alice".to_owned(),
+                boost: 1,
+            },
+            PowerBoost {
+                voter: "bob".to_owned(),
+                boost: 4,
+            },
+            PowerBoost {
+                voter: "carol".to_owned(),
+                boost: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn boost_cached_per_proposal() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 5)
+        .with_power_provider(10)
+        .build();
+
+    suite.set_power_boost("alice", 3).unwrap();
+    suite.propose("alice", "first", "proposal").unwrap();
+
+    // Changes of the provider don't affect recorded boosts
+    suite.set_power_boost("alice", 7).unwrap();
+    suite.propose("alice", "second", "proposal").unwrap();
+
+    assert_eq!(suite.query_proposal(1).unwrap().votes, Votes::yes(4));
+    assert_eq!(suite.query_proposal(2).unwrap().votes, Votes::yes(8));
+    assert_eq!(
+        suite.query_power_boosts(1).unwrap(),
+        [PowerBoost {
+            voter: "alice".to_owned(),
+            boost: 3,
+        }]
+    );
+}
+
+#[test]
+fn no_boost_without_provider() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .build();
+
+    suite.propose("alice", "regular", "proposal").unwrap();
+    suite.vote("bob", 1, Vote::Yes).unwrap();
+
+    assert_eq!(suite.query_proposal(1).unwrap().votes, Votes::yes(3));
+    assert_eq!(suite.query_power_boosts(1).unwrap(), []);
+}
+
+#[test]
+fn boost_clamped_to_cap() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_power_provider(3)
+        .build();
+
+    suite.set_power_boost("alice", u64::MAX).unwrap();
+    suite.propose("alice", "boosted", "proposal").unwrap();
+
+    let prop = suite.query_proposal(1).unwrap();
+    assert_eq!(prop.votes, Votes::yes(4));
+    assert_eq!(prop.total_points, 6);
+    assert_eq!(
+        suite.query_power_boosts(1).unwrap(),
+        [PowerBoost {
+            voter: "alice".to_owned(),
+            boost: 3,
+        }]
+    );
+}
+
+#[test]
+fn boosted_abstain_never_exceeds_total() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(40))
+        .with_allow_early(true)
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 2)
+        .with_rules(rules)
+        .with_power_provider(10)
+        .build();
+
+    suite.set_power_boost("bob", 10).unwrap();
+    suite.propose("alice", "boosted", "proposal").unwrap();
+
+    // Bob's abstain outweighs the group total, which counts his boost as well
+    suite.vote("bob", 1, Vote::Abstain).unwrap();
+    let prop = suite.query_proposal(1).unwrap();
+    assert_eq!(prop.votes.abstain, 12);
+    assert_eq!(prop.total_points, 15);
+    assert_eq!(prop.status, Status::Open);
+
+    suite.vote("carol", 1, Vote::Yes).unwrap();
+    let prop = suite.query_proposal(1).unwrap();
+    assert_eq!(prop.votes.yes, 3);
+    assert_eq!(prop.status, Status::Passed);
+}
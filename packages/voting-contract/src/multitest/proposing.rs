@@ -1,10 +1,14 @@
-use cosmwasm_std::StdError;
-use tg3::Status;
+use cosmwasm_std::{from_binary, Decimal, StdError};
+use tg3::{Status, Vote};
 use tg_utils::Expiration;
 
+use crate::msg::ProposalDataResponse;
 use crate::multitest::contracts::voting::Proposal;
 use crate::multitest::suite::{get_proposal_id, SuiteBuilder};
-use crate::state::{ProposalResponse, RulesBuilder, Votes};
+use crate::state::{
+    ProposalContentRules, ProposalLimits, ProposalResponse, ProposerPolicy, RulesBuilder, Votes,
+    MAX_IDEMPOTENCY_KEY_LENGTH,
+};
 use crate::ContractError;
 
 #[test]
@@ -43,6 +47,8 @@ fn proposal_creation() {
             rules,
             total_points: 4,
             votes: Votes::yes(1),
+            tags: vec![],
+            tie_break: None,
         }
     )
 }
@@ -125,3 +131,316 @@ fn proposal_from_voter_can_directly_pass() {
         ],
     );
 }
+
+#[test]
+fn proposal_content_is_not_validated_by_default() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    assert_eq!(
+        suite.query_content_rules().unwrap(),
+        ProposalContentRules::default()
+    );
+    suite.propose("alice", "", "\u{7}").unwrap();
+}
+
+#[test]
+fn proposal_content_validation() {
+    let content_rules = ProposalContentRules {
+        min_title_length: Some(3),
+        max_title_length: Some(10),
+        min_description_length: Some(5),
+        max_description_length: None,
+        disallow_control_chars: true,
+    };
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_content_rules(content_rules.clone())
+        .build();
+
+    assert_eq!(suite.query_content_rules().unwrap(), content_rules);
+
+    let err = suite.propose("alice", "ab", "long enough").unwrap_err();
+    assert_eq!(
+        ContractError::InvalidProposalContentLength {
+            field: "title",
+            len: 2,
+            min: 3,
+            max: Some(10),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Length is counted in characters, not bytes
+    suite.propose("alice", "żółćżółć", "long enough").unwrap();
+
+    let err = suite.propose("alice", "title", "four").unwrap_err();
+    assert_eq!(
+        ContractError::InvalidProposalContentLength {
+            field: "description",
+            len: 4,
+            min: 5,
+            max: None,
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose("alice", "ti\u{0}tle", "long enough")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ProposalControlCharacters { field: "title" },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose("alice", "title", "long\u{1b}enough")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ProposalControlCharacters {
+            field: "description"
+        },
+        err.downcast().unwrap()
+    );
+
+    // Line breaks are fine in description
+    suite
+        .propose("alice", "title", "first line\nsecond line")
+        .unwrap();
+}
+
+#[test]
+fn proposer_policy_min_points() {
+    let rules = RulesBuilder::new()
+        .with_quorum(Decimal::percent(80))
+        .with_proposer_policy(ProposerPolicy::MinPoints(2))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules)
+        .build();
+
+    let err = suite
+        .propose("alice", "do the thing", "do the thing")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotAllowedToPropose {},
+        err.downcast().unwrap()
+    );
+
+    // Members who cannot propose can still vote
+    let res = suite
+        .propose("bob", "do the thing", "do the thing")
+        .unwrap();
+    let id = get_proposal_id(&res).unwrap();
+    suite.vote("alice", id, Vote::Yes).unwrap();
+    assert_eq!(suite.query_proposal(id).unwrap().status, Status::Passed);
+}
+
+#[test]
+fn open_proposals_are_limited() {
+    let rules = RulesBuilder::new()
+        .with_quorum(Decimal::percent(80))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 5)
+        .with_rules(rules.clone())
+        .with_limits(ProposalLimits {
+            max_open_proposals: Some(3),
+            max_open_proposals_per_proposer: Some(2),
+            ..ProposalLimits::default()
+        })
+        .build();
+
+    suite.propose("alice", "first", "proposal").unwrap();
+    suite.propose("alice", "second", "proposal").unwrap();
+    let err = suite.propose("alice", "third", "proposal").unwrap_err();
+    assert_eq!(
+        ContractError::TooManyOpenProposalsByProposer { max: 2 },
+        err.downcast().unwrap()
+    );
+
+    suite.propose("bob", "third", "proposal").unwrap();
+    let err = suite.propose("carol", "fourth", "proposal").unwrap_err();
+    assert_eq!(
+        ContractError::TooManyOpenProposals { max: 3 },
+        err.downcast().unwrap()
+    );
+
+    let open = suite.query_open_proposals(Some("alice")).unwrap();
+    assert_eq!(open.open, 3);
+    assert_eq!(open.open_by_proposer, Some(2));
+    assert_eq!(
+        suite.query_open_proposals(None).unwrap().open_by_proposer,
+        None
+    );
+
+    // Passed proposals are not open anymore
+    suite.vote("carol", 1, Vote::Yes).unwrap();
+    suite.vote("bob", 1, Vote::Yes).unwrap();
+    assert_eq!(suite.query_proposal(1).unwrap().status, Status::Passed);
+    suite.propose("carol", "fourth", "proposal").unwrap();
+
+    // Neither are expired ones
+    suite.app.advance_seconds(rules.voting_period_secs());
+    assert_eq!(suite.query_open_proposals(None).unwrap().open, 0);
+    suite.propose("alice", "fifth", "proposal").unwrap();
+}
+
+#[test]
+fn open_proposals_are_counted() {
+    let rules = RulesBuilder::new()
+        .with_quorum(Decimal::percent(80))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 5)
+        .with_rules(rules.clone())
+        .with_limits(ProposalLimits {
+            max_open_proposals_per_proposer: Some(3),
+            ..ProposalLimits::default()
+        })
+        .build();
+
+    suite.propose("alice", "first", "proposal").unwrap();
+    suite.propose("alice", "second", "proposal").unwrap();
+    suite.propose("alice", "third", "proposal").unwrap();
+    suite.propose("bob", "fourth", "proposal").unwrap();
+    let open = suite.query_open_proposals(Some("alice")).unwrap();
+    assert_eq!([open.open, open.open_by_proposer.unwrap()], [4, 3]);
+
+    // Executing a passed proposal doesn't count it twice
+    suite.vote("carol", 1, Vote::Yes).unwrap();
+    suite.vote("bob", 1, Vote::Yes).unwrap();
+    suite.execute_proposal("carol", 1).unwrap();
+    let open = suite.query_open_proposals(Some("alice")).unwrap();
+    assert_eq!([open.open, open.open_by_proposer.unwrap()], [3, 2]);
+
+    // Proposals passing by the updated rules are not open anymore
+    suite.vote("carol", 2, Vote::Yes).unwrap();
+    let lowered = RulesBuilder::new()
+        .with_quorum(Decimal::percent(50))
+        .build();
+    suite.update_rules("alice", lowered, true).unwrap();
+    assert_eq!(suite.query_proposal(2).unwrap().status, Status::Passed);
+    let open = suite.query_open_proposals(Some("alice")).unwrap();
+    assert_eq!([open.open, open.open_by_proposer.unwrap()], [2, 1]);
+
+    // Expired proposals are not counted, closing them doesn't change it
+    suite.propose("alice", "fifth", "proposal").unwrap();
+    suite.app.advance_seconds(rules.voting_period_secs());
+    assert_eq!(suite.query_open_proposals(None).unwrap().open, 0);
+    suite.close("bob", 5).unwrap();
+    assert_eq!(suite.query_open_proposals(None).unwrap().open, 0);
+
+    suite.propose("alice", "sixth", "proposal").unwrap();
+    let open = suite.query_open_proposals(Some("alice")).unwrap();
+    assert_eq!([open.open, open.open_by_proposer.unwrap()], [1, 1]);
+}
+
+#[test]
+fn open_proposals_limits_are_validated() {
+    let limits = ProposalLimits {
+        max_open_proposals_per_proposer: Some(0),
+        ..ProposalLimits::default()
+    };
+    assert_eq!(
+        limits.validate().unwrap_err(),
+        ContractError::InvalidProposalLimits {}
+    );
+}
+
+#[test]
+fn proposer_policy_allow_list() {
+    let rules = RulesBuilder::new()
+        .with_proposer_policy(ProposerPolicy::AllowList(vec!["alice".to_owned()]))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 3)
+        .with_rules(rules)
+        .build();
+
+    let err = suite
+        .propose("bob", "do the thing", "do the thing")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotAllowedToPropose {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .propose("alice", "do the thing", "do the thing")
+        .unwrap();
+}
+
+#[test]
+fn proposer_policy_is_validated() {
+    let rules = RulesBuilder::new()
+        .with_proposer_policy(ProposerPolicy::AllowList(vec![]))
+        .build();
+    assert_eq!(
+        rules.validate().unwrap_err(),
+        ContractError::InvalidProposerPolicy {}
+    );
+
+    let rules = RulesBuilder::new()
+        .with_proposer_policy(ProposerPolicy::MinPoints(0))
+        .build();
+    assert_eq!(
+        rules.validate().unwrap_err(),
+        ContractError::InvalidProposerPolicy {}
+    );
+}
+
+#[test]
+fn proposing_with_idempotency_key_is_idempotent() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 3)
+        .build();
+
+    let res = suite
+        .propose_with_idempotency_key("alice", "proposal", "retry-me")
+        .unwrap();
+    let data: ProposalDataResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(data.proposal_id, 1);
+    assert!(!data.existing);
+
+    // Retry returns the proposal created before
+    suite.vote("bob", 1, Vote::No).unwrap();
+    let res = suite
+        .propose_with_idempotency_key("alice", "proposal", "retry-me")
+        .unwrap();
+    assert_eq!(get_proposal_id(&res).unwrap(), 1);
+    let data: ProposalDataResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(data.proposal_id, 1);
+    assert!(data.existing);
+    assert_eq!(data.votes.no, 3);
+    assert_eq!(suite.list_proposals(None, None).unwrap().len(), 1);
+
+    // Keys are namespaced per proposer
+    let res = suite
+        .propose_with_idempotency_key("bob", "proposal", "retry-me")
+        .unwrap();
+    assert_eq!(get_proposal_id(&res).unwrap(), 2);
+    let res = suite
+        .propose_with_idempotency_key("alice", "proposal", "another")
+        .unwrap();
+    assert_eq!(get_proposal_id(&res).unwrap(), 3);
+
+    let err = suite
+        .propose_with_idempotency_key("alice", "proposal", "")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidIdempotencyKey(MAX_IDEMPOTENCY_KEY_LENGTH),
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,145 @@
+use crate::msg::PrunableProposalsResponse;
+use crate::multitest::contracts::voting::QueryMsg;
+use crate::multitest::suite::{get_proposal_id, SuiteBuilder};
+use crate::state::{BallotPruning, RulesBuilder};
+use crate::{ContractError, MAX_PRUNABLE_COUNT};
+use tg3::{Status, Vote};
+
+#[test]
+fn pruning_requires_config() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    let err = suite.prune_ballots("anybody").unwrap_err();
+    assert_eq!(
+        ContractError::BallotPruningDisabled {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.query_prunable_proposals().unwrap(), 0);
+}
+
+#[test]
+fn ballots_of_finalized_proposals_are_pruned() {
+    let rules = RulesBuilder::new().with_allow_early(false).build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_rules(rules.clone())
+        .with_pruning(BallotPruning {
+            retention_blocks: 10,
+            max_ballots_per_call: 2,
+        })
+        .build();
+
+    let response = suite.propose("alice", "executed", "").unwrap();
+    let executed = get_proposal_id(&response).unwrap();
+    suite.vote("bob", executed, Vote::Yes).unwrap();
+    suite.vote("carol", executed, Vote::Yes).unwrap();
+
+    let response = suite.propose("alice", "open", "").unwrap();
+    let open = get_proposal_id(&response).unwrap();
+    suite.vote("bob", open, Vote::No).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    suite.execute_proposal("alice", executed).unwrap();
+
+    // Finalized, but not old enough
+    assert_eq!(suite.query_prunable_proposals().unwrap(), 0);
+    let response = suite.prune_ballots("anybody").unwrap();
+    assert_eq!(response.custom_attrs(1)[2], ("ballots_pruned", "0"));
+
+    suite.app.advance_blocks(10);
+    assert_eq!(suite.query_prunable_proposals().unwrap(), 1);
+
+    // Pruning is limited to two ballots per call
+    let response = suite.prune_ballots("anybody").unwrap();
+    assert_eq!(response.custom_attrs(1)[2], ("ballots_pruned", "2"));
+    assert_eq!(response.custom_attrs(1)[3], ("proposals_pruned", "0"));
+    assert_eq!(suite.list_votes(executed, None, None).unwrap().len(), 1);
+    assert_eq!(suite.query_prunable_proposals().unwrap(), 1);
+
+    let response = suite.prune_ballots("anybody").unwrap();
+    assert_eq!(response.custom_attrs(1)[2], ("ballots_pruned", "1"));
+    assert_eq!(response.custom_attrs(1)[3], ("proposals_pruned", "1"));
+    assert_eq!(suite.query_prunable_proposals().unwrap(), 0);
+
+    // Proposal summary is kept
+    let prop = suite.query_proposal(executed).unwrap();
+    assert_eq!(prop.status, Status::Executed);
+    assert_eq!(prop.votes.yes, 6);
+    assert!(suite.list_votes(executed, None, None).unwrap().is_empty());
+
+    // Ballots of proposal which is not finalized are untouched
+    assert_eq!(suite.list_votes(open, None, None).unwrap().len(), 2);
+}
+
+#[test]
+fn closed_proposals_are_pruned() {
+    let rules = RulesBuilder::new().build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 10)
+        .with_rules(rules.clone())
+        .with_pruning(BallotPruning {
+            retention_blocks: 5,
+            max_ballots_per_call: 10,
+        })
+        .build();
+
+    let response = suite.propose("alice", "rejected", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.vote("bob", proposal_id, Vote::No).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    suite.close("anybody", proposal_id).unwrap();
+
+    suite.app.advance_blocks(5);
+    assert_eq!(suite.query_prunable_proposals().unwrap(), 1);
+
+    let response = suite.prune_ballots("anybody").unwrap();
+    assert_eq!(response.custom_attrs(1)[2], ("ballots_pruned", "2"));
+    assert_eq!(response.custom_attrs(1)[3], ("proposals_pruned", "1"));
+    assert!(suite
+        .list_votes(proposal_id, None, None)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        suite.query_proposal(proposal_id).unwrap().status,
+        Status::Rejected
+    );
+}
+
+#[test]
+fn prunable_proposals_count_is_capped() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 10)
+        .with_member("bob", 1)
+        .with_pruning(BallotPruning {
+            retention_blocks: 1,
+            max_ballots_per_call: 10,
+        })
+        .build();
+
+    // Alice's proposals pass right away
+    for _ in 0..=MAX_PRUNABLE_COUNT {
+        let response = suite.propose("alice", "passed", "").unwrap();
+        let proposal_id = get_proposal_id(&response).unwrap();
+        suite.execute_proposal("alice", proposal_id).unwrap();
+    }
+    suite.app.advance_blocks(1);
+
+    let resp: PrunableProposalsResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.voting.clone(), &QueryMsg::PrunableProposals {})
+        .unwrap();
+    assert_eq!(
+        resp,
+        PrunableProposalsResponse {
+            count: MAX_PRUNABLE_COUNT,
+            more: true,
+        }
+    );
+}
@@ -73,8 +73,11 @@ fn query_proposal() {
                 yes: 1,
                 no: 0,
                 abstain: 0,
-                veto: 0
+                veto: 0,
+                absent: 0
             },
+            tags: vec![],
+            tie_break: None,
         }
     );
 
@@ -99,8 +102,11 @@ fn query_proposal() {
                 yes: 1,
                 no: 2,
                 abstain: 3,
-                veto: 4
+                veto: 4,
+                absent: 0
             },
+            tags: vec![],
+            tie_break: None,
         }
     );
 
@@ -122,8 +128,11 @@ fn query_proposal() {
                 yes: 1,
                 no: 2,
                 abstain: 3,
-                veto: 4
+                veto: 4,
+                absent: 0
             },
+            tags: vec![],
+            tie_break: None,
         }
     );
 }
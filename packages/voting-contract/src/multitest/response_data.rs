@@ -0,0 +1,97 @@
+use cosmwasm_std::{from_binary, Decimal};
+use tg3::{Status, Vote};
+
+use crate::msg::{
+    ProposalCreationResponse, ProposalDataResponse, VoteBatchMode, VoteBatchResponse,
+};
+use crate::multitest::suite::SuiteBuilder;
+use crate::state::{RulesBuilder, Votes};
+
+#[test]
+fn propose_vote_and_execute_set_data() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules)
+        .build();
+
+    let response = suite.propose("alice", "proposal", "proposal").unwrap();
+    let data: ProposalDataResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(
+        data,
+        ProposalDataResponse {
+            proposal_id: 1,
+            status: Status::Open,
+            votes: Votes::yes(1),
+            total_points: 3,
+            existing: false,
+        }
+    );
+
+    // data is still compatible with the original creation response
+    let response = suite.propose("alice", "second", "proposal").unwrap();
+    let data: ProposalCreationResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(data.proposal_id, 2);
+
+    let response = suite.vote("bob", 1, Vote::Yes).unwrap();
+    let data: ProposalDataResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(data.proposal_id, 1);
+    assert_eq!(data.status, Status::Passed);
+    assert_eq!(data.votes, Votes::yes(3));
+
+    let response = suite.execute_proposal("anybody", 1).unwrap();
+    let data: ProposalDataResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(data.status, Status::Executed);
+
+    let response = suite
+        .vote_batch(
+            "bob",
+            &[(1, Vote::No), (2, Vote::No)],
+            VoteBatchMode::BestEffort,
+        )
+        .unwrap();
+    let data: VoteBatchResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(
+        data.proposals,
+        vec![ProposalDataResponse {
+            proposal_id: 2,
+            status: Status::Open,
+            votes: Votes {
+                yes: 1,
+                no: 2,
+                abstain: 0,
+                veto: 0,
+                absent: 0,
+            },
+            total_points: 3,
+            existing: false,
+        }]
+    );
+}
+
+#[test]
+fn close_sets_data() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(35))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules.clone())
+        .build();
+
+    suite.propose("alice", "proposal", "proposal").unwrap();
+    suite.app.advance_seconds(rules.voting_period_secs());
+
+    let response = suite.close("anybody", 1).unwrap();
+    let data: ProposalDataResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(data.proposal_id, 1);
+    assert_eq!(data.status, Status::Rejected);
+    assert_eq!(data.votes, Votes::yes(1));
+}
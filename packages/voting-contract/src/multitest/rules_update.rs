@@ -0,0 +1,97 @@
+use cosmwasm_std::{to_vec, Decimal, Event};
+use tg3::Status;
+
+use crate::multitest::suite::SuiteBuilder;
+use crate::state::RulesBuilder;
+use crate::ContractError;
+
+#[test]
+fn open_proposals_keep_old_rules() {
+    let old_rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .build();
+    let new_rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(60))
+        .with_quorum(Decimal::percent(30))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(old_rules.clone())
+        .build();
+
+    suite.propose("alice", "first", "proposal").unwrap();
+
+    let resp = suite
+        .update_rules("alice", new_rules.clone(), false)
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-update_rules")
+            .add_attribute(
+                "before",
+                String::from_utf8(to_vec(&old_rules).unwrap()).unwrap(),
+            )
+            .add_attribute(
+                "after",
+                String::from_utf8(to_vec(&new_rules).unwrap()).unwrap(),
+            ),
+    );
+    assert_eq!(suite.query_rules().unwrap(), new_rules);
+
+    // In-flight proposal is finished with the rules it was created with
+    assert_eq!(suite.query_proposal(1).unwrap().rules, old_rules);
+
+    suite.propose("alice", "second", "proposal").unwrap();
+    assert_eq!(suite.query_proposal(2).unwrap().rules, new_rules);
+}
+
+#[test]
+fn rules_applied_to_open_proposals() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(35))
+        .build();
+    let new_rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(60))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules.clone())
+        .build();
+
+    suite.propose("alice", "expired", "proposal").unwrap();
+    suite.app.advance_seconds(rules.voting_period_secs());
+    suite.propose("alice", "open", "proposal").unwrap();
+
+    let resp = suite
+        .update_rules("alice", new_rules.clone(), true)
+        .unwrap();
+    resp.assert_event(&Event::new("wasm-update_rules").add_attribute("updated_proposals", "1"));
+
+    // Expired proposals are not affected
+    let expired = suite.query_proposal(1).unwrap();
+    assert_eq!(expired.status, Status::Rejected);
+    assert_eq!(expired.rules, rules);
+    let open = suite.query_proposal(2).unwrap();
+    assert_eq!(open.status, Status::Open);
+    assert_eq!(open.rules, new_rules);
+}
+
+#[test]
+fn invalid_rules_rejected() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+    let rules = suite.query_rules().unwrap();
+
+    let invalid = RulesBuilder::new()
+        .with_threshold(Decimal::percent(40))
+        .build();
+    let err = suite.update_rules("alice", invalid, true).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidThreshold(Decimal::percent(40)),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.query_rules().unwrap(), rules);
+}
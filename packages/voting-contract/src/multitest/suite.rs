@@ -1,10 +1,10 @@
 use super::contracts::{
-    self, engagement_contract,
+    self, engagement_contract, power_provider_contract,
     voting::{self, Proposal},
     VotingContract,
 };
 use anyhow::Result as AnyResult;
-use cosmwasm_std::{Addr, StdResult};
+use cosmwasm_std::{Addr, CosmosMsg, Empty, StdResult, Uint128};
 use cw_multi_test::{AppResponse, Executor};
 use derivative::Derivative;
 use tg3::{
@@ -12,12 +12,20 @@ use tg3::{
 };
 
 use tg4::Member;
+use tg_bindings::{TgradeMsg, TgradeSudoMsg};
 use tg_bindings_test::TgradeApp;
+use tg_utils::Duration;
 
 use crate::{
+    msg::{
+        ExecutionResultResponse, NamedProposalTemplate, OpenProposalsResponse, PowerBoost,
+        PowerBoostsResponse, ProposalTemplateListResponse, PrunableProposalsResponse,
+        UpdateRulesMsg, VoteBatchMode, VoteBonusesResponse,
+    },
     state::{
-        ProposalInfo, ProposalListResponse, ProposalResponse, RulesBuilder,
-        TextProposalListResponse, VotingRules,
+        AutoClose, BallotPruning, PowerBoostCaps, ProposalContentRules, ProposalInfo,
+        ProposalLimits, ProposalListResponse, ProposalResponse, ProposalTemplate, RulesBuilder,
+        TextProposalListResponse, VoteBonus, VoteBonusCaps, VotingRules,
     },
     ContractError,
 };
@@ -29,6 +37,14 @@ pub fn get_proposal_id(response: &AppResponse) -> Result<u64, std::num::ParseInt
 pub struct SuiteBuilder {
     members: Vec<Member>,
     rules: VotingRules,
+    limits: ProposalLimits,
+    pruning: Option<BallotPruning>,
+    content_rules: ProposalContentRules,
+    auto_close: Option<AutoClose>,
+    legacy_total_points: bool,
+    execution_period: Option<Duration>,
+    vote_bonus: Option<VoteBonusCaps>,
+    power_boost_caps: Option<PowerBoostCaps>,
 }
 
 impl SuiteBuilder {
@@ -36,6 +52,14 @@ impl SuiteBuilder {
         Self {
             members: vec![],
             rules: RulesBuilder::new().build(),
+            limits: ProposalLimits::default(),
+            pruning: None,
+            content_rules: ProposalContentRules::default(),
+            auto_close: None,
+            legacy_total_points: false,
+            execution_period: None,
+            vote_bonus: None,
+            power_boost_caps: None,
         }
     }
 
@@ -53,6 +77,50 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_limits(mut self, limits: ProposalLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_pruning(mut self, pruning: BallotPruning) -> Self {
+        self.pruning = Some(pruning);
+        self
+    }
+
+    pub fn with_content_rules(mut self, content_rules: ProposalContentRules) -> Self {
+        self.content_rules = content_rules;
+        self
+    }
+
+    pub fn with_auto_close(mut self, auto_close: AutoClose) -> Self {
+        self.auto_close = Some(auto_close);
+        self
+    }
+
+    pub fn with_legacy_total_points(mut self) -> Self {
+        self.legacy_total_points = true;
+        self
+    }
+
+    pub fn with_execution_period(mut self, execution_period: Duration) -> Self {
+        self.execution_period = Some(execution_period);
+        self
+    }
+
+    pub fn with_vote_bonus(mut self, max_voters: u32, max_points: u64) -> Self {
+        self.vote_bonus = Some(VoteBonusCaps {
+            max_voters,
+            max_points,
+        });
+        self
+    }
+
+    /// Uses `contracts::power_provider` for voting power boosts, up to `max_points` per voter
+    pub fn with_power_provider(mut self, max_points: u64) -> Self {
+        self.power_boost_caps = Some(PowerBoostCaps { max_points });
+        self
+    }
+
     pub fn build(self) -> Suite {
         let owner = Addr::unchecked("owner");
 
@@ -70,6 +138,9 @@ impl SuiteBuilder {
                     preauths_slashing: 0,
                     halflife: None,
                     denom: "poe-coin".to_string(),
+                    min_distribution: Uint128::zero(),
+                    distribution_interval: None,
+                    withdrawal_stream_period: None,
                 },
                 &[],
                 "engagement",
@@ -77,6 +148,23 @@ impl SuiteBuilder {
             )
             .unwrap();
 
+        let power_provider = if self.power_boost_caps.is_some() {
+            let power_provider_id = app.store_code(power_provider_contract());
+            Some(
+                app.instantiate_contract(
+                    power_provider_id,
+                    owner.clone(),
+                    &Empty {},
+                    &[],
+                    "power_provider",
+                    None,
+                )
+                .unwrap(),
+            )
+        } else {
+            None
+        };
+
         let voting_id = app.store_code(Box::new(VotingContract));
         let voting = app
             .instantiate_contract(
@@ -85,6 +173,15 @@ impl SuiteBuilder {
                 &contracts::voting::InstantiateMsg {
                     rules: self.rules,
                     group_addr: group.to_string(),
+                    limits: self.limits,
+                    pruning: self.pruning,
+                    content_rules: self.content_rules,
+                    auto_close: self.auto_close,
+                    legacy_total_points: self.legacy_total_points,
+                    execution_period: self.execution_period,
+                    vote_bonus: self.vote_bonus,
+                    power_provider: power_provider.as_ref().map(Addr::to_string),
+                    power_boost_caps: self.power_boost_caps,
                 },
                 &[],
                 "voting",
@@ -98,6 +195,7 @@ impl SuiteBuilder {
             app,
             voting,
             group,
+            power_provider,
             owner,
         }
     }
@@ -112,6 +210,8 @@ pub struct Suite {
     pub voting: Addr,
     /// Engagement contract address
     pub group: Addr,
+    /// Power provider contract address, if used
+    power_provider: Option<Addr>,
     /// Mixer contract address
     pub owner: Addr,
 }
@@ -155,6 +255,144 @@ impl Suite {
                 title: title.to_owned(),
                 description: description.to_owned(),
                 proposal: Proposal::Text {},
+                tags: vec![],
+                vote_bonuses: vec![],
+                template: None,
+                idempotency_key: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn propose_with_idempotency_key(
+        &mut self,
+        executor: &str,
+        title: &str,
+        idempotency_key: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::Propose {
+                title: title.to_owned(),
+                description: "proposal".to_owned(),
+                proposal: Proposal::Text {},
+                tags: vec![],
+                vote_bonuses: vec![],
+                template: None,
+                idempotency_key: Some(idempotency_key.to_owned()),
+            },
+            &[],
+        )
+    }
+
+    pub fn propose_with_tags(
+        &mut self,
+        executor: &str,
+        title: &str,
+        tags: &[&str],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::Propose {
+                title: title.to_owned(),
+                description: "tagged proposal".to_owned(),
+                proposal: Proposal::Text {},
+                tags: tags.iter().map(|tag| (*tag).to_owned()).collect(),
+                vote_bonuses: vec![],
+                template: None,
+                idempotency_key: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn propose_with_bonuses(
+        &mut self,
+        executor: &str,
+        title: &str,
+        bonuses: &[(&str, u64)],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::Propose {
+                title: title.to_owned(),
+                description: "proposal with vote bonuses".to_owned(),
+                proposal: Proposal::Text {},
+                tags: vec![],
+                vote_bonuses: bonuses
+                    .iter()
+                    .map(|(voter, points)| VoteBonus {
+                        voter: (*voter).to_owned(),
+                        points: *points,
+                    })
+                    .collect(),
+                template: None,
+                idempotency_key: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn propose_with_template(
+        &mut self,
+        executor: &str,
+        template: &str,
+        title: &str,
+        description: &str,
+        proposal: Proposal,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::Propose {
+                title: title.to_owned(),
+                description: description.to_owned(),
+                proposal,
+                tags: vec![],
+                vote_bonuses: vec![],
+                template: Some(template.to_owned()),
+                idempotency_key: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn set_proposal_template(
+        &mut self,
+        executor: &str,
+        name: &str,
+        template: Option<ProposalTemplate>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::SetProposalTemplate {
+                name: name.to_owned(),
+                template,
+            },
+            &[],
+        )
+    }
+
+    pub fn propose_messages(
+        &mut self,
+        executor: &str,
+        msgs: Vec<CosmosMsg<TgradeMsg>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::Propose {
+                title: "messages".to_owned(),
+                description: "proposal dispatching messages".to_owned(),
+                proposal: Proposal::Messages { msgs },
+                tags: vec![],
+                vote_bonuses: vec![],
+                template: None,
+                idempotency_key: None,
             },
             &[],
         )
@@ -180,6 +418,23 @@ impl Suite {
         )
     }
 
+    pub fn vote_batch(
+        &mut self,
+        executor: &str,
+        votes: &[(u64, Vote)],
+        mode: VoteBatchMode,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::VoteBatch {
+                votes: votes.to_vec(),
+                mode,
+            },
+            &[],
+        )
+    }
+
     pub fn execute_proposal(&mut self, executor: &str, proposal_id: u64) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(executor),
@@ -198,6 +453,103 @@ impl Suite {
         )
     }
 
+    pub fn prune_ballots(&mut self, executor: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::PruneBallots {},
+            &[],
+        )
+    }
+
+    pub fn end_block(&mut self) -> AnyResult<AppResponse> {
+        self.app
+            .wasm_sudo(self.voting.clone(), &TgradeSudoMsg::<Empty>::EndBlock {})
+    }
+
+    pub fn query_auto_close(&self) -> StdResult<Option<AutoClose>> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.voting.clone(), &voting::QueryMsg::AutoClose {})
+    }
+
+    pub fn query_vote_bonuses(&self, proposal_id: u64) -> StdResult<Vec<VoteBonus>> {
+        let resp: VoteBonusesResponse = self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::VoteBonuses { proposal_id },
+        )?;
+        Ok(resp.bonuses)
+    }
+
+    pub fn query_open_proposals(&self, proposer: Option<&str>) -> StdResult<OpenProposalsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::OpenProposals {
+                proposer: proposer.map(str::to_owned),
+            },
+        )
+    }
+
+    pub fn query_power_boosts(&self, proposal_id: u64) -> StdResult<Vec<PowerBoost>> {
+        let resp: PowerBoostsResponse = self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::PowerBoosts { proposal_id },
+        )?;
+        Ok(resp.boosts)
+    }
+
+    /// Sets boost granted by the power provider, only works with `with_power_provider`
+    pub fn set_power_boost(&mut self, voter: &str, boost: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.power_provider.clone().unwrap(),
+            &contracts::power_provider::ExecuteMsg::SetBoost {
+                voter: voter.to_owned(),
+                boost,
+            },
+            &[],
+        )
+    }
+
+    pub fn query_proposal_template(&self, name: &str) -> StdResult<Option<ProposalTemplate>> {
+        self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::ProposalTemplate {
+                name: name.to_owned(),
+            },
+        )
+    }
+
+    pub fn list_proposal_templates(
+        &self,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> StdResult<Vec<NamedProposalTemplate>> {
+        let resp: ProposalTemplateListResponse = self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::ListProposalTemplates {
+                start_after: start_after.map(str::to_owned),
+                limit,
+            },
+        )?;
+        Ok(resp.templates)
+    }
+
+    pub fn query_prunable_proposals(&self) -> StdResult<u64> {
+        let resp: PrunableProposalsResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.voting.clone(), &voting::QueryMsg::PrunableProposals {})?;
+        Ok(resp.count)
+    }
+
+    pub fn query_execution_result(&self, proposal_id: u64) -> StdResult<ExecutionResultResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::ExecutionResult { proposal_id },
+        )
+    }
+
     pub fn query_proposal(&self, proposal_id: u64) -> StdResult<ProposalResponse<Proposal>> {
         let prop: ProposalResponse<Proposal> = self.app.wrap().query_wasm_smart(
             self.voting.clone(),
@@ -206,6 +558,23 @@ impl Suite {
         Ok(prop)
     }
 
+    pub fn update_rules(
+        &mut self,
+        executor: &str,
+        rules: VotingRules,
+        apply_to_open_proposals: bool,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.voting.clone(),
+            &voting::ExecuteMsg::UpdateRules(UpdateRulesMsg {
+                rules,
+                apply_to_open_proposals,
+            }),
+            &[],
+        )
+    }
+
     pub fn query_rules(&self) -> StdResult<VotingRules> {
         let rules: VotingRules = self
             .app
@@ -214,6 +583,13 @@ impl Suite {
         Ok(rules)
     }
 
+    pub fn query_content_rules(&self) -> StdResult<ProposalContentRules> {
+        self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::ProposalContentRules {},
+        )
+    }
+
     pub fn query_vote_info(
         &self,
         proposal_id: u64,
@@ -259,6 +635,23 @@ impl Suite {
         Ok(proposals.proposals)
     }
 
+    pub fn list_proposals_by_tag(
+        &self,
+        tag: &str,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<usize>>,
+    ) -> StdResult<Vec<u64>> {
+        let proposals: ProposalListResponse<Proposal> = self.app.wrap().query_wasm_smart(
+            self.voting.clone(),
+            &voting::QueryMsg::ListProposalsByTag {
+                tag: tag.to_owned(),
+                start_after: start_after.into(),
+                limit: limit.into().unwrap_or(10),
+            },
+        )?;
+        Ok(proposals.proposals.into_iter().map(|p| p.id).collect())
+    }
+
     pub fn list_text_proposals(
         &self,
         start_after: impl Into<Option<u64>>,
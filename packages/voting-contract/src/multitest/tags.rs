@@ -0,0 +1,81 @@
+use crate::multitest::suite::SuiteBuilder;
+use crate::ContractError;
+
+#[test]
+fn proposals_listed_by_tag() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .build();
+
+    suite
+        .propose_with_tags("alice", "first", &["treasury", "urgent"])
+        .unwrap();
+    suite
+        .propose_with_tags("bob", "second", &["treasury"])
+        .unwrap();
+    suite.propose("alice", "untagged", "proposal").unwrap();
+    suite
+        .propose_with_tags("bob", "fourth", &["urgent"])
+        .unwrap();
+
+    assert_eq!(
+        suite.list_proposals_by_tag("treasury", None, None).unwrap(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        suite.list_proposals_by_tag("urgent", None, None).unwrap(),
+        vec![1, 4]
+    );
+    assert_eq!(
+        suite.list_proposals_by_tag("urgent", 1, None).unwrap(),
+        vec![4]
+    );
+    assert_eq!(
+        suite.list_proposals_by_tag("treasury", None, 1).unwrap(),
+        vec![1]
+    );
+    assert_eq!(
+        suite.list_proposals_by_tag("other", None, None).unwrap(),
+        Vec::<u64>::new()
+    );
+
+    let proposals = suite.list_proposals(None, None).unwrap();
+    assert_eq!(proposals[0].tags, ["treasury", "urgent"]);
+    assert!(proposals[2].tags.is_empty());
+}
+
+#[test]
+fn invalid_tags_rejected() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    let err = suite
+        .propose_with_tags("alice", "proposal", &["a", "b", "c", "d", "e", "f"])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TooManyTags { count: 6, max: 5 },
+        err.downcast().unwrap()
+    );
+
+    for tag in ["", "Upper", "with space", &"x".repeat(33)] {
+        let err = suite
+            .propose_with_tags("alice", "proposal", &[tag])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidTag(tag.to_owned()),
+            err.downcast().unwrap()
+        );
+    }
+
+    let err = suite
+        .propose_with_tags("alice", "proposal", &["dup", "dup"])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DuplicatedTag("dup".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .propose_with_tags("alice", "proposal", &["valid-tag_1"])
+        .unwrap();
+}
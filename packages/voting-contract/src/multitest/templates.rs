@@ -0,0 +1,168 @@
+use crate::msg::NamedProposalTemplate;
+use crate::multitest::contracts::voting::Proposal;
+use crate::multitest::suite::SuiteBuilder;
+use crate::state::ProposalTemplate;
+use crate::ContractError;
+
+fn text_template() -> ProposalTemplate {
+    ProposalTemplate {
+        title_prefix: "[Signal] ".to_owned(),
+        description: "## Motivation\n\n## Outcome\n".to_owned(),
+        required_sections: vec!["## Motivation".to_owned(), "## Outcome".to_owned()],
+        allowed_kinds: vec!["text".to_owned()],
+    }
+}
+
+#[test]
+fn templates_registered_and_listed() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    suite
+        .set_proposal_template("alice", "signal", Some(text_template()))
+        .unwrap();
+    let any_kind = ProposalTemplate {
+        allowed_kinds: vec![],
+        ..text_template()
+    };
+    suite
+        .set_proposal_template("alice", "any", Some(any_kind.clone()))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_proposal_template("signal").unwrap(),
+        Some(text_template())
+    );
+    assert_eq!(
+        suite.list_proposal_templates(None, 10).unwrap(),
+        vec![
+            NamedProposalTemplate {
+                name: "any".to_owned(),
+                template: any_kind,
+            },
+            NamedProposalTemplate {
+                name: "signal".to_owned(),
+                template: text_template(),
+            },
+        ]
+    );
+    assert_eq!(
+        suite
+            .list_proposal_templates(Some("any"), 10)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    suite.set_proposal_template("alice", "any", None).unwrap();
+    assert_eq!(suite.query_proposal_template("any").unwrap(), None);
+}
+
+#[test]
+fn invalid_templates_rejected() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    let err = suite
+        .set_proposal_template("alice", "Signal", Some(text_template()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidTemplateName("Signal".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Scaffold has to contain all required sections
+    let template = ProposalTemplate {
+        description: "## Motivation\n".to_owned(),
+        ..text_template()
+    };
+    let err = suite
+        .set_proposal_template("alice", "signal", Some(template))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidProposalTemplate {},
+        err.downcast().unwrap()
+    );
+
+    let template = ProposalTemplate {
+        allowed_kinds: vec![String::new()],
+        ..text_template()
+    };
+    let err = suite
+        .set_proposal_template("alice", "signal", Some(template))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidProposalTemplate {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn proposals_checked_against_template() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+    suite
+        .set_proposal_template("alice", "signal", Some(text_template()))
+        .unwrap();
+    let description = "## Motivation\nbecause\n## Outcome\nnone";
+
+    let err = suite
+        .propose_with_template(
+            "alice",
+            "other",
+            "[Signal] x",
+            description,
+            Proposal::Text {},
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnknownProposalTemplate("other".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_with_template("alice", "signal", "Signal", description, Proposal::Text {})
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TemplateTitleMismatch {
+            prefix: "[Signal] ".to_owned()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_with_template(
+            "alice",
+            "signal",
+            "[Signal] x",
+            "## Motivation\nbecause",
+            Proposal::Text {},
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TemplateSectionMissing("## Outcome".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_with_template(
+            "alice",
+            "signal",
+            "[Signal] x",
+            description,
+            Proposal::Messages { msgs: vec![] },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TemplateKindNotAllowed("messages".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .propose_with_template(
+            "alice",
+            "signal",
+            "[Signal] x",
+            description,
+            Proposal::Text {},
+        )
+        .unwrap();
+    assert_eq!(suite.query_proposal(1).unwrap().title, "[Signal] x");
+}
@@ -0,0 +1,136 @@
+use super::suite::{get_proposal_id, Suite, SuiteBuilder};
+use cosmwasm_std::Decimal;
+use tg3::{Status, Vote};
+
+use crate::state::{RulesBuilder, TieBreak, VotingRules};
+
+fn tied_proposal(tie_break: impl Into<Option<TieBreak>>) -> (Suite, VotingRules, u64) {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(50))
+        .with_allow_early(false)
+        .with_tie_break(tie_break)
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_member("dave", 4)
+        .with_rules(rules.clone())
+        .build();
+
+    // alice votes yes by proposing
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+    suite.vote("carol", proposal_id, Vote::No).unwrap();
+
+    (suite, rules, proposal_id)
+}
+
+#[test]
+fn tie_rejected_without_tie_break() {
+    let (mut suite, rules, proposal_id) = tied_proposal(None);
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Rejected);
+    assert_eq!(prop.tie_break, None);
+}
+
+#[test]
+fn tie_passes_with_pass_rule() {
+    let (mut suite, rules, proposal_id) = tied_proposal(TieBreak::Pass {});
+
+    // Tie is only resolved on expiration
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Open);
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Passed);
+    assert_eq!(prop.tie_break, None);
+
+    suite.execute_proposal("alice", proposal_id).unwrap();
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Executed);
+}
+
+#[test]
+fn tie_resolved_by_weighted_random() {
+    let (mut suite, rules, proposal_id) = tied_proposal(TieBreak::WeightedRandom {});
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    let tie_break = prop.tie_break.unwrap();
+    assert_eq!(tie_break.seed.len(), 32);
+    assert_eq!(tie_break.yes_points, 3);
+    assert_eq!(tie_break.against_points, 3);
+    assert!(tie_break.roll < 6);
+    assert_eq!(tie_break.passed, tie_break.roll < 3);
+
+    // Outcome is the same whenever it is computed
+    suite.app.advance_seconds(1000);
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.tie_break.as_ref(), Some(&tie_break));
+
+    // Finalization records the tie-break inputs
+    if tie_break.passed {
+        assert_eq!(prop.status, Status::Passed);
+        suite.execute_proposal("alice", proposal_id).unwrap();
+    } else {
+        assert_eq!(prop.status, Status::Rejected);
+        suite.close("alice", proposal_id).unwrap();
+    }
+    suite.app.advance_seconds(1000);
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.tie_break, Some(tie_break));
+}
+
+#[test]
+fn weighted_random_ignores_decided_proposals() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(50))
+        .with_allow_early(false)
+        .with_tie_break(TieBreak::WeightedRandom {})
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 4)
+        .with_rules(rules.clone())
+        .build();
+
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+    suite.vote("carol", proposal_id, Vote::No).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Rejected);
+    assert_eq!(prop.tie_break, None);
+}
+
+#[test]
+fn weighted_random_seed_includes_proposal_id() {
+    let (mut suite, rules, first_id) = tied_proposal(TieBreak::WeightedRandom {});
+
+    // Same proposer, block and tally - only the id differs
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let second_id = get_proposal_id(&response).unwrap();
+    suite.vote("bob", second_id, Vote::Yes).unwrap();
+    suite.vote("carol", second_id, Vote::No).unwrap();
+
+    suite.app.advance_seconds(rules.voting_period_secs());
+    let first = suite.query_proposal(first_id).unwrap().tie_break.unwrap();
+    let second = suite.query_proposal(second_id).unwrap().tie_break.unwrap();
+    assert_eq!(
+        (first.yes_points, first.against_points),
+        (second.yes_points, second.against_points)
+    );
+    assert_ne!(first.seed, second.seed);
+}
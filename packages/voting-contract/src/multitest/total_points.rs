@@ -0,0 +1,66 @@
+use super::suite::{get_proposal_id, SuiteBuilder};
+use cosmwasm_std::Decimal;
+use tg3::{Status, Vote};
+
+use crate::state::RulesBuilder;
+
+#[test]
+fn total_points_taken_at_proposal_start_height() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(60))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules)
+        .build();
+
+    // Membership change in the same block doesn't affect proposal total points,
+    // as voting power is also taken at the beginning of the block
+    let owner = suite.owner.clone();
+    suite
+        .modify_members(owner.as_str(), &[("carol", 7)], &[])
+        .unwrap();
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.total_points, 3);
+
+    // bob's vote reaches the quorum of initial members
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Passed);
+}
+
+#[test]
+fn legacy_total_points_taken_from_current_total() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(60))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_rules(rules)
+        .with_legacy_total_points()
+        .build();
+
+    let owner = suite.owner.clone();
+    suite
+        .modify_members(owner.as_str(), &[("carol", 7)], &[])
+        .unwrap();
+    let response = suite.propose("alice", "proposal", "").unwrap();
+    let proposal_id = get_proposal_id(&response).unwrap();
+
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.total_points, 10);
+
+    // carol cannot vote, so the quorum cannot be reached
+    suite.vote("bob", proposal_id, Vote::Yes).unwrap();
+    let prop = suite.query_proposal(proposal_id).unwrap();
+    assert_eq!(prop.status, Status::Open);
+}
@@ -0,0 +1,150 @@
+use cosmwasm_std::Decimal;
+use tg3::{Status, Vote};
+
+use crate::msg::VoteBatchMode;
+use crate::multitest::suite::{get_proposal_id, SuiteBuilder};
+use crate::state::RulesBuilder;
+use crate::ContractError;
+
+#[test]
+fn votes_on_multiple_proposals() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_rules(rules)
+        .build();
+
+    let response = suite.propose("alice", "first", "first").unwrap();
+    let first: u64 = get_proposal_id(&response).unwrap();
+    let response = suite.propose("alice", "second", "second").unwrap();
+    let second: u64 = get_proposal_id(&response).unwrap();
+
+    let response = suite
+        .vote_batch(
+            "carol",
+            &[(first, Vote::Yes), (second, Vote::No)],
+            VoteBatchMode::AllOrNothing,
+        )
+        .unwrap();
+    assert_eq!(
+        response.custom_attrs(1)[2],
+        (format!("proposal_{}", first), "Passed")
+    );
+    assert_eq!(
+        response.custom_attrs(1)[3],
+        (format!("proposal_{}", second), "Open")
+    );
+
+    let prop = suite.query_proposal(first).unwrap();
+    assert_eq!(prop.votes.yes, 4);
+    assert_eq!(prop.status, Status::Passed);
+    let prop = suite.query_proposal(second).unwrap();
+    assert_eq!(prop.votes.no, 3);
+    assert_eq!(prop.status, Status::Open);
+}
+
+#[test]
+fn empty_batch_fails() {
+    let mut suite = SuiteBuilder::new().with_member("alice", 1).build();
+
+    let err = suite
+        .vote_batch("alice", &[], VoteBatchMode::BestEffort)
+        .unwrap_err();
+    assert_eq!(ContractError::EmptyVoteBatch {}, err.downcast().unwrap());
+}
+
+#[test]
+fn all_or_nothing_reverts_on_failure() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .build();
+
+    let response = suite.propose("alice", "first", "first").unwrap();
+    let first: u64 = get_proposal_id(&response).unwrap();
+    let response = suite.propose("bob", "second", "second").unwrap();
+    let second: u64 = get_proposal_id(&response).unwrap();
+
+    // bob already voted on the second proposal as its creator
+    let err = suite
+        .vote_batch(
+            "bob",
+            &[(first, Vote::Yes), (second, Vote::Yes)],
+            VoteBatchMode::AllOrNothing,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::AlreadyVoted {}, err.downcast().unwrap());
+
+    // vote on the first proposal was reverted
+    let prop = suite.query_proposal(first).unwrap();
+    assert_eq!(prop.votes.yes, 1);
+}
+
+#[test]
+fn best_effort_skips_failures() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .build();
+
+    let response = suite.propose("alice", "first", "first").unwrap();
+    let first: u64 = get_proposal_id(&response).unwrap();
+    let response = suite.propose("bob", "second", "second").unwrap();
+    let second: u64 = get_proposal_id(&response).unwrap();
+
+    let response = suite
+        .vote_batch(
+            "bob",
+            &[(second, Vote::Yes), (first, Vote::No), (100, Vote::Yes)],
+            VoteBatchMode::BestEffort,
+        )
+        .unwrap();
+    let attrs = response.custom_attrs(1);
+    assert_eq!(
+        attrs[2],
+        (
+            format!("proposal_{}", second),
+            format!("failed: {}", ContractError::AlreadyVoted {})
+        )
+    );
+    assert_eq!(attrs[3], (format!("proposal_{}", first), "Open"));
+    assert_eq!(attrs[4].key, "proposal_100");
+    assert!(attrs[4].value.starts_with("failed: "));
+    assert_eq!(attrs[5], ("failed", "2"));
+
+    // successful vote was applied
+    let prop = suite.query_proposal(first).unwrap();
+    assert_eq!(prop.votes.no, 2);
+}
+
+#[test]
+fn best_effort_failure_leaves_no_state() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_power_provider(10)
+        .build();
+
+    let response = suite.propose("bob", "first", "first").unwrap();
+    let first: u64 = get_proposal_id(&response).unwrap();
+
+    // bob already voted as the creator, the new boost must not be recorded
+    suite.set_power_boost("bob", 5).unwrap();
+    let response = suite
+        .vote_batch("bob", &[(first, Vote::No)], VoteBatchMode::BestEffort)
+        .unwrap();
+    assert_eq!(response.custom_attrs(1)[3], ("failed", "1"));
+
+    let prop = suite.query_proposal(first).unwrap();
+    assert_eq!(prop.total_points, 3);
+    assert_eq!(prop.votes.yes, 2);
+    assert_eq!(prop.votes.no, 0);
+    let boosts = suite.query_power_boosts(first).unwrap();
+    assert_eq!(boosts.len(), 1);
+    assert_eq!(boosts[0].boost, 0);
+}
@@ -0,0 +1,127 @@
+use cosmwasm_std::{Decimal, StdError};
+use tg3::{Status, Vote};
+
+use crate::multitest::suite::SuiteBuilder;
+use crate::state::{RulesBuilder, VoteBonus, Votes};
+use crate::ContractError;
+
+#[test]
+fn bonus_counted_in_votes_and_total_points() {
+    let rules = RulesBuilder::new()
+        .with_threshold(Decimal::percent(51))
+        .with_quorum(Decimal::percent(40))
+        .build();
+
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 5)
+        .with_rules(rules)
+        .with_vote_bonus(2, 10)
+        .build();
+
+    suite
+        .propose_with_bonuses("alice", "committee", &[("bob", 6), ("alice", 2)])
+        .unwrap();
+
+    let prop = suite.query_proposal(1).unwrap();
+    assert_eq!(prop.total_points, 16);
+    assert_eq!(prop.votes, Votes::yes(3));
+    assert_eq!(prop.status, Status::Open);
+    assert_eq!(
+        suite.query_vote_bonuses(1).unwrap(),
+        vec![
+            VoteBonus {
+                voter: "alice".to_owned(),
+                points: 2,
+            },
+            VoteBonus {
+                voter: "bob".to_owned(),
+                points: 6,
+            },
+        ]
+    );
+
+    // With the bonus bob outweighs carol
+    suite.vote("carol", 1, Vote::No).unwrap();
+    suite.vote("bob", 1, Vote::Yes).unwrap();
+    let prop = suite.query_proposal(1).unwrap();
+    assert_eq!(prop.votes.yes, 11);
+    assert_eq!(prop.votes.no, 5);
+    assert_eq!(prop.status, Status::Passed);
+    assert_eq!(suite.query_vote_info(1, "bob").unwrap().unwrap().points, 8);
+
+    // Bonuses apply only to the proposal defining them
+    suite.propose("alice", "regular", "proposal").unwrap();
+    let prop = suite.query_proposal(2).unwrap();
+    assert_eq!(prop.total_points, 8);
+    assert_eq!(prop.votes, Votes::yes(1));
+    assert_eq!(suite.query_vote_bonuses(2).unwrap(), vec![]);
+}
+
+#[test]
+fn bonuses_rejected_when_disabled() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .build();
+
+    let err = suite
+        .propose_with_bonuses("alice", "committee", &[("bob", 1)])
+        .unwrap_err();
+    assert_eq!(ContractError::VoteBonusDisabled {}, err.downcast().unwrap());
+}
+
+#[test]
+fn bonuses_validated_against_caps() {
+    let mut suite = SuiteBuilder::new()
+        .with_member("alice", 1)
+        .with_member("bob", 2)
+        .with_member("carol", 3)
+        .with_vote_bonus(2, 10)
+        .build();
+
+    let err = suite
+        .propose_with_bonuses("alice", "many", &[("alice", 1), ("bob", 1), ("carol", 1)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TooManyBonusVoters { count: 3, max: 2 },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_with_bonuses("alice", "big", &[("bob", 11)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidVoteBonus {
+            points: 11,
+            max: 10
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_with_bonuses("alice", "zero", &[("bob", 0)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidVoteBonus { points: 0, max: 10 },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .propose_with_bonuses("alice", "duplicated", &[("bob", 1), ("bob", 2)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DuplicatedBonusVoter("bob".to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // Bonus points are only given to members able to vote
+    let err = suite
+        .propose_with_bonuses("alice", "outsider", &[("dave", 1)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err("Unauthorized: not member of a group")),
+        err.downcast().unwrap()
+    );
+}
@@ -1,11 +1,17 @@
 use schemars::JsonSchema;
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use cosmwasm_std::{BlockInfo, Decimal, StdResult, Storage, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    from_slice, to_vec, Addr, Binary, BlockInfo, Decimal, Empty, Order, StdError, StdResult,
+    Storage, Uint128,
+};
+use cw_storage_plus::{Item, Map, PrefixBound};
+use sha2::{Digest, Sha256};
 use tg3::{Status, Vote};
 use tg4::Tg4Contract;
-use tg_utils::Expiration;
+use tg_utils::{Duration, Expiration, MigrationHistory};
 
 use crate::ContractError;
 
@@ -19,6 +25,62 @@ pub struct Config {
     pub rules: VotingRules,
     // Total points and voters are queried from this contract
     pub group_contract: Tg4Contract,
+    /// Limits on proposal payloads, checked when proposing
+    #[serde(default)]
+    pub limits: ProposalLimits,
+    /// Pruning of ballots of finalized proposals, disabled if not set
+    #[serde(default)]
+    pub pruning: Option<BallotPruning>,
+    /// Validation of proposal title and description, checked when proposing
+    #[serde(default)]
+    pub content: ProposalContentRules,
+    /// Closing of expired proposals in end block, disabled if not set
+    #[serde(default)]
+    pub auto_close: Option<AutoClose>,
+    /// Use group's current total points as proposal total, instead of the total at proposal
+    /// start height. Only needed for groups not keeping history of total points.
+    #[serde(default)]
+    pub legacy_total_points: bool,
+    /// Time after proposal expiration in which a passed proposal can still be executed.
+    /// Passed proposals can be executed any time if not set.
+    #[serde(default)]
+    pub execution_period: Option<Duration>,
+    /// Caps on bonus voting points granted by proposals, bonuses are not allowed if not set
+    #[serde(default)]
+    pub vote_bonus: Option<VoteBonusCaps>,
+    /// Contract queried at vote time for additional voting points of a voter, see
+    /// `msg::PowerProviderQueryMsg`. No boosts if not set.
+    #[serde(default)]
+    pub power_provider: Option<Addr>,
+    /// Caps on boosts returned by the power provider, required if the provider is set
+    #[serde(default)]
+    pub power_boost_caps: Option<PowerBoostCaps>,
+}
+
+impl Config {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        self.rules.validate()?;
+        self.limits.validate()?;
+        self.content.validate()?;
+        if let Some(pruning) = &self.pruning {
+            pruning.validate()?;
+        }
+        if let Some(auto_close) = &self.auto_close {
+            auto_close.validate()?;
+        }
+        if let Some(vote_bonus) = &self.vote_bonus {
+            vote_bonus.validate()?;
+        }
+        match (&self.power_provider, &self.power_boost_caps) {
+            (_, Some(caps)) => caps.validate()?,
+            (Some(_), None) => return Err(ContractError::MissingPowerBoostCaps {}),
+            (None, None) => (),
+        }
+        if matches!(self.execution_period, Some(period) if period.seconds() == 0) {
+            return Err(ContractError::InvalidExecutionPeriod {});
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -36,6 +98,12 @@ pub struct Proposal<P> {
     pub total_points: u64,
     // summary of existing votes
     pub votes: Votes,
+    /// Short labels used to filter proposals, see `validate_tags`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Inputs and outcome of the tie-break, recorded when a tied proposal is finalized
+    #[serde(default)]
+    pub tie_break: Option<TieBreakRecord>,
 }
 
 impl<P> From<Proposal<P>> for ProposalInfo {
@@ -50,11 +118,11 @@ impl<P> From<Proposal<P>> for ProposalInfo {
 impl<P> Proposal<P> {
     /// current_status is non-mutable and returns what the status should be.
     /// (designed for queries)
-    pub fn current_status(&self, block: &BlockInfo) -> Status {
+    pub fn current_status(&self, proposal_id: u64, block: &BlockInfo) -> Status {
         let mut status = self.status;
 
         // if open, check if voting is passed or timed out
-        if status == Status::Open && self.is_passed(block) {
+        if status == Status::Open && self.is_passed(proposal_id, block) {
             status = Status::Passed;
         }
         if status == Status::Open && self.expires.is_expired(block) {
@@ -64,15 +132,98 @@ impl<P> Proposal<P> {
         status
     }
 
+    /// Like `current_status`, but reports passed proposals not executed within
+    /// `execution_period` after their expiration as `PassedExpired`.
+    /// (designed for queries and execution checks - `PassedExpired` is never stored)
+    pub fn execution_status(
+        &self,
+        proposal_id: u64,
+        block: &BlockInfo,
+        execution_period: Option<Duration>,
+    ) -> Status {
+        let status = self.current_status(proposal_id, block);
+        match execution_period {
+            Some(period)
+                if status == Status::Passed
+                    && period.after_time(self.expires.time()).is_expired(block) =>
+            {
+                Status::PassedExpired
+            }
+            _ => status,
+        }
+    }
+
     /// update_status sets the status of the proposal to current_status.
     /// (designed for handler logic)
-    pub fn update_status(&mut self, block: &BlockInfo) {
-        self.status = self.current_status(block);
+    pub fn update_status(&mut self, proposal_id: u64, block: &BlockInfo) {
+        let status = self.current_status(proposal_id, block);
+        // Points of members who never voted are recorded when the proposal is finalized on
+        // expiration, so the tally shows what the quorum was computed from
+        if self.status == Status::Open
+            && status != Status::Open
+            && self.rules.abstain_non_voters
+            && self.expires.is_expired(block)
+        {
+            self.votes.absent = self.non_voters();
+        }
+        if self.status == Status::Open && status != Status::Open {
+            self.tie_break = self.tie_break(proposal_id, block);
+        }
+        self.status = status;
+    }
+
+    /// Returns true if the proposal expired with quorum met and "Yes" points equal to the
+    /// points against it ("No" and "Veto")
+    pub fn is_tied(&self, block: &BlockInfo) -> bool {
+        if !self.expires.is_expired(block) || self.votes.yes == 0 {
+            return false;
+        }
+        let mut quorum_votes = self.votes.total();
+        if self.rules.abstain_non_voters {
+            quorum_votes += self.non_voters();
+        }
+        quorum_votes >= votes_needed(self.total_points, self.rules.quorum)
+            && self.votes.yes == self.votes.no + self.votes.veto
+    }
+
+    /// Resolves a tie with `TieBreak::WeightedRandom`. The seed is a sha256 hash of the
+    /// proposal id, creator, start height, expiration and the final tally, so the outcome is the
+    /// same whenever it is computed.
+    pub fn tie_break(&self, proposal_id: u64, block: &BlockInfo) -> Option<TieBreakRecord> {
+        if self.rules.tie_break != Some(TieBreak::WeightedRandom {}) || !self.is_tied(block) {
+            return None;
+        }
+        let Votes { yes, no, veto, .. } = self.votes;
+        let seed = Sha256::new()
+            .chain_update(proposal_id.to_be_bytes())
+            .chain_update(self.created_by.as_bytes())
+            .chain_update(self.start_height.to_be_bytes())
+            .chain_update(self.expires.time().nanos().to_be_bytes())
+            .chain_update(yes.to_be_bytes())
+            .chain_update(no.to_be_bytes())
+            .chain_update(veto.to_be_bytes())
+            .finalize();
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&seed[..8]);
+        // options are weighted by their points: "Yes" wins if the roll falls into its points
+        let roll = u64::from_be_bytes(prefix) % (yes + no + veto);
+        Some(TieBreakRecord {
+            seed: Binary::from(seed.to_vec()),
+            yes_points: yes,
+            against_points: no + veto,
+            roll,
+            passed: roll < yes,
+        })
+    }
+
+    /// Points of members who didn't vote on this proposal
+    pub fn non_voters(&self) -> u64 {
+        self.total_points.saturating_sub(self.votes.total())
     }
 
     // returns true iff this proposal is sure to pass (even before expiration if no future
     // sequence of possible votes can cause it to fail)
-    pub fn is_passed(&self, block: &BlockInfo) -> bool {
+    pub fn is_passed(&self, proposal_id: u64, block: &BlockInfo) -> bool {
         let VotingRules {
             quorum,
             threshold,
@@ -80,14 +231,30 @@ impl<P> Proposal<P> {
             ..
         } = self.rules;
 
-        // we always require the quorum
-        if self.votes.total() < votes_needed(self.total_points, quorum) {
+        let expired = self.expires.is_expired(block);
+        // we always require the quorum; once expired, non-voters may count as abstaining
+        let mut quorum_votes = self.votes.total();
+        if expired && self.rules.abstain_non_voters {
+            quorum_votes += self.non_voters();
+        }
+        if quorum_votes < votes_needed(self.total_points, quorum) {
             return false;
         }
-        if self.expires.is_expired(block) {
+        if expired {
             // If expired, we compare Yes votes against the total number of votes (minus abstain).
             let opinions = self.votes.total() - self.votes.abstain;
-            self.votes.yes >= votes_needed(opinions, threshold)
+            if self.votes.yes >= votes_needed(opinions, threshold) {
+                return true;
+            }
+            // Ties are rejected unless the rules break them differently
+            match self.rules.tie_break {
+                Some(TieBreak::Pass {}) => self.is_tied(block),
+                Some(TieBreak::WeightedRandom {}) => matches!(
+                    self.tie_break(proposal_id, block),
+                    Some(TieBreakRecord { passed: true, .. })
+                ),
+                None => false,
+            }
         } else if allow_end_early {
             // If not expired, we must assume all non-votes will be cast as No.
             // We compare threshold against the total points (minus abstain).
@@ -114,6 +281,10 @@ pub struct ProposalResponse<P> {
     pub rules: VotingRules,
     pub total_points: u64,
     pub votes: Votes,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub tie_break: Option<TieBreakRecord>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
@@ -136,6 +307,18 @@ pub struct VotingRules {
     pub threshold: Decimal,
     /// If true, and absolute threshold and quorum are met, we can end before voting period finished
     pub allow_end_early: bool,
+    /// If true, members who didn't vote until the proposal expiration are counted as abstaining
+    /// for quorum purposes (but not for threshold)
+    #[serde(default)]
+    pub abstain_non_voters: bool,
+    /// Restricts who can create proposals. If not set, every voting member can propose.
+    /// Voting is always open to all voting members.
+    #[serde(default)]
+    pub proposer_policy: Option<ProposerPolicy>,
+    /// How proposals expiring with "Yes" points equal to "No" and "Veto" points are resolved.
+    /// If not set, they pass or fail by the threshold like any other proposal.
+    #[serde(default)]
+    pub tie_break: Option<TieBreak>,
 }
 
 impl VotingRules {
@@ -154,6 +337,10 @@ impl VotingRules {
         if self.voting_period == 0 || self.voting_period > 365 {
             return Err(ContractError::InvalidVotingPeriod(self.voting_period));
         }
+
+        if let Some(policy) = &self.proposer_policy {
+            policy.validate()?;
+        }
         Ok(())
     }
 
@@ -162,11 +349,450 @@ impl VotingRules {
     }
 }
 
+/// Resolution of tied proposals, see `VotingRules::tie_break`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Tied proposals pass
+    Pass {},
+    /// Tied proposals pass with probability of "Yes" points among "Yes", "No" and "Veto"
+    /// points, drawn deterministically from a hash of the proposal
+    WeightedRandom {},
+}
+
+/// Inputs and outcome of a `TieBreak::WeightedRandom` tie-break
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+pub struct TieBreakRecord {
+    /// Hash the roll is taken from
+    pub seed: Binary,
+    pub yes_points: u64,
+    /// "No" and "Veto" points
+    pub against_points: u64,
+    /// First 8 bytes of `seed` modulo all points; the proposal passes if it's below `yes_points`
+    pub roll: u64,
+    pub passed: bool,
+}
+
+/// Which voting members are allowed to create proposals
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposerPolicy {
+    /// Only members with at least this many points can propose
+    MinPoints(u64),
+    /// Only listed members can propose
+    AllowList(Vec<String>),
+}
+
+impl ProposerPolicy {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self {
+            ProposerPolicy::MinPoints(0) => Err(ContractError::InvalidProposerPolicy {}),
+            ProposerPolicy::AllowList(list) if list.is_empty() => {
+                Err(ContractError::InvalidProposerPolicy {})
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks if a voting member with the given points can create proposals
+    pub fn allows(&self, proposer: &str, points: u64) -> bool {
+        match self {
+            ProposerPolicy::MinPoints(min_points) => points >= *min_points,
+            ProposerPolicy::AllowList(list) => list.iter().any(|addr| addr == proposer),
+        }
+    }
+}
+
+/// Limits on proposals carrying messages, so that a passed proposal can still be executed
+/// within the block gas limit, and on the number of open proposals, so that members cannot be
+/// flooded with them. `None` means no limit.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalLimits {
+    /// Maximum size in bytes of the serialized proposal payload
+    pub max_payload_bytes: Option<u32>,
+    /// Maximum number of messages dispatched when executing a proposal
+    pub max_messages: Option<u32>,
+    /// Maximum number of proposals open at the same time
+    #[serde(default)]
+    pub max_open_proposals: Option<u32>,
+    /// Maximum number of proposals of a single proposer open at the same time
+    #[serde(default)]
+    pub max_open_proposals_per_proposer: Option<u32>,
+}
+
+impl ProposalLimits {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if [
+            self.max_payload_bytes,
+            self.max_messages,
+            self.max_open_proposals,
+            self.max_open_proposals_per_proposer,
+        ]
+        .contains(&Some(0))
+        {
+            return Err(ContractError::InvalidProposalLimits {});
+        }
+        Ok(())
+    }
+
+    pub fn limits_open_proposals(&self) -> bool {
+        self.max_open_proposals.is_some() || self.max_open_proposals_per_proposer.is_some()
+    }
+
+    /// Checks if a new proposal can be opened, given numbers of proposals open in total and by
+    /// its proposer.
+    pub fn check_open(&self, open: u32, open_by_proposer: u32) -> Result<(), ContractError> {
+        if let Some(max) = self.max_open_proposals {
+            if open >= max {
+                return Err(ContractError::TooManyOpenProposals { max });
+            }
+        }
+        if let Some(max) = self.max_open_proposals_per_proposer {
+            if open_by_proposer >= max {
+                return Err(ContractError::TooManyOpenProposalsByProposer { max });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a proposal dispatching `messages` messages on execution against the limits.
+    pub fn check<P: Serialize>(&self, proposal: &P, messages: usize) -> Result<(), ContractError> {
+        if let Some(max) = self.max_payload_bytes {
+            let size = to_vec(proposal)?.len();
+            if size > max as usize {
+                return Err(ContractError::ProposalPayloadTooLarge { size, max });
+            }
+        }
+        if let Some(max) = self.max_messages {
+            if messages > max as usize {
+                return Err(ContractError::TooManyProposalMessages {
+                    count: messages,
+                    max,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validation of proposal title and description. Lengths are counted in characters, `None`
+/// means no limit. All checks are disabled by default.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalContentRules {
+    pub min_title_length: Option<u32>,
+    pub max_title_length: Option<u32>,
+    pub min_description_length: Option<u32>,
+    pub max_description_length: Option<u32>,
+    /// Rejects control characters in title and description. Line breaks and tabs are still
+    /// allowed in description.
+    #[serde(default)]
+    pub disallow_control_chars: bool,
+}
+
+impl ProposalContentRules {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let valid_range = |min: Option<u32>, max: Option<u32>| match (min, max) {
+            (_, Some(0)) => false,
+            (Some(min), Some(max)) => min <= max,
+            _ => true,
+        };
+        if !valid_range(self.min_title_length, self.max_title_length)
+            || !valid_range(self.min_description_length, self.max_description_length)
+        {
+            return Err(ContractError::InvalidProposalContentRules {});
+        }
+        Ok(())
+    }
+
+    /// Checks title and description of a proposal being created against the rules.
+    pub fn check(&self, title: &str, description: &str) -> Result<(), ContractError> {
+        Self::check_length("title", title, self.min_title_length, self.max_title_length)?;
+        Self::check_length(
+            "description",
+            description,
+            self.min_description_length,
+            self.max_description_length,
+        )?;
+
+        if self.disallow_control_chars {
+            if title.chars().any(char::is_control) {
+                return Err(ContractError::ProposalControlCharacters { field: "title" });
+            }
+            if description
+                .chars()
+                .any(|c| c.is_control() && c != '\n' && c != '\r' && c != '\t')
+            {
+                return Err(ContractError::ProposalControlCharacters {
+                    field: "description",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_length(
+        field: &'static str,
+        value: &str,
+        min: Option<u32>,
+        max: Option<u32>,
+    ) -> Result<(), ContractError> {
+        let len = value.chars().count();
+        let too_short = matches!(min, Some(min) if len < min as usize);
+        let too_long = matches!(max, Some(max) if len > max as usize);
+        if too_short || too_long {
+            return Err(ContractError::InvalidProposalContentLength {
+                field,
+                len,
+                min: min.unwrap_or(0),
+                max,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Maximal number of tags a single proposal can carry
+pub const MAX_PROPOSAL_TAGS: usize = 5;
+/// Maximal length of a single tag
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Checks tags of a proposal being created: up to `MAX_PROPOSAL_TAGS` unique tags, each
+/// non-empty, at most `MAX_TAG_LENGTH` long, and built of lowercase ascii letters, digits, `-`
+/// and `_` only.
+pub fn validate_tags(tags: &[String]) -> Result<(), ContractError> {
+    if tags.len() > MAX_PROPOSAL_TAGS {
+        return Err(ContractError::TooManyTags {
+            count: tags.len(),
+            max: MAX_PROPOSAL_TAGS,
+        });
+    }
+    for (idx, tag) in tags.iter().enumerate() {
+        if !is_valid_label(tag) {
+            return Err(ContractError::InvalidTag(tag.clone()));
+        }
+        if tags[..idx].contains(tag) {
+            return Err(ContractError::DuplicatedTag(tag.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Maximal length of an idempotency key of a proposal
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 64;
+
+/// Checks idempotency key of a proposal being created: non-empty, at most
+/// `MAX_IDEMPOTENCY_KEY_LENGTH` bytes long.
+pub fn validate_idempotency_key(key: &str) -> Result<(), ContractError> {
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(ContractError::InvalidIdempotencyKey(
+            MAX_IDEMPOTENCY_KEY_LENGTH,
+        ));
+    }
+    Ok(())
+}
+
+/// Tags and template names are 1-32 lowercase ascii letters, digits, `-` and `_`
+fn is_valid_label(label: &str) -> bool {
+    let valid_chars = label
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    !label.is_empty() && label.len() <= MAX_TAG_LENGTH && valid_chars
+}
+
+/// Checks name of a proposal template being registered, same rules as for tags apply
+pub fn validate_template_name(name: &str) -> Result<(), ContractError> {
+    if !is_valid_label(name) {
+        return Err(ContractError::InvalidTemplateName(name.to_owned()));
+    }
+    Ok(())
+}
+
+/// Ballots of executed or rejected (closed) proposals are pruned after `retention_blocks`, so
+/// that the state does not grow unbounded. Proposals themselves, including final votes tally,
+/// are kept.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct BallotPruning {
+    /// Number of blocks after a proposal is finalized before its ballots can be pruned
+    pub retention_blocks: u64,
+    /// Maximum number of ballots removed in a single prune call
+    pub max_ballots_per_call: u32,
+}
+
+impl BallotPruning {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.retention_blocks == 0 || self.max_ballots_per_call == 0 {
+            return Err(ContractError::InvalidBallotPruning {});
+        }
+        Ok(())
+    }
+}
+
+/// Automatic closing of expired proposals which didn't pass, performed in end block, so rejected
+/// proposals don't stay open forever if nobody closes them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct AutoClose {
+    /// Maximum number of proposals closed in a single end block
+    pub max_proposals: u32,
+    /// Expired proposals are closed every `interval_blocks` blocks
+    pub interval_blocks: u64,
+}
+
+impl AutoClose {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.max_proposals == 0 || self.interval_blocks == 0 {
+            return Err(ContractError::InvalidAutoClose {});
+        }
+        Ok(())
+    }
+}
+
+/// Caps on bonus points a proposal can grant to a designated subset of voters (eg. technical
+/// committee), on top of their group points
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteBonusCaps {
+    /// Maximum number of voters getting a bonus in a single proposal
+    pub max_voters: u32,
+    /// Maximum bonus points of a single voter
+    pub max_points: u64,
+}
+
+impl VoteBonusCaps {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.max_voters == 0 || self.max_points == 0 {
+            return Err(ContractError::InvalidVoteBonusCaps {});
+        }
+        Ok(())
+    }
+
+    /// Checks bonuses of a proposal being created against the caps
+    pub fn check(&self, bonuses: &[VoteBonus]) -> Result<(), ContractError> {
+        if bonuses.len() > self.max_voters as usize {
+            return Err(ContractError::TooManyBonusVoters {
+                count: bonuses.len(),
+                max: self.max_voters,
+            });
+        }
+        for (idx, bonus) in bonuses.iter().enumerate() {
+            if bonus.points == 0 || bonus.points > self.max_points {
+                return Err(ContractError::InvalidVoteBonus {
+                    points: bonus.points,
+                    max: self.max_points,
+                });
+            }
+            if bonuses[..idx].iter().any(|b| b.voter == bonus.voter) {
+                return Err(ContractError::DuplicatedBonusVoter(bonus.voter.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Caps on boosts the power provider can grant to a single voter. Boosts above the cap are
+/// clamped to it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PowerBoostCaps {
+    /// Maximum boost of a single voter on a single proposal
+    pub max_points: u64,
+}
+
+impl PowerBoostCaps {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.max_points == 0 {
+            return Err(ContractError::InvalidPowerBoostCaps {});
+        }
+        Ok(())
+    }
+}
+
+/// Bonus points added to the voter's group points when voting on a single proposal
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteBonus {
+    pub voter: String,
+    pub points: u64,
+}
+
+/// Named scaffold of a proposal, which UIs can offer for guided proposal creation. Proposals
+/// created with a template are checked against it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalTemplate {
+    /// Prefix the proposal title has to start with, eg. "[Upgrade] "
+    #[serde(default)]
+    pub title_prefix: String,
+    /// Description scaffold to be filled in, containing all the `required_sections`
+    #[serde(default)]
+    pub description: String,
+    /// Lines (eg. "## Motivation") the proposal description has to contain
+    #[serde(default)]
+    pub required_sections: Vec<String>,
+    /// Payload kinds allowed, any kind if empty. The kind is the top level variant name of the
+    /// contract's proposal payload, eg. "migrate_contract".
+    #[serde(default)]
+    pub allowed_kinds: Vec<String>,
+}
+
+impl ProposalTemplate {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let sections_valid = self
+            .required_sections
+            .iter()
+            .all(|section| !section.is_empty() && self.description.contains(section.as_str()));
+        if !sections_valid || self.allowed_kinds.iter().any(String::is_empty) {
+            return Err(ContractError::InvalidProposalTemplate {});
+        }
+        Ok(())
+    }
+
+    /// Checks a proposal being created from this template
+    pub fn check<P: Serialize>(
+        &self,
+        title: &str,
+        description: &str,
+        proposal: &P,
+    ) -> Result<(), ContractError> {
+        if !title.starts_with(&self.title_prefix) {
+            return Err(ContractError::TemplateTitleMismatch {
+                prefix: self.title_prefix.clone(),
+            });
+        }
+        if let Some(section) = self
+            .required_sections
+            .iter()
+            .find(|section| !description.contains(section.as_str()))
+        {
+            return Err(ContractError::TemplateSectionMissing(section.clone()));
+        }
+        if !self.allowed_kinds.is_empty() {
+            let kind = proposal_kind(proposal)?;
+            if !self.allowed_kinds.contains(&kind) {
+                return Err(ContractError::TemplateKindNotAllowed(kind));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Top level variant name of the proposal payload. Externally tagged enums are serialized either
+/// as `"variant"` or as `{"variant": ...}`.
+fn proposal_kind<P: Serialize>(proposal: &P) -> StdResult<String> {
+    let raw = to_vec(proposal)?;
+    if let Ok(kind) = from_slice::<String>(&raw) {
+        return Ok(kind);
+    }
+    let payload: BTreeMap<String, IgnoredAny> = from_slice(&raw)?;
+    payload
+        .into_keys()
+        .next()
+        .ok_or_else(|| StdError::generic_err("Proposal payload has no kind"))
+}
+
 pub struct RulesBuilder {
     voting_period: u32,
     quorum: Decimal,
     threshold: Decimal,
     allow_end_early: bool,
+    abstain_non_voters: bool,
+    proposer_policy: Option<ProposerPolicy>,
+    tie_break: Option<TieBreak>,
 }
 
 impl RulesBuilder {
@@ -176,6 +802,9 @@ impl RulesBuilder {
             quorum: Decimal::percent(20),
             threshold: Decimal::percent(50),
             allow_end_early: true,
+            abstain_non_voters: false,
+            proposer_policy: None,
+            tie_break: None,
         }
     }
 
@@ -194,12 +823,33 @@ impl RulesBuilder {
         self
     }
 
+    pub fn with_abstain_non_voters(mut self, abstain_non_voters: bool) -> Self {
+        self.abstain_non_voters = abstain_non_voters;
+        self
+    }
+
+    pub fn with_proposer_policy(
+        mut self,
+        proposer_policy: impl Into<Option<ProposerPolicy>>,
+    ) -> Self {
+        self.proposer_policy = proposer_policy.into();
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: impl Into<Option<TieBreak>>) -> Self {
+        self.tie_break = tie_break.into();
+        self
+    }
+
     pub fn build(&self) -> VotingRules {
         VotingRules {
             voting_period: self.voting_period,
             quorum: self.quorum,
             threshold: self.threshold,
             allow_end_early: self.allow_end_early,
+            abstain_non_voters: self.abstain_non_voters,
+            proposer_policy: self.proposer_policy.clone(),
+            tie_break: self.tie_break,
         }
     }
 }
@@ -217,6 +867,10 @@ pub struct Votes {
     pub no: u64,
     pub abstain: u64,
     pub veto: u64,
+    /// Points of members who didn't vote, counted as abstaining for quorum. Only recorded when
+    /// the proposal is finalized after expiration with the `abstain_non_voters` rule.
+    #[serde(default)]
+    pub absent: u64,
 }
 
 impl Votes {
@@ -232,6 +886,7 @@ impl Votes {
             no: 0,
             abstain: 0,
             veto: 0,
+            absent: 0,
         }
     }
 
@@ -269,6 +924,148 @@ pub struct ProposalInfo {
 
 pub const TEXT_PROPOSALS: Map<u64, ProposalInfo> = Map::new("text_proposals");
 
+/// Bonus points of voters, keyed by `(proposal id, voter)`. Bonuses are counted in the
+/// proposal's `total_points` as well.
+pub const VOTE_BONUSES: Map<(u64, &Addr), u64> = Map::new("vote_bonuses");
+
+/// Boosts returned by the power provider, keyed by `(proposal id, voter)`. Recorded with the
+/// voter's ballot. Boosts are counted in the proposal's `total_points` as they are recorded.
+pub const POWER_BOOSTS: Map<(u64, &Addr), u64> = Map::new("power_boosts");
+
+/// Index of proposals by their tags, keyed by `(tag, id)`
+pub const PROPOSALS_BY_TAG: Map<(&str, u64), Empty> = Map::new("proposals_by_tag");
+
+/// Proposals created with an idempotency key, keyed by `(proposer, key)`, so retried proposing
+/// returns the proposal created before instead of a duplicate
+pub const PROPOSALS_BY_IDEMPOTENCY_KEY: Map<(&Addr, &str), u64> =
+    Map::new("proposals_by_idempotency_key");
+
+/// Proposal templates registered by the contract, keyed by name
+pub const PROPOSAL_TEMPLATES: Map<&str, ProposalTemplate> = Map::new("proposal_templates");
+
+/// Migrations of the voting contract with the steps applied during them
+pub const MIGRATIONS: MigrationHistory = MigrationHistory::new("migrations");
+
+/// Proposals not finalized yet, keyed by `(expiration, id)`, so expired ones can be closed
+pub const PROPOSALS_BY_EXPIRATION: Map<(u64, u64), Empty> = Map::new("proposals_by_expiration");
+
+/// Number of proposals counted as open for voting
+pub const OPEN_PROPOSALS: Item<u32> = Item::new("open_proposals");
+
+/// Number of proposals counted as open for voting, by proposer
+pub const OPEN_PROPOSALS_BY_PROPOSER: Map<&Addr, u32> = Map::new("open_proposals_by_proposer");
+
+/// Proposals counted in `OPEN_PROPOSALS`, keyed by `(expiration, id)` with their proposer, so
+/// they stop being counted once they expire
+pub const COUNTED_PROPOSALS: Map<(u64, u64), Addr> = Map::new("counted_proposals");
+
+/// Counts the proposal as open for voting, unless it is counted already
+pub fn count_open_proposal(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    expires: &Expiration,
+    proposer: &Addr,
+) -> StdResult<()> {
+    let key = (expires.as_key(), proposal_id);
+    if COUNTED_PROPOSALS.has(storage, key) {
+        return Ok(());
+    }
+    COUNTED_PROPOSALS.save(storage, key, proposer)?;
+    let open = OPEN_PROPOSALS.may_load(storage)?.unwrap_or_default();
+    OPEN_PROPOSALS.save(storage, &(open + 1))?;
+    OPEN_PROPOSALS_BY_PROPOSER.update(storage, proposer, |open| -> StdResult<_> {
+        Ok(open.unwrap_or_default() + 1)
+    })?;
+    Ok(())
+}
+
+/// Stops counting the proposal as open for voting, if it is counted
+pub fn uncount_open_proposal(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    expires: &Expiration,
+) -> StdResult<()> {
+    uncount(storage, (expires.as_key(), proposal_id))
+}
+
+fn uncount(storage: &mut dyn Storage, key: (u64, u64)) -> StdResult<()> {
+    let proposer = match COUNTED_PROPOSALS.may_load(storage, key)? {
+        Some(proposer) => proposer,
+        None => return Ok(()),
+    };
+    COUNTED_PROPOSALS.remove(storage, key);
+    let open = OPEN_PROPOSALS.load(storage)?;
+    OPEN_PROPOSALS.save(storage, &open.saturating_sub(1))?;
+    match OPEN_PROPOSALS_BY_PROPOSER.load(storage, &proposer)? {
+        1 => OPEN_PROPOSALS_BY_PROPOSER.remove(storage, &proposer),
+        open => OPEN_PROPOSALS_BY_PROPOSER.save(storage, &proposer, &(open - 1))?,
+    }
+    Ok(())
+}
+
+/// Counted proposals expired at `block`, proposals expiring exactly now are expired already
+fn expired_counted_proposals(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+) -> StdResult<Vec<((u64, u64), Addr)>> {
+    COUNTED_PROPOSALS
+        .prefix_range(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(block.time.nanos())),
+            Order::Ascending,
+        )
+        .collect()
+}
+
+/// Stops counting proposals expired at `block`. Every proposal is visited once, so the cost is
+/// spread over the calls.
+pub fn uncount_expired_proposals(storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+    for (key, _) in expired_counted_proposals(storage, block)? {
+        uncount(storage, key)?;
+    }
+    Ok(())
+}
+
+/// Numbers of proposals open for voting at `block`, in total and created by `proposer`.
+/// Proposals expired since they were last uncounted are left out.
+pub fn open_proposals(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    proposer: Option<&Addr>,
+) -> StdResult<(u32, u32)> {
+    let mut open = OPEN_PROPOSALS.may_load(storage)?.unwrap_or_default();
+    let mut open_by_proposer = match proposer {
+        Some(proposer) => OPEN_PROPOSALS_BY_PROPOSER
+            .may_load(storage, proposer)?
+            .unwrap_or_default(),
+        None => 0,
+    };
+    for (_, expired_proposer) in expired_counted_proposals(storage, block)? {
+        open = open.saturating_sub(1);
+        if proposer == Some(&expired_proposer) {
+            open_by_proposer = open_by_proposer.saturating_sub(1);
+        }
+    }
+    Ok((open, open_by_proposer))
+}
+
+/// Finalized proposals which ballots are still to be pruned, keyed by `(finalized_height, id)`
+pub const FINALIZED_PROPOSALS: Map<(u64, u64), Empty> = Map::new("finalized_proposals");
+
+/// Outcome of a single message dispatched by an executed proposal
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MessageExecutionResult {
+    pub success: bool,
+    /// Error returned by the failing message
+    pub error: Option<String>,
+}
+
+/// Outcomes of messages of proposals executed with `execute_with_results`, in dispatch order
+pub const EXECUTION_RESULTS: Map<u64, Vec<MessageExecutionResult>> = Map::new("execution_results");
+/// Proposal which messages are being dispatched, so replies know where to record the outcome
+pub const EXECUTING_PROPOSAL: Item<u64> = Item::new("executing_proposal");
+
 pub fn next_id(store: &mut dyn Storage) -> StdResult<u64> {
     let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
     PROPOSAL_COUNT.save(store, &id)?;